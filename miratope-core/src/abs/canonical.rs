@@ -0,0 +1,232 @@
+//! Puts abstract polytopes in a form that doesn't depend on the order of their
+//! elements, so that they can be compared, sorted and hashed up to
+//! isomorphism.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use super::{flag::Flag, Abstract, Ranked};
+use crate::Polytope;
+
+use vec_like::VecLike;
+
+/// The sorted subelements of every element of every rank of a polytope, from
+/// the vertices up, under some labeling of its elements.
+type Labeling = Vec<Vec<Vec<usize>>>;
+
+/// The canonical form of an abstract polytope, made out of its element counts
+/// and the least of the labelings of its elements given by a traversal of its
+/// flags. The labelings are compared lexicographically, rank by rank.
+///
+/// Two polytopes whose flags are all connected to each other are isomorphic
+/// if and only if their canonical forms are equal. Other polytopes, like
+/// compounds, are only described by their element counts, so their canonical
+/// forms are [incomplete](Self::is_complete).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalAbstract {
+    /// The number of elements of each rank.
+    el_counts: Vec<usize>,
+
+    /// The sorted subelements of every element under the least labeling, or
+    /// `None` if some element isn't reached by a traversal of the flags.
+    labeling: Option<Labeling>,
+}
+
+/// A union-find structure over the flags of a polytope, where each class is
+/// contained in an orbit of its automorphism group.
+struct FlagOrbits {
+    /// The parent of every flag, or itself for the representative of a class.
+    parents: Vec<usize>,
+
+    /// Whether the labeling of some flag in the class of every representative
+    /// has been computed.
+    done: Vec<bool>,
+}
+
+impl FlagOrbits {
+    /// Puts every flag in its own class.
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            done: vec![false; len],
+        }
+    }
+
+    /// Returns the representative of the class of a flag.
+    fn find(&mut self, mut idx: usize) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+
+        idx
+    }
+
+    /// Merges the classes of two flags.
+    fn union(&mut self, i: usize, j: usize) {
+        let (i, j) = (self.find(i), self.find(j));
+        if i != j {
+            self.parents[j] = i;
+            self.done[i] |= self.done[j];
+        }
+    }
+}
+
+/// Returns the least of the labelings of a polytope given by a traversal of
+/// its flags, or `None` if some element isn't reached by them.
+///
+/// Flags in the same orbit under the automorphism group give the same
+/// labeling, so only one of them is labeled. Whenever two flags give the least
+/// labeling found so far, the automorphism between them is read off from their
+/// labels, and the orbits of every flag under it are merged. For flag-transitive
+/// polytopes, this means that only a few labelings are computed.
+///
+/// # Panics
+/// You must call [`Polytope::element_sort`] before calling this function.
+fn least_labeling(poly: &Abstract) -> Option<Labeling> {
+    let flags: Vec<Flag> = poly.flags().collect();
+    let indices: HashMap<_, _> = flags.iter().enumerate().map(|(idx, f)| (f, idx)).collect();
+    let mut orbits = FlagOrbits::new(flags.len());
+
+    // The least labeling, and the labels of the flag that gave it.
+    let mut least: Option<(Labeling, Vec<Vec<usize>>)> = None;
+
+    for (idx, flag) in flags.iter().enumerate() {
+        let root = orbits.find(idx);
+        if orbits.done[root] {
+            continue;
+        }
+        orbits.done[root] = true;
+
+        // Either every flag reaches every element, or none does.
+        let labels = poly.flag_labels(flag.clone())?;
+        let labeling = poly.labeled_subs(&labels);
+
+        match &least {
+            Some((least_labeling, least_labels)) if &labeling == least_labeling => {
+                // The automorphism maps each element to the one with the same
+                // label from the other flag.
+                let automorphism: Vec<Vec<usize>> = least_labels
+                    .iter()
+                    .zip(&labels)
+                    .map(|(from, to)| {
+                        let mut unlabel = vec![0; to.len()];
+                        for (el, &label) in to.iter().enumerate() {
+                            unlabel[label] = el;
+                        }
+                        from.iter().map(|&label| unlabel[label]).collect()
+                    })
+                    .collect();
+
+                for (i, flag) in flags.iter().enumerate() {
+                    let image: Flag = flag
+                        .iter()
+                        .enumerate()
+                        .map(|(r, &el)| automorphism[r][el])
+                        .collect();
+                    orbits.union(i, indices[&image]);
+                }
+            }
+            Some((least_labeling, _)) if &labeling > least_labeling => {}
+            _ => least = Some((labeling, labels)),
+        }
+    }
+
+    least.map(|(labeling, _)| labeling)
+}
+
+impl CanonicalAbstract {
+    /// Puts a polytope in canonical form. This takes time proportional to the
+    /// square of its flag count.
+    pub fn new(poly: &Abstract) -> Self {
+        let el_counts = poly.el_count_iter().collect();
+
+        // The nullitope has no flags to traverse, but it's determined by its
+        // element counts.
+        if poly.rank() == 0 {
+            return Self {
+                el_counts,
+                labeling: Some(Vec::new()),
+            };
+        }
+
+        let mut poly = poly.clone();
+        if !poly.sorted() {
+            poly.element_sort();
+        }
+
+        Self {
+            el_counts,
+            labeling: least_labeling(&poly),
+        }
+    }
+
+    /// Returns whether the canonical form determines the polytope up to
+    /// isomorphism, which is the case whenever its flags are all connected to
+    /// each other.
+    pub fn is_complete(&self) -> bool {
+        self.labeling.is_some()
+    }
+
+    /// Returns a hash of the canonical form, which is stable across runs.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Abstract {
+    /// Puts the polytope in canonical form. See [`CanonicalAbstract`].
+    pub fn canonical_form(&self) -> CanonicalAbstract {
+        CanonicalAbstract::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reordering the elements doesn't change the canonical form.
+    #[test]
+    fn reorder() {
+        let cube = Abstract::cube();
+        let form = cube.canonical_form();
+
+        assert!(form.is_complete());
+        assert_eq!(cube.dual().dual().canonical_form(), form);
+        assert_eq!(Abstract::dyad().prism().prism().canonical_form(), form);
+        assert_ne!(Abstract::octahedron().canonical_form(), form);
+        assert_eq!(
+            Abstract::octahedron().canonical_form(),
+            cube.dual().canonical_form()
+        );
+    }
+
+    /// Canonical forms are sorted first by element counts.
+    #[test]
+    fn order() {
+        let mut forms = [
+            Abstract::cube().canonical_form(),
+            Abstract::tetrahedron().canonical_form(),
+            Abstract::octahedron().canonical_form(),
+        ];
+        forms.sort();
+
+        assert_eq!(forms[0], Abstract::tetrahedron().canonical_form());
+        assert_eq!(forms[1], Abstract::octahedron().canonical_form());
+        assert_eq!(forms[2], Abstract::cube().canonical_form());
+    }
+
+    /// Compounds only get incomplete canonical forms.
+    #[test]
+    fn compound() {
+        let mut compound = Abstract::polygon(3);
+        compound.comp_append(Abstract::polygon(3));
+
+        assert!(!compound.canonical_form().is_complete());
+        assert!(Abstract::nullitope().canonical_form().is_complete());
+    }
+}
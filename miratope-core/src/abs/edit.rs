@@ -0,0 +1,273 @@
+//! Deletes a vertex or a facet of a polytope, and closes up the hole that's
+//! left behind.
+
+use std::collections::BTreeSet;
+
+use super::{Abstract, AbstractBuilder, AbstractResult, Ranked, SubelementList, Subelements};
+
+use vec_like::*;
+
+impl Abstract {
+    /// Deletes a vertex of the polytope together with the edges through it,
+    /// and closes up the hole with a new facet spanned by its neighbors.
+    ///
+    /// Every other element through the vertex loses it, and gains a new
+    /// subelement spanned by the neighbors of the vertex within it. For
+    /// instance, a face through the vertex gets cut along a new edge, and the
+    /// new facet is shaped like the vertex figure. Every rank of the result
+    /// lists the remaining elements in their original order, followed by the
+    /// new ones, so the new facet is the last one.
+    ///
+    /// New elements are added even if some element already has the same
+    /// subelements. Deleting a vertex of a tetrahedron thus turns the three
+    /// triangles through it into digons.
+    ///
+    /// Returns an error if the result isn't a valid polytope.
+    ///
+    /// # Panics
+    /// Panics if the polytope has rank less than 3, or if the index is out of
+    /// bounds.
+    pub fn delete_vertex(&self, idx: usize) -> AbstractResult<Self> {
+        let rank = self.rank();
+        assert!(
+            rank >= 3,
+            "only polygons and higher have vertices to delete"
+        );
+        assert!(idx < self.vertex_count(), "vertex index out of bounds");
+
+        // Whether each element contains the deleted vertex.
+        let mut contains = vec![
+            vec![false],
+            (0..self.vertex_count()).map(|v| v == idx).collect(),
+        ];
+        for r in 2..=rank {
+            let prev = &contains[r - 1];
+            let cur = self[r]
+                .iter()
+                .map(|el| el.subs.iter().any(|&s| prev[s]))
+                .collect();
+            contains.push(cur);
+        }
+
+        // The new index of every element that's kept. The vertex and the edges
+        // through it are removed, every other element is kept.
+        let mut new_idx: Vec<Vec<Option<usize>>> = Vec::with_capacity(rank + 1);
+        for (r, contains) in contains.iter().enumerate() {
+            let mut count = 0;
+            new_idx.push(
+                contains
+                    .iter()
+                    .map(|&c| {
+                        (r >= 3 || !c).then(|| {
+                            count += 1;
+                            count - 1
+                        })
+                    })
+                    .collect(),
+            );
+        }
+
+        // The index of the new element that closes up each element through
+        // the vertex, within the rank below it. For an edge, this is its other
+        // endpoint.
+        let mut caps: Vec<Vec<usize>> = vec![Vec::new(); rank + 1];
+        for r in 2..=rank {
+            let mut count = new_idx[r - 1].iter().flatten().count();
+            caps[r] = self[r]
+                .iter()
+                .zip(&contains[r])
+                .map(|(el, &c)| {
+                    if !c {
+                        0
+                    } else if r == 2 {
+                        let other = el.subs.iter().find(|&&s| s != idx).unwrap();
+                        new_idx[1][*other].unwrap()
+                    } else {
+                        count += 1;
+                        count - 1
+                    }
+                })
+                .collect();
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(self.vertex_count() - 1);
+
+        for r in 2..rank {
+            let mut subelements = SubelementList::new();
+
+            // The remaining elements.
+            for (i, el) in self[r].iter().enumerate() {
+                if new_idx[r][i].is_some() {
+                    let mut subs: Subelements =
+                        el.subs.iter().filter_map(|&s| new_idx[r - 1][s]).collect();
+                    if contains[r][i] {
+                        subs.push(caps[r][i]);
+                    }
+
+                    subelements.push(subs);
+                }
+            }
+
+            // The new elements, closing up the elements of the next rank.
+            for (el, &c) in self[r + 1].iter().zip(&contains[r + 1]) {
+                if c {
+                    subelements.push(
+                        el.subs
+                            .iter()
+                            .filter(|&&s| contains[r][s])
+                            .map(|&s| caps[r][s])
+                            .collect(),
+                    );
+                }
+            }
+
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+        builder.ranks().is_valid()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Ok(unsafe { builder.build() })
+    }
+
+    /// Deletes a facet of the polytope, and closes up the hole by extending
+    /// the facets next to it until they meet at a new vertex. This is the dual
+    /// operation to [`Self::delete_vertex`].
+    ///
+    /// The ridges of the facet are removed, and every element of the facet
+    /// other than these gains a new superelement joining it to the new vertex.
+    /// The remaining vertices keep their order, and the new vertex is the last
+    /// one.
+    ///
+    /// Returns an error if the result isn't a valid polytope.
+    ///
+    /// # Panics
+    /// Panics if the polytope has rank less than 3, or if the index is out of
+    /// bounds.
+    pub fn delete_facet(&self, idx: usize) -> AbstractResult<Self> {
+        self.dual().delete_vertex(idx).map(Self::into_dual)
+    }
+
+    /// Removes an element that lies in exactly two elements of the next rank,
+    /// and merges these two into one. This undoes the subdivision of an
+    /// element, like a vertex in the middle of an edge.
+    ///
+    /// The merged element has the subelements of both, other than the removed
+    /// one, and takes the place of the first of the two. Every other element
+    /// keeps its order.
+    ///
+    /// Returns an error if the result isn't a valid polytope.
+    ///
+    /// # Panics
+    /// Panics if the element is the minimal element, a facet, or the maximal
+    /// element, or if it doesn't lie in exactly two elements of the next rank.
+    pub fn dissolve(&self, rank: usize, idx: usize) -> AbstractResult<Self> {
+        assert!(
+            rank >= 1 && rank + 1 < self.rank(),
+            "only elements below the facets can be dissolved"
+        );
+        let sups = &self[(rank, idx)].sups;
+        assert_eq!(sups.len(), 2, "the element must lie in two elements");
+        let (h1, h2) = (sups[0].min(sups[1]), sups[0].max(sups[1]));
+
+        // The new index of every element. The second merged element is sent
+        // to the first one.
+        let mut new_idx: Vec<Vec<Option<usize>>> = Vec::with_capacity(self.rank() + 1);
+        for r in 0..=self.rank() {
+            let mut count = 0;
+            new_idx.push(
+                (0..self.el_count(r))
+                    .map(|i| {
+                        if r == rank && i == idx {
+                            None
+                        } else if r == rank + 1 && i == h2 {
+                            Some(h1)
+                        } else {
+                            count += 1;
+                            Some(count - 1)
+                        }
+                    })
+                    .collect(),
+            );
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(self.rank());
+        builder.push_min();
+        builder.push_vertices(self.vertex_count() - (rank == 1) as usize);
+
+        for r in 2..self.rank() {
+            let mut subelements = SubelementList::new();
+
+            for (i, el) in self[r].iter().enumerate() {
+                if (r == rank && i == idx) || (r == rank + 1 && i == h2) {
+                    continue;
+                }
+
+                let mut subs: BTreeSet<_> =
+                    el.subs.iter().filter_map(|&s| new_idx[r - 1][s]).collect();
+                if r == rank + 1 && i == h1 {
+                    subs.extend(self[(r, h2)].subs.iter().filter_map(|&s| new_idx[r - 1][s]));
+                }
+
+                subelements.push(subs.into_iter().collect());
+            }
+
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+        builder.ranks().is_valid()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Ok(unsafe { builder.build() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Deleting a vertex of a polygon leaves a polygon with one less side.
+    #[test]
+    fn polygon() {
+        test(
+            &Abstract::polygon(5).delete_vertex(2).unwrap(),
+            [1, 4, 4, 1],
+        );
+        test(&Abstract::polygon(5).delete_facet(2).unwrap(), [1, 4, 4, 1]);
+    }
+
+    /// Deleting a vertex of a cube cuts off a corner.
+    #[test]
+    fn cube() {
+        test(
+            &Abstract::cube().delete_vertex(0).unwrap(),
+            [1, 7, 12, 7, 1],
+        );
+        test(
+            &Abstract::octahedron().delete_facet(0).unwrap(),
+            [1, 7, 12, 7, 1],
+        );
+    }
+
+    /// Dissolving a vertex of a polygon merges its two edges, and dissolving
+    /// an edge of a cube merges its two faces.
+    #[test]
+    fn dissolve() {
+        test(&Abstract::polygon(5).dissolve(1, 2).unwrap(), [1, 4, 4, 1]);
+        test(&Abstract::cube().dissolve(2, 0).unwrap(), [1, 8, 11, 5, 1]);
+    }
+
+    /// Deleting a vertex of a tetrahedron leaves three digons.
+    #[test]
+    fn tetrahedron() {
+        test(
+            &Abstract::simplex(4).delete_vertex(0).unwrap(),
+            [1, 3, 6, 5, 1],
+        );
+    }
+}
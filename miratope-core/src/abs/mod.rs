@@ -1,20 +1,28 @@
 //! Declares the [`Abstract`] polytope type and all associated data structures.
 
 pub mod antiprism;
+pub mod canonical;
+pub mod edit;
 pub mod flag;
+pub mod petrie;
+pub mod pretty;
 pub mod product;
+pub mod provenance;
+pub mod quotient;
 pub mod ranked;
+pub mod sggi;
+pub mod tower;
 pub mod valid;
 
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     convert::Infallible,
     ops::{Index, IndexMut},
     slice, vec, iter,
 };
 
 use self::flag::{Flag, FlagSet};
-use super::Polytope;
+use super::{AntiprismError, Polytope};
 
 use itertools::Itertools;
 use vec_like::VecLike;
@@ -253,12 +261,79 @@ impl Abstract {
         antiprism::antiprism(self)
     }
 
+    /// Checks that the abstract antiprism of a polytope exists, which is the
+    /// case whenever the polytope is dyadic. Otherwise, returns the section at
+    /// fault.
+    pub fn check_antiprism(&self) -> Result<(), AntiprismError> {
+        self.ranks.is_dyadic().map_err(|err| match err {
+            AbstractError::Dyadic { section, more } => AntiprismError::Dyadic { section, more },
+            _ => unreachable!("only the diamond property is checked"),
+        })
+    }
+
     /// Gets the indices of the vertices of an element in the polytope, if it
     /// exists.
     pub fn element_vertices(&self, rank: usize, idx: usize) -> Option<Vec<usize>> {
         Some(ElementHash::new(self, rank, idx)?.to_vertices())
     }
 
+    /// Gets the indices of the subelements of a given rank of an element in
+    /// the polytope, if it exists. These are listed in the order they're found
+    /// by walking down the subelements.
+    pub fn element_subelements(
+        &self,
+        rank: usize,
+        idx: usize,
+        sub_rank: usize,
+    ) -> Option<Vec<usize>> {
+        Some(ElementHash::new(self, rank, idx)?.to_elements(sub_rank))
+    }
+
+    /// Returns the indices of the facets that contain each face, in
+    /// increasing order. Every face of a polyhedron is its own only facet. A
+    /// polytope of lower rank has no faces under its facets, so the list is
+    /// empty.
+    pub fn face_facets(&self) -> Vec<Vec<usize>> {
+        let rank = self.rank();
+        if rank < 4 {
+            return Vec::new();
+        }
+
+        // The facets of every element of the current rank, from the facets
+        // down to the faces.
+        let mut facets: Vec<Vec<usize>> = (0..self.facet_count()).map(|idx| vec![idx]).collect();
+        for r in (3..rank - 1).rev() {
+            facets = self
+                .rank_iter(r)
+                .map(|(_, el)| {
+                    let mut el_facets: Vec<usize> =
+                        el.sups.iter().flat_map(|&sup| facets[sup].iter().copied()).collect();
+                    el_facets.sort_unstable();
+                    el_facets.dedup();
+                    el_facets
+                })
+                .collect();
+        }
+
+        facets
+    }
+
+    /// Returns up to `count` elements of a given rank, starting from the
+    /// element with index `start`. The range is clamped to the elements that
+    /// exist, so that an empty slice is returned past the end of the list or
+    /// for a rank out of bounds.
+    pub fn elements_page(&self, rank: usize, start: usize, count: usize) -> &[Element] {
+        match self.ranks.get(rank) {
+            Some(elements) => {
+                let elements = elements.as_slice();
+                let start = start.min(elements.len());
+                let end = start.saturating_add(count).min(elements.len());
+                &elements[start..end]
+            }
+            None => &[],
+        }
+    }
+
     /// Gets both elements with a given rank and index as a polytope and the
     /// indices of its vertices on the original polytope, if it exists.
     pub fn element_and_vertices(&self, rank: usize, idx: usize) -> Option<(Vec<usize>, Self)> {
@@ -266,6 +341,135 @@ impl Abstract {
         Some((element_hash.to_vertices(), element_hash.to_polytope(self)))
     }
 
+    /// Returns the indices of the elements of a given rank that share a
+    /// subelement with a given element, sorted and without repetitions.
+    ///
+    /// # Panics
+    /// Panics if the element doesn't exist.
+    pub fn adjacent(&self, rank: usize, idx: usize) -> Vec<usize> {
+        let mut adjacent = Vec::new();
+
+        if rank != 0 {
+            for &sub in &self[(rank, idx)].subs {
+                adjacent.extend(
+                    self[(rank - 1, sub)]
+                        .sups
+                        .iter()
+                        .copied()
+                        .filter(|&sup| sup != idx),
+                );
+            }
+        }
+
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
+    }
+
+    /// Returns the adjacency lists of all elements of a given rank, where two
+    /// elements are adjacent whenever they share a subelement. This is computed
+    /// in a single pass through the superelements of the rank below.
+    pub fn adjacency(&self, rank: usize) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.el_count(rank)];
+
+        if rank != 0 {
+            for el in self[rank - 1].iter() {
+                for (i, &sup0) in el.sups.iter().enumerate() {
+                    for &sup1 in el.sups.iter().skip(i + 1) {
+                        adjacency[sup0].push(sup1);
+                        adjacency[sup1].push(sup0);
+                    }
+                }
+            }
+        }
+
+        for list in &mut adjacency {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        adjacency
+    }
+
+    /// Returns the adjacency lists of the facets of a polytope, where two
+    /// facets are adjacent whenever they share a ridge. This is the dual graph
+    /// of the polytope.
+    pub fn facet_adjacency(&self) -> Vec<Vec<usize>> {
+        match self.rank() {
+            0 => Vec::new(),
+            rank => self.adjacency(rank - 1),
+        }
+    }
+
+    /// Returns the adjacency lists of the vertices of a polytope, where two
+    /// vertices are adjacent whenever they share an edge. This is the
+    /// 1-skeleton of the polytope.
+    pub fn vertex_adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.vertex_count()];
+
+        if self.rank() >= 2 {
+            for edge in self[2].iter() {
+                for (i, &v0) in edge.subs.iter().enumerate() {
+                    for &v1 in edge.subs.iter().skip(i + 1) {
+                        adjacency[v0].push(v1);
+                        adjacency[v1].push(v0);
+                    }
+                }
+            }
+        }
+
+        for list in &mut adjacency {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        adjacency
+    }
+
+    /// Colors the 2-faces of a polytope so that no two faces sharing an edge
+    /// have the same color, using the DSatur heuristic. Returns the color index
+    /// of each face.
+    pub fn greedy_face_coloring(&self) -> Vec<usize> {
+        if self.rank() < 3 {
+            return Vec::new();
+        }
+
+        let adjacency = self.adjacency(3);
+        let mut colors: Vec<Option<usize>> = vec![None; adjacency.len()];
+
+        for _ in 0..adjacency.len() {
+            // Picks the uncolored face with the most distinctly colored
+            // neighbors, breaking ties by degree and then by index.
+            let mut best = None;
+            let mut best_key = (0, 0);
+
+            for (idx, neighbors) in adjacency.iter().enumerate() {
+                if colors[idx].is_some() {
+                    continue;
+                }
+
+                let saturation = neighbors
+                    .iter()
+                    .filter_map(|&n| colors[n])
+                    .collect::<HashSet<_>>()
+                    .len();
+                let key = (saturation, neighbors.len());
+
+                if best.is_none() || key > best_key {
+                    best = Some(idx);
+                    best_key = key;
+                }
+            }
+
+            // Assigns the least color not used by any neighbor.
+            let idx = best.unwrap();
+            let used: HashSet<_> = adjacency[idx].iter().filter_map(|&n| colors[n]).collect();
+            colors[idx] = (0..).find(|c| !used.contains(c));
+        }
+
+        colors.into_iter().map(Option::unwrap).collect()
+    }
+
     /// Returns the omnitruncate of a polytope, along with the flags that make
     /// up its respective vertices.
     ///
@@ -493,6 +697,176 @@ impl Abstract {
         }
         partition.amount_of_sets() > 1
     }
+
+    /// Labels the elements of a polytope in the order in which they're reached
+    /// by a breadth-first traversal of its flags, starting from a given flag
+    /// and applying the flag changes in increasing order. Returns the label of
+    /// every element of every rank, or `None` if some element isn't reached.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn flag_labels(&self, flag: Flag) -> Option<Vec<Vec<usize>>> {
+        let rank = self.rank();
+        let mut labels = vec![HashMap::new(); rank + 1];
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        // Once every element is labeled, the rest of the traversal can't
+        // change anything.
+        let mut unlabeled: usize = self.el_count_iter().sum();
+
+        visited.insert(flag.clone());
+        queue.push_back(flag);
+
+        while let Some(flag) = queue.pop_front() {
+            for (r, labels) in labels.iter_mut().enumerate() {
+                let len = labels.len();
+                labels.entry(flag[r]).or_insert_with(|| {
+                    unlabeled -= 1;
+                    len
+                });
+            }
+
+            if unlabeled == 0 {
+                break;
+            }
+
+            for r in 1..rank {
+                let new_flag = flag.change(self, r);
+                if visited.insert(new_flag.clone()) {
+                    queue.push_back(new_flag);
+                }
+            }
+        }
+
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(r, labels)| {
+                (labels.len() == self.el_count(r))
+                    .then(|| (0..labels.len()).map(|idx| labels[&idx]).collect())
+            })
+            .collect()
+    }
+
+    /// Returns the sorted subelements of every element under a labeling given
+    /// by [`Self::flag_labels`].
+    fn labeled_subs(&self, labels: &[Vec<usize>]) -> Vec<Vec<Vec<usize>>> {
+        (1..=self.rank())
+            .map(|r| {
+                let mut els = vec![Vec::new(); self.el_count(r)];
+
+                for (idx, &label) in labels[r].iter().enumerate() {
+                    let mut subs: Vec<_> = self[(r, idx)].subs.iter().map(|&s| labels[r - 1][s]).collect();
+                    subs.sort_unstable();
+                    els[label] = subs;
+                }
+
+                els
+            })
+            .collect()
+    }
+
+    /// Labels the elements of a polytope as in [`Self::flag_labels`], and
+    /// returns the sorted subelements of every element under this labeling, or
+    /// `None` if some element isn't reached.
+    ///
+    /// Since isomorphisms map flags to flags and commute with flag changes,
+    /// two polytopes are isomorphic if and only if they give the same labeling
+    /// for some pair of flags.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn flag_labeling(&self, flag: Flag) -> Option<Vec<Vec<Vec<usize>>>> {
+        self.flag_labels(flag).map(|labels| self.labeled_subs(&labels))
+    }
+
+    /// Returns whether two polytopes are combinatorially equal, that is,
+    /// whether there's a bijection between their elements that preserves
+    /// incidences. Polytopes whose flags aren't all connected to each other,
+    /// like compounds, are only compared through their element counts.
+    ///
+    /// This takes time proportional to the product of the flag counts.
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        if self.rank() != other.rank() || self.el_count_iter().ne(other.el_count_iter()) {
+            return false;
+        }
+
+        if self.rank() == 0 {
+            return true;
+        }
+
+        let mut poly = self.clone();
+        poly.element_sort();
+        let mut other = other.clone();
+        other.element_sort();
+
+        // Compares a fixed labeling of one polytope with every labeling of the
+        // other.
+        match other.flag_labeling(other.first_flag()) {
+            Some(labeling) => poly
+                .flags()
+                .any(|flag| poly.flag_labeling(flag).as_ref() == Some(&labeling)),
+            None => poly.flag_labeling(poly.first_flag()).is_none(),
+        }
+    }
+
+    /// Returns an isomorphism from the polytope into another, as a list with
+    /// the index of the image of every element of every rank. Returns `None`
+    /// if the polytopes aren't isomorphic, or if the flags of either of them
+    /// aren't all connected to each other.
+    ///
+    /// This takes time proportional to the product of the flag counts.
+    pub fn isomorphism(&self, other: &Self) -> Option<Vec<Vec<usize>>> {
+        if self.rank() != other.rank() || self.el_count_iter().ne(other.el_count_iter()) {
+            return None;
+        }
+
+        if self.rank() == 0 {
+            return Some(vec![vec![0]]);
+        }
+
+        let mut poly = self.clone();
+        poly.element_sort();
+        let mut other = other.clone();
+        other.element_sort();
+
+        // The element of the other polytope with each label.
+        let other_labels = other.flag_labels(other.first_flag())?;
+        let labeling = other.labeled_subs(&other_labels);
+        let mut unlabel: Vec<_> = other_labels.iter().map(|labels| vec![0; labels.len()]).collect();
+        for (r, labels) in other_labels.iter().enumerate() {
+            for (idx, &label) in labels.iter().enumerate() {
+                unlabel[r][label] = idx;
+            }
+        }
+
+        // Sends every element to the element of the other polytope with the
+        // same label, for the first labeling that matches.
+        poly.flags().find_map(|flag| {
+            let labels = poly.flag_labels(flag)?;
+            (poly.labeled_subs(&labels) == labeling).then(|| {
+                labels
+                    .iter()
+                    .enumerate()
+                    .map(|(r, labels)| labels.iter().map(|&label| unlabel[r][label]).collect())
+                    .collect()
+            })
+        })
+    }
+
+    /// Returns an isomorphism from the polytope into its dual, if it's
+    /// self-dual. The element of rank `r` and index `i` is sent to the element
+    /// of rank `self.rank() - r` and index `map[r][i]`, so that subelements
+    /// are sent to superelements and vice versa. See [`Self::isomorphism`].
+    pub fn dual_isomorphism(&self) -> Option<Vec<Vec<usize>>> {
+        self.isomorphism(&self.dual())
+    }
+
+    /// Returns whether the polytope is combinatorially equal to its dual.
+    pub fn is_self_dual(&self) -> bool {
+        self.dual_isomorphism().is_some()
+    }
 }
 
 impl Polytope for Abstract {
@@ -623,93 +997,20 @@ impl Polytope for Abstract {
     }
 
     /// Builds the [Petrial](https://polytope.miraheze.org/wiki/Petrial) of a
-    /// polyhedron in place.
+    /// polyhedron in place. See [`Self::petrie_dual`] for the details.
     fn petrial_mut(&mut self) -> bool {
         // Petrials only really make sense for polyhedra.
         if self.rank() != 4 {
             return false;
         }
 
-        // Consider a flag in a polytope. It has an associated edge. It turns
-        // out that if we repeatedly apply a vertex-change, an edge-change, and
-        // a face-change, we get the edges that form the Petrial face.
-        //
-        // We go through all flags in the polytope. As we build one Petrial
-        // face, we mark any other flag that gives the same face as "traversed".
-        // Once we've traversed all flags, we got our Petrial's faces.
-        let mut traversed_flags = BTreeSet::new();
-        let mut faces = SubelementList::new();
-
-        self.element_sort();
-        for mut flag in self.flags() {
-            // If we've found the face associated to this flag before, we skip.
-            if !traversed_flags.insert(flag.clone()) {
-                continue;
-            }
-
-            let mut face = BTreeSet::new();
-            let mut edge = flag[2];
-            let mut loop_continue = true;
-
-            // We apply our flag changes and mark our flags until we reach the
-            // original edge. We then intentionally overshoot and do it one more
-            // time.
-            //
-            // TODO: this loop is awkward, rewrite.
-            while loop_continue {
-                loop_continue = face.insert(edge);
-
-                flag.change_mut(self, 1);
-                traversed_flags.insert(flag.change(self, 3));
-                flag.change_mut(self, 2);
-                flag.change_mut(self, 3);
-                traversed_flags.insert(flag.clone());
-
-                edge = flag[2];
-            }
-
-            // If the edge we found after we returned to the original edge was
-            // not already in the face, this means that the Petrial loop
-            // self-intersects, and hence the Petrial is not a valid polytope.
-            if !face.contains(&edge) {
-                return false;
-            }
-
-            faces.push(face.into_iter().collect());
-        }
-
-        // Safety: TODO we need to define the safety guarantees of this function.
-        let ranks = unsafe { self.ranks_mut() };
-
-        // Removes the faces and maximal polytope from self.
-        ranks.pop();
-        ranks.pop();
-
-        // Pushes the new faces and a new maximal element.
-        for el in &mut ranks[2] {
-            el.sups.clear();
-        }
-
-        let face_count = faces.len();
-        let mut new_faces = ElementList::with_capacity(face_count);
-        for (idx, face) in faces.into_iter().enumerate() {
-            for &sub in &face {
-                ranks[(2, sub)].sups.push(idx);
+        match self.petrie_dual() {
+            Ok(petrial) => {
+                *self = petrial;
+                true
             }
-
-            new_faces.push(Element {
-                sups: vec![0].into(),
-                subs: face,
-            });
+            Err(_) => false,
         }
-
-        ranks.push(new_faces);
-        ranks.push(ElementList::max(face_count));
-
-        // Checks for dyadicity, since that sometimes fails.
-        ranks.ranks().is_dyadic().is_ok()
-
-        // TODO MAKE THIS SOUND instead of just returning whether it failed or not!
     }
 
     fn petrie_polygon_with(&mut self, flag: Flag) -> Option<Self> {
@@ -717,9 +1018,10 @@ impl Polytope for Abstract {
     }
 
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
-    /// based on a given polytope. Use [`Self::antiprism`] instead, as this
-    /// method can never fail.
-    fn try_antiprism(&self) -> Result<Self, Self::DualError> {
+    /// based on a given polytope, or returns the section at fault if the
+    /// polytope isn't dyadic.
+    fn try_antiprism(&self) -> Result<Self, AntiprismError> {
+        self.check_antiprism()?;
         Ok(self.antiprism())
     }
 
@@ -1066,8 +1368,8 @@ impl Polytope for Abstract {
             product.ranks.push(other[r].clone());
         }
 
-        let bottom_facet_count = self.el_count(self.rank()-1);
-        let top_vertex_count = other.el_count(1);
+        let bottom_facet_count = self.facet_count();
+        let top_vertex_count = other.vertex_count();
 
         for bottom_facet in &mut product[self.rank()-1] {
             bottom_facet.sups = (0..top_vertex_count).collect();
@@ -1081,6 +1383,11 @@ impl Polytope for Abstract {
 
     /// Builds a [ditope](https://polytope.miraheze.org/wiki/Ditope) of a given
     /// polytope in place. Does nothing in the case of the nullitope.
+    ///
+    /// The maximal element becomes a facet, and gets a copy with the same
+    /// subelements. Both are distinct elements under a new maximal element,
+    /// so that every ridge lies on exactly two facets, and repeated ditopes
+    /// are valid too. Sorted polytopes stay sorted.
     fn ditope_mut(&mut self) {
         if self.rank() != 0 {
             let rank = self.rank();
@@ -1100,6 +1407,10 @@ impl Polytope for Abstract {
 
     /// Builds a [hosotope](https://polytope.miraheze.org/wiki/hosotope) of a
     /// given polytope in place. Does nothing in case of the nullitope.
+    ///
+    /// This is the dual construction to the ditope: the minimal element
+    /// becomes a vertex, and gets a copy with the same superelements, both of
+    /// them above a new minimal element.
     fn hosotope_mut(&mut self) {
         if self.rank() != 0 {
             let ranks = &mut self.ranks;
@@ -1222,7 +1533,7 @@ impl IndexMut<(usize, usize)> for Abstract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test;
+    use crate::{test, ProductKind};
 
     /// Checks a nullitope.
     #[test]
@@ -1242,6 +1553,57 @@ mod tests {
         test(&Abstract::dyad(), [1, 2, 1]);
     }
 
+    /// Checks that isomorphic polytopes are told apart from non-isomorphic
+    /// ones.
+    #[test]
+    fn isomorphic() {
+        let hexagon = Abstract::polygon(6);
+        assert!(hexagon.is_isomorphic(&hexagon.dual()));
+        assert!(!hexagon.is_isomorphic(&Abstract::polygon(5)));
+
+        // Same element counts, but not connected.
+        let triangles = Abstract::compound(vec![Abstract::polygon(3); 2].into_iter());
+        assert!(!hexagon.is_isomorphic(&triangles));
+
+        let cube = Abstract::cube();
+        assert!(cube.is_isomorphic(&Abstract::polygon(4).prism()));
+        assert!(!cube.is_isomorphic(&Abstract::octahedron()));
+    }
+
+    /// Checks that a map from a polytope into its dual sends the subelements
+    /// of every element to the superelements of its image.
+    fn assert_dual_isomorphism(poly: &Abstract) {
+        let rank = poly.rank();
+        let map = poly.dual_isomorphism().expect("polytope isn't self-dual");
+
+        for r in 1..=rank {
+            for (idx, el) in poly[r].iter().enumerate() {
+                let image = &poly[(rank - r, map[r][idx])];
+                for &sub in &el.subs {
+                    assert!(image.sups.contains(&map[r - 1][sub]));
+                }
+            }
+        }
+    }
+
+    /// Checks self-duality on some polytopes.
+    #[test]
+    fn self_dual() {
+        for n in 0..=6 {
+            assert_dual_isomorphism(&Abstract::simplex(n));
+        }
+
+        // The 24-cell, as a rectified 16-cell.
+        let mut orthoplex = Abstract::orthoplex(5);
+        orthoplex.element_sort();
+        let icositetrachoron = orthoplex.truncate_and_flags(vec![1]).0;
+        assert_eq!(icositetrachoron.el_count_iter().collect::<Vec<_>>(), [1, 24, 96, 96, 24, 1]);
+        assert_dual_isomorphism(&icositetrachoron);
+
+        assert!(!Abstract::cube().is_self_dual());
+        assert!(Abstract::polygon(7).is_self_dual());
+    }
+
     /// Checks some polygons.
     #[test]
     fn polygon() {
@@ -1311,6 +1673,53 @@ mod tests {
         }
     }
 
+    /// Checks that hypercubes built by squaring the intermediate products have
+    /// the right element counts, including ranks that aren't powers of two.
+    #[test]
+    fn hypercube_power() {
+        for n in 2..=9 {
+            assert!(Abstract::hypercube(n).el_count_iter().eq(orthoplex_counts(n).rev()));
+        }
+    }
+
+    /// Builds a large hypercube. This is a check on performance, run with
+    /// `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn hypercube_power_large() {
+        let start = std::time::Instant::now();
+        let hypercube = Abstract::hypercube(12);
+        println!("building a 12-hypercube took {:?}", start.elapsed());
+
+        assert!(hypercube.el_count_iter().eq(orthoplex_counts(12).rev()));
+    }
+
+    /// Checks that powers agree with repeated products.
+    #[test]
+    fn power() {
+        let dyad = Abstract::dyad();
+        assert!(Abstract::power(ProductKind::Prism, &dyad, 0).is_isomorphic(&Abstract::point()));
+        assert!(Abstract::power(ProductKind::Prism, &dyad, 1).is_isomorphic(&dyad));
+
+        for kind in [
+            ProductKind::Pyramid,
+            ProductKind::Prism,
+            ProductKind::Tegum,
+            ProductKind::Comb,
+        ] {
+            let mut product = dyad.clone();
+            for n in 2..=3 {
+                product = kind.apply(&product, &dyad);
+                assert!(
+                    Abstract::power(kind, &dyad, n).is_isomorphic(&product),
+                    "{:?} power {} differs from repeated products",
+                    kind,
+                    n
+                );
+            }
+        }
+    }
+
     /// Checks orthoplices.
     #[test]
     fn orthoplex() {
@@ -1322,6 +1731,83 @@ mod tests {
         }
     }
 
+    /// Checks that the facet adjacency graph of a cube is the edge graph of
+    /// an octahedron.
+    #[test]
+    fn facet_adjacency() {
+        let cube = Abstract::cube();
+        let adjacency = cube.facet_adjacency();
+        let oct_adjacency = cube.dual().vertex_adjacency();
+        assert_eq!(adjacency, oct_adjacency);
+
+        // Every face of a cube is adjacent to all but its opposite.
+        for (idx, list) in adjacency.iter().enumerate() {
+            assert_eq!(list.len(), 4);
+            assert_eq!(list, &cube.adjacent(3, idx));
+        }
+    }
+
+    /// Checks that the vertex adjacency graph of a simplex is complete.
+    #[test]
+    fn vertex_adjacency() {
+        for (idx, list) in Abstract::simplex(5).vertex_adjacency().iter().enumerate() {
+            assert_eq!(list.len(), 4);
+            assert!(!list.contains(&idx));
+        }
+    }
+
+    /// Pages through the edges of a cube.
+    #[test]
+    fn elements_page() {
+        let cube = Abstract::cube();
+        assert_eq!(cube.elements_page(2, 0, 5), &cube[2].as_slice()[..5]);
+        assert_eq!(cube.elements_page(2, 10, 5), &cube[2].as_slice()[10..]);
+        assert!(cube.elements_page(2, 12, 5).is_empty());
+        assert!(cube.elements_page(2, 20, usize::MAX).is_empty());
+        assert!(cube.elements_page(5, 0, 5).is_empty());
+    }
+
+    /// Every face of a tesseract is in two of its cells, and every face of a
+    /// cube is its own facet.
+    #[test]
+    fn face_facets() {
+        let tesseract = Abstract::hypercube(5);
+        let face_facets = tesseract.face_facets();
+        assert_eq!(face_facets.len(), 24);
+
+        for (face, facets) in face_facets.iter().enumerate() {
+            assert_eq!(facets.len(), 2);
+            for &facet in facets {
+                let faces = tesseract.element_subelements(4, facet, 3).unwrap();
+                assert!(faces.contains(&face));
+            }
+        }
+
+        let cube = Abstract::cube();
+        assert_eq!(cube.face_facets(), (0..6).map(|idx| vec![idx]).collect::<Vec<_>>());
+        assert!(Abstract::polygon(4).face_facets().is_empty());
+    }
+
+    /// Walks down from a cell of a tesseract to its subelements.
+    #[test]
+    fn element_subelements() {
+        let tesseract = Abstract::hypercube(5);
+        let mut edges = tesseract.element_subelements(4, 0, 2).unwrap();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(edges.len(), 12);
+
+        for &edge in &edges {
+            for &vertex in &tesseract[(2, edge)].subs {
+                assert!(tesseract.element_vertices(4, 0).unwrap().contains(&vertex));
+            }
+        }
+
+        assert_eq!(tesseract.element_subelements(4, 0, 4), Some(vec![0]));
+        assert_eq!(tesseract.element_subelements(4, 0, 5), Some(Vec::new()));
+        assert_eq!(tesseract.element_subelements(4, 8, 2), None);
+    }
+
     /// Tests a few duals.
     #[test]
     fn dual() {
@@ -1329,4 +1815,30 @@ mod tests {
         test(&Abstract::polygon(6).into_dual(), [1, 6, 6, 1]);
         test(&Abstract::cube().into_dual(), [1, 6, 12, 8, 1]);
     }
+
+    /// The shorthand counts agree with those of the ranks they stand for.
+    #[test]
+    fn shorthand_counts() {
+        for poly in [
+            Abstract::polygon(5),
+            Abstract::cube(),
+            Abstract::simplex(6),
+            Abstract::polygon(3).duoprism(&Abstract::polygon(4)),
+        ] {
+            let rank = poly.rank();
+            assert_eq!(poly.vertex_count(), poly.el_count(1));
+            assert_eq!(poly.edge_count(), poly.el_count(2));
+            assert_eq!(poly.face_count(), poly.el_count(3));
+            assert_eq!(poly.facet_count(), poly.el_count(rank - 1));
+            assert_eq!(poly.ridge_count(), poly.el_count(rank - 2));
+
+            assert_eq!(poly.facets().count(), poly.facet_count());
+            assert!(poly.ridges().all(|(idx, ridge)| ridge == &poly[(rank - 2, idx)]));
+        }
+
+        // The nullitope has no facets or ridges.
+        let nullitope = Abstract::nullitope();
+        assert_eq!(nullitope.facet_count(), 0);
+        assert_eq!(nullitope.ridges().count(), 0);
+    }
 }
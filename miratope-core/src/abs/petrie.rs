@@ -0,0 +1,228 @@
+//! Replaces the faces of a polyhedron by other closed paths along its edges,
+//! such as its Petrie polygons or its holes.
+
+use std::collections::HashSet;
+
+use super::{
+    flag::Flag,
+    ranked::Section,
+    valid::AbstractError,
+    Abstract, AbstractBuilder, AbstractResult, Ranked, SubelementList, Subelements,
+};
+use crate::Polytope;
+
+use vec_like::*;
+
+/// Applies a word of flag changes to a flag.
+fn apply(flag: &mut Flag, poly: &Abstract, word: &[usize]) {
+    for &r in word {
+        flag.change_mut(poly, r);
+    }
+}
+
+impl Abstract {
+    /// Replaces the faces of a polyhedron by the orbits of its flags under two
+    /// words of flag changes, which take the place of the vertex and edge
+    /// changes. The vertex word must fix the edge of every flag, and the edge
+    /// word must fix its vertex. The vertices and edges are kept as they are.
+    ///
+    /// Returns an error if the result isn't a valid polytope. This happens
+    /// when some path passes through an edge more than once, or when the new
+    /// faces through a vertex don't form a single cycle.
+    fn replace_faces(&self, vertex_word: &[usize], edge_word: &[usize]) -> AbstractResult<Self> {
+        assert_eq!(self.rank(), 4, "only polyhedra have faces to replace");
+
+        let mut poly = self.clone();
+        poly.element_sort();
+
+        let mut traversed = HashSet::new();
+        let mut faces = SubelementList::new();
+        let mut checked_vertices = vec![false; poly.vertex_count()];
+
+        for flag in poly.flags() {
+            // Checks that the edges through the vertex are all reached by the
+            // new edge changes together with the face changes.
+            let vertex = flag[1];
+            if !checked_vertices[vertex] {
+                checked_vertices[vertex] = true;
+
+                let mut edges = HashSet::new();
+                let mut new_flag = flag.clone();
+                loop {
+                    edges.insert(new_flag[2]);
+                    apply(&mut new_flag, &poly, edge_word);
+                    new_flag.change_mut(&poly, 3);
+
+                    if new_flag == flag {
+                        break;
+                    }
+                }
+
+                if edges.len() != poly[(1, vertex)].sups.len() {
+                    return Err(AbstractError::Connected(Section::new(1, vertex, 4, 0)));
+                }
+            }
+
+            // If we've found the face associated to this flag before, we skip.
+            if traversed.contains(&flag) {
+                continue;
+            }
+
+            // Walks along the path until we return to the original flag. Any
+            // edge that's passed through twice gets listed twice.
+            let mut face = Subelements::new();
+            let mut new_flag = flag.clone();
+            loop {
+                face.push(new_flag[2]);
+                traversed.insert(new_flag.clone());
+                apply(&mut new_flag, &poly, vertex_word);
+                traversed.insert(new_flag.clone());
+                apply(&mut new_flag, &poly, edge_word);
+
+                if new_flag == flag {
+                    break;
+                }
+            }
+
+            faces.push(face);
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(poly.vertex_count());
+        builder.push(poly[2].iter().map(|edge| edge.subs.clone()).collect());
+        builder.push(faces);
+        builder.push_max();
+        builder.ranks().is_valid()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Ok(unsafe { builder.build() })
+    }
+
+    /// Builds the [Petrial](https://polytope.miraheze.org/wiki/Petrial) of a
+    /// polyhedron, whose faces are its Petrie polygons. The Petrial of the
+    /// Petrial is the original polyhedron.
+    ///
+    /// The vertices and edges keep their order. Returns an error if the
+    /// Petrial isn't a valid polytope, which happens when some Petrie polygon
+    /// passes through an edge more than once.
+    ///
+    /// # Panics
+    /// Panics if the polytope isn't a polyhedron.
+    pub fn petrie_dual(&self) -> AbstractResult<Self> {
+        self.replace_faces(&[1, 3], &[2])
+    }
+
+    /// Builds the polyhedron whose faces are the `j`-holes of a polyhedron.
+    /// These are the paths along its edges that leave every vertex by the
+    /// `j`-th edge from the one they entered by, always turning the same way.
+    /// The 2-holes of the icosahedron form the great dodecahedron, and the
+    /// 1-holes of any polyhedron are just its faces.
+    ///
+    /// The vertices and edges keep their order. Returns an error if the
+    /// result isn't a valid polytope. Besides the holes passing through an
+    /// edge more than once, this happens when the holes through some vertex
+    /// don't pass through all of its edges, such as when skipping every other
+    /// edge around a vertex with an even number of them.
+    ///
+    /// # Panics
+    /// Panics if the polytope isn't a polyhedron, or if `j` is zero.
+    pub fn hole(&self, j: usize) -> AbstractResult<Self> {
+        assert!(j >= 1, "holes must skip at least one edge");
+
+        let mut edge_word = vec![2];
+        for _ in 1..j {
+            edge_word.extend_from_slice(&[3, 2]);
+        }
+
+        self.replace_faces(&[1], &edge_word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::Concrete, test};
+
+    /// The Petrial of the cube has four hexagons.
+    #[test]
+    fn cube() {
+        let petrial = Abstract::cube().petrie_dual().unwrap();
+        test(&petrial, [1, 8, 12, 4, 1]);
+        assert!(petrial[3].iter().all(|face| face.subs.len() == 6));
+    }
+
+    /// The Petrial is an involution.
+    #[test]
+    fn involution() {
+        for poly in [
+            Abstract::cube(),
+            Abstract::simplex(4),
+            Concrete::platonic(3).abs,
+            Concrete::platonic(4).abs,
+        ] {
+            let petrial = poly.petrie_dual().unwrap();
+            assert!(petrial.petrie_dual().unwrap().is_isomorphic(&poly));
+        }
+    }
+
+    /// The Petrial of the octahedron has four hexagons, and that of the
+    /// tetrahedron is the hemicube.
+    #[test]
+    fn octahedron() {
+        test(&Abstract::octahedron().petrie_dual().unwrap(), [1, 6, 12, 4, 1]);
+        test(&Abstract::simplex(4).petrie_dual().unwrap(), [1, 4, 6, 3, 1]);
+    }
+
+    /// The 1-holes are the faces, and the 2-holes of the icosahedron are the
+    /// faces of the great dodecahedron.
+    #[test]
+    fn holes() {
+        let cube = Abstract::cube();
+        assert!(cube.hole(1).unwrap().is_isomorphic(&cube));
+
+        let mut icosahedron = Concrete::platonic(4).abs;
+        icosahedron.element_sort();
+        let great_dodecahedron = icosahedron.hole(2).unwrap();
+        test(&great_dodecahedron, [1, 12, 30, 12, 1]);
+
+        // The faces of the great dodecahedron are the links of the vertices
+        // of the icosahedron.
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(12);
+        builder.push(icosahedron[2].iter().map(|edge| edge.subs.clone()).collect());
+        builder.push(
+            (0..12)
+                .map(|v| {
+                    let neighbors: Vec<_> = icosahedron[(1, v)]
+                        .sups
+                        .iter()
+                        .flat_map(|&e| icosahedron[(2, e)].subs.iter().copied())
+                        .filter(|&u| u != v)
+                        .collect();
+
+                    (0..icosahedron[2].len())
+                        .filter(|&e| {
+                            icosahedron[(2, e)]
+                                .subs
+                                .iter()
+                                .all(|u| neighbors.contains(u))
+                        })
+                        .collect()
+                })
+                .collect(),
+        );
+        builder.push_max();
+        let expected = unsafe { builder.build() };
+
+        assert!(great_dodecahedron.is_isomorphic(&expected));
+    }
+
+    /// Skipping every other edge around the vertices of the octahedron splits
+    /// its vertex figures.
+    #[test]
+    fn octahedron_holes() {
+        assert!(Abstract::octahedron().hole(2).is_err());
+    }
+}
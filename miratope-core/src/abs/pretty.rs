@@ -0,0 +1,145 @@
+//! Prints out abstract polytopes in a readable way.
+
+use std::fmt::{Display, Formatter, Result};
+
+use super::{Abstract, Ranked};
+
+use vec_like::*;
+
+/// The number of elements of every rank that [`Abstract`]'s `Display`
+/// implementation prints out.
+const DISPLAYED_ELEMENTS: usize = 8;
+
+impl Abstract {
+    /// Writes out the polytope with one line for every rank with its element
+    /// count, followed by a line `r#idx: [subs...]` for each of its first
+    /// elements.
+    fn write_pretty(&self, f: &mut Formatter<'_>, max_elements_per_rank: usize) -> Result {
+        for (r, elements) in self.ranks().iter().enumerate() {
+            writeln!(
+                f,
+                "rank {}: {} element{}",
+                r,
+                elements.len(),
+                if elements.len() == 1 { "" } else { "s" }
+            )?;
+
+            for (idx, el) in elements.iter().take(max_elements_per_rank).enumerate() {
+                writeln!(f, "  {}#{}: {:?}", r, idx, el.subs.iter().collect::<Vec<_>>())?;
+            }
+
+            if elements.len() > max_elements_per_rank {
+                writeln!(f, "  ... {} more", elements.len() - max_elements_per_rank)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the polytope written out with one line for every rank with its
+    /// element count, followed by a line `r#idx: [subs...]` for each of its
+    /// first `max_elements_per_rank` elements.
+    ///
+    /// ```
+    /// use miratope_core::{abs::Abstract, Polytope};
+    ///
+    /// assert_eq!(
+    ///     Abstract::cube().pretty(2),
+    ///     "rank 0: 1 element
+    ///   0#0: []
+    /// rank 1: 8 elements
+    ///   1#0: [0]
+    ///   1#1: [0]
+    ///   ... 6 more
+    /// rank 2: 12 elements
+    ///   2#0: [0, 1]
+    ///   2#1: [2, 3]
+    ///   ... 10 more
+    /// rank 3: 6 elements
+    ///   3#0: [0, 1, 2, 3]
+    ///   3#1: [4, 5, 6, 7]
+    ///   ... 4 more
+    /// rank 4: 1 element
+    ///   4#0: [0, 1, 2, 3, 4, 5]
+    /// "
+    /// );
+    /// ```
+    pub fn pretty(&self, max_elements_per_rank: usize) -> String {
+        struct Pretty<'a>(&'a Abstract, usize);
+
+        impl<'a> Display for Pretty<'a> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                self.0.write_pretty(f, self.1)
+            }
+        }
+
+        Pretty(self, max_elements_per_rank).to_string()
+    }
+
+    /// Returns a hash of the polytope that doesn't depend on the order of its
+    /// elements, so that two polytopes with different hashes are never
+    /// isomorphic.
+    ///
+    /// The hash is taken of the [canonical form](Abstract::canonical_form) of
+    /// the polytope. This takes time proportional to the square of the flag
+    /// count. Polytopes whose flags aren't all connected to each other, like
+    /// compounds, are only hashed through their element counts.
+    pub fn canonical_hash(&self) -> u64 {
+        self.canonical_form().fingerprint()
+    }
+}
+
+/// Writes out the first few elements of every rank, as in
+/// [`Abstract::pretty`]. With the alternate flag, only the element counts and
+/// the [`Abstract::canonical_hash`] are written, so that two polytopes can be
+/// compared at a glance.
+///
+/// ```
+/// use miratope_core::{abs::Abstract, Polytope};
+///
+/// let cube = Abstract::cube();
+/// assert!(cube.to_string().starts_with("rank 0: 1 element\n  0#0: []\nrank 1: 8 elements\n"));
+/// assert_eq!(
+///     format!("{:#}", cube),
+///     format!("(1, 8, 12, 6, 1) #{:016x}", cube.canonical_hash())
+/// );
+/// ```
+impl Display for Abstract {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if f.alternate() {
+            write!(f, "{} #{:016x}", self.el_counts(), self.canonical_hash())
+        } else {
+            self.write_pretty(f, DISPLAYED_ELEMENTS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::Concrete, Polytope};
+
+    /// Reordering the elements doesn't change the hash, but taking the dual
+    /// of a polytope that's not self-dual does.
+    #[test]
+    fn canonical_hash() {
+        let cube = Abstract::cube();
+        let hash = cube.canonical_hash();
+
+        // The duals have the elements in a different order.
+        assert_eq!(cube.dual().dual().canonical_hash(), hash);
+        assert_eq!(Concrete::hypercube(4).abs.canonical_hash(), hash);
+        assert_ne!(Abstract::octahedron().canonical_hash(), hash);
+        assert_ne!(Abstract::cube().prism().canonical_hash(), hash);
+    }
+
+    /// Short ranks aren't cut off.
+    #[test]
+    fn pretty() {
+        assert_eq!(
+            Abstract::polygon(3).pretty(3),
+            "rank 0: 1 element\n  0#0: []\nrank 1: 3 elements\n  1#0: [0]\n  1#1: [0]\n  1#2: [0]\n\
+            rank 2: 3 elements\n  2#0: [0, 1]\n  2#1: [1, 2]\n  2#2: [0, 2]\nrank 3: 1 element\n  3#0: [0, 1, 2]\n"
+        );
+    }
+}
@@ -1,6 +1,23 @@
 //! Contains the code for the polytope products.
 
-use super::*;
+use std::{mem::size_of, ops::ControlFlow};
+
+use super::{provenance::{Provenance, Source}, *};
+
+/// The number of elements that are added to a product between two calls to the
+/// progress callback.
+const PROGRESS_INTERVAL: usize = 4096;
+
+/// The progress of a polytope product, reported periodically while it's being
+/// built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProductProgress {
+    /// The rank of the elements currently being added.
+    pub rank: usize,
+
+    /// The total number of elements added so far.
+    pub elements: usize,
+}
 
 /// When we compute any polytope product, we add the elements of any given rank
 /// in lexicographic order of the ranks of the elements they come from. This
@@ -115,15 +132,114 @@ impl<const MIN: bool, const MAX: bool> Index<(usize, usize)> for OffsetMemo<MIN,
 /// of elements in the set of polytopes. The elements of a specific rank are
 /// sorted first by lexicographic order of the ranks, then by lexicographic
 /// order of the indices of the elements.
+///
+/// # Degenerate cases
+/// Products involving the nullitope or the point are returned early:
+/// - the pyramid product of a polytope with the nullitope is the polytope,
+/// - every other product with the nullitope is the nullitope,
+/// - the prism and tegum products of a polytope with the point are the
+///   polytope,
+/// - the comb product of a polytope with the point is the nullitope.
+///
+/// # Element counts
+/// Outside of the degenerate cases, an element of `p` of rank `i` and an
+/// element of `q` of rank `j` give an element of rank `i + j - MIN` in the
+/// product, where `i` and `j` range over the ranks of the elements that are
+/// considered. The number of elements of each rank is thus a sum of products
+/// of element counts of `p` and `q`, and it's never zero.
 fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abstract {
+    product_with_progress::<MIN, MAX>(p, q, &|_| ControlFlow::Continue(()), None)
+        .expect("the product can't be cancelled")
+}
+
+/// Returns the provenance of a product that's equal to one of its factors,
+/// where each element of the factor is paired with the element of the other
+/// factor with a given rank and index.
+fn factor_provenance<F: Fn(usize) -> (usize, usize)>(
+    factor: &Abstract,
+    factor_is_p: bool,
+    partner: F,
+) -> Provenance {
+    let mut provenance = Provenance::with_capacity(factor.rank() + 1);
+
+    for (rank, count) in factor.el_count_iter().enumerate() {
+        provenance.push(
+            (0..count)
+                .map(|idx| {
+                    Some(if factor_is_p {
+                        Source::Pair((rank, idx), partner(rank))
+                    } else {
+                        Source::Pair(partner(rank), (rank, idx))
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    provenance
+}
+
+/// Computes a [`product`], calling a progress callback at the start of every
+/// rank and every few thousand elements. If the callback returns
+/// [`ControlFlow::Break`], the computation is abandoned and `None` is
+/// returned.
+///
+/// If a provenance is passed, every element of the product is recorded as
+/// coming from the pair of elements of `p` and `q` it corresponds to.
+fn product_with_progress<const MIN: bool, const MAX: bool>(
+    p: &Abstract,
+    q: &Abstract,
+    progress: &dyn Fn(ProductProgress) -> ControlFlow<()>,
+    mut provenance: Option<&mut Provenance>,
+) -> Option<Abstract> {
     // The ranks of p and q.
     let p_rank = p.rank();
     let q_rank = q.rank();
 
-    // For anything but a pyramid product, we'll just special-case the product
-    // with a nullitope as the nullitope.
-    if (MIN || MAX) && (p_rank == 0 || q_rank == 0) {
-        return Abstract::nullitope();
+    // Returns the product in the degenerate cases, recording its provenance.
+    let mut degenerate = |product: Abstract, factor_is_p: Option<bool>| {
+        if let Some(provenance) = provenance.as_mut() {
+            **provenance = match factor_is_p {
+                // Within the prism and tegum products, the point's maximal
+                // element pairs up with the proper elements of the other
+                // factor, or with its maximal element, respectively.
+                Some(factor_is_p) => factor_provenance(&product, factor_is_p, |rank| {
+                    if (MIN && rank != 0) || (MAX && rank == product.rank()) {
+                        (1, 0)
+                    } else {
+                        (0, 0)
+                    }
+                }),
+                None => Provenance::unknown(&product),
+            };
+        }
+
+        Some(product)
+    };
+
+    // The nullitope is the identity of the pyramid product, and it absorbs
+    // every other product.
+    if p_rank == 0 || q_rank == 0 {
+        return if MIN || MAX {
+            degenerate(Abstract::nullitope(), None)
+        } else if p_rank == 0 {
+            degenerate(q.clone(), Some(false))
+        } else {
+            degenerate(p.clone(), Some(true))
+        };
+    }
+
+    // The point is the identity of both the prism and the tegum product. Since
+    // its only proper element is its maximal element, it absorbs the comb
+    // product.
+    if MIN || MAX {
+        if MIN && MAX && (p_rank == 1 || q_rank == 1) {
+            return degenerate(Abstract::nullitope(), None);
+        } else if p_rank == 1 {
+            return degenerate(q.clone(), Some(false));
+        } else if q_rank == 1 {
+            return degenerate(p.clone(), Some(true));
+        }
     }
 
     // 0 or 1 depending on whether the minimum/maximum elements are in the
@@ -132,7 +248,6 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
     let max_u = MAX as usize;
 
     // The highest ranks we'll use to take products in p and q.
-    // TODO: THIS CURRENTLY FAILS IN THE NULLITOPE
     let p_hi = p_rank - max_u;
     let q_hi = q_rank - max_u;
 
@@ -149,16 +264,45 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
     if MIN {
         builder.push_min();
         builder.push_vertices(p.vertex_count() * q.vertex_count());
+
+        if let Some(provenance) = provenance.as_mut() {
+            **provenance = Provenance::with_capacity(rank + 1);
+            provenance.push(vec![Some(Source::Pair((0, 0), (0, 0)))]);
+            provenance.push(
+                (0..p.vertex_count())
+                    .flat_map(|p_idx| {
+                        (0..q.vertex_count()).map(move |q_idx| Some(Source::Pair((1, p_idx), (1, q_idx))))
+                    })
+                    .collect(),
+            );
+        }
+    } else if let Some(provenance) = provenance.as_mut() {
+        **provenance = Provenance::with_capacity(rank + 1);
     }
 
     let lo = 2 * min_u;
     let hi = rank - max_u;
 
+    // The number of elements added so far, and the number at which we'll next
+    // report our progress.
+    let mut elements = 0;
+    let mut next_report = 0;
+
     // Adds elements in order of rank.
     for prod_rank in lo..=hi {
         let lo = (min_u as isize).max((prod_rank + min_u) as isize - q_hi as isize) as usize;
         let hi = p_hi.min(prod_rank);
         let mut subelements = SubelementList::new();
+        let mut sources = Vec::new();
+
+        if progress(ProductProgress {
+            rank: prod_rank,
+            elements,
+        })
+        .is_break()
+        {
+            return None;
+        }
 
         // Adds elements by lexicographic order of the ranks.
         for p_el_rank in lo..=hi {
@@ -168,8 +312,27 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
             // with every element in q with rank q_els_rank.
             for (p_idx, p_el) in p[p_el_rank].iter().enumerate() {
                 for (q_idx, q_el) in q[q_el_rank].iter().enumerate() {
+                    // Reports the progress every so often.
+                    if elements == next_report {
+                        next_report += PROGRESS_INTERVAL;
+
+                        if progress(ProductProgress {
+                            rank: prod_rank,
+                            elements,
+                        })
+                        .is_break()
+                        {
+                            return None;
+                        }
+                    }
+
+                    elements += 1;
                     let mut subs = Subelements::new();
 
+                    if provenance.is_some() {
+                        sources.push(Some(Source::Pair((p_el_rank, p_idx), (q_el_rank, q_idx))));
+                    }
+
                     // Products of p's subelements with q.
                     if !MIN || p_el_rank != 1 {
                         for &p_sub in &p_el.subs {
@@ -202,19 +365,155 @@ fn product<const MIN: bool, const MAX: bool>(p: &Abstract, q: &Abstract) -> Abst
         }
 
         builder.push(subelements);
+
+        if let Some(provenance) = provenance.as_mut() {
+            provenance.push(sources);
+        }
     }
 
     // If MAX, we have to set a maximal element manually.
     if MAX {
         builder.push_max();
+
+        if let Some(provenance) = provenance.as_mut() {
+            provenance.push(vec![Some(Source::Pair((p_rank, 0), (q_rank, 0)))]);
+        }
     }
 
+    // Every rank in between the minimal and maximal elements must have come
+    // from some pair of ranks.
+    debug_assert!(
+        builder.ranks().iter().all(|elements| !elements.is_empty()),
+        "some rank of the product is empty"
+    );
+
     // TODO: If `p` and `q` are sorted, this should be too?
 
     // Safety: we've built one of the four products on polytopes. For a
     // proof that these constructions yield valid abstract polytopes, see
     // [TODO: write proof].
-    unsafe { builder.build() }
+    Some(unsafe { builder.build() })
+}
+
+impl Abstract {
+    /// Computes the product of two polytopes, where `min` and `max` determine
+    /// whether the minimal and maximal elements are considered, as in
+    /// [`product`]. The arguments are taken in the order given, so that for
+    /// instance the duopyramid of `p` and `q` is the product of `q` and `p`.
+    ///
+    /// The `progress` callback is called at the start of every rank and every
+    /// few thousand elements. If it ever returns [`ControlFlow::Break`], the
+    /// computation is abandoned and `None` is returned.
+    pub fn product_with_progress(
+        p: &Self,
+        q: &Self,
+        min: bool,
+        max: bool,
+        progress: &dyn Fn(ProductProgress) -> ControlFlow<()>,
+    ) -> Option<Self> {
+        Self::product_impl(p, q, min, max, progress, None)
+    }
+
+    /// Computes the product of two polytopes, as in
+    /// [`Self::product_with_progress`], together with the pair of elements of
+    /// `p` and `q` that each of its elements comes from.
+    pub fn product_with_provenance(p: &Self, q: &Self, min: bool, max: bool) -> (Self, Provenance) {
+        let mut provenance = Provenance::default();
+        let product = Self::product_impl(
+            p,
+            q,
+            min,
+            max,
+            &|_| ControlFlow::Continue(()),
+            Some(&mut provenance),
+        )
+        .expect("the product can't be cancelled");
+
+        (product, provenance)
+    }
+
+    /// Returns the element counts of the product of two polytopes, as built by
+    /// [`Self::product_with_progress`], without building it. Each count is the
+    /// sum over every pair of ranks adding up to its rank of the products of
+    /// the element counts of the factors. If either factor has no elements of
+    /// the ranks that are considered, the product is the nullitope.
+    pub fn product_counts(p: &Self, q: &Self, min: bool, max: bool) -> Vec<usize> {
+        let (min_u, max_u) = (min as usize, max as usize);
+        let (p_hi, q_hi) = match (
+            p.rank().checked_sub(max_u).filter(|&hi| hi >= min_u),
+            q.rank().checked_sub(max_u).filter(|&hi| hi >= min_u),
+        ) {
+            (Some(p_hi), Some(q_hi)) => (p_hi, q_hi),
+            _ => return vec![1],
+        };
+
+        let rank = p.rank() + q.rank() - min_u - max_u;
+        let mut counts = vec![0; rank + 1];
+        for i in min_u..=p_hi {
+            for j in min_u..=q_hi {
+                counts[i + j - min_u] += p.el_count(i) * q.el_count(j);
+            }
+        }
+
+        if min {
+            counts[0] = 1;
+        }
+        if max {
+            counts[rank] = 1;
+        }
+
+        counts
+    }
+
+    /// Estimates the number of bytes that the product of two polytopes takes
+    /// up, without building it. Every element of the product has as
+    /// subelements those of both of the elements it comes from, and as many
+    /// superelements overall, so this is only exact away from the elements
+    /// added or left out by `min` and `max`.
+    pub fn product_size_estimate(p: &Self, q: &Self, min: bool, max: bool) -> usize {
+        let counts = Self::product_counts(p, q, min, max);
+        if counts.len() == 1 {
+            return size_of::<Element>();
+        }
+
+        // The number of elements of each rank that are considered, and their
+        // total number of subelements.
+        let (min_u, max_u) = (min as usize, max as usize);
+        let ranks = |poly: &Self| -> Vec<(usize, usize)> {
+            poly.iter()
+                .take(poly.rank() + 1 - max_u)
+                .skip(min_u)
+                .map(|list| (list.len(), list.iter().map(|el| el.subs.len()).sum()))
+                .collect()
+        };
+
+        let incidences: usize = ranks(p)
+            .into_iter()
+            .cartesian_product(ranks(q))
+            .map(|((p_count, p_subs), (q_count, q_subs))| p_subs * q_count + p_count * q_subs)
+            .sum();
+
+        counts.iter().sum::<usize>() * size_of::<Element>()
+            + 2 * incidences * size_of::<usize>()
+    }
+
+    /// Dispatches a product to the right instance of
+    /// [`product_with_progress`].
+    fn product_impl(
+        p: &Self,
+        q: &Self,
+        min: bool,
+        max: bool,
+        progress: &dyn Fn(ProductProgress) -> ControlFlow<()>,
+        provenance: Option<&mut Provenance>,
+    ) -> Option<Self> {
+        match (min, max) {
+            (false, false) => product_with_progress::<false, false>(p, q, progress, provenance),
+            (true, false) => product_with_progress::<true, false>(p, q, progress, provenance),
+            (false, true) => product_with_progress::<false, true>(p, q, progress, provenance),
+            (true, true) => product_with_progress::<true, true>(p, q, progress, provenance),
+        }
+    }
 }
 
 /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
@@ -266,6 +565,143 @@ mod tests {
         assert!(nullitope.duocomb(&cube).is_nullitope());
     }
 
+    /// Returns whether two polytopes have the same element counts.
+    fn same_counts(p: &Abstract, q: &Abstract) -> bool {
+        p.el_count_iter().eq(q.el_count_iter())
+    }
+
+    /// Checks every product against the nullitope, the point, and the dyad.
+    #[test]
+    fn degenerate_products() {
+        let factors = [Abstract::nullitope(), Abstract::point(), Abstract::dyad()];
+        let products: [fn(&Abstract, &Abstract) -> Abstract; 4] = [
+            Abstract::duopyramid,
+            Abstract::duoprism,
+            Abstract::duotegum,
+            Abstract::duocomb,
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                for product in &products {
+                    product(p, q).assert_valid();
+                }
+
+                if p.is_nullitope() {
+                    assert!(same_counts(&p.duopyramid(q), q));
+                    assert!(p.duoprism(q).is_nullitope());
+                    assert!(p.duotegum(q).is_nullitope());
+                    assert!(p.duocomb(q).is_nullitope());
+                } else if p.rank() == 1 && !q.is_nullitope() {
+                    assert!(same_counts(&p.duoprism(q), q));
+                    assert!(same_counts(&p.duotegum(q), q));
+                    assert!(p.duocomb(q).is_nullitope());
+                }
+            }
+        }
+    }
+
+    /// Checks the element counts of every product of every pair of a few
+    /// small polytopes, including compounds and non-orientable polytopes.
+    #[test]
+    fn product_counts_formula() {
+        let triangles = Abstract::compound(vec![Abstract::polygon(3); 2].into_iter());
+        let hemicube = Abstract::cube()
+            .quotient(&[(0, 7), (1, 6), (2, 5), (3, 4)])
+            .unwrap();
+        let factors = [
+            Abstract::nullitope(),
+            Abstract::point(),
+            Abstract::dyad(),
+            Abstract::polygon(3),
+            Abstract::polygon(4),
+            triangles,
+            Abstract::simplex(4),
+            Abstract::cube(),
+            Abstract::octahedron(),
+            hemicube,
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                for (min, max) in [(false, false), (true, false), (false, true), (true, true)] {
+                    let product = Abstract::product_with_progress(p, q, min, max, &|_| {
+                        ControlFlow::Continue(())
+                    })
+                    .unwrap();
+
+                    assert_eq!(
+                        product.el_count_iter().collect::<Vec<_>>(),
+                        Abstract::product_counts(p, q, min, max),
+                        "wrong counts for ranks {} and {}, min {} and max {}",
+                        p.rank(),
+                        q.rank(),
+                        min,
+                        max
+                    );
+                }
+            }
+        }
+    }
+
+    /// The estimated size of a few products is exact for their duopyramids,
+    /// and close to their actual size otherwise.
+    #[test]
+    fn product_size_estimate() {
+        let size = |poly: &Abstract| -> usize {
+            poly.iter()
+                .flat_map(|list| list.iter())
+                .map(|el| {
+                    size_of::<Element>()
+                        + (el.subs.len() + el.sups.len()) * size_of::<usize>()
+                })
+                .sum()
+        };
+
+        let factors = [Abstract::polygon(7), Abstract::cube(), Abstract::simplex(5)];
+        for p in &factors {
+            for q in &factors {
+                for (min, max) in [(false, false), (true, false), (false, true), (true, true)] {
+                    let product = Abstract::product_with_progress(p, q, min, max, &|_| {
+                        ControlFlow::Continue(())
+                    })
+                    .unwrap();
+
+                    let estimate = Abstract::product_size_estimate(p, q, min, max);
+                    if !min && !max {
+                        assert_eq!(estimate, size(&product));
+                    } else {
+                        let ratio = estimate as f64 / size(&product) as f64;
+                        assert!((0.85..1.15).contains(&ratio), "{}", ratio);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks the provenance of the elements of a square, built as a duoprism
+    /// of two dyads, and of a prism product with a point.
+    #[test]
+    fn provenance() {
+        let dyad = Abstract::dyad();
+        let (square, provenance) = Abstract::product_with_provenance(&dyad, &dyad, true, false);
+        test(&square, [1, 4, 4, 1]);
+
+        assert_eq!(provenance.len(), 4);
+        assert_eq!(provenance.get(0, 0), Some(Source::Pair((0, 0), (0, 0))));
+        assert_eq!(provenance.get(1, 2), Some(Source::Pair((1, 1), (1, 0))));
+        assert_eq!(provenance.get(2, 1), Some(Source::Pair((1, 1), (2, 0))));
+        assert_eq!(provenance.get(2, 2), Some(Source::Pair((2, 0), (1, 0))));
+        assert_eq!(provenance.get(3, 0), Some(Source::Pair((2, 0), (2, 0))));
+
+        let cube = Abstract::cube();
+        let (prism, provenance) =
+            Abstract::product_with_provenance(&Abstract::point(), &cube, true, false);
+        assert!(same_counts(&prism, &cube));
+        assert_eq!(provenance.get(0, 0), Some(Source::Pair((0, 0), (0, 0))));
+        assert_eq!(provenance.get(3, 5), Some(Source::Pair((1, 0), (3, 5))));
+    }
+
     /// Tests that polygonal duoproducts (i.e. duopyramids, duoprisms,
     /// duotegums, duocombs) are generated correctly by checking that the
     /// element counts for the product of an m-gon and an n-gon match for a few
@@ -321,4 +757,58 @@ mod tests {
     fn duocomb() {
         test_duoproduct(Abstract::duocomb, |m, n| [1, m * n, 2 * m * n, m * n, 1])
     }
+
+    /// Checks that the progress callback is called once per rank, and once
+    /// every few thousand elements, with progress that never goes back.
+    #[test]
+    fn progress() {
+        use std::cell::RefCell;
+
+        let p = Abstract::polygon(100);
+        let reports = RefCell::new(Vec::new());
+
+        let prod = Abstract::product_with_progress(&p, &p, true, false, &|progress| {
+            reports.borrow_mut().push(progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert!(same_counts(&prod, &p.duoprism(&p)));
+
+        let reports = reports.into_inner();
+        // One call as each rank from the edges up is started, and one every
+        // few thousand elements.
+        let elements: usize = prod.el_count_iter().skip(2).sum();
+        assert_eq!(
+            reports.len(),
+            prod.rank() - 1 + (elements - 1) / PROGRESS_INTERVAL + 1
+        );
+        assert_eq!(reports.last().unwrap().rank, prod.rank());
+        assert!(reports.windows(2).all(|pair| {
+            pair[0].rank <= pair[1].rank && pair[0].elements <= pair[1].elements
+        }));
+    }
+
+    /// Checks that a product can be cancelled, and that it stops as soon as
+    /// it is.
+    #[test]
+    fn cancel() {
+        use std::cell::Cell;
+
+        let p = Abstract::polygon(100);
+        let calls = Cell::new(0);
+
+        assert!(Abstract::product_with_progress(&p, &p, true, false, &|progress| {
+            calls.set(calls.get() + 1);
+            if progress.elements > 0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_none());
+
+        // Both the start of the edges and the first edge are reported with no
+        // elements, and the next report cancels the product.
+        assert_eq!(calls.get(), 3);
+    }
 }
@@ -0,0 +1,149 @@
+//! Keeps track of which elements of the original polytopes each element of a
+//! derived polytope comes from.
+
+use std::fmt::{self, Display};
+
+use super::{Abstract, Ranked};
+
+/// Gets the name for a single element of a given rank.
+fn element_name(rank: usize) -> String {
+    match rank {
+        0 => "minimal element".to_string(),
+        1 => "vertex".to_string(),
+        2 => "edge".to_string(),
+        3 => "face".to_string(),
+        4 => "cell".to_string(),
+        _ => format!("{}-element", rank - 1),
+    }
+}
+
+/// The element or elements of the original polytopes that an element of a
+/// derived polytope comes from. Elements are given as pairs of their rank and
+/// their index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// The element comes from a single element of the original polytope.
+    Element(usize, usize),
+
+    /// The element comes from an element of each factor of a product.
+    Pair((usize, usize), (usize, usize)),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Element(rank, idx) => write!(f, "{} {}", element_name(rank), idx),
+            Self::Pair((p_rank, p_idx), (q_rank, q_idx)) => write!(
+                f,
+                "{} {} × {} {}",
+                element_name(p_rank),
+                p_idx,
+                element_name(q_rank),
+                q_idx
+            ),
+        }
+    }
+}
+
+/// Maps every element of a derived polytope to the [`Source`] it comes from,
+/// if any. Provenance is only tracked by the `_with_provenance` variants of the
+/// operations that support it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance(Vec<Vec<Option<Source>>>);
+
+impl Provenance {
+    /// Initializes an empty provenance with a capacity for a given number of
+    /// ranks.
+    pub fn with_capacity(rank: usize) -> Self {
+        Self(Vec::with_capacity(rank))
+    }
+
+    /// Initializes a provenance for a polytope where no element has a known
+    /// source.
+    pub fn unknown(poly: &Abstract) -> Self {
+        Self(poly.el_count_iter().map(|count| vec![None; count]).collect())
+    }
+
+    /// Initializes a provenance where every element of a polytope comes from
+    /// the element with the same rank and index in the original polytope.
+    pub fn identity(poly: &Abstract) -> Self {
+        Self(
+            poly.el_count_iter()
+                .enumerate()
+                .map(|(rank, count)| (0..count).map(|idx| Some(Source::Element(rank, idx))).collect())
+                .collect(),
+        )
+    }
+
+    /// Pushes the sources of the elements of the next rank.
+    pub fn push(&mut self, sources: Vec<Option<Source>>) {
+        self.0.push(sources);
+    }
+
+    /// Returns the number of ranks stored.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether no ranks are stored.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the source of the element with a given rank and index, if it
+    /// exists and is known.
+    pub fn get(&self, rank: usize, idx: usize) -> Option<Source> {
+        *self.0.get(rank)?.get(idx)?
+    }
+
+    /// Returns a mutable reference to the source of the element with a given
+    /// rank and index.
+    ///
+    /// # Panics
+    /// Panics if the element doesn't exist.
+    pub fn get_mut(&mut self, rank: usize, idx: usize) -> &mut Option<Source> {
+        &mut self.0[rank][idx]
+    }
+
+    /// Given the provenance of a polytope derived from another one, itself
+    /// derived from an original polytope, returns the provenance of the first
+    /// polytope with respect to the original one. Pairs of sources can't be
+    /// traced back any further, and are kept as they are.
+    pub fn compose(&self, earlier: &Self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|sources| {
+                    sources
+                        .iter()
+                        .map(|source| match *source {
+                            Some(Source::Element(rank, idx)) => earlier.get(rank, idx),
+                            source => source,
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks that composing with the identity does nothing.
+    #[test]
+    fn compose_identity() {
+        let poly = Abstract::polygon(4);
+        let mut provenance = Provenance::unknown(&poly);
+        *provenance.get_mut(2, 1) = Some(Source::Element(3, 5));
+        *provenance.get_mut(1, 0) = Some(Source::Pair((1, 0), (1, 2)));
+
+        let earlier = Provenance::identity(&Abstract::polygon(6).prism());
+        assert_eq!(provenance.compose(&earlier), provenance);
+        assert_eq!(provenance.get(2, 1).unwrap().to_string(), "face 5");
+        assert_eq!(provenance.get(0, 0), None);
+        assert_eq!(provenance.get(7, 0), None);
+    }
+}
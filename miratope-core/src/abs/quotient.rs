@@ -0,0 +1,124 @@
+//! Builds the quotient of a polytope by identifying some of its vertices,
+//! like the hemicube as a quotient of the cube.
+
+use std::collections::HashMap;
+
+use super::{Abstract, AbstractBuilder, AbstractResult, Ranked, SubelementList};
+
+use partitions::partition_vec;
+use vec_like::*;
+
+/// Merges the vertices of a polytope into classes, by identifying every given
+/// pair of vertices. Returns the smallest vertex of the class of each vertex.
+///
+/// # Panics
+/// Panics if some index is out of bounds.
+pub(crate) fn vertex_classes(vertex_count: usize, identification: &[(usize, usize)]) -> Vec<usize> {
+    let mut partition = partition_vec![(); vertex_count];
+    for &(v, w) in identification {
+        partition.union(v, w);
+    }
+
+    (0..vertex_count)
+        .map(|v| partition.set(v).map(|(idx, _)| idx).min().unwrap())
+        .collect()
+}
+
+impl Abstract {
+    /// Builds the quotient of the polytope by identifying the given pairs of
+    /// vertices, and any other elements that end up with the same
+    /// subelements.
+    ///
+    /// Each vertex of the quotient is a class of identified vertices. These
+    /// are sorted by the smallest vertex of each class. Every other element is
+    /// sent to the list of the classes of its subelements, and elements that
+    /// get sent to the same list are merged. The facets are merged in the same
+    /// way, and the maximal element contains each merged facet only once.
+    ///
+    /// Returns an error if the result isn't a valid polytope, for instance if
+    /// two vertices of the same edge get identified.
+    ///
+    /// # Panics
+    /// Panics if some index is out of bounds.
+    pub fn quotient(&self, identification: &[(usize, usize)]) -> AbstractResult<Self> {
+        let rank = self.rank();
+        if rank < 2 {
+            return Ok(self.clone());
+        }
+
+        // Maps every vertex to its class.
+        let classes = vertex_classes(self.vertex_count(), identification);
+        let mut map = Vec::with_capacity(classes.len());
+        let mut vertex_count = 0;
+        for (v, &first) in classes.iter().enumerate() {
+            if first == v {
+                map.push(vertex_count);
+                vertex_count += 1;
+            } else {
+                map.push(map[first]);
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+        builder.push_vertices(vertex_count);
+
+        for r in 2..rank {
+            let mut merged = HashMap::new();
+            let mut subelements = SubelementList::new();
+            let mut new_map = Vec::with_capacity(self.el_count(r));
+
+            for el in &self[r] {
+                // Repeated subelements are kept, so that they get caught when
+                // validating the polytope.
+                let mut subs: Vec<_> = el.subs.iter().map(|&sub| map[sub]).collect();
+                subs.sort_unstable();
+
+                let idx = *merged.entry(subs.clone()).or_insert_with(|| {
+                    subelements.push(subs.into());
+                    subelements.len() - 1
+                });
+                new_map.push(idx);
+            }
+
+            builder.push(subelements);
+            map = new_map;
+        }
+
+        builder.push_max();
+        builder.ranks().is_valid()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Ok(unsafe { builder.build() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::AbstractError, test, Polytope};
+
+    /// Identifying no vertices leaves the polytope unchanged.
+    #[test]
+    fn trivial() {
+        test(&Abstract::cube().quotient(&[]).unwrap(), [1, 8, 12, 6, 1]);
+    }
+
+    /// Identifying opposite vertices of a hexagon gives a triangle.
+    #[test]
+    fn hexagon() {
+        let hexagon = Abstract::polygon(6);
+        test(&hexagon.quotient(&[(0, 3), (1, 4), (2, 5)]).unwrap(), [1, 3, 3, 1]);
+    }
+
+    /// Identifying the vertices of an edge isn't valid.
+    #[test]
+    fn invalid() {
+        let square = Abstract::polygon(4);
+        match square.quotient(&[(0, 1)]) {
+            Err(AbstractError::DuplicateSub { rank: 2, .. }) => {}
+            Err(err) => panic!("unexpected error: {}", err),
+            Ok(_) => panic!("identifying an edge should fail"),
+        }
+    }
+}
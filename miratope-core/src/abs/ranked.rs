@@ -41,7 +41,10 @@ impl<T> IndexMut<(usize, usize)> for ElementMap<T> {
 /// as its index in the [`ElementList`] of the previous rank. This is used as
 /// one of the fields in an [`Element`].
 ///
-/// Internally, this is just a wrapper around a `Vec<usize>`.
+/// Internally, this is just a wrapper around a `Vec<usize>`. However, the
+/// subelements of any element, faces included, form a set: their order carries
+/// no meaning, and no index may appear twice. The cyclic order of the vertices
+/// of a polygon is recovered from its edges whenever it's needed.
 ///
 /// # Note on notation
 /// Throughout the code, and unless specified otherwise, we use the word
@@ -127,7 +130,9 @@ impl Element {
         std::mem::swap(&mut self.subs.0, &mut self.sups.0)
     }
 
-    /// Sorts both the subelements and superelements by index.
+    /// Sorts both the subelements and superelements by index. As these form
+    /// sets, this is their canonical order. Duplicate indices are kept, so that
+    /// [`Ranks::is_valid`] can still report them.
     pub fn sort(&mut self) {
         self.subs.sort_unstable();
         self.sups.sort_unstable();
@@ -194,6 +199,47 @@ impl SubelementList {
     }
 }
 
+/// The number of elements of every rank of a structure, as returned by
+/// [`Ranked::el_counts`].
+///
+/// It's displayed as a tuple of counts, or with the alternate flag, with every
+/// count labeled by its rank. As everywhere else, the minimal element has rank
+/// 0.
+///
+/// ```
+/// use miratope_core::{abs::{Abstract, Ranked}, Polytope};
+///
+/// let counts = Abstract::cube().el_counts();
+/// assert_eq!(counts.to_string(), "(1, 8, 12, 6, 1)");
+/// assert_eq!(format!("{:#}", counts), "(0: 1, 1: 8, 2: 12, 3: 6, 4: 1)");
+/// ```
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ElementCounts(pub Vec<usize>);
+
+impl std::fmt::Display for ElementCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (r, count) in self.0.iter().enumerate() {
+            if r != 0 {
+                write!(f, ", ")?;
+            }
+
+            if f.alternate() {
+                write!(f, "{}: {}", r, count)?;
+            } else {
+                write!(f, "{}", count)?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+impl FromIterator<usize> for ElementCounts {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /// Represents the lowest and highest element of a section of an abstract
 /// polytope. Not to be confused with a cross-section.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -258,13 +304,6 @@ impl Section {
     }
 }
 
-/// The signature of the function that turns an `&ElementList` into an iterator.
-type IterFn = for<'r> fn(&'r ElementList) -> slice::Iter<'r, Element>;
-
-/// The signature of the function that turns an `&mut ElementList` into a
-/// mutable iterator.
-type IterMutFn = for<'r> fn(&'r mut ElementList) -> slice::IterMut<'r, Element>;
-
 /// The signature of the function that turns an `ElementList` into an owned
 /// iterator.
 type IntoIterFn = fn(ElementList) -> std::vec::IntoIter<Element>;
@@ -272,11 +311,156 @@ type IntoIterFn = fn(ElementList) -> std::vec::IntoIter<Element>;
 /// The signature of the function that returns the length of an `ElementList`.
 type LenFn = for<'r> fn(&'r ElementList) -> usize;
 
-/// The signature of an iterator over an `&'a ElementList`.
-pub type ElementIter<'a> = iter::Flatten<iter::Map<slice::Iter<'a, ElementList>, IterFn>>;
+/// An iterator over the elements of every rank, from the minimal element up,
+/// which yields each of them together with its rank and index. Returned by
+/// [`Ranked::element_iter`].
+///
+/// ```
+/// use miratope_core::{abs::{Abstract, Ranked}, Polytope};
+///
+/// let square = Abstract::polygon(4);
+/// let mut iter = square.element_iter();
+/// assert_eq!(iter.len(), 10);
+///
+/// assert_eq!(iter.next().map(|(r, idx, _)| (r, idx)), Some((0, 0)));
+/// assert_eq!(iter.next_back().map(|(r, idx, _)| (r, idx)), Some((3, 0)));
+/// assert_eq!(iter.next_back().map(|(r, idx, _)| (r, idx)), Some((2, 3)));
+/// assert_eq!(iter.len(), 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ElementIter<'a> {
+    /// The element lists that haven't been started yet, with their ranks.
+    lists: iter::Enumerate<slice::Iter<'a, ElementList>>,
+
+    /// The rank being iterated over from the front, and its elements left.
+    front: Option<(usize, iter::Enumerate<slice::Iter<'a, Element>>)>,
+
+    /// The rank being iterated over from the back, and its elements left.
+    back: Option<(usize, iter::Enumerate<slice::Iter<'a, Element>>)>,
+
+    /// The number of elements left.
+    len: usize,
+}
+
+/// A mutable iterator over the elements of every rank, from the minimal
+/// element up, which yields each of them together with its rank and index.
+/// Returned by [`Ranks::element_iter_mut`].
+#[derive(Debug)]
+pub struct ElementIterMut<'a> {
+    /// The element lists that haven't been started yet, with their ranks.
+    lists: iter::Enumerate<slice::IterMut<'a, ElementList>>,
+
+    /// The rank being iterated over from the front, and its elements left.
+    front: Option<(usize, iter::Enumerate<slice::IterMut<'a, Element>>)>,
+
+    /// The rank being iterated over from the back, and its elements left.
+    back: Option<(usize, iter::Enumerate<slice::IterMut<'a, Element>>)>,
+
+    /// The number of elements left.
+    len: usize,
+}
+
+/// Implements the iterator traits for [`ElementIter`] and [`ElementIterMut`].
+/// These work like a flattened iterator over the element lists, which also
+/// keeps track of the rank of each list and of the total number of elements.
+macro_rules! impl_element_iter {
+    ($name:ident, $lists_iter:ident, $iter:ident $(, $mut:tt)?) => {
+        impl<'a> $name<'a> {
+            /// Initializes an iterator over some element lists with a given
+            /// total number of elements.
+            fn new(lists: slice::$lists_iter<'a, ElementList>, len: usize) -> Self {
+                Self {
+                    lists: lists.enumerate(),
+                    front: None,
+                    back: None,
+                    len,
+                }
+            }
+        }
+
+        impl<'a> Iterator for $name<'a> {
+            type Item = (usize, usize, &'a $($mut)? Element);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                loop {
+                    if let Some((rank, els)) = &mut self.front {
+                        if let Some((idx, el)) = els.next() {
+                            self.len -= 1;
+                            return Some((*rank, idx, el));
+                        }
+                    }
+
+                    match self.lists.next() {
+                        Some((rank, els)) => self.front = Some((rank, els.$iter().enumerate())),
+
+                        // Only the rank started from the back is left.
+                        None => {
+                            let (rank, els) = self.back.as_mut()?;
+                            let (idx, el) = els.next()?;
+                            self.len -= 1;
+                            return Some((*rank, idx, el));
+                        }
+                    }
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $name<'a> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                loop {
+                    if let Some((rank, els)) = &mut self.back {
+                        if let Some((idx, el)) = els.next_back() {
+                            self.len -= 1;
+                            return Some((*rank, idx, el));
+                        }
+                    }
+
+                    match self.lists.next_back() {
+                        Some((rank, els)) => self.back = Some((rank, els.$iter().enumerate())),
+
+                        // Only the rank started from the front is left.
+                        None => {
+                            let (rank, els) = self.front.as_mut()?;
+                            let (idx, el) = els.next_back()?;
+                            self.len -= 1;
+                            return Some((*rank, idx, el));
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<'a> ExactSizeIterator for $name<'a> {}
+    };
+}
+
+impl_element_iter!(ElementIter, Iter, iter);
+impl_element_iter!(ElementIterMut, IterMut, iter_mut, mut);
+
+/// A reference to an element of a polytope, bundled with its rank and index,
+/// so that it can be passed around on its own. Returned by
+/// [`Ranked::element_ref`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElementRef<'a> {
+    /// The rank of the element.
+    pub rank: usize,
 
-/// The signature of an iterator over an `&'a mut ElementList`.
-pub type ElementIterMut<'a> = iter::Flatten<iter::Map<slice::IterMut<'a, ElementList>, IterMutFn>>;
+    /// The index of the element in its rank.
+    pub idx: usize,
+
+    /// The element itself.
+    pub el: &'a Element,
+}
+
+impl<'a> From<(usize, usize, &'a Element)> for ElementRef<'a> {
+    fn from((rank, idx, el): (usize, usize, &'a Element)) -> Self {
+        Self { rank, idx, el }
+    }
+}
 
 /// The signature of an owned iterator over an `ElementList`.
 pub type ElementIntoIter = iter::Flatten<iter::Map<vec::IntoIter<ElementList>, IntoIterFn>>;
@@ -341,7 +525,7 @@ pub trait Ranked:
     /// Returns the number of elements of a given rank. Returns 0 if the rank is
     /// out of bounds.
     fn el_count(&self, rank: usize) -> usize {
-        self.ranks().get(rank).map(ElementList::len).unwrap_or(0)
+        self.rank_iter(rank).len()
     }
 
     /// Returns an iterator over the element counts of the structure.
@@ -349,12 +533,24 @@ pub trait Ranked:
         self.ranks().iter().map(ElementList::len as LenFn)
     }
 
+    /// Returns the element counts of the structure, which can be displayed.
+    fn el_counts(&self) -> ElementCounts {
+        self.el_count_iter().collect()
+    }
+
     /// Returns a reference to an element of the polytope. To actually get the
     /// entire polytope it defines, use [`Polytope::element`](crate::Polytope::element).
     fn get_element(&self, rank: usize, idx: usize) -> Option<&Element> {
         self.ranks().get(rank)?.get(idx)
     }
 
+    /// Returns a reference to an element of the polytope, bundled with its
+    /// rank and index.
+    fn element_ref(&self, rank: usize, idx: usize) -> Option<ElementRef<'_>> {
+        let el = self.get_element(rank, idx)?;
+        Some(ElementRef { rank, idx, el })
+    }
+
     /// Gets a reference to the element list of a given rank.
     fn get_element_list(&self, rank: usize) -> Option<&ElementList> {
         self.ranks().get(rank)
@@ -398,17 +594,54 @@ pub trait Ranked:
         self.el_count(2)
     }
 
+    /// Returns the number of faces.
+    fn face_count(&self) -> usize {
+        self.el_count(3)
+    }
+
     /// Returns the number of facets.
     fn facet_count(&self) -> usize {
         self.el_count(self.rank().wrapping_sub(1))
     }
 
-    /// Returns an iterator over the elements.
-    fn element_iter(&self) -> ElementIter<'_> {
+    /// Returns the number of ridges, the elements right below the facets.
+    fn ridge_count(&self) -> usize {
+        self.el_count(self.rank().wrapping_sub(2))
+    }
+
+    /// Returns an iterator over the elements of a given rank together with
+    /// their indices, which is empty if the rank is out of bounds.
+    fn rank_iter(&self, rank: usize) -> iter::Enumerate<slice::Iter<'_, Element>> {
         self.ranks()
+            .get(rank)
+            .map_or(&[][..], ElementList::as_slice)
             .iter()
-            .map(ElementList::iter as IterFn)
-            .flatten()
+            .enumerate()
+    }
+
+    /// Returns an iterator over the facets together with their indices.
+    fn facets(&self) -> iter::Enumerate<slice::Iter<'_, Element>> {
+        self.rank_iter(self.rank().wrapping_sub(1))
+    }
+
+    /// Returns an iterator over the ridges together with their indices.
+    fn ridges(&self) -> iter::Enumerate<slice::Iter<'_, Element>> {
+        self.rank_iter(self.rank().wrapping_sub(2))
+    }
+
+    /// Returns an iterator over the elements of every rank, from the minimal
+    /// element up, together with their ranks and indices.
+    ///
+    /// ```
+    /// use miratope_core::{abs::{Abstract, Ranked}, Polytope};
+    ///
+    /// let tesseract = Abstract::hypercube(5);
+    /// let proper = tesseract.element_iter().filter(|&(r, _, _)| r != 0 && r != 5);
+    /// assert_eq!(proper.count(), 16 + 32 + 24 + 8);
+    /// ```
+    fn element_iter(&self) -> ElementIter<'_> {
+        let ranks = self.ranks();
+        ElementIter::new(ranks.as_slice().iter(), ranks.iter().map(ElementList::len).sum())
     }
 
     /// Returns an owned iterator over the elements.
@@ -459,11 +692,20 @@ impl Ranks {
         self.get_mut(rank)?.get_mut(idx)
     }
 
-    /// Returns a mutable iterator over the elements.
+    /// Returns a mutable iterator over the elements of a given rank together
+    /// with their indices, which is empty if the rank is out of bounds.
+    pub fn rank_iter_mut(&mut self, rank: usize) -> iter::Enumerate<slice::IterMut<'_, Element>> {
+        self.get_mut(rank)
+            .map_or(&mut [][..], ElementList::as_mut_slice)
+            .iter_mut()
+            .enumerate()
+    }
+
+    /// Returns a mutable iterator over the elements of every rank, from the
+    /// minimal element up, together with their ranks and indices.
     pub fn element_iter_mut(&mut self) -> ElementIterMut<'_> {
-        self.iter_mut()
-            .map(ElementList::iter_mut as IterMutFn)
-            .flatten()
+        let len = self.iter().map(ElementList::len).sum();
+        ElementIterMut::new(self.as_mut_slice().iter_mut(), len)
     }
 
     /// Applies a function to all elements in parallel.
@@ -682,7 +924,7 @@ impl ElementHash {
 
     /// Gets the indices of the elements of a given rank in the original
     /// polytope.
-    fn to_elements(&self, rank: usize) -> Vec<usize> {
+    pub fn to_elements(&self, rank: usize) -> Vec<usize> {
         if let Some(elements) = self.get(rank) {
             let mut new_elements = Vec::new();
             new_elements.resize(elements.len(), 0);
@@ -0,0 +1,329 @@
+//! Builds abstract regular polytopes from their automorphism groups, given as
+//! string C-groups. These are groups generated by involutions `ρ₀, …, ρₙ₋₁`,
+//! where non-adjacent generators commute, which satisfy the intersection
+//! property. This gives access to polytopes like the 11-cell and the 57-cell,
+//! which have no concrete realization.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{Abstract, AbstractBuilder, AbstractError, SubelementList};
+use crate::{group::permutation::DPermutation, Polytope};
+
+use vec_like::VecLike;
+
+/// The largest group that we attempt to enumerate.
+pub const MAX_GROUP_ORDER: usize = 1 << 20;
+
+/// An error while building a polytope from the generators of its group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SggiError {
+    /// The generator with a given index doesn't act on as many elements as
+    /// the first one.
+    LengthMismatch(usize),
+
+    /// The generator with a given index isn't an involution.
+    NotInvolution(usize),
+
+    /// Two generators that aren't adjacent don't commute.
+    NotString(usize, usize),
+
+    /// The group has more than [`MAX_GROUP_ORDER`] elements.
+    TooLarge,
+
+    /// The intersection of the subgroups generated by `ρ_first, …, ρ_(last-1)`
+    /// and by `ρ_(first+1), …, ρ_last` is larger than the subgroup generated
+    /// by the generators they share.
+    Intersection {
+        /// The index of the first generator.
+        first: usize,
+
+        /// The index of the last generator.
+        last: usize,
+    },
+
+    /// The result isn't a valid polytope.
+    Abstract(AbstractError),
+}
+
+impl From<AbstractError> for SggiError {
+    fn from(err: AbstractError) -> Self {
+        Self::Abstract(err)
+    }
+}
+
+impl Display for SggiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch(idx) => write!(
+                f,
+                "generator {} doesn't act on as many elements as the first",
+                idx
+            ),
+            Self::NotInvolution(idx) => write!(f, "generator {} isn't an involution", idx),
+            Self::NotString(i, j) => write!(f, "generators {} and {} don't commute", i, j),
+            Self::TooLarge => write!(f, "the group has more than {} elements", MAX_GROUP_ORDER),
+            Self::Intersection { first, last } => write!(
+                f,
+                "the generators from {} to {} fail the intersection property",
+                first, last
+            ),
+            Self::Abstract(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SggiError {}
+
+/// The Cayley graph of a group with respect to its generators.
+struct Cayley {
+    /// For every element of the group, the indices of its products with each
+    /// generator on the right. The identity has index 0.
+    table: Vec<Vec<usize>>,
+}
+
+impl Cayley {
+    /// Enumerates the group generated by some permutations.
+    fn new(gens: &[DPermutation]) -> Result<Self, SggiError> {
+        let id = DPermutation::id(gens[0].len());
+        let mut elements = vec![id.clone()];
+        let mut indices = BTreeMap::new();
+        indices.insert(id, 0);
+        let mut table = Vec::new();
+
+        while let Some(el) = elements.get(table.len()) {
+            let mut row = Vec::with_capacity(gens.len());
+            let mut new = Vec::new();
+
+            for gen in gens {
+                let len = indices.len();
+                let idx = *indices.entry(el * gen).or_insert_with_key(|product| {
+                    new.push(product.clone());
+                    len
+                });
+                row.push(idx);
+            }
+
+            elements.append(&mut new);
+            if elements.len() > MAX_GROUP_ORDER {
+                return Err(SggiError::TooLarge);
+            }
+            table.push(row);
+        }
+
+        Ok(Self { table })
+    }
+
+    /// Returns the order of the group.
+    fn order(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Splits the group into the left cosets of the subgroup generated by the
+    /// generators whose indices satisfy a predicate. Returns the index of the coset of each
+    /// element, together with the number of cosets. The subgroup itself has
+    /// index 0.
+    fn cosets<F: Fn(usize) -> bool>(&self, gens: F) -> (Vec<usize>, usize) {
+        let mut labels = vec![usize::MAX; self.order()];
+        let mut count = 0;
+        let mut stack = Vec::new();
+
+        for start in 0..self.order() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+
+            // Since the generators are involutions, the edges of the graph go
+            // both ways.
+            labels[start] = count;
+            stack.push(start);
+            while let Some(el) = stack.pop() {
+                for (i, &next) in self.table[el].iter().enumerate() {
+                    if gens(i) && labels[next] == usize::MAX {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        (labels, count)
+    }
+
+    /// Returns the order of the subgroup generated by the generators whose
+    /// indices satisfy a predicate.
+    fn subgroup_order<F: Fn(usize) -> bool>(&self, gens: F) -> usize {
+        self.cosets(gens)
+            .0
+            .iter()
+            .filter(|&&label| label == 0)
+            .count()
+    }
+}
+
+impl Abstract {
+    /// Builds the abstract regular polytope whose automorphism group is the
+    /// string C-group generated by the given involutions. These are usually
+    /// given as permutations of the flags of the polytope, but they can act on
+    /// any set, as long as they generate the correct group.
+    ///
+    /// The flags of the polytope are the elements of the group. Its elements
+    /// of rank `i` are the left cosets of the subgroup generated by every
+    /// generator but `ρᵢ`, and two elements are incident whenever their cosets
+    /// intersect.
+    ///
+    /// Returns an error if the generators don't act on the same number of
+    /// elements, aren't involutions, don't commute when they aren't adjacent,
+    /// or don't satisfy the intersection property.
+    pub fn from_sggi(generators: &[DPermutation]) -> Result<Self, SggiError> {
+        let rank = generators.len();
+        if rank == 0 {
+            return Ok(Self::point());
+        }
+
+        let len = generators[0].len();
+        let id = DPermutation::id(len);
+        for (i, gen) in generators.iter().enumerate() {
+            if gen.len() != len {
+                return Err(SggiError::LengthMismatch(i));
+            }
+            if *gen == id || gen * gen != id {
+                return Err(SggiError::NotInvolution(i));
+            }
+        }
+
+        for (i, gen_i) in generators.iter().enumerate() {
+            for (j, gen_j) in generators.iter().enumerate().skip(i + 2) {
+                if gen_i * gen_j != gen_j * gen_i {
+                    return Err(SggiError::NotString(i, j));
+                }
+            }
+        }
+
+        // A string group generated by involutions satisfies the intersection
+        // property whenever each interval of generators does with respect to
+        // its two largest subintervals.
+        let cayley = Cayley::new(generators)?;
+        for first in 0..rank {
+            for last in first + 1..rank {
+                let shared = cayley.subgroup_order(|i| first < i && i < last);
+                let lower = cayley.cosets(|i| first <= i && i < last).0;
+                let upper = cayley.cosets(|i| first < i && i <= last).0;
+                let intersection = lower
+                    .iter()
+                    .zip(&upper)
+                    .filter(|&(&l, &u)| l == 0 && u == 0)
+                    .count();
+
+                if intersection != shared {
+                    return Err(SggiError::Intersection { first, last });
+                }
+            }
+        }
+
+        let cosets: Vec<_> = (0..rank).map(|r| cayley.cosets(|i| i != r)).collect();
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(cosets[0].1);
+
+        for r in 1..rank {
+            let (labels, count) = &cosets[r];
+            let lower = &cosets[r - 1].0;
+            let mut subs = vec![Vec::new(); *count];
+            for (el, &label) in labels.iter().enumerate() {
+                subs[label].push(lower[el]);
+            }
+
+            let mut subelements = SubelementList::with_capacity(*count);
+            for mut subs in subs {
+                subs.sort_unstable();
+                subs.dedup();
+                subelements.push(subs.into());
+            }
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+        builder.ranks().is_valid()?;
+
+        // Safety: we just checked that the polytope is valid.
+        Ok(unsafe { builder.build() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::Concrete, test};
+
+    /// Parses some generators in cycle notation.
+    fn generators(cycles: &[&str], len: usize) -> Vec<DPermutation> {
+        cycles
+            .iter()
+            .map(|cycles| DPermutation::from_cycles(cycles, len).unwrap())
+            .collect()
+    }
+
+    /// The symmetric group on 4 elements is the group of the tetrahedron.
+    #[test]
+    fn tetrahedron() {
+        let gens = generators(&["(0 1)", "(1 2)", "(2 3)"], 4);
+        let tet = Abstract::from_sggi(&gens).unwrap();
+        test(&tet, [1, 4, 6, 4, 1]);
+        assert!(tet.is_isomorphic(&Abstract::simplex(4)));
+    }
+
+    /// Low ranks, and a polygon given by the symmetries of its vertices.
+    #[test]
+    fn polygons() {
+        test(&Abstract::from_sggi(&[]).unwrap(), [1, 1]);
+        test(
+            &Abstract::from_sggi(&generators(&["(0 1)"], 2)).unwrap(),
+            [1, 2, 1],
+        );
+
+        let pentagon = Abstract::from_sggi(&generators(&["(1 4)(2 3)", "(0 1)(2 4)"], 5));
+        test(&pentagon.unwrap(), [1, 5, 5, 1]);
+    }
+
+    /// The hemicube, as a quotient of the symmetric group on 4 elements.
+    #[test]
+    fn hemicube() {
+        let gens = generators(&["(0 1)(2 3)", "(1 2)", "(2 3)"], 4);
+        let hemicube = Abstract::from_sggi(&gens).unwrap();
+        test(&hemicube, [1, 4, 6, 3, 1]);
+        let quotient = Concrete::cube().antipodal_quotient().unwrap();
+        assert!(hemicube.is_isomorphic(&quotient.abs));
+    }
+
+    /// Invalid generators give the corresponding errors.
+    #[test]
+    fn errors() {
+        let err = |cycles: &[&str], len| Abstract::from_sggi(&generators(cycles, len)).unwrap_err();
+
+        assert_eq!(err(&["(0 1 2)", "(0 1)"], 3), SggiError::NotInvolution(0));
+        assert_eq!(err(&["(0 1)", ""], 3), SggiError::NotInvolution(1));
+        assert_eq!(
+            err(&["(0 1)", "(1 2)", "(0 2)"], 3),
+            SggiError::NotString(0, 2)
+        );
+        assert_eq!(
+            err(&["(0 1)", "(0 1)"], 2),
+            SggiError::Intersection { first: 0, last: 1 }
+        );
+        assert_eq!(
+            err(&["(0 1)(2 3)", "(0 2)(1 3)", "(0 1)(2 3)"], 4),
+            SggiError::Intersection { first: 0, last: 2 }
+        );
+
+        let gens = vec![
+            DPermutation::from_cycles("(0 1)", 2).unwrap(),
+            DPermutation::from_cycles("(0 1)", 3).unwrap(),
+        ];
+        assert_eq!(
+            Abstract::from_sggi(&gens).unwrap_err(),
+            SggiError::LengthMismatch(1)
+        );
+    }
+}
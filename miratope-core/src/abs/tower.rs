@@ -0,0 +1,113 @@
+//! Builds a tower of copies of a polytope, stacked along a new dimension, like
+//! a prism with intermediate layers.
+
+use super::{Abstract, AbstractBuilder, Ranked, SubelementList, Subelements};
+
+use vec_like::*;
+
+impl Abstract {
+    /// Builds a tower of a given number of layers of the polytope. Consecutive
+    /// layers are joined by the prism over each of the polytope's elements.
+    ///
+    /// Each layer shares its elements with the layers directly above and below
+    /// it, so the intermediate layers aren't facets. A tower of two layers is
+    /// combinatorially a prism, and a tower of `k` squares has `4k` vertices.
+    ///
+    /// The elements of each rank are ordered as follows: first the copies of
+    /// the elements in each layer, then the lateral elements between each pair
+    /// of consecutive layers. In the facets, the copies are replaced by the
+    /// bottom and top layers.
+    ///
+    /// # Panics
+    /// Panics if the polytope has rank less than 2, or if there are less than
+    /// two layers.
+    pub fn tower(&self, layers: usize) -> Self {
+        let rank = self.rank();
+        assert!(rank >= 2, "only polytopes of rank 2 or more have towers");
+        assert!(layers >= 2, "a tower needs at least two layers");
+
+        // The index of the copy of an element in a layer, for ranks below the
+        // facets.
+        let layer = |r: usize, j: usize, e: usize| j * self.el_count(r) + e;
+
+        // The index of the lateral element between layers `j` and `j + 1`
+        // built over an element of rank `r - 1`, for ranks below the facets.
+        let lateral =
+            |r: usize, j: usize, e: usize| layers * self.el_count(r) + j * self.el_count(r - 1) + e;
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(layers * self.vertex_count());
+
+        for r in 2..=rank {
+            let mut subelements = SubelementList::new();
+
+            if r == rank {
+                // The bottom and top layers.
+                for j in [0, layers - 1] {
+                    subelements.push(
+                        (0..self.facet_count())
+                            .map(|f| layer(r - 1, j, f))
+                            .collect(),
+                    );
+                }
+            } else {
+                for j in 0..layers {
+                    for el in &self[r] {
+                        subelements.push(el.subs.iter().map(|&s| layer(r - 1, j, s)).collect());
+                    }
+                }
+            }
+
+            // The lateral elements.
+            for j in 0..layers - 1 {
+                for (e, el) in self[r - 1].iter().enumerate() {
+                    let mut subs = Subelements::with_capacity(el.subs.len() + 2);
+                    subs.push(layer(r - 1, j, e));
+                    subs.push(layer(r - 1, j + 1, e));
+
+                    if r > 2 {
+                        subs.extend(el.subs.iter().map(|&s| lateral(r - 1, j, s)));
+                    }
+
+                    subelements.push(subs);
+                }
+            }
+
+            builder.push(subelements);
+        }
+
+        builder.push_max();
+
+        // Safety: each layer is a copy of a valid polytope, and the elements
+        // between them are prisms over its elements.
+        unsafe { builder.build() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// A tower of two layers is a prism.
+    #[test]
+    fn prism() {
+        let square = Abstract::polygon(4);
+        let tower = square.tower(2);
+        test(&tower, [1, 8, 12, 6, 1]);
+        assert!(tower.is_isomorphic(&square.prism()));
+    }
+
+    /// Checks a tower of three squares.
+    #[test]
+    fn square() {
+        test(&Abstract::polygon(4).tower(3), [1, 12, 20, 10, 1]);
+    }
+
+    /// Checks a tower of dyads, which is a hexagon for three layers.
+    #[test]
+    fn dyad() {
+        test(&Abstract::dyad().tower(3), [1, 6, 6, 1]);
+    }
+}
@@ -3,17 +3,19 @@
 
 // TODO: finish these!
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 use vec_like::VecLike;
 
-use super::{Ranked, Ranks, Section};
+use super::{Abstract, Ranked, Ranks, Section};
+use crate::Polytope;
 
 /// Represents the way in which two elements with one rank of difference are
 /// incident to one another. Used as a field in some [`AbstractError`] variants.
 
-#[derive(Clone, Copy, Debug, Display)]
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
 pub enum IncidenceType {
     /// This element is a subelement of another.
     #[strum(serialize = "subelement")]
@@ -25,7 +27,7 @@ pub enum IncidenceType {
 }
 
 /// Represents an error in an abstract polytope.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AbstractError {
     /// The polytope is not bounded, i.e. it doesn't have a single minimal and
     /// maximal element.
@@ -63,6 +65,18 @@ pub enum AbstractError {
         index: usize,
     },
 
+    /// Some element lists the same subelement more than once.
+    DuplicateSub {
+        /// The rank of the element at fault.
+        rank: usize,
+
+        /// The index of the element at fault.
+        index: usize,
+
+        /// The repeated subelement.
+        sub: usize,
+    },
+
     /// The polytope is not ranked, i.e. some element that's not minimal or not
     /// maximal lacks a subelement or superelement, respectively.
     Ranked {
@@ -86,6 +100,13 @@ pub enum AbstractError {
     /// The polytope is not strictly connected, i.e. some section's flags don't
     /// form a connected graph under flag changes.
     Connected(Section),
+
+    /// The polytope is fissary, i.e. some proper element or its element figure
+    /// is a compound.
+    Fissary {
+        /// The coordinates of the element at fault.
+        el: (usize, usize),
+    },
 }
 
 impl std::fmt::Display for AbstractError {
@@ -122,6 +143,14 @@ impl std::fmt::Display for AbstractError {
                 el, incidence_type, index
             ),
 
+            // Some element has a repeated subelement.
+            AbstractError::DuplicateSub { rank, index, sub } => write!(
+                f,
+                "Polytope has a duplicate subelement: {:?} lists the subelement {} more than once",
+                (rank, index),
+                sub
+            ),
+
             // The polytope is not ranked.
             AbstractError::Ranked { el, incidence_type } => write!(
                 f,
@@ -143,6 +172,13 @@ impl std::fmt::Display for AbstractError {
                 "Polytope is not strictly connected: {} is not connected",
                 section
             ),
+
+            // The polytope is fissary.
+            AbstractError::Fissary { el } => write!(
+                f,
+                "Polytope is fissary: {:?} or its element figure is a compound",
+                el
+            ),
         }
     }
 }
@@ -152,11 +188,42 @@ impl std::error::Error for AbstractError {}
 /// The return value for [`Ranks::is_valid`].
 pub type AbstractResult<T> = Result<T, AbstractError>;
 
+/// How thoroughly [`Ranks::validate`] and [`Abstract::validate`] check a
+/// polytope.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationLevel {
+    /// Nothing is checked.
+    Off,
+
+    /// Only checks that the polytope is bounded and that every index refers
+    /// to an existing element. This takes linear time and no extra memory.
+    #[default]
+    Cheap,
+
+    /// Also checks that incidences are consistent, that the polytope is
+    /// dyadic, and that its elements and element figures are connected.
+    Full,
+}
+
 impl Ranks {
+    /// Checks the ranks up to a given [`ValidationLevel`]. The full check is
+    /// the same as [`Self::is_valid`].
+    pub fn validate(&self, level: ValidationLevel) -> AbstractResult<()> {
+        match level {
+            ValidationLevel::Off => Ok(()),
+            ValidationLevel::Cheap => {
+                self.bounded()?;
+                self.check_indices()
+            }
+            ValidationLevel::Full => self.is_valid(),
+        }
+    }
+
     /// Checks whether the ranks form a valid polytope, i.e. whether the poset
     /// is bounded, dyadic, and all of its indices refer to valid elements.
     pub fn is_valid(&self) -> AbstractResult<()> {
         self.bounded()?;
+        self.check_duplicates()?;
         self.check_incidences()?;
         self.is_dyadic()?;
 
@@ -181,78 +248,142 @@ impl Ranks {
         }
     }
 
+    /// Checks that every subelement and superelement refers to an existing
+    /// element, without checking that they match up.
+    pub fn check_indices(&self) -> AbstractResult<()> {
+        for (r, idx, el) in self.element_iter() {
+            let sub_count = r.checked_sub(1).map_or(0, |r| self[r].len());
+            if let Some(&sub) = el.subs.iter().find(|&&sub| sub >= sub_count) {
+                return Err(AbstractError::Index {
+                    el: (r, idx),
+                    incidence_type: IncidenceType::Subelement,
+                    index: sub,
+                });
+            }
+
+            let sup_count = self.get(r + 1).map_or(0, |elements| elements.len());
+            if let Some(&sup) = el.sups.iter().find(|&&sup| sup >= sup_count) {
+                return Err(AbstractError::Index {
+                    el: (r, idx),
+                    incidence_type: IncidenceType::Superelement,
+                    index: sup,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no element lists the same subelement twice. Since
+    /// subelements form a set, a repeated index always indicates a malformed
+    /// polytope, and would make other checks miscount.
+    pub fn check_duplicates(&self) -> AbstractResult<()> {
+        for (rank, elements) in self.iter().enumerate() {
+            for (index, el) in elements.iter().enumerate() {
+                let mut subs = HashSet::new();
+
+                for &sub in &el.subs {
+                    if !subs.insert(sub) {
+                        return Err(AbstractError::DuplicateSub { rank, index, sub });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Checks whether subelements and superelements match up, and whether they
     /// all refer to valid elements in the polytope. If this returns `false`,
     /// then either the polytope hasn't fully built up, or there's something
     /// seriously wrong.
     pub fn check_incidences(&self) -> AbstractResult<()> {
-        for (r, elements) in self.iter().enumerate() {
-            for (idx, el) in elements.iter().enumerate() {
-                // Only the minimal element can have no subelements.
-                if r != 0 && el.subs.is_empty() {
-                    return Err(AbstractError::Ranked {
-                        el: (r, idx),
-                        incidence_type: IncidenceType::Subelement,
-                    });
-                }
-
-                // Iterates over the element's subelements.
-                for &sub in &el.subs {
-                    // Attempts to get the subelement's superelements.
-                    if r >= 1 {
-                        if let Some(sub_el) = self.get_element(r - 1, sub) {
-                            if sub_el.sups.contains(&idx) {
-                                continue;
-                            } else {
-                                // The element contains a subelement, but not viceversa.
-                                return Err(AbstractError::Consistency {
-                                    el: (r, idx),
-                                    index: sub,
-                                    incidence_type: IncidenceType::Subelement,
-                                });
-                            }
+        // The pairs of incident elements listed by the subelements and by the
+        // superelements of each rank, as the index of the lower element and
+        // that of the upper one. These are looked up instead of searching the
+        // lists of other elements, which would take quadratic time on huge
+        // elements.
+        let incidences = |sups: bool| -> Vec<HashSet<(usize, usize)>> {
+            self.iter()
+                .map(|elements| {
+                    let mut pairs = HashSet::new();
+                    for (idx, el) in elements.iter().enumerate() {
+                        if sups {
+                            pairs.extend(el.sups.iter().map(|&sup| (idx, sup)));
+                        } else {
+                            pairs.extend(el.subs.iter().map(|&sub| (sub, idx)));
                         }
                     }
+                    pairs
+                })
+                .collect()
+        };
+        let from_subs = incidences(false);
+        let from_sups = incidences(true);
+
+        for (r, idx, el) in self.element_iter() {
+            // Only the minimal element can have no subelements.
+            if r != 0 && el.subs.is_empty() {
+                return Err(AbstractError::Ranked {
+                    el: (r, idx),
+                    incidence_type: IncidenceType::Subelement,
+                });
+            }
 
-                    // We got ourselves an invalid index.
-                    return Err(AbstractError::Index {
-                        el: (r, idx),
-                        index: sub,
-                        incidence_type: IncidenceType::Subelement,
-                    });
+            // Iterates over the element's subelements.
+            for &sub in &el.subs {
+                // Attempts to get the subelement's superelements.
+                if r >= 1 && self.get_element(r - 1, sub).is_some() {
+                    if from_sups[r - 1].contains(&(sub, idx)) {
+                        continue;
+                    } else {
+                        // The element contains a subelement, but not viceversa.
+                        return Err(AbstractError::Consistency {
+                            el: (r, idx),
+                            index: sub,
+                            incidence_type: IncidenceType::Subelement,
+                        });
+                    }
                 }
 
-                // Only the maximal element can have no superelements.
-                if r != self.rank() && el.sups.is_empty() {
-                    return Err(AbstractError::Ranked {
-                        el: (r, idx),
-                        incidence_type: IncidenceType::Superelement,
-                    });
-                }
+                // We got ourselves an invalid index.
+                return Err(AbstractError::Index {
+                    el: (r, idx),
+                    index: sub,
+                    incidence_type: IncidenceType::Subelement,
+                });
+            }
 
-                // Iterates over the element's superelements.
-                for &sup in &el.sups {
-                    // Attempts to get the subelement's superelements.
-                    if let Some(sub_el) = self.get_element(r + 1, sup) {
-                        if sub_el.subs.contains(&idx) {
-                            continue;
-                        } else {
-                            // The element contains a superelement, but not viceversa.
-                            return Err(AbstractError::Consistency {
-                                el: (r, idx),
-                                index: sup,
-                                incidence_type: IncidenceType::Superelement,
-                            });
-                        }
-                    }
+            // Only the maximal element can have no superelements.
+            if r != self.rank() && el.sups.is_empty() {
+                return Err(AbstractError::Ranked {
+                    el: (r, idx),
+                    incidence_type: IncidenceType::Superelement,
+                });
+            }
 
-                    // We got ourselves an invalid index.
-                    return Err(AbstractError::Index {
-                        el: (r, idx),
-                        index: sup,
-                        incidence_type: IncidenceType::Superelement,
-                    });
+            // Iterates over the element's superelements.
+            for &sup in &el.sups {
+                // Attempts to get the subelement's superelements.
+                if self.get_element(r + 1, sup).is_some() {
+                    if from_subs[r + 1].contains(&(idx, sup)) {
+                        continue;
+                    } else {
+                        // The element contains a superelement, but not viceversa.
+                        return Err(AbstractError::Consistency {
+                            el: (r, idx),
+                            index: sup,
+                            incidence_type: IncidenceType::Superelement,
+                        });
+                    }
                 }
+
+                // We got ourselves an invalid index.
+                return Err(AbstractError::Index {
+                    el: (r, idx),
+                    index: sup,
+                    incidence_type: IncidenceType::Superelement,
+                });
             }
         }
 
@@ -275,7 +406,7 @@ impl Ranks {
         // For every element, by looking through the subelements of its
         // subelements, we need to find each exactly twice.
         for r in 2..self.rank() {
-            for (idx, el) in self[r].iter().enumerate() {
+            for (idx, el) in self.rank_iter(r) {
                 let mut hash_sub_subs = HashMap::new();
 
                 for &sub in &el.subs {
@@ -331,3 +462,204 @@ impl Ranks {
         todo!()
     }
 }
+
+impl Abstract {
+    /// Checks whether the polytope is valid, as in [`Ranks::is_valid`]. If
+    /// `strict` is set, fissary polytopes are also rejected, as in
+    /// [`Self::fissary_elements`].
+    pub fn is_valid(&self, strict: bool) -> AbstractResult<()> {
+        self.ranks().is_valid()?;
+
+        if strict {
+            if let Some(&(rank, idx)) = self.fissary_elements().first() {
+                return Err(AbstractError::Fissary {
+                    el: ((rank + 1) as usize, idx),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the polytope up to a given [`ValidationLevel`]. The full check is
+    /// the same as [`Self::is_valid`] in strict mode.
+    pub fn validate(&self, level: ValidationLevel) -> AbstractResult<()> {
+        match level {
+            ValidationLevel::Full => self.is_valid(true),
+            _ => self.ranks().validate(level),
+        }
+    }
+
+    /// Returns whether all of the flags of a polytope are connected to each
+    /// other through flag changes. Points and dyads are always connected.
+    fn flags_connected(mut self) -> bool {
+        if self.rank() < 3 {
+            return true;
+        }
+
+        if !self.sorted() {
+            self.element_sort();
+        }
+
+        self.flag_events().filter_flags().count() == self.flags().count()
+    }
+
+    /// Returns the ranks and indices of the fissary elements of the polytope,
+    /// namely the proper elements such that either themselves or their element
+    /// figures are compounds, i.e. their flags aren't all connected to each
+    /// other. Ranks are given as in [`element_name`](crate::conc::element_types::element_name),
+    /// so that vertices have rank 0.
+    pub fn fissary_elements(&self) -> Vec<(isize, usize)> {
+        let rank = self.rank();
+        if rank < 3 {
+            return Vec::new();
+        }
+
+        // The element figures are the duals of the elements of the dual, and a
+        // polytope is connected if and only if its dual is.
+        let dual = self.dual();
+        let mut fissary = Vec::new();
+
+        for r in 1..rank {
+            for idx in 0..self.el_count(r) {
+                let el = self.element(r, idx).unwrap();
+                let fig = dual.element(rank - r, idx).unwrap();
+
+                if !el.flags_connected() || !fig.flags_connected() {
+                    fissary.push((r as isize - 1, idx));
+                }
+            }
+        }
+
+        fissary
+    }
+
+    /// Returns whether the polytope has any fissary elements, as in
+    /// [`Self::fissary_elements`].
+    pub fn is_fissary(&self) -> bool {
+        !self.fissary_elements().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that a repeated edge in a polygon is reported.
+    #[test]
+    fn duplicate_edge() {
+        let mut ranks = Abstract::polygon(4).into_ranks();
+        ranks[3][0].subs.push(2);
+
+        assert!(matches!(
+            ranks.is_valid(),
+            Err(AbstractError::DuplicateSub {
+                rank: 3,
+                index: 0,
+                sub: 2
+            })
+        ));
+    }
+
+    /// Checks that a degenerate edge, with the same vertex twice, is reported
+    /// rather than counted as a dyad.
+    #[test]
+    fn degenerate_edge() {
+        let mut ranks = Abstract::polygon(3).into_ranks();
+        ranks[2][1].subs = vec![1, 1].into();
+
+        assert!(matches!(
+            ranks.is_valid(),
+            Err(AbstractError::DuplicateSub {
+                rank: 2,
+                index: 1,
+                sub: 1
+            })
+        ));
+    }
+
+    /// Checks what each validation level catches on corrupted polygons.
+    #[test]
+    fn validation_levels() {
+        use ValidationLevel::*;
+        let levels = |ranks: &Ranks| [Off, Cheap, Full].map(|level| ranks.validate(level).is_ok());
+
+        // An index out of bounds.
+        let mut ranks = Abstract::polygon(4).into_ranks();
+        ranks[2][0].sups.push(1);
+        assert_eq!(levels(&ranks), [true, false, false]);
+        assert!(matches!(
+            ranks.validate(Cheap),
+            Err(AbstractError::Index {
+                el: (2, 0),
+                incidence_type: IncidenceType::Superelement,
+                index: 1
+            })
+        ));
+
+        // An unbounded polytope.
+        let mut ranks = Abstract::polygon(4).into_ranks();
+        let face = ranks[3][0].clone();
+        ranks[3].push(face);
+        assert_eq!(levels(&ranks), [true, false, false]);
+
+        // An edge with a single vertex, whose incidences are consistent.
+        let mut ranks = Abstract::polygon(4).into_ranks();
+        let [v, w] = [0, 1].map(|i| ranks[2][0].subs[i]);
+        ranks[2][0].subs = vec![v].into();
+        let sups = ranks[1][w].sups.iter().copied().filter(|&e| e != 0).collect::<Vec<_>>();
+        ranks[1][w].sups = sups.into();
+        assert_eq!(levels(&ranks), [true, true, false]);
+
+        // A fissary polytope only fails the full check.
+        let triangle = Abstract::polygon(3);
+        let pyramid = Abstract::compound(vec![triangle.clone(), triangle].into_iter()).pyramid();
+        assert!(pyramid.validate(Cheap).is_ok());
+        assert!(pyramid.ranks().validate(Full).is_ok());
+        assert!(matches!(
+            pyramid.validate(Full),
+            Err(AbstractError::Fissary { .. })
+        ));
+    }
+
+    /// Checks that the base and the apex of a pyramid on a compound of two
+    /// triangles, used as a single face, are fissary.
+    #[test]
+    fn fissary() {
+        let triangle = Abstract::polygon(3);
+        let base = Abstract::compound(vec![triangle.clone(), triangle].into_iter());
+        let pyramid = base.pyramid();
+        assert!(pyramid.ranks().is_valid().is_ok());
+
+        // The base is the only face with six edges, and the apex is the last
+        // vertex.
+        let face = (0..pyramid.face_count())
+            .find(|&idx| pyramid[(3, idx)].subs.len() == 6)
+            .unwrap();
+        assert_eq!(pyramid.fissary_elements(), vec![(0, 6), (2, face)]);
+
+        assert!(pyramid.is_fissary());
+        assert!(pyramid.is_valid(false).is_ok());
+        assert!(matches!(
+            pyramid.is_valid(true),
+            Err(AbstractError::Fissary { el: (1, 6) })
+        ));
+    }
+
+    /// Checks that the uniform polytopes from the generators aren't fissary.
+    #[test]
+    fn not_fissary() {
+        for poly in [
+            Abstract::polygon(5),
+            Abstract::cube(),
+            Abstract::simplex(5),
+            Abstract::hypercube(5),
+            Abstract::orthoplex(5),
+            Abstract::polygon(3).duoprism(&Abstract::polygon(4)),
+            Abstract::polygon(5).antiprism(),
+        ] {
+            assert_eq!(poly.fissary_elements(), Vec::new());
+            assert!(poly.is_valid(true).is_ok());
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! A command line interface that runs polytope operations without the GUI.
+//!
+//! The supported commands are:
+//!
+//! - `convert <in> <out> [--format off] [--no-comments]`: converts a polytope
+//!   file into an OFF file.
+//! - `op <operation> <in> <out>`: applies an operation to a polytope and saves
+//!   the result.
+//! - `info <in>`: prints out the [report](crate::conc::report::Report) of a
+//!   polytope as JSON.
+//! - `build <cd> <out>`: builds a polytope from a Coxeter diagram.
+
+use std::{fmt::Display, path::Path};
+
+use crate::{
+    conc::{
+        report::{ReportEntry, ReportOptions},
+        Concrete, ConcretePolytope,
+    },
+    cox::cd::Cd,
+    file::{off::OffOptions, FromFile},
+    Polytope,
+};
+
+/// The usage message of the command line interface.
+pub const USAGE: &str = "usage:
+    miratope --headless convert <in> <out> [--format off] [--no-comments]
+    miratope --headless op <operation> <in> <out>
+    miratope --headless info <in>
+    miratope --headless build <cd> <out>
+
+operations: dual, petrial, pyramid, prism, tegum, antiprism, ditope, hosotope,
+omnitruncate, recenter";
+
+/// The environment variable that makes `op` print out the element counts and
+/// canonical hashes of the polytope before and after the operation.
+pub const DEBUG_VAR: &str = "MIRATOPE_DEBUG";
+
+/// An error while running a command.
+#[derive(Clone, Debug)]
+pub enum CliError {
+    /// The command line arguments couldn't be understood.
+    Usage(String),
+
+    /// A file couldn't be read or written.
+    File(String),
+
+    /// An operation on a polytope failed.
+    Operation(String),
+}
+
+impl CliError {
+    /// The exit code associated to the error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => 2,
+            Self::File(_) | Self::Operation(_) => 1,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Usage(msg) => write!(f, "{}\n\n{}", msg, USAGE),
+            Self::File(msg) => write!(f, "file error: {}", msg),
+            Self::Operation(msg) => write!(f, "operation failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// The result of running a command.
+pub type CliResult<T> = Result<T, CliError>;
+
+/// Converts any file error into a [`CliError`].
+fn file_err<T: Display>(err: T) -> CliError {
+    CliError::File(err.to_string())
+}
+
+/// Loads a polytope from a path.
+pub fn load<P: AsRef<Path>>(path: P) -> CliResult<Concrete> {
+    Concrete::from_path(&path.as_ref()).map_err(file_err)
+}
+
+/// Saves a polytope as an OFF file in a given path.
+pub fn save<P: AsRef<Path>>(poly: &Concrete, path: P, options: OffOptions) -> CliResult<()> {
+    poly.to_path(path, options).map_err(file_err)
+}
+
+/// Converts a polytope file into a file of a given format. Only the OFF format
+/// is currently supported as an output.
+pub fn convert<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output: Q,
+    format: &str,
+    options: OffOptions,
+) -> CliResult<()> {
+    if format != "off" {
+        return Err(CliError::Usage(format!("unsupported format \"{}\"", format)));
+    }
+
+    save(&load(input)?, output, options)
+}
+
+/// Applies an operation, given by its name, to a polytope.
+pub fn op(operation: &str, poly: &Concrete) -> CliResult<Concrete> {
+    let op_err = |err: &dyn Display| CliError::Operation(format!("{}: {}", operation, err));
+
+    Ok(match operation {
+        "dual" => poly.try_dual().map_err(|err| op_err(&err))?,
+        "petrial" => poly
+            .petrial()
+            .ok_or_else(|| op_err(&"the Petrial isn't a valid polytope"))?,
+        "pyramid" => poly.pyramid(),
+        "prism" => poly.prism(),
+        "tegum" => poly.tegum(),
+        "antiprism" => poly.try_antiprism().map_err(|err| op_err(&err))?,
+        "ditope" => poly.ditope(),
+        "hosotope" => poly.hosotope(),
+        "omnitruncate" => {
+            let mut poly = poly.clone();
+            poly.element_sort();
+            poly.omnitruncate()
+        }
+        "recenter" => poly.clone().recenter(),
+        _ => return Err(CliError::Usage(format!("unknown operation \"{}\"", operation))),
+    })
+}
+
+/// Returns the [`Report`] of a polytope as a JSON object. Undefined values are
+/// written as `null`.
+pub fn info(poly: &Concrete) -> String {
+    let report = poly.report(ReportOptions::all());
+
+    let counts: Vec<_> = report.el_counts.iter().map(usize::to_string).collect();
+    let float_or_null = |x: &ReportEntry<f64>| match x {
+        ReportEntry::Value(x) if x.is_finite() => x.to_string(),
+        _ => "null".to_string(),
+    };
+    let orientable = match report.orientable {
+        ReportEntry::Value(orientable) => orientable.to_string(),
+        _ => "null".to_string(),
+    };
+
+    format!(
+        "{{\"element_counts\": [{}], \"circumradius\": {}, \"midradius\": {}, \"inradius\": {}, \
+        \"euler_characteristic\": {}, \"equilateral\": {}, \"volume\": {}, \"orientable\": {}}}",
+        counts.join(", "),
+        float_or_null(&report.circumradius),
+        float_or_null(&report.midradius),
+        float_or_null(&report.inradius),
+        report.euler_characteristic,
+        report.equilateral.passed,
+        float_or_null(&report.volume),
+        orientable
+    )
+}
+
+/// Builds a polytope from a Coxeter diagram.
+pub fn build(cd: &str) -> CliResult<Concrete> {
+    let cd = Cd::parse(cd).map_err(|err| CliError::Usage(format!("invalid diagram: {}", err)))?;
+
+    Err(CliError::Operation(format!(
+        "building polytopes from Coxeter diagrams isn't supported yet ({} nodes given)",
+        cd.node_count()
+    )))
+}
+
+/// Runs a command, given by its arguments, not including the program name nor
+/// the `--headless` flag. Any output is written to `stdout`.
+pub fn run<I: IntoIterator<Item = String>>(args: I) -> CliResult<()> {
+    let args: Vec<_> = args.into_iter().collect();
+    let usage_err = |msg: &str| Err(CliError::Usage(msg.to_string()));
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["convert", input, output, flags @ ..] => {
+            let mut format = "off";
+            let mut options = OffOptions::default();
+            let mut flags = flags.iter();
+
+            while let Some(&flag) = flags.next() {
+                match flag {
+                    "--format" => match flags.next() {
+                        Some(&f) => format = f,
+                        None => return usage_err("missing value for --format"),
+                    },
+                    "--no-comments" => options.comments = false,
+                    flag => return Err(CliError::Usage(format!("unknown flag \"{}\"", flag))),
+                }
+            }
+
+            convert(input, output, format, options)
+        }
+        ["op", operation, input, output] => {
+            let poly = load(input)?;
+            let result = op(operation, &poly)?;
+
+            if std::env::var_os(DEBUG_VAR).is_some() {
+                eprintln!("input: {:#}", poly.abs);
+                eprintln!("{}: {:#}", operation, result.abs);
+            }
+
+            save(&result, output, Default::default())
+        }
+        ["info", input] => {
+            println!("{}", info(&load(input)?));
+            Ok(())
+        }
+        ["build", cd, output] => save(&build(cd)?, output, Default::default()),
+        [] => usage_err("no command given"),
+        [command, ..] => Err(CliError::Usage(format!(
+            "unknown command or wrong number of arguments for \"{}\"",
+            command
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    /// Returns a path in the temporary directory for a given file name.
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("miratope-cli-{}-{}", std::process::id(), name))
+    }
+
+    /// Converts an OFF file and reads it back.
+    #[test]
+    fn convert_off() {
+        let input = temp_path("tet.off");
+        let output = temp_path("tet-converted.off");
+        std::fs::write(&input, include_str!("file/off/tet.off")).unwrap();
+
+        run(vec![
+            "convert".to_string(),
+            input.to_string_lossy().into_owned(),
+            output.to_string_lossy().into_owned(),
+            "--no-comments".to_string(),
+        ])
+        .unwrap();
+        test(&load(&output).unwrap(), [1, 4, 6, 4, 1]);
+
+        // Binary output isn't supported.
+        let err = convert(&input, &output, "binary", Default::default()).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+
+        std::fs::remove_file(input).unwrap();
+        std::fs::remove_file(output).unwrap();
+    }
+
+    /// Checks the info of a cube.
+    #[test]
+    fn info_cube() {
+        let info = info(&Concrete::cube());
+        assert!(info.starts_with("{\"element_counts\": [1, 8, 12, 6, 1], \"circumradius\": 0.866"));
+        assert!(info.ends_with("\"volume\": 1, \"orientable\": true}"));
+    }
+
+    /// Checks some operations.
+    #[test]
+    fn ops() {
+        test(&op("dual", &Concrete::cube()).unwrap(), [1, 6, 12, 8, 1]);
+        test(&op("prism", &Concrete::polygon(5)).unwrap(), [1, 10, 15, 7, 1]);
+        assert_eq!(op("frobnicate", &Concrete::cube()).unwrap_err().exit_code(), 2);
+        assert!(matches!(run(Vec::new()), Err(CliError::Usage(_))));
+    }
+}
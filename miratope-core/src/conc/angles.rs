@@ -0,0 +1,288 @@
+//! Computes the angles between the facets of a polytope at each of its ridges,
+//! like the dihedral angles of a polyhedron.
+
+use std::borrow::Cow;
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::Ranked,
+    float::Float,
+    geometry::{Subspace, Vector},
+};
+
+use vec_like::*;
+
+/// The angles between the facets of a polytope at each of its ridges.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RidgeAngles {
+    /// The index of every ridge incident to exactly two facets, together with
+    /// the interior angle between them, in radians.
+    pub angles: Vec<(usize, f64)>,
+
+    /// The number of ridges that were skipped, either because they aren't
+    /// incident to exactly two facets, or because a facet's orientation
+    /// couldn't be determined.
+    pub skipped: usize,
+
+    /// Whether some facet isn't flat, in which case its best-fit hyperplane
+    /// was used instead.
+    pub non_planar: bool,
+}
+
+impl RidgeAngles {
+    /// Returns the distinct angles, in increasing order, together with the
+    /// number of ridges with each of them. Angles that differ by less than
+    /// the tolerance are grouped together.
+    pub fn distinct(&self, tolerance: f64) -> Vec<(f64, usize)> {
+        let mut angles: Vec<_> = self.angles.iter().map(|&(_, angle)| angle).collect();
+        angles.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut distinct: Vec<(f64, usize)> = Vec::new();
+        for angle in angles {
+            match distinct.last_mut() {
+                Some((first, count)) if angle - *first < tolerance => *count += 1,
+                _ => distinct.push((angle, 1)),
+            }
+        }
+
+        distinct
+    }
+}
+
+impl Concrete {
+    /// Returns the outward unit normal of every facet, or `None` for the
+    /// facets whose hyperplane goes through the gravicenter. Also returns
+    /// whether any facet isn't flat.
+    fn facet_normals(&self) -> (Vec<Option<Vector<f64>>>, bool) {
+        let rank = self.rank();
+        let dim = self.dim_or();
+        let gravicenter = self.gravicenter().unwrap();
+        let mut non_planar = false;
+
+        let normals = (0..self.facet_count())
+            .map(|idx| {
+                let vertices = self.element_vertices_ref(rank - 1, idx).unwrap();
+                let plane = Subspace::fit_points(vertices.iter().copied(), dim - 1)?;
+
+                let radius = vertices
+                    .iter()
+                    .map(|&v| (v - &plane.offset).norm())
+                    .fold(1.0, f64::max);
+                if vertices
+                    .iter()
+                    .any(|&v| plane.distance(v) > f64::EPS * radius)
+                {
+                    non_planar = true;
+                }
+
+                plane.normal(&gravicenter).map(|n| -n)
+            })
+            .collect();
+
+        (normals, non_planar)
+    }
+
+    /// Computes the interior angle between the two facets at every ridge of
+    /// the polytope, from their outward normals, which are oriented away from
+    /// the gravicenter. The polytope is flattened first if it isn't
+    /// full-dimensional.
+    ///
+    /// For polyhedra, these are the dihedral angles, and for polygons, these
+    /// are the angles at every vertex.
+    pub fn ridge_angles(&self) -> RidgeAngles {
+        let rank = self.rank();
+        if rank < 3 {
+            return RidgeAngles::default();
+        }
+
+        let poly = if self.full_dimensional() {
+            Cow::Borrowed(self)
+        } else {
+            let mut poly = self.clone();
+            poly.flatten();
+            Cow::Owned(poly)
+        };
+
+        let (normals, non_planar) = poly.facet_normals();
+        let mut angles = RidgeAngles {
+            non_planar,
+            ..Default::default()
+        };
+
+        for (idx, ridge) in poly.ridges() {
+            let facets = &ridge.sups;
+            if facets.len() != 2 {
+                angles.skipped += 1;
+                continue;
+            }
+
+            match (&normals[facets[0]], &normals[facets[1]]) {
+                (Some(n0), Some(n1)) => {
+                    let cos = n0.dot(n1).clamp(-1.0, 1.0);
+                    angles.angles.push((idx, f64::PI - cos.acos()));
+                }
+                _ => angles.skipped += 1,
+            }
+        }
+
+        angles
+    }
+
+    /// Computes the dihedral angle at every edge of a polyhedron, in radians.
+    /// See [`Self::ridge_angles`] for the details. Returns an empty list if
+    /// the polytope isn't a polyhedron.
+    pub fn dihedral_angles(&self) -> Vec<(usize, f64)> {
+        if self.rank() == 4 {
+            self.ridge_angles().angles
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns the distance between two vertices, measured in the full
+    /// dimension of the polytope, or `None` if either of them doesn't exist.
+    pub fn vertex_distance(&self, i: usize, j: usize) -> Option<f64> {
+        Some((self.vertices.get(i)? - self.vertices.get(j)?).norm())
+    }
+
+    /// Returns the angle at the vertex `j` between the segments to the
+    /// vertices `i` and `k`, in radians, measured in the full dimension of the
+    /// polytope. Returns `None` if some vertex doesn't exist, or if either
+    /// segment has no length.
+    pub fn vertex_angle(&self, i: usize, j: usize, k: usize) -> Option<f64> {
+        let center = self.vertices.get(j)?;
+        let u = self.vertices.get(i)? - center;
+        let v = self.vertices.get(k)? - center;
+
+        let norms = u.norm() * v.norm();
+        (norms > f64::EPS).then(|| (u.dot(&v) / norms).clamp(-1.0, 1.0).acos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::Point, Polytope};
+
+    /// The tolerance used to group angles.
+    const TOLERANCE: f64 = 1e-6;
+
+    /// Checks that every dihedral angle of a polytope equals a given value,
+    /// in degrees.
+    fn test_dihedral(poly: &Concrete, degrees: f64) {
+        let angles = poly.dihedral_angles();
+        assert_eq!(angles.len(), poly.edge_count());
+        for (_, angle) in angles {
+            assert!(
+                (angle.to_degrees() - degrees).abs() < TOLERANCE,
+                "expected {}°, found {}°",
+                degrees,
+                angle.to_degrees()
+            );
+        }
+    }
+
+    #[test]
+    fn cube() {
+        test_dihedral(&Concrete::cube(), 90.0);
+    }
+
+    #[test]
+    fn icosahedron() {
+        test_dihedral(&Concrete::platonic(4), 138.189_685_104_221_4);
+    }
+
+    #[test]
+    fn tetrahedron() {
+        test_dihedral(&Concrete::platonic(0), 70.528_779_365_509_3);
+    }
+
+    /// A pentagonal prism has right angles at its lateral edges, and 108° ones
+    /// between its bases and its lateral faces.
+    #[test]
+    fn prism() {
+        let distinct = Concrete::polygon(5)
+            .prism()
+            .ridge_angles()
+            .distinct(TOLERANCE);
+        assert_eq!(distinct.len(), 2);
+        assert!((distinct[0].0.to_degrees() - 90.0).abs() < TOLERANCE);
+        assert_eq!(distinct[0].1, 10);
+        assert!((distinct[1].0.to_degrees() - 108.0).abs() < TOLERANCE);
+        assert_eq!(distinct[1].1, 5);
+    }
+
+    /// The ridge angles of a polygon are its interior angles, and those of a
+    /// tesseract are right angles.
+    #[test]
+    fn other_ranks() {
+        let distinct = Concrete::polygon(6).ridge_angles().distinct(TOLERANCE);
+        assert_eq!(distinct.len(), 1);
+        assert!((distinct[0].0.to_degrees() - 120.0).abs() < TOLERANCE);
+        assert_eq!(distinct[0].1, 6);
+
+        let distinct = Concrete::hypercube(5).ridge_angles().distinct(TOLERANCE);
+        assert_eq!(distinct.len(), 1);
+        assert!((distinct[0].0.to_degrees() - 90.0).abs() < TOLERANCE);
+        assert_eq!(distinct[0].1, 24);
+        assert!(Concrete::hypercube(5).dihedral_angles().is_empty());
+    }
+
+    /// A square embedded in 3D is flattened first.
+    #[test]
+    fn embedded() {
+        let mut square = Concrete::polygon(4);
+        for v in &mut square.vertices {
+            *v = Point::from_vec(vec![v[0], v[1], 1.0]);
+        }
+
+        let angles = square.ridge_angles();
+        assert_eq!(angles.angles.len(), 4);
+        assert_eq!(angles.skipped, 0);
+        assert!(!angles.non_planar);
+    }
+
+    /// Distances and angles between vertices are measured in every
+    /// dimension, not just the first three.
+    #[test]
+    fn vertex_measures() {
+        let tesseract = Concrete::hypercube(5);
+        let far = (1..tesseract.vertex_count())
+            .filter_map(|j| tesseract.vertex_distance(0, j))
+            .fold(0.0, f64::max);
+        assert!((far - 2.0).abs() < TOLERANCE);
+
+        let square = Concrete::polygon(4);
+        let side = square.vertex_distance(0, 1).unwrap();
+        assert!((side - 2f64.sqrt()).abs() < TOLERANCE);
+        let angle = square.vertex_angle(0, 1, 2).unwrap();
+        assert!((angle.to_degrees() - 90.0).abs() < TOLERANCE);
+
+        assert_eq!(square.vertex_distance(0, 4), None);
+        assert_eq!(square.vertex_angle(0, 0, 1), None);
+    }
+
+    /// Ridges on only one facet are skipped, and non-planar facets are
+    /// flagged.
+    #[test]
+    fn degenerate() {
+        // A cube with one face removed.
+        let mut cube = Concrete::cube();
+        let rank = cube.rank();
+        cube.abs[rank - 1].0.pop();
+        cube.abs[rank][0].subs.pop();
+        for edge in cube.abs[rank - 2].iter_mut() {
+            edge.sups.as_inner_mut().retain(|&f| f < 5);
+        }
+
+        let angles = cube.ridge_angles();
+        assert_eq!(angles.skipped, 4);
+        assert_eq!(angles.angles.len(), 8);
+
+        // A cube with a displaced vertex.
+        let mut cube = Concrete::cube();
+        cube.vertices[0][0] += 0.1;
+        assert!(cube.ridge_angles().non_planar);
+        assert!(!Concrete::cube().ridge_angles().non_planar);
+    }
+}
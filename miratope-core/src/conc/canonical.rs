@@ -0,0 +1,287 @@
+//! Computes the [canonical form](https://en.wikipedia.org/wiki/Canonical_polyhedron)
+//! of a convex polyhedron, following George Hart's iterative algorithm.
+
+use super::{cycle::CycleList, Concrete, ConcretePolytope, NotFullDimensional};
+use crate::{
+    abs::Ranked,
+    geometry::{Point, Vector},
+};
+
+use vec_like::*;
+
+/// The fraction of each correction that's applied on every iteration. Smaller
+/// values converge slower, but more reliably.
+const STABILITY: f64 = 0.2;
+
+/// An error when canonicalizing a polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanonicalizeError {
+    /// The polytope isn't a polyhedron in 3D.
+    NotPolyhedron,
+
+    /// The polyhedron isn't convex.
+    NotConvex,
+
+    /// The polyhedron lives in a higher dimension and hasn't been flattened.
+    NotFullDimensional(NotFullDimensional),
+}
+
+impl From<NotFullDimensional> for CanonicalizeError {
+    fn from(err: NotFullDimensional) -> Self {
+        Self::NotFullDimensional(err)
+    }
+}
+
+impl std::fmt::Display for CanonicalizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotPolyhedron => write!(f, "canonicalization only applies to polyhedra in 3D"),
+            Self::NotConvex => write!(f, "canonicalization only applies to convex polyhedra"),
+            Self::NotFullDimensional(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalizeError {}
+
+/// Returns the point on the line through two points that's closest to the
+/// origin.
+fn tangent_point(v0: &Point<f64>, v1: &Point<f64>) -> Point<f64> {
+    let d = v1 - v0;
+    v0 - &d * (d.dot(v0) / d.norm_squared())
+}
+
+/// Returns the normal of a cycle of points in 3D, using Newell's method. The
+/// normal isn't normalized.
+fn newell_normal<'a, I: Iterator<Item = &'a Point<f64>> + Clone>(points: I) -> Vector<f64> {
+    let mut normal = Vector::zeros(3);
+
+    for (p, q) in points.clone().zip(points.cycle().skip(1)) {
+        normal[0] += (p[1] - q[1]) * (p[2] + q[2]);
+        normal[1] += (p[2] - q[2]) * (p[0] + q[0]);
+        normal[2] += (p[0] - q[0]) * (p[1] + q[1]);
+    }
+
+    normal
+}
+
+impl Concrete {
+    /// Returns the vertices of every face of a polyhedron in cyclic order.
+    /// Only the first component of every face is considered.
+    fn face_cycles(&self) -> Vec<Vec<usize>> {
+        self[3]
+            .iter()
+            .map(|face| {
+                CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs))
+                    .iter()
+                    .next()
+                    .map(|cycle| cycle.iter().copied().collect())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Determines whether a polyhedron is convex, by checking that the
+    /// remaining vertices lie on one side of every face. The faces needn't be
+    /// exactly planar.
+    fn is_convex_polyhedron(&self, faces: &[Vec<usize>]) -> bool {
+        let tolerance = 1e-6
+            * self
+                .vertices
+                .iter()
+                .map(|v| v.norm())
+                .fold(1.0, f64::max);
+
+        faces.iter().all(|face| {
+            let points = face.iter().map(|&v| &self.vertices[v]);
+            let normal = newell_normal(points.clone());
+            let center = points.sum::<Point<f64>>() / face.len() as f64;
+
+            let mut above = false;
+            let mut below = false;
+            for (idx, v) in self.vertices.iter().enumerate() {
+                if !face.contains(&idx) {
+                    let dist = normal.dot(&(v - &center));
+                    above |= dist > tolerance;
+                    below |= dist < -tolerance;
+                }
+            }
+
+            !(above && below)
+        })
+    }
+
+    /// Moves every vertex so that each edge gets closer to being tangent to
+    /// the unit sphere.
+    fn tangentify(&mut self, edges: &[[usize; 2]]) {
+        let mut vertices = self.vertices.clone();
+
+        for &[v0, v1] in edges {
+            let t = tangent_point(&self.vertices[v0], &self.vertices[v1]);
+            let c = &t * (STABILITY * 0.5 * (1.0 - t.norm()));
+            vertices[v0] += &c;
+            vertices[v1] += &c;
+        }
+
+        self.vertices = vertices;
+    }
+
+    /// Moves the polyhedron so that the average of the points where its edges
+    /// are closest to the origin is the origin.
+    fn recenter_tangents(&mut self, edges: &[[usize; 2]]) {
+        let center = edges
+            .iter()
+            .map(|&[v0, v1]| tangent_point(&self.vertices[v0], &self.vertices[v1]))
+            .sum::<Point<f64>>()
+            / edges.len() as f64;
+
+        self.recenter_with_mut(&center);
+    }
+
+    /// Moves every vertex so that each face gets closer to being planar.
+    fn planarize(&mut self, faces: &[Vec<usize>]) {
+        let mut vertices = self.vertices.clone();
+
+        for face in faces {
+            let points = face.iter().map(|&v| &self.vertices[v]);
+            let mut normal = newell_normal(points.clone()).normalize();
+            let center = points.sum::<Point<f64>>() / face.len() as f64;
+
+            if normal.dot(&center) < 0.0 {
+                normal = -normal;
+            }
+
+            for &v in face {
+                vertices[v] += &normal * (STABILITY * normal.dot(&(&center - &self.vertices[v])));
+            }
+        }
+
+        self.vertices = vertices;
+    }
+
+    /// Iteratively moves the vertices of a convex polyhedron into its
+    /// canonical form, where every edge is tangent to the unit sphere, the
+    /// points of tangency average out to the origin, and every face is planar.
+    ///
+    /// Stops after a given number of iterations, or whenever no vertex moves
+    /// more than `eps` in a single iteration. Returns whether the algorithm
+    /// converged. Returns an error and leaves the polytope unchanged if it
+    /// isn't a convex, flattened polyhedron.
+    pub fn canonicalize(&mut self, iterations: usize, eps: f64) -> Result<bool, CanonicalizeError> {
+        if self.rank() == 4 && self.dim_or() > 3 {
+            self.check_full_dimensional()?;
+        }
+
+        if self.rank() != 4 || self.dim() != Some(3) {
+            return Err(CanonicalizeError::NotPolyhedron);
+        }
+
+        let faces = self.face_cycles();
+        if !self.is_convex_polyhedron(&faces) {
+            return Err(CanonicalizeError::NotConvex);
+        }
+
+        let edges: Vec<_> = self[2]
+            .iter()
+            .map(|edge| [edge.subs[0], edge.subs[1]])
+            .collect();
+
+        for _ in 0..iterations {
+            let old_vertices = self.vertices.clone();
+            self.tangentify(&edges);
+            self.recenter_tangents(&edges);
+            self.planarize(&faces);
+
+            let change = old_vertices
+                .iter()
+                .zip(&self.vertices)
+                .map(|(v, w)| (v - w).norm())
+                .fold(0.0, f64::max);
+
+            if change < eps {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{float::Float, geometry::Subspace, Polytope};
+
+    use approx::abs_diff_eq;
+
+    /// Returns a cube whose vertices have been displaced by some arbitrary
+    /// amounts.
+    fn perturbed_cube() -> Concrete {
+        let mut cube = Concrete::cube();
+
+        for (idx, v) in cube.vertices.iter_mut().enumerate() {
+            for (i, x) in v.iter_mut().enumerate() {
+                *x += 0.05 * ((7 * idx + 3 * i) as f64).sin();
+            }
+        }
+
+        cube
+    }
+
+    /// Checks that a perturbed cube gets its planar faces and unit midradius
+    /// back.
+    #[test]
+    fn cube() {
+        let mut cube = perturbed_cube();
+        assert!(cube.canonicalize(100_000, 1e-13).unwrap());
+
+        for face in cube.face_cycles() {
+            // The plane through the first three vertices of the face.
+            let plane = Subspace::from_points(face.iter().take(3).map(|&v| &cube.vertices[v]));
+            for &v in &face {
+                assert!(plane.distance(&cube.vertices[v]) < 1e-8);
+            }
+        }
+
+        for edge in cube[2].iter() {
+            let t = tangent_point(&cube.vertices[edge.subs[0]], &cube.vertices[edge.subs[1]]);
+            assert!(abs_diff_eq!(t.norm(), 1.0, epsilon = 1e-8));
+        }
+
+        assert!(cube.is_equilateral());
+    }
+
+    /// Checks that the canonical dual of a canonical polyhedron is dual to it.
+    #[test]
+    fn dual() {
+        let mut cube = perturbed_cube();
+        assert!(cube.canonicalize(100_000, 1e-13).unwrap());
+
+        let dual = cube.try_dual().unwrap().try_dual().unwrap();
+        for (v, w) in cube.vertices.iter().zip(&dual.vertices) {
+            assert!((v - w).norm() < 1e-6);
+        }
+    }
+
+    /// Checks that non-convex polyhedra are rejected and left unchanged.
+    #[test]
+    fn non_convex() {
+        let mut stip = Concrete::star_polygon(5, 2).prism();
+        let vertices = stip.vertices.clone();
+
+        assert_eq!(
+            stip.canonicalize(100, f64::EPS),
+            Err(CanonicalizeError::NotConvex)
+        );
+        assert_eq!(stip.vertices, vertices);
+    }
+
+    /// Checks that polytopes other than polyhedra in 3D are rejected.
+    #[test]
+    fn not_polyhedron() {
+        assert_eq!(
+            Concrete::polygon(5).canonicalize(100, f64::EPS),
+            Err(CanonicalizeError::NotPolyhedron)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Decides what operations do with compound polytopes: apply themselves to
+//! each of the components, refuse them, or treat them as a single polytope.
+
+use std::{error::Error, fmt::Display};
+
+use super::{canonical::CanonicalizeError, Concrete, ConcretePolytope};
+use crate::{geometry::Hypersphere, DualError, Polytope};
+
+/// What an operation does with a compound polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompoundPolicy {
+    /// Applies the operation to each component separately, and builds the
+    /// compound of the results.
+    PerComponent,
+
+    /// Refuses to apply the operation to a compound.
+    Reject,
+
+    /// Applies the operation to the compound as a whole, as if it were a
+    /// single polytope.
+    Merge,
+}
+
+impl CompoundPolicy {
+    /// The policy for duals. The dual of a compound is the compound of the
+    /// duals of its components.
+    pub const DUAL: Self = Self::PerComponent;
+
+    /// The policy for antiprisms. Each component gets an antiprism of its own.
+    pub const ANTIPRISM: Self = Self::PerComponent;
+
+    /// The policy for canonicalization. A compound has no canonical form,
+    /// since its components needn't share a midsphere.
+    pub const CANONICALIZE: Self = Self::Reject;
+}
+
+/// An error while applying an operation to a polytope that might be a
+/// compound.
+#[derive(Clone, Copy, Debug)]
+pub enum CompoundError<E> {
+    /// The operation doesn't apply to compounds.
+    Rejected {
+        /// The name of the operation.
+        operation: &'static str,
+
+        /// The number of components of the polytope.
+        components: usize,
+    },
+
+    /// The operation itself failed, on the polytope or on one of its
+    /// components.
+    Operation(E),
+}
+
+impl<E> From<E> for CompoundError<E> {
+    fn from(err: E) -> Self {
+        Self::Operation(err)
+    }
+}
+
+impl<E: Display> Display for CompoundError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rejected {
+                operation,
+                components,
+            } => write!(
+                f,
+                "{} doesn't apply to compounds, and this polytope has {} components",
+                operation, components
+            ),
+            Self::Operation(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: Error> Error for CompoundError<E> {}
+
+impl Concrete {
+    /// Splits the polytope into its components. A polytope that isn't a
+    /// compound is its only component.
+    pub fn components(&self) -> Vec<Self> {
+        let mut poly = self.clone();
+        poly.element_sort();
+        poly.split()
+    }
+
+    /// Returns whether the polytope is a compound, that is, whether it has
+    /// more than one component.
+    pub fn is_compound(&self) -> bool {
+        if self.abs.sorted() {
+            self.abs.is_compound()
+        } else {
+            let mut abs = self.abs.clone();
+            abs.element_sort();
+            abs.is_compound()
+        }
+    }
+
+    /// Applies an operation to the polytope following a compound policy.
+    /// Returns the result, together with the number of components the
+    /// operation was applied to separately, which is 1 unless the policy is
+    /// [`CompoundPolicy::PerComponent`] and the polytope is a compound.
+    pub fn apply_with_policy<E, F: FnMut(&Self) -> Result<Self, E>>(
+        &self,
+        operation: &'static str,
+        policy: CompoundPolicy,
+        mut f: F,
+    ) -> Result<(Self, usize), CompoundError<E>> {
+        if policy == CompoundPolicy::Merge || !self.is_compound() {
+            return Ok((f(self)?, 1));
+        }
+
+        let components = self.components();
+        if policy == CompoundPolicy::Reject {
+            return Err(CompoundError::Rejected {
+                operation,
+                components: components.len(),
+            });
+        }
+
+        let count = components.len();
+        let results = components
+            .iter()
+            .map(&mut f)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((Self::compound(results.into_iter()), count))
+    }
+
+    /// Returns the dual of the polytope with respect to a given sphere,
+    /// following a compound policy. Also returns the number of components
+    /// the dual was taken of separately.
+    pub fn try_dual_with_policy(
+        &self,
+        sphere: &Hypersphere<f64>,
+        policy: CompoundPolicy,
+    ) -> Result<(Self, usize), CompoundError<DualError>> {
+        self.apply_with_policy("The dual", policy, |p| p.try_dual_with(sphere))
+    }
+
+    /// Canonicalizes the polytope in place following a compound policy, like
+    /// [`Self::canonicalize`]. Returns whether every component converged, and
+    /// the number of components that were canonicalized separately. Leaves
+    /// the polytope unchanged if it fails.
+    pub fn canonicalize_with_policy(
+        &mut self,
+        iterations: usize,
+        eps: f64,
+        policy: CompoundPolicy,
+    ) -> Result<(bool, usize), CompoundError<CanonicalizeError>> {
+        let mut converged = true;
+        let (poly, count) = self.apply_with_policy("Canonicalization", policy, |p| {
+            let mut p = p.clone();
+            converged &= p.canonicalize(iterations, eps)?;
+            Ok(p)
+        })?;
+
+        *self = poly;
+        Ok((converged, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, float::Float};
+
+    /// The hexagram {6/2} is a compound of two triangles, while the
+    /// pentagram is a single polygon.
+    #[test]
+    fn is_compound() {
+        let hexagram = Concrete::star_polygon(6, 2);
+        assert!(hexagram.is_compound());
+        assert_eq!(hexagram.components().len(), 2);
+
+        assert!(!Concrete::star_polygon(5, 2).is_compound());
+        assert!(!Concrete::cube().is_compound());
+        assert_eq!(Concrete::cube().components().len(), 1);
+    }
+
+    /// The dual of the stella octangula is another stella octangula.
+    #[test]
+    fn stella_octangula_dual() {
+        let stella = Concrete::simplex(4).self_dual_compound().unwrap();
+        let sphere = Hypersphere::unit(3);
+        let (dual, count) = stella.try_dual_with_policy(&sphere, CompoundPolicy::DUAL).unwrap();
+
+        assert_eq!(count, 2);
+        assert!(dual.abs.is_isomorphic(&stella.abs));
+
+        // All of its vertices are at the same distance from the center.
+        let radius = dual.vertices[0].norm();
+        for v in &dual.vertices {
+            assert!((v.norm() - radius).abs() < f64::EPS);
+        }
+    }
+
+    /// The antiprism of the hexagram is a compound of two octahedra.
+    #[test]
+    fn hexagram_antiprism() {
+        let hexagram = Concrete::star_polygon(6, 2);
+        let (antiprism, count) = hexagram
+            .apply_with_policy("The antiprism", CompoundPolicy::ANTIPRISM, |p| {
+                p.try_antiprism_auto().map(|(q, _)| q)
+            })
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            antiprism.el_count_iter().collect::<Vec<_>>(),
+            vec![1, 12, 24, 16, 1]
+        );
+        let components = antiprism.components();
+        assert_eq!(components.len(), 2);
+        for component in components {
+            assert!(component.abs.is_isomorphic(&Concrete::orthoplex(4).abs));
+        }
+    }
+
+    /// Canonicalization refuses compounds, and leaves them unchanged.
+    #[test]
+    fn canonicalize_rejects() {
+        let mut stella = Concrete::simplex(4).self_dual_compound().unwrap();
+        let original = stella.clone();
+
+        let err = stella
+            .canonicalize_with_policy(100, f64::EPS, CompoundPolicy::CANONICALIZE)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Canonicalization doesn't apply to compounds, and this polytope has 2 components"
+        );
+        assert_eq!(stella.vertices, original.vertices);
+
+        // A single polytope is canonicalized as usual.
+        let mut cube = Concrete::cube();
+        assert_eq!(
+            cube.canonicalize_with_policy(100, f64::EPS, CompoundPolicy::CANONICALIZE)
+                .unwrap()
+                .1,
+            1
+        );
+    }
+}
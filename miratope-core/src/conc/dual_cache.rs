@@ -0,0 +1,129 @@
+//! Caches the dual of a polytope, so that toggling back and forth between a
+//! polytope and its dual is instant and exactly involutive.
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    geometry::{Hypersphere, Point},
+    DualError,
+};
+
+/// The dual pairing stored in a [`DualCache`].
+#[derive(Clone, Debug)]
+struct DualPair {
+    /// The center of the reciprocation sphere.
+    center: Point<f64>,
+
+    /// The squared radius of the reciprocation sphere.
+    squared_radius: f64,
+
+    /// The polytope on the other side of the pairing.
+    other: Concrete,
+}
+
+/// Remembers the last polytope whose dual was taken, together with the
+/// reciprocation sphere. Taking the dual again with the same sphere swaps the
+/// remembered polytope back in, without computing anything, so that
+/// `dual(dual(P))` is bit-identical to `P`.
+///
+/// The cache doesn't know whether the polytope changed in between, so it must
+/// be [cleared](Self::clear) whenever the polytope changes in any other way.
+#[derive(Clone, Debug, Default)]
+pub struct DualCache(Option<DualPair>);
+
+impl DualCache {
+    /// Returns whether the cache holds a dual pairing.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Forgets the cached dual pairing.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Replaces a polytope by its dual with respect to a given sphere. If the
+    /// polytope was itself built as a dual with respect to the same sphere,
+    /// the polytope it came from is restored instead. If unsuccessful, leaves
+    /// the polytope unchanged and returns the index of a facet through the
+    /// reciprocation center.
+    pub fn dual_mut_with(
+        &mut self,
+        poly: &mut Concrete,
+        sphere: &Hypersphere<f64>,
+    ) -> Result<(), DualError> {
+        if let Some(pair) = &mut self.0 {
+            if pair.center == sphere.center && pair.squared_radius == sphere.squared_radius {
+                std::mem::swap(poly, &mut pair.other);
+                return Ok(());
+            }
+        }
+
+        let original = poly.clone();
+        poly.try_dual_mut_with(sphere)?;
+        self.0 = Some(DualPair {
+            center: sphere.center.clone(),
+            squared_radius: sphere.squared_radius,
+            other: original,
+        });
+
+        Ok(())
+    }
+
+    /// Replaces a polytope by its dual with respect to the unit hypersphere,
+    /// using the cache like [`Self::dual_mut_with`].
+    pub fn dual_mut(&mut self, poly: &mut Concrete) -> Result<(), DualError> {
+        let sphere = Hypersphere::unit(poly.dim().unwrap_or(1));
+        self.dual_mut_with(poly, &sphere)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::Ranked;
+
+    /// Toggles the dual of a dodecahedron many times, and checks that it ends
+    /// up exactly where it started.
+    #[test]
+    fn involutive() {
+        let doe = Concrete::platonic(3);
+        let mut poly = doe.clone();
+        let mut cache = DualCache::default();
+
+        for i in 0..100 {
+            cache.dual_mut(&mut poly).unwrap();
+            let expected = if i % 2 == 0 { 20 } else { 12 };
+            assert_eq!(poly.facet_count(), expected);
+        }
+
+        assert_eq!(poly.vertices, doe.vertices);
+        assert_eq!(poly.ranks(), doe.ranks());
+    }
+
+    /// Checks that the dual is recomputed with a different sphere, and after
+    /// clearing the cache.
+    #[test]
+    fn recompute() {
+        let mut poly = Concrete::platonic(1);
+        let mut cache = DualCache::default();
+        assert!(cache.is_empty());
+
+        cache.dual_mut(&mut poly).unwrap();
+        assert_eq!(poly.vertex_count(), 6);
+        assert!(!cache.is_empty());
+
+        // A different sphere gives the dual of the octahedron.
+        let sphere = Hypersphere::with_radius(Point::zeros(3), 2.0);
+        cache.dual_mut_with(&mut poly, &sphere).unwrap();
+        assert_eq!(poly.vertex_count(), 8);
+        assert!(poly
+            .vertices
+            .iter()
+            .all(|v| (v.norm() - 2.0 * 3f64.sqrt()).abs() < 1e-9));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        cache.dual_mut_with(&mut poly, &sphere).unwrap();
+        assert_eq!(poly.vertex_count(), 6);
+    }
+}
@@ -0,0 +1,411 @@
+//! Computes statistics on the edge lengths of a polytope, which help judge how
+//! far a polytope is from being equilateral, like a near-miss Johnson solid.
+
+use std::fmt::{self, Display};
+
+use super::{Concrete, ConcretePolytope};
+use crate::{abs::Ranked, float::Float};
+
+use serde::{Deserialize, Serialize};
+
+/// A group of edges whose lengths are within a relative tolerance of each
+/// other.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EdgeBucket {
+    /// The mean length of the edges in the bucket.
+    pub length: f64,
+
+    /// The indices of the edges in the bucket, in increasing order.
+    pub edges: Vec<usize>,
+}
+
+/// Statistics on the lengths of the non-degenerate edges of a polytope. If the
+/// polytope has no such edges, every value is zero and there are no buckets.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EdgeStats {
+    /// The least edge length.
+    pub min: f64,
+
+    /// The greatest edge length.
+    pub max: f64,
+
+    /// The mean edge length.
+    pub mean: f64,
+
+    /// The standard deviation of the edge lengths.
+    pub std_dev: f64,
+
+    /// The edges grouped by their lengths, in increasing order of length.
+    pub buckets: Vec<EdgeBucket>,
+}
+
+impl EdgeStats {
+    /// Groups a list of edge indices and lengths into buckets. A new bucket
+    /// starts whenever a length exceeds the least one in the current bucket by
+    /// more than the given fraction of the latter.
+    fn new(mut lengths: Vec<(usize, f64)>, tolerance: f64) -> Self {
+        if lengths.is_empty() {
+            return Self::default();
+        }
+
+        let count = lengths.len() as f64;
+        let mean = lengths.iter().map(|&(_, len)| len).sum::<f64>() / count;
+        let variance = lengths
+            .iter()
+            .map(|&(_, len)| (len - mean) * (len - mean))
+            .sum::<f64>()
+            / count;
+
+        lengths.sort_unstable_by(|(_, x), (_, y)| {
+            x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut buckets: Vec<(f64, Vec<(usize, f64)>)> = Vec::new();
+        for (idx, len) in lengths.iter().copied() {
+            match buckets.last_mut() {
+                Some((least, edges)) if len - *least <= tolerance * *least => {
+                    edges.push((idx, len))
+                }
+                _ => buckets.push((len, vec![(idx, len)])),
+            }
+        }
+
+        Self {
+            min: lengths[0].1,
+            max: lengths[lengths.len() - 1].1,
+            mean,
+            std_dev: variance.sqrt(),
+            buckets: buckets
+                .into_iter()
+                .map(|(_, edges)| {
+                    let length =
+                        edges.iter().map(|&(_, len)| len).sum::<f64>() / edges.len() as f64;
+                    let mut edges: Vec<_> = edges.into_iter().map(|(idx, _)| idx).collect();
+                    edges.sort_unstable();
+
+                    EdgeBucket { length, edges }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the number of edges that were measured.
+    pub fn edge_count(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.edges.len()).sum()
+    }
+
+    /// Returns the bucket with the most edges, or the shortest such bucket in
+    /// case of a tie. Returns `None` if there are no buckets.
+    pub fn modal(&self) -> Option<&EdgeBucket> {
+        self.buckets
+            .iter()
+            .rev()
+            .max_by_key(|bucket| bucket.edges.len())
+    }
+
+    /// Returns the indices of the edges whose length differs from the modal
+    /// length by more than a given fraction of it, in increasing order.
+    pub fn outliers(&self, relative: f64) -> Vec<usize> {
+        let modal = match self.modal() {
+            Some(modal) => modal.length,
+            None => return Vec::new(),
+        };
+
+        let mut outliers: Vec<_> = self
+            .buckets
+            .iter()
+            .filter(|bucket| (bucket.length - modal).abs() > relative * modal)
+            .flat_map(|bucket| bucket.edges.iter().copied())
+            .collect();
+        outliers.sort_unstable();
+        outliers
+    }
+}
+
+/// A tolerance for comparing a length to a reference one.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RelativeOrAbsolute {
+    /// The lengths may differ by up to this fraction of the reference.
+    Relative(f64),
+
+    /// The lengths may differ by up to this amount.
+    Absolute(f64),
+}
+
+impl Default for RelativeOrAbsolute {
+    fn default() -> Self {
+        Self::Relative(f64::EPS)
+    }
+}
+
+impl RelativeOrAbsolute {
+    /// Returns whether a length is within the tolerance of a reference length.
+    pub fn allows(self, reference: f64, len: f64) -> bool {
+        let diff = (len - reference).abs();
+        match self {
+            Self::Relative(tolerance) => diff <= tolerance * reference.abs(),
+            Self::Absolute(tolerance) => diff <= tolerance,
+        }
+    }
+}
+
+/// Whether the edges of a polytope all have the same length up to some
+/// tolerance, and which of them don't.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EquilateralReport {
+    /// The median length of the non-degenerate edges, which every edge is
+    /// compared against, or `None` if there are no such edges.
+    pub reference: Option<f64>,
+
+    /// Whether every edge has the reference length.
+    pub passed: bool,
+
+    /// The index and the length of every edge that doesn't have the reference
+    /// length, in increasing order of index. Degenerate edges have length 0.
+    pub outliers: Vec<(usize, f64)>,
+}
+
+impl EquilateralReport {
+    /// Compares a list of edge indices and lengths against their median, and
+    /// also reports a number of degenerate edges.
+    fn new(
+        lengths: Vec<(usize, f64)>,
+        degenerate: Vec<usize>,
+        tolerance: RelativeOrAbsolute,
+    ) -> Self {
+        let mut sorted: Vec<_> = lengths.iter().map(|&(_, len)| len).collect();
+        sorted.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+
+        // The lower median is always the length of some edge.
+        let reference = sorted.get(sorted.len().saturating_sub(1) / 2).copied();
+        let mut outliers: Vec<_> = degenerate.into_iter().map(|idx| (idx, 0.0)).collect();
+        if let Some(reference) = reference {
+            outliers.extend(
+                lengths
+                    .into_iter()
+                    .filter(|&(_, len)| !tolerance.allows(reference, len)),
+            );
+        }
+        outliers.sort_unstable_by_key(|&(idx, _)| idx);
+
+        Self {
+            reference,
+            passed: outliers.is_empty(),
+            outliers,
+        }
+    }
+
+    /// Returns the indices of the edges that don't have the reference length,
+    /// in increasing order.
+    pub fn outlier_edges(&self) -> Vec<usize> {
+        self.outliers.iter().map(|&(idx, _)| idx).collect()
+    }
+}
+
+impl Display for EquilateralReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Lists at most this many of the edges that don't have the reference
+        // length.
+        const SHOWN: usize = 10;
+
+        if self.passed {
+            return write!(f, "yes");
+        }
+
+        write!(f, "no, {} edges", self.outliers.len())?;
+        if let Some(reference) = self.reference {
+            write!(f, " differ from the median length {}", reference)?;
+        } else {
+            write!(f, " are degenerate")?;
+        }
+
+        let shown: Vec<_> = self
+            .outliers
+            .iter()
+            .take(SHOWN)
+            .map(|(idx, len)| format!("edge {} ({})", idx, len))
+            .collect();
+        write!(f, ": {}", shown.join(", "))?;
+        if self.outliers.len() > SHOWN {
+            write!(f, ", …")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for EdgeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.buckets.is_empty() {
+            return writeln!(f, "no edges");
+        }
+
+        writeln!(
+            f,
+            "min {}, max {}, mean {}, standard deviation {}",
+            self.min, self.max, self.mean, self.std_dev
+        )?;
+
+        for bucket in &self.buckets {
+            writeln!(f, "  {}: {} edges", bucket.length, bucket.edges.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Concrete {
+    /// Returns the index and the length of every non-degenerate edge of the
+    /// polytope.
+    pub fn edge_lengths(&self) -> Vec<(usize, f64)> {
+        (0..self.edge_count())
+            .filter_map(|idx| Some((idx, self.edge_len(idx)?)))
+            .collect()
+    }
+
+    /// Computes the statistics of the edge lengths of the polytope, grouping
+    /// together the lengths that agree up to a relative tolerance of
+    /// [`f64::EPS`].
+    pub fn edge_length_stats(&self) -> EdgeStats {
+        self.edge_length_stats_with(f64::EPS)
+    }
+
+    /// Computes the statistics of the edge lengths of the polytope, grouping
+    /// together the lengths that agree up to a given relative tolerance.
+    pub fn edge_length_stats_with(&self, tolerance: f64) -> EdgeStats {
+        EdgeStats::new(self.edge_lengths(), tolerance)
+    }
+
+    /// Compares the length of every edge of the polytope against the median
+    /// one up to a given tolerance. Degenerate edges never pass.
+    pub fn equilateral_report(&self, tolerance: RelativeOrAbsolute) -> EquilateralReport {
+        let mut lengths = Vec::new();
+        let mut degenerate = Vec::new();
+        for idx in 0..self.edge_count() {
+            match self.edge_len(idx) {
+                Some(len) => lengths.push((idx, len)),
+                None => degenerate.push(idx),
+            }
+        }
+
+        EquilateralReport::new(lengths, degenerate, tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    use approx::abs_diff_eq;
+    use itertools::Itertools;
+
+    /// Checks the edge lengths of a cube.
+    #[test]
+    fn cube() {
+        let stats = Concrete::cube().edge_length_stats();
+
+        assert_eq!(stats.buckets.len(), 1);
+        assert_eq!(stats.edge_count(), 12);
+        assert!(abs_diff_eq!(stats.mean, 1.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(stats.std_dev, 0.0, epsilon = f64::EPS));
+        assert!(stats.outliers(0.01).is_empty());
+    }
+
+    /// Checks that a slightly stretched cube has two kinds of edges.
+    #[test]
+    fn perturbed_cube() {
+        let mut cube = Concrete::cube();
+        for v in cube.vertices_mut() {
+            v[0] *= 1.01;
+        }
+
+        let stats = cube.edge_length_stats();
+        assert_eq!(stats.buckets.len(), 2);
+        assert!(abs_diff_eq!(stats.min, 1.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(stats.max, 1.01, epsilon = f64::EPS));
+
+        // The stretched edges are those parallel to the x-axis.
+        let stretched: Vec<_> = (0..cube.edge_count())
+            .filter(|&idx| {
+                let [v0, v1] = cube.edge_endpoints(idx).unwrap();
+                v0[0] != v1[0]
+            })
+            .collect();
+        let others: Vec<_> = (0..cube.edge_count())
+            .filter(|idx| !stretched.contains(idx))
+            .collect();
+
+        assert_eq!(stats.buckets[0].edges, others);
+        assert_eq!(stats.buckets[1].edges, stretched);
+        assert_eq!(stats.modal().unwrap().edges, others);
+        assert_eq!(stats.outliers(0.005), stretched);
+        assert!(stats.outliers(0.02).is_empty());
+
+        // A loose enough tolerance puts all edges together.
+        assert_eq!(cube.edge_length_stats_with(0.02).buckets.len(), 1);
+    }
+
+    /// A huge cube is only equilateral up to a relative tolerance.
+    #[test]
+    fn huge_cube() {
+        let mut cube = Concrete::cube();
+        cube.scale_mut(1e9);
+        cube.vertices[0][0] += 1e-6;
+
+        let report = cube.equilateral_report(RelativeOrAbsolute::default());
+        assert!(report.passed);
+        assert_eq!(report.reference, Some(1e9));
+        assert!(cube.is_equilateral());
+
+        // Only the edge along the displacement changes its length enough.
+        let report = cube.equilateral_report(RelativeOrAbsolute::Absolute(1e-9));
+        assert!(!report.passed);
+        assert_eq!(report.outliers.len(), 1);
+    }
+
+    /// A single stretched edge is reported along with its length, and doesn't
+    /// change the reference length. Moving the vertices of a cube would
+    /// stretch more than one edge, so the lengths are changed directly.
+    #[test]
+    fn stretched_edge() {
+        let mut lengths = Concrete::cube().edge_lengths();
+        let stretched = lengths.len() - 1;
+        lengths[stretched].1 = 1.5;
+
+        let report = EquilateralReport::new(lengths, Vec::new(), Default::default());
+        assert!(!report.passed);
+        assert_eq!(report.reference, Some(1.0));
+        assert_eq!(report.outliers, vec![(stretched, 1.5)]);
+        assert_eq!(
+            report.to_string(),
+            format!("no, 1 edges differ from the median length 1: edge {} (1.5)", stretched)
+        );
+    }
+
+    /// Displacing a vertex of a cube stretches exactly the edges through it.
+    #[test]
+    fn displaced_vertex() {
+        let mut cube = Concrete::cube();
+        let last = cube.vertex_count() - 1;
+        cube.vertices[last] *= 1.5;
+
+        let report = cube.equilateral_report(RelativeOrAbsolute::Relative(0.01));
+        assert!(!cube.is_equilateral());
+        assert_eq!(report.reference, Some(1.0));
+        assert_eq!(
+            report.outlier_edges(),
+            cube[(1, last)].sups.clone().into_iter().sorted().collect::<Vec<_>>()
+        );
+        for (idx, len) in report.outliers {
+            assert_eq!(cube.edge_len(idx), Some(len));
+        }
+    }
+
+    /// Checks that the nullitope and the point have no edges.
+    #[test]
+    fn no_edges() {
+        assert_eq!(Concrete::nullitope().edge_length_stats(), EdgeStats::default());
+        assert!(Concrete::point().edge_length_stats().modal().is_none());
+    }
+}
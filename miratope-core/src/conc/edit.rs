@@ -0,0 +1,295 @@
+//! Deletes a vertex or a facet of a polytope, and closes up the hole that's
+//! left behind.
+
+use std::collections::BTreeSet;
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::{AbstractError, Ranked},
+    float::Float,
+    geometry::{Matrix, Point, Vector},
+};
+
+use vec_like::*;
+
+/// An error while deleting an element of a polytope.
+#[derive(Clone, Copy, Debug)]
+pub enum EditError {
+    /// The polytope has rank less than 3, so deleting an element leaves
+    /// nothing to close up.
+    RankTooLow,
+
+    /// There's no element with the given index.
+    OutOfBounds(usize),
+
+    /// The facets next to the deleted facet don't meet at a single point.
+    NoApex,
+
+    /// The facets next to the deleted facet meet at the vertex with the given
+    /// index.
+    DegenerateApex(usize),
+
+    /// An element of the deleted facet is left between two elements that
+    /// don't lie in a common flat, so it can't be merged away.
+    NotFlat,
+
+    /// The result isn't a valid polytope.
+    Abstract(AbstractError),
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RankTooLow => write!(f, "only polygons and higher can be edited"),
+            Self::OutOfBounds(idx) => write!(f, "there's no element with index {}", idx),
+            Self::NoApex => write!(f, "the adjacent facets don't meet at a single point"),
+            Self::DegenerateApex(idx) => {
+                write!(f, "the adjacent facets meet at vertex {}", idx)
+            }
+            Self::NotFlat => write!(f, "the result has an element that can't be merged away"),
+            Self::Abstract(err) => write!(f, "the result isn't valid: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl From<AbstractError> for EditError {
+    fn from(err: AbstractError) -> Self {
+        Self::Abstract(err)
+    }
+}
+
+impl Concrete {
+    /// Checks that the polytope has rank at least 3, and that it has an
+    /// element of a given rank and index.
+    fn check_edit(&self, rank: usize, idx: usize) -> Result<(), EditError> {
+        if self.rank() < 3 {
+            Err(EditError::RankTooLow)
+        } else if idx >= self.el_count(rank) {
+            Err(EditError::OutOfBounds(idx))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Deletes a vertex of the polytope together with the edges through it,
+    /// and closes up the hole with a new facet spanned by its neighbors. See
+    /// [`Abstract::delete_vertex`](crate::abs::Abstract::delete_vertex).
+    ///
+    /// Deleting a vertex of a cube leaves 7 vertices, 12 edges, and 7 faces,
+    /// as the corner gets cut off by a triangle.
+    pub fn delete_vertex(&mut self, idx: usize) -> Result<(), EditError> {
+        self.check_edit(1, idx)?;
+        self.abs = self.abs.delete_vertex(idx)?;
+        self.vertices.remove(idx);
+        Ok(())
+    }
+
+    /// Deletes a facet of the polytope, and closes up the hole by extending
+    /// the facets next to it until they meet at a new vertex. See
+    /// [`Abstract::delete_facet`](crate::abs::Abstract::delete_facet).
+    ///
+    /// The elements of the deleted facet that end up in the middle of an
+    /// extended element, like the vertices of a face of a dodecahedron, are
+    /// merged away.
+    ///
+    /// Returns an error if the facets next to the deleted one don't meet at a
+    /// single point, like the lateral faces of a prism, or if they meet at a
+    /// vertex that's already there, like the faces of a tetrahedron.
+    pub fn delete_facet(&mut self, idx: usize) -> Result<(), EditError> {
+        let rank = self.rank();
+        if rank < 3 {
+            return Err(EditError::RankTooLow);
+        }
+        self.check_edit(rank - 1, idx)?;
+
+        let apex = self.apex(idx)?;
+        if let Some(v) = self
+            .vertices
+            .iter()
+            .position(|v| (v - &apex).norm() < f64::EPS)
+        {
+            return Err(EditError::DegenerateApex(v));
+        }
+
+        let abs = self.abs.delete_facet(idx)?;
+        let facet = self.abs.element_vertices(rank - 1, idx).unwrap();
+        let mut in_facet = vec![false; self.vertices.len()];
+
+        // The vertices of a deleted edge are deleted with it. Those of any
+        // other facet are kept track of, so that they can be merged away.
+        let mut vertices = if rank == 3 {
+            in_facet.truncate(self.vertices.len() - facet.len());
+            self.vertices
+                .iter()
+                .enumerate()
+                .filter(|(v, _)| !facet.contains(v))
+                .map(|(_, v)| v.clone())
+                .collect()
+        } else {
+            for &v in &facet {
+                in_facet[v] = true;
+            }
+            self.vertices.clone()
+        };
+
+        vertices.push(apex);
+        in_facet.push(false);
+        let mut poly = Self {
+            vertices,
+            abs,
+            units: self.units.clone(),
+        };
+        poly.dissolve_facet(&mut in_facet)?;
+
+        *self = poly;
+        Ok(())
+    }
+
+    /// Merges away the elements of a deleted facet that lie in exactly two
+    /// elements of the next rank, given which vertices belonged to the facet.
+    /// Higher ranks go first, since merging them away can leave more such
+    /// elements in the ranks below.
+    fn dissolve_facet(&mut self, in_facet: &mut Vec<bool>) -> Result<(), EditError> {
+        loop {
+            let rank = self.rank();
+            let found = (1..rank - 2).rev().find_map(|r| {
+                (0..self.el_count(r))
+                    .find(|&i| {
+                        self[(r, i)].sups.len() == 2
+                            && self
+                                .abs
+                                .element_vertices(r, i)
+                                .unwrap()
+                                .into_iter()
+                                .all(|v| in_facet[v])
+                    })
+                    .map(|i| (r, i))
+            });
+
+            let (r, i) = match found {
+                Some(found) => found,
+                None => return Ok(()),
+            };
+
+            let merged = self[(r, i)].sups.iter().copied().min().unwrap();
+            self.abs = self.abs.dissolve(r, i)?;
+            if r == 1 {
+                self.vertices.remove(i);
+                in_facet.remove(i);
+            }
+
+            if self.affine_hull(r + 1, merged).rank() != r {
+                return Err(EditError::NotFlat);
+            }
+        }
+    }
+
+    /// Returns the single point where the hyperplanes of the facets sharing a
+    /// ridge with a given facet meet.
+    fn apex(&self, idx: usize) -> Result<Point<f64>, EditError> {
+        let rank = self.rank();
+        let center = self.gravicenter().ok_or(EditError::NoApex)?;
+
+        let mut adjacent = BTreeSet::new();
+        for &ridge in &self[(rank - 1, idx)].subs {
+            adjacent.extend(self[(rank - 2, ridge)].sups.iter().filter(|&&f| f != idx));
+        }
+
+        // Each hyperplane is given by a normal and its dot product with any of
+        // its points.
+        let mut normals = Vec::with_capacity(adjacent.len());
+        let mut offsets = Vec::with_capacity(adjacent.len());
+        for f in adjacent {
+            let hull = self.affine_hull(rank - 1, f);
+            if !hull.is_hyperplane() {
+                return Err(EditError::NoApex);
+            }
+
+            let normal = hull.normal(&center).ok_or(EditError::NoApex)?;
+            offsets.push(normal.dot(&hull.project(&center)));
+            normals.push(normal);
+        }
+
+        // Solves the system in the least squares sense, and checks that the
+        // solution is exact.
+        let a = Matrix::from_rows(&normals.iter().map(|n| n.transpose()).collect::<Vec<_>>());
+        let b = Vector::from_vec(offsets);
+        let apex = (a.transpose() * &a)
+            .lu()
+            .solve(&(a.transpose() * &b))
+            .ok_or(EditError::NoApex)?;
+
+        if apex.iter().all(|x| x.is_finite()) && (a * &apex - b).amax() < f64::EPS {
+            Ok(apex)
+        } else {
+            Err(EditError::NoApex)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Deleting a vertex of a cube cuts off a corner.
+    #[test]
+    fn cube_vertex() {
+        let mut cube = Concrete::cube();
+        cube.delete_vertex(0).unwrap();
+        test(&cube, [1, 7, 12, 7, 1]);
+    }
+
+    /// Deleting a face of a dodecahedron extends the five faces next to it
+    /// into a pentagonal pyramid, whose lateral edges absorb the vertices of
+    /// the deleted face.
+    #[test]
+    fn dodecahedron_facet() {
+        let mut dodecahedron = Concrete::platonic(3);
+        dodecahedron.delete_facet(0).unwrap();
+        test(&dodecahedron, [1, 16, 25, 11, 1]);
+    }
+
+    /// Deleting an edge of a pentagon extends the edges next to it into a
+    /// quadrilateral.
+    #[test]
+    fn pentagon_facet() {
+        let mut pentagon = Concrete::polygon(5);
+        pentagon.delete_facet(0).unwrap();
+        test(&pentagon, [1, 4, 4, 1]);
+    }
+
+    /// The faces next to a face of a tetrahedron already meet at a vertex, and
+    /// those next to a face of a cube don't meet at all.
+    #[test]
+    fn degenerate_facet() {
+        let mut tetrahedron = Concrete::simplex(4);
+        assert!(matches!(
+            tetrahedron.delete_facet(0),
+            Err(EditError::DegenerateApex(_))
+        ));
+
+        let mut cube = Concrete::cube();
+        assert!(matches!(cube.delete_facet(0), Err(EditError::NoApex)));
+        test(&cube, [1, 8, 12, 6, 1]);
+    }
+
+    /// Polytopes of rank less than 3 give an error.
+    #[test]
+    fn rank_too_low() {
+        for mut poly in [Concrete::nullitope(), Concrete::point(), Concrete::dyad()] {
+            assert!(matches!(poly.delete_facet(0), Err(EditError::RankTooLow)));
+        }
+    }
+
+    /// Out of bounds indices give an error.
+    #[test]
+    fn out_of_bounds() {
+        assert!(matches!(
+            Concrete::cube().delete_vertex(8),
+            Err(EditError::OutOfBounds(8))
+        ));
+    }
+}
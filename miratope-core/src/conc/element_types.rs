@@ -1,6 +1,6 @@
 //! The code used to tally up the "element types" in a polytope.
 
-use std::{collections::BTreeMap, cmp::Ordering};
+use std::{borrow::Cow, collections::BTreeMap, cmp::Ordering, convert::TryFrom};
 
 use crate::{
     abs::{ElementMap, Ranked},
@@ -10,6 +10,7 @@ use crate::{
 };
 
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 use vec_like::*;
 
 /// Every element in a polytope can be assigned a "type" depending on its
@@ -24,6 +25,34 @@ pub struct ElementType {
     pub count: usize,
 }
 
+/// A key that identifies an element type across reclassifications of a
+/// polytope, such as after it's replaced by its dual. It's made of the number
+/// of facets of the elements of the type, and the number of these elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TypeSignature {
+    /// The number of facets of each element of the type.
+    pub facets: usize,
+
+    /// The number of elements of the type.
+    pub count: usize,
+}
+
+impl TypeSignature {
+    /// Returns the index of the signature among some others that best matches
+    /// this one. This is one equal to it if there's any, and otherwise the one
+    /// with the same number of facets and the closest count, if there's any.
+    pub fn best_match(self, others: &[Self]) -> Option<usize> {
+        others.iter().position(|&other| other == self).or_else(|| {
+            others
+                .iter()
+                .enumerate()
+                .filter(|(_, other)| other.facets == self.facets)
+                .min_by_key(|(_, other)| (other.count as isize - self.count as isize).abs())
+                .map(|(idx, _)| idx)
+        })
+    }
+}
+
 /// Stores the metadata associated with an element type.
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 struct TypeData {
@@ -46,6 +75,16 @@ pub const EL_NAMES: [&str; 25] = [
     "Ika", "Ikena", "Ikoda", "Iktra",
 ];
 
+/// Returns the name of the elements of a given rank, such as `Faces` for rank
+/// 2. The ranks past those in [`EL_NAMES`] get names like `30-elements`.
+pub fn element_name(rank: isize) -> Cow<'static, str> {
+    match usize::try_from(rank + 1) {
+        Ok(0) => Cow::Borrowed("Nullitopes"),
+        Ok(r) if r < EL_NAMES.len() => Cow::Borrowed(EL_NAMES[r]),
+        _ => Cow::Owned(format!("{}-elements", rank)),
+    }
+}
+
 /// Suffixes of elements of each rank.
 pub const EL_SUFFIXES: [&str; 25] = [
     "", "", "telon", "gon", "hedron", "choron", "teron", "peton", "exon", "zetton", "yotton",
@@ -224,6 +263,14 @@ impl Concrete {
         self.element_types_common().1
     }
 
+    /// Returns the signature of an element type of a given rank.
+    pub fn type_signature(&self, rank: usize, t: ElementType) -> TypeSignature {
+        TypeSignature {
+            facets: self[(rank, t.example)].subs.len(),
+            count: t.count,
+        }
+    }
+
     /// Prints all element types of a polytope into the console.
     pub fn print_element_types(&self) {
         for (r, types) in self.element_types().into_iter().enumerate().skip(1) {
@@ -232,7 +279,7 @@ impl Concrete {
                 break;
             }
 
-            println!("{}", EL_NAMES[r]);
+            println!("{}", element_name(r as isize - 1));
             for t in types {
                 let i = t.example;
                 println!(
@@ -248,3 +295,48 @@ impl Concrete {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every rank gets a name, even past the end of the table.
+    #[test]
+    fn element_names() {
+        assert_eq!(element_name(-1), "Nullitopes");
+        assert_eq!(element_name(0), "Vertices");
+        assert_eq!(element_name(2), "Faces");
+        assert_eq!(element_name(10), "Daka");
+        assert_eq!(element_name(23), "Iktra");
+        assert_eq!(element_name(24), "24-elements");
+
+        for rank in -1..=20 {
+            assert!(!element_name(rank).is_empty());
+        }
+    }
+
+    /// The face types of the cuboctahedron are matched to those of the rhombic
+    /// dodecahedron by their signatures.
+    #[test]
+    fn type_signatures() {
+        let face_signatures = |poly: &Concrete| -> Vec<TypeSignature> {
+            poly.element_types()[3]
+                .iter()
+                .map(|&t| poly.type_signature(3, t))
+                .collect()
+        };
+
+        let cuboctahedron = Concrete::archimedean(1);
+        let mut old = face_signatures(&cuboctahedron);
+        old.sort_by_key(|s| s.facets);
+        let triangles = TypeSignature { facets: 3, count: 8 };
+        let squares = TypeSignature { facets: 4, count: 6 };
+        assert_eq!(old, vec![triangles, squares]);
+        assert_eq!(squares.best_match(&old), Some(1));
+
+        let new = face_signatures(&Concrete::catalan(1));
+        assert_eq!(new, vec![TypeSignature { facets: 4, count: 12 }]);
+        assert_eq!(squares.best_match(&new), Some(0));
+        assert_eq!(triangles.best_match(&new), None);
+    }
+}
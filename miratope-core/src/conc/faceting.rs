@@ -970,8 +970,9 @@ fn faceting_subdim(
                             let mut poly = Concrete {
                                 vertices: new_vertices.clone(),
                                 abs: abs.clone(),
+                                units: None,
                             };
-                            poly.recenter();
+                            poly.recenter_mut();
 
                             let amount = poly.element_types()[1].len();
                             
@@ -983,7 +984,7 @@ fn faceting_subdim(
                                 let components = poly.split();
                                 let mut isogonal = true;
                                 for mut component in components {
-                                    component.recenter();
+                                    component.recenter_mut();
                                     if component.element_types()[1].len() > 1 {
                                         isogonal = false;
                                         break;
@@ -2198,6 +2199,7 @@ impl Concrete {
                         let poly = Concrete {
                             vertices: new_vertices,
                             abs: abs.clone(),
+                            units: None,
                         };
 
                         let mut fissary_status = "";
@@ -2274,11 +2276,11 @@ impl Concrete {
 
                 for i in used_facets_vec {
                     let mut poly = i.1.clone();
-                    poly.flatten();
+                    poly.ensure_flat();
                     if let Some(sphere) = poly.circumsphere() {
-                        poly.recenter_with(&sphere.center);
+                        poly.recenter_with_mut(&sphere.center);
                     } else {
-                        poly.recenter();
+                        poly.recenter_mut();
                     }
 
                     let mut fissary_status = "";
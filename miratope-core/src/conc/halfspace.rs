@@ -0,0 +1,446 @@
+//! The half-space representation of convex polytopes, which describes them as
+//! the intersection of the half-spaces bounded by their facet hyperplanes, and
+//! the conversions between it and the usual vertex representation.
+
+use std::fmt::Display;
+
+use super::{Concrete, ConcretePolytope, NotFullDimensional};
+use crate::{
+    abs::Ranked,
+    float::Float,
+    geometry::{Hyperplane, Point, PointCloud, Vector},
+};
+
+use itertools::Itertools;
+use nalgebra::DMatrix;
+
+/// A half-space, given by an outward unit normal `n` and an offset `b`. It
+/// consists of the points `x` with `n · x ≤ b`.
+pub type Halfspace = (Vector<f64>, f64);
+
+/// The most subsets of half-spaces that [`Concrete::from_halfspaces`] tries
+/// to intersect, since their number grows very quickly with the dimension.
+pub const MAX_COMBINATIONS: u128 = 10_000_000;
+
+/// A vertex found outside of the hyperplane of a facet, which shows that a
+/// polytope isn't convex.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvexityViolation {
+    /// The index of the facet.
+    pub facet: usize,
+
+    /// The index of the vertex.
+    pub vertex: usize,
+
+    /// How far the vertex is outside of the hyperplane.
+    pub distance: f64,
+}
+
+/// An error when converting between the vertex and the half-space
+/// representations of a polytope.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HalfspaceError {
+    /// The polytope doesn't span the space it lives in.
+    NotFullDimensional(NotFullDimensional),
+
+    /// The rank of the polytope doesn't match its dimension, so its facets
+    /// aren't hyperplanes.
+    RankMismatch {
+        /// The rank of the polytope.
+        rank: usize,
+
+        /// The dimension of the space the polytope lives in.
+        dim: usize,
+    },
+
+    /// The vertices of a facet with a given index don't span a hyperplane
+    /// that misses the gravicenter.
+    DegenerateFacet(usize),
+
+    /// Some vertices lie outside of some facet hyperplanes, so the polytope
+    /// isn't convex.
+    NotConvex(Vec<ConvexityViolation>),
+
+    /// The half-space with a given index has a zero normal or non-finite
+    /// values, has a different dimension than the first one, or lives in
+    /// fewer than two dimensions.
+    InvalidHalfspace(usize),
+
+    /// There are more subsets of half-spaces to intersect than
+    /// [`MAX_COMBINATIONS`].
+    TooManyCombinations(u128),
+
+    /// The half-spaces don't bound a full-dimensional polytope, as they're
+    /// either unbounded, flat or empty.
+    NotBounded,
+}
+
+impl Display for HalfspaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFullDimensional(err) => write!(f, "{}", err),
+            Self::RankMismatch { rank, dim } => write!(
+                f,
+                "a polytope of rank {} in {} dimensions has no facet hyperplanes",
+                rank, dim
+            ),
+            Self::DegenerateFacet(idx) => write!(f, "facet {} doesn't span a hyperplane", idx),
+            Self::NotConvex(violations) => {
+                let worst = violations
+                    .iter()
+                    .max_by(|a, b| a.distance.total_cmp(&b.distance))
+                    .unwrap();
+                write!(
+                    f,
+                    "the polytope isn't convex, {} vertices lie outside of facet hyperplanes, \
+                    like vertex {}, at distance {:e} from facet {}",
+                    violations.len(),
+                    worst.vertex,
+                    worst.distance,
+                    worst.facet
+                )
+            }
+            Self::InvalidHalfspace(idx) => write!(f, "half-space {} is invalid", idx),
+            Self::TooManyCombinations(count) => write!(
+                f,
+                "{} subsets of half-spaces would have to be intersected, more than the limit of {}",
+                count, MAX_COMBINATIONS
+            ),
+            Self::NotBounded => write!(f, "the half-spaces don't bound a polytope"),
+        }
+    }
+}
+
+impl std::error::Error for HalfspaceError {}
+
+impl From<NotFullDimensional> for HalfspaceError {
+    fn from(err: NotFullDimensional) -> Self {
+        Self::NotFullDimensional(err)
+    }
+}
+
+/// Returns the binomial coefficient `n` choose `k`, or `u128::MAX` if it
+/// overflows.
+fn binomial(n: usize, k: usize) -> u128 {
+    if k > n {
+        return 0;
+    }
+
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+
+    for i in 0..k as u128 {
+        result = match result.checked_mul(n as u128 - i) {
+            Some(product) => product / (i + 1),
+            None => return u128::MAX,
+        };
+    }
+
+    result
+}
+
+/// Returns the distance below which points are considered to be on a
+/// hyperplane, for coordinates of a given size.
+fn tolerance(scale: f64) -> f64 {
+    f64::EPS * scale.max(1.0)
+}
+
+impl Concrete {
+    /// Returns the outward unit normal and the offset of the hyperplane of
+    /// every facet, which together make up the half-space representation of
+    /// the polytope.
+    ///
+    /// The polytope must be full-dimensional and convex. The latter is checked
+    /// by verifying that every vertex lies on the inner side of every facet
+    /// hyperplane, and every vertex that doesn't is reported.
+    pub fn facet_hyperplanes(&self) -> Result<Vec<Halfspace>, HalfspaceError> {
+        self.check_full_dimensional()?;
+        let dim = self.dim_or();
+        let rank = self.rank();
+        if dim == 0 || rank != dim + 1 {
+            return Err(HalfspaceError::RankMismatch {
+                rank: rank.saturating_sub(1),
+                dim,
+            });
+        }
+
+        let gravicenter = self.gravicenter().unwrap();
+        let tolerance = tolerance(
+            self.vertices
+                .iter()
+                .map(|v| (v - &gravicenter).norm())
+                .fold(0.0, f64::max),
+        );
+
+        let mut halfspaces = Vec::with_capacity(self.facet_count());
+        for idx in 0..self.facet_count() {
+            let vertices = self.element_vertices_ref(rank - 1, idx).unwrap();
+            let hyperplane = Hyperplane::from_points_away(vertices.iter().copied(), &gravicenter)
+                .ok_or(HalfspaceError::DegenerateFacet(idx))?;
+
+            let normal = hyperplane.normal().clone();
+            let offset =
+                vertices.iter().map(|&v| normal.dot(v)).sum::<f64>() / vertices.len() as f64;
+            halfspaces.push((normal, offset));
+        }
+
+        let mut violations = Vec::new();
+        for (facet, (normal, offset)) in halfspaces.iter().enumerate() {
+            for (vertex, v) in self.vertices.iter().enumerate() {
+                let distance = normal.dot(v) - offset;
+                if distance > tolerance {
+                    violations.push(ConvexityViolation {
+                        facet,
+                        vertex,
+                        distance,
+                    });
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(halfspaces)
+        } else {
+            Err(HalfspaceError::NotConvex(violations))
+        }
+    }
+
+    /// Builds the convex polytope bounded by a set of half-spaces, given as
+    /// normals and offsets as in [`Self::facet_hyperplanes`]. The normals
+    /// needn't be unit vectors, and redundant half-spaces are allowed.
+    ///
+    /// The vertices are found by intersecting the hyperplanes of every subset
+    /// of as many half-spaces as there are dimensions, and keeping the points
+    /// within all other half-spaces. Since this takes exponential time, at most
+    /// [`MAX_COMBINATIONS`] subsets are tried.
+    pub fn from_halfspaces(halfspaces: &[Halfspace]) -> Result<Self, HalfspaceError> {
+        let dim = halfspaces.first().map_or(0, |(normal, _)| normal.len());
+
+        let mut normalized = Vec::with_capacity(halfspaces.len());
+        for (idx, (normal, offset)) in halfspaces.iter().enumerate() {
+            let norm = normal.norm();
+            if dim < 2
+                || normal.len() != dim
+                || !(norm > 0.0 && norm.is_finite() && offset.is_finite())
+            {
+                return Err(HalfspaceError::InvalidHalfspace(idx));
+            }
+
+            normalized.push((normal / norm, offset / norm));
+        }
+
+        if normalized.len() <= dim {
+            return Err(HalfspaceError::NotBounded);
+        }
+
+        let combinations = binomial(normalized.len(), dim);
+        if combinations > MAX_COMBINATIONS {
+            return Err(HalfspaceError::TooManyCombinations(combinations));
+        }
+
+        let tolerance = tolerance(
+            normalized
+                .iter()
+                .map(|(_, offset)| offset.abs())
+                .fold(0.0, f64::max),
+        );
+        let is_inside = |p: &Point<f64>| {
+            normalized
+                .iter()
+                .all(|(normal, offset)| normal.dot(p) - offset <= tolerance)
+        };
+
+        let mut vertices = PointCloud::new();
+        for subset in (0..normalized.len()).combinations(dim) {
+            let matrix = DMatrix::from_fn(dim, dim, |i, j| normalized[subset[i]].0[j]);
+            let lu = matrix.lu();
+            if lu.determinant().abs() < f64::EPS {
+                continue;
+            }
+
+            let offsets = Vector::from_iterator(dim, subset.iter().map(|&i| normalized[i].1));
+            if let Some(p) = lu.solve(&offsets) {
+                if is_inside(&p) {
+                    vertices.insert_or_get(p, tolerance);
+                }
+            }
+        }
+
+        // Unbounded regions still have vertices, but their hull has facets
+        // that aren't among the half-spaces.
+        let poly = Self::convex_hull(vertices.into_points()).ok_or(HalfspaceError::NotBounded)?;
+        for (normal, offset) in poly.facet_hyperplanes()? {
+            if !normalized.iter().any(|(n, b)| {
+                (n - &normal).norm() <= tolerance.sqrt() && (b - offset).abs() <= tolerance.sqrt()
+            }) {
+                return Err(HalfspaceError::NotBounded);
+            }
+        }
+
+        Ok(poly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Returns whether two polytopes have the same vertices, up to order.
+    fn same_vertices(p: &Concrete, q: &Concrete) -> bool {
+        p.vertex_count() == q.vertex_count()
+            && p.vertices
+                .iter()
+                .all(|v| q.vertices.iter().any(|w| (v - w).norm() < f64::EPS * 100.0))
+    }
+
+    /// Returns whether two lists of half-spaces are the same, up to order.
+    fn same_halfspaces(h: &[Halfspace], k: &[Halfspace]) -> bool {
+        h.len() == k.len()
+            && h.iter().all(|(n, b)| {
+                k.iter().any(|(m, c)| {
+                    (n - m).norm() < f64::EPS * 100.0 && (b - c).abs() < f64::EPS * 100.0
+                })
+            })
+    }
+
+    /// The facets of a cube are the planes at distance 1/2 from its center
+    /// along each axis.
+    #[test]
+    fn cube() {
+        let cube = Concrete::cube();
+        let halfspaces = cube.facet_hyperplanes().unwrap();
+        assert_eq!(halfspaces.len(), 6);
+        for (normal, offset) in &halfspaces {
+            assert!((normal.amax() - 1.0).abs() < f64::EPS);
+            assert!((offset - 0.5).abs() < f64::EPS);
+        }
+
+        let rebuilt = Concrete::from_halfspaces(&halfspaces).unwrap();
+        test(&rebuilt, [1, 8, 12, 6, 1]);
+        assert!(same_vertices(&rebuilt, &cube));
+    }
+
+    /// The 5-cell survives a round trip through both representations, and so
+    /// do its half-spaces.
+    #[test]
+    fn pentachoron() {
+        let simplex = Concrete::simplex(5);
+        let halfspaces = simplex.facet_hyperplanes().unwrap();
+        assert_eq!(halfspaces.len(), 5);
+
+        let rebuilt = Concrete::from_halfspaces(&halfspaces).unwrap();
+        test(&rebuilt, [1, 5, 10, 10, 5, 1]);
+        assert!(same_vertices(&rebuilt, &simplex));
+        assert!(same_halfspaces(
+            &rebuilt.facet_hyperplanes().unwrap(),
+            &halfspaces
+        ));
+    }
+
+    /// Reciprocating the half-spaces of a polytope about the unit sphere gives
+    /// the vertices of its dual, and going the other way gives its half-spaces
+    /// back.
+    #[test]
+    fn dual() {
+        for poly in [Concrete::cube(), Concrete::simplex(5)] {
+            let dual = poly.try_dual().unwrap();
+            let halfspaces = poly.facet_hyperplanes().unwrap();
+            let reciprocals = Concrete::from_halfspaces(
+                &dual
+                    .vertices
+                    .iter()
+                    .map(|v| (v.clone(), 1.0))
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+
+            assert!(same_vertices(&reciprocals, &poly));
+            assert!(same_halfspaces(
+                &reciprocals.facet_hyperplanes().unwrap(),
+                &halfspaces
+            ));
+            for (normal, offset) in &halfspaces {
+                let vertex = normal / *offset;
+                assert!(dual.vertices.iter().any(|v| (v - &vertex).norm() < 1e-6));
+            }
+        }
+    }
+
+    /// Redundant half-spaces don't show up as facets.
+    #[test]
+    fn redundant() {
+        let mut halfspaces = Concrete::cube().facet_hyperplanes().unwrap();
+        halfspaces.push((Vector::from_vec(vec![1.0, 1.0, 1.0]), 5.0));
+        halfspaces.push((Vector::from_vec(vec![2.0, 0.0, 0.0]), 1.0));
+
+        let rebuilt = Concrete::from_halfspaces(&halfspaces).unwrap();
+        test(&rebuilt, [1, 8, 12, 6, 1]);
+    }
+
+    /// Polytopes that aren't convex or full-dimensional, and half-spaces
+    /// that don't bound anything, are rejected.
+    #[test]
+    fn errors() {
+        let star = Concrete::star_polygon(5, 2);
+        match star.facet_hyperplanes() {
+            Err(HalfspaceError::NotConvex(violations)) => {
+                assert_eq!(violations.len(), 5);
+            }
+            other => panic!("expected a convexity violation, found {:?}", other),
+        }
+
+        let mut square = Concrete::polygon(4);
+        square.vertices = square
+            .vertices
+            .iter()
+            .map(|v| Point::from_vec(vec![v[0], v[1], 0.0]))
+            .collect();
+        assert!(matches!(
+            square.facet_hyperplanes(),
+            Err(HalfspaceError::NotFullDimensional(_))
+        ));
+
+        // A half-strip with a corner cut off.
+        let unbounded = [
+            (Vector::from_vec(vec![1.0, 0.0]), 1.0),
+            (Vector::from_vec(vec![-1.0, 0.0]), 5.0),
+            (Vector::from_vec(vec![0.0, 1.0]), 1.0),
+            (Vector::from_vec(vec![1.0, 1.0]), 1.5),
+        ];
+        assert_eq!(
+            Concrete::from_halfspaces(&unbounded).unwrap_err(),
+            HalfspaceError::NotBounded
+        );
+
+        let empty = [
+            (Vector::from_vec(vec![1.0, 0.0]), -1.0),
+            (Vector::from_vec(vec![-1.0, 0.0]), -1.0),
+            (Vector::from_vec(vec![0.0, 1.0]), 1.0),
+            (Vector::from_vec(vec![0.0, -1.0]), 1.0),
+        ];
+        assert_eq!(
+            Concrete::from_halfspaces(&empty).unwrap_err(),
+            HalfspaceError::NotBounded
+        );
+
+        assert_eq!(
+            Concrete::from_halfspaces(&[(Vector::zeros(3), 1.0)]).unwrap_err(),
+            HalfspaceError::InvalidHalfspace(0)
+        );
+
+        let many = vec![(Vector::from_element(12, 1.0), 1.0); 60];
+        assert!(matches!(
+            Concrete::from_halfspaces(&many),
+            Err(HalfspaceError::TooManyCombinations(_))
+        ));
+    }
+
+    #[test]
+    fn binomial() {
+        assert_eq!(super::binomial(5, 2), 10);
+        assert_eq!(super::binomial(60, 12), 1_399_358_844_975);
+        assert_eq!(super::binomial(3, 5), 0);
+        assert_eq!(super::binomial(1000, 500), u128::MAX);
+    }
+}
@@ -0,0 +1,545 @@
+//! Builds convex polytopes from their vertices, and some families of polytopes
+//! that are defined this way, like the step prisms and the duoantiprisms.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+};
+
+use super::Concrete;
+use crate::{
+    abs::{Abstract, AbstractBuilder, SubelementList, Subelements},
+    float::Float,
+    geometry::{
+        predicates::{ExactPredicates, FastPredicates, Predicates, RobustnessMode},
+        Matrix, Point, PointCloud, Subspace,
+    },
+    group::Group,
+    Polytope,
+};
+
+use gcd::Gcd;
+use itertools::Itertools;
+use vec_like::*;
+
+/// Returns the indices of the points on every facet of the convex hull of a
+/// set of points, by checking every hyperplane through `dim` of them. This
+/// takes time proportional to the number of such hyperplanes, so it's only
+/// suitable for small sets of points.
+fn hull_facets<P: Predicates>(
+    points: &[Point<f64>],
+    dim: usize,
+    predicates: &P,
+) -> HashSet<Vec<usize>> {
+    let mut facets = HashSet::new();
+
+    for idxs in (0..points.len()).combinations(dim) {
+        let simplex = idxs.iter().map(|&i| &points[i]).collect_vec();
+        let sides = match predicates.orientations(&simplex, points) {
+            Some(sides) => sides,
+            None => continue,
+        };
+
+        if sides.iter().all(|&s| s != Ordering::Greater)
+            || sides.iter().all(|&s| s != Ordering::Less)
+        {
+            facets.insert(sides.iter().positions(|&s| s == Ordering::Equal).collect());
+        }
+    }
+
+    facets
+}
+
+/// Returns whether the step prism with `n` vertices and a given list of steps
+/// is well-defined, that is, whether its vertices are distinct and span the
+/// space. This is the case exactly when the steps and their negatives are all
+/// distinct and nonzero modulo `n`, and `n` shares no common factor with the
+/// steps.
+pub fn step_prism_valid(n: usize, steps: &[usize]) -> bool {
+    if n == 0 || steps.is_empty() || steps.iter().fold(n, |g, &s| g.gcd(s)) != 1 {
+        return false;
+    }
+
+    let freqs = steps
+        .iter()
+        .flat_map(|&s| [s % n, (n - s % n) % n])
+        .collect_vec();
+    !freqs.contains(&0) && freqs.iter().all_unique()
+}
+
+/// Returns whether the `n1/d1`-`n2/d2` duoantiprism has a uniform
+/// realization, in which the lateral edges between the two kinds of
+/// antiprisms are as long as those of the base polygons. This happens when
+/// `1 / cos²(π d1 / 2n1) + 1 / cos²(π d2 / 2n2) = 4`, such as for the great
+/// duoantiprism `5`-`5/3`.
+pub fn duoantiprism_uniform(n1: usize, d1: usize, n2: usize, d2: usize) -> bool {
+    let sec_sq = |n, d| {
+        let cos = (f64::PI * f64::usize(d) / f64::usize(2 * n)).fcos();
+        1.0 / (cos * cos)
+    };
+
+    (sec_sq(n1, d1) + sec_sq(n2, d2) - 4.0).abs() < f64::EPS
+}
+
+/// An error while building a duoantiprism.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuoantiprismError {
+    /// Only convex duoantiprisms can be built as convex hulls.
+    Star,
+
+    /// The alternated vertices don't span 4D space.
+    Degenerate,
+}
+
+impl Display for DuoantiprismError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Star => write!(f, "only convex duoantiprisms can be built"),
+            Self::Degenerate => write!(f, "the duoantiprism is degenerate"),
+        }
+    }
+}
+
+impl std::error::Error for DuoantiprismError {}
+
+/// An error while building the orbit polytope of a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrbitError {
+    /// The group has no elements.
+    EmptyGroup,
+
+    /// The matrices of the group don't have the dimension of the point.
+    Dimension {
+        /// The dimension of the point.
+        expected: usize,
+
+        /// The dimension of the matrices.
+        found: usize,
+    },
+
+    /// The convex hull of the orbit couldn't be built, because some of its
+    /// coordinates aren't finite.
+    Hull,
+}
+
+impl Display for OrbitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyGroup => write!(f, "the group has no elements"),
+            Self::Dimension { expected, found } => write!(
+                f,
+                "the group acts on {}D space, but the point is {}D",
+                found, expected
+            ),
+            Self::Hull => write!(f, "the convex hull of the orbit couldn't be built"),
+        }
+    }
+}
+
+impl std::error::Error for OrbitError {}
+
+impl Concrete {
+    /// Builds the convex hull of a set of points. Points in the interior of
+    /// the hull or of any of its elements are discarded.
+    ///
+    /// Returns `None` if the points don't span the space they live in, if
+    /// there are fewer than two dimensions, or if the points don't all have the
+    /// same dimension and finite coordinates.
+    pub fn convex_hull(points: Vec<Point<f64>>) -> Option<Self> {
+        Self::convex_hull_with_mode(points, RobustnessMode::Fast)
+    }
+
+    /// Builds the convex hull of a set of points, deciding whether points lie
+    /// on the hyperplanes through others with a given
+    /// [robustness](RobustnessMode). In the adaptive mode, the combinatorics of
+    /// the hull are exactly those of the hull of the given coordinates, so
+    /// that nearly coplanar points split facets instead of merging them.
+    ///
+    /// Returns `None` in the same cases as [`Self::convex_hull`].
+    pub fn convex_hull_with_mode(points: Vec<Point<f64>>, mode: RobustnessMode) -> Option<Self> {
+        let dim = points.first()?.len();
+        if dim < 2
+            || points.len() <= dim
+            || points
+                .iter()
+                .any(|p| p.len() != dim || !p.iter().all(|x| x.is_finite()))
+        {
+            return None;
+        }
+
+        let mut facets = match mode {
+            RobustnessMode::Fast => {
+                let tolerance = 1e-9 * points.iter().map(|p| p.norm()).fold(1.0, f64::max);
+                hull_facets(&points, dim, &FastPredicates { tolerance })
+            }
+            RobustnessMode::Adaptive => hull_facets(&points, dim, &ExactPredicates),
+        }
+        .into_iter()
+        .collect_vec();
+
+        // A facet with every point means that the points lie on a hyperplane.
+        if facets.is_empty() || facets.iter().any(|facet| facet.len() == points.len()) {
+            return None;
+        }
+        facets.sort();
+
+        // The facets of an element of a convex polytope are the maximal proper
+        // intersections of it with the facets of the polytope. We find them
+        // rank by rank, until we're left with the vertices.
+        let mut levels = vec![facets.clone()];
+        let mut subs = Vec::new();
+
+        for _ in 1..dim {
+            let mut next = Vec::new();
+            let mut indices = HashMap::new();
+            let mut level_subs = SubelementList::new();
+
+            for el in levels.last().unwrap() {
+                let candidates: BTreeSet<Vec<usize>> = facets
+                    .iter()
+                    .map(|facet| el.iter().copied().filter(|v| facet.contains(v)).collect_vec())
+                    .filter(|sub| !sub.is_empty() && sub.len() < el.len())
+                    .collect();
+
+                let mut el_subs = Subelements::new();
+                for sub in &candidates {
+                    let is_maximal = candidates.iter().all(|other| {
+                        other.len() <= sub.len() || !sub.iter().all(|v| other.contains(v))
+                    });
+
+                    if is_maximal {
+                        let idx = *indices.entry(sub.clone()).or_insert_with(|| {
+                            next.push(sub.clone());
+                            next.len() - 1
+                        });
+                        el_subs.push(idx);
+                    }
+                }
+
+                el_subs.sort_unstable();
+                level_subs.push(el_subs);
+            }
+
+            levels.push(next);
+            subs.push(level_subs);
+        }
+
+        // The last level consists of the vertices.
+        let vertices = levels.pop().unwrap();
+        debug_assert!(vertices.iter().all(|v| v.len() == 1));
+
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 2);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        for level_subs in subs.into_iter().rev() {
+            builder.push(level_subs);
+        }
+        builder.push_max();
+
+        // Safety: the face lattice of a convex polytope is a valid polytope.
+        let abs = unsafe { builder.build() };
+        let vertices = vertices.into_iter().map(|v| points[v[0]].clone()).collect();
+        Self::try_new(vertices, abs).ok()
+    }
+
+    /// Builds the step prism with `n` vertices and a given list of steps, in
+    /// twice as many dimensions as there are steps. The coordinates of vertex
+    /// `k` are those of vertex `k * s` of a regular `n`-gon, for every step
+    /// `s`.
+    ///
+    /// Returns `None` if the step prism isn't [valid](step_prism_valid).
+    pub fn step_prism(n: usize, steps: &[usize]) -> Option<Self> {
+        if !step_prism_valid(n, steps) {
+            return None;
+        }
+
+        let vertices = (0..n)
+            .map(|k| {
+                steps
+                    .iter()
+                    .flat_map(|&s| {
+                        let angle = f64::TAU * f64::usize(k * s % n) / f64::usize(n);
+                        [angle.fcos(), angle.fsin()]
+                    })
+                    .collect_vec()
+                    .into()
+            })
+            .collect();
+
+        Self::convex_hull(vertices)
+    }
+
+    /// Builds the `n1/d1`-`n2/d2` duoantiprism, the alternation of the
+    /// `2n1/d1`-`2n2/d2` duoprism, as the convex hull of its vertices. Its
+    /// cells are `n1`-gonal and `n2`-gonal antiprisms, together with
+    /// tetrahedra.
+    ///
+    /// The base polygons have unit edge length, so that the polytope is
+    /// uniform exactly when [`duoantiprism_uniform`] holds. Otherwise the
+    /// lateral edges have a different length.
+    pub fn duoantiprism(
+        n1: usize,
+        d1: usize,
+        n2: usize,
+        d2: usize,
+    ) -> Result<Self, DuoantiprismError> {
+        if d1 != 1 || d2 != 1 {
+            return Err(DuoantiprismError::Star);
+        }
+
+        // The circumradius of an n-gon with unit edge.
+        let radius = |n| 0.5 / (f64::PI / f64::usize(n)).fsin();
+        let (r1, r2) = (radius(n1), radius(n2));
+
+        let mut vertices = Vec::with_capacity(2 * n1 * n2);
+        for i in 0..2 * n1 {
+            for j in (i % 2..2 * n2).step_by(2) {
+                let a1 = f64::PI * f64::usize(i) / f64::usize(n1);
+                let a2 = f64::PI * f64::usize(j) / f64::usize(n2);
+                vertices.push(
+                    vec![
+                        r1 * a1.fcos(),
+                        r1 * a1.fsin(),
+                        r2 * a2.fcos(),
+                        r2 * a2.fsin(),
+                    ]
+                    .into(),
+                );
+            }
+        }
+
+        Self::convex_hull(vertices).ok_or(DuoantiprismError::Degenerate)
+    }
+
+    /// Builds the convex hull of the orbit of a point under a group of
+    /// matrices, which is a vertex-transitive polytope whenever the matrices
+    /// are orthogonal. Images of the point that coincide up to a tolerance are
+    /// identified.
+    ///
+    /// The orbit may span a subspace of lower rank, in which case the hull is
+    /// taken within it. In particular, a point fixed by the whole group results
+    /// in a point.
+    pub fn orbit_polytope<I: Iterator<Item = Matrix<f64>> + Clone>(
+        seed: Point<f64>,
+        group: &Group<I>,
+    ) -> Result<Self, OrbitError> {
+        let dim = seed.len();
+        let tolerance = f64::EPS * seed.norm().max(1.0);
+        let mut orbit = PointCloud::new();
+
+        for mat in group.clone() {
+            if mat.nrows() != dim || mat.ncols() != dim {
+                return Err(OrbitError::Dimension {
+                    expected: dim,
+                    found: mat.nrows(),
+                });
+            }
+
+            orbit.insert_or_get(mat * &seed, tolerance);
+        }
+
+        if orbit.is_empty() {
+            return Err(OrbitError::EmptyGroup);
+        }
+        let orbit = orbit.into_points();
+
+        let subspace = Subspace::from_points(orbit.iter());
+        match subspace.rank() {
+            0 => Ok(Self::new(orbit, Abstract::point())),
+
+            // The orbit lies on a sphere, so there are two points on a line.
+            1 if orbit.len() == 2 => Ok(Self::new(orbit, Abstract::dyad())),
+            1 => Err(OrbitError::Hull),
+
+            // We take the hull within the subspace, and then put back the
+            // original coordinates.
+            _ => {
+                let flat = subspace.flatten_vec(&orbit).into_owned();
+                let mut hull = Self::convex_hull(flat.clone()).ok_or(OrbitError::Hull)?;
+
+                for v in hull.vertices.iter_mut() {
+                    let idx = flat.iter().position(|p| p == v).unwrap();
+                    *v = orbit[idx].clone();
+                }
+
+                Ok(hull)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, conc::ConcretePolytope, test, Polytope};
+
+    /// Checks that the convex hull of a cube's vertices is a cube, even with a
+    /// point in its interior.
+    #[test]
+    fn cube() {
+        let mut vertices = Concrete::cube().vertices;
+        vertices.push(Point::zeros(3));
+        test(&Concrete::convex_hull(vertices).unwrap(), [1, 8, 12, 6, 1]);
+    }
+
+    /// Checks the convex hull of the vertices of a cube, which lie on a sphere,
+    /// after perturbing them by about `1e-12`. The fast mode still sees a cube,
+    /// but the adaptive mode must split every face into two triangles, the
+    /// same way every time.
+    #[test]
+    fn perturbed_sphere() {
+        // A linear congruential generator, so that the test is deterministic.
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut perturbation = || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((seed >> 11) as f64 / (1_u64 << 53) as f64 - 0.5) * 2e-12
+        };
+
+        let points = Concrete::cube()
+            .vertices
+            .into_iter()
+            .map(|v| v.map(|x| x + perturbation()))
+            .collect_vec();
+
+        let fast = Concrete::convex_hull(points.clone()).unwrap();
+        test(&fast, [1, 8, 12, 6, 1]);
+
+        let adaptive =
+            Concrete::convex_hull_with_mode(points.clone(), RobustnessMode::Adaptive).unwrap();
+        test(&adaptive, [1, 8, 18, 12, 1]);
+        assert!(adaptive.abs.is_valid(true).is_ok());
+
+        for _ in 0..4 {
+            let again =
+                Concrete::convex_hull_with_mode(points.clone(), RobustnessMode::Adaptive).unwrap();
+            assert_eq!(again.vertices, adaptive.vertices);
+            assert_eq!(format!("{:?}", again.abs), format!("{:?}", adaptive.abs));
+        }
+    }
+
+    /// Checks the orbits of points under the group of the cube.
+    #[test]
+    fn orbit_polytope() {
+        let group = Group::hypercube(3).cache();
+
+        // A point in no mirror gives an omnitruncated cube.
+        let seed = Point::from_vec(vec![0.1, 0.25, 0.45]);
+        let omnitruncate = Concrete::orbit_polytope(seed, &group).unwrap();
+        test(&omnitruncate, [1, 48, 72, 26, 1]);
+
+        // A point on two mirrors gives an octahedron.
+        let seed = Point::from_vec(vec![1.0, 0.0, 0.0]);
+        let octahedron = Concrete::orbit_polytope(seed, &group).unwrap();
+        test(&octahedron, [1, 6, 12, 8, 1]);
+
+        // The center is fixed by the whole group.
+        let point = Concrete::orbit_polytope(Point::zeros(3), &group).unwrap();
+        test(&point, [1, 1]);
+        assert_eq!(point.vertices[0], Point::zeros(3));
+    }
+
+    /// Checks the orbits of points under groups of lower rank, or that can't
+    /// act on them.
+    #[test]
+    fn orbit_polytope_degenerate() {
+        // An orbit spanning a plane in 3D space.
+        let group = Group::hypercube(2).pad(1).cache();
+        let seed = Point::from_vec(vec![1.0, 0.0, 2.0]);
+        let square = Concrete::orbit_polytope(seed, &group).unwrap();
+        test(&square, [1, 4, 4, 1]);
+        assert!(square.vertices.iter().all(|v| v[2] == 2.0));
+
+        // An orbit of two points.
+        let group = Group::central_inv(2);
+        let seed = Point::from_vec(vec![1.0, 1.0]);
+        test(&Concrete::orbit_polytope(seed, &group).unwrap(), [1, 2, 1]);
+
+        let seed = Point::from_vec(vec![1.0, 0.0]);
+        assert_eq!(
+            Concrete::orbit_polytope(seed.clone(), &Group::hypercube(3)).unwrap_err(),
+            OrbitError::Dimension {
+                expected: 2,
+                found: 3
+            }
+        );
+
+        // Safety: this is not a group, but it's only used to check the error.
+        let empty = unsafe { Group::new(2, std::iter::empty::<Matrix<f64>>()) };
+        assert_eq!(
+            Concrete::orbit_polytope(seed, &empty).unwrap_err(),
+            OrbitError::EmptyGroup
+        );
+    }
+
+    /// Checks that points on a hyperplane have no convex hull.
+    #[test]
+    fn flat() {
+        let vertices: Vec<_> = Concrete::polygon(5)
+            .vertices
+            .into_iter()
+            .map(|v| Point::from_vec(vec![v[0], v[1], 0.0]))
+            .collect();
+        assert!(Concrete::convex_hull(vertices.clone()).is_none());
+        assert!(Concrete::convex_hull_with_mode(vertices, RobustnessMode::Adaptive).is_none());
+    }
+
+    /// Checks that the 5-vertex step prism with steps 1 and 2 is a regular
+    /// pentachoron.
+    #[test]
+    fn pentachoron() {
+        let pen = Concrete::step_prism(5, &[1, 2]).unwrap();
+        test(&pen, [1, 5, 10, 10, 5, 1]);
+        assert!(pen.is_equilateral());
+    }
+
+    /// Checks some step prisms against the Euler characteristic, and checks
+    /// that invalid step prisms are rejected.
+    #[test]
+    fn step_prisms() {
+        for (n, s) in [(7, 2), (8, 3), (9, 2), (10, 3), (12, 5)] {
+            let poly = Concrete::step_prism(n, &[1, s]).unwrap();
+            poly.assert_valid();
+            assert_eq!(poly.vertex_count(), n);
+            assert_eq!(
+                poly.el_count(1) + poly.el_count(3),
+                poly.el_count(2) + poly.el_count(4),
+                "{}-{} step prism",
+                n,
+                s
+            );
+        }
+
+        // Vertices coincide.
+        assert!(!step_prism_valid(6, &[2, 4]));
+        // The vertices lie on a plane.
+        assert!(Concrete::step_prism(6, &[1, 1]).is_none());
+        assert!(Concrete::step_prism(7, &[1, 6]).is_none());
+        assert!(!step_prism_valid(8, &[1, 4]));
+    }
+
+    /// Checks the cells of some duoantiprisms. The pentagonal duoantiprism
+    /// has 20 pentagonal antiprisms and 50 tetrahedra, and the 3-4
+    /// duoantiprism has 6 octahedra, 8 square antiprisms and 24 tetrahedra.
+    #[test]
+    fn duoantiprisms() {
+        let poly = Concrete::duoantiprism(5, 1, 5, 1).unwrap();
+        test(&poly, [1, 50, 200, 220, 70, 1]);
+        let antiprisms = poly[4].iter().filter(|cell| cell.subs.len() == 12).count();
+        assert_eq!(antiprisms, 20);
+
+        test(
+            &Concrete::duoantiprism(3, 1, 4, 1).unwrap(),
+            [1, 24, 96, 110, 38, 1],
+        );
+
+        assert_eq!(
+            Concrete::duoantiprism(5, 1, 5, 3).err(),
+            Some(DuoantiprismError::Star)
+        );
+        assert!(duoantiprism_uniform(5, 1, 5, 3));
+        assert!(!duoantiprism_uniform(5, 1, 5, 1));
+    }
+}
@@ -0,0 +1,268 @@
+//! Identifies polytopes combinatorially, by looking up their canonical forms in
+//! a registry of named polytopes.
+
+use std::collections::HashMap;
+
+use super::{shapes::PLATONIC_NAMES, Concrete};
+use crate::{
+    abs::{canonical::CanonicalAbstract, Abstract, AbstractBuilder, Ranked, SubelementList, Subelements},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// The greatest rank, as in the number of dimensions, of the simplices,
+/// hypercubes and orthoplexes in the [default registry](Registry::generated).
+const MAX_FAMILY_RANK: usize = 5;
+
+/// The greatest number of sides of the polygons in the [default
+/// registry](Registry::generated), by themselves or in products.
+const MAX_POLYGON_SIDES: usize = 12;
+
+/// The greatest number of sides of the polygons in the duoprisms of the
+/// [default registry](Registry::generated).
+const MAX_DUOPRISM_SIDES: usize = 8;
+
+impl Concrete {
+    /// Returns a hash of the combinatorial structure of the polytope, which
+    /// doesn't depend on its coordinates or on the order of its elements. See
+    /// [`Abstract::canonical_hash`].
+    pub fn combinatorial_fingerprint(&self) -> u64 {
+        self.abs.canonical_hash()
+    }
+}
+
+/// Builds the great dodecahedron, which has the edges of the icosahedron, and
+/// a pentagon for every vertex of it, made out of the edges opposite to it in
+/// the triangles around it. It's isomorphic to its dual, the small stellated
+/// dodecahedron.
+fn great_dodecahedron() -> Abstract {
+    let icosahedron = Concrete::platonic(4).abs;
+
+    let faces: SubelementList = (0..icosahedron.vertex_count())
+        .map(|v| {
+            let mut face = Subelements::new();
+            for &edge in &icosahedron[(1, v)].sups {
+                for &triangle in &icosahedron[(2, edge)].sups {
+                    for &opposite in &icosahedron[(3, triangle)].subs {
+                        if !icosahedron[(2, opposite)].subs.contains(&v)
+                            && !face.contains(&opposite)
+                        {
+                            face.push(opposite);
+                        }
+                    }
+                }
+            }
+
+            face
+        })
+        .collect();
+
+    let mut builder = AbstractBuilder::with_rank_capacity(4);
+    builder.push_min();
+    builder.push_vertices(icosahedron.vertex_count());
+    builder.push(
+        icosahedron[2]
+            .iter()
+            .map(|edge| edge.subs.clone())
+            .collect(),
+    );
+    builder.push(faces);
+    builder.push_max();
+
+    // Safety: the great dodecahedron is a regular polyhedron.
+    unsafe { builder.build() }
+}
+
+/// A registry of named polytopes, indexed by the hashes of their canonical
+/// forms. Hash hits are confirmed by comparing the canonical forms in full.
+#[derive(Clone, Debug, Default)]
+pub struct Registry(HashMap<u64, Vec<(CanonicalAbstract, String)>>);
+
+impl Registry {
+    /// Initializes an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of polytopes in the registry.
+    pub fn len(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+
+    /// Returns whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds a polytope to the registry with a given name. Returns `false` and
+    /// keeps the old name if an isomorphic polytope was already registered.
+    /// Polytopes whose canonical forms are
+    /// [incomplete](CanonicalAbstract::is_complete) can't be registered.
+    pub fn insert<S: Into<String>>(&mut self, poly: &Abstract, name: S) -> bool {
+        let form = poly.canonical_form();
+        if !form.is_complete() {
+            return false;
+        }
+
+        let bucket = self.0.entry(form.fingerprint()).or_default();
+        if bucket.iter().any(|(other, _)| other == &form) {
+            false
+        } else {
+            bucket.push((form, name.into()));
+            true
+        }
+    }
+
+    /// Returns the name of a polytope in the registry isomorphic to a given
+    /// one, if any.
+    pub fn identify(&self, poly: &Abstract) -> Option<&str> {
+        let form = poly.canonical_form();
+        if !form.is_complete() {
+            return None;
+        }
+
+        self.0
+            .get(&form.fingerprint())?
+            .iter()
+            .find(|(other, _)| other == &form)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Builds a registry with the families of polytopes that this crate
+    /// generates: the polygons, the simplices, hypercubes and orthoplexes, the
+    /// Platonic solids, the regular star polyhedra, and the prisms and
+    /// duoprisms of small polygons.
+    ///
+    /// Whenever two of these are isomorphic, the first name in the order above
+    /// is kept. In particular, the great icosahedron and great stellated
+    /// dodecahedron are identified with the icosahedron and dodecahedron, and
+    /// the small stellated dodecahedron with the great dodecahedron.
+    pub fn generated() -> Self {
+        let mut registry = Self::new();
+        registry.insert(&Abstract::nullitope(), "nullitope");
+        registry.insert(&Abstract::point(), "point");
+        registry.insert(&Abstract::dyad(), "dyad");
+
+        for n in 3..=MAX_POLYGON_SIDES {
+            registry.insert(&Abstract::polygon(n), format!("{}-gon", n));
+        }
+
+        for rank in 3..=MAX_FAMILY_RANK {
+            registry.insert(&Abstract::simplex(rank + 1), format!("{}-simplex", rank));
+            registry.insert(&Abstract::hypercube(rank + 1), format!("{}-cube", rank));
+            registry.insert(&Abstract::orthoplex(rank + 1), format!("{}-orthoplex", rank));
+        }
+
+        for (idx, name) in PLATONIC_NAMES.iter().enumerate() {
+            registry.insert(&Concrete::platonic(idx).abs, name.to_lowercase());
+        }
+
+        registry.insert(&great_dodecahedron(), "great dodecahedron");
+
+        for n in 3..=MAX_POLYGON_SIDES {
+            registry.insert(&Abstract::polygon(n).prism(), format!("{}-gonal prism", n));
+        }
+
+        for p in 3..=MAX_DUOPRISM_SIDES {
+            for q in p..=MAX_DUOPRISM_SIDES {
+                registry.insert(
+                    &Abstract::polygon(p).duoprism(&Abstract::polygon(q)),
+                    format!("{}-{} duoprism", p, q),
+                );
+            }
+        }
+
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::FromFile;
+
+    /// A cube written as an OFF file.
+    const CUBE: &str = "OFF
+        8 6 0
+        0 0 0
+        1 0 0
+        0 1 0
+        1 1 0
+        0 0 1
+        1 0 1
+        0 1 1
+        1 1 1
+        4 0 1 3 2
+        4 4 5 7 6
+        4 0 1 5 4
+        4 2 3 7 6
+        4 0 2 6 4
+        4 1 3 7 5";
+
+    /// A cube loaded from a file is identified as the 3-hypercube, and an
+    /// orthoplex as itself.
+    #[test]
+    fn cube() {
+        let registry = Registry::generated();
+        let cube = Concrete::from_off(CUBE).unwrap();
+
+        assert_eq!(registry.identify(&cube.abs), Some("3-cube"));
+        assert_eq!(
+            registry.identify(&Concrete::orthoplex(5).abs),
+            Some("4-orthoplex")
+        );
+        assert_eq!(
+            cube.combinatorial_fingerprint(),
+            Abstract::hypercube(4).canonical_hash()
+        );
+    }
+
+    /// The first name given to isomorphic polytopes is kept.
+    #[test]
+    fn first_name() {
+        let registry = Registry::generated();
+
+        assert_eq!(registry.identify(&Abstract::polygon(4)), Some("4-gon"));
+        assert_eq!(registry.identify(&Abstract::tetrahedron()), Some("3-simplex"));
+        assert_eq!(registry.identify(&Abstract::cube().prism()), Some("4-cube"));
+        assert_eq!(registry.identify(&Concrete::platonic(3).abs), Some("dodecahedron"));
+    }
+
+    /// The regular star polyhedra that aren't isomorphic to convex ones, which
+    /// are isomorphic to each other.
+    #[test]
+    fn star() {
+        let great_dodecahedron = great_dodecahedron();
+        crate::test(&great_dodecahedron, [1, 12, 30, 12, 1]);
+        assert!(!great_dodecahedron.is_isomorphic(&Concrete::platonic(3).abs));
+
+        let registry = Registry::generated();
+        assert_eq!(
+            registry.identify(&great_dodecahedron),
+            Some("great dodecahedron")
+        );
+        assert_eq!(
+            registry.identify(&great_dodecahedron.dual()),
+            Some("great dodecahedron")
+        );
+    }
+
+    /// Polytopes outside of the registry, or whose flags aren't connected,
+    /// aren't identified.
+    #[test]
+    fn unknown() {
+        let mut registry = Registry::new();
+        assert!(registry.is_empty());
+        assert!(registry.insert(&Abstract::cube(), "cube"));
+        assert!(!registry.insert(&Abstract::cube().dual().dual(), "other cube"));
+        assert_eq!(registry.len(), 1);
+
+        assert_eq!(registry.identify(&Abstract::octahedron()), None);
+
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::cube());
+        assert!(!registry.insert(&compound, "compound"));
+        assert_eq!(registry.identify(&compound), None);
+    }
+}
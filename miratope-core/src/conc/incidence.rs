@@ -0,0 +1,378 @@
+//! Computes the incidence matrix of a polytope, and compares it against
+//! incidence matrices written out as text.
+//!
+//! The rows and columns of the matrix are the element types of every proper
+//! rank, in the order given by [`Concrete::element_types`]. The diagonal holds
+//! the number of elements of each type, and every other entry holds the number
+//! of elements of the column's type incident to an element of the row's type.
+//! Different types of the same rank are never incident.
+
+use std::{collections::BTreeSet, fmt::Display};
+
+use super::Concrete;
+use crate::abs::Ranked;
+
+use vec_like::*;
+
+/// A reason why an incidence matrix written out as text doesn't match that of
+/// a polytope. Rows and columns are indexed from zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatrixMismatch {
+    /// An entry is neither a number nor a dot.
+    Parse {
+        /// The row of the entry.
+        row: usize,
+
+        /// The entry that couldn't be parsed.
+        entry: String,
+    },
+
+    /// The text has a different number of rows than the polytope has element
+    /// types.
+    Rows {
+        /// The number of element types of the polytope.
+        expected: usize,
+
+        /// The number of rows in the text.
+        found: usize,
+    },
+
+    /// A row of the text has fewer entries than the polytope has element
+    /// types.
+    Columns {
+        /// The row with missing entries.
+        row: usize,
+
+        /// The number of element types of the polytope.
+        expected: usize,
+
+        /// The number of entries in the row.
+        found: usize,
+    },
+
+    /// Some entries differ from those of the polytope.
+    Entries(Vec<EntryMismatch>),
+}
+
+/// An entry of an incidence matrix that differs from that of the polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryMismatch {
+    /// The row of the entry.
+    pub row: usize,
+
+    /// The column of the entry.
+    pub column: usize,
+
+    /// The entry in the text.
+    pub expected: usize,
+
+    /// The entry of the polytope.
+    pub found: usize,
+}
+
+impl Display for MatrixMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { row, entry } => {
+                write!(f, "could not parse entry \"{}\" in row {}", entry, row)
+            }
+            Self::Rows { expected, found } => {
+                write!(f, "expected {} rows but found {}", expected, found)
+            }
+            Self::Columns {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} entries but found {} in row {}",
+                expected, found, row
+            ),
+            Self::Entries(entries) => {
+                for (i, e) in entries.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(
+                        f,
+                        "row {}, column {}: expected {} but found {}",
+                        e.row, e.column, e.expected, e.found
+                    )?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for MatrixMismatch {}
+
+impl Concrete {
+    /// Returns the rank of every element type, together with the incidence
+    /// matrix.
+    fn incidence_data(&self) -> (Vec<usize>, Vec<Vec<usize>>) {
+        let rank = self.rank();
+        let (types, type_of_element) = self.element_types_common();
+
+        let mut type_ranks = Vec::new();
+        let mut type_indices = Vec::new();
+        for (r, types) in types.iter().enumerate().take(rank).skip(1) {
+            for t in 0..types.len() {
+                type_ranks.push(r);
+                type_indices.push(t);
+            }
+        }
+
+        let mut matrix = Vec::new();
+        for (&r, &t) in type_ranks.iter().zip(&type_indices) {
+            // The elements incident to the representative of the type.
+            let mut incident = vec![BTreeSet::new(); rank + 1];
+            incident[r].insert(types[r][t].example);
+
+            for s in (1..r).rev() {
+                incident[s] = incident[s + 1]
+                    .iter()
+                    .flat_map(|&el| self[(s + 1, el)].subs.iter().copied())
+                    .collect();
+            }
+
+            for s in r + 1..rank {
+                incident[s] = incident[s - 1]
+                    .iter()
+                    .flat_map(|&el| self[(s - 1, el)].sups.iter().copied())
+                    .collect();
+            }
+
+            let row = type_ranks
+                .iter()
+                .zip(&type_indices)
+                .map(|(&s, &u)| {
+                    if s == r {
+                        if u == t {
+                            types[r][t].count
+                        } else {
+                            0
+                        }
+                    } else {
+                        incident[s]
+                            .iter()
+                            .filter(|&&el| type_of_element[s][el] == u)
+                            .count()
+                    }
+                })
+                .collect();
+
+            matrix.push(row);
+        }
+
+        (type_ranks, matrix)
+    }
+
+    /// Returns the incidence matrix of the polytope, as described in the
+    /// [module docs](self).
+    pub fn incidence_matrix(&self) -> Vec<Vec<usize>> {
+        self.incidence_data().1
+    }
+
+    /// Writes out the incidence matrix in the usual layout, with the types of
+    /// every rank separated by lines, and dots for the pairs of types of the
+    /// same rank.
+    ///
+    /// ```
+    /// use miratope_core::{conc::Concrete, Polytope};
+    ///
+    /// assert_eq!(
+    ///     Concrete::cube().incidence_matrix_text(),
+    ///     "8 |  3 | 3\n\
+    ///      --+----+--\n\
+    ///      2 | 12 | 2\n\
+    ///      --+----+--\n\
+    ///      4 |  4 | 6\n"
+    /// );
+    /// ```
+    pub fn incidence_matrix_text(&self) -> String {
+        let (type_ranks, matrix) = self.incidence_data();
+        let n = type_ranks.len();
+
+        let entry = |i: usize, j: usize| {
+            if i != j && type_ranks[i] == type_ranks[j] {
+                ".".to_string()
+            } else {
+                matrix[i][j].to_string()
+            }
+        };
+
+        let widths: Vec<_> = (0..n)
+            .map(|j| (0..n).map(|i| entry(i, j).len()).max().unwrap_or_default())
+            .collect();
+
+        // Writes out the cells of a line, using a separator within the ranks
+        // and another one between them.
+        let line = |cell: &dyn Fn(usize) -> String, within: &str, between: &str| {
+            let mut line = String::new();
+            for j in 0..n {
+                if j != 0 {
+                    line.push_str(if type_ranks[j] == type_ranks[j - 1] {
+                        within
+                    } else {
+                        between
+                    });
+                }
+                line.push_str(&cell(j));
+            }
+            line.push('\n');
+            line
+        };
+
+        let mut text = String::new();
+        for i in 0..n {
+            if i != 0 && type_ranks[i] != type_ranks[i - 1] {
+                text.push_str(&line(&|j| "-".repeat(widths[j]), "-", "-+-"));
+            }
+
+            text.push_str(&line(
+                &|j| format!("{:>width$}", entry(i, j), width = widths[j]),
+                " ",
+                " | ",
+            ));
+        }
+
+        text
+    }
+
+    /// Compares the incidence matrix of the polytope against one written out
+    /// as text, such as that returned by [`Self::incidence_matrix_text`].
+    ///
+    /// Every line of the text that has more than dashes, plus signs and bars
+    /// is a row, and dots count as zeros. Any labels before the entries of a
+    /// row are ignored. The element types must be listed in the same order as
+    /// in [`Self::element_types`].
+    pub fn check_against_incidence_matrix(&self, text: &str) -> Result<(), MatrixMismatch> {
+        let matrix = self.incidence_matrix();
+        let n = matrix.len();
+
+        let rows: Vec<_> = text
+            .lines()
+            .filter(|line| line.chars().any(|c| !c.is_whitespace() && !"-+|".contains(c)))
+            .collect();
+
+        if rows.len() != n {
+            return Err(MatrixMismatch::Rows {
+                expected: n,
+                found: rows.len(),
+            });
+        }
+
+        let mut mismatches = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let entries: Vec<_> = row
+                .split(|c: char| c.is_whitespace() || c == '|')
+                .filter(|s| !s.is_empty())
+                .collect();
+
+            if entries.len() < n {
+                return Err(MatrixMismatch::Columns {
+                    row: i,
+                    expected: n,
+                    found: entries.len(),
+                });
+            }
+
+            for (j, &entry) in entries[entries.len() - n..].iter().enumerate() {
+                let expected = if entry == "." {
+                    0
+                } else {
+                    entry.parse().map_err(|_| MatrixMismatch::Parse {
+                        row: i,
+                        entry: entry.to_string(),
+                    })?
+                };
+
+                if expected != matrix[i][j] {
+                    mismatches.push(EntryMismatch {
+                        row: i,
+                        column: j,
+                        expected,
+                        found: matrix[i][j],
+                    });
+                }
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(MatrixMismatch::Entries(mismatches))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// The cube has one type of each rank.
+    #[test]
+    fn cube() {
+        let cube = Concrete::cube();
+        assert_eq!(
+            cube.incidence_matrix(),
+            vec![vec![8, 3, 3], vec![2, 12, 2], vec![4, 4, 6]]
+        );
+        assert_eq!(cube.check_against_incidence_matrix(&cube.incidence_matrix_text()), Ok(()));
+    }
+
+    /// The triangular prism has two types of edges and faces.
+    #[test]
+    fn trip() {
+        let trip = Concrete::polygon(3).prism();
+        let text = trip.incidence_matrix_text();
+        assert_eq!(trip.check_against_incidence_matrix(&text), Ok(()));
+        assert_eq!(text.matches('.').count(), 4);
+
+        assert_eq!(
+            trip.incidence_matrix(),
+            vec![
+                vec![6, 1, 2, 2, 1],
+                vec![2, 3, 0, 2, 0],
+                vec![2, 0, 6, 1, 1],
+                vec![4, 2, 2, 3, 0],
+                vec![3, 0, 3, 0, 2],
+            ]
+        );
+    }
+
+    /// Labels before the rows are skipped, and a wrong entry is reported.
+    #[test]
+    fn mismatch() {
+        let cube = Concrete::cube();
+        let text = "
+            . . . | 8 |  3 | 3
+            ------+---+----+--
+            x . . | 2 | 12 | 2
+            ------+---+----+--
+            x4o . | 4 |  3 | 6
+        ";
+
+        assert_eq!(
+            cube.check_against_incidence_matrix(text),
+            Err(MatrixMismatch::Entries(vec![EntryMismatch {
+                row: 2,
+                column: 1,
+                expected: 3,
+                found: 4
+            }]))
+        );
+
+        assert_eq!(
+            cube.check_against_incidence_matrix("8 3 3\n2 12 2"),
+            Err(MatrixMismatch::Rows {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
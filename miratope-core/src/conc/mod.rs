@@ -1,26 +1,57 @@
 //! Declares the [`Concrete`] polytope type and all associated data structures.
 
+pub mod angles;
+pub mod canonical;
+pub mod compound;
 pub mod cycle;
+pub mod dual_cache;
+pub mod edge_stats;
+pub mod edit;
 pub mod element_types;
 pub mod faceting;
+pub mod halfspace;
+pub mod hull;
+pub mod identify;
+pub mod incidence;
+pub mod net;
+pub mod quotient;
+pub mod report;
+pub mod schlafli;
+pub mod self_dual;
+pub mod simplicial;
+pub mod slices;
+pub mod shapes;
+pub mod snap;
 pub mod symmetry;
+pub mod tower;
+pub mod uniformity;
+pub mod units;
+pub mod vertex_fold;
 
 use std::{
-    collections::{HashMap, HashSet, BTreeMap, BTreeSet},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
     ops::{Index, IndexMut}, iter,
+    sync::Arc,
 };
 
 use super::{
     abs::{
         flag::{Flag, FlagChanges, FlagEvent, OrientedFlagIter},
+        provenance::{Provenance, Source},
+        valid::AbstractError,
         Abstract, ElementList, Ranked, SubelementList,
     },
-    DualError, Polytope,
+    binary_power, AntiprismError, DualError, Polytope, ProductKind,
 };
 use crate::{
     abs::{AbstractBuilder, Element, ElementMap, Subelements, Superelements, Ranks},
+    conc::cycle::CycleList,
     float::Float,
-    geometry::*,
+    geometry::{
+        predicates::{ExactPredicates, Predicates, RobustnessMode},
+        *,
+    },
 };
 use approx::abs_diff_eq;
 use itertools::Itertools;
@@ -39,6 +70,318 @@ pub struct Concrete {
 
     /// The underlying abstract polytope.
     pub abs: Abstract,
+
+    /// The unit the coordinates are given in, if known. It's read from and
+    /// written to files, but operations that build a new polytope don't carry
+    /// it over.
+    pub units: Option<units::UnitInfo>,
+}
+
+// Polytopes are sent to other threads to be worked on, and stored as Bevy
+// components, both of which need them to be `Send + Sync`.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Abstract>();
+    assert_send_sync::<Concrete>();
+};
+
+/// Returns a mutable reference to a shared polytope. The polytope is only
+/// cloned if something else holds onto it, in which case the reference now
+/// points to the clone. See [`Arc::make_mut`].
+pub fn make_mut(poly: &mut Arc<Concrete>) -> &mut Concrete {
+    Arc::make_mut(poly)
+}
+
+/// An error when applying a matrix to a polytope, in which the matrix isn't
+/// square with the dimension of the polytope as its size.
+#[derive(Clone, Copy, Debug)]
+pub struct DimensionError {
+    /// The dimension of the polytope.
+    pub expected: usize,
+
+    /// The number of rows and columns of the matrix.
+    pub found: (usize, usize),
+}
+
+impl std::fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected a {0} by {0} matrix, found a {1} by {2} one",
+            self.expected, self.found.0, self.found.1
+        )
+    }
+}
+
+impl std::error::Error for DimensionError {}
+
+/// An error when an operation requires a polytope to span the space it lives
+/// in, but it doesn't. Such polytopes should be [flattened](ConcretePolytope::flatten)
+/// first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotFullDimensional {
+    /// The rank of the affine hull of the polytope.
+    pub rank: usize,
+
+    /// The dimension of the space the polytope lives in.
+    pub dim: usize,
+}
+
+impl std::fmt::Display for NotFullDimensional {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the polytope only spans {} of its {} dimensions, flatten it first",
+            self.rank, self.dim
+        )
+    }
+}
+
+impl std::error::Error for NotFullDimensional {}
+
+/// An error when building a concrete polytope from a list of vertices that
+/// doesn't fit its abstract polytope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcreteError {
+    /// The number of vertices doesn't match the abstract vertex count.
+    VertexCount {
+        /// The number of abstract vertices.
+        expected: usize,
+
+        /// The number of concrete vertices.
+        found: usize,
+    },
+
+    /// A vertex doesn't have as many coordinates as the first one.
+    Dimension {
+        /// The index of the vertex.
+        idx: usize,
+
+        /// The number of coordinates of the first vertex.
+        expected: usize,
+
+        /// The number of coordinates of the vertex.
+        found: usize,
+    },
+
+    /// A vertex has a NaN or infinite coordinate.
+    NonFinite {
+        /// The index of the vertex.
+        idx: usize,
+    },
+
+    /// The underlying abstract polytope is invalid.
+    Abstract(AbstractError),
+}
+
+impl std::fmt::Display for ConcreteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VertexCount { expected, found } => write!(
+                f,
+                "expected {} vertices but found {}",
+                expected, found
+            ),
+            Self::Dimension {
+                idx,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} coordinates but found {} in vertex {}",
+                expected, found, idx
+            ),
+            Self::NonFinite { idx } => write!(f, "vertex {} has a non-finite coordinate", idx),
+            Self::Abstract(err) => write!(f, "invalid abstract polytope: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ConcreteError {}
+
+/// The [planarity](ConcretePolytope::face_planarity) above which a face is
+/// considered skew. The areas of skew faces are only approximate.
+pub const SKEW_PLANARITY: f64 = 1e-3;
+
+/// Returns the area and the centroid of a polygon given by one or more cycles
+/// of vertices, after projecting these onto a plane. The areas of the cycles
+/// add up regardless of their orientation. If the polygon has no area, its
+/// centroid is the average of its vertices.
+fn polygon_area_centroid(cycles: &[Vec<&Point<f64>>], plane: &Subspace<f64>) -> (f64, Point<f64>) {
+    let mut area = 0.0;
+    let mut moment = Point::zeros(plane.rank());
+
+    for cycle in cycles {
+        let flat: Vec<_> = cycle.iter().map(|&p| plane.flatten(p)).collect();
+        let mut cycle_area = 0.0;
+        let mut cycle_moment = Point::zeros(plane.rank());
+
+        // The shoelace formula, along with the first moments of the triangles
+        // it adds up.
+        for (p, q) in flat.iter().zip(flat.iter().cycle().skip(1)) {
+            let cross = p[0] * q[1] - p[1] * q[0];
+            cycle_area += cross;
+            cycle_moment += (p + q) * cross;
+        }
+
+        area += cycle_area.abs() / 2.0;
+        moment += cycle_moment * (cycle_area.signum() / 6.0);
+    }
+
+    let centroid = if area > 0.0 {
+        let flat = moment / area;
+        plane
+            .basis
+            .iter()
+            .zip(flat.iter())
+            .fold(plane.offset.clone(), |centroid, (b, &x)| centroid + b * x)
+    } else {
+        let count = cycles.iter().map(Vec::len).sum::<usize>() as f64;
+        cycles.iter().flatten().copied().sum::<Point<f64>>() / count
+    };
+
+    (area, centroid)
+}
+
+/// Returns the rank of the affine hull of a set of points. Stops as soon as
+/// the points are found to span the space.
+fn affine_rank(points: &[Point<f64>]) -> usize {
+    let mut points = points.iter();
+    let mut subspace = match points.next() {
+        Some(p) => Subspace::new(p.clone()),
+        None => return 0,
+    };
+
+    for p in points {
+        if subspace.is_full_rank() {
+            break;
+        }
+
+        subspace.add(p);
+    }
+
+    subspace.rank()
+}
+
+/// An error when morphing a polytope into another.
+#[derive(Clone, Debug)]
+pub enum MorphError {
+    /// The polytopes don't have the same number of vertices.
+    VertexCount {
+        /// The vertex count of the first polytope.
+        expected: usize,
+
+        /// The vertex count of the second polytope.
+        found: usize,
+    },
+
+    /// The polytopes don't live in the same dimension.
+    Dimension {
+        /// The dimension of the first polytope.
+        expected: usize,
+
+        /// The dimension of the second polytope.
+        found: usize,
+    },
+
+    /// The nearest neighbor matching between the vertices of both polytopes
+    /// isn't a bijection. Stores the vertices of the second polytope that
+    /// weren't matched to any vertex of the first.
+    Unmatched(Vec<usize>),
+
+    /// Some vertex of either polytope has a coordinate that isn't finite, so
+    /// the vertices can't be matched by distance.
+    NonFinite,
+}
+
+impl std::fmt::Display for MorphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VertexCount { expected, found } => write!(
+                f,
+                "expected a polytope with {} vertices, found {}",
+                expected, found
+            ),
+            Self::Dimension { expected, found } => write!(
+                f,
+                "expected a polytope of dimension {}, found {}",
+                expected, found
+            ),
+            Self::Unmatched(unmatched) => {
+                write!(f, "vertices {:?} could not be matched", unmatched)
+            }
+            Self::NonFinite => write!(f, "some vertex has non-finite coordinates"),
+        }
+    }
+}
+
+impl std::error::Error for MorphError {}
+
+/// An error when radially projecting a polytope onto a hypersphere, storing the
+/// index of a vertex that coincides with its center.
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectError(pub usize);
+
+impl std::fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {} coincides with the projection center", self.0)
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+/// An error when scaling a factor of a product by a non-positive or infinite
+/// amount, storing the offending scale factor.
+#[derive(Clone, Copy, Debug)]
+pub struct ScaleError(pub f64);
+
+impl std::fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "scale factor {} is not a positive finite number", self.0)
+    }
+}
+
+impl std::error::Error for ScaleError {}
+
+/// The center of the hypersphere used to take the dual of the base of an
+/// antiprism.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DualCenter {
+    /// The origin.
+    Origin,
+
+    /// The circumcenter of the base.
+    Circumcenter,
+
+    /// The gravicenter of the base.
+    Gravicenter,
+}
+
+impl DualCenter {
+    /// All of the centers, in the order in which they're tried.
+    pub const ALL: [Self; 3] = [Self::Origin, Self::Circumcenter, Self::Gravicenter];
+}
+
+impl std::fmt::Display for DualCenter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Origin => "origin",
+            Self::Circumcenter => "circumcenter",
+            Self::Gravicenter => "gravicenter",
+        })
+    }
+}
+
+/// Checks that the scale factors of both factors of a product are positive
+/// and finite.
+fn check_scales(p_scale: f64, q_scale: f64) -> Result<(), ScaleError> {
+    for scale in [p_scale, q_scale] {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(ScaleError(scale));
+        }
+    }
+
+    Ok(())
 }
 
 impl Index<usize> for Concrete {
@@ -73,28 +416,450 @@ impl IndexMut<(usize, usize)> for Concrete {
     }
 }
 
+/// Checks that there are as many vertices as abstract ones, that they all have
+/// the same dimension, and that none of their coordinates is NaN or infinite.
+fn check_vertices(vertices: &[Point<f64>], abs: &Abstract) -> Result<(), ConcreteError> {
+    // There must be as many abstract vertices as concrete ones.
+    if abs.vertex_count() != vertices.len() {
+        return Err(ConcreteError::VertexCount {
+            expected: abs.vertex_count(),
+            found: vertices.len(),
+        });
+    }
+
+    // All vertices must have the same dimension, and finite coordinates.
+    if let Some(vertex0) = vertices.get(0) {
+        for (idx, vertex) in vertices.iter().enumerate() {
+            if vertex.len() != vertex0.len() {
+                return Err(ConcreteError::Dimension {
+                    idx,
+                    expected: vertex0.len(),
+                    found: vertex.len(),
+                });
+            }
+
+            if !vertex.iter().all(|x| x.is_finite()) {
+                return Err(ConcreteError::NonFinite { idx });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl Concrete {
     /// Initializes a new concrete polytope from a set of vertices and an
-    /// underlying abstract polytope. Does some debug assertions on the input.
+    /// underlying abstract polytope.
+    ///
+    /// # Panics
+    /// Panics if the vertices don't fit the abstract polytope. Use
+    /// [`Self::try_new`] for input that hasn't been checked.
     pub fn new(vertices: Vec<Point<f64>>, abs: Abstract) -> Self {
-        // There must be as many abstract vertices as concrete ones.
-        debug_assert_eq!(
-            abs.vertex_count(),
-            vertices.len(),
-            "Abstract vertex count doesn't match concrete vertex count!"
+        match Self::try_new(vertices, abs) {
+            Ok(poly) => poly,
+            Err(err) => panic!("invalid concrete polytope: {}", err),
+        }
+    }
+
+    /// Initializes a new concrete polytope from a set of vertices and an
+    /// underlying abstract polytope. Fails if there aren't as many vertices as
+    /// abstract ones, if they don't all have the same dimension, or if any of
+    /// their coordinates is NaN or infinite.
+    pub fn try_new(vertices: Vec<Point<f64>>, abs: Abstract) -> Result<Self, ConcreteError> {
+        check_vertices(&vertices, &abs)?;
+        Ok(Self {
+            vertices,
+            abs,
+            units: None,
+        })
+    }
+
+    /// Checks that the polytope is valid, namely that its vertices fit its
+    /// abstract polytope as in [`Self::try_new`], and that the latter is valid
+    /// as in [`Abstract::is_valid`]. Fissary polytopes are allowed.
+    pub fn validate(&self) -> Result<(), ConcreteError> {
+        check_vertices(&self.vertices, &self.abs)?;
+        self.abs.is_valid(false).map_err(ConcreteError::Abstract)
+    }
+
+    /// Returns a hash of the vertices and elements of the polytope. It doesn't
+    /// depend on the order of the subelements of each element, so that sorting
+    /// them keeps it. Two polytopes with the same fingerprint are almost
+    /// certainly equal.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        for v in &self.vertices {
+            for x in v.iter() {
+                x.to_bits().hash(&mut hasher);
+            }
+        }
+
+        for (r, _, el) in self.abs.element_iter() {
+            // A sum doesn't depend on the order of its terms.
+            let subs = el.subs.iter().fold(0u64, |acc, &sub| {
+                acc.wrapping_add((sub as u64 ^ 0x5555_5555).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            });
+            (r, el.subs.len(), subs).hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Returns the greatest distance from the origin to a vertex with finite
+    /// coordinates, or `None` if there are no such vertices.
+    pub fn bounding_radius(&self) -> Option<f64> {
+        self.vertices
+            .iter()
+            .map(|v| v.norm())
+            .filter(|r| r.is_finite())
+            .reduce(f64::max)
+    }
+
+    /// Returns the centroids of the first elements of a given rank, up to a
+    /// given count, that is, the averages of the vertices of each of them.
+    /// Elements without vertices, like the minimal element, are skipped.
+    pub fn element_centroids(&self, rank: usize, count: usize) -> Vec<(usize, Point<f64>)> {
+        if rank > self.rank() {
+            return Vec::new();
+        }
+
+        (0..self.el_count(rank).min(count))
+            .filter_map(|idx| {
+                let vertices = self.element_vertices_ref(rank, idx)?;
+                let len = vertices.len();
+                let zero = Point::zeros(self.dim_or());
+                let sum = vertex_fold::fold_points(vertices, zero, |sum, _, v| sum + v)?;
+                Some((idx, sum / len as f64))
+            })
+            .collect()
+    }
+
+    /// Embeds the vertices into a space with at least a given number of
+    /// dimensions, by appending zero coordinates to them.
+    fn embed_into(&mut self, dim: usize) {
+        if self.dim_or() < dim {
+            for v in &mut self.vertices {
+                *v = v.clone().resize_vertically(dim, 0.0);
+            }
+        }
+    }
+
+    /// Builds a [duoprism](https://polytope.miraheze.org/wiki/Prism_product)
+    /// from two polytopes, and records the pair of elements of `self` and
+    /// `other` that each of its elements comes from.
+    pub fn duoprism_with_provenance(&self, other: &Self) -> (Self, Provenance) {
+        let (abs, provenance) = Abstract::product_with_provenance(&self.abs, &other.abs, true, false);
+        let vertices = duoprism_vertices(
+            &self.vertices,
+            &other.vertices,
+            self.dim_or(),
+            other.dim_or(),
+            1.0,
+            1.0,
         );
 
-        // All vertices must have the same dimension.
-        if cfg!(debug_assertions) {
-            if let Some(vertex0) = vertices.get(0) {
-                for vertex1 in &vertices {
-                    debug_assert_eq!(vertex0.len(), vertex1.len());
+        (Self::new(vertices, abs), provenance)
+    }
+
+    /// Returns the signed distance from every vertex to a hyperplane.
+    fn slice_distances(&self, slice: &Hyperplane<f64>) -> Vec<f64> {
+        self.vertices.iter().map(|v| slice.distance(v)).collect()
+    }
+
+    /// Takes the cross-section of a polytope through a hyperplane, given by
+    /// the signed distance from every vertex to it.
+    ///
+    /// If the sides of the hyperplane on which the vertices lie are passed,
+    /// they alone decide which edges cross it, as in
+    /// [`Segment::crossing_sides`]. Otherwise, this is decided from the
+    /// distances, as in [`Segment::crossing`].
+    ///
+    /// If a provenance is passed, every element of the cross-section is
+    /// recorded as coming from the element of the original polytope that it
+    /// lies in.
+    ///
+    /// Crossings that coincide, as when the hyperplane goes through a vertex,
+    /// are merged into a single vertex through a [`PointCloud`]. Vertices
+    /// that don't end up on any edge are dropped, unless the hyperplane only
+    /// touches the polytope at a single point, which is then returned.
+    ///
+    /// # Todo
+    /// We should make this function take a general [`Subspace`] instead.
+    fn cross_section_impl(
+        &self,
+        distances: &[f64],
+        sides: Option<&[std::cmp::Ordering]>,
+        mut provenance: Option<&mut Provenance>,
+    ) -> Self {
+        if self.rank() < 4 {
+            unreachable!();
+        }
+
+        let mut vertices = PointCloud::new();
+        let mut ranks = Vec::with_capacity(self.rank());
+
+        // We map all indices of k-elements in the original polytope to the
+        // indices of the new (k-1)-elements resulting from taking their
+        // intersections with the slicing hyperplane.
+        let mut hash_element = HashMap::new();
+
+        // The sources of the elements of the cross-section, by rank.
+        let mut sources = vec![vec![None]];
+        let mut vertex_sources = Vec::new();
+
+        // Determines the vertices of the cross-section.
+        for (idx, edge) in self[2].iter().enumerate() {
+            let (v0, v1) = (edge.subs[0], edge.subs[1]);
+            let segment = Segment(&self.vertices[v0], &self.vertices[v1]);
+
+            let crossing = match sides {
+                Some(sides) => {
+                    segment.crossing_sides(distances[v0], distances[v1], sides[v0], sides[v1])
+                }
+                None => segment.crossing(distances[v0], distances[v1]),
+            };
+
+            if let Some(p) = crossing {
+                let len = vertices.len();
+                let v = vertices.insert_or_get(p, f64::EPS);
+                hash_element.insert(idx, v);
+
+                // If we got ourselves a new vertex:
+                if v == len {
+                    if provenance.is_some() {
+                        vertex_sources.push(Some(Source::Element(2, idx)));
+                    }
+                }
+                // If the crossing merged with that of an edge sharing an
+                // endpoint, the vertex is that endpoint.
+                else if let Some(Source::Element(2, other)) = vertex_sources.get(v).copied().flatten() {
+                    let other = &self[2][other].subs;
+                    if let Some(&shared) = edge.subs.iter().find(|w| other.contains(w)) {
+                        vertex_sources[v] = Some(Source::Element(1, shared));
+                    }
                 }
             }
         }
 
-        // With no further info, we create a generic name for the polytope.
-        Self { vertices, abs }
+        let vertex_count = vertices.len();
+
+        // The slice does not intersect the polytope.
+        if vertex_count == 0 {
+            if let Some(provenance) = provenance {
+                *provenance = Provenance::unknown(&Abstract::nullitope());
+            }
+
+            return Self::nullitope();
+        }
+
+        // The slice only touches the polytope at a point.
+        if vertex_count == 1 {
+            if let Some(provenance) = provenance {
+                *provenance = Provenance::with_capacity(2);
+                provenance.push(vec![None]);
+                provenance.push(vertex_sources);
+            }
+
+            return Self::new(vertices.into_points(), Abstract::point());
+        }
+
+        sources.push(vertex_sources);
+
+        ranks.push(SubelementList::min());
+        ranks.push(SubelementList::vertices(vertex_count));
+
+        // Takes care of building everything else.
+        for r in 3..self.rank() {
+            let mut new_hash_element = HashMap::new();
+            let mut new_els = SubelementList::new();
+            let mut el_sources = Vec::new();
+
+            for (idx, el) in self[r].iter().enumerate() {
+                let mut new_subs = Subelements::new();
+                for sub in &el.subs {
+                    if let Some(&v) = hash_element.get(sub) {
+                        // Two edges of a face only cross the hyperplane at the
+                        // same point if they meet there, and their other
+                        // endpoints lie on its negative side. The face then
+                        // just touches the hyperplane at that point, so both
+                        // crossings cancel out.
+                        if let Some(pos) = new_subs.iter().position(|&w| w == v) {
+                            new_subs.swap_remove(pos);
+                        } else {
+                            new_subs.push(v);
+                        }
+                    }
+                }
+
+                // If we got ourselves a new edge:
+                if !new_subs.is_empty() {
+                    new_hash_element.insert(idx, new_els.len());
+                    new_els.push(new_subs);
+
+                    if provenance.is_some() {
+                        el_sources.push(Some(Source::Element(r, idx)));
+                    }
+                }
+            }
+
+            ranks.push(new_els);
+            sources.push(el_sources);
+            hash_element = new_hash_element;
+        }
+
+        // Drops the vertices that don't lie on any edge, keeping the order of
+        // the rest.
+        let mut vertices = vertices.into_points();
+        let mut used = vec![false; vertex_count];
+        for edge in ranks[2].iter() {
+            for &v in edge {
+                used[v] = true;
+            }
+        }
+
+        if used.contains(&false) {
+            let mut new_idx = Vec::with_capacity(vertex_count);
+            let mut count = 0;
+            for (v, &used) in used.iter().enumerate() {
+                new_idx.push(count);
+                if used {
+                    vertices.swap(count, v);
+                    if provenance.is_some() {
+                        sources[1].swap(count, v);
+                    }
+                    count += 1;
+                }
+            }
+
+            // The slice only touches the polytope at isolated points.
+            if count == 0 {
+                if let Some(provenance) = provenance {
+                    *provenance = Provenance::unknown(&Abstract::nullitope());
+                }
+
+                return Self::nullitope();
+            }
+
+            vertices.truncate(count);
+            if provenance.is_some() {
+                sources[1].truncate(count);
+            }
+            ranks[1] = SubelementList::vertices(count);
+            for edge in ranks[2].iter_mut() {
+                for v in edge.iter_mut() {
+                    *v = new_idx[*v];
+                }
+            }
+        }
+
+        // Adds a maximal element manually.
+        ranks.push(SubelementList::max(ranks.last().unwrap().len()));
+        sources.push(vec![Some(Source::Element(self.rank(), 0))]);
+
+        // Splits compounds of dyads.
+        let (first, last) = ranks.split_at_mut(3);
+
+        if let (Some(edges), Some(faces)) = (first.last_mut(), last.first_mut()) {
+            // Keeps track of the indices of our new edges.
+            let mut edge_num = edges.len();
+            let mut new_edges = SubelementList::new();
+
+            // The superelements of all edges.
+            let mut edge_sups = Vec::new();
+            for _ in 0..edge_num {
+                edge_sups.push(Superelements::new());
+            }
+
+            for (idx, face) in faces.iter().enumerate() {
+                for &sub in face {
+                    edge_sups[sub].push(idx);
+                }
+            }
+
+            for (edge_idx, subs) in edges.iter_mut().enumerate() {
+                debug_assert_eq!(
+                    subs.len() % 2,
+                    0,
+                    "A line should always intersect a polygon an even amount of times!"
+                );
+                let comps = subs.len() / 2;
+
+                if comps > 1 {
+                    // Sorts the component's vertices along the line they lie
+                    // on. Pairing them up in this order follows the even-odd
+                    // rule, which is the only one under which every vertex
+                    // bounds a single segment, even when the face crosses
+                    // itself, as in a pentagram.
+                    let origin = vertices[subs[0]].clone();
+                    let direction = subs
+                        .iter()
+                        .map(|&x| &vertices[x] - &origin)
+                        .max_by(|v, w| v.norm().partial_cmp(&w.norm()).unwrap())
+                        .unwrap();
+                    let pos = |x: usize| (&vertices[x] - &origin).dot(&direction);
+
+                    subs.as_mut_slice()
+                        .sort_unstable_by(|&x, &y| pos(x).partial_cmp(&pos(y)).unwrap());
+
+                    // Splits the edge, adds the new split edges as subelements
+                    // to the edge's superelements.
+                    for _ in 1..comps {
+                        let v0 = subs.pop().unwrap();
+                        let v1 = subs.pop().unwrap();
+                        new_edges.push(vec![v0, v1].into());
+
+                        // The split edges lie in the same face as the
+                        // original one.
+                        if provenance.is_some() {
+                            let source = sources[2][edge_idx];
+                            sources[2].push(source);
+                        }
+
+                        for &sup in &edge_sups[edge_idx] {
+                            faces[sup].push(edge_num);
+                        }
+
+                        edge_num += 1;
+                    }
+                }
+            }
+
+            // Adds the new edges.
+            edges.append(&mut new_edges);
+        }
+
+        if let Some(provenance) = provenance.as_mut() {
+            **provenance = Provenance::with_capacity(sources.len());
+            for rank_sources in sources {
+                provenance.push(rank_sources);
+            }
+        }
+
+        // Builds the polytope.
+        // TODO: no need for ranks, just use the builder directly.
+        let mut builder = AbstractBuilder::new();
+        for subelements in ranks {
+            builder.push(subelements);
+        }
+
+        // Safety: the minimal and maximal elements were added by hand. Every
+        // other element is where an element of the original polytope meets
+        // the hyperplane, and inherits its incidences. So any section of rank
+        // 2 whose bottom element is a vertex has the same two middle elements
+        // as the matching section of the original polytope. The sections
+        // whose bottom element is the minimal one are the edges, which are
+        // dyads: every edge of the original polytope crosses the hyperplane at
+        // most once, so every face crosses it an even number of times. Pairs
+        // of crossings merged into a single vertex cancel out, which keeps
+        // this number even, and the remaining crossings were paired up above.
+        // Vertices on no edge were dropped. When the sides are passed,
+        // they alone decide the crossings. If not, an edge almost parallel to
+        // the hyperplane might be missed, which the debug assertion above
+        // catches as an odd number of crossings.
+        unsafe {
+            Self::new(vertices, builder.build())
+        }
     }
 }
 
@@ -203,7 +968,7 @@ impl Polytope for Concrete {
             if let Some(mut element_fig) = self.try_dual()?.element(self.rank() - rank, idx) {
                 let subspace = Subspace::from_points(element_fig.vertices.iter());
                 element_fig.flatten();
-                element_fig.recenter_with(
+                element_fig.recenter_with_mut(
                     &subspace.flatten(&subspace.project(&Point::zeros(self.dim().unwrap()))),
                 );
                 element_fig.try_dual_mut()?;
@@ -299,7 +1064,7 @@ impl Polytope for Concrete {
             unsafe {
                 if builder.ranks().is_dyadic().is_ok() {
                     let abs = builder.build();
-                    let conc = Concrete{abs, vertices};
+                    let conc = Concrete{abs, vertices, units: None};
                     output.push(conc);
                 }
             }
@@ -454,7 +1219,8 @@ impl Polytope for Concrete {
                 }
                 output.push(Concrete {
                     vertices: vertices,
-                    abs: abs
+                    abs: abs,
+                    units: None,
                 });
             }
         }
@@ -487,19 +1253,28 @@ impl Polytope for Concrete {
     /// Builds a [duopyramid](https://polytope.miraheze.org/wiki/Pyramid_product)
     /// with unit height from two polytopes. Does not offset either polytope.
     fn duopyramid(&self, other: &Self) -> Self {
+        // Unit scales are always valid.
         self.duopyramid_with(
             other,
             &Point::zeros(self.dim_or()),
             &Point::zeros(other.dim_or()),
             1.0,
+            1.0,
+            1.0,
         )
+        .unwrap()
     }
 
     /// Builds a [duoprism](https://polytope.miraheze.org/wiki/Prism_product)
     /// from two polytopes.
     fn duoprism(&self, p: &Self) -> Self {
+        // The nullitope absorbs the prism product.
+        if self.is_nullitope() || p.is_nullitope() {
+            return Self::nullitope();
+        }
+
         Self::new(
-            duoprism_vertices(&self.vertices, &p.vertices),
+            duoprism_vertices(&self.vertices, &p.vertices, self.dim_or(), p.dim_or(), 1.0, 1.0),
             self.abs.duoprism(&p.abs),
         )
     }
@@ -507,43 +1282,80 @@ impl Polytope for Concrete {
     /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
     /// from two polytopes.
     fn duotegum(&self, other: &Self) -> Self {
+        // Unit scales are always valid.
         Self::duotegum_with(
             self,
             other,
             &Point::zeros(self.dim_or()),
             &Point::zeros(other.dim_or()),
+            1.0,
+            1.0,
         )
+        .unwrap()
     }
 
     /// Builds a [duocomb](https://polytope.miraheze.org/wiki/Honeycomb_product)
     /// from two polytopes.
     fn duocomb(&self, other: &Self) -> Self {
+        // The nullitope and the point absorb the comb product.
+        if self.rank() <= 1 || other.rank() <= 1 {
+            return Self::nullitope();
+        }
+
         Self::new(
-            duoprism_vertices(&self.vertices, &other.vertices),
+            duoprism_vertices(
+                &self.vertices,
+                &other.vertices,
+                self.dim_or(),
+                other.dim_or(),
+                1.0,
+                1.0,
+            ),
             self.abs.duocomb(other.abs()),
         )
     }
 
     /// Builds a [ditope](https://polytope.miraheze.org/wiki/Ditope) of a given
-    /// polytope in place.
+    /// polytope in place. Its two facets are copies of the polytope, so the
+    /// vertices stay where they are, but are embedded into one more dimension
+    /// if needed. Does nothing in the case of the nullitope.
     fn ditope_mut(&mut self) {
+        match self.rank() {
+            0 => return,
+
+            // The facets of the ditope of a point are the point and its copy.
+            1 => {
+                let v = self.vertices[0].clone();
+                self.vertices.push(v);
+            }
+
+            _ => {}
+        }
+
         self.abs.ditope_mut();
+        self.embed_into(self.rank() - 1);
     }
 
     /// Builds a [hosotope](https://polytope.miraheze.org/wiki/hosotope) of a
-    /// given polytope.
+    /// given polytope. Its only vertices are the two copies of the minimal
+    /// element, which are placed at opposite poles. Returns the nullitope in
+    /// the case of the nullitope.
     fn hosotope(&self) -> Self {
-        Self::new(
-            vec![vec![-0.5].into(), vec![0.5].into()],
-            self.abs.hosotope(),
-        )
+        if self.rank() == 0 {
+            return Self::nullitope();
+        }
+
+        let abs = self.abs.hosotope();
+        Self::new(hosotope_vertices(abs.rank() - 1), abs)
     }
 
     /// Builds a [hosotope](https://polytope.miraheze.org/wiki/hosotope) of a
-    /// given polytope in place.
+    /// given polytope in place, as in [`Self::hosotope`].
     fn hosotope_mut(&mut self) {
-        self.vertices = vec![vec![-0.5].into(), vec![0.5].into()];
-        self.abs.hosotope_mut();
+        if self.rank() != 0 {
+            self.abs.hosotope_mut();
+            self.vertices = hosotope_vertices(self.rank() - 1);
+        }
     }
 
     /// Builds a [star product](https://en.wikipedia.org/wiki/Star_product)
@@ -555,15 +1367,15 @@ impl Polytope for Concrete {
         )
     }
 
-    /// Attempts to build an antiprism based on a given polytope. Uses the unit
+    /// Attempts to build an antiprism based on a given polytope. Uses a unit
     /// hypersphere to take the dual, and places the bases at a distance of 1.
-    /// If it fails, it returns the index of a facet through the inversion
-    /// center.
+    /// See [`ConcretePolytope::try_antiprism_auto`] for how the hypersphere is
+    /// centered.
     ///
     /// If you want more control over the arguments, you can use
-    /// [`Self::try_antiprism_with`].
-    fn try_antiprism(&self) -> Result<Self, Self::DualError> {
-        Self::try_antiprism_with(self, &Hypersphere::unit(self.dim().unwrap_or(1)), 1.0)
+    /// [`ConcretePolytope::try_antiprism_with`].
+    fn try_antiprism(&self) -> Result<Self, AntiprismError> {
+        self.try_antiprism_auto().map(|(antiprism, _)| antiprism)
     }
 
     /// Builds a [simplex](https://polytope.miraheze.org/wiki/Simplex) with a
@@ -589,18 +1401,75 @@ impl Polytope for Concrete {
             vertices.push(vec![a; dim].into());
 
             let mut simplex = Concrete::new(vertices, Abstract::simplex(rank));
-            simplex.recenter();
+            simplex.recenter_mut();
             simplex
         }
     }
 
-    /// Builds an [orthoplex](https://polytope.miraheze.org/wiki/Orthoplex) with
-    /// a given rank.
-    fn orthoplex(rank: usize) -> Self {
+    /// Builds a [hypercube](https://polytope.miraheze.org/wiki/Hypercube) with
+    /// a given rank and unit edge length, whose vertices are all points with
+    /// coordinates &plusmn;1/2.
+    fn hypercube(rank: usize) -> Self {
         if rank == 0 {
             Self::nullitope()
         } else {
-            Self::multitegum(iter::repeat(&Self::dyad_with(f64::SQRT_2)).take(rank - 1))
+            let dim = rank - 1;
+
+            // The coordinates are the binary digits of the vertex index, from
+            // the most to the least significant one, so that the vertices
+            // are in the same order as in a product of dyads.
+            let vertices = (0..1usize << dim)
+                .map(|idx| {
+                    Point::from_fn(dim, |i, _| {
+                        if (idx >> (dim - 1 - i)) & 1 == 0 {
+                            -0.5
+                        } else {
+                            0.5
+                        }
+                    })
+                })
+                .collect();
+
+            Self::new(vertices, Abstract::hypercube(rank))
+        }
+    }
+
+    /// Builds an [orthoplex](https://polytope.miraheze.org/wiki/Orthoplex) with
+    /// a given rank and unit edge length, whose vertices are all points with
+    /// one coordinate equal to &plusmn;√2/2 and all others equal to 0.
+    fn orthoplex(rank: usize) -> Self {
+        if rank <= 1 {
+            Self::simplex(rank)
+        } else {
+            let dim = rank - 1;
+
+            // The vertices are in the same order as in a tegum product of
+            // dyads.
+            let vertices = (0..dim)
+                .flat_map(|i| {
+                    [-f64::HALF_SQRT_2, f64::HALF_SQRT_2].map(|x| {
+                        let mut v = Point::zeros(dim);
+                        v[i] = x;
+                        v
+                    })
+                })
+                .collect();
+
+            Self::new(vertices, Abstract::orthoplex(rank))
+        }
+    }
+
+    /// Takes the product of a given kind of `n` copies of a polytope. Pyramid
+    /// products of concrete polytopes aren't associative, since the height of
+    /// a pyramid product doesn't depend on the heights of its factors, so these
+    /// are built one factor at a time.
+    fn power(kind: ProductKind, base: &Self, n: usize) -> Self {
+        match kind {
+            ProductKind::Pyramid => match n {
+                0 => Self::nullitope(),
+                _ => (1..n).fold(base.clone(), |p, _| p.duopyramid(base)),
+            },
+            _ => binary_power(base, n, |p, q| kind.apply(p, q), || kind.empty()),
         }
     }
 
@@ -618,26 +1487,53 @@ impl Polytope for Concrete {
     }
 }
 
+/// Returns the two vertices of a hosotope living in a given number of
+/// dimensions, at opposite poles of the first axis.
+fn hosotope_vertices(dim: usize) -> Vec<Point<f64>> {
+    [-0.5, 0.5]
+        .iter()
+        .map(|&x| {
+            let mut v = Point::zeros(dim);
+            v[0] = x;
+            v
+        })
+        .collect()
+}
+
 /// Generates the vertices for either a tegum or a pyramid product with two
-/// given vertex sets and a given height.
+/// given vertex sets and a given height. The dimensions of both factors are
+/// passed explicitly, with `None` standing for the nullitope.
 ///
 /// The vertices are the padded vertices of `p`, followed by the padded
-/// vertices of `q`.
+/// vertices of `q`. The vertices of each factor are scaled by the
+/// corresponding factor before padding, while the pads are left as they are.
+#[allow(clippy::too_many_arguments)]
 fn duopyramid_vertices(
     p: &[Point<f64>],
     q: &[Point<f64>],
+    p_dim: Option<usize>,
+    q_dim: Option<usize>,
     p_pad: &Point<f64>,
     q_pad: &Point<f64>,
+    p_scale: f64,
+    q_scale: f64,
     height: f64,
     tegum: bool,
 ) -> Vec<Point<f64>> {
-    // Duotegums with points should just return the original polytopes.
-    if tegum {
-        if p.get(0).map(|vp| vp.len()) == Some(0) {
-            return q.to_owned();
-        } else if q.get(0).map(|vq| vq.len()) == Some(0) {
-            return p.to_owned();
-        }
+    let scaled = |vs: &[Point<f64>], scale: f64| vs.iter().map(|v| v * scale).collect();
+
+    match (p_dim, q_dim) {
+        // The nullitope absorbs the tegum product, and is the identity of the
+        // pyramid product.
+        (None, _) | (_, None) if tegum => return Vec::new(),
+        (None, _) => return scaled(q, q_scale),
+        (_, None) => return scaled(p, p_scale),
+
+        // Duotegums with points should just return the original polytopes.
+        (Some(0), _) if tegum => return scaled(q, q_scale),
+        (_, Some(0)) if tegum => return scaled(p, p_scale),
+
+        _ => {}
     }
 
     let half_height = height / 2.0;
@@ -645,7 +1541,11 @@ fn duopyramid_vertices(
     // To every point in p, we append zeros to the right.
     p.iter()
         .map(|vp| {
-            let mut v: Vec<_> = vp.iter().copied().chain(q_pad.iter().copied()).collect();
+            let mut v: Vec<_> = vp
+                .iter()
+                .map(|x| x * p_scale)
+                .chain(q_pad.iter().copied())
+                .collect();
             if !tegum {
                 v.push(half_height);
             }
@@ -653,38 +1553,44 @@ fn duopyramid_vertices(
         })
         // To every point in q, we append zeros to the left.
         .chain(q.iter().map(|vq| {
-            let mut v: Vec<_> = p_pad.iter().copied().chain(vq.iter().copied()).collect();
+            let mut v: Vec<_> = p_pad
+                .iter()
+                .copied()
+                .chain(vq.iter().map(|x| x * q_scale))
+                .collect();
             if !tegum {
                 v.push(-half_height);
-            }
-            v.into()
-        }))
-        .collect()
-}
-
-/// Generates the vertices for a duoprism with two given vertex sets.
-fn duoprism_vertices(p: &[Point<f64>], q: &[Point<f64>]) -> Vec<Point<f64>> {
-    // The dimension of the points in p.
-    let p_dim = if let Some(vp) = p.get(0) {
-        vp.len()
-    } else {
-        return Vec::new();
-    };
-
-    // The dimension of the points in q.
-    let q_dim = if let Some(vq) = q.get(0) {
-        vq.len()
-    } else {
-        return Vec::new();
-    };
+            }
+            v.into()
+        }))
+        .collect()
+}
 
+/// Generates the vertices for a duoprism with two given vertex sets, whose
+/// points have the given dimensions, after scaling each of them by the
+/// corresponding factor. If either vertex set is empty, so is the result.
+fn duoprism_vertices(
+    p: &[Point<f64>],
+    q: &[Point<f64>],
+    p_dim: usize,
+    q_dim: usize,
+    p_scale: f64,
+    q_scale: f64,
+) -> Vec<Point<f64>> {
     // The dimension of our new points.
     let dim = p_dim + q_dim;
 
     // We take all elements in the cartesian product p × q, and chain each
     // pair together.
     itertools::iproduct!(p.iter(), q.iter())
-        .map(|(vp, vq)| Point::from_iterator(dim, vp.iter().chain(vq.iter()).copied()))
+        .map(|(vp, vq)| {
+            Point::from_iterator(
+                dim,
+                vp.iter()
+                    .map(|x| x * p_scale)
+                    .chain(vq.iter().map(|x| x * q_scale)),
+            )
+        })
         .collect::<Vec<_>>()
 }
 
@@ -693,7 +1599,7 @@ fn duoprism_vertices(p: &[Point<f64>], q: &[Point<f64>]) -> Vec<Point<f64>> {
 /// This trait exists so that we can reuse this code for `miratope_lang`. The
 /// traits that are not auto-implemented require us to manually set names over
 /// there.
-pub trait ConcretePolytope: Polytope {
+pub trait ConcretePolytope: Polytope<DualError = DualError> {
     /// Returns a reference to the underlying [`Concrete`] polytope.
     fn con(&self) -> &Concrete;
 
@@ -713,7 +1619,7 @@ pub trait ConcretePolytope: Polytope {
     /// Returns the number of dimensions of the space the polytope lives in,
     /// or `None` in the case of the nullitope.
     fn dim(&self) -> Option<usize> {
-        (!self.is_nullitope()).then(|| self.vertices()[0].len())
+        self.vertices().first().map(|v| v.len())
     }
 
     /// Returns the number of dimensions of the space the polytope lives in,
@@ -766,38 +1672,233 @@ pub trait ConcretePolytope: Polytope {
     /// nothing sensible to do.
     fn star_polygon_with_edge(n: usize, d: usize, a: f64) -> Self {
         let mut p = Self::star_polygon(n, d);
-        p.scale(a / 2.0 / (f64::PI * d as f64 / n as f64).fsin());
+        p.scale_mut(a / 2.0 / (f64::PI * d as f64 / n as f64).fsin());
         p
     }
 
-    /// Scales a polytope by a given factor.
-    fn scale(&mut self, k: f64) {
+    /// Scales a polytope by a given factor in place.
+    fn scale_mut(&mut self, k: f64) {
         for v in self.vertices_mut() {
             *v *= k;
         }
     }
 
-    /// Recenters a polytope so that the gravicenter is at the origin.
-    fn recenter(&mut self) {
+    /// Scales a polytope by a given factor.
+    fn scale(mut self, k: f64) -> Self {
+        self.scale_mut(k);
+        self
+    }
+
+    /// Translates a polytope by a given vector in place.
+    fn shift_mut(&mut self, o: &Vector<f64>) {
+        for v in self.vertices_mut() {
+            *v += o;
+        }
+    }
+
+    /// Translates a polytope by a given vector.
+    fn shift(mut self, o: &Vector<f64>) -> Self {
+        self.shift_mut(o);
+        self
+    }
+
+    /// Recenters a polytope in place so that the gravicenter is at the origin.
+    fn recenter_mut(&mut self) {
         if let Some(gravicenter) = self.gravicenter() {
-            self.recenter_with(&gravicenter);
+            self.recenter_with_mut(&gravicenter);
         }
     }
 
-    /// Recenters a polytope so that a certain point is at the origin.
-    fn recenter_with(&mut self, p: &Point<f64>) {
+    /// Recenters a polytope so that the gravicenter is at the origin.
+    fn recenter(mut self) -> Self {
+        self.recenter_mut();
+        self
+    }
+
+    /// Radially projects every vertex of a polytope onto a hypersphere in
+    /// place, leaving the abstract structure untouched. If any vertex
+    /// coincides with the center of the hypersphere, returns its index and
+    /// leaves the polytope unchanged.
+    fn project_to_sphere_mut(&mut self, sphere: &Hypersphere<f64>) -> Result<(), ProjectError> {
+        if let Some(idx) = self
+            .vertices()
+            .iter()
+            .position(|v| abs_diff_eq!((v - &sphere.center).norm(), 0.0, epsilon = f64::EPS))
+        {
+            return Err(ProjectError(idx));
+        }
+
+        let radius = sphere.radius();
+        for v in self.vertices_mut() {
+            let dir = (&*v - &sphere.center).normalize();
+            *v = &sphere.center + dir * radius;
+        }
+
+        Ok(())
+    }
+
+    /// Radially projects every vertex of a polytope onto a hypersphere,
+    /// leaving the abstract structure untouched. Fails if any vertex coincides
+    /// with the center of the hypersphere.
+    fn project_to_sphere(&self, sphere: &Hypersphere<f64>) -> Result<Self, ProjectError> {
+        let mut clone = self.clone();
+        clone.project_to_sphere_mut(sphere).map(|_| clone)
+    }
+
+    /// Returns the index, coordinates, and norm of every vertex of the
+    /// polytope.
+    fn vertex_table(&self) -> Vec<(usize, Point<f64>, f64)> {
+        self.vertices()
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (idx, v.clone(), v.norm()))
+            .collect()
+    }
+
+    /// Recenters a polytope in place so that a certain point is at the origin.
+    fn recenter_with_mut(&mut self, p: &Point<f64>) {
         for v in self.vertices_mut() {
             *v -= p;
         }
     }
 
-    /// Applies a linear transformation to all vertices of a polytope.
-    fn apply(mut self, m: &Matrix<f64>) -> Self {
+    /// Recenters a polytope so that a certain point is at the origin.
+    fn recenter_with(mut self, p: &Point<f64>) -> Self {
+        self.recenter_with_mut(p);
+        self
+    }
+
+    /// Applies a linear transformation to all vertices of a polytope in place.
+    /// Fails and leaves the polytope unchanged if the matrix isn't square with
+    /// the dimension of the polytope as its size, so that the dimension never
+    /// changes.
+    fn apply_mut(&mut self, m: &Matrix<f64>) -> Result<(), DimensionError> {
+        if let Some(dim) = self.dim() {
+            if m.nrows() != dim || m.ncols() != dim {
+                return Err(DimensionError {
+                    expected: dim,
+                    found: m.shape(),
+                });
+            }
+        }
+
         for v in self.vertices_mut() {
             *v = m * v as &_;
         }
 
-        self
+        Ok(())
+    }
+
+    /// Applies a linear transformation to all vertices of a polytope. Fails if
+    /// the matrix isn't square with the dimension of the polytope as its size.
+    fn apply(mut self, m: &Matrix<f64>) -> Result<Self, DimensionError> {
+        self.apply_mut(m).map(|_| self)
+    }
+
+    /// Matches every vertex of a polytope to its nearest vertex in another.
+    /// The result at index `i` is the vertex of `other` matched to vertex `i`.
+    /// Fails if the vertex counts or dimensions differ, if some coordinate
+    /// isn't finite, or if the matching isn't a bijection.
+    fn match_vertices(&self, other: &Self) -> Result<Vec<usize>, MorphError> {
+        let (vertices, other_vertices) = (self.vertices(), other.vertices());
+        if vertices.len() != other_vertices.len() {
+            return Err(MorphError::VertexCount {
+                expected: vertices.len(),
+                found: other_vertices.len(),
+            });
+        }
+
+        if let (Some(expected), Some(found)) = (self.dim(), other.dim()) {
+            if expected != found {
+                return Err(MorphError::Dimension { expected, found });
+            }
+        }
+
+        // Non-finite distances can't be compared.
+        if vertices
+            .iter()
+            .chain(other_vertices)
+            .any(|v| v.iter().any(|x| !x.is_finite()))
+        {
+            return Err(MorphError::NonFinite);
+        }
+
+        let matching: Vec<_> = vertices
+            .iter()
+            .map(|v| {
+                other_vertices
+                    .iter()
+                    .map(|w| (v - w).norm())
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                    .unwrap()
+                    .0
+            })
+            .collect();
+
+        let mut matched = vec![false; other_vertices.len()];
+        for &idx in &matching {
+            matched[idx] = true;
+        }
+
+        let unmatched: Vec<_> = (0..matched.len()).filter(|&idx| !matched[idx]).collect();
+        if unmatched.is_empty() {
+            Ok(matching)
+        } else {
+            Err(MorphError::Unmatched(unmatched))
+        }
+    }
+
+    /// Linearly interpolates the vertices of a polytope towards those of
+    /// another, using the given vertex matching, while keeping the abstract
+    /// structure of the former. Values of `t` outside of `[0, 1]` extrapolate.
+    fn morph_with(&self, other: &Self, t: f64, matching: &[usize]) -> Result<Self, MorphError> {
+        if matching.len() != self.vertex_count() {
+            return Err(MorphError::VertexCount {
+                expected: self.vertex_count(),
+                found: matching.len(),
+            });
+        }
+
+        if let (Some(expected), Some(found)) = (self.dim(), other.dim()) {
+            if expected != found {
+                return Err(MorphError::Dimension { expected, found });
+            }
+        }
+
+        let mut morph = self.clone();
+        let other_vertices = other.vertices();
+        for (v, &idx) in morph.vertices_mut().iter_mut().zip(matching) {
+            *v = v.lerp(&other_vertices[idx], t);
+        }
+
+        Ok(morph)
+    }
+
+    /// Linearly interpolates the vertices of a polytope towards those of
+    /// another, matching them by their order, while keeping the abstract
+    /// structure of the former. Values of `t` outside of `[0, 1]` extrapolate.
+    /// Fails if the vertex counts or dimensions differ.
+    fn morph(&self, other: &Self, t: f64) -> Result<Self, MorphError> {
+        if self.vertex_count() != other.vertex_count() {
+            return Err(MorphError::VertexCount {
+                expected: self.vertex_count(),
+                found: other.vertex_count(),
+            });
+        }
+
+        let matching: Vec<_> = (0..self.vertex_count()).collect();
+        self.morph_with(other, t, &matching)
+    }
+
+    /// Linearly interpolates the vertices of a polytope towards those of
+    /// another, matching every vertex to its nearest neighbor, while keeping
+    /// the abstract structure of the former. Values of `t` outside of `[0, 1]`
+    /// extrapolate. Fails if the vertex counts or dimensions differ, or if the
+    /// matching isn't a bijection.
+    fn morph_matched(&self, other: &Self, t: f64) -> Result<Self, MorphError> {
+        let matching = self.match_vertices(other)?;
+        self.morph_with(other, t, &matching)
     }
 
     /// Returns an arbitrary truncate of a polytope.
@@ -812,33 +1913,38 @@ pub trait ConcretePolytope: Polytope {
     /// Calculates the gravicenter of a polytope, or returns `None` in the case
     /// of the nullitope.
     fn gravicenter(&self) -> Option<Point<f64>> {
-        (!self.is_nullitope())
-            .then(|| self.vertices().iter().sum::<Point<f64>>() / (self.vertex_count()) as f64)
+        let dim = self.vertices().first()?.len();
+        vertex_fold::fold_points(self.vertices(), Point::zeros(dim), |sum, _, v| sum + v)
+            .map(|sum| sum / self.vertex_count() as f64)
     }
 
     /// Gets the least and greatest distance of a vertex of the polytope,
     /// measuring from a specified direction, or returns `None` in the case of
-    /// the nullitope.
+    /// the nullitope. The distances are the dot products of the vertices with
+    /// the direction, which should be a unit vector.
     fn minmax(&self, direction: Vector<f64>) -> Option<(f64, f64)> {
-        use itertools::MinMaxResult::*;
-
-        let hyperplane = Hyperplane::new(direction, 0.0);
-
-        match self
-            .vertices()
-            .iter()
-            .map(|v| ordered_float::OrderedFloat(hyperplane.distance(v)))
-            .minmax()
-        {
-            // The vertex vector is empty.
-            NoElements => None,
+        vertex_fold::fold_points(
+            self.vertices(),
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), _, v| {
+                let dist = v.dot(&direction);
+                (min.min(dist), max.max(dist))
+            },
+        )
+    }
 
-            // The single vertex gives both the minimum and maximum distance.
-            OneElement(x) => Some((x.0, x.0)),
+    /// Returns the least and the greatest value of every coordinate of the
+    /// vertices of the polytope, as two opposite corners of its bounding box,
+    /// or returns `None` in the case of the nullitope.
+    fn bounding_box(&self) -> Option<(Point<f64>, Point<f64>)> {
+        vertex_fold::bounding_box(self.vertices())
+    }
 
-            // The minimum and maximum distances.
-            MinMax(x, y) => Some((x.0, y.0)),
-        }
+    /// Returns half the length of the diagonal of the bounding box of the
+    /// polytope, or 0 in the case of the nullitope.
+    fn extent_radius(&self) -> f64 {
+        self.bounding_box()
+            .map_or(0.0, |(min, max)| (max - min).norm() / 2.0)
     }
 
     /// Returns a map from the elements in a polytope to a crude average of
@@ -875,31 +1981,203 @@ pub trait ConcretePolytope: Polytope {
         map
     }
 
-    /// Returns the length of a given edge.
+    /// Returns the two endpoints of a given edge, or `None` if the edge doesn't
+    /// exist or is degenerate, i.e. doesn't have two distinct vertices.
+    fn edge_endpoints(&self, idx: usize) -> Option<[&Point<f64>; 2]> {
+        match self.get_element(2, idx)?.subs.as_slice() {
+            &[v0, v1] if v0 != v1 => Some([&self.vertices()[v0], &self.vertices()[v1]]),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of a given edge, or `None` if the edge doesn't exist
+    /// or is degenerate.
     fn edge_len(&self, idx: usize) -> Option<f64> {
-        let edge = self.get_element(2, idx)?;
-        Some((&self.vertices()[edge.subs[0]] - &self.vertices()[edge.subs[1]]).norm())
+        let [v0, v1] = self.edge_endpoints(idx)?;
+        Some((v0 - v1).norm())
     }
 
     /// Checks whether a polytope is equilateral to a fixed precision, and with
-    /// a specified edge length.
+    /// a specified edge length. Degenerate edges make a polytope not
+    /// equilateral.
     fn is_equilateral_with(&self, len: f64) -> bool {
-        (0..self.edge_count())
-            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = f64::EPS))
+        (0..self.edge_count()).all(|idx| {
+            matches!(self.edge_len(idx), Some(l) if abs_diff_eq!(l, len, epsilon = f64::EPS))
+        })
     }
 
-    /// Checks whether a polytope is equilateral to a fixed precision.
+    /// Checks whether all edges of a polytope have the same length, up to a
+    /// relative precision. See [`Concrete::equilateral_report`] for which
+    /// edges don't.
     fn is_equilateral(&self) -> bool {
-        self.edge_count() == 0 || self.is_equilateral_with(self.edge_len(0).unwrap())
+        self.con().equilateral_report(Default::default()).passed
     }
 
+    /// Returns the distance from the origin to the midpoint of the first
+    /// non-degenerate edge, or `None` if there's no such edge.
+    ///
     /// I haven't actually implemented this in the general case.
     ///
     /// # Todo
     /// Maybe make this work in the general case?
-    fn midradius(&self) -> f64 {
-        let edge_subs = &self[(2, 0)].subs;
-        (&self.vertices()[edge_subs[0]] + &self.vertices()[edge_subs[1]]).norm() / 2.0
+    fn midradius(&self) -> Option<f64> {
+        (0..self.edge_count())
+            .find_map(|idx| self.edge_endpoints(idx))
+            .map(|[v0, v1]| (v0 + v1).norm() / 2.0)
+    }
+
+    /// Measures how far each 2-element of the polytope is from being planar.
+    /// This is the greatest distance from one of its vertices to the plane
+    /// that best fits them, divided by the greatest distance from one of its
+    /// vertices to their centroid, so that the measure doesn't depend on the
+    /// scale. Planar faces give zero. Returns an empty list if the polytope
+    /// lives in fewer than two dimensions, where no plane fits the faces.
+    fn face_planarity(&self) -> Vec<f64> {
+        if self.rank() < 3 || self.dim_or() < 2 {
+            return Vec::new();
+        }
+
+        (0..self.face_count())
+            .map(|idx| {
+                let vertices = self.element_vertices_ref(3, idx).unwrap();
+                let plane = Subspace::fit_points(vertices.iter().copied(), 2).unwrap();
+
+                let radius = vertices
+                    .iter()
+                    .map(|&v| (v - &plane.offset).norm())
+                    .fold(0.0, f64::max);
+                let deviation = vertices
+                    .iter()
+                    .map(|&v| plane.distance(v))
+                    .fold(0.0, f64::max);
+
+                if radius == 0.0 {
+                    0.0
+                } else {
+                    deviation / radius
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the greatest value of [`Self::face_planarity`] over all faces,
+    /// or `None` if the polytope has no faces.
+    fn max_face_planarity(&self) -> Option<f64> {
+        self.face_planarity()
+            .into_iter()
+            .max_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Returns the area and the centroid of each 2-element of the polytope.
+    /// Faces are measured in their best-fit plane, with the shoelace formula
+    /// applied to each of their cycles of vertices. Star faces count each
+    /// region as many times as they wind around it.
+    ///
+    /// The areas and centroids of [skew faces](Self::skew_faces) are those of
+    /// their projections onto their best-fit planes, which only approximate
+    /// them. Returns an empty list if the polytope lives in fewer than two
+    /// dimensions.
+    fn face_area_centroids(&self) -> Vec<(f64, Point<f64>)> {
+        if self.rank() < 3 || self.dim_or() < 2 {
+            return Vec::new();
+        }
+
+        let abs = self.abs();
+        abs[3]
+            .iter()
+            .map(|face| {
+                let cycles: Vec<Vec<_>> =
+                    CycleList::from_edges(face.subs.iter().map(|&i| &abs[(2, i)].subs))
+                        .iter()
+                        .map(|cycle| cycle.iter().map(|&v| &self.vertices()[v]).collect())
+                        .collect();
+
+                let plane = Subspace::fit_points(cycles.iter().flatten().copied(), 2).unwrap();
+                polygon_area_centroid(&cycles, &plane)
+            })
+            .collect()
+    }
+
+    /// Returns the area of each 2-element of the polytope, as computed by
+    /// [`Self::face_area_centroids`]. The areas of skew faces are only
+    /// approximate.
+    fn face_areas(&self) -> Vec<f64> {
+        self.face_area_centroids()
+            .into_iter()
+            .map(|(area, _)| area)
+            .collect()
+    }
+
+    /// Returns the indices of the 2-elements whose [planarity](Self::face_planarity)
+    /// is above [`SKEW_PLANARITY`], and whose areas are thus only approximate.
+    fn skew_faces(&self) -> Vec<usize> {
+        self.face_planarity()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, planarity)| planarity > SKEW_PLANARITY)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns the total (rank − 1)-dimensional content of the facets of the
+    /// polytope. For polyhedra, this is the sum of the
+    /// [face areas](Self::face_areas), which is only approximate when there
+    /// are skew faces. Otherwise, it adds up the [volumes](Self::volume) of
+    /// the flattened facets. Returns `None` if the polytope has rank less than
+    /// 2 or the volume of some facet isn't defined.
+    fn surface_area(&self) -> Option<f64> {
+        match self.rank() {
+            0..=2 => None,
+            4 => Some(self.face_areas().into_iter().sum()),
+            _ => (0..self.facet_count())
+                .map(|idx| {
+                    let mut facet = self.facet(idx)?;
+                    facet.flatten();
+                    facet.volume_mut().ok().flatten()
+                })
+                .sum(),
+        }
+    }
+
+    /// Returns the centroid of the 2-elements of the polytope, weighting the
+    /// centroid of each by its area. Unlike the [gravicenter](Self::gravicenter),
+    /// this doesn't depend on how the vertices are spread out over the faces.
+    /// Returns `None` if the polytope has rank less than 2 or its faces have
+    /// no area.
+    fn centroid_by_area(&self) -> Option<Point<f64>> {
+        let faces = self.face_area_centroids();
+        let area: f64 = faces.iter().map(|(area, _)| area).sum();
+        if area <= 0.0 {
+            return None;
+        }
+
+        let dim = faces[0].1.len();
+        let moment = faces
+            .iter()
+            .fold(Point::zeros(dim), |sum, (area, centroid)| sum + centroid * *area);
+        Some(moment / area)
+    }
+
+    /// Returns the indices of the facets sorted by the value of a key at their
+    /// centroids, from least to greatest. The centroid of a facet is the
+    /// average of its vertices.
+    fn facet_order_by<F: Fn(&Point<f64>) -> f64>(&self, key: F) -> Vec<usize> {
+        let facet_rank = self.rank().wrapping_sub(1);
+        let keys: Vec<_> = (0..self.facet_count())
+            .map(|idx| {
+                let vertices = self.element_vertices_ref(facet_rank, idx).unwrap();
+                let centroid = vertices.iter().copied().sum::<Point<f64>>() / vertices.len() as f64;
+                key(&centroid)
+            })
+            .collect();
+
+        let mut order: Vec<_> = (0..keys.len()).collect();
+        order.sort_by(|&i, &j| {
+            keys[i]
+                .partial_cmp(&keys[j])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        order
     }
 
     /// Builds the dual of a polytope with a given reciprocation sphere in
@@ -944,11 +2222,16 @@ pub trait ConcretePolytope: Polytope {
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
     /// based on a given polytope. Uses the specified [`Hypersphere`] to build
     /// the dual base, and separates the bases by the given height.
+    ///
+    /// Fails if the base isn't dyadic, or if one of its facets passes through
+    /// the center of the hypersphere.
     fn try_antiprism_with(
         &self,
         sphere: &Hypersphere<f64>,
         height: f64,
-    ) -> Result<Self, Self::DualError> {
+    ) -> Result<Self, AntiprismError> {
+        self.abs().check_antiprism()?;
+
         let half_height = height / 2.0;
         let vertices = self.vertices().iter().map(|v| v.push(-half_height));
         let dual = self.try_dual_with(sphere)?;
@@ -961,12 +2244,51 @@ pub trait ConcretePolytope: Polytope {
     /// with a given height.
     ///
     /// # Panics
-    /// Panics if any facets pass through the inversion center. If you want to
-    /// handle this possibility, use [`Self::try_antiprism_with`] instead.
+    /// Panics if the base isn't dyadic, or if any facets pass through the
+    /// inversion center. If you want to handle this possibility, use
+    /// [`Self::try_antiprism_with`] instead.
     fn antiprism_with(&self, sphere: &Hypersphere<f64>, height: f64) -> Self {
         self.try_antiprism_with(sphere, height).unwrap()
     }
 
+    /// Attempts to build an antiprism of unit height based on a given
+    /// polytope, taking the dual with respect to a unit hypersphere. The
+    /// hypersphere is centered at the origin, or if some facet passes through
+    /// it, at the circumcenter of the base, or else at its gravicenter.
+    ///
+    /// Returns the antiprism together with the center that was used. If no
+    /// center works, returns the error for the origin.
+    fn try_antiprism_auto(&self) -> Result<(Self, DualCenter), AntiprismError> {
+        let dim = self.dim().unwrap_or(1);
+        let mut first_err = None;
+
+        for center in DualCenter::ALL {
+            let point = match center {
+                DualCenter::Origin => Some(Point::zeros(dim)),
+                DualCenter::Circumcenter => self.circumsphere().map(|sphere| sphere.center),
+                DualCenter::Gravicenter => self.gravicenter(),
+            };
+
+            if let Some(point) = point {
+                let sphere = Hypersphere::with_squared_radius(point, 1.0);
+
+                match self.try_antiprism_with(&sphere, 1.0) {
+                    Ok(antiprism) => return Ok((antiprism, center)),
+
+                    // Recentering won't help with these.
+                    Err(err @ AntiprismError::Dyadic { .. }) => return Err(err),
+
+                    Err(err) => {
+                        first_err.get_or_insert(err);
+                    }
+                }
+            }
+        }
+
+        // The origin is always tried.
+        Err(first_err.unwrap())
+    }
+
     /// Builds a uniform antiprism of unit edge length.
     fn uniform_antiprism(n: usize, d: usize) -> Self {
         let polygon = Self::star_polygon(n, d);
@@ -975,13 +2297,22 @@ pub trait ConcretePolytope: Polytope {
         if n != 2 * d {
             let angle = f64::PI * f64::usize(d) / f64::usize(n);
             let cos = angle.fcos();
-            let height = ((cos - (2.0 * angle).fcos()) * 2.0).fsqrt();
+            let sin = angle.fsin();
+
+            // The polygon has unit circumradius, and so does its dual. A
+            // vertex of the dual base lies above one of the latter's vertices,
+            // at the length of an edge from a vertex of the base.
+            let vertex = Point::from_vec(vec![1.0, 0.0, 0.0]);
+            let dual_vertex = Point::from_vec(vec![cos, sin, 0.0]);
+            let (_, height) = Hypersphere::with_radius(vertex, 2.0 * sin)
+                .intersect_line(&dual_vertex, &Vector::from_vec(vec![0.0, 0.0, 1.0]))
+                .expect("lateral edges should reach the dual base");
 
             let mut antiprism = polygon.antiprism_with(
                 &Hypersphere::with_squared_radius(Point::zeros(2), cos),
                 height,
             );
-            antiprism.scale(0.5 / (f64::PI * d as f64 / n as f64).fsin());
+            antiprism.scale_mut(0.5 / (f64::PI * d as f64 / n as f64).fsin());
 
             antiprism
         }
@@ -995,7 +2326,7 @@ pub trait ConcretePolytope: Polytope {
                 .map(|v| vec![v[1], -v[0], half_height].into());
 
             let mut antiprism = polygon.antiprism_with_vertices(vertices, dual_vertices);
-            antiprism.scale(0.5);
+            antiprism.scale_mut(0.5);
 
             antiprism
         }
@@ -1013,48 +2344,77 @@ pub trait ConcretePolytope: Polytope {
         )
     }
 
-    /// Generates a duopyramid from two given polytopes with a given height and
-    /// a given offset.
+    /// Generates a duoprism from two given polytopes, after scaling the
+    /// vertices of each of them by the corresponding factor. Returns an error
+    /// if either scale factor isn't positive.
+    fn duoprism_with(p: &Self, q: &Self, p_scale: f64, q_scale: f64) -> Result<Self, ScaleError>;
+
+    /// Generates a duopyramid from two given polytopes with a given height, a
+    /// given offset and a given scale factor for each of them. If either
+    /// polytope is the nullitope, returns the other one scaled. Returns an
+    /// error if either scale factor isn't positive.
     fn duopyramid_with(
         &self,
         other: &Self,
         self_offset: &Point<f64>,
         other_offset: &Point<f64>,
+        self_scale: f64,
+        other_scale: f64,
         height: f64,
-    ) -> Self;
-
-    /// Generates a duopyramid from two given polytopes with a given offset.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self;
+    ) -> Result<Self, ScaleError>;
+
+    /// Generates a duotegum from two given polytopes with a given offset and a
+    /// given scale factor for each of them. If either polytope is the
+    /// nullitope, returns the nullitope. If either polytope is a point,
+    /// returns the other one scaled. Returns an error if either scale factor
+    /// isn't positive.
+    fn duotegum_with(
+        p: &Self,
+        q: &Self,
+        p_offset: &Point<f64>,
+        q_offset: &Point<f64>,
+        p_scale: f64,
+        q_scale: f64,
+    ) -> Result<Self, ScaleError>;
 
     /// Computes the volume of a polytope by adding up the contributions of all
-    /// flags. Returns `None` if the volume is undefined.
+    /// flags. Returns `None` if the volume is undefined, and an error if the
+    /// polytope lives in a higher dimension than its rank without being
+    /// [full-dimensional](Self::full_dimensional).
     ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
-    fn volume(&self) -> Option<f64> {
+    fn volume(&self) -> Result<Option<f64>, NotFullDimensional> {
         let rank = self.rank();
 
         // We leave the nullitope's volume undefined.
         if rank == 0 {
-            return None;
+            return Ok(None);
+        }
+
+        // Polytopes embedded into a higher dimension should be flattened first,
+        // so that they're not mistaken for skew polytopes.
+        if self.dim_or() > rank - 1 {
+            self.check_full_dimensional()?;
         }
 
         // The flattened vertices (may possibly be the original vertices).
         let subspace = Subspace::from_points(self.vertices().iter());
         let flat_vertices = subspace.flatten_vec(self.vertices());
 
-        match flat_vertices.get(0)?.len().cmp(&(rank - 1)) {
+        let flat_dim = match flat_vertices.get(0) {
+            Some(v) => v.len(),
+            None => return Ok(None),
+        };
+
+        match flat_dim.cmp(&(rank - 1)) {
             // Degenerate polytopes have volume 0 (if they're orientable).
             std::cmp::Ordering::Less => {
-                if self.orientable() {
-                    return Some(0.0)
-                } else {
-                    return None
-                }
+                return Ok(if self.orientable() { Some(0.0) } else { None });
             }
             // Skew polytopes don't have a defined volume.
             std::cmp::Ordering::Greater => {
-                return None;
+                return Ok(None);
             }
             _ => {}
         }
@@ -1100,7 +2460,7 @@ pub trait ConcretePolytope: Polytope {
                     }
                     // A non-orientable polytope doesn't have a volume.
                     else {
-                        return None;
+                        return Ok(None);
                     }
                 }
 
@@ -1109,12 +2469,14 @@ pub trait ConcretePolytope: Polytope {
             }
         }
 
-        Some(volume / f64::u32(crate::factorial(rank - 1)))
+        Ok(Some(volume / f64::u32(crate::factorial(rank - 1))))
     }
 
     /// Computes the volume of a polytope by adding up the contributions of all
-    /// flags. Returns `None` if the volume is undefined.
-    fn volume_mut(&mut self) -> Option<f64> {
+    /// flags. Returns `None` if the volume is undefined, and an error if the
+    /// polytope lives in a higher dimension than its rank without being
+    /// [full-dimensional](Self::full_dimensional).
+    fn volume_mut(&mut self) -> Result<Option<f64>, NotFullDimensional> {
         self.element_sort();
         self.volume()
     }
@@ -1126,8 +2488,68 @@ pub trait ConcretePolytope: Polytope {
     /// Flattens the vertices of a polytope into a specified subspace.
     fn flatten_into(&mut self, subspace: &Subspace<f64>);
 
+    /// Returns whether the vertices of the polytope span the space it lives
+    /// in. The nullitope is considered full-dimensional.
+    fn full_dimensional(&self) -> bool {
+        self.check_full_dimensional().is_ok()
+    }
+
+    /// Returns an error if the polytope isn't
+    /// [full-dimensional](Self::full_dimensional).
+    fn check_full_dimensional(&self) -> Result<(), NotFullDimensional> {
+        let dim = self.dim_or();
+        let rank = affine_rank(self.vertices());
+
+        if rank == dim {
+            Ok(())
+        } else {
+            Err(NotFullDimensional { rank, dim })
+        }
+    }
+
+    /// Returns the affine hull of the vertices of the polytope, ignoring
+    /// deviations from it of up to a given fraction of the diagonal of its
+    /// bounding box. Returns `None` in the case of the nullitope.
+    fn affine_hull_within(&self, tolerance: f64) -> Option<Subspace<f64>> {
+        if self.vertices().is_empty() {
+            None
+        } else {
+            Some(Subspace::from_points_within(
+                self.vertices().iter(),
+                2.0 * tolerance * self.extent_radius(),
+            ))
+        }
+    }
+
+    /// Flattens the polytope only if it isn't already
+    /// [full-dimensional](Self::full_dimensional). This is cheap for polytopes
+    /// that already are, so it can be called unconditionally.
+    fn ensure_flat(&mut self) {
+        if !self.full_dimensional() {
+            self.flatten();
+        }
+    }
+
     /// Slices the polytope through a given plane.
     fn cross_section(&self, slice: &Hyperplane<f64>) -> Self;
+
+    /// Slices the polytope through a given plane, and records the element of
+    /// the original polytope that each element of the cross-section lies in.
+    fn cross_section_with_provenance(&self, slice: &Hyperplane<f64>) -> (Self, Provenance);
+
+    /// Slices the polytope through a given plane, deciding on which side of it
+    /// each vertex lies with a given [robustness](RobustnessMode). In the
+    /// adaptive mode, vertices only lie on the plane when they do so exactly.
+    fn cross_section_with_mode(&self, slice: &Hyperplane<f64>, mode: RobustnessMode) -> Self;
+
+    /// Clips a convex polytope by the halfspace on the negative side of a
+    /// hyperplane. Returns `None` if the result is empty or lower-dimensional.
+    fn clip(&self, hyperplane: &Hyperplane<f64>) -> Option<Self>;
+
+    /// Computes the intersection of two convex polytopes of full rank in the
+    /// same dimension, by clipping the first by every facet hyperplane of the
+    /// second. Returns `None` if the intersection is empty or lower-dimensional.
+    fn intersect(&self, other: &Self) -> Option<Self>;
   
 	  /// Checks if the polytope is [fissary](https://polytope.miraheze.org/wiki/Fissary).
     fn is_fissary(&self) -> bool;
@@ -1278,35 +2700,98 @@ impl ConcretePolytope for Concrete {
         Self::new(new_vertices, abs)
     }
 
-    /// Generates a duopyramid from two given polytopes with a given height and
-    /// a given offset.
+    /// Generates a duoprism from two given polytopes, after scaling each of
+    /// them.
+    fn duoprism_with(p: &Self, q: &Self, p_scale: f64, q_scale: f64) -> Result<Self, ScaleError> {
+        check_scales(p_scale, q_scale)?;
+
+        // The nullitope absorbs the prism product.
+        if p.is_nullitope() || q.is_nullitope() {
+            return Ok(Self::nullitope());
+        }
+
+        Ok(Self::new(
+            duoprism_vertices(
+                &p.vertices,
+                &q.vertices,
+                p.dim_or(),
+                q.dim_or(),
+                p_scale,
+                q_scale,
+            ),
+            p.abs.duoprism(&q.abs),
+        ))
+    }
+
+    /// Generates a duopyramid from two given polytopes with a given height, a
+    /// given offset and a given scale.
     fn duopyramid_with(
         &self,
         other: &Self,
         self_offset: &Point<f64>,
         other_offset: &Point<f64>,
+        self_scale: f64,
+        other_scale: f64,
         height: f64,
-    ) -> Self {
-        Self::new(
+    ) -> Result<Self, ScaleError> {
+        check_scales(self_scale, other_scale)?;
+
+        // The nullitope is the identity of the pyramid product.
+        if self.is_nullitope() {
+            return Ok(other.clone().scale(other_scale));
+        } else if other.is_nullitope() {
+            return Ok(self.clone().scale(self_scale));
+        }
+
+        Ok(Self::new(
             duopyramid_vertices(
                 &self.vertices,
                 &other.vertices,
+                self.dim(),
+                other.dim(),
                 self_offset,
                 other_offset,
+                self_scale,
+                other_scale,
                 height,
                 false,
             ),
             Abstract::duopyramid(&self.abs, &other.abs),
-        )
+        ))
     }
 
     /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
-    /// from two polytopes.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self {
-        Self::new(
-            duopyramid_vertices(&p.vertices, &q.vertices, p_offset, q_offset, 0.0, true),
+    /// from two polytopes, with a given offset and a given scale.
+    fn duotegum_with(
+        p: &Self,
+        q: &Self,
+        p_offset: &Point<f64>,
+        q_offset: &Point<f64>,
+        p_scale: f64,
+        q_scale: f64,
+    ) -> Result<Self, ScaleError> {
+        check_scales(p_scale, q_scale)?;
+
+        // The nullitope absorbs the tegum product.
+        if p.is_nullitope() || q.is_nullitope() {
+            return Ok(Self::nullitope());
+        }
+
+        Ok(Self::new(
+            duopyramid_vertices(
+                &p.vertices,
+                &q.vertices,
+                p.dim(),
+                q.dim(),
+                p_offset,
+                q_offset,
+                p_scale,
+                q_scale,
+                0.0,
+                true,
+            ),
             p.abs.duotegum(&q.abs),
-        )
+        ))
     }
 
     /// Projects the vertices of the polytope into the lowest dimension possible.
@@ -1327,133 +2812,211 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Takes the cross-section of a polytope through a given hyperplane.
-    ///
-    /// # Todo
-    /// We should make this function take a general [`Subspace`] instead.
     fn cross_section(&self, slice: &Hyperplane<f64>) -> Self {
-        if self.rank() < 4 {
-            unreachable!();
-        }
-
-        let mut vertices = Vec::new();
-        let mut ranks = Vec::with_capacity(self.rank());
-
-        // We map all indices of k-elements in the original polytope to the
-        // indices of the new (k-1)-elements resulting from taking their
-        // intersections with the slicing hyperplane.
-        let mut hash_element = HashMap::new();
-
-        // Determines the vertices of the cross-section.
-        for (idx, edge) in self[2].iter().enumerate() {
-            let segment = Segment(&self.vertices[edge.subs[0]], &self.vertices[edge.subs[1]]);
+        self.cross_section_impl(&self.slice_distances(slice), None, None)
+    }
 
-            // If we got ourselves a new vertex:
-            if let Some(p) = slice.intersect(segment) {
-                hash_element.insert(idx, vertices.len());
-                vertices.push(p);
+    /// Slices the polytope through a given plane, deciding on which side of it
+    /// each vertex lies with a given [robustness](RobustnessMode).
+    fn cross_section_with_mode(&self, slice: &Hyperplane<f64>, mode: RobustnessMode) -> Self {
+        let distances = self.slice_distances(slice);
+        match mode {
+            RobustnessMode::Fast => self.cross_section_impl(&distances, None, None),
+            RobustnessMode::Adaptive => {
+                let sides = ExactPredicates.sides(slice, &self.vertices);
+                self.cross_section_impl(&distances, Some(&sides), None)
             }
         }
+    }
 
-        let vertex_count = vertices.len();
+    /// Takes the cross-section of a polytope through a given hyperplane, and
+    /// records the element of the original polytope that each element of the
+    /// cross-section lies in.
+    fn cross_section_with_provenance(&self, slice: &Hyperplane<f64>) -> (Self, Provenance) {
+        let mut provenance = Provenance::default();
+        let section = self.cross_section_impl(&self.slice_distances(slice), None, Some(&mut provenance));
+        (section, provenance)
+    }
 
-        // The slice does not intersect the polytope.
-        if vertex_count == 0 {
-            return Self::nullitope();
+    fn clip(&self, hyperplane: &Hyperplane<f64>) -> Option<Self> {
+        /// The position of a vertex with respect to the hyperplane.
+        #[derive(Clone, Copy, PartialEq)]
+        enum Side {
+            In,
+            On,
+            Out,
         }
 
-        ranks.push(SubelementList::min());
-        ranks.push(SubelementList::vertices(vertex_count));
-
-        // Takes care of building everything else.
-        for r in 3..self.rank() {
-            let mut new_hash_element = HashMap::new();
-            let mut new_els = SubelementList::new();
-
-            for (idx, el) in self[r].iter().enumerate() {
-                let mut new_subs = Subelements::new();
-                for sub in &el.subs {
-                    if let Some(&v) = hash_element.get(sub) {
-                        new_subs.push(v);
-                    }
-                }
-
-                // If we got ourselves a new edge:
-                if !new_subs.is_empty() {
-                    new_hash_element.insert(idx, new_els.len());
-                    new_els.push(new_subs);
-                }
-            }
-
-            ranks.push(new_els);
-            hash_element = new_hash_element;
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
         }
 
-        // Adds a maximal element manually.
-        ranks.push(SubelementList::max(ranks.last().unwrap().len()));
-
-        // Splits compounds of dyads.
-        let (first, last) = ranks.split_at_mut(3);
+        let distances: Vec<_> = self.vertices.iter().map(|v| hyperplane.distance(v)).collect();
+        let sides: Vec<_> = distances
+            .iter()
+            .map(|&d| {
+                if d < -f64::EPS {
+                    Side::In
+                } else if d > f64::EPS {
+                    Side::Out
+                } else {
+                    Side::On
+                }
+            })
+            .collect();
 
-        if let (Some(edges), Some(faces)) = (first.last_mut(), last.first_mut()) {
-            // Keeps track of the indices of our new edges.
-            let mut edge_num = edges.len();
-            let mut new_edges = SubelementList::new();
+        // Nothing is left, or nothing is cut off.
+        if !sides.contains(&Side::In) {
+            return None;
+        } else if !sides.contains(&Side::Out) {
+            return Some(self.clone());
+        }
 
-            // The superelements of all edges.
-            let mut edge_sups = Vec::new();
-            for _ in 0..edge_num {
-                edge_sups.push(Superelements::new());
+        // Keeps every vertex that isn't cut off.
+        let mut vertices = Vec::new();
+        let mut new_idx = vec![None; self.vertex_count()];
+        for (idx, v) in self.vertices.iter().enumerate() {
+            if sides[idx] != Side::Out {
+                new_idx[idx] = Some(vertices.len());
+                vertices.push(v.clone());
             }
+        }
 
-            for (idx, face) in faces.iter().enumerate() {
-                for &sub in face {
-                    edge_sups[sub].push(idx);
+        // The vertices of every element, and the new vertices where its edges
+        // cross the hyperplane.
+        let mut el_vertices = vec![
+            Vec::new(),
+            (0..self.vertex_count()).map(|v| vec![v]).collect(),
+        ];
+        let mut el_cuts = vec![Vec::new(), vec![Vec::new(); self.vertex_count()]];
+
+        for r in 2..=rank {
+            let mut rank_vertices = Vec::new();
+            let mut rank_cuts = Vec::new();
+
+            for el in self[r].iter() {
+                let mut verts = BTreeSet::new();
+                let mut cuts = BTreeSet::new();
+
+                for &sub in &el.subs {
+                    verts.extend(el_vertices[r - 1][sub].iter().copied());
+                    cuts.extend(el_cuts[r - 1][sub].iter().copied());
                 }
-            }
 
-            for (edge_idx, subs) in edges.iter_mut().enumerate() {
-                debug_assert_eq!(
-                    subs.len() % 2,
-                    0,
-                    "A line should always intersect a polygon an even amount of times!"
-                );
-                let comps = subs.len() / 2;
+                // Adds a new vertex where an edge crosses the hyperplane.
+                if r == 2 {
+                    let (v0, v1) = (el.subs[0], el.subs[1]);
+                    if (sides[v0] == Side::In && sides[v1] == Side::Out)
+                        || (sides[v0] == Side::Out && sides[v1] == Side::In)
+                    {
+                        let (d0, d1) = (distances[v0], distances[v1]);
+                        let segment = Segment(&self.vertices[v0], &self.vertices[v1]);
+                        cuts.insert(vertices.len());
+                        vertices.push(segment.at(d1 / (d1 - d0)));
+                    }
+                }
 
-                if comps > 1 {
-                    // Sorts the component's vertices lexicographically.
-                    subs.sort_unstable_by_key(|&x| PointOrd::new(vertices[x].clone()));
+                rank_vertices.push(verts.into_iter().collect::<Vec<_>>());
+                rank_cuts.push(cuts.into_iter().collect::<Vec<_>>());
+            }
 
-                    // Splits the edge, adds the new split edges as subelements
-                    // to the edge's superelements.
-                    for _ in 1..comps {
-                        let v0 = subs.pop().unwrap();
-                        let v1 = subs.pop().unwrap();
-                        new_edges.push(vec![v0, v1].into());
+            el_vertices.push(rank_vertices);
+            el_cuts.push(rank_cuts);
+        }
 
-                        for &sup in &edge_sups[edge_idx] {
-                            faces[sup].push(edge_num);
-                        }
+        // The vertex sets of the elements of the clipped polytope, by rank.
+        let mut new_els: Vec<BTreeSet<Vec<usize>>> = vec![BTreeSet::new(); rank + 1];
+        for r in 2..=rank {
+            for (verts, cuts) in el_vertices[r].iter().zip(&el_cuts[r]) {
+                let has_in = verts.iter().any(|&v| sides[v] == Side::In);
+                let has_out = verts.iter().any(|&v| sides[v] == Side::Out);
+                let all_on = verts.iter().all(|&v| sides[v] == Side::On);
+
+                // The part of the element that's kept.
+                if has_in || all_on {
+                    let mut new_verts: Vec<_> =
+                        verts.iter().filter_map(|&v| new_idx[v]).collect();
+                    new_verts.extend(cuts.iter().copied());
+                    new_verts.sort_unstable();
+                    new_els[r].insert(new_verts);
+                }
 
-                        edge_num += 1;
+                // The section of the element by the hyperplane.
+                if has_in && has_out {
+                    let mut new_verts: Vec<_> = verts
+                        .iter()
+                        .filter(|&&v| sides[v] == Side::On)
+                        .map(|&v| new_idx[v].unwrap())
+                        .collect();
+                    new_verts.extend(cuts.iter().copied());
+                    new_verts.sort_unstable();
+
+                    if r > 2 {
+                        new_els[r - 1].insert(new_verts);
                     }
                 }
             }
-
-            // Adds the new edges.
-            edges.append(&mut new_edges);
         }
 
-        // Builds the polytope.
-        // TODO: no need for ranks, just use the builder directly.
+        // Elements are incident whenever their vertex sets are.
         let mut builder = AbstractBuilder::new();
-        for subelements in ranks {
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+        let mut prev_els: Vec<BTreeSet<usize>> =
+            (0..vertices.len()).map(|v| iter::once(v).collect()).collect();
+
+        for els in new_els.into_iter().skip(2).take(rank - 2) {
+            let els: Vec<BTreeSet<usize>> =
+                els.into_iter().map(|el| el.into_iter().collect()).collect();
+            let mut subelements = SubelementList::new();
+
+            for el in &els {
+                let mut subs = Subelements::new();
+                for (idx, prev) in prev_els.iter().enumerate() {
+                    if prev.is_subset(el) {
+                        subs.push(idx);
+                    }
+                }
+                subelements.push(subs);
+            }
+
             builder.push(subelements);
+            prev_els = els;
         }
 
-        // Safety: TODO shit, this one's complicated... I'll come back to it.
-        unsafe {
-            Self::new(vertices, builder.build())
+        builder.push_max();
+
+        // Safety: the faces of the intersection of a convex polytope with a
+        // halfspace are exactly the ones found above.
+        Some(Self::new(vertices, unsafe { builder.build() }))
+    }
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let dim = self.dim()?;
+        if other.dim() != Some(dim) || self.rank() != dim + 1 || other.rank() != dim + 1 {
+            return None;
+        }
+
+        // A point is only clipped by itself.
+        if dim == 0 {
+            return Some(self.clone());
+        }
+
+        // Every facet hyperplane, oriented outwards.
+        let center = other.gravicenter()?;
+        let facet_rank = other.rank() - 1;
+        let mut intersection = self.clone();
+
+        for idx in 0..other.el_count(facet_rank) {
+            let hyperplane = Hyperplane::from_points_away(
+                other.element_vertices_ref(facet_rank, idx)?.into_iter(),
+                &center,
+            )?;
+            intersection = intersection.clip(&hyperplane)?;
         }
+
+        Some(intersection)
     }
 
     fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self {
@@ -1520,7 +3083,10 @@ impl ConcretePolytope for Concrete {
 
         builder.push_empty();
 
-        let mut compound = BTreeMap::<PointOrd<f64>,(usize,Subelements)>::new();
+        // Facets that don't pass through the origin are identified by the
+        // point of their hyperplane closest to it.
+        let mut feet = PointCloud::new();
+        let mut compound = Vec::<(usize,Subelements)>::new();
         let mut compound_hemi = HashMap::<Vec<usize>,(usize,Subelements)>::new();
         let mut current: usize = 0;
         for i in 0..self.facet_count() {
@@ -1538,16 +3104,16 @@ impl ConcretePolytope for Concrete {
                     current+=1;
                 }
             } else {
-                let subspace_point = PointOrd::new(subspace.project(&Point::zeros(self.dim().unwrap())));
-                if compound.contains_key(&subspace_point) {
-                    compound.get_mut(&subspace_point).unwrap().1.extend(facet.subs.clone());
+                let idx = feet.insert_or_get(subspace.project(&Point::zeros(self.dim().unwrap())), f64::EPS);
+                if idx < compound.len() {
+                    compound[idx].1.extend(facet.subs.clone());
                 } else {
-                    compound.insert(subspace_point,(current,facet.subs.clone()));
+                    compound.push((current,facet.subs.clone()));
                     current+=1;
                 }
             }
         }
-        let mut compound_ordered = compound.iter().map(|x| x.1).chain(compound_hemi.iter().map(|x| x.1)).collect::<Vec<&(usize,Subelements)>>();
+        let mut compound_ordered = compound.iter().chain(compound_hemi.iter().map(|x| x.1)).collect::<Vec<&(usize,Subelements)>>();
         compound_ordered.sort_unstable_by(|a,b| a.0.cmp(&b.0));
         compound_ordered.iter().for_each(|x| builder.push_subs(x.1.clone()));
         
@@ -1558,16 +3124,26 @@ impl ConcretePolytope for Concrete {
 
 #[cfg(test)]
 mod tests {
-    use super::{Concrete, ConcretePolytope};
-    use crate::{float::Float, Polytope};
+    use std::sync::Arc;
+
+    use super::{make_mut, Concrete, ConcreteError, ConcretePolytope, DualCenter, NotFullDimensional};
+    use crate::{
+        abs::{provenance::Source, valid::AbstractError, Ranked},
+        float::Float,
+        geometry::{Hypersphere, Point},
+        AntiprismError, DualError, Polytope,
+    };
 
     use approx::abs_diff_eq;
+    use vec_like::VecLike;
 
     /// Tests that a polytope has an expected volume.
     fn test_volume(mut poly: Concrete, volume: Option<f64>) {
+        // Some degenerate products live in a higher dimension than they span.
+        poly.ensure_flat();
         poly.element_sort();
 
-        if let Some(poly_volume) = poly.volume() {
+        if let Some(poly_volume) = poly.volume().unwrap() {
             let volume = volume.expect(&format!(
                 "Expected no volume for {}, found volume {}!",
                 "TBA: name", poly_volume
@@ -1603,6 +3179,148 @@ mod tests {
         test_volume(Concrete::dyad(), Some(1.0));
     }
 
+    /// Checks that vertices that don't fit the abstract polytope are rejected.
+    #[test]
+    fn try_new() {
+        use crate::abs::Abstract;
+
+        let dyad = |vertices: Vec<Vec<f64>>| {
+            Concrete::try_new(
+                vertices.into_iter().map(Point::from_vec).collect(),
+                Abstract::dyad(),
+            )
+        };
+
+        assert!(dyad(vec![vec![0.0, 0.0], vec![1.0, 0.0]]).is_ok());
+        assert_eq!(
+            dyad(vec![vec![0.0]]).unwrap_err(),
+            ConcreteError::VertexCount { expected: 2, found: 1 }
+        );
+        assert_eq!(
+            dyad(vec![vec![0.0, 0.0], vec![1.0]]).unwrap_err(),
+            ConcreteError::Dimension { idx: 1, expected: 2, found: 1 }
+        );
+        assert_eq!(
+            dyad(vec![vec![0.0, f64::NAN], vec![1.0, 0.0]]).unwrap_err(),
+            ConcreteError::NonFinite { idx: 0 }
+        );
+        assert_eq!(
+            dyad(vec![vec![0.0], vec![f64::NEG_INFINITY]]).unwrap_err(),
+            ConcreteError::NonFinite { idx: 1 }
+        );
+    }
+
+    /// Checks that loaded polytopes with bad vertices or inconsistent elements
+    /// are caught.
+    #[test]
+    fn validate() {
+        let cube = Concrete::hypercube(4);
+        assert_eq!(cube.validate(), Ok(()));
+
+        let mut nan = cube.clone();
+        nan.vertices[3][1] = f64::NAN;
+        assert_eq!(nan.validate(), Err(ConcreteError::NonFinite { idx: 3 }));
+
+        let mut inconsistent = cube;
+        inconsistent[(1, 0)].sups.pop();
+        assert!(matches!(
+            inconsistent.validate(),
+            Err(ConcreteError::Abstract(AbstractError::Consistency { .. }))
+        ));
+    }
+
+    /// The fingerprint of a polytope is kept by reordering subelements, but
+    /// not by moving a vertex or taking the dual.
+    #[test]
+    fn fingerprint() {
+        let cube = Concrete::hypercube(4);
+        let mut reordered = cube.clone();
+        reordered[(3, 2)].subs.reverse();
+        assert_eq!(reordered.fingerprint(), cube.fingerprint());
+
+        let mut moved = cube.clone();
+        moved.vertices[0][2] += 0.5;
+        assert_ne!(moved.fingerprint(), cube.fingerprint());
+
+        let octahedron = cube.try_dual().unwrap();
+        assert_ne!(octahedron.fingerprint(), cube.fingerprint());
+        assert_eq!(octahedron.fingerprint(), cube.try_dual().unwrap().fingerprint());
+    }
+
+    /// The bounding radius of the unit-edge cube is half its long diagonal.
+    #[test]
+    fn bounding_radius() {
+        let mut cube = Concrete::hypercube(4);
+        assert!((cube.bounding_radius().unwrap() - 0.75f64.sqrt()).abs() < f64::EPS);
+
+        cube.vertices[0][0] = f64::NAN;
+        assert!((cube.bounding_radius().unwrap() - 0.75f64.sqrt()).abs() < f64::EPS);
+        assert_eq!(Concrete::nullitope().bounding_radius(), None);
+    }
+
+    /// The centroids of the faces of a cube are the centers of the faces, and
+    /// only as many as asked for are returned.
+    #[test]
+    fn element_centroids() {
+        let cube = Concrete::hypercube(4);
+
+        let faces = cube.element_centroids(3, usize::MAX);
+        assert_eq!(faces.len(), 6);
+        for (idx, centroid) in faces {
+            assert_eq!(cube.element_vertices_ref(3, idx).unwrap().len(), 4);
+            let mut coords: Vec<_> = centroid.iter().map(|x| x.abs()).collect();
+            coords.sort_by(|x, y| x.partial_cmp(y).unwrap());
+            assert_eq!(coords, vec![0.0, 0.0, 0.5]);
+        }
+
+        let vertices = cube.element_centroids(1, 3);
+        assert_eq!(vertices.len(), 3);
+        assert_eq!(vertices[2], (2, cube.vertices[2].clone()));
+
+        assert_eq!(cube.element_centroids(4, 1)[0].1, Point::zeros(3));
+        assert!(cube.element_centroids(0, 1).is_empty());
+        assert!(cube.element_centroids(5, 1).is_empty());
+    }
+
+    /// Checks that `Concrete::new` panics with the same message.
+    #[test]
+    #[should_panic(expected = "expected 2 vertices but found 3")]
+    fn new_panics() {
+        Concrete::new(
+            vec![Point::zeros(1), Point::zeros(1), Point::zeros(1)],
+            crate::abs::Abstract::dyad(),
+        );
+    }
+
+    /// Checks that the volume of a square embedded in 3D is only computed
+    /// once it's flattened.
+    #[test]
+    fn embedded_square() {
+        let mut square = Concrete::hypercube(3);
+        for v in &mut square.vertices {
+            *v = Point::from_vec(vec![v[0], v[1], 0.0]);
+        }
+        square.element_sort();
+
+        assert!(!square.full_dimensional());
+        let err = square.volume().unwrap_err();
+        assert_eq!(err, NotFullDimensional { rank: 2, dim: 3 });
+        assert_eq!(
+            err.to_string(),
+            "the polytope only spans 2 of its 3 dimensions, flatten it first"
+        );
+
+        square.ensure_flat();
+        assert!(square.full_dimensional());
+        assert_eq!(square.dim(), Some(2));
+        assert!(abs_diff_eq!(square.volume().unwrap().unwrap(), 1.0, epsilon = f64::EPS));
+
+        // Flattening again does nothing.
+        let vertices = square.vertices.clone();
+        square.ensure_flat();
+        assert_eq!(square.vertices, vertices);
+    }
+
     fn polygon_area(n: usize, d: usize) -> f64 {
         let n = n as f64;
         let d = d as f64;
@@ -1672,6 +3390,105 @@ mod tests {
         }
     }
 
+    /// Returns the polytopes that ditopes and hosotopes are tested on.
+    fn dihoso_suite() -> Vec<Concrete> {
+        vec![
+            Concrete::nullitope(),
+            Concrete::point(),
+            Concrete::dyad(),
+            Concrete::polygon(5),
+            Concrete::star_polygon(7, 2),
+            Concrete::hypercube(4),
+            Concrete::simplex(5),
+            Concrete::orthoplex(4),
+            Concrete::polygon(3).duoprism(&Concrete::polygon(4)),
+        ]
+    }
+
+    /// Ditopes and hosotopes, repeated or combined, are valid polytopes that
+    /// live in as many dimensions as their rank asks for. A ditope adds a rank
+    /// and a facet, and a hosotope adds a rank and a vertex.
+    #[test]
+    fn ditope_hosotope() {
+        for poly in dihoso_suite() {
+            let counts: Vec<_> = poly.el_count_iter().collect();
+            let rank = poly.rank();
+
+            let ditope = poly.ditope();
+            let hosotope = poly.hosotope();
+            if rank == 0 {
+                assert_eq!(ditope.el_count_iter().collect::<Vec<_>>(), counts);
+                assert_eq!(hosotope.el_count_iter().collect::<Vec<_>>(), counts);
+                continue;
+            }
+
+            let mut ditope_counts = counts.clone();
+            ditope_counts[rank] = 2;
+            ditope_counts.push(1);
+            assert_eq!(ditope.el_count_iter().collect::<Vec<_>>(), ditope_counts);
+
+            let mut hosotope_counts = counts.clone();
+            hosotope_counts[0] = 2;
+            hosotope_counts.insert(0, 1);
+            assert_eq!(hosotope.el_count_iter().collect::<Vec<_>>(), hosotope_counts);
+
+            for q in [
+                ditope.clone(),
+                ditope.ditope(),
+                hosotope.clone(),
+                hosotope.hosotope(),
+                ditope.hosotope(),
+                hosotope.ditope(),
+            ] {
+                assert_eq!(q.validate(), Ok(()));
+                assert_eq!(q.dim_or(), q.rank() - 1);
+
+                let mut sorted = q.abs.clone();
+                sorted.element_sort();
+                assert_eq!(sorted.is_valid(false), Ok(()));
+                assert_eq!(sorted.dual().is_valid(false), Ok(()));
+            }
+
+            // Building them in place gives the same polytopes.
+            let mut in_place = poly.clone();
+            in_place.ditope_mut();
+            assert_eq!(in_place.vertices, ditope.vertices);
+            in_place.hosotope_mut();
+            assert_eq!(in_place.vertices, ditope.hosotope().vertices);
+        }
+    }
+
+    /// Ditopes are written to OFF files and read back with the same element
+    /// counts. Hosotopes of polygons and higher can't be, since their edges
+    /// all join the same two vertices.
+    #[test]
+    fn ditope_off() {
+        use crate::file::FromFile;
+
+        for poly in dihoso_suite().into_iter().skip(1) {
+            for ditope in [poly.ditope(), poly.ditope().ditope()] {
+                // Neither can digons and their ditopes, whose two edges join
+                // the same two vertices.
+                if ditope.rank() >= 3 && ditope.el_count(2) == 2 {
+                    continue;
+                }
+
+                let off = ditope.to_off(Default::default()).unwrap();
+                let reloaded = Concrete::from_off(&off).unwrap();
+
+                assert_eq!(
+                    reloaded.el_count_iter().collect::<Vec<_>>(),
+                    ditope.el_count_iter().collect::<Vec<_>>()
+                );
+                assert_eq!(reloaded.dim(), ditope.dim());
+            }
+        }
+
+        let hosotope = Concrete::point().hosotope();
+        let off = hosotope.to_off(Default::default()).unwrap();
+        assert_eq!(Concrete::from_off(&off).unwrap().vertices, hosotope.vertices);
+    }
+
     #[test]
     fn duotegum() {
         let (polygons, areas) = polygons_areas();
@@ -1700,6 +3517,578 @@ mod tests {
         }
     }
 
+    /// Scaling the factors of a duoprism scales its edges along each of them.
+    #[test]
+    fn scaled_duoprism() {
+        // Polygons with unit edge length.
+        let polygon = |n| {
+            let polygon = Concrete::polygon(n);
+            let edge = polygon.edge_len(0).unwrap();
+            polygon.scale(1.0 / edge)
+        };
+
+        let square = polygon(4);
+        let triangle = polygon(3);
+        let duoprism = Concrete::duoprism_with(&square, &triangle, 1.0, 2.0).unwrap();
+        crate::test(&duoprism, [1, 12, 24, 19, 7, 1]);
+
+        // Every edge lies along one of the factors.
+        for idx in 0..duoprism.edge_count() {
+            let edge = duoprism.element_vertices_ref(2, idx).unwrap();
+            let diff = edge[0] - edge[1];
+            let (p_len, q_len) = (diff.rows(0, 2).norm(), diff.rows(2, 2).norm());
+
+            assert!(
+                abs_diff_eq!(p_len, 1.0, epsilon = f64::EPS) && abs_diff_eq!(q_len, 0.0)
+                    || abs_diff_eq!(p_len, 0.0) && abs_diff_eq!(q_len, 2.0, epsilon = f64::EPS)
+            );
+        }
+
+        assert!(Concrete::duoprism_with(&square, &triangle, 0.0, 1.0).is_err());
+        assert!(Concrete::duoprism_with(&square, &triangle, 1.0, f64::INFINITY).is_err());
+        assert!(Concrete::duotegum_with(
+            &square,
+            &triangle,
+            &Point::zeros(2),
+            &Point::zeros(2),
+            1.0,
+            -1.0
+        )
+        .is_err());
+    }
+
+    /// The circumradius of a scaled duopyramid of a square and a triangle.
+    ///
+    /// Both polygons have unit circumradius, so with scales `a` and `b` and
+    /// height `h`, the circumcenter lies at a distance `(a² - b²) / 2h` from
+    /// the middle, towards the larger of the two.
+    #[test]
+    fn scaled_duopyramid() {
+        let (a, b, h) = (2.0, 3.0, 1.0);
+        let duopyramid = Concrete::polygon(4)
+            .duopyramid_with(
+                &Concrete::polygon(3),
+                &Point::zeros(2),
+                &Point::zeros(2),
+                a,
+                b,
+                h,
+            )
+            .unwrap();
+
+        let z = (a * a - b * b) / (2.0 * h);
+        let radius = (a * a + (h / 2.0 - z).powi(2)).sqrt();
+        assert!(abs_diff_eq!(
+            duopyramid.circumsphere().unwrap().radius(),
+            radius,
+            epsilon = f64::EPS
+        ));
+    }
+
+    /// Checks that every product against the nullitope, the point, and the
+    /// dyad has as many concrete vertices as abstract ones, all of them finite
+    /// and of the same dimension.
+    #[test]
+    fn degenerate_products() {
+        let factors = [Concrete::nullitope(), Concrete::point(), Concrete::dyad()];
+        let products: [fn(&Concrete, &Concrete) -> Concrete; 4] = [
+            Concrete::duopyramid,
+            Concrete::duoprism,
+            Concrete::duotegum,
+            Concrete::duocomb,
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                for product in &products {
+                    let poly = product(p, q);
+                    assert_eq!(poly.vertices.len(), poly.vertex_count());
+
+                    let dim = poly.dim_or();
+                    for v in &poly.vertices {
+                        assert_eq!(v.len(), dim);
+                        assert!(v.iter().all(|x| x.is_finite()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks the exact vertices of every product against the nullitope and
+    /// the point.
+    #[test]
+    fn nullitope_point_products() {
+        fn coords(poly: &Concrete) -> Vec<Vec<f64>> {
+            poly.vertices.iter().map(|v| v.iter().copied().collect()).collect()
+        }
+
+        let nullitope = Concrete::nullitope();
+        let point = Concrete::point();
+        let dyad = Concrete::dyad();
+        let empty: Vec<Vec<f64>> = Vec::new();
+
+        // The nullitope is the identity of the pyramid product, and absorbs
+        // every other product.
+        assert_eq!(coords(&dyad.duopyramid(&nullitope)), coords(&dyad));
+        assert_eq!(coords(&nullitope.duopyramid(&dyad)), coords(&dyad));
+        assert_eq!(coords(&nullitope.duopyramid(&nullitope)), empty);
+        for p in [&nullitope, &point, &dyad] {
+            assert_eq!(coords(&p.duoprism(&nullitope)), empty);
+            assert_eq!(coords(&nullitope.duoprism(p)), empty);
+            assert_eq!(coords(&p.duotegum(&nullitope)), empty);
+            assert_eq!(coords(&nullitope.duotegum(p)), empty);
+            assert_eq!(coords(&p.duocomb(&nullitope)), empty);
+            assert_eq!(coords(&nullitope.duocomb(p)), empty);
+        }
+
+        // The pyramid product with a point is a pyramid.
+        assert_eq!(
+            coords(&dyad.duopyramid(&point)),
+            vec![vec![-0.5, 0.5], vec![0.5, 0.5], vec![0.0, -0.5]]
+        );
+        assert_eq!(coords(&point.duopyramid(&point)), vec![vec![0.5], vec![-0.5]]);
+
+        // The prism and tegum products with a point leave the other factor
+        // unchanged.
+        assert_eq!(coords(&dyad.duoprism(&point)), coords(&dyad));
+        assert_eq!(coords(&point.duoprism(&dyad)), coords(&dyad));
+        assert_eq!(coords(&dyad.duotegum(&point)), coords(&dyad));
+        assert_eq!(coords(&point.duotegum(&dyad)), coords(&dyad));
+        assert_eq!(coords(&point.duotegum(&point)), vec![Vec::<f64>::new()]);
+
+        // The point absorbs the comb product.
+        assert_eq!(coords(&dyad.duocomb(&point)), empty);
+        assert_eq!(coords(&point.duocomb(&dyad)), empty);
+
+        // The provenance variant agrees with the plain duoprism.
+        assert_eq!(coords(&nullitope.duoprism_with_provenance(&dyad).0), empty);
+        assert_eq!(coords(&point.duoprism_with_provenance(&dyad).0), coords(&dyad));
+    }
+
+    /// Checks that rotating a hexacosichoron in place preserves its element
+    /// counts and edge lengths.
+    #[test]
+    fn apply_mut() {
+        use crate::geometry::Matrix;
+
+        let mut ex = Concrete::hexacosichoron();
+        assert!(ex.el_count_iter().eq([1, 120, 720, 1200, 600, 1]));
+        let edge_lens: Vec<_> = (0..ex.edge_count())
+            .map(|idx| ex.edge_len(idx).unwrap())
+            .collect();
+
+        // Rotates in the xy and zw planes by different angles.
+        let (sin1, cos1) = 0.3f64.sin_cos();
+        let (sin2, cos2) = 1.1f64.sin_cos();
+        let mut m = Matrix::identity(4, 4);
+        m[(0, 0)] = cos1;
+        m[(0, 1)] = -sin1;
+        m[(1, 0)] = sin1;
+        m[(1, 1)] = cos1;
+        m[(2, 2)] = cos2;
+        m[(2, 3)] = -sin2;
+        m[(3, 2)] = sin2;
+        m[(3, 3)] = cos2;
+
+        ex.apply_mut(&m).unwrap();
+        assert!(ex.el_count_iter().eq([1, 120, 720, 1200, 600, 1]));
+        for (idx, len) in edge_lens.into_iter().enumerate() {
+            assert!(abs_diff_eq!(ex.edge_len(idx).unwrap(), len, epsilon = 1e-12));
+        }
+
+        // Matrices that aren't 4 by 4 should be rejected, and leave the
+        // polytope unchanged.
+        let vertices = ex.vertices.clone();
+        for (rows, cols) in [(3, 3), (5, 4), (4, 5)] {
+            assert!(ex.apply_mut(&Matrix::identity(rows, cols)).is_err());
+        }
+        assert_eq!(ex.vertices, vertices);
+    }
+
+    /// Checks that the rotations built from vectors preserve edge lengths, and
+    /// that a fifth of a turn about an icosahedron's vertex is a symmetry.
+    #[test]
+    fn rotation_constructors() {
+        use crate::geometry::{Matrix, Rotation};
+
+        let cube = Concrete::hypercube(5);
+        let rotations = [
+            Matrix::rotation_in_plane(
+                &Point::from_vec(vec![1.0, 2.0, 0.0, -1.0]),
+                &Point::from_vec(vec![0.0, 1.0, 3.0, 1.0]),
+                0.7,
+            ),
+            Matrix::rotation_from_to(
+                &Point::from_vec(vec![1.0, 1.0, 1.0, 1.0]),
+                &Point::from_vec(vec![0.0, -1.0, 0.0, 0.0]),
+            ),
+        ];
+
+        for m in rotations {
+            let rotated = cube.clone().apply(&m.unwrap()).unwrap();
+            for idx in 0..cube.edge_count() {
+                let len = rotated.edge_len(idx).unwrap();
+                assert!(abs_diff_eq!(len, 1.0, epsilon = 1e-12), "edge length {}", len);
+            }
+        }
+
+        let ico = Concrete::platonic(4);
+        let m = Matrix::rotation_about_axis(&ico.vertices[0], f64::TAU / 5.0).unwrap();
+        let rotated = ico.clone().apply(&m).unwrap();
+        for idx in 0..ico.edge_count() {
+            let len = rotated.edge_len(idx).unwrap();
+            assert!(abs_diff_eq!(len, ico.edge_len(idx).unwrap(), epsilon = 1e-12));
+        }
+        for v in &rotated.vertices {
+            assert!(ico.vertices.iter().any(|w| (v - w).norm() < 1e-12));
+        }
+    }
+
+    /// Checks the planarity of the faces of a few polytopes.
+    #[test]
+    fn face_planarity() {
+        use crate::geometry::{Hyperplane, Vector};
+
+        let cube = Concrete::cube();
+        assert!(cube
+            .face_planarity()
+            .iter()
+            .all(|&x| abs_diff_eq!(x, 0.0, epsilon = f64::EPS)));
+
+        // The faces of a duocomb are products of edges, so they're planar.
+        let duocomb = Concrete::polygon(4).duocomb(&Concrete::polygon(5));
+        assert!(duocomb.max_face_planarity().unwrap() < f64::EPS);
+
+        // The faces of the Petrial of a cube are skew hexagons.
+        let petrial = Concrete::cube().petrial().unwrap();
+        assert!(petrial.face_planarity().iter().all(|&x| x > 0.1));
+
+        let slice = Hyperplane::new(Vector::from_vec(vec![1.0, 1.0, 1.0, 1.0]).normalize(), 0.1);
+        let mut section = Concrete::orthoplex(5).cross_section(&slice);
+        section.flatten();
+        assert_eq!(section.dim(), Some(3));
+        assert!(section.max_face_planarity().unwrap() < f64::EPS);
+
+        // No plane fits a square squashed onto a line.
+        let square = Concrete::polygon(4);
+        let line = Concrete::new(
+            square.vertices.iter().map(|v| Point::from_vec(vec![v[0]])).collect(),
+            square.abs.clone(),
+        );
+        assert!(line.face_planarity().is_empty());
+        assert!(line.face_area_centroids().is_empty());
+    }
+
+    /// Checks the areas of the faces of a few polytopes, and the content of
+    /// their surfaces.
+    #[test]
+    fn face_areas() {
+        use nalgebra::dvector;
+
+        let cube = Concrete::cube();
+        let areas = cube.face_areas();
+        assert_eq!(areas.len(), 6);
+        assert!(areas
+            .iter()
+            .all(|&area| abs_diff_eq!(area, 1.0, epsilon = f64::EPS)));
+        assert!(abs_diff_eq!(cube.surface_area().unwrap(), 6.0, epsilon = f64::EPS));
+        assert!(cube.skew_faces().is_empty());
+
+        let icosahedron = Concrete::icosahedron();
+        assert!(abs_diff_eq!(
+            icosahedron.surface_area().unwrap(),
+            5.0 * 3f64.sqrt(),
+            epsilon = 1e-9
+        ));
+
+        // Star polygons count their regions as many times as they wind around
+        // them, just like their volume.
+        for (n, d) in [(5, 1), (5, 2), (7, 3), (6, 2)] {
+            let area = Concrete::star_polygon(n, d).face_areas()[0];
+            assert!(abs_diff_eq!(area, polygon_area(n, d), epsilon = f64::EPS));
+        }
+
+        // The surface of a polygon is its perimeter, and that of a tesseract
+        // consists of eight cubes.
+        let perimeter = Concrete::polygon(6).surface_area().unwrap();
+        assert!(abs_diff_eq!(perimeter, 6.0, epsilon = f64::EPS));
+        let surface = Concrete::hypercube(5).surface_area().unwrap();
+        assert!(abs_diff_eq!(surface, 8.0, epsilon = f64::EPS));
+        assert_eq!(Concrete::dyad().surface_area(), None);
+
+        // A square with a vertex pushed out of its plane is skew.
+        let skew = Concrete::new(
+            vec![
+                dvector![0.0, 0.0, 0.0],
+                dvector![1.0, 0.0, 0.0],
+                dvector![1.0, 1.0, 0.5],
+                dvector![0.0, 1.0, 0.0],
+            ],
+            crate::abs::Abstract::polygon(4),
+        );
+        assert_eq!(skew.skew_faces(), vec![0]);
+        assert!(skew.face_areas()[0] > 1.0);
+    }
+
+    /// The area centroid doesn't depend on how the vertices are spread out,
+    /// unlike the gravicenter.
+    #[test]
+    fn centroid_by_area() {
+        use nalgebra::dvector;
+
+        // A unit square with an extra vertex halfway through its top edge.
+        let pentagon = Concrete::new(
+            vec![
+                dvector![0.0, 0.0],
+                dvector![1.0, 0.0],
+                dvector![1.0, 1.0],
+                dvector![0.5, 1.0],
+                dvector![0.0, 1.0],
+            ],
+            crate::abs::Abstract::polygon(5),
+        );
+        let centroid = pentagon.centroid_by_area().unwrap();
+        assert!(abs_diff_eq!(centroid, dvector![0.5, 0.5], epsilon = f64::EPS));
+        let gravicenter = pentagon.gravicenter().unwrap();
+        assert!(abs_diff_eq!(gravicenter, dvector![0.5, 0.6], epsilon = f64::EPS));
+
+        let mut cube = Concrete::cube();
+        cube.recenter_with_mut(&dvector![-1.0, 2.0, 0.5]);
+        let centroid = cube.centroid_by_area().unwrap();
+        assert!(abs_diff_eq!(centroid, dvector![1.0, -2.0, -0.5], epsilon = f64::EPS));
+
+        assert_eq!(Concrete::dyad().centroid_by_area(), None);
+    }
+
+    /// The facets of a tesseract are ordered by their last coordinate, with
+    /// ties kept in order.
+    #[test]
+    fn facet_order_by() {
+        let tesseract = Concrete::hypercube(5);
+        let order = tesseract.facet_order_by(|p| p[3]);
+        assert_eq!(order.len(), 8);
+
+        let w = |idx: usize| {
+            let vertices = tesseract.element_vertices_ref(4, idx).unwrap();
+            vertices.iter().map(|v| v[3]).sum::<f64>() / vertices.len() as f64
+        };
+        assert!(w(order[0]) < -0.25);
+        assert!(w(order[7]) > 0.25);
+        assert!(order.windows(2).all(|pair| w(pair[0]) <= w(pair[1])));
+    }
+
+    /// Checks projecting the vertices of a polytope onto a sphere.
+    #[test]
+    fn project_to_sphere() {
+        let cube = Concrete::cube().project_to_sphere(&Hypersphere::unit(3)).unwrap();
+        crate::test(&cube, [1, 8, 12, 6, 1]);
+        assert!(abs_diff_eq!(
+            cube.circumsphere().unwrap().radius(),
+            1.0,
+            epsilon = f64::EPS
+        ));
+        assert!(cube
+            .vertex_table()
+            .iter()
+            .all(|(_, _, norm)| abs_diff_eq!(*norm, 1.0, epsilon = f64::EPS)));
+
+        // The pyramid has its apex at the center of the sphere.
+        let mut pyramid = Concrete::polygon(4).pyramid();
+        let apex = pyramid.vertices.last().unwrap().clone();
+        let err = pyramid
+            .project_to_sphere_mut(&Hypersphere::with_radius(apex, 1.0))
+            .unwrap_err();
+        assert_eq!(err.0, 4);
+    }
+
+    /// Checks morphing a cube into a scaled and reordered copy of itself.
+    #[test]
+    fn morph() {
+        let cube = Concrete::cube();
+        let mut big = cube.clone().scale(3.0);
+        big.vertices_mut().reverse();
+
+        // Matching by order keeps the structure but swaps opposite vertices.
+        let half = cube.morph(&big, 0.5).unwrap();
+        assert!(half.el_count_iter().eq(cube.el_count_iter()));
+        for (idx, v) in half.vertices().iter().enumerate() {
+            let mid = (&cube.vertices()[idx] + &big.vertices()[idx]) / 2.0;
+            assert!(abs_diff_eq!((v - mid).norm(), 0.0, epsilon = f64::EPS));
+        }
+
+        // Matching by nearest neighbor recovers the scaling, and extrapolates
+        // past the endpoints.
+        for t in [-1.0, 0.0, 0.5, 1.0, 2.0] {
+            let morph = cube.morph_matched(&big, t).unwrap();
+            for (v, w) in morph.vertices().iter().zip(cube.vertices()) {
+                assert!(abs_diff_eq!((v - w * (1.0 + 2.0 * t)).norm(), 0.0, epsilon = f64::EPS));
+            }
+        }
+
+        // Mismatched vertex counts and non-bijective matchings fail.
+        assert!(cube.morph(&Concrete::simplex(4), 0.5).is_err());
+        let mut squashed = cube.clone();
+        squashed.vertices_mut()[0] = squashed.vertices()[1].clone();
+        assert!(matches!(
+            cube.morph_matched(&squashed, 0.5),
+            Err(super::MorphError::Unmatched(_))
+        ));
+
+        // Vertices that aren't finite can't be matched.
+        squashed.vertices_mut()[0][0] = f64::NAN;
+        assert!(matches!(
+            cube.morph_matched(&squashed, 0.5),
+            Err(super::MorphError::NonFinite)
+        ));
+    }
+
+    /// Checks the intersections of some convex polytopes.
+    #[test]
+    fn intersect() {
+        use crate::geometry::Vector;
+
+        // A cube and a translated copy intersect in a box.
+        let cube = Concrete::cube();
+        let shifted = cube.clone().shift(&Vector::from_vec(vec![0.5, 0.25, 0.0]));
+        let mut cuboid = cube.intersect(&shifted).unwrap();
+        cuboid.assert_valid();
+        crate::test(&cuboid, [1, 8, 12, 6, 1]);
+        cuboid.element_sort();
+        assert!(abs_diff_eq!(cuboid.volume().unwrap().unwrap(), 0.375, epsilon = f64::EPS));
+
+        // A cube and a far away cube don't intersect.
+        let far = cube.clone().shift(&Vector::from_vec(vec![3.0, 0.0, 0.0]));
+        assert!(cube.intersect(&far).is_none());
+
+        // Cubes sharing a face only intersect in that face.
+        let adjacent = cube.clone().shift(&Vector::from_vec(vec![1.0, 0.0, 0.0]));
+        assert!(cube.intersect(&adjacent).is_none());
+
+        // The components of the stella octangula intersect in an octahedron.
+        let tet = Concrete::tetrahedron().recenter();
+        let oct = tet.intersect(&tet.clone().scale(-1.0)).unwrap();
+        oct.assert_valid();
+        crate::test(&oct, [1, 6, 12, 8, 1]);
+    }
+
+    /// Checks that slicing a pentagrammic prism through both of its
+    /// pentagram bases gives a compound of two rectangles.
+    #[test]
+    fn pentagram_cross_section() {
+        use crate::geometry::{Hyperplane, Vector};
+
+        let prism = Concrete::star_polygon(5, 2).prism_with(1.0);
+        let slice = Hyperplane::new(Vector::from_vec(vec![1.0, 0.0, 0.0]), 0.05);
+        let mut section = prism.cross_section(&slice);
+
+        section.assert_valid();
+        crate::test(&section, [1, 8, 8, 1]);
+        section.element_sort();
+        assert_eq!(section.defiss().len(), 2);
+    }
+
+    /// Slices a cube barely above its bottom face. The fast mode considers
+    /// the bottom vertices to lie on the slice, and so misses the cube, while
+    /// the adaptive mode finds the square cross-section.
+    #[test]
+    fn cross_section_with_mode() {
+        use crate::geometry::{predicates::RobustnessMode, Hyperplane, Vector};
+
+        let cube = Concrete::cube();
+        let slice = Hyperplane::new(Vector::from_vec(vec![0.0, 0.0, 1.0]), -0.5 + 1e-12);
+
+        let fast = cube.cross_section_with_mode(&slice, RobustnessMode::Fast);
+        crate::test(&fast, [1]);
+
+        let adaptive = cube.cross_section_with_mode(&slice, RobustnessMode::Adaptive);
+        crate::test(&adaptive, [1, 4, 4, 1]);
+        assert!(adaptive.vertices.iter().all(|v| (v[2] + 0.5).abs() < 1e-11));
+    }
+
+    /// Checks that every element of a slice of a cube is traced back to the
+    /// element of the cube it lies in.
+    #[test]
+    fn cross_section_provenance() {
+        use crate::geometry::{Hyperplane, Vector};
+
+        let cube = Concrete::cube();
+        let slice = Hyperplane::new(Vector::from_vec(vec![0.0, 0.0, 1.0]), 0.0);
+        let (section, provenance) = cube.cross_section_with_provenance(&slice);
+        crate::test(&section, [1, 4, 4, 1]);
+
+        // Every vertex is the midpoint of a vertical edge.
+        for (idx, v) in section.vertices.iter().enumerate() {
+            let edge = match provenance.get(1, idx) {
+                Some(Source::Element(2, edge)) => edge,
+                source => panic!("unexpected source {:?}", source),
+            };
+
+            let [v0, v1] = cube.edge_endpoints(edge).unwrap();
+            assert!(((v0 + v1) / 2.0 - v).norm() < f64::EPS);
+        }
+
+        // Every edge lies on a side face.
+        for idx in 0..section.edge_count() {
+            let face = match provenance.get(2, idx) {
+                Some(Source::Element(3, face)) => face,
+                source => panic!("unexpected source {:?}", source),
+            };
+
+            let center = cube.element_vertices_ref(3, face).unwrap().into_iter().sum::<Point<f64>>() / 4.0;
+            let [v0, v1] = section.edge_endpoints(idx).unwrap();
+            assert!(center[2].abs() < f64::EPS);
+            assert!(((v0 + v1) / 2.0 - &center).dot(&center).abs() < f64::EPS);
+        }
+
+        assert_eq!(provenance.get(3, 0), Some(Source::Element(4, 0)));
+    }
+
+    /// Slices a cube through three of its vertices, and through a single one.
+    /// The crossings of the edges meeting at each of these vertices are
+    /// merged, giving a triangle and a point.
+    #[test]
+    fn cross_section_through_vertices() {
+        use crate::geometry::{Hyperplane, Vector};
+
+        let cube = Concrete::cube();
+        let normal = Vector::from_vec(vec![1.0, 1.0, 1.0]) / 3f64.sqrt();
+        let slice = Hyperplane::new(normal.clone(), 0.5 / 3f64.sqrt());
+        let (section, provenance) = cube.cross_section_with_provenance(&slice);
+        section.assert_valid();
+        crate::test(&section, [1, 3, 3, 1]);
+
+        // Every vertex is a vertex of the cube.
+        for (idx, v) in section.vertices.iter().enumerate() {
+            match provenance.get(1, idx) {
+                Some(Source::Element(1, w)) => assert!((&cube.vertices[w] - v).norm() < f64::EPS),
+                source => panic!("unexpected source {:?}", source),
+            }
+        }
+
+        let slice = Hyperplane::new(normal, 1.5 / 3f64.sqrt());
+        let (section, provenance) = cube.cross_section_with_provenance(&slice);
+        crate::test(&section, [1, 1]);
+        assert!((&section.vertices[0] - Point::from_vec(vec![0.5; 3])).norm() < f64::EPS);
+        assert!(matches!(provenance.get(1, 0), Some(Source::Element(1, _))));
+    }
+
+    /// Checks the provenance of the elements of a duoprism.
+    #[test]
+    fn duoprism_provenance() {
+        let (prism, provenance) = Concrete::polygon(3).duoprism_with_provenance(&Concrete::dyad());
+        crate::test(&prism, [1, 6, 9, 5, 1]);
+
+        // The vertices are ordered lexicographically by their sources.
+        assert_eq!(provenance.get(1, 3), Some(Source::Pair((1, 1), (1, 1))));
+        assert_eq!(provenance.get(4, 0), Some(Source::Pair((3, 0), (2, 0))));
+
+        // Every lateral face is an edge of the triangle times the dyad.
+        let laterals = (0..5)
+            .filter(|&idx| matches!(provenance.get(3, idx), Some(Source::Pair((2, _), (2, 0)))))
+            .count();
+        assert_eq!(laterals, 3);
+    }
+
     #[test]
     fn simplex() {
         for n in 1..=6 {
@@ -1726,4 +4115,136 @@ mod tests {
             );
         }
     }
+
+    /// Checks the bounding box of a triangle with negative coordinates, of a
+    /// cube, and of the nullitope.
+    #[test]
+    fn bounding_box() {
+        let triangle = Concrete::new(
+            vec![
+                Point::from_vec(vec![-3.0, 1.0]),
+                Point::from_vec(vec![2.0, -4.0]),
+                Point::from_vec(vec![0.0, 5.0]),
+            ],
+            crate::abs::Abstract::polygon(3),
+        );
+        assert_eq!(
+            triangle.bounding_box(),
+            Some((
+                Point::from_vec(vec![-3.0, -4.0]),
+                Point::from_vec(vec![2.0, 5.0])
+            ))
+        );
+        assert!(abs_diff_eq!(
+            triangle.extent_radius(),
+            106f64.sqrt() / 2.0,
+            epsilon = f64::EPS
+        ));
+
+        let cube = Concrete::hypercube(4);
+        let (min, max) = cube.bounding_box().unwrap();
+        assert_eq!(min, Point::from_element(3, -0.5));
+        assert_eq!(max, Point::from_element(3, 0.5));
+        assert!(abs_diff_eq!(cube.extent_radius(), 3f64.sqrt() / 2.0, epsilon = f64::EPS));
+
+        assert_eq!(Concrete::nullitope().bounding_box(), None);
+        assert_eq!(Concrete::nullitope().extent_radius(), 0.0);
+    }
+
+    /// Checks that uniform antiprisms have unit edges.
+    #[test]
+    fn uniform_antiprism() {
+        for (n, d) in [(3, 1), (5, 1), (5, 2), (7, 3), (4, 2)] {
+            let antiprism = Concrete::uniform_antiprism(n, d);
+            for idx in 0..antiprism.edge_count() {
+                let len = antiprism.edge_len(idx).unwrap();
+                assert!(
+                    abs_diff_eq!(len, 1.0, epsilon = f64::EPS),
+                    "edge {} of the {}/{} antiprism has length {}",
+                    idx,
+                    n,
+                    d,
+                    len
+                );
+            }
+        }
+    }
+
+    /// Checks that an antiprism is retried with a recentered hypersphere when
+    /// the base has a facet through the origin.
+    #[test]
+    fn antiprism_recenter() {
+        // A pentagon with a vertex at the origin.
+        let mut pentagon = Concrete::polygon(5);
+        let vertex = pentagon.vertices[0].clone();
+        pentagon.recenter_with_mut(&vertex);
+
+        assert!(matches!(
+            pentagon.try_antiprism_with(&Hypersphere::unit(2), 1.0),
+            Err(AntiprismError::Dual(_))
+        ));
+
+        let (antiprism, center) = pentagon.try_antiprism_auto().unwrap();
+        assert_eq!(center, DualCenter::Circumcenter);
+        crate::test(&antiprism, [1, 10, 20, 12, 1]);
+    }
+
+    /// Checks that an antiprism fails when a facet of the base passes through
+    /// every center that's tried.
+    #[test]
+    fn antiprism_dual_error() {
+        // A crossed square, whose diagonals pass through all of its centers.
+        let bowtie = Concrete::new(
+            vec![
+                Point::from_vec(vec![1.0, 1.0]),
+                Point::from_vec(vec![-1.0, -1.0]),
+                Point::from_vec(vec![1.0, -1.0]),
+                Point::from_vec(vec![-1.0, 1.0]),
+            ],
+            crate::abs::Abstract::polygon(4),
+        );
+
+        match bowtie.try_antiprism() {
+            Err(AntiprismError::Dual(DualError(idx))) => {
+                let edge = bowtie.element_vertices_ref(2, idx).unwrap();
+                assert!(abs_diff_eq!(*edge[0], -edge[1]));
+            }
+            result => panic!("expected a dual error, found {:?}", result.map(|_| ())),
+        }
+    }
+
+    /// A shared polytope is only cloned when it's modified while something
+    /// else holds onto it.
+    #[test]
+    fn copy_on_write() {
+        let mut poly = Arc::new(Concrete::hypercube(4));
+        let ptr = Arc::as_ptr(&poly);
+        make_mut(&mut poly).scale_mut(2.0);
+        assert_eq!(Arc::as_ptr(&poly), ptr);
+
+        // Storing the polytope in many places doesn't copy it.
+        let copies: Vec<_> = (0..15).map(|_| Arc::clone(&poly)).collect();
+        assert!(copies.iter().all(|copy| Arc::ptr_eq(copy, &poly)));
+
+        make_mut(&mut poly).scale_mut(0.5);
+        assert_ne!(Arc::as_ptr(&poly), ptr);
+        assert!(copies.iter().all(|copy| Arc::as_ptr(copy) == ptr));
+        assert_eq!(copies[0].vertices[0], Point::from_element(3, -1.0));
+        assert_eq!(poly.vertices[0], Point::from_element(3, -0.5));
+    }
+
+    /// Collinear edges of a polygon are fused, even when rounding errors
+    /// move their lines apart.
+    #[test]
+    fn fuse_facets() {
+        let vertices = vec![
+            Point::from_vec(vec![1.0, 1.0]),
+            Point::from_vec(vec![2.0, 1.0 + 1e-12]),
+            Point::from_vec(vec![3.0, 1.0]),
+            Point::from_vec(vec![3.0, 3.0]),
+            Point::from_vec(vec![1.0, 3.0]),
+        ];
+        let poly = Concrete::new(vertices, crate::abs::Abstract::polygon(5));
+        assert_eq!(poly.fuse_facets().facet_count(), 4);
+    }
 }
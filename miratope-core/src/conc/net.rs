@@ -0,0 +1,449 @@
+//! Unfolds polyhedra into nets, by laying their faces out in the plane.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Display,
+};
+
+use super::{cycle::CycleList, Concrete};
+use crate::{
+    abs::Ranked,
+    float::Float,
+    geometry::{Point, Subspace},
+};
+
+use vec_like::*;
+
+/// An error while unfolding a polyhedron.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnfoldError {
+    /// Only polyhedra can be unfolded.
+    Rank(usize),
+
+    /// There's no face with the given index.
+    FaceIndex(usize),
+
+    /// The face with the given index doesn't span a plane.
+    Degenerate(usize),
+
+    /// The face with the given index is a compound of several polygons.
+    CompoundFace(usize),
+
+    /// The face with the given index can't be reached from the root face
+    /// through the edges.
+    Disconnected(usize),
+}
+
+impl Display for UnfoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rank(rank) => write!(
+                f,
+                "only polyhedra can be unfolded, found a polytope of rank {}",
+                *rank as isize - 1
+            ),
+            Self::FaceIndex(idx) => write!(f, "there's no face with index {}", idx),
+            Self::Degenerate(idx) => write!(f, "face {} doesn't span a plane", idx),
+            Self::CompoundFace(idx) => write!(f, "face {} is a compound", idx),
+            Self::Disconnected(idx) => {
+                write!(f, "face {} isn't connected to the root face", idx)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnfoldError {}
+
+/// The net of a polyhedron, as a surface on the plane. Its edges along the
+/// border only belong to a single face, so this isn't a valid polytope.
+#[derive(Clone, Debug)]
+pub struct Net {
+    /// The vertices of the net, on the plane.
+    pub vertices: Vec<Point<f64>>,
+
+    /// The faces of the net as cycles of vertex indices, in the same order as
+    /// in the polyhedron. Faces glued along an edge share its vertices.
+    pub faces: Vec<Vec<usize>>,
+
+    /// The pairs of faces of the net that overlap.
+    pub overlaps: Vec<(usize, usize)>,
+}
+
+impl Net {
+    /// Returns the number of edges of the net, counting each edge shared by
+    /// two faces once.
+    pub fn edge_count(&self) -> usize {
+        let mut edges = BTreeSet::new();
+        for face in &self.faces {
+            for (i, &v) in face.iter().enumerate() {
+                let w = face[(i + 1) % face.len()];
+                edges.insert((v.min(w), v.max(w)));
+            }
+        }
+
+        edges.len()
+    }
+
+    /// Writes the net into an OFF file, on the plane z = 0. Loading this file
+    /// gives an open surface, like any other OFF file with edges that only
+    /// belong to a single face.
+    pub fn to_off(&self) -> String {
+        let mut off = format!(
+            "OFF\n{} {} {}\n",
+            self.vertices.len(),
+            self.faces.len(),
+            self.edge_count()
+        );
+
+        for v in &self.vertices {
+            off.push_str(&format!("{} {} 0\n", v[0], v[1]));
+        }
+
+        for face in &self.faces {
+            off.push_str(&face.len().to_string());
+            for v in face {
+                off.push_str(&format!(" {}", v));
+            }
+            off.push('\n');
+        }
+
+        off
+    }
+}
+
+/// Returns the cross product of two vectors in the plane.
+fn cross(p: &Point<f64>, q: &Point<f64>) -> f64 {
+    p[0] * q[1] - p[1] * q[0]
+}
+
+/// Returns a vector in the plane rotated by a quarter turn.
+fn perp(p: &Point<f64>) -> Point<f64> {
+    Point::from_vec(vec![-p[1], p[0]])
+}
+
+/// Returns the convex hull of a set of points in the plane, in
+/// counterclockwise order.
+fn convex_hull(points: &[Point<f64>]) -> Vec<Point<f64>> {
+    let mut points = points.to_vec();
+    points.sort_by(|p, q| (p[0], p[1]).partial_cmp(&(q[0], q[1])).unwrap());
+
+    // Builds the lower hull, then the upper hull.
+    let mut hull: Vec<Point<f64>> = Vec::new();
+    for pass in 0..2 {
+        let start = hull.len();
+        for p in &points {
+            while hull.len() >= start + 2 {
+                let a = &hull[hull.len() - 2];
+                let b = &hull[hull.len() - 1];
+                if cross(&(b - a), &(p - a)) > f64::EPS {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(p.clone());
+        }
+
+        hull.pop();
+        if pass == 0 {
+            points.reverse();
+        }
+    }
+
+    hull
+}
+
+/// Returns the area of a polygon in the plane.
+fn area(polygon: &[Point<f64>]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| cross(&polygon[i], &polygon[(i + 1) % n]))
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Clips a polygon by a convex polygon in counterclockwise order, and returns
+/// the area of their intersection.
+fn intersection_area(polygon: &[Point<f64>], convex: &[Point<f64>]) -> f64 {
+    let mut clipped = polygon.to_vec();
+
+    for i in 0..convex.len() {
+        let a = &convex[i];
+        let dir = &convex[(i + 1) % convex.len()] - a;
+        let side = |p: &Point<f64>| cross(&dir, &(p - a));
+
+        let input = std::mem::take(&mut clipped);
+        for j in 0..input.len() {
+            let p = &input[j];
+            let q = &input[(j + 1) % input.len()];
+            let (sp, sq) = (side(p), side(q));
+
+            if sp >= 0.0 {
+                clipped.push(p.clone());
+            }
+            if (sp >= 0.0) != (sq >= 0.0) {
+                clipped.push(p + (q - p) * (sp / (sp - sq)));
+            }
+        }
+    }
+
+    area(&clipped).abs()
+}
+
+impl Concrete {
+    /// Unfolds a polyhedron into a net. The faces are found through a
+    /// breadth-first search along the edges starting from the root face,
+    /// which is placed first. Every other face is then rotated about the edge
+    /// it shares with the face it was reached from, until both lie flat on
+    /// the same plane, on either side of the edge.
+    ///
+    /// Overlapping faces don't stop the net from being built, but they're
+    /// listed in the result. Non-convex faces are compared through their
+    /// convex hulls, so they may be reported as overlapping when they only
+    /// come close.
+    pub fn unfold(&self, root_face: usize) -> Result<Net, UnfoldError> {
+        let rank = self.rank();
+        if rank != 4 {
+            return Err(UnfoldError::Rank(rank));
+        }
+
+        let face_count = self.face_count();
+        if root_face >= face_count {
+            return Err(UnfoldError::FaceIndex(root_face));
+        }
+
+        // The vertices of every face, in coordinates on the plane of the face,
+        // and the cycle they form.
+        let mut local = Vec::with_capacity(face_count);
+        let mut cycles = Vec::with_capacity(face_count);
+        for idx in 0..face_count {
+            let mut face_cycles = CycleList::from_edges(
+                self[(3, idx)].subs.iter().map(|&i| &self[(2, i)].subs),
+            );
+            if face_cycles.len() != 1 {
+                return Err(UnfoldError::CompoundFace(idx));
+            }
+            cycles.push(face_cycles.swap_remove(0));
+
+            let vertices = self.abs.element_vertices(3, idx).unwrap();
+            let subspace = Subspace::from_points(vertices.iter().map(|&v| &self.vertices[v]));
+            if subspace.rank() != 2 {
+                return Err(UnfoldError::Degenerate(idx));
+            }
+
+            local.push(
+                vertices
+                    .into_iter()
+                    .map(|v| (v, subspace.flatten(&self.vertices[v])))
+                    .collect::<BTreeMap<_, _>>(),
+            );
+        }
+
+        // The vertices of the net, and the index in the net of every vertex
+        // of every face that has been placed.
+        let mut net_vertices: Vec<Point<f64>> = Vec::new();
+        let mut placed: Vec<Option<BTreeMap<usize, usize>>> = vec![None; face_count];
+
+        let mut root = BTreeMap::new();
+        for (&v, p) in &local[root_face] {
+            root.insert(v, net_vertices.len());
+            net_vertices.push(p.clone());
+        }
+        placed[root_face] = Some(root);
+
+        let mut queue = VecDeque::new();
+        queue.push_back(root_face);
+
+        while let Some(parent) = queue.pop_front() {
+            for &edge in &self[(3, parent)].subs {
+                for &child in &self[(2, edge)].sups {
+                    if placed[child].is_some() {
+                        continue;
+                    }
+
+                    let parent_map = placed[parent].as_ref().unwrap();
+                    let (a, b) = (self[(2, edge)].subs[0], self[(2, edge)].subs[1]);
+                    let pa = net_vertices[parent_map[&a]].clone();
+                    let pb = &net_vertices[parent_map[&b]];
+                    let (la, lb) = (&local[child][&a], &local[child][&b]);
+
+                    // The rigid motion taking the shared edge of the child
+                    // face onto that of the parent face.
+                    let u = (lb - la).normalize();
+                    let v = (pb - &pa).normalize();
+                    let (u_perp, v_perp) = (perp(&u), perp(&v));
+
+                    // The child must end up on the other side of the edge.
+                    let side = |p: &Point<f64>| (p - &pa).dot(&v_perp);
+                    let parent_side: f64 =
+                        parent_map.values().map(|&i| side(&net_vertices[i])).sum();
+                    let child_side: f64 =
+                        local[child].values().map(|p| (p - la).dot(&u_perp)).sum();
+                    let sign = if parent_side * child_side > 0.0 {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+
+                    let mut map = BTreeMap::new();
+                    map.insert(a, parent_map[&a]);
+                    map.insert(b, parent_map[&b]);
+
+                    for (&w, p) in &local[child] {
+                        if w != a && w != b {
+                            let d = p - la;
+                            map.insert(w, net_vertices.len());
+                            net_vertices
+                                .push(&pa + &v * d.dot(&u) + &v_perp * (sign * d.dot(&u_perp)));
+                        }
+                    }
+
+                    placed[child] = Some(map);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        let maps = placed
+            .into_iter()
+            .enumerate()
+            .map(|(idx, map)| map.ok_or(UnfoldError::Disconnected(idx)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The faces of the net, with the vertices of the edges shared by a
+        // face and the face it was reached from in common.
+        let faces = cycles
+            .iter()
+            .zip(&maps)
+            .map(|(cycle, map)| cycle.iter().map(|v| map[v]).collect())
+            .collect();
+
+        // Finds the overlapping pairs of faces.
+        let hulls: Vec<_> = maps
+            .iter()
+            .map(|map| {
+                let mut hull = convex_hull(
+                    &map.values()
+                        .map(|&i| net_vertices[i].clone())
+                        .collect::<Vec<_>>(),
+                );
+                if area(&hull) < 0.0 {
+                    hull.reverse();
+                }
+                hull
+            })
+            .collect();
+
+        let mut overlaps = Vec::new();
+        for i in 0..face_count {
+            for j in i + 1..face_count {
+                let tolerance = f64::EPS * area(&hulls[i]).min(area(&hulls[j]));
+                if intersection_area(&hulls[i], &hulls[j]) > tolerance {
+                    overlaps.push((i, j));
+                }
+            }
+        }
+
+        Ok(Net {
+            vertices: net_vertices,
+            faces,
+            overlaps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, file::FromFile, Polytope};
+
+    use approx::assert_abs_diff_eq;
+
+    /// Returns the area of every face of a net.
+    fn face_areas(net: &Net) -> Vec<f64> {
+        net.faces
+            .iter()
+            .map(|face| {
+                let vertices: Vec<_> = face.iter().map(|&v| net.vertices[v].clone()).collect();
+                area(&vertices).abs()
+            })
+            .collect()
+    }
+
+    /// The net of a cube is a hexomino.
+    #[test]
+    fn cube() {
+        let cube = Concrete::hypercube(4);
+        let net = cube.unfold(0).unwrap();
+        assert!(net.overlaps.is_empty());
+
+        // There are 6 squares, glued along 5 edges.
+        assert_eq!(net.faces.len(), 6);
+        assert!(net.faces.iter().all(|face| face.len() == 4));
+        assert_eq!(net.edge_count(), 24 - 5);
+        assert_eq!(net.vertices.len(), 24 - 2 * 5);
+
+        // The net loads as a surface with the same elements, whose shared
+        // edges belong to two faces.
+        let poly = Concrete::from_off(&net.to_off()).unwrap();
+        assert_eq!(poly.el_count(3), 6);
+        assert_eq!(poly.el_count(2), 24 - 5);
+        assert_eq!(poly.vertex_count(), 24 - 2 * 5);
+        assert_eq!(
+            poly[2].iter().filter(|edge| edge.sups.len() == 2).count(),
+            5
+        );
+        assert!(poly.vertices.iter().all(|v| v[2] == 0.0));
+
+        // Every edge keeps its length.
+        let edge = &cube[(2, 0)].subs;
+        let edge_length = (&cube.vertices[edge[0]] - &cube.vertices[edge[1]]).norm();
+        for edge in poly[2].iter() {
+            let length = (&poly.vertices[edge.subs[0]] - &poly.vertices[edge.subs[1]]).norm();
+            assert_abs_diff_eq!(length, edge_length, epsilon = 1e-9);
+        }
+
+        // The net covers as much area as the surface of the cube.
+        assert_abs_diff_eq!(
+            face_areas(&net).iter().sum::<f64>(),
+            6.0 * edge_length * edge_length,
+            epsilon = 1e-9
+        );
+    }
+
+    /// Every root gives a net of the icosahedron with the same area.
+    #[test]
+    fn icosahedron() {
+        let icosahedron = Concrete::platonic(4);
+        let area = face_areas(&icosahedron.unfold(0).unwrap()).iter().sum::<f64>();
+
+        for root in [0, 7, 19] {
+            let net = icosahedron.unfold(root).unwrap();
+            assert_eq!(net.faces.len(), 20);
+            assert_eq!(net.edge_count(), 60 - 19);
+            assert_abs_diff_eq!(face_areas(&net).iter().sum::<f64>(), area, epsilon = 1e-9);
+        }
+    }
+
+    /// Only the faces of polyhedra can be laid out.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Concrete::polygon(5).unfold(0).err(),
+            Some(UnfoldError::Rank(3))
+        );
+        assert_eq!(
+            Concrete::hypercube(4).unfold(6).err(),
+            Some(UnfoldError::FaceIndex(6))
+        );
+
+        let mut compound = Concrete::hypercube(4);
+        compound.comp_append(Concrete::hypercube(4));
+        assert_eq!(compound.unfold(0).err(), Some(UnfoldError::Disconnected(6)));
+
+        // The bases of a hexagrammic prism are compounds of two triangles.
+        assert!(matches!(
+            Concrete::star_polygon(6, 2).prism().unfold(0),
+            Err(UnfoldError::CompoundFace(_))
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+//! Builds the antipodal quotient of a centrally symmetric polytope, like the
+//! hemicube or the hemidodecahedron.
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::{quotient::vertex_classes, AbstractError, Ranked},
+    float::Float,
+};
+
+/// An error while building the antipodal quotient of a polytope.
+#[derive(Clone, Copy, Debug)]
+pub enum QuotientError {
+    /// Some vertex has no antipodal vertex, so the polytope isn't centrally
+    /// symmetric.
+    NoAntipode(usize),
+
+    /// The quotient isn't a valid polytope.
+    Abstract(AbstractError),
+}
+
+impl std::fmt::Display for QuotientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoAntipode(idx) => write!(f, "vertex {} has no antipodal vertex", idx),
+            Self::Abstract(err) => write!(f, "the quotient isn't valid: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for QuotientError {}
+
+impl From<AbstractError> for QuotientError {
+    fn from(err: AbstractError) -> Self {
+        Self::Abstract(err)
+    }
+}
+
+impl Concrete {
+    /// Builds the quotient of a centrally symmetric polytope by its central
+    /// inversion, by identifying every vertex with the one opposite to it
+    /// through the gravicenter. See
+    /// [`Abstract::quotient`](crate::abs::Abstract::quotient).
+    ///
+    /// Each vertex of the quotient is placed at the first vertex of its pair,
+    /// so the result is only an abstract quotient drawn over half of the
+    /// original polytope.
+    pub fn antipodal_quotient(&self) -> Result<Self, QuotientError> {
+        let center = match self.gravicenter() {
+            Some(center) => center,
+            None => return Ok(self.clone()),
+        };
+
+        let mut identification = Vec::with_capacity(self.vertex_count());
+        for (idx, v) in self.vertices.iter().enumerate() {
+            let antipode = &center * 2.0 - v;
+            let antipode_idx = self
+                .vertices
+                .iter()
+                .position(|w| (w - &antipode).norm() < f64::EPS)
+                .ok_or(QuotientError::NoAntipode(idx))?;
+
+            identification.push((idx, antipode_idx));
+        }
+
+        let abs = self.abs.quotient(&identification)?;
+        let vertices = vertex_classes(self.vertex_count(), &identification)
+            .into_iter()
+            .enumerate()
+            .filter(|&(v, first)| v == first)
+            .map(|(v, _)| self.vertices[v].clone())
+            .collect();
+
+        Ok(Self::new(vertices, abs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// The antipodal quotient of a cube is the hemicube.
+    #[test]
+    fn hemicube() {
+        let mut hemicube = Concrete::cube().antipodal_quotient().unwrap();
+        test(&hemicube, [1, 4, 6, 3, 1]);
+        assert!(!hemicube.orientable_mut());
+    }
+
+    /// The antipodal quotient of a dodecahedron is the hemidodecahedron.
+    #[test]
+    fn hemidodecahedron() {
+        let mut hemidodecahedron = Concrete::platonic(3).antipodal_quotient().unwrap();
+        test(&hemidodecahedron, [1, 10, 15, 6, 1]);
+        assert!(!hemidodecahedron.orientable_mut());
+    }
+
+    /// Polytopes without central symmetry have no antipodal quotient.
+    #[test]
+    fn tetrahedron() {
+        assert!(matches!(
+            Concrete::platonic(0).antipodal_quotient(),
+            Err(QuotientError::NoAntipode(_))
+        ));
+    }
+}
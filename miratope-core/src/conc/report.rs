@@ -0,0 +1,393 @@
+//! Builds a structured report of the properties of a polytope, which can be
+//! shown in the UI or printed from the command line.
+
+use std::fmt::{self, Display};
+
+use super::{
+    edge_stats::{EdgeStats, EquilateralReport}, element_types::element_name, element_types::EL_SUFFIXES, Concrete,
+    ConcretePolytope,
+};
+use crate::{
+    abs::Ranked,
+    float::Float,
+    geometry::{Point, Subspace},
+    Polytope,
+};
+
+use approx::abs_diff_eq;
+use serde::{Deserialize, Serialize};
+use vec_like::*;
+
+/// A single entry of a [`Report`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReportEntry<T> {
+    /// The entry hasn't been computed.
+    Skipped,
+
+    /// The entry isn't defined for the polytope.
+    Undefined,
+
+    /// The value of the entry.
+    Value(T),
+}
+
+impl<T> ReportEntry<T> {
+    /// Returns whether the entry still has to be computed.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skipped)
+    }
+
+    /// Returns the value of the entry, if it was computed and defined.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for ReportEntry<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Value(value),
+            None => Self::Undefined,
+        }
+    }
+}
+
+impl<T: Display> Display for ReportEntry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Skipped => write!(f, "not computed"),
+            Self::Undefined => write!(f, "undefined"),
+            Self::Value(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Determines which of the expensive entries of a [`Report`] get computed.
+/// Every other entry is always computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportOptions {
+    /// Whether to compute the midradius and inradius.
+    pub radii: bool,
+
+    /// Whether to compute the volume.
+    pub volume: bool,
+
+    /// Whether to determine the orientability.
+    pub orientability: bool,
+
+    /// Whether to tally up the element types.
+    pub element_types: bool,
+}
+
+impl ReportOptions {
+    /// Options that compute every entry of a report.
+    pub fn all() -> Self {
+        Self {
+            radii: true,
+            volume: true,
+            orientability: true,
+            element_types: true,
+        }
+    }
+}
+
+/// A report of the properties of a polytope.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// The name of the polytope, if known.
+    pub name: Option<String>,
+
+    /// The rank of the polytope, which is one less than its rank in the
+    /// element lists.
+    pub rank: isize,
+
+    /// The number of dimensions of the space the polytope lives in, or `None`
+    /// in the case of the nullitope.
+    pub dim: Option<usize>,
+
+    /// The number of elements of each rank.
+    pub el_counts: Vec<usize>,
+
+    /// The alternating sum of the counts of the proper elements.
+    pub euler_characteristic: isize,
+
+    /// Whether all of the edges of the polytope have the same length, and
+    /// which don't.
+    pub equilateral: EquilateralReport,
+
+    /// The statistics of the edge lengths.
+    pub edge_lengths: EdgeStats,
+
+    /// The radius of the circumsphere.
+    pub circumradius: ReportEntry<f64>,
+
+    /// The common distance from the center to the edge midpoints.
+    pub midradius: ReportEntry<f64>,
+
+    /// The common distance from the center to the facets.
+    pub inradius: ReportEntry<f64>,
+
+    /// The volume of the polytope.
+    pub volume: ReportEntry<f64>,
+
+    /// The total content of the facets of the polytope, which is only
+    /// approximate if it has skew faces.
+    pub surface_area: ReportEntry<f64>,
+
+    /// Whether the polytope is orientable.
+    pub orientable: ReportEntry<bool>,
+
+    /// A description of the element types of each rank, from the vertices to
+    /// the facets.
+    pub element_types: ReportEntry<Vec<Vec<String>>>,
+}
+
+/// Returns the common value of an iterator of floats, or `None` if the values
+/// differ or the iterator is empty.
+fn common_value<I: Iterator<Item = f64>>(mut iter: I) -> Option<f64> {
+    let first = iter.next()?;
+    if iter.all(|x| abs_diff_eq!(x, first, epsilon = f64::EPS)) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+impl Report {
+    /// Computes the entries of a report that were requested in the options
+    /// but haven't been computed yet.
+    pub fn compute(&mut self, poly: &Concrete, options: ReportOptions) {
+        if options.radii && self.inradius.is_skipped() {
+            let center = poly
+                .circumsphere()
+                .map(|sphere| sphere.center)
+                .or_else(|| poly.gravicenter());
+
+            if let Some(center) = center {
+                self.midradius = poly.midradius_from(&center).into();
+                self.inradius = poly.inradius_from(&center).into();
+            } else {
+                self.midradius = ReportEntry::Undefined;
+                self.inradius = ReportEntry::Undefined;
+            }
+        }
+
+        // Volume and orientability are computed on a sorted, flattened copy.
+        let needs_sort = (options.volume && self.volume.is_skipped())
+            || (options.orientability && self.orientable.is_skipped());
+
+        if needs_sort {
+            let mut poly = poly.clone();
+            poly.element_sort();
+
+            if options.volume && self.volume.is_skipped() {
+                poly.ensure_flat();
+                self.volume = poly.volume().ok().flatten().into();
+                self.surface_area = poly.surface_area().into();
+            }
+
+            if options.orientability && self.orientable.is_skipped() {
+                // Orientability is only defined for polytopes with flags.
+                self.orientable = (poly.rank() >= 2).then(|| poly.orientable()).into();
+            }
+        }
+
+        if options.element_types && self.element_types.is_skipped() {
+            self.element_types = poly.element_type_descriptions().into();
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            writeln!(f, "Name: {}", name)?;
+        }
+
+        writeln!(f, "Rank: {}", self.rank)?;
+        match self.dim {
+            Some(dim) => writeln!(f, "Dimension: {}", dim)?,
+            None => writeln!(f, "Dimension: undefined")?,
+        }
+
+        let counts: Vec<_> = self.el_counts.iter().map(usize::to_string).collect();
+        writeln!(f, "Element counts: {}", counts.join(", "))?;
+        writeln!(f, "Euler characteristic: {}", self.euler_characteristic)?;
+        writeln!(f, "Equilateral: {}", self.equilateral)?;
+        write!(f, "Edge lengths: {}", self.edge_lengths)?;
+        writeln!(f, "Circumradius: {}", self.circumradius)?;
+        writeln!(f, "Midradius: {}", self.midradius)?;
+        writeln!(f, "Inradius: {}", self.inradius)?;
+        writeln!(f, "Volume: {}", self.volume)?;
+        writeln!(f, "Surface area: {}", self.surface_area)?;
+        writeln!(f, "Orientable: {}", self.orientable)?;
+
+        match &self.element_types {
+            ReportEntry::Value(types) => {
+                writeln!(f, "Element types:")?;
+                for (r, types) in types.iter().enumerate() {
+                    writeln!(f, "  {}: {}", element_name(r as isize), types.join("; "))?;
+                }
+
+                Ok(())
+            }
+            ReportEntry::Skipped => writeln!(f, "Element types: not computed"),
+            ReportEntry::Undefined => writeln!(f, "Element types: undefined"),
+        }
+    }
+}
+
+impl Concrete {
+    /// Builds a report of the properties of the polytope. The expensive
+    /// entries are only computed when requested in the options.
+    pub fn report(&self, options: ReportOptions) -> Report {
+        let rank = self.rank();
+        let euler_characteristic = (1..rank)
+            .map(|r| {
+                let count = self.el_count(r) as isize;
+                if r % 2 == 1 {
+                    count
+                } else {
+                    -count
+                }
+            })
+            .sum();
+
+        let mut report = Report {
+            name: None,
+            rank: rank as isize - 1,
+            dim: self.dim(),
+            el_counts: self.el_count_iter().collect(),
+            euler_characteristic,
+            equilateral: self.equilateral_report(Default::default()),
+            edge_lengths: self.edge_length_stats(),
+            circumradius: self.circumsphere().map(|sphere| sphere.radius()).into(),
+            midradius: ReportEntry::Skipped,
+            inradius: ReportEntry::Skipped,
+            volume: ReportEntry::Skipped,
+            surface_area: ReportEntry::Skipped,
+            orientable: ReportEntry::Skipped,
+            element_types: ReportEntry::Skipped,
+        };
+
+        report.compute(self, options);
+        report
+    }
+
+    /// Returns the common distance from a point to the edge midpoints, or
+    /// `None` if these differ or there are no edges.
+    fn midradius_from(&self, center: &Point<f64>) -> Option<f64> {
+        if self.rank() < 2 {
+            return None;
+        }
+
+        // Degenerate edges are skipped.
+        common_value(
+            (0..self.edge_count())
+                .filter_map(|idx| self.edge_endpoints(idx))
+                .map(|[v0, v1]| ((v0 + v1) / 2.0 - center).norm()),
+        )
+    }
+
+    /// Returns the common distance from a point to the facets, or `None` if
+    /// these differ or the polytope has no proper facets.
+    fn inradius_from(&self, center: &Point<f64>) -> Option<f64> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        common_value((0..self.facet_count()).map(|idx| {
+            let vertices = self.element_vertices_ref(rank - 1, idx).unwrap();
+            Subspace::from_points(vertices.into_iter()).distance(center)
+        }))
+    }
+
+    /// Describes the element types of every proper rank, in the same format
+    /// as [`Concrete::print_element_types`]. Returns `None` for polytopes
+    /// without proper elements.
+    fn element_type_descriptions(&self) -> Option<Vec<Vec<String>>> {
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        Some(
+            self.element_types()
+                .into_iter()
+                .enumerate()
+                .skip(1)
+                .take(rank - 1)
+                .map(|(r, types)| {
+                    types
+                        .into_iter()
+                        .map(|t| {
+                            let el = &self[(r, t.example)];
+                            format!(
+                                "{} × {}-{}, {}-{}",
+                                t.count,
+                                el.subs.len(),
+                                EL_SUFFIXES[r],
+                                el.sups.len(),
+                                EL_SUFFIXES[rank - r],
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the report of a cube.
+    #[test]
+    fn cube() {
+        let report = Concrete::cube().report(ReportOptions::all());
+
+        assert_eq!(report.rank, 3);
+        assert_eq!(report.dim, Some(3));
+        assert_eq!(report.el_counts, vec![1, 8, 12, 6, 1]);
+        assert_eq!(report.euler_characteristic, 2);
+        assert!(report.equilateral.passed);
+        assert_eq!(report.edge_lengths.buckets.len(), 1);
+        assert_eq!(report.orientable, ReportEntry::Value(true));
+        assert_eq!(report.element_types.value().unwrap().len(), 3);
+
+        let circumradius = *report.circumradius.value().unwrap();
+        let midradius = *report.midradius.value().unwrap();
+        let inradius = *report.inradius.value().unwrap();
+        let volume = *report.volume.value().unwrap();
+        let surface_area = *report.surface_area.value().unwrap();
+        assert!(abs_diff_eq!(circumradius, 3f64.sqrt() / 2.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(midradius, 2f64.sqrt() / 2.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(inradius, 0.5, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(volume, 1.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(surface_area, 6.0, epsilon = f64::EPS));
+    }
+
+    /// Checks that expensive entries are only computed when requested.
+    #[test]
+    fn lazy() {
+        let cube = Concrete::cube();
+        let mut report = cube.report(ReportOptions::default());
+        assert!(report.volume.is_skipped());
+        assert!(report.to_string().contains("Volume: not computed"));
+
+        report.compute(
+            &cube,
+            ReportOptions {
+                volume: true,
+                ..Default::default()
+            },
+        );
+        assert!(report.volume.value().is_some());
+        assert!(report.orientable.is_skipped());
+    }
+}
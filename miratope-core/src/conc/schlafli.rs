@@ -0,0 +1,421 @@
+//! Parses [Schläfli symbols](https://polytope.miraheze.org/wiki/Schläfli_symbol)
+//! and builds the regular polytopes they describe.
+//!
+//! The simplices, hypercubes, orthoplices, polygons and the icosahedral
+//! Platonic solids are built through their own constructors. Every other
+//! symbol is built by a Wythoff construction from the reflections of its
+//! Coxeter diagram, which covers the 120-cell and 600-cell families and the
+//! star polyhedra and polychora.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Display,
+};
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::{AbstractBuilder, SubelementList},
+    cox::Cox,
+    float::Float,
+    geometry::{Matrix, Point},
+    Polytope,
+};
+
+use vec_like::*;
+
+/// The largest vertex count of a polytope built by a Wythoff construction,
+/// that of the 120-cell. Any symbol with more vertices than this has to be
+/// an infinite one.
+const MAX_VERTICES: usize = 600;
+
+/// An error while reading or building a Schläfli symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchlafliError {
+    /// The symbol isn't a list of entries between braces.
+    Syntax(String),
+
+    /// An entry isn't an integer or a fraction in lowest terms greater
+    /// than 2.
+    Entry(String),
+
+    /// The symbol describes a Euclidean tiling.
+    Tiling(String),
+
+    /// The symbol describes a hyperbolic tiling.
+    Hyperbolic(String),
+
+    /// The symbol doesn't describe any finite polytope.
+    NotFinite(String),
+}
+
+impl Display for SchlafliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(symbol) => write!(f, "\"{}\" is not of the form {{p,q,...}}", symbol),
+            Self::Entry(entry) => write!(
+                f,
+                "\"{}\" is not an integer or fraction in lowest terms greater than 2",
+                entry
+            ),
+            Self::Tiling(symbol) => write!(f, "{} is a tiling, not a finite polytope", symbol),
+            Self::Hyperbolic(symbol) => write!(
+                f,
+                "{} is a hyperbolic tiling, not a finite polytope",
+                symbol
+            ),
+            Self::NotFinite(symbol) => {
+                write!(f, "{} does not describe a finite polytope", symbol)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchlafliError {}
+
+/// Parses a single entry `n` or `n/d` of a Schläfli symbol.
+fn parse_entry(entry: &str) -> Result<(usize, usize), SchlafliError> {
+    use gcd::Gcd;
+
+    let err = || SchlafliError::Entry(entry.to_string());
+    let (n, d) = match entry.split_once('/') {
+        Some((n, d)) => (n.trim(), d.trim()),
+        None => (entry, "1"),
+    };
+
+    let n: usize = n.parse().map_err(|_| err())?;
+    let d: usize = d.parse().map_err(|_| err())?;
+
+    if d == 0 || n <= 2 * d || n.gcd(d) != 1 {
+        Err(err())
+    } else {
+        Ok((n, d))
+    }
+}
+
+/// Parses a Schläfli symbol into its entries.
+fn parse(symbol: &str) -> Result<Vec<(usize, usize)>, SchlafliError> {
+    let inner = symbol
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| SchlafliError::Syntax(symbol.to_string()))?
+        .trim();
+
+    if inner.is_empty() {
+        Ok(Vec::new())
+    } else {
+        inner.split(',').map(|e| parse_entry(e.trim())).collect()
+    }
+}
+
+/// Writes out the entries of a Schläfli symbol in its usual form.
+fn write_symbol(entries: &[(usize, usize)]) -> String {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|&(n, d)| {
+            if d == 1 {
+                n.to_string()
+            } else {
+                format!("{}/{}", n, d)
+            }
+        })
+        .collect();
+
+    format!("{{{}}}", entries.join(","))
+}
+
+/// Scales a polytope so that its first edge has unit length.
+fn with_unit_edge(mut poly: Concrete) -> Concrete {
+    if let Some(len) = poly.edge_len(0) {
+        poly.scale_mut(1.0 / len);
+    }
+
+    poly
+}
+
+/// Returns the closure of a set of vertices under some permutations.
+fn closure(set: BTreeSet<usize>, perms: &[Vec<usize>]) -> BTreeSet<usize> {
+    let mut set = set;
+    let mut queue: VecDeque<_> = set.iter().copied().collect();
+
+    while let Some(v) = queue.pop_front() {
+        for perm in perms {
+            if set.insert(perm[v]) {
+                queue.push_back(perm[v]);
+            }
+        }
+    }
+
+    set
+}
+
+/// Builds the regular polytope with a given Schläfli symbol by a Wythoff
+/// construction, with unit edge length. Every element is identified by its
+/// set of vertices.
+///
+/// Returns `None` if the reflections don't generate a finite group with few
+/// enough vertices, or if the result isn't a valid polytope.
+fn wythoff(normals: &Matrix<f64>) -> Option<Concrete> {
+    let dim = normals.nrows();
+
+    // The generating vertex lies on every mirror but the first.
+    let mut e0 = Point::zeros(dim);
+    e0[0] = 1.0;
+    let v0 = normals.transpose().try_inverse()? * e0;
+    let v0 = &v0 / v0.norm();
+
+    // Finds the orbit of the generating vertex, and the permutation of the
+    // vertices by each reflection.
+    let mut vertices = vec![v0];
+    let mut perms = vec![Vec::new(); dim];
+    let mut idx = 0;
+    while idx < vertices.len() {
+        for (i, n) in normals.column_iter().enumerate() {
+            let v = &vertices[idx];
+            let image = v - n * (2.0 * v.dot(&n));
+            let image_idx = match vertices
+                .iter()
+                .position(|w| (w - &image).norm() < f64::EPS.fsqrt())
+            {
+                Some(image_idx) => image_idx,
+                None => {
+                    if vertices.len() == MAX_VERTICES {
+                        return None;
+                    }
+
+                    vertices.push(image);
+                    vertices.len() - 1
+                }
+            };
+
+            perms[i].push(image_idx);
+        }
+
+        idx += 1;
+    }
+
+    let edge_len = (&vertices[0] - &vertices[perms[0][0]]).norm();
+    let mut builder = AbstractBuilder::with_rank_capacity(dim + 1);
+    builder.push_min();
+    builder.push_vertices(vertices.len());
+
+    // The vertex sets of the elements of the previous rank, with their indices.
+    let mut prev: BTreeMap<BTreeSet<usize>, usize> = (0..vertices.len())
+        .map(|v| (std::iter::once(v).collect(), v))
+        .collect();
+    let mut base_prev: BTreeSet<usize> = std::iter::once(0).collect();
+
+    for k in 1..dim {
+        // The base element of this rank and its subelements, which are the
+        // orbits of the previous ones under the first k reflections.
+        let base_perms = &perms[..k];
+        let base = closure(base_prev.clone(), base_perms);
+        let mut base_subs = vec![base_prev.clone()];
+        let mut idx = 0;
+        while idx < base_subs.len() {
+            for perm in base_perms {
+                let image: BTreeSet<_> = base_subs[idx].iter().map(|&v| perm[v]).collect();
+                if !base_subs.contains(&image) {
+                    base_subs.push(image);
+                }
+            }
+
+            idx += 1;
+        }
+
+        // Moves the base element around by every reflection.
+        let mut elements = vec![(base.clone(), base_subs)];
+        let mut indices: BTreeMap<BTreeSet<usize>, usize> =
+            std::iter::once((base.clone(), 0)).collect();
+        let mut idx = 0;
+        while idx < elements.len() {
+            for perm in &perms {
+                let image: BTreeSet<_> = elements[idx].0.iter().map(|&v| perm[v]).collect();
+                if !indices.contains_key(&image) {
+                    let subs = elements[idx]
+                        .1
+                        .iter()
+                        .map(|sub| sub.iter().map(|&v| perm[v]).collect())
+                        .collect();
+                    indices.insert(image.clone(), elements.len());
+                    elements.push((image, subs));
+                }
+            }
+
+            idx += 1;
+        }
+
+        let mut list = SubelementList::new();
+        for (_, subs) in &elements {
+            list.push(
+                subs.iter()
+                    .map(|sub| prev.get(sub).copied())
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            );
+        }
+        builder.push(list);
+
+        prev = indices;
+        base_prev = base;
+    }
+
+    builder.push_max();
+    builder.ranks().is_valid().ok()?;
+
+    // Safety: we just checked that the polytope is valid.
+    let abs = unsafe { builder.build() };
+    Some(Concrete::new(vertices, abs).scale(1.0 / edge_len))
+}
+
+impl Concrete {
+    /// Builds the regular polytope with a given Schläfli symbol, such as
+    /// `{4,3,3}` or `{5/2,5}`, with unit edge length.
+    ///
+    /// ```
+    /// use miratope_core::{abs::Ranked, conc::Concrete};
+    ///
+    /// let tesseract = Concrete::from_schlafli("{4,3,3}").unwrap();
+    /// assert_eq!(tesseract.abs.el_counts().to_string(), "(1, 16, 32, 24, 8, 1)");
+    ///
+    /// assert_eq!(
+    ///     Concrete::from_schlafli("{6,3}").unwrap_err().to_string(),
+    ///     "{6,3} is a tiling, not a finite polytope"
+    /// );
+    /// ```
+    pub fn from_schlafli(symbol: &str) -> Result<Self, SchlafliError> {
+        let entries = parse(symbol)?;
+        let symbol = write_symbol(&entries);
+        let rank = entries.len() + 2;
+        let all_3 = |entries: &[(usize, usize)]| entries.iter().all(|&e| e == (3, 1));
+
+        match entries.as_slice() {
+            [] => Ok(Self::dyad()),
+            &[(n, d)] => Ok(Self::star_polygon_with_edge(n, d, 1.0)),
+            [(3, 1), (5, 1)] => Ok(Self::platonic(4)),
+            [(5, 1), (3, 1)] => Ok(Self::platonic(3)),
+            _ if all_3(&entries) => Ok(with_unit_edge(Self::simplex(rank))),
+            [(4, 1), rest @ ..] if all_3(rest) => Ok(Self::hypercube(rank)),
+            [rest @ .., (4, 1)] if all_3(rest) => Ok(with_unit_edge(Self::orthoplex(rank))),
+            _ => Self::wythoff_from_schlafli(&entries, symbol),
+        }
+    }
+
+    /// Builds the regular polytope with a given Schläfli symbol by a Wythoff
+    /// construction, or classifies the tiling it describes.
+    fn wythoff_from_schlafli(
+        entries: &[(usize, usize)],
+        symbol: String,
+    ) -> Result<Self, SchlafliError> {
+        let edge = |&(n, d): &(usize, usize)| n as f64 / d as f64;
+        let cox = Cox::from_lin_diagram_iter(entries.iter().map(edge), entries.len() + 1);
+
+        match cox.normals() {
+            Some(normals) => wythoff(&normals).ok_or(SchlafliError::NotFinite(symbol)),
+
+            // The symbol describes a Euclidean tiling when the Gram matrix of
+            // the mirrors is singular, and the diagrams with either end node
+            // removed are spherical.
+            None => {
+                let gram =
+                    Matrix::from_fn(cox.dim(), cox.dim(), |i, j| -(f64::PI / cox[(i, j)]).fcos());
+
+                let euclidean = gram.determinant().abs() < f64::EPS
+                    && [&entries[1..], &entries[..entries.len() - 1]]
+                        .iter()
+                        .all(|entries| {
+                            Cox::from_lin_diagram_iter(entries.iter().map(edge), entries.len() + 1)
+                                .normals()
+                                .is_some()
+                        });
+
+                if euclidean {
+                    Err(SchlafliError::Tiling(symbol))
+                } else {
+                    Err(SchlafliError::Hyperbolic(symbol))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abs::Ranked, test};
+
+    /// The icosahedron has the golden ratio in its circumradius.
+    #[test]
+    fn icosahedron() {
+        let icosahedron = Concrete::from_schlafli("{3,5}").unwrap();
+        assert_eq!(icosahedron.vertex_count(), 12);
+
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+        let radius = icosahedron.circumsphere().unwrap().radius();
+        approx::assert_abs_diff_eq!(radius, (phi * 5f64.sqrt()).sqrt() / 2.0, epsilon = f64::EPS);
+    }
+
+    /// The star polyhedra and polychora are built by the Wythoff construction.
+    #[test]
+    fn stars() {
+        test(
+            &Concrete::from_schlafli("{5/2,5}").unwrap(),
+            [1, 12, 30, 12, 1],
+        );
+        test(
+            &Concrete::from_schlafli("{5, 5/2}").unwrap(),
+            [1, 12, 30, 12, 1],
+        );
+        test(
+            &Concrete::from_schlafli("{5/2,3}").unwrap(),
+            [1, 20, 30, 12, 1],
+        );
+        test(
+            &Concrete::from_schlafli("{5/2,5,3}").unwrap(),
+            [1, 120, 1200, 720, 120, 1],
+        );
+    }
+
+    /// The families with their own constructors, and the 600-cell.
+    #[test]
+    fn families() {
+        for (symbol, counts) in [
+            ("{}", vec![1, 2, 1]),
+            ("{7/3}", vec![1, 7, 7, 1]),
+            ("{3,3,3}", vec![1, 5, 10, 10, 5, 1]),
+            ("{4,3,3}", vec![1, 16, 32, 24, 8, 1]),
+            ("{3,3,4}", vec![1, 8, 24, 32, 16, 1]),
+            ("{3,3,5}", vec![1, 120, 720, 1200, 600, 1]),
+        ] {
+            let poly = Concrete::from_schlafli(symbol).unwrap();
+            assert_eq!(
+                poly.el_count_iter().collect::<Vec<_>>(),
+                counts,
+                "{}",
+                symbol
+            );
+            assert!(poly.is_equilateral_with(1.0), "{}", symbol);
+        }
+    }
+
+    /// Tilings and malformed symbols are errors.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Concrete::from_schlafli("{4,4}").unwrap_err().to_string(),
+            "{4,4} is a tiling, not a finite polytope"
+        );
+        assert_eq!(
+            Concrete::from_schlafli("{7,3}").err(),
+            Some(SchlafliError::Hyperbolic("{7,3}".to_string()))
+        );
+        assert_eq!(
+            Concrete::from_schlafli("{6/2}").err(),
+            Some(SchlafliError::Entry("6/2".to_string()))
+        );
+        assert_eq!(
+            Concrete::from_schlafli("4,3").err(),
+            Some(SchlafliError::Syntax("4,3".to_string()))
+        );
+    }
+}
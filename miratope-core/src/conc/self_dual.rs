@@ -0,0 +1,67 @@
+//! Detects self-dual polytopes, and builds their compounds with their duals.
+
+use super::{Concrete, ConcretePolytope};
+use crate::{abs::Ranked, geometry::Hypersphere, Polytope};
+
+impl Concrete {
+    /// Returns whether the polytope is combinatorially equal to its dual. See
+    /// [`Abstract::dual_isomorphism`](crate::abs::Abstract::dual_isomorphism).
+    pub fn is_self_dual(&self) -> bool {
+        self.abs.is_self_dual()
+    }
+
+    /// Builds the compound of a self-dual polytope with its dual, reciprocated
+    /// about the gravicenter. The dual is scaled so that the image of the first
+    /// vertex under the isomorphism into the dual is as far from the center as
+    /// the first vertex itself.
+    ///
+    /// Returns `None` if the polytope isn't self-dual, or if some facet passes
+    /// through its gravicenter.
+    pub fn self_dual_compound(&self) -> Option<Self> {
+        let map = self.abs.dual_isomorphism()?;
+        let center = self.gravicenter()?;
+
+        let mut dual = self.try_dual_with(&Hypersphere::with_radius(center.clone(), 1.0)).ok()?;
+        if self.rank() >= 2 {
+            // The vertices of the dual are the facets of the polytope.
+            let image = &dual.vertices[map[1][0]];
+            let k = (&self.vertices[0] - &center).norm() / (image - &center).norm();
+
+            for v in dual.vertices_mut() {
+                *v = &center + (&*v - &center) * k;
+            }
+        }
+
+        let mut compound = self.clone();
+        compound.comp_append(dual);
+        Some(compound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{float::Float, test};
+
+    /// Builds the compound of two tetrahedra.
+    #[test]
+    fn stella_octangula() {
+        let tet = Concrete::simplex(4);
+        assert!(tet.is_self_dual());
+
+        let compound = tet.self_dual_compound().unwrap();
+        test(&compound, [1, 8, 12, 8, 1]);
+
+        let radius = tet.vertices[0].norm();
+        for v in &compound.vertices {
+            assert!((v.norm() - radius).abs() < f64::EPS);
+        }
+    }
+
+    /// The cube isn't self-dual.
+    #[test]
+    fn cube() {
+        assert!(!Concrete::cube().is_self_dual());
+        assert!(Concrete::cube().self_dual_compound().is_none());
+    }
+}
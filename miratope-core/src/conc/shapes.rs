@@ -0,0 +1,836 @@
+//! Builds some families of polyhedra from exact coordinates, namely the
+//! Platonic, Archimedean and Catalan solids, the cupolae and rotunda with
+//! regular faces, and the members of the icosahedral families in 3D and 4D.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
+    float::Float,
+    geometry::{Hypersphere, Point},
+};
+
+use itertools::Itertools;
+use vec_like::*;
+
+/// The golden ratio, rounded to the nearest `f64`.
+pub(crate) const PHI: f64 = 1.618_033_988_749_895;
+
+/// The names of the Platonic solids, in the order used by
+/// [`Concrete::platonic`].
+pub const PLATONIC_NAMES: [&str; 5] = [
+    "Tetrahedron",
+    "Cube",
+    "Octahedron",
+    "Dodecahedron",
+    "Icosahedron",
+];
+
+/// The names of the Archimedean solids, in the order used by
+/// [`Concrete::archimedean`].
+pub const ARCHIMEDEAN_NAMES: [&str; 13] = [
+    "Truncated tetrahedron",
+    "Cuboctahedron",
+    "Truncated cube",
+    "Truncated octahedron",
+    "Rhombicuboctahedron",
+    "Truncated cuboctahedron",
+    "Snub cube",
+    "Icosidodecahedron",
+    "Truncated dodecahedron",
+    "Truncated icosahedron",
+    "Rhombicosidodecahedron",
+    "Truncated icosidodecahedron",
+    "Snub dodecahedron",
+];
+
+/// The names of the Catalan solids, in the order used by
+/// [`Concrete::catalan`]. Each of them is the dual of the Archimedean solid
+/// with the same index.
+pub const CATALAN_NAMES: [&str; 13] = [
+    "Triakis tetrahedron",
+    "Rhombic dodecahedron",
+    "Triakis octahedron",
+    "Tetrakis hexahedron",
+    "Deltoidal icositetrahedron",
+    "Disdyakis dodecahedron",
+    "Pentagonal icositetrahedron",
+    "Rhombic triacontahedron",
+    "Triakis icosahedron",
+    "Pentakis dodecahedron",
+    "Deltoidal hexecontahedron",
+    "Disdyakis triacontahedron",
+    "Pentagonal hexecontahedron",
+];
+
+/// The names of the polytopes with icosahedral symmetry built from their
+/// coordinates, in the order used by [`Concrete::icosahedral`].
+pub const ICOSAHEDRAL_NAMES: [&str; 5] = [
+    "Icosahedron",
+    "Dodecahedron",
+    "Icosidodecahedron",
+    "600-cell",
+    "120-cell",
+];
+
+/// The coordinates of a point in 3D.
+type Coords = [f64; 3];
+
+/// Subtracts two points.
+fn sub(p: &Coords, q: &Coords) -> Coords {
+    [p[0] - q[0], p[1] - q[1], p[2] - q[2]]
+}
+
+/// The dot product of two vectors.
+fn dot(p: &Coords, q: &Coords) -> f64 {
+    p[0] * q[0] + p[1] * q[1] + p[2] * q[2]
+}
+
+/// The cross product of two vectors.
+fn cross(p: &Coords, q: &Coords) -> Coords {
+    [
+        p[1] * q[2] - p[2] * q[1],
+        p[2] * q[0] - p[0] * q[2],
+        p[0] * q[1] - p[1] * q[0],
+    ]
+}
+
+/// The distance between two points.
+fn dist(p: &Coords, q: &Coords) -> f64 {
+    let d = sub(p, q);
+    dot(&d, &d).fsqrt()
+}
+
+/// Returns the points obtained by permuting the coordinates of some seeds and
+/// changing their signs, without duplicates. The filter is given whether the
+/// permutation is odd and whether an odd number of signs were changed, and
+/// decides which of these points are kept.
+fn orbit<F: Fn(bool, bool) -> bool>(seeds: &[Coords], filter: F) -> Vec<Coords> {
+    const PERMUTATIONS: [([usize; 3], bool); 6] = [
+        ([0, 1, 2], false),
+        ([1, 2, 0], false),
+        ([2, 0, 1], false),
+        ([0, 2, 1], true),
+        ([2, 1, 0], true),
+        ([1, 0, 2], true),
+    ];
+
+    let mut points: Vec<Coords> = Vec::new();
+
+    for seed in seeds {
+        for &(perm, odd_perm) in &PERMUTATIONS {
+            for signs in 0..8u32 {
+                if !filter(odd_perm, signs.count_ones() % 2 == 1) {
+                    continue;
+                }
+
+                let mut p = [0.0; 3];
+                for (i, x) in p.iter_mut().enumerate() {
+                    *x = if signs & (1 << i) == 0 {
+                        seed[perm[i]]
+                    } else {
+                        -seed[perm[i]]
+                    };
+                }
+
+                // Zero coordinates give repeated points.
+                if points.iter().all(|q| dist(&p, q) > f64::EPS) {
+                    points.push(p);
+                }
+            }
+        }
+    }
+
+    points
+}
+
+/// Returns the points whose coordinates are an even permutation of the given
+/// ones, without duplicates. When some coordinate is repeated, these are all of
+/// the permutations of the coordinates.
+pub fn even_permutations(coords: &[f64]) -> Vec<Point<f64>> {
+    let n = coords.len();
+    let mut points: Vec<Point<f64>> = Vec::new();
+
+    for perm in (0..n).permutations(n) {
+        let inversions = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| perm[i] > perm[j])
+            .count();
+        if inversions % 2 == 1 {
+            continue;
+        }
+
+        let p: Point<f64> = perm.iter().map(|&i| coords[i]).collect::<Vec<_>>().into();
+        if !points.contains(&p) {
+            points.push(p);
+        }
+    }
+
+    points
+}
+
+/// Returns the points obtained by changing the signs of the nonzero
+/// coordinates of some points in every possible way.
+fn sign_changes(points: Vec<Point<f64>>) -> Vec<Point<f64>> {
+    let mut changed = Vec::new();
+
+    for p in points {
+        let nonzero: Vec<_> = (0..p.len()).filter(|&i| p[i] != 0.0).collect();
+
+        for signs in 0..1u32 << nonzero.len() {
+            let mut q = p.clone();
+            for (bit, &i) in nonzero.iter().enumerate() {
+                if signs & (1 << bit) != 0 {
+                    q[i] = -q[i];
+                }
+            }
+            changed.push(q);
+        }
+    }
+
+    changed
+}
+
+/// Returns the points whose coordinates are an even permutation of those of
+/// some seed, with their nonzero coordinates changed in sign in every possible
+/// way. The seeds are scaled by a factor beforehand.
+fn even_orbit(seeds: &[&[f64]], scale: f64) -> Vec<Point<f64>> {
+    seeds
+        .iter()
+        .flat_map(|seed| {
+            let scaled: Vec<_> = seed.iter().map(|x| x * scale).collect();
+            sign_changes(even_permutations(&scaled))
+        })
+        .collect()
+}
+
+/// Returns the vertices of the Archimedean solid with a given index in
+/// [`ARCHIMEDEAN_NAMES`], at an arbitrary scale.
+///
+/// # Panics
+/// Will panic if the index is out of range.
+fn archimedean_vertices(idx: usize) -> Vec<Coords> {
+    let all = |_, _| true;
+    let even = |odd_perm: bool, _| !odd_perm;
+    let sqrt_2 = f64::SQRT_2;
+
+    match idx {
+        0 => orbit(&[[3.0, 1.0, 1.0]], |_, odd_signs| !odd_signs),
+        1 => orbit(&[[1.0, 1.0, 0.0]], all),
+        2 => orbit(&[[sqrt_2 - 1.0, 1.0, 1.0]], all),
+        3 => orbit(&[[0.0, 1.0, 2.0]], all),
+        4 => orbit(&[[1.0, 1.0, 1.0 + sqrt_2]], all),
+        5 => orbit(&[[1.0, 1.0 + sqrt_2, 1.0 + 2.0 * sqrt_2]], all),
+        6 => {
+            // The tribonacci constant.
+            let s = 3.0 * 33f64.fsqrt();
+            let t = (1.0 + (19.0 + s).cbrt() + (19.0 - s).cbrt()) / 3.0;
+
+            orbit(&[[1.0, 1.0 / t, t]], |odd_perm, odd_signs| {
+                odd_perm != odd_signs
+            })
+        }
+        7 => {
+            let mut vertices = orbit(&[[0.0, 0.0, PHI]], all);
+            vertices.append(&mut orbit(
+                &[[0.5, PHI / 2.0, PHI * PHI / 2.0]],
+                even,
+            ));
+            vertices
+        }
+        8 => orbit(
+            &[
+                [0.0, 1.0 / PHI, 2.0 + PHI],
+                [1.0 / PHI, PHI, 2.0 * PHI],
+                [PHI, 2.0, PHI + 1.0],
+            ],
+            even,
+        ),
+        9 => orbit(
+            &[
+                [0.0, 1.0, 3.0 * PHI],
+                [1.0, 2.0 + PHI, 2.0 * PHI],
+                [PHI, 2.0, 2.0 * PHI + 1.0],
+            ],
+            even,
+        ),
+        10 => orbit(
+            &[
+                [1.0, 1.0, 2.0 * PHI + 1.0],
+                [PHI + 1.0, PHI, 2.0 * PHI],
+                [2.0 + PHI, 0.0, PHI + 1.0],
+            ],
+            even,
+        ),
+        11 => orbit(
+            &[
+                [1.0 / PHI, 1.0 / PHI, 3.0 + PHI],
+                [2.0 / PHI, PHI, 1.0 + 2.0 * PHI],
+                [1.0 / PHI, PHI + 1.0, 3.0 * PHI - 1.0],
+                [2.0 * PHI - 1.0, 2.0, 2.0 + PHI],
+                [PHI, 3.0, 2.0 * PHI],
+            ],
+            even,
+        ),
+        12 => {
+            // The real root of x³ - 2x = φ.
+            let s = (PHI * PHI / 4.0 - 8.0 / 27.0).fsqrt();
+            let x = (PHI / 2.0 + s).cbrt() + (PHI / 2.0 - s).cbrt();
+
+            let a = x - 1.0 / x;
+            let b = x * PHI + PHI * PHI + PHI / x;
+
+            orbit(
+                &[
+                    [2.0, 2.0 * a, 2.0 * b],
+                    [
+                        a + b / PHI - PHI,
+                        a / PHI + b * PHI + 1.0,
+                        a * PHI - b + 1.0 / PHI,
+                    ],
+                    [
+                        -a * PHI + b + 1.0 / PHI,
+                        a + b / PHI + PHI,
+                        a / PHI + b * PHI - 1.0,
+                    ],
+                    [
+                        -a + b / PHI + PHI,
+                        a / PHI - b * PHI + 1.0,
+                        a * PHI + b + 1.0 / PHI,
+                    ],
+                    [
+                        a - b / PHI + PHI,
+                        a / PHI - b * PHI - 1.0,
+                        a * PHI + b - 1.0 / PHI,
+                    ],
+                ],
+                |odd_perm, odd_signs| !odd_perm && odd_signs,
+            )
+        }
+        _ => panic!("There are only 13 Archimedean solids."),
+    }
+}
+
+impl Concrete {
+    /// Builds a convex polyhedron from its vertices, assuming that its edges
+    /// are exactly the shortest segments between them, as is the case for all
+    /// of the polyhedra in this module. The result is scaled so that its edges
+    /// have unit length.
+    ///
+    /// # Panics
+    /// Will panic if the vertices don't form such a polyhedron.
+    fn from_convex_vertices(vertices: Vec<Coords>) -> Self {
+        let n = vertices.len();
+        let tolerance = 1e-9 * vertices.iter().map(|v| dot(v, v).fsqrt()).fold(1.0, f64::max);
+
+        let len = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| dist(&vertices[i], &vertices[j]))
+            .fold(f64::INFINITY, f64::min);
+
+        // Finds the edges and the neighbors of every vertex.
+        let mut neighbors = vec![Vec::new(); n];
+        let mut edge_indices = HashMap::new();
+        let mut edges = SubelementList::new();
+
+        for i in 0..n {
+            for j in i + 1..n {
+                if (dist(&vertices[i], &vertices[j]) - len).abs() < tolerance {
+                    neighbors[i].push(j);
+                    neighbors[j].push(i);
+                    edge_indices.insert((i, j), edges.len());
+                    edges.push(vec![i, j].into());
+                }
+            }
+        }
+
+        // Walks around every face, keeping the polyhedron on the same side of
+        // every directed edge. Each directed edge belongs to a single face.
+        let mut visited = HashSet::new();
+        let mut faces = SubelementList::new();
+
+        for u in 0..n {
+            for &v in &neighbors[u] {
+                if visited.contains(&(u, v)) {
+                    continue;
+                }
+
+                let mut face = Subelements::new();
+                let (mut a, mut b) = (u, v);
+
+                loop {
+                    visited.insert((a, b));
+                    face.push(edge_indices[&(a.min(b), a.max(b))]);
+
+                    let c = neighbors[b]
+                        .iter()
+                        .copied()
+                        .find(|&c| {
+                            let normal = cross(
+                                &sub(&vertices[b], &vertices[a]),
+                                &sub(&vertices[c], &vertices[b]),
+                            );
+                            let norm = dot(&normal, &normal).fsqrt();
+
+                            c != a
+                                && vertices.iter().all(|x| {
+                                    dot(&normal, &sub(x, &vertices[b])) <= tolerance * norm
+                                })
+                        })
+                        .expect("The vertices don't form a convex polyhedron.");
+
+                    a = b;
+                    b = c;
+                    if (a, b) == (u, v) {
+                        break;
+                    }
+                }
+
+                faces.push(face);
+            }
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(4);
+        builder.push_min();
+        builder.push_vertices(n);
+        builder.push(edges);
+        builder.push(faces);
+        builder.push_max();
+
+        // Safety: the edges and faces of a convex polyhedron form a valid
+        // polytope.
+        let abs = unsafe { builder.build() };
+        let vertices = vertices.iter().map(|v| v.to_vec().into()).collect();
+        Self::new(vertices, abs).scale(1.0 / len)
+    }
+
+    /// Builds the Platonic solid with a given index in [`PLATONIC_NAMES`],
+    /// with unit edge length.
+    ///
+    /// # Panics
+    /// Will panic if the index is out of range.
+    pub fn platonic(idx: usize) -> Self {
+        let vertices = match idx {
+            0 => orbit(&[[1.0, 1.0, 1.0]], |_, odd_signs| !odd_signs),
+            1 => orbit(&[[1.0, 1.0, 1.0]], |_, _| true),
+            2 => orbit(&[[1.0, 0.0, 0.0]], |_, _| true),
+            3 => orbit(&[[1.0, 1.0, 1.0], [0.0, 1.0 / PHI, PHI]], |odd_perm, _| {
+                !odd_perm
+            }),
+            4 => orbit(&[[0.0, 1.0, PHI]], |odd_perm, _| !odd_perm),
+            _ => panic!("There are only 5 Platonic solids."),
+        };
+
+        Self::from_convex_vertices(vertices)
+    }
+
+    /// Builds the Archimedean solid with a given index in
+    /// [`ARCHIMEDEAN_NAMES`], with unit edge length. The snub solids are the
+    /// only ones whose coordinates aren't given in closed form.
+    ///
+    /// # Panics
+    /// Will panic if the index is out of range.
+    pub fn archimedean(idx: usize) -> Self {
+        Self::from_convex_vertices(archimedean_vertices(idx))
+    }
+
+    /// Builds the Catalan solid with a given index in [`CATALAN_NAMES`]. This
+    /// is the dual of the corresponding Archimedean solid with unit edge
+    /// length with respect to its midsphere, so that both solids share it.
+    ///
+    /// # Panics
+    /// Will panic if the index is out of range.
+    pub fn catalan(idx: usize) -> Self {
+        let archimedean = Self::archimedean(idx);
+        let midradius = archimedean.midradius().unwrap();
+
+        // The origin is in the interior, so the dual always exists.
+        archimedean
+            .try_dual_with(&Hypersphere::with_radius(Point::zeros(3), midradius))
+            .unwrap()
+    }
+
+    /// Builds an n-gonal cupola with unit edge length. The digonal cupola is
+    /// the triangular prism.
+    ///
+    /// # Panics
+    /// Will panic if `n < 2` or `n > 5`, as no other cupolae have regular
+    /// faces.
+    pub fn cupola(n: usize) -> Self {
+        assert!(
+            (2..=5).contains(&n),
+            "Only the cupolae with 2 to 5 sides have regular faces."
+        );
+
+        let angle = f64::PI / n as f64;
+
+        // The distance between the bases is such that the squares have unit
+        // sides.
+        let inradius_diff = 0.5 / (angle / 2.0).tan() - 0.5 / angle.tan();
+        let height = (1.0 - inradius_diff * inradius_diff).fsqrt();
+
+        let top_radius = 0.5 / angle.fsin();
+        let bottom_radius = 0.5 / (angle / 2.0).fsin();
+
+        let mut vertices = Vec::with_capacity(3 * n);
+        for k in 0..n {
+            let (s, c) = ((2 * k + 1) as f64 * angle).fsin_cos();
+            vertices.push([top_radius * c, top_radius * s, height]);
+        }
+        for k in 0..2 * n {
+            let (s, c) = ((2 * k + 1) as f64 * angle / 2.0).fsin_cos();
+            vertices.push([bottom_radius * c, bottom_radius * s, 0.0]);
+        }
+
+        Self::from_convex_vertices(vertices)
+    }
+
+    /// Builds the convex hull of some points given by their exact coordinates,
+    /// which are kept as they are.
+    ///
+    /// # Panics
+    /// Will panic if the points don't span the space they live in.
+    fn from_exact_vertices(vertices: Vec<Point<f64>>) -> Self {
+        Self::convex_hull(vertices).expect("The vertices don't span their space.")
+    }
+
+    /// Builds a simplicial polytope from its vertices, whose proper elements
+    /// are exactly the simplices of unit edge length between them, as is the
+    /// case for the 600-cell. This is much faster than taking the convex hull
+    /// of many points in 4D.
+    fn from_simplicial_vertices(vertices: Vec<Point<f64>>) -> Self {
+        let n = vertices.len();
+        let dim = vertices[0].len();
+        let neighbors: Vec<Vec<usize>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| (((&vertices[i] - &vertices[j]).norm()) - 1.0).abs() < f64::EPS)
+                    .collect()
+            })
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(dim + 1);
+        builder.push_min();
+        builder.push_vertices(n);
+
+        // Every simplex is extended by the neighbors of all of its vertices
+        // with a greater index than any of them.
+        let mut simplices: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+        for _ in 1..dim {
+            let indices: HashMap<_, _> = simplices.iter().enumerate().map(|(i, s)| (s, i)).collect();
+            let mut next = Vec::new();
+            let mut subelements = SubelementList::new();
+
+            for simplex in &simplices {
+                let last = *simplex.last().unwrap();
+                for &v in &neighbors[last] {
+                    if v < last || !simplex.iter().all(|u| neighbors[*u].contains(&v)) {
+                        continue;
+                    }
+
+                    let mut new = simplex.clone();
+                    new.push(v);
+                    subelements.push(
+                        (0..new.len())
+                            .map(|k| {
+                                let mut facet = new.clone();
+                                facet.remove(k);
+                                indices[&facet]
+                            })
+                            .collect::<Vec<_>>()
+                            .into(),
+                    );
+                    next.push(new);
+                }
+            }
+
+            builder.push(subelements);
+            simplices = next;
+        }
+        builder.push_max();
+
+        // Safety: the caller asserts that these simplices are the elements of a
+        // valid polytope.
+        Self::new(vertices, unsafe { builder.build() })
+    }
+
+    /// Builds the icosahedron with unit edge length, whose vertices are the
+    /// even permutations of (0, ±1/2, ±φ/2). Its circumradius is
+    /// √(φ√5) / 2.
+    pub fn icosahedron() -> Self {
+        Self::from_exact_vertices(even_orbit(&[&[0.0, 1.0, PHI]], 0.5))
+    }
+
+    /// Builds the dodecahedron with unit edge length, whose vertices are
+    /// (±φ/2, ±φ/2, ±φ/2) and the even permutations of (0, ±1/2, ±φ²/2). Its
+    /// circumradius is √3 φ / 2.
+    pub fn dodecahedron() -> Self {
+        Self::from_exact_vertices(even_orbit(
+            &[&[PHI, PHI, PHI], &[0.0, 1.0, PHI + 1.0]],
+            0.5,
+        ))
+    }
+
+    /// Builds the icosidodecahedron with unit edge length, whose vertices are
+    /// the permutations of (0, 0, ±φ) and the even permutations of
+    /// (±1/2, ±φ/2, ±φ²/2). Its circumradius is φ.
+    pub fn icosidodecahedron() -> Self {
+        Self::from_exact_vertices(even_orbit(
+            &[&[0.0, 0.0, 2.0 * PHI], &[1.0, PHI, PHI + 1.0]],
+            0.5,
+        ))
+    }
+
+    /// Builds the 600-cell with unit edge length, whose vertices are the
+    /// permutations of (±φ, 0, 0, 0), the points (±φ/2, ±φ/2, ±φ/2, ±φ/2),
+    /// and the even permutations of (±φ²/2, ±φ/2, ±1/2, 0). Its circumradius
+    /// is φ.
+    pub fn hexacosichoron() -> Self {
+        Self::from_simplicial_vertices(even_orbit(
+            &[
+                &[2.0, 0.0, 0.0, 0.0],
+                &[1.0, 1.0, 1.0, 1.0],
+                &[PHI, 1.0, 1.0 / PHI, 0.0],
+            ],
+            PHI / 2.0,
+        ))
+    }
+
+    /// Builds the 120-cell with unit edge length. Its vertices are those of
+    /// the 120-cell with edge length 2/φ² and circumradius 2√2, whose
+    /// coordinates are the permutations of (0, 0, ±2, ±2), (±1, ±1, ±1, ±√5),
+    /// (±1/φ², ±φ, ±φ, ±φ) and (±1/φ, ±1/φ, ±1/φ, ±φ²), and the even
+    /// permutations of (0, ±1/φ², ±1, ±φ²), (0, ±1/φ, ±φ, ±√5) and
+    /// (±1/φ, ±1, ±φ, ±2), scaled by φ²/2. Its circumradius is √2 φ².
+    ///
+    /// Its elements are those of the dual of the 600-cell. Once the 600-cell is
+    /// mirrored by swapping its first two coordinates, each of its cells lies
+    /// in the direction of one of these vertices.
+    pub fn hecatonicosachoron() -> Self {
+        let phi_2 = PHI + 1.0;
+        let sqrt_5 = 2.0 * PHI - 1.0;
+
+        let vertices = even_orbit(
+            &[
+                &[0.0, 0.0, 2.0, 2.0],
+                &[1.0, 1.0, 1.0, sqrt_5],
+                &[1.0 / phi_2, PHI, PHI, PHI],
+                &[1.0 / PHI, 1.0 / PHI, 1.0 / PHI, phi_2],
+                &[0.0, 1.0 / phi_2, 1.0, phi_2],
+                &[0.0, 1.0 / PHI, PHI, sqrt_5],
+                &[1.0 / PHI, 1.0, PHI, 2.0],
+            ],
+            phi_2 / 2.0,
+        );
+
+        let hexacosichoron = Self::hexacosichoron();
+        let cells = hexacosichoron.abs.el_count(4);
+        let vertices = (0..cells)
+            .map(|idx| {
+                let mut center: Point<f64> = hexacosichoron
+                    .abs
+                    .element_vertices(4, idx)
+                    .unwrap()
+                    .into_iter()
+                    .map(|v| &hexacosichoron.vertices[v])
+                    .sum();
+                center.swap_rows(0, 1);
+
+                // All of the vertices are at the same distance from the origin.
+                vertices
+                    .iter()
+                    .max_by(|p, q| p.dot(&center).partial_cmp(&q.dot(&center)).unwrap())
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+
+        Self::new(vertices, hexacosichoron.abs.into_dual())
+    }
+
+    /// Builds the polytope with a given index in [`ICOSAHEDRAL_NAMES`], with
+    /// unit edge length, from its exact coordinates.
+    ///
+    /// # Panics
+    /// Will panic if the index is out of range.
+    pub fn icosahedral(idx: usize) -> Self {
+        match idx {
+            0 => Self::icosahedron(),
+            1 => Self::dodecahedron(),
+            2 => Self::icosidodecahedron(),
+            3 => Self::hexacosichoron(),
+            4 => Self::hecatonicosachoron(),
+            _ => panic!("There are only 5 icosahedral polytopes."),
+        }
+    }
+
+    /// Builds the pentagonal rotunda with unit edge length, as the half of an
+    /// icosidodecahedron cut along one of its decagons.
+    pub fn pentagonal_rotunda() -> Self {
+        // A fivefold axis of the icosidodecahedron.
+        let axis = [0.0, PHI, 1.0];
+
+        let vertices = archimedean_vertices(7)
+            .into_iter()
+            .filter(|v| dot(v, &axis) > -f64::EPS)
+            .collect();
+
+        Self::from_convex_vertices(vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    use approx::abs_diff_eq;
+
+    /// Checks that the Platonic solids are equilateral and have the correct
+    /// element counts.
+    #[test]
+    fn platonic() {
+        let counts = [
+            [1, 4, 6, 4, 1],
+            [1, 8, 12, 6, 1],
+            [1, 6, 12, 8, 1],
+            [1, 20, 30, 12, 1],
+            [1, 12, 30, 20, 1],
+        ];
+
+        for (idx, counts) in counts.iter().enumerate() {
+            let poly = Concrete::platonic(idx);
+            test(&poly, *counts);
+            assert!(poly.is_equilateral_with(1.0), "{}", PLATONIC_NAMES[idx]);
+        }
+    }
+
+    /// Checks that the Archimedean solids are equilateral and have the
+    /// correct element counts, and that the Catalan solids are their duals.
+    #[test]
+    fn archimedean_catalan() {
+        let counts = [
+            [12, 18, 8],
+            [12, 24, 14],
+            [24, 36, 14],
+            [24, 36, 14],
+            [24, 48, 26],
+            [48, 72, 26],
+            [24, 60, 38],
+            [30, 60, 32],
+            [60, 90, 32],
+            [60, 90, 32],
+            [60, 120, 62],
+            [120, 180, 62],
+            [60, 150, 92],
+        ];
+
+        for (idx, &[v, e, f]) in counts.iter().enumerate() {
+            let archimedean = Concrete::archimedean(idx);
+            test(&archimedean, [1, v, e, f, 1]);
+            assert!(
+                archimedean.is_equilateral_with(1.0),
+                "{}",
+                ARCHIMEDEAN_NAMES[idx]
+            );
+
+            // Both solids have their edges tangent to the same sphere.
+            let midradius = archimedean.midradius().unwrap();
+            let catalan = Concrete::catalan(idx);
+            test(&catalan, [1, f, e, v, 1]);
+            for edge in catalan[2].iter() {
+                let v0 = &catalan.vertices[edge.subs[0]];
+                let v1 = &catalan.vertices[edge.subs[1]];
+                let d = v1 - v0;
+                let tangent = v0 - &d * (d.dot(v0) / d.norm_squared());
+
+                assert!(
+                    abs_diff_eq!(tangent.norm(), midradius, epsilon = f64::EPS),
+                    "{}",
+                    CATALAN_NAMES[idx]
+                );
+            }
+        }
+    }
+
+    /// Checks the rhombic dodecahedron.
+    #[test]
+    fn rhombic_dodecahedron() {
+        let rhombic_dodecahedron = Concrete::catalan(1);
+        test(&rhombic_dodecahedron, [1, 14, 24, 12, 1]);
+        assert!(rhombic_dodecahedron.is_equilateral());
+    }
+
+    /// Checks the cupolae and the pentagonal rotunda.
+    #[test]
+    fn cupolae() {
+        // The digonal cupola is a triangular prism.
+        test(&Concrete::cupola(2), [1, 6, 9, 5, 1]);
+
+        for n in 3..=5 {
+            let cupola = Concrete::cupola(n);
+            test(&cupola, [1, 3 * n, 5 * n, 2 * n + 2, 1]);
+            assert!(cupola.is_equilateral_with(1.0));
+        }
+
+        let rotunda = Concrete::pentagonal_rotunda();
+        test(&rotunda, [1, 20, 35, 17, 1]);
+        assert!(rotunda.is_equilateral_with(1.0));
+    }
+
+    /// Even permutations keep repeated coordinates apart.
+    #[test]
+    fn even_permutations() {
+        assert_eq!(super::even_permutations(&[1.0, 2.0, 3.0]).len(), 3);
+        assert_eq!(super::even_permutations(&[1.0, 2.0, 3.0, 4.0]).len(), 12);
+        assert_eq!(super::even_permutations(&[0.0, 0.0, 1.0]).len(), 3);
+        assert_eq!(super::even_permutations(&[0.0, 0.0, 1.0, 1.0]).len(), 6);
+        assert!(super::even_permutations(&[1.0, 2.0, 3.0])
+            .contains(&vec![2.0, 3.0, 1.0].into()));
+        assert!(!super::even_permutations(&[1.0, 2.0, 3.0])
+            .contains(&vec![2.0, 1.0, 3.0].into()));
+    }
+
+    /// Checks the element counts of the icosahedral polytopes, that their
+    /// edges have unit length, and that their vertices lie at their known
+    /// circumradii, all to a tighter tolerance than usual.
+    #[test]
+    fn icosahedral() {
+        const TOLERANCE: f64 = 1e-12;
+
+        let sqrt_2 = f64::SQRT_2;
+        let sqrt_3 = 3f64.fsqrt();
+        let sqrt_5 = 5f64.fsqrt();
+        let expected: [(&[usize], f64); 5] = [
+            (&[1, 12, 30, 20, 1], (PHI * sqrt_5).fsqrt() / 2.0),
+            (&[1, 20, 30, 12, 1], sqrt_3 * PHI / 2.0),
+            (&[1, 30, 60, 32, 1], PHI),
+            (&[1, 120, 720, 1200, 600, 1], PHI),
+            (&[1, 600, 1200, 720, 120, 1], sqrt_2 * PHI * PHI),
+        ];
+
+        for (idx, &(counts, circumradius)) in expected.iter().enumerate() {
+            let poly = Concrete::icosahedral(idx);
+            let name = ICOSAHEDRAL_NAMES[idx];
+            assert_eq!(poly.abs.el_count_iter().collect::<Vec<_>>(), counts, "{}", name);
+            assert_eq!(poly.abs.is_valid(true), Ok(()), "{}", name);
+
+            for edge in 0..poly.edge_count() {
+                let len = poly.edge_len(edge).unwrap();
+                assert!((len - 1.0).abs() < TOLERANCE, "{}: {}", name, len);
+            }
+
+            for v in &poly.vertices {
+                assert!((v.norm() - circumradius).abs() < TOLERANCE, "{}", name);
+            }
+        }
+
+        // These match the solids built from their shortest edges.
+        assert!(Concrete::icosahedron().abs.is_isomorphic(&Concrete::platonic(4).abs));
+        assert!(Concrete::dodecahedron().abs.is_isomorphic(&Concrete::platonic(3).abs));
+        assert!(Concrete::icosidodecahedron()
+            .abs
+            .is_isomorphic(&Concrete::archimedean(7).abs));
+    }
+}
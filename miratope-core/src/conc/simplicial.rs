@@ -0,0 +1,332 @@
+//! Decompositions of polytopes into simplices, as plain data. Unlike the
+//! triangulations used for rendering, these fill the solid, and so can be used
+//! to integrate over it or to export it into meshing software.
+
+use std::{f64::consts::TAU, fmt::Display};
+
+use super::{cycle::CycleList, polygon_area_centroid, Concrete, ConcretePolytope};
+use crate::{
+    abs::Ranked,
+    float::Float,
+    geometry::{Matrix, Point, Subspace},
+    Polytope,
+};
+
+use vec_like::VecLike;
+
+/// A list of simplices, given by the indices of their vertices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimplicialComplex {
+    /// The coordinates of the vertices. These may include points that aren't
+    /// vertices of the original polytope, like the centroids of its elements.
+    pub vertices: Vec<Point<f64>>,
+
+    /// The indices of the vertices of every simplex.
+    pub simplices: Vec<Vec<usize>>,
+}
+
+impl SimplicialComplex {
+    /// Returns the number of simplices.
+    pub fn len(&self) -> usize {
+        self.simplices.len()
+    }
+
+    /// Returns whether there are no simplices.
+    pub fn is_empty(&self) -> bool {
+        self.simplices.is_empty()
+    }
+
+    /// Returns the vertices of the simplex with a given index.
+    pub fn simplex_vertices(&self, idx: usize) -> Vec<&Point<f64>> {
+        self.simplices[idx]
+            .iter()
+            .map(|&v| &self.vertices[v])
+            .collect()
+    }
+
+    /// Returns the unsigned content of the simplex with a given index, which
+    /// may live in a higher dimension than its own.
+    pub fn simplex_volume(&self, idx: usize) -> f64 {
+        let vertices = self.simplex_vertices(idx);
+        let (first, rest) = match vertices.split_first() {
+            Some(split) => split,
+            None => return 0.0,
+        };
+
+        let edges = Matrix::from_columns(&rest.iter().map(|&v| v - *first).collect::<Vec<_>>());
+        let factorial = (1..=rest.len()).product::<usize>() as f64;
+        (edges.transpose() * edges).determinant().max(0.0).sqrt() / factorial
+    }
+
+    /// Returns the total content of the simplices. This is the content of the
+    /// region they cover as long as they don't overlap.
+    pub fn volume(&self) -> f64 {
+        (0..self.len()).map(|idx| self.simplex_volume(idx)).sum()
+    }
+}
+
+/// An error while decomposing a polytope into tetrahedra.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimplicialError {
+    /// The polytope isn't a polyhedron in 3D space.
+    NotPolyhedron,
+
+    /// The polytope isn't star-shaped with respect to its gravicenter, so
+    /// coning its surface to it doesn't decompose it.
+    NotStarShaped,
+}
+
+impl Display for SimplicialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotPolyhedron => write!(f, "the polytope isn't a polyhedron in 3D space"),
+            Self::NotStarShaped => write!(
+                f,
+                "the polytope isn't star-shaped with respect to its gravicenter"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SimplicialError {}
+
+/// Returns the signed volume of the parallelepiped spanned by three vectors.
+fn triple_product(a: &Point<f64>, b: &Point<f64>, c: &Point<f64>) -> f64 {
+    a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+        + a[2] * (b[0] * c[1] - b[1] * c[0])
+}
+
+impl Concrete {
+    /// Triangulates the faces of a polyhedron in 3D space. Triangles are kept
+    /// as they are, and every other face is split into a fan of triangles
+    /// around its area centroid, which is added as a new vertex after the
+    /// vertices of the polyhedron.
+    ///
+    /// The triangles are oriented along the cycles of their faces, which needn't
+    /// agree with each other.
+    pub fn surface_triangulation(&self) -> Result<SimplicialComplex, SimplicialError> {
+        if self.rank() != 4 || self.dim() != Some(3) {
+            return Err(SimplicialError::NotPolyhedron);
+        }
+
+        let mut complex = SimplicialComplex {
+            vertices: self.vertices.clone(),
+            simplices: Vec::new(),
+        };
+
+        for face in self[3].iter() {
+            let cycles = CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs));
+            if let [cycle] = cycles.as_slice() {
+                if cycle.len() == 3 {
+                    complex.simplices.push(cycle.as_slice().to_vec());
+                    continue;
+                }
+            }
+
+            let points: Vec<Vec<_>> = cycles
+                .iter()
+                .map(|cycle| cycle.iter().map(|&v| &self.vertices[v]).collect())
+                .collect();
+            let plane = Subspace::fit_points(points.iter().flatten().copied(), 2)
+                .ok_or(SimplicialError::NotPolyhedron)?;
+            let center = complex.vertices.len();
+            complex
+                .vertices
+                .push(polygon_area_centroid(&points, &plane).1);
+
+            for cycle in cycles.iter() {
+                for (&v, &w) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+                    complex.simplices.push(vec![center, v, w]);
+                }
+            }
+        }
+
+        Ok(complex)
+    }
+
+    /// Decomposes a polyhedron in 3D space into tetrahedra, by coning the
+    /// [triangles of its surface](Self::surface_triangulation) to its
+    /// gravicenter, which is added as the last vertex.
+    ///
+    /// Every tetrahedron starts with the gravicenter and is positively
+    /// oriented, so that the other three vertices give its surface triangle
+    /// with an outward normal.
+    ///
+    /// This only works when every ray from the gravicenter crosses the surface
+    /// exactly once, which we check by adding up the solid angles of the
+    /// triangles. Otherwise, an error is returned.
+    pub fn simplicial_decomposition(&self) -> Result<SimplicialComplex, SimplicialError> {
+        let mut complex = self.surface_triangulation()?;
+        let center = complex.vertices.len();
+        let g = self.gravicenter().ok_or(SimplicialError::NotPolyhedron)?;
+        let mut solid_angle = 0.0;
+
+        let vertices = &complex.vertices;
+        for simplex in &mut complex.simplices {
+            let [a, b, c] = [0, 1, 2].map(|i| &vertices[simplex[i]] - &g);
+            let (na, nb, nc) = (a.norm(), b.norm(), c.norm());
+            let det = triple_product(&a, &b, &c);
+
+            // A triangle in a plane through the gravicenter gives a flat
+            // tetrahedron.
+            if det.abs() <= f64::EPS * na * nb * nc {
+                return Err(SimplicialError::NotStarShaped);
+            }
+
+            // The formula of Van Oosterom and Strackee.
+            let den = na * nb * nc + a.dot(&b) * nc + a.dot(&c) * nb + b.dot(&c) * na;
+            solid_angle += 2.0 * det.abs().atan2(den);
+
+            if det < 0.0 {
+                simplex.swap(1, 2);
+            }
+            simplex.insert(0, center);
+        }
+
+        if (solid_angle - 2.0 * TAU).abs() > 1e-6 {
+            return Err(SimplicialError::NotStarShaped);
+        }
+
+        complex.vertices.push(g);
+        Ok(complex)
+    }
+
+    /// Decomposes a polytope of any rank into the simplices given by its flags.
+    /// The vertices of each simplex are the centroids of the elements of a
+    /// flag, from its vertex up to the polytope itself.
+    ///
+    /// The vertices are listed by rank, so that the centroid of the element of
+    /// rank `r` with index `i` comes after those of every element of lower
+    /// rank. The simplices are listed in the same order as
+    /// [`Polytope::flags`].
+    ///
+    /// For polytopes whose elements are star-shaped with respect to their
+    /// centroids, like convex polytopes, the simplices fill the solid without
+    /// overlapping.
+    pub fn flag_simplices(&self) -> SimplicialComplex {
+        let rank = self.rank();
+        let mut complex = SimplicialComplex::default();
+        if rank == 0 {
+            return complex;
+        }
+
+        // The minimal element has no centroid.
+        let mut offsets = vec![0];
+        for r in 1..=rank {
+            offsets.push(complex.vertices.len());
+            for idx in 0..self.el_count(r) {
+                let vertices = self.element_vertices_ref(r, idx).unwrap();
+                let count = vertices.len() as f64;
+                complex
+                    .vertices
+                    .push(vertices.into_iter().sum::<Point<f64>>() / count);
+            }
+        }
+
+        complex.simplices = self
+            .flags()
+            .map(|flag| (1..=rank).map(|r| offsets[r] + flag[r]).collect())
+            .collect();
+        complex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Returns the vertices of a simplex, sorted.
+    fn sorted(simplex: &[usize]) -> Vec<usize> {
+        let mut simplex = simplex.to_vec();
+        simplex.sort_unstable();
+        simplex
+    }
+
+    /// The tetrahedra of the cube add up to its volume, and each triangle of
+    /// its surface bounds exactly one of them, facing outwards.
+    #[test]
+    fn cube() {
+        let cube = Concrete::cube();
+        let surface = cube.surface_triangulation().unwrap();
+        let complex = cube.simplicial_decomposition().unwrap();
+        assert_eq!(surface.len(), 24);
+        assert_eq!(complex.len(), 24);
+        assert_eq!(complex.vertices.len(), 8 + 6 + 1);
+        assert_abs_diff_eq!(complex.volume(), 1.0, epsilon = f64::EPS);
+
+        let center = complex.vertices.len() - 1;
+        let mut triangles = Vec::new();
+        for (idx, simplex) in complex.simplices.iter().enumerate() {
+            assert_eq!(simplex[0], center);
+            let [g, a, b, c] = [0, 1, 2, 3].map(|i| &complex.vertices[simplex[i]]);
+            let normal = (b - a).cross(&(c - a));
+            assert!(normal.dot(&(a - g)) > 0.0);
+            assert_abs_diff_eq!(
+                triple_product(&(a - g), &(b - g), &(c - g)) / 6.0,
+                complex.simplex_volume(idx),
+                epsilon = f64::EPS
+            );
+
+            triangles.push(sorted(&simplex[1..]));
+        }
+
+        let mut expected: Vec<_> = surface.simplices.iter().map(|t| sorted(t)).collect();
+        expected.sort();
+        triangles.sort();
+        assert_eq!(triangles, expected);
+        triangles.dedup();
+        assert_eq!(triangles.len(), 24);
+    }
+
+    /// Triangles are kept as they are.
+    #[test]
+    fn tetrahedron() {
+        let mut tet = Concrete::simplex(4);
+        let complex = tet.simplicial_decomposition().unwrap();
+        assert_eq!(complex.len(), 4);
+        assert_eq!(complex.vertices.len(), 5);
+        assert_abs_diff_eq!(
+            complex.volume(),
+            tet.volume_mut().unwrap().unwrap().abs(),
+            epsilon = f64::EPS
+        );
+    }
+
+    /// Polytopes that can't be coned to their gravicenter.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Concrete::polygon(5).simplicial_decomposition(),
+            Err(SimplicialError::NotPolyhedron)
+        );
+        assert_eq!(
+            Concrete::star_polygon(5, 2)
+                .prism()
+                .simplicial_decomposition(),
+            Err(SimplicialError::NotStarShaped)
+        );
+    }
+
+    /// The flag simplices of a polytope of any rank fill it.
+    #[test]
+    fn flag_simplices() {
+        let pentagon = Concrete::polygon(5).flag_simplices();
+        assert_eq!(pentagon.len(), 10);
+        assert_eq!(pentagon.vertices.len(), 5 + 5 + 1);
+        assert_abs_diff_eq!(
+            pentagon.volume(),
+            Concrete::polygon(5).volume_mut().unwrap().unwrap().abs(),
+            epsilon = f64::EPS
+        );
+
+        let cube = Concrete::cube().flag_simplices();
+        assert_eq!(cube.len(), 48);
+        assert_abs_diff_eq!(cube.volume(), 1.0, epsilon = f64::EPS);
+
+        let tesseract = Concrete::hypercube(5).flag_simplices();
+        assert_eq!(tesseract.len(), 384);
+        assert!(tesseract.simplices.iter().all(|simplex| simplex.len() == 5));
+        assert_abs_diff_eq!(tesseract.volume(), 1.0, epsilon = f64::EPS);
+    }
+}
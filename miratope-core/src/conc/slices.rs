@@ -0,0 +1,284 @@
+//! Takes many parallel cross-sections of a polytope at once, or many
+//! cross-sections through hyperplanes that rotate about a point.
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::{provenance::Provenance, Ranked},
+    float::Float,
+    geometry::{Hyperplane, Point, Vector},
+};
+
+/// The heights of the vertices of a polytope along a direction. Any number of
+/// cross-sections through hyperplanes orthogonal to it can then be taken
+/// without projecting the vertices again.
+pub struct SlicePrecomputation<'a> {
+    /// The polytope being sliced.
+    poly: &'a Concrete,
+
+    /// The height of every vertex along the direction.
+    heights: Vec<f64>,
+}
+
+impl<'a> SlicePrecomputation<'a> {
+    /// Computes the heights of the vertices of a polytope along a direction,
+    /// which must be a unit vector.
+    pub fn new(poly: &'a Concrete, direction: &Vector<f64>) -> Self {
+        Self {
+            poly,
+            heights: poly.vertices.iter().map(|v| v.dot(direction)).collect(),
+        }
+    }
+
+    /// Returns the least and greatest heights of the vertices, or `None` if
+    /// there are no vertices.
+    pub fn minmax(&self) -> Option<(f64, f64)> {
+        let mut heights = self.heights.iter().copied();
+        let first = heights.next()?;
+        Some(heights.fold((first, first), |(min, max), h| (min.min(h), max.max(h))))
+    }
+
+    /// Takes the cross-section through the hyperplane at a given height.
+    pub fn cross_section(&self, pos: f64) -> Concrete {
+        let distances: Vec<_> = self.heights.iter().map(|h| h - pos).collect();
+        self.poly.cross_section_impl(&distances, None, None)
+    }
+
+    /// Takes the cross-sections through the hyperplanes at the given heights.
+    pub fn cross_sections(&self, positions: &[f64]) -> Vec<Concrete> {
+        positions
+            .iter()
+            .map(|&pos| self.cross_section(pos))
+            .collect()
+    }
+}
+
+/// The heights of the vertices of a polytope along two orthonormal directions
+/// spanning a plane, measured from a center. Any number of cross-sections
+/// through hyperplanes that pass through the center, and whose normals lie on
+/// the plane, can then be taken without projecting the vertices again.
+///
+/// This doesn't borrow the polytope, so that it can be stored along with it.
+/// It must only be used with the polytope it was built from.
+#[derive(Clone, Debug)]
+pub struct RotatingSection {
+    /// An orthonormal basis of the plane the normals rotate in.
+    plane: (Vector<f64>, Vector<f64>),
+
+    /// The point every hyperplane passes through.
+    center: Point<f64>,
+
+    /// The heights of every vertex along both directions of the plane.
+    heights: Vec<(f64, f64)>,
+}
+
+impl RotatingSection {
+    /// Computes the heights of the vertices of a polytope along a plane
+    /// spanned by two vectors, which are orthonormalized first. Returns `None`
+    /// if the vectors don't span a plane, if they or the center don't have
+    /// the dimension of the polytope, or if the polytope isn't at least a
+    /// polyhedron.
+    pub fn new(
+        poly: &Concrete,
+        plane: (&Vector<f64>, &Vector<f64>),
+        center: &Point<f64>,
+    ) -> Option<Self> {
+        let dim = poly.dim_or();
+        if poly.rank() < 4 || plane.0.len() != dim || plane.1.len() != dim || center.len() != dim
+        {
+            return None;
+        }
+
+        let e1 = plane.0.try_normalize(f64::EPS)?;
+        let e2 = (plane.1 - &e1 * e1.dot(plane.1)).try_normalize(f64::EPS)?;
+        let heights = poly
+            .vertices
+            .iter()
+            .map(|v| {
+                let v = v - center;
+                (v.dot(&e1), v.dot(&e2))
+            })
+            .collect();
+
+        Some(Self {
+            plane: (e1, e2),
+            center: center.clone(),
+            heights,
+        })
+    }
+
+    /// Returns the normal of the hyperplane at a given angle, which goes from
+    /// the first direction of the plane towards the second.
+    pub fn normal(&self, angle: f64) -> Vector<f64> {
+        &self.plane.0 * angle.cos() + &self.plane.1 * angle.sin()
+    }
+
+    /// Returns the position along its normal of the hyperplane at a given
+    /// angle.
+    pub fn pos(&self, angle: f64) -> f64 {
+        self.normal(angle).dot(&self.center)
+    }
+
+    /// Returns the hyperplane at a given angle.
+    pub fn hyperplane(&self, angle: f64) -> Hyperplane<f64> {
+        Hyperplane::new(self.normal(angle), self.pos(angle))
+    }
+
+    /// Returns the signed distances from every vertex to the hyperplane at a
+    /// given angle.
+    fn distances(&self, angle: f64) -> Vec<f64> {
+        let (sin, cos) = angle.sin_cos();
+        self.heights.iter().map(|(h1, h2)| cos * h1 + sin * h2).collect()
+    }
+
+    /// Takes the cross-section of the polytope through the hyperplane at a
+    /// given angle.
+    pub fn cross_section(&self, poly: &Concrete, angle: f64) -> Concrete {
+        poly.cross_section_impl(&self.distances(angle), None, None)
+    }
+
+    /// Takes the cross-section of the polytope through the hyperplane at a
+    /// given angle, and records the element of the polytope that each element
+    /// of the cross-section lies in.
+    pub fn cross_section_with_provenance(
+        &self,
+        poly: &Concrete,
+        angle: f64,
+    ) -> (Concrete, Provenance) {
+        let mut provenance = Provenance::default();
+        let distances = self.distances(angle);
+        let section = poly.cross_section_impl(&distances, None, Some(&mut provenance));
+        (section, provenance)
+    }
+}
+
+impl Concrete {
+    /// Takes the cross-section through the hyperplane that passes through a
+    /// given center, and whose normal is `cos(angle) e1 + sin(angle) e2` for
+    /// the orthonormalized vectors `e1` and `e2` spanning a plane. Returns
+    /// `None` if the vectors don't span a plane, if they or the center don't
+    /// have the dimension of the polytope, or if the polytope isn't at least
+    /// a polyhedron.
+    ///
+    /// To take many of these cross-sections, use a [`RotatingSection`].
+    pub fn rotating_section(
+        &self,
+        plane: (Vector<f64>, Vector<f64>),
+        center: &Point<f64>,
+        angle: f64,
+    ) -> Option<Self> {
+        RotatingSection::new(self, (&plane.0, &plane.1), center)
+            .map(|section| section.cross_section(self, angle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    use approx::abs_diff_eq;
+
+    /// The slices taken at once match those taken one by one.
+    #[test]
+    fn cross_sections() {
+        let poly = Concrete::hypercube(5);
+        let direction = Point::from_vec(vec![1.0, 2.0, 3.0, 4.0]).normalize();
+        let precomputation = SlicePrecomputation::new(&poly, &direction);
+
+        let (min, max) = precomputation.minmax().unwrap();
+        assert!(abs_diff_eq!(max, -min, epsilon = f64::EPS));
+
+        let positions: Vec<_> = (1..8)
+            .map(|i| min + (max - min) * f64::usize(i) / 8.0)
+            .collect();
+        let slices = precomputation.cross_sections(&positions);
+        assert_eq!(slices.len(), positions.len());
+
+        for (slice, &pos) in slices.iter().zip(&positions) {
+            let expected = poly.cross_section(&Hyperplane::new(direction.clone(), pos));
+            assert_eq!(slice.el_counts(), expected.el_counts());
+            assert!(slice
+                .vertices
+                .iter()
+                .zip(&expected.vertices)
+                .all(|(v, w)| abs_diff_eq!((v - w).norm(), 0.0, epsilon = f64::EPS)));
+        }
+
+        // Slices outside of the polytope are empty.
+        assert_eq!(precomputation.cross_section(max + 1.0).rank(), 0);
+    }
+
+    /// Asserts that two slices have the same elements and vertices.
+    fn assert_same_slice(slice: &Concrete, expected: &Concrete) {
+        assert_eq!(slice.el_counts(), expected.el_counts());
+        assert!(slice
+            .vertices
+            .iter()
+            .zip(&expected.vertices)
+            .all(|(v, w)| abs_diff_eq!((v - w).norm(), 0.0, epsilon = f64::EPS)));
+    }
+
+    /// The sections of a cube rotating in the xz-plane start and end a quarter
+    /// turn later as the slices orthogonal to the x and z axes.
+    #[test]
+    fn rotating_cube() {
+        let cube = Concrete::hypercube(4);
+        let axis = |i| Point::from_fn(3, |j, _| if i == j { 1.0 } else { 0.0 });
+        let plane = (axis(0), axis(2));
+        let center = Point::zeros(3);
+
+        for (angle, normal) in [(0.0, axis(0)), (f64::PI / 2.0, axis(2))] {
+            let slice = cube.rotating_section(plane.clone(), &center, angle).unwrap();
+            let expected = cube.cross_section(&Hyperplane::new(normal, 0.0));
+            test(&slice, [1, 4, 4, 1]);
+            assert_same_slice(&slice, &expected);
+        }
+
+        // Halfway, the section is a rectangle through opposite edges.
+        let slice = cube.rotating_section(plane, &center, f64::PI / 4.0).unwrap();
+        let mut lengths: Vec<_> = slice.edge_lengths().into_iter().map(|(_, len)| len).collect();
+        lengths.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        assert!(abs_diff_eq!(lengths[0], 1.0, epsilon = f64::EPS));
+        assert!(abs_diff_eq!(lengths[3], f64::SQRT_2, epsilon = f64::EPS));
+    }
+
+    /// The sections taken from the precomputation match those through the
+    /// corresponding hyperplanes, even for planes that aren't orthonormal and
+    /// centers off the origin.
+    #[test]
+    fn rotating_precomputation() {
+        let poly = Concrete::hypercube(5);
+        let e1 = Point::from_vec(vec![1.0, 1.0, 0.0, 0.0]);
+        let e2 = Point::from_vec(vec![0.0, 1.0, 2.0, 1.0]);
+        let center = Point::from_vec(vec![0.1, -0.1, 0.2, 0.0]);
+        let section = RotatingSection::new(&poly, (&e1, &e2), &center).unwrap();
+
+        for i in 0..12 {
+            let angle = f64::usize(i) * f64::PI / 6.0;
+            let hyperplane = section.hyperplane(angle);
+            assert!(abs_diff_eq!(hyperplane.normal().norm(), 1.0, epsilon = f64::EPS));
+            assert!(abs_diff_eq!(hyperplane.distance(&center), 0.0, epsilon = f64::EPS));
+
+            let (slice, provenance) = section.cross_section_with_provenance(&poly, angle);
+            let (expected, expected_provenance) = poly.cross_section_with_provenance(&hyperplane);
+            assert_same_slice(&slice, &expected);
+            assert_eq!(provenance, expected_provenance);
+        }
+    }
+
+    /// The section needs a plane, and the dimensions to match.
+    #[test]
+    fn rotating_invalid() {
+        let cube = Concrete::hypercube(4);
+        let x = Point::from_vec(vec![1.0, 0.0, 0.0]);
+        let center = Point::zeros(3);
+
+        assert!(cube.rotating_section((x.clone(), &x * 2.0), &center, 0.0).is_none());
+        assert!(cube
+            .rotating_section((x.clone(), Point::from_vec(vec![0.0, 1.0])), &center, 0.0)
+            .is_none());
+        assert!(Concrete::polygon(4)
+            .rotating_section((x.clone(), x), &Point::zeros(2), 0.0)
+            .is_none());
+    }
+}
@@ -0,0 +1,270 @@
+//! Snaps the coordinates of a polytope to nearby exact values, so as to clean
+//! up the numerical noise left behind by long chains of operations.
+
+use std::fmt::Display;
+
+use super::{shapes::PHI, Concrete};
+use crate::float::Float;
+
+/// The constants that coordinates can be snapped to, along with their
+/// negatives: 1, φ, φ², √2 and √3, and their halves. These show up in the
+/// coordinates of most uniform polytopes.
+pub const SNAP_CONSTANTS: [f64; 10] = [
+    1.0,
+    0.5,
+    PHI,
+    PHI / 2.0,
+    PHI + 1.0,
+    (PHI + 1.0) / 2.0,
+    f64::SQRT_2,
+    f64::HALF_SQRT_2,
+    f64::SQRT_3,
+    f64::SQRT_3 / 2.0,
+];
+
+/// The options with which the coordinates of a polytope are snapped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapOptions {
+    /// The greatest amount by which a coordinate is snapped.
+    pub eps: f64,
+
+    /// Whether coordinates are snapped to 0, ±1/2 and ±1.
+    pub simple: bool,
+
+    /// A value whose multiples coordinates are snapped to, if any.
+    pub quantum: Option<f64>,
+
+    /// Whether coordinates are snapped to the [`SNAP_CONSTANTS`] and their
+    /// negatives.
+    pub constants: bool,
+
+    /// The greatest distance by which snapping may move a vertex, as a
+    /// fraction of the least edge length.
+    pub max_shift: f64,
+}
+
+impl Default for SnapOptions {
+    fn default() -> Self {
+        Self {
+            eps: 1e-6,
+            simple: true,
+            quantum: None,
+            constants: false,
+            max_shift: 0.01,
+        }
+    }
+}
+
+impl SnapOptions {
+    /// Returns the closest value within `eps` that a coordinate snaps to, if
+    /// any.
+    pub fn snap(&self, x: f64) -> Option<f64> {
+        let mut best: Option<f64> = None;
+        let mut consider = |c: f64| {
+            let dist = (x - c).abs();
+            if dist <= self.eps && !matches!(best, Some(b) if (x - b).abs() <= dist) {
+                best = Some(c);
+            }
+        };
+
+        if self.simple {
+            for c in [0.0, 0.5, -0.5, 1.0, -1.0] {
+                consider(c);
+            }
+        }
+
+        if self.constants {
+            for c in SNAP_CONSTANTS {
+                consider(c);
+                consider(-c);
+            }
+        }
+
+        if let Some(quantum) = self.quantum {
+            if quantum > 0.0 {
+                consider((x / quantum).round() * quantum);
+            }
+        }
+
+        best
+    }
+}
+
+/// What snapping the coordinates of a polytope changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SnapReport {
+    /// The number of coordinates that changed.
+    pub changed: usize,
+
+    /// The greatest amount by which a single coordinate changed.
+    pub max_adjustment: f64,
+
+    /// The greatest distance by which a vertex moved.
+    pub max_shift: f64,
+}
+
+impl Display for SnapReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} coordinates changed, by at most {:e}",
+            self.changed, self.max_adjustment
+        )
+    }
+}
+
+/// The error returned when snapping would move a vertex further than the
+/// options allow, which would distort the polytope.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapError {
+    /// The index of the vertex that would move the furthest.
+    pub vertex: usize,
+
+    /// The distance the vertex would move.
+    pub shift: f64,
+
+    /// The greatest distance any vertex may move.
+    pub limit: f64,
+}
+
+impl Display for SnapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "snapping would move vertex {} by {:e}, more than the limit of {:e}",
+            self.vertex, self.shift, self.limit
+        )
+    }
+}
+
+impl std::error::Error for SnapError {}
+
+impl Concrete {
+    /// Snaps the coordinates of the vertices of the polytope to nearby exact
+    /// values, following the given options, and reports what changed.
+    ///
+    /// If some vertex would move by more than `options.max_shift` times the
+    /// least edge length, the polytope is left unchanged and an error is
+    /// returned instead.
+    pub fn snap_coordinates(&mut self, options: SnapOptions) -> Result<SnapReport, SnapError> {
+        let mut report = SnapReport::default();
+        let mut vertices = self.vertices.clone();
+        let mut worst = 0;
+
+        for (idx, v) in vertices.iter_mut().enumerate() {
+            let mut shift = 0.0;
+
+            for x in v.iter_mut() {
+                if let Some(y) = options.snap(*x) {
+                    if y != *x {
+                        let adjustment = (y - *x).abs();
+                        report.changed += 1;
+                        report.max_adjustment = report.max_adjustment.max(adjustment);
+                        shift += adjustment * adjustment;
+                        *x = y;
+                    }
+                }
+            }
+
+            let shift = shift.sqrt();
+            if shift > report.max_shift {
+                report.max_shift = shift;
+                worst = idx;
+            }
+        }
+
+        // Polytopes without edges have no scale to compare against.
+        let min_edge = self
+            .edge_lengths()
+            .into_iter()
+            .map(|(_, len)| len)
+            .fold(f64::INFINITY, f64::min);
+        if min_edge.is_finite() {
+            let limit = options.max_shift * min_edge;
+            if report.max_shift > limit {
+                return Err(SnapError {
+                    vertex: worst,
+                    shift: report.max_shift,
+                    limit,
+                });
+            }
+        }
+
+        self.vertices = vertices;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Returns a cube whose vertices have been displaced by tiny arbitrary
+    /// amounts.
+    fn noisy_cube() -> Concrete {
+        let mut cube = Concrete::hypercube(4);
+
+        for (idx, v) in cube.vertices.iter_mut().enumerate() {
+            for (i, x) in v.iter_mut().enumerate() {
+                *x += 1e-9 * ((7 * idx + 3 * i) as f64).sin();
+            }
+        }
+
+        cube
+    }
+
+    /// A noisy cube snaps back to the exact cube.
+    #[test]
+    fn cube() {
+        let mut cube = noisy_cube();
+        assert_ne!(cube.vertices, Concrete::hypercube(4).vertices);
+
+        let report = cube.snap_coordinates(SnapOptions::default()).unwrap();
+        assert_eq!(cube.vertices, Concrete::hypercube(4).vertices);
+        assert_eq!(report.changed, 23);
+        assert!(report.max_adjustment < 1e-9);
+
+        // Snapping again changes nothing.
+        assert_eq!(cube.snap_coordinates(SnapOptions::default()).unwrap(), SnapReport::default());
+    }
+
+    /// Coordinates snap to the closest constant or multiple of the quantum.
+    #[test]
+    fn snap() {
+        let constants = SnapOptions {
+            constants: true,
+            ..Default::default()
+        };
+        assert_eq!(constants.snap(PHI + 1e-9), Some(PHI));
+        assert_eq!(constants.snap(-f64::SQRT_3 / 2.0 - 1e-9), Some(-f64::SQRT_3 / 2.0));
+        assert_eq!(constants.snap(1e-12), Some(0.0));
+        assert_eq!(constants.snap(0.3), None);
+
+        let quantum = SnapOptions {
+            simple: false,
+            quantum: Some(0.25),
+            ..Default::default()
+        };
+        assert_eq!(quantum.snap(0.75 - 1e-9), Some(0.75));
+        assert_eq!(quantum.snap(0.7), None);
+    }
+
+    /// Snapping refuses to move vertices too far, and leaves the polytope
+    /// unchanged.
+    #[test]
+    fn too_far() {
+        let mut cube = Concrete::hypercube(4);
+        let options = SnapOptions {
+            eps: 0.6,
+            simple: false,
+            quantum: Some(1.0),
+            ..Default::default()
+        };
+
+        let err = cube.snap_coordinates(options).unwrap_err();
+        assert!((err.shift - 0.75f64.sqrt()).abs() < f64::EPS);
+        assert!((err.limit - 0.01).abs() < f64::EPS);
+        assert_eq!(cube.vertices, Concrete::hypercube(4).vertices);
+    }
+}
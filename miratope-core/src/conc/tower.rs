@@ -0,0 +1,150 @@
+//! Builds a tower of combinatorially equal polytopes, stacked at increasing
+//! heights along a new axis.
+
+use super::{Concrete, ConcretePolytope};
+use crate::abs::Ranked;
+
+/// An error while building a tower of polytopes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TowerError {
+    /// There were less than two layers.
+    TooFewLayers,
+
+    /// The layers have rank less than 2, so they have no lateral facets.
+    RankTooLow,
+
+    /// The layer with the given index isn't combinatorially equal to the first
+    /// one, using the same indexing of its elements.
+    StructureMismatch(usize),
+
+    /// The layer with the given index doesn't have the same dimension as the
+    /// first one.
+    DimensionMismatch(usize),
+
+    /// The layer with the given index isn't strictly higher than the one below
+    /// it.
+    HeightNotIncreasing(usize),
+}
+
+impl std::fmt::Display for TowerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewLayers => write!(f, "a tower needs at least two layers"),
+            Self::RankTooLow => write!(f, "only polytopes of rank 2 or more have towers"),
+            Self::StructureMismatch(idx) => {
+                write!(f, "layer {} isn't combinatorially equal to the first", idx)
+            }
+            Self::DimensionMismatch(idx) => {
+                write!(f, "layer {} has a different dimension than the first", idx)
+            }
+            Self::HeightNotIncreasing(idx) => {
+                write!(f, "layer {} isn't higher than the one below it", idx)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TowerError {}
+
+impl Concrete {
+    /// Stacks combinatorially equal layers at the given heights, along a new
+    /// last coordinate axis. Consecutive layers are joined by lateral prisms
+    /// over each of their facets. See [`Abstract::tower`](crate::abs::Abstract::tower).
+    ///
+    /// The facets of the result are the bottom layer, the top layer, and the
+    /// lateral prisms. The intermediate layers are shared by the prisms above
+    /// and below them, so three layers of squares give 12 vertices, rather
+    /// than the 16 vertices of two separate cubes.
+    ///
+    /// The layers must have the same elements with the same indices, and the
+    /// same dimension. Their heights must be strictly increasing.
+    pub fn tower(layers: &[(Concrete, f64)]) -> Result<Self, TowerError> {
+        let (first, mut height) = match layers {
+            [(first, height), _, ..] => (first, *height),
+            _ => return Err(TowerError::TooFewLayers),
+        };
+
+        if first.rank() < 2 {
+            return Err(TowerError::RankTooLow);
+        }
+
+        let dim = first.dim_or();
+        for (idx, (layer, layer_height)) in layers.iter().enumerate().skip(1) {
+            if layer.abs.ranks() != first.abs.ranks() {
+                return Err(TowerError::StructureMismatch(idx));
+            }
+            if layer.dim_or() != dim {
+                return Err(TowerError::DimensionMismatch(idx));
+            }
+            if *layer_height <= height {
+                return Err(TowerError::HeightNotIncreasing(idx));
+            }
+
+            height = *layer_height;
+        }
+
+        let vertices = layers
+            .iter()
+            .flat_map(|(layer, height)| layer.vertices.iter().map(move |v| v.push(*height)))
+            .collect();
+
+        Ok(Self::new(vertices, first.abs.tower(layers.len())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Stacks three squares of different sizes. The middle layer is shared by
+    /// both frusta, so there are 12 vertices rather than 16.
+    #[test]
+    fn squares() {
+        let square = Concrete::polygon(4);
+        let tower = Concrete::tower(&[
+            (square.clone(), 0.0),
+            (square.clone().scale(2.0), 1.0),
+            (square.clone(), 3.0),
+        ])
+        .unwrap();
+
+        test(&tower, [1, 12, 20, 10, 1]);
+        assert_eq!(tower.dim(), Some(3));
+    }
+
+    /// A tower of two equal layers is a prism.
+    #[test]
+    fn prism() {
+        let square = Concrete::polygon(4);
+        let tower = Concrete::tower(&[(square.clone(), -0.5), (square.clone(), 0.5)]).unwrap();
+        assert!(tower.abs.is_isomorphic(&square.prism_with(1.0).abs));
+    }
+
+    /// Checks that mismatched layers give the corresponding errors.
+    #[test]
+    fn mismatch() {
+        let square = Concrete::polygon(4);
+        let square_3d = Concrete::new(
+            square.vertices.iter().map(|v| v.push(0.0)).collect(),
+            square.abs.clone(),
+        );
+
+        assert!(matches!(
+            Concrete::tower(&[(square.clone(), 0.0)]),
+            Err(TowerError::TooFewLayers)
+        ));
+        assert!(matches!(
+            Concrete::tower(&[(square.clone(), 0.0), (Concrete::polygon(5), 1.0)]),
+            Err(TowerError::StructureMismatch(1))
+        ));
+        assert!(matches!(
+            Concrete::tower(&[(square.clone(), 0.0), (square_3d, 1.0)]),
+            Err(TowerError::DimensionMismatch(1))
+        ));
+        assert!(matches!(
+            Concrete::tower(&[(square.clone(), 0.0), (square, 0.0)]),
+            Err(TowerError::HeightNotIncreasing(1))
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+//! Checks the conditions that a polytope must satisfy in order to be uniform,
+//! and reports which of them fail.
+
+use std::fmt::{self, Display};
+
+use super::{Concrete, ConcretePolytope};
+use crate::{abs::Ranked, geometry::Hypersphere, Polytope};
+
+use itertools::Itertools;
+use vec_like::*;
+
+/// The result of checking the conditions for a polytope to be uniform. Each
+/// entry stores the first element for which a condition fails, if any.
+///
+/// These conditions are necessary, but not sufficient: a polytope that passes
+/// every check still needs uniform facets and a vertex-transitive symmetry
+/// group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UniformityReport {
+    /// The first vertex that doesn't lie on the sphere through the previous
+    /// ones.
+    pub off_sphere_vertex: Option<usize>,
+
+    /// The first edge whose length differs from the median edge length, as in
+    /// [`Concrete::equilateral_report`]. Degenerate edges never have the
+    /// median length.
+    pub unequal_edge: Option<usize>,
+
+    /// The first vertex whose figure is combinatorially distinct from the
+    /// figure of vertex 0.
+    pub different_verf: Option<usize>,
+}
+
+impl UniformityReport {
+    /// Returns whether every check passed.
+    pub fn is_uniform(&self) -> bool {
+        self.off_sphere_vertex.is_none()
+            && self.unequal_edge.is_none()
+            && self.different_verf.is_none()
+    }
+}
+
+impl Display for UniformityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.off_sphere_vertex {
+            Some(idx) => writeln!(
+                f,
+                "Common circumsphere: no, vertex {} isn't on the sphere through the previous vertices",
+                idx
+            )?,
+            None => writeln!(f, "Common circumsphere: yes")?,
+        }
+
+        match self.unequal_edge {
+            Some(idx) => writeln!(
+                f,
+                "Equal edge lengths: no, edge {}'s length differs from the median",
+                idx
+            )?,
+            None => writeln!(f, "Equal edge lengths: yes")?,
+        }
+
+        match self.different_verf {
+            Some(idx) => writeln!(
+                f,
+                "Equal vertex figures: no, vertex {}'s figure differs from vertex 0's",
+                idx
+            ),
+            None => writeln!(f, "Equal vertex figures: yes"),
+        }
+    }
+}
+
+impl Concrete {
+    /// Returns whether the polytope passes some quick necessary conditions for
+    /// being isogonal, namely, whether its vertices lie on a common sphere and
+    /// all of them belong to the same number of edges.
+    pub fn is_isogonal_candidate(&self) -> bool {
+        if self.vertex_count() == 0 {
+            return true;
+        }
+
+        Hypersphere::try_circumsphere(self.vertices()).is_ok()
+            && (self.rank() < 2 || self[1].iter().map(|v| v.sups.len()).all_equal())
+    }
+
+    /// Checks that the vertices of the polytope lie on a common sphere, that
+    /// all of its edges have the same length, and that all of its vertex
+    /// figures are combinatorially equal. The report stores the first element
+    /// that fails each of these checks.
+    pub fn uniformity_report(&self) -> UniformityReport {
+        UniformityReport {
+            off_sphere_vertex: self.off_sphere_vertex(),
+            unequal_edge: self.unequal_edge(),
+            different_verf: self.different_verf(),
+        }
+    }
+
+    /// Returns the first vertex that doesn't lie on the sphere through the
+    /// previous ones.
+    fn off_sphere_vertex(&self) -> Option<usize> {
+        if self.vertex_count() == 0 {
+            None
+        } else {
+            Hypersphere::try_circumsphere(self.vertices()).err()
+        }
+    }
+
+    /// Returns the first edge whose length differs from the median edge
+    /// length.
+    fn unequal_edge(&self) -> Option<usize> {
+        if self.rank() < 2 {
+            return None;
+        }
+
+        self.equilateral_report(Default::default())
+            .outliers
+            .first()
+            .map(|&(idx, _)| idx)
+    }
+
+    /// Returns the first vertex whose figure is combinatorially distinct from
+    /// that of vertex 0.
+    fn different_verf(&self) -> Option<usize> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        // The vertex figures are the duals of the facets of the dual, and
+        // duality preserves isomorphism.
+        let dual = self.abs().dual();
+        let verf = dual.facet(0)?;
+
+        (1..self.vertex_count()).find(|&idx| match dual.facet(idx) {
+            Some(other) => !verf.is_isomorphic(&other),
+            None => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the cuboctahedron passes every check.
+    #[test]
+    fn cuboctahedron() {
+        let cuboctahedron = Concrete::archimedean(1);
+        assert!(cuboctahedron.is_isogonal_candidate());
+
+        let report = cuboctahedron.uniformity_report();
+        assert!(report.is_uniform());
+        assert_eq!(report, UniformityReport::default());
+    }
+
+    /// Checks that the vertex figures of the square cupola are told apart.
+    #[test]
+    fn square_cupola() {
+        let cupola = Concrete::cupola(4);
+        assert!(!cupola.is_isogonal_candidate());
+
+        // The first four vertices belong to the square on top.
+        let report = cupola.uniformity_report();
+        assert_eq!(report.off_sphere_vertex, None);
+        assert_eq!(report.unequal_edge, None);
+        assert_eq!(report.different_verf, Some(4));
+    }
+
+    /// Checks a cube with a displaced vertex.
+    #[test]
+    fn distorted_cube() {
+        let mut cube = Concrete::cube();
+        let last = cube.vertex_count() - 1;
+        cube.vertices[last] *= 1.5;
+
+        let report = cube.uniformity_report();
+        assert!(!report.is_uniform());
+        assert_eq!(report.off_sphere_vertex, Some(last));
+        assert!(cube[(2, report.unequal_edge.unwrap())].subs.contains(&last));
+        assert_eq!(report.different_verf, None);
+        assert!(report.to_string().contains(&format!("vertex {} isn't", last)));
+    }
+}
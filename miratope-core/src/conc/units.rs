@@ -0,0 +1,252 @@
+//! Physical units for the coordinates of a polytope, which are read from and
+//! written to files as a comment, and the operations that rescale a polytope
+//! from one unit into another or to a given physical size.
+
+use std::fmt::Display;
+
+use super::{Concrete, ConcretePolytope};
+
+/// The units that can be picked as a target, along with the length of each in
+/// meters.
+pub const UNITS: [(&str, f64); 5] = [
+    ("mm", 0.001),
+    ("cm", 0.01),
+    ("m", 1.0),
+    ("in", 0.0254),
+    ("ft", 0.3048),
+];
+
+/// The prefix of the comment that stores the units of a polytope in a file.
+const COMMENT_PREFIX: &str = "units:";
+
+/// The unit in which the coordinates of a polytope are given.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitInfo {
+    /// The name of the unit, like `mm`.
+    pub name: String,
+
+    /// The length of one unit, in meters.
+    pub scale: f64,
+}
+
+impl UnitInfo {
+    /// Returns one of the known [`UNITS`] by name.
+    pub fn named(name: &str) -> Option<Self> {
+        UNITS
+            .iter()
+            .find(|(unit, _)| *unit == name)
+            .map(|&(name, scale)| Self {
+                name: name.to_string(),
+                scale,
+            })
+    }
+
+    /// Returns the unit of millimeters, which STL consumers assume.
+    pub fn millimeters() -> Self {
+        Self::named("mm").unwrap()
+    }
+
+    /// Returns the number a length in this unit has to be multiplied by to
+    /// give the same length in another unit.
+    pub fn factor_to(&self, target: &Self) -> f64 {
+        self.scale / target.scale
+    }
+
+    /// Returns the comment that stores this unit in a file, without the
+    /// leading `#`.
+    pub fn to_comment(&self) -> String {
+        format!("{} {} scale: {}", COMMENT_PREFIX, self.name, self.scale)
+    }
+
+    /// Parses a comment of the form `units: <name> scale: <factor>`, with or
+    /// without the leading `#`. The scale can be left out for the known
+    /// [`UNITS`].
+    pub fn from_comment(comment: &str) -> Option<Self> {
+        let comment = comment.trim_start();
+        let comment = comment.strip_prefix('#').unwrap_or(comment).trim();
+        let mut words = comment.strip_prefix(COMMENT_PREFIX)?.split_whitespace();
+        let name = words.next()?;
+
+        match (words.next(), words.next()) {
+            (Some("scale:"), Some(scale)) => {
+                let scale: f64 = scale.parse().ok()?;
+                (scale.is_finite() && scale > 0.0).then(|| Self {
+                    name: name.to_string(),
+                    scale,
+                })
+            }
+            (None, _) => Self::named(name),
+            _ => None,
+        }
+    }
+
+    /// Finds the units stored in a comment line of a file, if any.
+    pub fn find_in(src: &str) -> Option<Self> {
+        src.lines()
+            .filter(|line| line.trim_start().starts_with('#'))
+            .find_map(Self::from_comment)
+    }
+}
+
+impl Display for UnitInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Concrete {
+    /// Rescales the polytope from the units it's stored in into another unit,
+    /// and returns the factor it was scaled by. Returns `None` if the units of
+    /// the polytope aren't known.
+    pub fn convert_units(&mut self, target: &UnitInfo) -> Option<f64> {
+        let factor = self.units.as_ref()?.factor_to(target);
+        self.scale_mut(factor);
+        self.units = Some(target.clone());
+        Some(factor)
+    }
+
+    /// Rescales the polytope so that the longest side of its bounding box has
+    /// a given length in a given unit, which becomes the unit of the polytope,
+    /// and returns the factor it was scaled by. Returns `None` if the bounding
+    /// box has no positive side, as for the nullitope or a point.
+    pub fn fit_to_size(&mut self, size: f64, unit: &UnitInfo) -> Option<f64> {
+        let (min, max) = self.bounding_box()?;
+        let longest = (max - min).amax();
+        if longest.is_nan() || longest <= 0.0 {
+            return None;
+        }
+
+        let factor = size / longest;
+        self.scale_mut(factor);
+        self.units = Some(unit.clone());
+        Some(factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        file::{off::OffOptions, FromFile},
+        float::Float,
+        test, Polytope,
+    };
+
+    use approx::abs_diff_eq;
+
+    /// Units are written as a comment and read back, both for known units
+    /// and for arbitrary ones.
+    #[test]
+    fn comment() {
+        let mm = UnitInfo::millimeters();
+        assert_eq!(mm.to_comment(), "units: mm scale: 0.001");
+        assert_eq!(
+            UnitInfo::from_comment("# units: mm scale: 0.001"),
+            Some(mm.clone())
+        );
+        assert_eq!(UnitInfo::from_comment("#units: mm"), Some(mm));
+
+        let league = UnitInfo {
+            name: "league".to_string(),
+            scale: 4828.032,
+        };
+        assert_eq!(UnitInfo::from_comment(&league.to_comment()), Some(league));
+
+        assert_eq!(UnitInfo::from_comment("# units: league"), None);
+        assert_eq!(UnitInfo::from_comment("# units: mm scale: -1"), None);
+        assert_eq!(UnitInfo::from_comment("# Vertices, Faces, Edges"), None);
+    }
+
+    /// The units of a polytope survive a round trip through an OFF file, and
+    /// can be left out of it.
+    #[test]
+    fn off_roundtrip() {
+        let mut cube = Concrete::cube();
+        let off = cube.to_off(Default::default()).unwrap();
+        assert_eq!(Concrete::from_off(&off).unwrap().units, None);
+
+        cube.units = UnitInfo::named("in");
+        for comments in [true, false] {
+            let off = cube
+                .to_off(OffOptions {
+                    comments,
+                    ..Default::default()
+                })
+                .unwrap();
+            assert!(off.contains("# units: in scale: 0.0254\n"));
+
+            let reloaded = Concrete::from_off(&off).unwrap();
+            test(&reloaded, [1, 8, 12, 6, 1]);
+            assert_eq!(reloaded.units, cube.units);
+        }
+
+        let off = cube
+            .to_off(OffOptions {
+                units: false,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(!off.contains("units:"));
+        assert_eq!(Concrete::from_off(&off).unwrap().units, None);
+    }
+
+    /// Converting between units scales by the ratio of their lengths, and
+    /// needs the polytope to have units to begin with.
+    #[test]
+    fn convert() {
+        let mut cube = Concrete::cube();
+        assert_eq!(cube.convert_units(&UnitInfo::millimeters()), None);
+
+        cube.units = UnitInfo::named("in");
+        let factor = cube.convert_units(&UnitInfo::millimeters()).unwrap();
+        assert!(abs_diff_eq!(factor, 25.4, epsilon = f64::EPS * 100.0));
+        assert_eq!(cube.units, Some(UnitInfo::millimeters()));
+        assert!(abs_diff_eq!(
+            cube.vertices[0].amax(),
+            12.7,
+            epsilon = f64::EPS * 100.0
+        ));
+    }
+
+    /// A unit-edge polytope printed at a given size in millimeters has the
+    /// longest side of its bounding box that long.
+    #[test]
+    fn fit() {
+        // The bounding box of a unit-edge cube is also a unit cube.
+        let mut cube = Concrete::cube();
+        let factor = cube.fit_to_size(50.0, &UnitInfo::millimeters()).unwrap();
+        assert_eq!(factor, 50.0);
+        assert_eq!(cube.edge_lengths()[0].1, 50.0);
+
+        // The bounding box of a pentagon is as wide as its diagonals, which
+        // are φ times as long as its edges.
+        let mut pentagon = Concrete::polygon(5);
+        let edge = pentagon.edge_lengths()[0].1;
+        let factor = pentagon
+            .fit_to_size(50.0, &UnitInfo::millimeters())
+            .unwrap();
+        let (min, max) = pentagon.bounding_box().unwrap();
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+
+        assert!(abs_diff_eq!(
+            (max - min).amax(),
+            50.0,
+            epsilon = f64::EPS * 100.0
+        ));
+        assert!(abs_diff_eq!(
+            factor * phi * edge,
+            50.0,
+            epsilon = f64::EPS * 100.0
+        ));
+        assert_eq!(pentagon.units, Some(UnitInfo::millimeters()));
+
+        assert_eq!(
+            Concrete::point().fit_to_size(50.0, &UnitInfo::millimeters()),
+            None
+        );
+        assert_eq!(
+            Concrete::nullitope().fit_to_size(50.0, &UnitInfo::millimeters()),
+            None
+        );
+    }
+}
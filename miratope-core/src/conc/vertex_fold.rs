@@ -0,0 +1,166 @@
+//! Reductions over the vertices of a polytope, such as the extreme vertex in a
+//! direction or the bounding box, all written as a single fold that doesn't
+//! allocate.
+
+use super::Concrete;
+use crate::geometry::{Point, Vector};
+
+/// Folds a list of points together with their indices, or returns `None` if
+/// there are no points.
+pub fn fold_points<'a, T, I, F>(points: I, init: T, mut f: F) -> Option<T>
+where
+    I: IntoIterator<Item = &'a Point<f64>>,
+    F: FnMut(T, usize, &'a Point<f64>) -> T,
+{
+    let mut points = points.into_iter().enumerate().peekable();
+    points.peek()?;
+    Some(points.fold(init, |acc, (idx, p)| f(acc, idx, p)))
+}
+
+/// Returns the index and the value of the point that maximizes a function, or
+/// returns `None` if there are no points. Values that are NaN are skipped, and
+/// ties go to the first point.
+pub fn max_point_by<'a, I, F>(points: I, mut f: F) -> Option<(usize, f64)>
+where
+    I: IntoIterator<Item = &'a Point<f64>>,
+    F: FnMut(&'a Point<f64>) -> f64,
+{
+    fold_points(points, None, |best: Option<(usize, f64)>, idx, p| {
+        let value = f(p);
+        if value.is_nan() || matches!(best, Some((_, max)) if value <= max) {
+            best
+        } else {
+            Some((idx, value))
+        }
+    })
+    .flatten()
+}
+
+/// Returns the least and the greatest value of every coordinate of a list of
+/// points, or returns `None` if there are no points.
+pub fn bounding_box<'a, I>(points: I) -> Option<(Point<f64>, Point<f64>)>
+where
+    I: IntoIterator<Item = &'a Point<f64>>,
+{
+    fold_points(
+        points,
+        None,
+        |bbox: Option<(Point<f64>, Point<f64>)>, _, p| {
+            Some(match bbox {
+                None => (p.clone(), p.clone()),
+                Some((mut min, mut max)) => {
+                    for ((lo, hi), &x) in min.iter_mut().zip(max.iter_mut()).zip(p.iter()) {
+                        *lo = lo.min(x);
+                        *hi = hi.max(x);
+                    }
+                    (min, max)
+                }
+            })
+        },
+    )
+    .flatten()
+}
+
+impl Concrete {
+    /// Folds the vertices of the polytope, or returns `None` in the case of
+    /// the nullitope.
+    pub fn fold_vertices<T, F: FnMut(T, &Point<f64>) -> T>(&self, init: T, mut f: F) -> Option<T> {
+        fold_points(&self.vertices, init, |acc, _, p| f(acc, p))
+    }
+
+    /// Returns the index of the vertex of the polytope that lies furthest in a
+    /// given direction, along with its dot product with the direction, or
+    /// returns `None` in the case of the nullitope. This is the support
+    /// function of the convex hull of the polytope.
+    pub fn support(&self, direction: &Vector<f64>) -> Option<(usize, f64)> {
+        max_point_by(&self.vertices, |v| v.dot(direction))
+    }
+
+    /// Returns the index of the vertex of the polytope furthest from a given
+    /// point, along with its distance to it, or returns `None` in the case of
+    /// the nullitope.
+    pub fn farthest_from(&self, p: &Point<f64>) -> Option<(usize, f64)> {
+        max_point_by(&self.vertices, |v| v.metric_distance(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, float::Float, Polytope};
+
+    use approx::abs_diff_eq;
+
+    /// The support function and the farthest vertex of a cube.
+    #[test]
+    fn cube() {
+        let cube = Concrete::hypercube(4);
+
+        let (idx, value) = cube
+            .support(&Vector::from_vec(vec![1.0, 2.0, 3.0]))
+            .unwrap();
+        assert_eq!(cube.vertices[idx], Point::from_element(3, 0.5));
+        assert_eq!(value, 3.0);
+
+        let corner = Point::from_vec(vec![-0.5, -0.5, -0.5]);
+        let (idx, dist) = cube.farthest_from(&corner).unwrap();
+        assert_eq!(cube.vertices[idx], Point::from_element(3, 0.5));
+        assert!(abs_diff_eq!(dist, 3f64.sqrt(), epsilon = f64::EPS));
+
+        assert_eq!(cube.fold_vertices(0, |n, _| n + 1), Some(8));
+    }
+
+    /// Every reduction returns `None` for the nullitope.
+    #[test]
+    fn nullitope() {
+        let nullitope = Concrete::nullitope();
+        let origin = Point::zeros(0);
+
+        assert_eq!(nullitope.fold_vertices(0, |n, _| n + 1), None);
+        assert_eq!(nullitope.support(&origin), None);
+        assert_eq!(nullitope.farthest_from(&origin), None);
+        assert_eq!(nullitope.bounding_box(), None);
+        assert_eq!(nullitope.minmax(origin), None);
+        assert_eq!(nullitope.gravicenter(), None);
+    }
+
+    /// The support function agrees with the greatest distance along random
+    /// directions, on polytopes of various shapes and dimensions, and the
+    /// least distance is the support function of the opposite direction.
+    #[test]
+    fn support_minmax() {
+        // A linear congruential generator, so that the test is deterministic.
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64 - 0.5
+        };
+
+        let polytopes = vec![
+            Concrete::polygon(7),
+            Concrete::star_polygon(7, 3),
+            Concrete::hypercube(5),
+            Concrete::orthoplex(4),
+            Concrete::simplex(6),
+            Concrete::polygon(5).duoprism(&Concrete::polygon(4)),
+            Concrete::hypercube(4).try_antiprism_auto().unwrap().0,
+        ];
+
+        for poly in polytopes {
+            let dim = poly.dim_or();
+
+            for _ in 0..20 {
+                let direction = Vector::from_fn(dim, |_, _| random()).normalize();
+                let (min, max) = poly.minmax(direction.clone()).unwrap();
+                let (idx, value) = poly.support(&direction).unwrap();
+
+                assert_eq!(value, max);
+                assert_eq!(poly.vertices[idx].dot(&direction), max);
+                assert_eq!(-poly.support(&-&direction).unwrap().1, min);
+                assert!(poly.vertices.iter().all(|v| v.dot(&direction) <= value));
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! A registry of the file formats that polytopes can be loaded from or saved
+//! to. Supporting a new format only requires implementing [`FileFormat`] for
+//! it and adding it to [`FORMATS`].
+
+use std::{
+    fs::{self, File},
+    io::ErrorKind,
+    path::Path,
+};
+
+use super::{
+    off::OffOptions, types::sidecar_path, FileError, FileResult, FromFile, IoError,
+};
+use crate::conc::Concrete;
+
+/// The options used when saving a polytope, for every format that takes any.
+#[derive(Clone, Copy, Default)]
+pub struct SaveOptions {
+    /// The options for OFF files.
+    pub off: OffOptions,
+
+    /// Whether to also save the element types into a
+    /// [sidecar file](super::types::sidecar_path).
+    pub types: bool,
+}
+
+/// A file format that polytopes can be loaded from, saved to, or both.
+pub trait FileFormat: Sync {
+    /// The name of the format, as shown in file dialogs.
+    fn name(&self) -> &'static str;
+
+    /// The extensions of the files in this format, in lowercase and without
+    /// the leading dot.
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Whether polytopes can be loaded from this format.
+    fn can_load(&self) -> bool {
+        true
+    }
+
+    /// Whether polytopes can be saved in this format.
+    fn can_save(&self) -> bool {
+        true
+    }
+
+    /// Loads a polytope from a file in this format.
+    fn load(&self, path: &Path) -> Result<Concrete, IoError>;
+
+    /// Saves a polytope into a file in this format.
+    fn save(&self, path: &Path, poly: &Concrete, options: &SaveOptions) -> Result<(), IoError>;
+}
+
+/// Wraps an error into an [`IoError`] about invalid data.
+fn invalid_data<E: std::error::Error + Send + Sync + 'static>(err: E) -> IoError {
+    IoError::new(ErrorKind::InvalidData, err)
+}
+
+/// The OFF format.
+#[derive(Clone, Copy)]
+pub struct Off;
+
+impl FileFormat for Off {
+    fn name(&self) -> &'static str {
+        "OFF File"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["off"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Concrete, IoError> {
+        let src = String::from_utf8(fs::read(path)?).map_err(invalid_data)?;
+        Concrete::from_off(&src).map_err(invalid_data)
+    }
+
+    fn save(&self, path: &Path, poly: &Concrete, options: &SaveOptions) -> Result<(), IoError> {
+        fs::write(path, poly.to_off(options.off).map_err(invalid_data)?)
+    }
+}
+
+/// The GeoGebra format. Polytopes can't be saved in it.
+#[derive(Clone, Copy)]
+pub struct Ggb;
+
+impl FileFormat for Ggb {
+    fn name(&self) -> &'static str {
+        "GeoGebra File"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ggb"]
+    }
+
+    fn can_save(&self) -> bool {
+        false
+    }
+
+    fn load(&self, path: &Path) -> Result<Concrete, IoError> {
+        Concrete::from_ggb(File::open(path)?).map_err(invalid_data)
+    }
+
+    fn save(&self, _: &Path, _: &Concrete, _: &SaveOptions) -> Result<(), IoError> {
+        Err(IoError::other("polytopes can't be saved as GGB files"))
+    }
+}
+
+/// The `.ine` format of cdd, which holds the
+/// [half-space representation](Concrete::facet_hyperplanes) of a convex
+/// polytope.
+#[derive(Clone, Copy)]
+pub struct Ine;
+
+impl FileFormat for Ine {
+    fn name(&self) -> &'static str {
+        "cdd H-representation"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ine"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Concrete, IoError> {
+        let src = String::from_utf8(fs::read(path)?).map_err(invalid_data)?;
+        Concrete::from_ine(&src).map_err(invalid_data)
+    }
+
+    fn save(&self, path: &Path, poly: &Concrete, _: &SaveOptions) -> Result<(), IoError> {
+        fs::write(path, poly.to_ine().map_err(invalid_data)?)
+    }
+}
+
+/// The half-space representation of a convex polytope as CSV, with a row per
+/// facet.
+#[derive(Clone, Copy)]
+pub struct HalfspaceCsv;
+
+impl FileFormat for HalfspaceCsv {
+    fn name(&self) -> &'static str {
+        "Half-space CSV"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["csv"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Concrete, IoError> {
+        let src = String::from_utf8(fs::read(path)?).map_err(invalid_data)?;
+        Concrete::from_halfspace_csv(&src).map_err(invalid_data)
+    }
+
+    fn save(&self, path: &Path, poly: &Concrete, _: &SaveOptions) -> Result<(), IoError> {
+        fs::write(path, poly.to_halfspace_csv().map_err(invalid_data)?)
+    }
+}
+
+/// The `.node` and `.ele` files of TetGen, which hold the
+/// [tetrahedra](Concrete::simplicial_decomposition) that decompose a
+/// polyhedron. Saving into either of them writes both. Polytopes can't be
+/// loaded from them.
+#[derive(Clone, Copy)]
+pub struct TetGen;
+
+impl FileFormat for TetGen {
+    fn name(&self) -> &'static str {
+        "TetGen mesh"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ele", "node"]
+    }
+
+    fn can_load(&self) -> bool {
+        false
+    }
+
+    fn load(&self, _: &Path) -> Result<Concrete, IoError> {
+        Err(IoError::other("polytopes can't be loaded from TetGen files"))
+    }
+
+    fn save(&self, path: &Path, poly: &Concrete, _: &SaveOptions) -> Result<(), IoError> {
+        let complex = poly.simplicial_decomposition().map_err(invalid_data)?;
+        fs::write(path.with_extension("node"), complex.to_tetgen_node())?;
+        fs::write(path.with_extension("ele"), complex.to_tetgen_ele())
+    }
+}
+
+/// Every supported file format.
+pub static FORMATS: &[&dyn FileFormat] = &[&Off, &Ggb, &Ine, &HalfspaceCsv, &TetGen];
+
+/// Returns the format with a given extension, ignoring case.
+pub fn format_for_extension(ext: &str) -> Option<&'static dyn FileFormat> {
+    let ext = ext.to_lowercase();
+    FORMATS
+        .iter()
+        .copied()
+        .find(|format| format.extensions().contains(&ext.as_str()))
+}
+
+/// Returns the format of a file path, as determined by its extension.
+pub fn format_for_path<P: AsRef<Path> + ?Sized>(path: &P) -> FileResult<'_, &'static dyn FileFormat> {
+    let ext = path
+        .as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+
+    format_for_extension(ext).ok_or(FileError::InvalidExtension(ext))
+}
+
+impl Concrete {
+    /// Saves a polytope into a file path, in the format given by its
+    /// extension.
+    pub fn save_to_path<'a, P: AsRef<Path> + ?Sized>(
+        &self,
+        path: &'a P,
+        options: &SaveOptions,
+    ) -> FileResult<'a, ()> {
+        format_for_path(path)?.save(path.as_ref(), self, options)?;
+
+        if options.types {
+            self.export_types(&sidecar_path(path))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    /// Checks that every extension is dispatched to the right format.
+    #[test]
+    fn dispatch() {
+        fn name(path: &str) -> FileResult<'_, &'static str> {
+            format_for_path(path).map(|format| format.name())
+        }
+
+        assert_eq!(name("tet.off").unwrap(), "OFF File");
+        assert_eq!(name("dir.ggb/TET.OFF").unwrap(), "OFF File");
+        assert_eq!(name("construction.GgB").unwrap(), "GeoGebra File");
+        assert_eq!(name("cube.ine").unwrap(), "cdd H-representation");
+        assert_eq!(name("cube.csv").unwrap(), "Half-space CSV");
+        assert_eq!(name("cube.ele").unwrap(), "TetGen mesh");
+        assert_eq!(name("cube.NODE").unwrap(), "TetGen mesh");
+
+        assert!(matches!(name("tet"), Err(FileError::InvalidExtension(""))));
+        assert!(matches!(name("tet.xyz"), Err(FileError::InvalidExtension("xyz"))));
+        assert_eq!(
+            name("tet.xyz").unwrap_err().to_string(),
+            "unknown extension \".xyz\""
+        );
+    }
+
+    /// Saves and loads an OFF file through the registry.
+    #[test]
+    fn roundtrip() {
+        let path = std::env::temp_dir().join(format!("miratope-format-{}.OFF", std::process::id()));
+
+        Concrete::cube()
+            .save_to_path(&path, &Default::default())
+            .unwrap();
+        test(&Concrete::from_path(&path).unwrap(), [1, 8, 12, 6, 1]);
+        fs::remove_file(&path).unwrap();
+
+        assert!(Concrete::cube()
+            .save_to_path("cube.ggb", &Default::default())
+            .is_err());
+    }
+
+    /// Saving a TetGen mesh writes both of its files, but it can't be loaded.
+    #[test]
+    fn tetgen() {
+        let path = std::env::temp_dir().join(format!("miratope-format-{}.ele", std::process::id()));
+        let node = path.with_extension("node");
+
+        Concrete::cube()
+            .save_to_path(&path, &Default::default())
+            .unwrap();
+        assert!(fs::read_to_string(&path).unwrap().starts_with("24 4 0\n"));
+        assert!(fs::read_to_string(&node).unwrap().starts_with("15 3 0 0\n"));
+        assert!(Concrete::from_path(&node).is_err());
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&node).unwrap();
+    }
+}
@@ -0,0 +1,364 @@
+//! Reading and writing the [half-space representation](crate::conc::halfspace)
+//! of convex polytopes, both in the `.ine` format of cdd and lrs, and as plain
+//! CSV with a row per facet.
+//!
+//! An `.ine` file stores every half-space `n · x ≤ b` as the row `b -n`,
+//! meaning that `b - n · x ≥ 0`. A CSV file stores it as the row `n, b`.
+
+use std::fmt::{Display, Write};
+
+use crate::conc::{
+    halfspace::{Halfspace, HalfspaceError},
+    Concrete,
+};
+use crate::geometry::Vector;
+
+/// An error while reading half-spaces from a file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HalfspaceParseError {
+    /// The `.ine` file has no `begin` line.
+    MissingBegin,
+
+    /// The `.ine` file holds a V-representation rather than an
+    /// H-representation.
+    VRepresentation,
+
+    /// The option on a given line isn't supported.
+    Unsupported(usize),
+
+    /// The size line of the `.ine` file, on a given line, is malformed.
+    InvalidHeader(usize),
+
+    /// A value on a given line isn't a number.
+    InvalidNumber(usize),
+
+    /// The row on a given line has the wrong number of values.
+    WrongLength(usize),
+
+    /// The number of rows doesn't match the one in the size line.
+    RowCount {
+        /// The number of rows in the size line.
+        expected: usize,
+
+        /// The number of rows found.
+        found: usize,
+    },
+
+    /// The half-spaces don't make up a polytope.
+    Halfspace(HalfspaceError),
+}
+
+impl Display for HalfspaceParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingBegin => write!(f, "the file has no \"begin\" line"),
+            Self::VRepresentation => {
+                write!(f, "the file holds vertices rather than half-spaces")
+            }
+            Self::Unsupported(line) => write!(f, "unsupported option at line {}", line),
+            Self::InvalidHeader(line) => write!(f, "invalid size line at line {}", line),
+            Self::InvalidNumber(line) => write!(f, "invalid number at line {}", line),
+            Self::WrongLength(line) => write!(f, "wrong number of values at line {}", line),
+            Self::RowCount { expected, found } => {
+                write!(f, "expected {} rows, found {}", expected, found)
+            }
+            Self::Halfspace(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for HalfspaceParseError {}
+
+impl From<HalfspaceError> for HalfspaceParseError {
+    fn from(err: HalfspaceError) -> Self {
+        Self::Halfspace(err)
+    }
+}
+
+/// Writes a number, without a sign if it's zero.
+fn write_number(s: &mut String, x: f64) {
+    write!(s, "{}", x + 0.0).unwrap();
+}
+
+/// Parses a number, which may also be written as a fraction like `1/2`.
+fn parse_number(token: &str, line: usize) -> Result<f64, HalfspaceParseError> {
+    let err = |_| HalfspaceParseError::InvalidNumber(line);
+
+    match token.split_once('/') {
+        Some((num, den)) => Ok(num.parse::<f64>().map_err(err)? / den.parse::<f64>().map_err(err)?),
+        None => token.parse().map_err(err),
+    }
+}
+
+/// Returns the lines of a file along with their numbers, trimmed, skipping
+/// empty lines and lines starting with a given comment character.
+fn content_lines(src: &str, comment: char) -> impl Iterator<Item = (usize, &str)> {
+    src.lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(move |(_, line)| !line.is_empty() && !line.starts_with(comment))
+}
+
+/// Writes a list of half-spaces in the `.ine` format.
+pub fn write_ine(halfspaces: &[Halfspace]) -> String {
+    let dim = halfspaces.first().map_or(0, |(normal, _)| normal.len());
+    let mut ine = String::from("H-representation\nbegin\n");
+    writeln!(ine, "{} {} real", halfspaces.len(), dim + 1).unwrap();
+
+    for (normal, offset) in halfspaces {
+        write_number(&mut ine, *offset);
+        for x in normal.iter() {
+            ine.push(' ');
+            write_number(&mut ine, -x);
+        }
+        ine.push('\n');
+    }
+
+    ine.push_str("end\n");
+    ine
+}
+
+/// Reads a list of half-spaces in the `.ine` format. Comments, the name of
+/// the polytope and any options after the `end` line are skipped.
+pub fn parse_ine(src: &str) -> Result<Vec<Halfspace>, HalfspaceParseError> {
+    let mut lines = content_lines(src, '*');
+
+    loop {
+        let (idx, line) = lines.next().ok_or(HalfspaceParseError::MissingBegin)?;
+        match line {
+            "begin" => break,
+            "V-representation" => return Err(HalfspaceParseError::VRepresentation),
+            _ if line.starts_with("linearity") => {
+                return Err(HalfspaceParseError::Unsupported(idx))
+            }
+            _ => {}
+        }
+    }
+
+    let (idx, header) = lines.next().ok_or(HalfspaceParseError::MissingBegin)?;
+    let header: Vec<_> = header.split_whitespace().collect();
+    let (rows, columns) = match header[..] {
+        [rows, columns, "real" | "integer" | "rational"] => (
+            rows.parse::<usize>()
+                .map_err(|_| HalfspaceParseError::InvalidHeader(idx))?,
+            columns
+                .parse::<usize>()
+                .map_err(|_| HalfspaceParseError::InvalidHeader(idx))?,
+        ),
+        _ => return Err(HalfspaceParseError::InvalidHeader(idx)),
+    };
+
+    // The header can't be trusted to reserve memory.
+    let mut halfspaces = Vec::new();
+    for (idx, line) in lines.take_while(|&(_, line)| line != "end") {
+        let row = line
+            .split_whitespace()
+            .map(|token| parse_number(token, idx))
+            .collect::<Result<Vec<_>, _>>()?;
+        if row.len() != columns {
+            return Err(HalfspaceParseError::WrongLength(idx));
+        }
+
+        let normal = Vector::from_iterator(columns - 1, row[1..].iter().map(|x| -x));
+        halfspaces.push((normal, row[0]));
+    }
+
+    if halfspaces.len() != rows {
+        return Err(HalfspaceParseError::RowCount {
+            expected: rows,
+            found: halfspaces.len(),
+        });
+    }
+
+    Ok(halfspaces)
+}
+
+/// Writes a list of half-spaces as CSV, after a header naming the columns.
+pub fn write_halfspace_csv(halfspaces: &[Halfspace]) -> String {
+    let dim = halfspaces.first().map_or(0, |(normal, _)| normal.len());
+    let mut csv = String::new();
+    for i in 1..=dim {
+        write!(csv, "n{},", i).unwrap();
+    }
+    csv.push_str("b\n");
+
+    for (normal, offset) in halfspaces {
+        for &x in normal.iter() {
+            write_number(&mut csv, x);
+            csv.push(',');
+        }
+        write_number(&mut csv, *offset);
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Reads a list of half-spaces from CSV. The first line is skipped if it
+/// isn't made of numbers, since it's then a header.
+pub fn parse_halfspace_csv(src: &str) -> Result<Vec<Halfspace>, HalfspaceParseError> {
+    let mut halfspaces: Vec<Halfspace> = Vec::new();
+    let mut columns = None;
+
+    for (n, (idx, line)) in content_lines(src, '#').enumerate() {
+        let row = line
+            .split(',')
+            .map(|token| parse_number(token.trim(), idx))
+            .collect::<Result<Vec<_>, _>>();
+        let row = match row {
+            Ok(row) => row,
+            Err(_) if n == 0 => continue,
+            Err(err) => return Err(err),
+        };
+
+        if row.len() < 2 || *columns.get_or_insert(row.len()) != row.len() {
+            return Err(HalfspaceParseError::WrongLength(idx));
+        }
+
+        let (offset, normal) = row.split_last().unwrap();
+        halfspaces.push((Vector::from_column_slice(normal), *offset));
+    }
+
+    Ok(halfspaces)
+}
+
+impl Concrete {
+    /// Writes the [half-space representation](Self::facet_hyperplanes) of a
+    /// convex polytope in the `.ine` format.
+    pub fn to_ine(&self) -> Result<String, HalfspaceError> {
+        Ok(write_ine(&self.facet_hyperplanes()?))
+    }
+
+    /// Builds the convex polytope bounded by the half-spaces in an `.ine`
+    /// file.
+    pub fn from_ine(src: &str) -> Result<Self, HalfspaceParseError> {
+        Ok(Self::from_halfspaces(&parse_ine(src)?)?)
+    }
+
+    /// Writes the [half-space representation](Self::facet_hyperplanes) of a
+    /// convex polytope as CSV.
+    pub fn to_halfspace_csv(&self) -> Result<String, HalfspaceError> {
+        Ok(write_halfspace_csv(&self.facet_hyperplanes()?))
+    }
+
+    /// Builds the convex polytope bounded by the half-spaces in a CSV file.
+    pub fn from_halfspace_csv(src: &str) -> Result<Self, HalfspaceParseError> {
+        Ok(Self::from_halfspaces(&parse_halfspace_csv(src)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, test, Polytope};
+
+    /// The half-spaces of a square, as written by cdd.
+    const SQUARE: &str = "* a square
+H-representation
+begin
+ 4 3 rational
+ 1/2 -1 0
+ 1/2 1 0
+ 1/2 0 -1
+ 1/2 0 1
+end
+minimize
+ 0 1 1
+";
+
+    /// A cube is written with a row per facet, and read back.
+    #[test]
+    fn ine() {
+        let cube = Concrete::cube();
+        let ine = cube.to_ine().unwrap();
+        assert!(ine.starts_with("H-representation\nbegin\n6 4 real\n"));
+        assert!(ine.contains("\n0.5 -1 0 0\n"));
+        assert!(ine.contains("\n0.5 0 0 1\n"));
+        assert!(ine.ends_with("end\n"));
+
+        test(&Concrete::from_ine(&ine).unwrap(), [1, 8, 12, 6, 1]);
+        test(&Concrete::from_ine(SQUARE).unwrap(), [1, 4, 4, 1]);
+    }
+
+    /// The 5-cell survives a round trip through both formats.
+    #[test]
+    fn pentachoron() {
+        let simplex = Concrete::simplex(5);
+
+        let csv = simplex.to_halfspace_csv().unwrap();
+        assert!(csv.starts_with("n1,n2,n3,n4,b\n"));
+        assert_eq!(csv.lines().count(), 6);
+        test(
+            &Concrete::from_halfspace_csv(&csv).unwrap(),
+            [1, 5, 10, 10, 5, 1],
+        );
+
+        let ine = simplex.to_ine().unwrap();
+        test(&Concrete::from_ine(&ine).unwrap(), [1, 5, 10, 10, 5, 1]);
+
+        // Numbers are written exactly.
+        let halfspaces = simplex.facet_hyperplanes().unwrap();
+        assert_eq!(parse_ine(&write_ine(&halfspaces)).unwrap(), halfspaces);
+        assert_eq!(
+            parse_halfspace_csv(&write_halfspace_csv(&halfspaces)).unwrap(),
+            halfspaces
+        );
+    }
+
+    /// Malformed files are rejected with the line at fault.
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_ine("H-representation\n"),
+            Err(HalfspaceParseError::MissingBegin)
+        );
+        assert_eq!(
+            parse_ine("V-representation\nbegin\n"),
+            Err(HalfspaceParseError::VRepresentation)
+        );
+        assert_eq!(
+            parse_ine("linearity 1 1\nbegin\n"),
+            Err(HalfspaceParseError::Unsupported(1))
+        );
+        assert_eq!(
+            parse_ine("begin\n4 3 complex\nend\n"),
+            Err(HalfspaceParseError::InvalidHeader(2))
+        );
+        assert_eq!(
+            parse_ine(&SQUARE.replace("1/2 0 1", "1/2 0")),
+            Err(HalfspaceParseError::WrongLength(8))
+        );
+        assert_eq!(
+            parse_ine(&SQUARE.replace("1/2 0 1", "1/2 0 x")),
+            Err(HalfspaceParseError::InvalidNumber(8))
+        );
+        assert_eq!(
+            parse_ine(&SQUARE.replace(" 4 3", " 5 3")),
+            Err(HalfspaceParseError::RowCount {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(
+            parse_ine(&SQUARE.replace(" 4 3", " 99999999999999999 3")),
+            Err(HalfspaceParseError::RowCount {
+                expected: 99_999_999_999_999_999,
+                found: 4
+            })
+        );
+
+        assert_eq!(
+            parse_halfspace_csv("n1,n2,b\n1,0,1\n1,1\n"),
+            Err(HalfspaceParseError::WrongLength(3))
+        );
+        assert_eq!(
+            parse_halfspace_csv("1,0,1\n1,a,1\n"),
+            Err(HalfspaceParseError::InvalidNumber(2))
+        );
+        assert!(matches!(
+            Concrete::from_halfspace_csv("1,0,1\n0,1,1\n"),
+            Err(HalfspaceParseError::Halfspace(HalfspaceError::NotBounded))
+        ));
+        assert!(Concrete::star_polygon(5, 2).to_ine().is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Exports the Hasse diagram of the face lattice of a polytope, so that it can
+//! be studied in external graph tools.
+//!
+//! Each element becomes a node with id `e{rank}_{idx}`, where the rank is the
+//! internal one, and each element gets an edge to each of its subelements.
+
+use std::fmt::Write;
+
+use crate::abs::{Abstract, ElementMap, Ranked};
+
+use vec_like::*;
+
+/// The formats into which the face lattice of a polytope can be exported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatticeFormat {
+    /// The DOT format used by Graphviz.
+    Dot,
+
+    /// The GraphML format, read by Gephi, yEd and most other graph tools.
+    GraphMl,
+}
+
+impl LatticeFormat {
+    /// Every lattice format, in the order in which they're shown.
+    pub const ALL: [Self; 2] = [Self::Dot, Self::GraphMl];
+
+    /// The name of the format.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dot => "DOT",
+            Self::GraphMl => "GraphML",
+        }
+    }
+
+    /// The extension of the files in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::GraphMl => "graphml",
+        }
+    }
+}
+
+/// Whether to export the elements below or above a given element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Closure {
+    /// The element and all of its recursive subelements.
+    Down,
+
+    /// The element and all of its recursive superelements.
+    Up,
+}
+
+/// The options that restrict the elements exported from the face lattice.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatticeOptions<'a> {
+    /// The least and greatest (internal) ranks of the exported elements, if
+    /// only some of them are to be exported.
+    pub ranks: Option<(usize, usize)>,
+
+    /// The rank and index of an element, if only the elements below or above
+    /// it are to be exported.
+    pub element: Option<(usize, usize, Closure)>,
+
+    /// The type of every element, if it's to be written along with it.
+    pub types: Option<&'a ElementMap<usize>>,
+}
+
+impl Abstract {
+    /// Returns which elements of the polytope are exported with the given
+    /// options.
+    fn lattice_mask(&self, options: &LatticeOptions<'_>) -> Vec<Vec<bool>> {
+        let (lo, hi) = options.ranks.unwrap_or((0, self.rank()));
+        let mut mask: Vec<Vec<bool>> = (0..=self.rank())
+            .map(|r| vec![options.element.is_none() && lo <= r && r <= hi; self.el_count(r)])
+            .collect();
+
+        // Walks the lattice from the element, one rank at a time.
+        if let Some((rank, idx, closure)) = options.element {
+            if rank > self.rank() || idx >= self.el_count(rank) {
+                return mask;
+            }
+
+            let mut reached = vec![idx];
+            let mut r = rank;
+            loop {
+                if lo <= r && r <= hi {
+                    for &i in &reached {
+                        mask[r][i] = true;
+                    }
+                }
+
+                let next_rank = match closure {
+                    Closure::Down if r > 0 => r - 1,
+                    Closure::Up if r < self.rank() => r + 1,
+                    _ => break,
+                };
+
+                let mut next = vec![false; self.el_count(next_rank)];
+                for &i in &reached {
+                    let el = &self[(r, i)];
+                    let adjacent = match closure {
+                        Closure::Down => el.subs.iter(),
+                        Closure::Up => el.sups.iter(),
+                    };
+                    for &j in adjacent {
+                        next[j] = true;
+                    }
+                }
+
+                reached = (0..next.len()).filter(|&j| next[j]).collect();
+                r = next_rank;
+            }
+        }
+
+        mask
+    }
+
+    /// Returns the number of nodes in the face lattice exported with the given
+    /// options.
+    pub fn lattice_size(&self, options: &LatticeOptions<'_>) -> usize {
+        self.lattice_mask(options)
+            .iter()
+            .map(|rank| rank.iter().filter(|&&b| b).count())
+            .sum()
+    }
+
+    /// Writes the Hasse diagram of the face lattice in DOT format. Elements of
+    /// the same rank are drawn on the same row, and are labeled by their rank
+    /// and index, and their type if given.
+    pub fn to_dot(&self, options: &LatticeOptions<'_>) -> String {
+        let mask = self.lattice_mask(options);
+        let mut dot = String::from("digraph lattice {\n    node [shape=box];\n");
+
+        for (r, rank) in mask.iter().enumerate() {
+            if !rank.contains(&true) {
+                continue;
+            }
+
+            writeln!(dot, "    subgraph rank{} {{\n        rank=same;", r).unwrap();
+            for idx in (0..rank.len()).filter(|&idx| rank[idx]) {
+                write!(dot, "        e{}_{} [label=\"{} #{}", r, idx, r as isize - 1, idx).unwrap();
+                if let Some(types) = options.types {
+                    write!(dot, " (type {})", types[(r, idx)]).unwrap();
+                }
+                dot.push_str("\"];\n");
+            }
+            dot.push_str("    }\n");
+        }
+
+        for (r, idx, el) in self.element_iter() {
+            if r == 0 || !mask[r][idx] {
+                continue;
+            }
+
+            for &sub in el.subs.iter().filter(|&&sub| mask[r - 1][sub]) {
+                writeln!(dot, "    e{}_{} -> e{}_{};", r, idx, r - 1, sub).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the Hasse diagram of the face lattice in GraphML format. Every
+    /// node stores the rank and index of its element, and its type if given.
+    pub fn to_graphml(&self, options: &LatticeOptions<'_>) -> String {
+        let mask = self.lattice_mask(options);
+        let mut graphml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"rank\" for=\"node\" attr.name=\"rank\" attr.type=\"int\"/>\n",
+            "  <key id=\"index\" for=\"node\" attr.name=\"index\" attr.type=\"int\"/>\n",
+        ));
+        if options.types.is_some() {
+            graphml.push_str("  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"int\"/>\n");
+        }
+        graphml.push_str("  <graph id=\"lattice\" edgedefault=\"directed\">\n");
+
+        for (r, idx, _) in self.element_iter().filter(|&(r, idx, _)| mask[r][idx]) {
+            write!(
+                graphml,
+                "    <node id=\"e{}_{}\"><data key=\"rank\">{}</data><data key=\"index\">{}</data>",
+                r,
+                idx,
+                r as isize - 1,
+                idx
+            )
+            .unwrap();
+            if let Some(types) = options.types {
+                write!(graphml, "<data key=\"type\">{}</data>", types[(r, idx)]).unwrap();
+            }
+            graphml.push_str("</node>\n");
+        }
+
+        for (r, idx, el) in self.element_iter() {
+            if r == 0 || !mask[r][idx] {
+                continue;
+            }
+
+            for &sub in el.subs.iter().filter(|&&sub| mask[r - 1][sub]) {
+                writeln!(
+                    graphml,
+                    "    <edge source=\"e{}_{}\" target=\"e{}_{}\"/>",
+                    r,
+                    idx,
+                    r - 1,
+                    sub
+                )
+                .unwrap();
+            }
+        }
+
+        graphml.push_str("  </graph>\n</graphml>\n");
+        graphml
+    }
+
+    /// Writes the Hasse diagram of the face lattice in the given format.
+    pub fn to_lattice(&self, format: LatticeFormat, options: &LatticeOptions<'_>) -> String {
+        match format {
+            LatticeFormat::Dot => self.to_dot(options),
+            LatticeFormat::GraphMl => self.to_graphml(options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    use std::collections::HashSet;
+
+    /// The nodes and edges of a lattice read back from its DOT file.
+    type Graph = (HashSet<String>, HashSet<(String, String)>);
+
+    /// Reads back the nodes and edges from a DOT file written by
+    /// [`Abstract::to_dot`].
+    fn parse_dot(dot: &str) -> Graph {
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
+
+        for line in dot.lines().map(str::trim) {
+            if let Some((from, to)) = line.split_once(" -> ") {
+                let to = to.trim_end_matches(';');
+                assert!(nodes.contains(from) && nodes.contains(to), "{}", line);
+                assert!(edges.insert((from.to_string(), to.to_string())), "{}", line);
+            } else if let Some((node, _)) = line.split_once(" [label=") {
+                assert!(nodes.insert(node.to_string()), "{}", line);
+            }
+        }
+
+        (nodes, edges)
+    }
+
+    /// Returns the number of nodes and edges exported from a polytope.
+    fn size(poly: &Abstract, options: &LatticeOptions<'_>) -> (usize, usize) {
+        let (nodes, edges) = parse_dot(&poly.to_dot(options));
+        assert_eq!(nodes.len(), poly.lattice_size(options));
+        assert_eq!(
+            poly.to_graphml(options).matches("<node ").count(),
+            nodes.len()
+        );
+        assert_eq!(
+            poly.to_graphml(options).matches("<edge ").count(),
+            edges.len()
+        );
+
+        (nodes.len(), edges.len())
+    }
+
+    /// The square has 10 elements, and every edge of its lattice goes from an
+    /// element to one of its subelements.
+    #[test]
+    fn square() {
+        let square = Abstract::polygon(4);
+        let (nodes, edges) = parse_dot(&square.to_dot(&Default::default()));
+        assert_eq!(nodes.len(), 10);
+        assert_eq!(edges.len(), 16);
+
+        for (r, idx, el) in square.element_iter().skip(1) {
+            for &sub in &el.subs {
+                let edge = (format!("e{}_{}", r, idx), format!("e{}_{}", r - 1, sub));
+                assert!(edges.contains(&edge), "missing edge {:?}", edge);
+            }
+        }
+    }
+
+    /// Restricting the ranks of the tesseract.
+    #[test]
+    fn tesseract_ranks() {
+        let tesseract = Abstract::hypercube(5);
+        let ranks = |lo, hi| LatticeOptions {
+            ranks: Some((lo, hi)),
+            ..Default::default()
+        };
+
+        assert_eq!(size(&tesseract, &Default::default()), (82, 8 + 6 * 8 + 4 * 24 + 2 * 32 + 16));
+        assert_eq!(size(&tesseract, &ranks(1, 2)), (48, 64));
+        assert_eq!(size(&tesseract, &ranks(2, 3)), (56, 96));
+        assert_eq!(size(&tesseract, &ranks(3, 3)), (24, 0));
+    }
+
+    /// The elements below a cell and above a vertex of the tesseract.
+    #[test]
+    fn tesseract_closure() {
+        let tesseract = Abstract::hypercube(5);
+        let closure = |rank, closure| LatticeOptions {
+            element: Some((rank, 0, closure)),
+            ..Default::default()
+        };
+
+        assert_eq!(size(&tesseract, &closure(4, Closure::Down)), (28, 8 + 2 * 12 + 4 * 6 + 6));
+        assert_eq!(size(&tesseract, &closure(1, Closure::Up)), (16, 4 + 2 * 6 + 3 * 4 + 4));
+
+        let options = LatticeOptions {
+            ranks: Some((1, 2)),
+            ..closure(4, Closure::Down)
+        };
+        assert_eq!(size(&tesseract, &options), (20, 24));
+    }
+
+    /// Element types are written along with the elements.
+    #[test]
+    fn types() {
+        let square = Abstract::polygon(4);
+        let types: ElementMap<usize> = (0..=square.rank())
+            .map(|r| vec![r; square.el_count(r)])
+            .collect::<Vec<_>>()
+            .into();
+        let options = LatticeOptions {
+            types: Some(&types),
+            ..Default::default()
+        };
+
+        assert!(square.to_dot(&options).contains("e2_3 [label=\"1 #3 (type 2)\"];"));
+        assert!(square
+            .to_graphml(&options)
+            .contains("<data key=\"type\">3</data>"));
+    }
+}
@@ -1,13 +1,23 @@
 //! Reading from and writing to files in various different formats.
 
+pub mod format;
 pub mod ggb;
+pub mod halfspace;
+pub mod lattice;
 pub mod off;
+pub mod tetgen;
+pub mod text;
+pub mod types;
 
 use self::{
     ggb::{GgbError, GgbResult},
-    off::{OffParseResult, OffReader},
+    off::{OffOptions, OffParseResult, OffReader},
+};
+use crate::{
+    abs::Ranked,
+    conc::{units::UnitInfo, Concrete, ConcretePolytope},
+    Polytope,
 };
-use crate::conc::Concrete;
 
 use off::OffParseError;
 use zip::result::ZipError;
@@ -46,7 +56,8 @@ impl<'a> Display for FileError<'a> {
             Self::IoError(err) => write!(f, "IO error: {}", err),
             Self::ZipError(err) => write!(f, "ZIP error while opening GGB: {}", err),
             Self::InvalidFile(err) => write!(f, "invalid file: {}", err),
-            Self::InvalidExtension(ext) => write!(f, "invalid file extension \"{}\"", ext),
+            Self::InvalidExtension("") => write!(f, "file has no extension"),
+            Self::InvalidExtension(ext) => write!(f, "unknown extension \".{}\"", ext),
         }
     }
 }
@@ -91,6 +102,68 @@ impl<'a> From<ZipError> for FileError<'a> {
 /// The result of loading a polytope from a file.
 pub type FileResult<'a, T> = Result<T, FileError<'a>>;
 
+/// A note on a loaded polytope whose vertices don't span the space they live
+/// in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HullNote {
+    /// The rank of the affine hull of the vertices.
+    pub rank: usize,
+
+    /// The dimension of the space the vertices live in.
+    pub dim: usize,
+
+    /// Whether the polytope was flattened into its affine hull.
+    pub flattened: bool,
+}
+
+impl Display for HullNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "loaded polytope spans only {} of {} dimensions",
+            self.rank, self.dim
+        )?;
+
+        if self.flattened {
+            write!(f, ", flattened it")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks whether a loaded polytope spans the space it lives in, up to the
+/// [tolerance](OffOptions::hull_tolerance) in the options. If it only spans a
+/// hyperplane and [auto-flattening](OffOptions::auto_flatten) is enabled, the
+/// polytope is flattened into it, and if it has a single facet, like a face
+/// stored as a polyhedron, it's replaced by that facet. Polytopes that span
+/// even fewer dimensions are left as they are, since they're most likely
+/// malformed.
+pub fn check_affine_hull(poly: &mut Concrete, options: &OffOptions) -> Option<HullNote> {
+    let subspace = poly.affine_hull_within(options.hull_tolerance)?;
+    let (rank, dim) = (subspace.rank(), subspace.dim());
+    if rank == dim {
+        return None;
+    }
+
+    let flattened = options.auto_flatten && rank + 1 == dim;
+    if flattened {
+        poly.flatten_into(&subspace);
+
+        if poly.rank() > 2 && poly.facet_count() == 1 {
+            let units = poly.units.take();
+            *poly = poly.facet(0).unwrap();
+            poly.units = units;
+        }
+    }
+
+    Some(HullNote {
+        rank,
+        dim,
+        flattened,
+    })
+}
+
 /// A trait for polytopes that can be read from an OFF file or a GGB file.
 pub trait FromFile: Sized {
     /// Converts an OFF file into a new struct of type `Self`.
@@ -99,39 +172,56 @@ pub trait FromFile: Sized {
     /// Maybe don't load the entire file at once?
     fn from_off(src: &str) -> OffParseResult<Self>;
 
+    /// Converts an OFF file into a new struct of type `Self`, then
+    /// [checks](check_affine_hull) that it spans the space it lives in,
+    /// printing a note if it doesn't.
+    fn from_off_with(src: &str, options: OffOptions) -> OffParseResult<Self>;
+
     /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
     /// 3D.
     fn from_ggb(file: File) -> GgbResult<Self>;
 
-    /// Loads a polytope from a file path.
+    /// Loads a polytope from a file path, in the [format](format::FileFormat)
+    /// given by its extension, with the default [`OffOptions`].
     fn from_path<U: AsRef<std::path::Path>>(fp: &U) -> FileResult<'_, Self> {
-        use std::{ffi::OsStr, fs};
-
-        let ext = fp
-            .as_ref()
-            .extension()
-            .and_then(OsStr::to_str)
-            .unwrap_or_default();
-
-        match ext {
-            // Reads the file as an OFF file.
-            "off" => match String::from_utf8(fs::read(fp)?) {
-                Ok(src) => Ok(Self::from_off(&src)?),
-                Err(err) => Err(err.into()),
-            },
-
-            // Reads the file as a GGB file.
-            "ggb" => Ok(Self::from_ggb(fs::File::open(fp)?)?),
-
-            // Could not recognize the file extension.
-            ext => Err(FileError::InvalidExtension(ext)),
-        }
+        Self::from_path_with(fp, OffOptions::default())
     }
+
+    /// Loads a polytope from a file path, in the [format](format::FileFormat)
+    /// given by its extension, then [checks](check_affine_hull) that it spans
+    /// the space it lives in, printing a note if it doesn't.
+    fn from_path_with<U: AsRef<std::path::Path>>(
+        fp: &U,
+        options: OffOptions,
+    ) -> FileResult<'_, Self>;
 }
 
 impl FromFile for Concrete {
     fn from_off(src: &str) -> OffParseResult<Self> {
-        OffReader::new(src).build()
+        let mut poly = OffReader::new(src).build()?;
+        poly.units = UnitInfo::find_in(src);
+        Ok(poly)
+    }
+
+    fn from_off_with(src: &str, options: OffOptions) -> OffParseResult<Self> {
+        let mut poly = Self::from_off(src)?;
+        if let Some(note) = check_affine_hull(&mut poly, &options) {
+            println!("Note: {}.", note);
+        }
+
+        Ok(poly)
+    }
+
+    fn from_path_with<U: AsRef<std::path::Path>>(
+        fp: &U,
+        options: OffOptions,
+    ) -> FileResult<'_, Self> {
+        let mut poly = format::format_for_path(fp)?.load(fp.as_ref())?;
+        if let Some(note) = check_affine_hull(&mut poly, &options) {
+            println!("Note: {}.", note);
+        }
+
+        Ok(poly)
     }
 
     /// Attempts to read a GGB file. If succesful, outputs a polytope in at most
@@ -164,6 +254,16 @@ pub struct Position {
 }
 
 impl Position {
+    /// Returns the row index, starting from 0.
+    pub fn row(&self) -> usize {
+        self.row as usize
+    }
+
+    /// Returns the column index, starting from 0.
+    pub fn column(&self) -> usize {
+        self.column as usize
+    }
+
     /// Increments the column number by 1.
     pub fn next(&mut self) {
         self.column += 1;
@@ -7,7 +7,8 @@ use super::Position;
 
 use crate::{
     abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
-    conc::{cycle::CycleList, Concrete, element_types::EL_NAMES},
+    conc::{cycle::CycleList, Concrete, ConcreteError, element_types::element_name},
+    float::Float,
     geometry::Point,
     Polytope, COMPONENTS
 };
@@ -38,6 +39,31 @@ pub enum OffParseError {
 
     /// Didn't find the OFF magic word.
     MagicWord(Position),
+
+    /// The vertices don't fit the polytope.
+    Concrete(ConcreteError),
+
+    /// A block of the OFF file after the first one ended before its polytope
+    /// did.
+    PartialBlock {
+        /// The number of the block, starting from 1.
+        block: usize,
+
+        /// The position where the file ended.
+        pos: Position,
+    },
+
+    /// A face has no vertices.
+    EmptyFace(Position),
+
+    /// A block of the OFF file has a different rank than the first one.
+    BlockRank {
+        /// The number of the block, starting from 1.
+        block: usize,
+
+        /// The position of the header of the block.
+        pos: Position,
+    },
 }
 
 impl Display for OffParseError {
@@ -48,6 +74,16 @@ impl Display for OffParseError {
             Self::Parsing(pos) => write!(f, "could not parse number at {}", pos),
             Self::Rank(pos) => write!(f, "could not read rank at {}", pos),
             Self::MagicWord(pos) => write!(f, "no \"OFF\" detected at {}", pos),
+            Self::Concrete(err) => write!(f, "invalid vertices: {}", err),
+            Self::PartialBlock { block, pos } => {
+                write!(f, "block {} ended unexpectedly at {}", block, pos)
+            }
+            Self::EmptyFace(pos) => write!(f, "face with no vertices at {}", pos),
+            Self::BlockRank { block, pos } => write!(
+                f,
+                "block {} at {} has a different rank than the first one",
+                block, pos
+            ),
         }
     }
 }
@@ -57,14 +93,6 @@ impl std::error::Error for OffParseError {}
 /// The result of parsing an OFF file.
 pub type OffParseResult<T> = Result<T, OffParseError>;
 
-/// Gets the name for an element with a given rank.
-fn element_name(rank: usize) -> String {
-    match EL_NAMES.get(rank) {
-        Some(&name) => String::from(name),
-        None => rank.to_string() + "-elements",
-    }
-}
-
 /// The result of trying to read the next token from an OFF file.
 enum OffNext<'a> {
     /// We've read a token from the OFF file. We don't directly store a
@@ -214,14 +242,95 @@ impl<'a> Iterator for TokenIter<'a> {
     }
 }
 
+/// Gets the edges and faces of a polytope of a given rank from the vertex
+/// cycles of its faces. In the case of polygons, the cycles are taken to be its
+/// components, and a single face is returned.
+pub(crate) fn edges_and_faces<I: IntoIterator<Item = Vec<usize>>>(
+    rank: usize,
+    face_cycles: I,
+) -> (SubelementList, SubelementList) {
+    let mut edges = SubelementList::new();
+    let mut faces = SubelementList::new();
+    let mut hash_edges = HashMap::new();
+
+    // The last face that used each edge, so that repeated edges are found
+    // without searching through the face.
+    let mut last_face = Vec::new();
+
+    // Add each face to the element list.
+    for (face_idx, mut face_verts) in face_cycles.into_iter().enumerate() {
+        let face_sub_num = face_verts.len();
+        let mut face = Subelements::new();
+
+        // Consecutive repeated vertices would give degenerate edges.
+        face_verts.dedup();
+        if face_verts.len() > 1 && face_verts.first() == face_verts.last() {
+            face_verts.pop();
+        }
+        if face_verts.len() != face_sub_num {
+            println!(
+                "WARNING: Face {} repeats consecutive vertices, the repeats were removed!",
+                face_idx
+            );
+        }
+
+        // We add the first vertex to the end for simplicity.
+        let face_sub_num = face_verts.len();
+        face_verts.push(face_verts[0]);
+
+        // Gets all edges of the face.
+        for i in 0..face_sub_num {
+            let mut v0 = face_verts[i];
+            let mut v1 = face_verts[i + 1];
+
+            if v0 > v1 {
+                std::mem::swap(&mut v0, &mut v1);
+            }
+
+            let edge: Subelements = vec![v0, v1].into();
+
+            if let Some(&idx) = hash_edges.get(&edge) {
+                // Edges traversed twice by the same face are only added once.
+                if last_face[idx] == face_idx {
+                    println!(
+                        "WARNING: Face {} repeats the edge {:?}, the repeat was removed!",
+                        face_idx, edge
+                    );
+                } else {
+                    last_face[idx] = face_idx;
+                    face.push(idx);
+                }
+            } else {
+                hash_edges.insert(edge.clone(), edges.len());
+                last_face.push(face_idx);
+                face.push(edges.len());
+                edges.push(edge);
+            }
+        }
+
+        // If these are truly faces and not just components, we add them.
+        if rank != 3 {
+            faces.push(face);
+        }
+    }
+
+    // If this is a polygon, we add a single maximal element as a face.
+    if rank == 3 {
+        faces = SubelementList::max(edges.len());
+    }
+
+    (edges, faces)
+}
+
 /// An auxiliary struct that reads through an OFF file and builds a concrete
 /// polytope out of it.
+///
+/// Some files store the components of a compound as consecutive blocks, each
+/// with its own header. These are read one by one, and put together into a
+/// single compound polytope.
 pub struct OffReader<'a> {
     /// An iterator over the tokens of the OFF file.
     iter: TokenIter<'a>,
-
-    /// The underlying abstract polytope.
-    abs: AbstractBuilder,
 }
 
 impl<'a> OffReader<'a> {
@@ -229,7 +338,6 @@ impl<'a> OffReader<'a> {
     pub fn new(src: &'a str) -> Self {
         Self {
             iter: TokenIter::new(src),
-            abs: AbstractBuilder::new(),
         }
     }
 
@@ -307,49 +415,27 @@ impl<'a> OffReader<'a> {
         num_edges: usize,
         num_faces: usize,
     ) -> OffParseResult<(SubelementList, SubelementList)> {
-        let mut edges = SubelementList::with_capacity(num_edges);
-        let mut faces = SubelementList::with_capacity(num_faces);
-        let mut hash_edges = HashMap::new();
+        let mut face_cycles = Vec::with_capacity(num_faces);
 
-        // Add each face to the element list.
+        // Reads the vertices of each face.
         for _ in 0..num_faces {
-            let face_sub_num = self.iter.parse_next()?;
-            let mut face = Subelements::new();
+            let token = self
+                .iter
+                .next()
+                .ok_or(OffParseError::UnexpectedEnding(self.iter.position))?;
+            let pos = token.pos;
+            let face_sub_num: usize = token.parse()?;
+            if face_sub_num == 0 {
+                return Err(OffParseError::EmptyFace(pos));
+            }
+
             let mut face_verts = Vec::with_capacity(face_sub_num + 1);
 
-            // Reads all vertices of the face.
             for _ in 0..face_sub_num {
                 face_verts.push(self.iter.parse_next()?);
             }
 
-            // We add the first vertex to the end for simplicity.
-            face_verts.push(face_verts[0]);
-
-            // Gets all edges of the face.
-            for i in 0..face_sub_num {
-                let mut v0 = face_verts[i];
-                let mut v1 = face_verts[i + 1];
-
-                if v0 > v1 {
-                    std::mem::swap(&mut v0, &mut v1);
-                }
-
-                let edge: Subelements = vec![v0, v1].into();
-
-                if let Some(idx) = hash_edges.get(&edge) {
-                    face.push(*idx);
-                } else {
-                    hash_edges.insert(edge.clone(), edges.len());
-                    face.push(edges.len());
-                    edges.push(edge);
-                }
-            }
-
-            // If these are truly faces and not just components, we add them.
-            // Hopefully the compiler can optimize this better, I'm lazy.
-            if rank != 3 {
-                faces.push(face);
-            }
+            face_cycles.push(face_verts);
 
             // Goes to the end of the line in order to ignore things like colour info.
             if self.iter.position.column != 0 {
@@ -357,10 +443,7 @@ impl<'a> OffReader<'a> {
             }
         }
 
-        // If this is a polygon, we add a single maximal element as a face.
-        if rank == 3 {
-            faces = SubelementList::max(edges.len());
-        }
+        let (edges, faces) = edges_and_faces(rank, face_cycles);
 
         // The number of edges in the file should match the number of read
         // edges, though this isn't obligatory.
@@ -374,15 +457,26 @@ impl<'a> OffReader<'a> {
     /// Parses the next set of d-elements from the OFF file.
     fn parse_els(&mut self, num_el: usize) -> OffParseResult<SubelementList> {
         let mut els_subs = SubelementList::with_capacity(num_el);
+        let mut seen = HashSet::new();
 
         // Adds every d-element to the element list.
         for _ in 0..num_el {
             let el_sub_num = self.iter.parse_next()?;
             let mut subs = Subelements::with_capacity(el_sub_num);
+            seen.clear();
 
             // Reads all sub-elements of the d-element.
             for _ in 0..el_sub_num {
-                subs.push(self.iter.parse_next()?);
+                let sub = self.iter.parse_next()?;
+
+                if !seen.insert(sub) {
+                    println!(
+                        "WARNING: An element lists the subelement {} twice, the repeat was removed!",
+                        sub
+                    );
+                } else {
+                    subs.push(sub);
+                }
             }
 
             els_subs.push(subs);
@@ -406,11 +500,9 @@ impl<'a> OffReader<'a> {
             .flatten()
     }*/
 
-    /// Builds a concrete polytope from the OFF reader.
-    pub fn build(mut self) -> OffParseResult<Concrete> {
-        // Reads the rank of the polytope.
-        let rank = self.rank()?;
-
+    /// Reads a single block of the OFF file, whose header has already been
+    /// read, into a concrete polytope.
+    fn parse_block(&mut self, rank: usize) -> OffParseResult<Concrete> {
         // Deals with dumb degenerate cases.
         match rank {
             0 => return Ok(Concrete::nullitope()),
@@ -423,35 +515,76 @@ impl<'a> OffReader<'a> {
         let vertices = self.parse_vertices(num_elems[0], rank - 1)?;
 
         // Adds nullitope and vertices.
-        self.abs.reserve(rank + 2);
-        self.abs.push_min();
-        self.abs.push_vertices(vertices.len());
+        let mut abs = AbstractBuilder::new();
+        abs.reserve(rank + 2);
+        abs.push_min();
+        abs.push_vertices(vertices.len());
 
         // Reads edges and faces.
         if rank >= 3 {
             let (edges, faces) = self.parse_edges_and_faces(rank, num_elems[1], num_elems[2])?;
-            self.abs.push(edges);
-            self.abs.push(faces);
+            abs.push(edges);
+            abs.push(faces);
         }
 
         // Adds all higher elements.
         for &num_el in num_elems.iter().take(rank - 1).skip(3) {
             let subelements = self.parse_els(num_el)?;
-            self.abs.push(subelements);
+            abs.push(subelements);
         }
 
         // Caps the abstract polytope.
         if rank != 3 {
-            self.abs.push_max();
+            abs.push_max();
         }
 
         // Builds the concrete polytope.
 
         // Safety: TODO this isn't actually safe. We need to do some checking.
-        Ok(Concrete::new(vertices, unsafe { self.abs.build() }))
+        Concrete::try_new(vertices, unsafe { abs.build() }).map_err(OffParseError::Concrete)
+    }
+
+    /// Builds a concrete polytope from the OFF reader.
+    pub fn build(mut self) -> OffParseResult<Concrete> {
+        // Reads the rank of the polytope and its first block.
+        let rank = self.rank()?;
+        let mut poly = self.parse_block(rank)?;
+
+        // Any further blocks are components of a compound. Anything else after
+        // the first block is ignored, like it always has been.
+        let mut block = 1;
+        while let Some(token) = self.next() {
+            if rank < 2 || !token.slice.ends_with("OFF") {
+                break;
+            }
+
+            block += 1;
+            if token.rank()? != rank {
+                return Err(OffParseError::BlockRank {
+                    block,
+                    pos: token.pos,
+                });
+            }
+
+            let component = self.parse_block(rank).map_err(|err| match err {
+                OffParseError::UnexpectedEnding(pos) => OffParseError::PartialBlock { block, pos },
+                err => err,
+            })?;
+            poly.comp_append(component);
+        }
+
+        Ok(poly)
     }
 }
 
+/// Returns the number of blocks in an OFF file, each of which is read as a
+/// component of a compound. See [`OffReader`].
+pub fn block_count(src: &str) -> usize {
+    TokenIter::new(src)
+        .filter(|token| token.slice.ends_with("OFF"))
+        .count()
+}
+
 /*
 impl Concrete {
     /// Gets the name from the first line of an OFF file.
@@ -480,16 +613,39 @@ impl Concrete {
     }
 }*/
 
-/// A set of options to be used when saving the OFF file.
+/// A set of options to be used when loading or saving OFF files.
 #[derive(Clone, Copy)]
 pub struct OffOptions {
     /// Whether the OFF file should have comments specifying each face type.
     pub comments: bool,
+
+    /// Whether a loaded polytope whose vertices only span a hyperplane of the
+    /// space they live in should be flattened into it.
+    pub auto_flatten: bool,
+
+    /// The distance below which a loaded vertex is considered to lie on the
+    /// subspace spanned by the others, relative to the diagonal of the
+    /// bounding box of the polytope.
+    pub hull_tolerance: f64,
+
+    /// Whether each component of a compound should be written as its own
+    /// block of the OFF file.
+    pub split_components: bool,
+
+    /// Whether the units of the polytope, if known, should be written as a
+    /// comment. They're written even if other comments aren't.
+    pub units: bool,
 }
 
 impl Default for OffOptions {
     fn default() -> Self {
-        OffOptions { comments: true }
+        OffOptions {
+            comments: true,
+            auto_flatten: true,
+            hull_tolerance: f64::EPS,
+            split_components: false,
+            units: true,
+        }
     }
 }
 
@@ -641,14 +797,14 @@ impl<'a> OffWriter<'a> {
 
                 for r in 4..rank {
                     self.push_str(", ");
-                    self.push_str(element_name(r));
+                    self.push_str(element_name(r as isize - 1));
                 }
             }
 
             self.push('\n');
         }
 
-        self.push_to_str(self.el_count(1));
+        self.push_to_str(self.poly.vertex_count());
 
         match rank {
             2 => {}
@@ -661,11 +817,11 @@ impl<'a> OffWriter<'a> {
             _ => {
                 // Swaps edges and faces because OFF format bad.
                 self.push(' ');
-                self.push_to_str(self.el_count(3));
+                self.push_to_str(self.poly.face_count());
 
                 let coincident_edges = self.check_edges();
                 self.push(' ');
-                self.push_to_str(self.el_count(2) - coincident_edges);
+                self.push_to_str(self.poly.edge_count() - coincident_edges);
                 if coincident_edges > 0 {
                     println!("Warning: Polytope contains coincident edges. They will be merged in the OFF file.");
                 }
@@ -685,7 +841,7 @@ impl<'a> OffWriter<'a> {
         // # Vertices
         if self.comments() {
             self.push_str("\n# ");
-            self.push_str(element_name(1));
+            self.push_str(element_name(0));
             self.push('\n');
         }
 
@@ -750,7 +906,7 @@ impl<'a> OffWriter<'a> {
         // # n-elements
         if self.comments() {
             self.push_str("\n# ");
-            self.push_str(element_name(rank));
+            self.push_str(element_name(rank as isize - 1));
             self.push('\n');
         }
 
@@ -768,29 +924,16 @@ impl<'a> OffWriter<'a> {
         }
     }
 
-    /// Consumes the OFF writer, returns the actual OFF file as a `String`.
-    pub fn build(mut self) -> OffWriteResult<String> {
+    /// Writes the polytope into the OFF file as a single block.
+    fn write_block(&mut self) -> OffWriteResult<()> {
         let rank = self.poly.rank();
 
-        // Serialized name.
-        /* self.off.push_str("# ");
-        self.off
-            .push_str(&ron::to_string(&self.polytope.name).unwrap_or_default());
-        self.off.push('\n'); */
-
-        // Blatant advertising.
-        if self.comments() {
-            self.push_str("# ");
-            self.push_str(HEADER);
-            self.push('\n');
-        }
-
         // Writes header.
         self.write_rank();
 
         // If we have a nullitope or point on our hands, that is all.
         if rank < 2 {
-            return Ok(self.off);
+            return Ok(());
         }
 
         // Adds the element counts.
@@ -809,6 +952,51 @@ impl<'a> OffWriter<'a> {
             self.write_els(r);
         }
 
+        Ok(())
+    }
+
+    /// Consumes the OFF writer, returns the actual OFF file as a `String`.
+    pub fn build(mut self) -> OffWriteResult<String> {
+        // Serialized name.
+        /* self.off.push_str("# ");
+        self.off
+            .push_str(&ron::to_string(&self.polytope.name).unwrap_or_default());
+        self.off.push('\n'); */
+
+        // Blatant advertising.
+        if self.comments() {
+            self.push_str("# ");
+            self.push_str(HEADER);
+            self.push('\n');
+        }
+
+        // The units of the coordinates.
+        if self.options.units {
+            if let Some(units) = &self.poly.units {
+                self.push_str(format!("# {}\n", units.to_comment()));
+            }
+        }
+
+        // Writes every component of a compound as its own block.
+        if self.options.split_components {
+            let components = self.poly.split();
+
+            if components.len() > 1 {
+                for (idx, component) in components.iter().enumerate() {
+                    let mut writer = OffWriter::new(component, self.options);
+                    if self.comments() {
+                        writer.push_str(format!("\n# Component {}\n", idx + 1));
+                    }
+
+                    writer.write_block()?;
+                    self.push_str(writer.off);
+                }
+
+                return Ok(self.off);
+            }
+        }
+
+        self.write_block()?;
         Ok(self.off)
     }
 }
@@ -870,6 +1058,7 @@ impl Concrete {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conc::ConcretePolytope;
     use crate::file::FromFile;
     use crate::test;
 
@@ -930,6 +1119,27 @@ mod tests {
         test_off!("so", [1, 8, 12, 8, 1])
     }
 
+    /// Checks that a dodecahedron has the correct amount of elements.
+    #[test]
+    fn doe_nums() {
+        test_off!("doe", [1, 20, 30, 12, 1])
+    }
+
+    /// Checks that the faces of a dodecahedron can be colored with at most 4
+    /// colors, so that no two adjacent faces share a color.
+    #[test]
+    fn doe_coloring() {
+        let doe = Concrete::from_off(include_str!("doe.off")).unwrap();
+        let colors = doe.abs.greedy_face_coloring();
+        assert_eq!(colors.len(), 12);
+        assert!(colors.iter().all(|&c| c < 4));
+
+        for (idx, list) in doe.abs.adjacency(3).iter().enumerate() {
+            assert_eq!(list.len(), 5);
+            assert!(list.iter().all(|&n| colors[n] != colors[idx]));
+        }
+    }
+
     /// Checks that a pentachoron has the correct amount of elements.
     #[test]
     fn pen_nums() {
@@ -942,6 +1152,33 @@ mod tests {
         test_off!("comments", [1, 4, 6, 4, 1])
     }
 
+    /// Checks that repeated vertices and edges in a face are removed.
+    #[test]
+    fn duplicate_subs() {
+        let tet = Concrete::from_off(
+            "OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+            4 0 1 1 2\n4 3 0 2 3\n3 0 1 3\n6 3 1 2 3 1 2",
+        )
+        .unwrap();
+
+        test(&tet, [1, 4, 6, 4, 1]);
+        assert!(tet.is_equilateral());
+    }
+
+    /// Faces with more vertices than fit in 16 bits are saved and loaded
+    /// without taking quadratic time.
+    #[test]
+    fn huge_face() {
+        let n = 100_000;
+        let prism = Concrete::polygon(n).prism();
+        let off = prism.to_off(Default::default()).unwrap();
+
+        let loaded = Concrete::from_off(&off).unwrap();
+        let el_counts: Vec<_> = loaded.abs.el_count_iter().collect();
+        assert_eq!(el_counts, vec![1, 2 * n, 3 * n, n + 2, 1]);
+        assert_eq!(loaded.validate(), Ok(()));
+    }
+
     /// Attempts to parse an OFF file, unwraps it.
     fn unwrap_off(src: &str) {
         Concrete::from_off(src).unwrap();
@@ -974,4 +1211,118 @@ mod tests {
     fn parse() {
         unwrap_off("OFF\n10 foo bar")
     }
+
+    /// A file with a non-finite coordinate should fail.
+    #[test]
+    #[should_panic(expected = "Concrete(NonFinite { idx: 1 })")]
+    fn non_finite() {
+        unwrap_off("1OFF\n2\n-1\ninf")
+    }
+
+    /// Two tetrahedra, stored as two blocks of an OFF file.
+    const TWO_BLOCKS: &str = "OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+        3 0 1 2\n3 3 0 2\n3 0 1 3\n3 3 1 2\n\
+        OFF\n4 4 6\n-1 -1 -1\n-1 1 1\n1 -1 1\n1 1 -1\n\
+        3 0 1 2\n3 3 0 2\n3 0 1 3\n3 3 1 2";
+
+    /// Every block of an OFF file becomes a component of a compound, which
+    /// can be written back as separate blocks.
+    #[test]
+    fn blocks() {
+        let so = Concrete::from_off(TWO_BLOCKS).unwrap();
+        test(&so, [1, 8, 12, 8, 1]);
+        assert_eq!(so.split().len(), 2);
+        assert_eq!(block_count(TWO_BLOCKS), 2);
+
+        let split_options = OffOptions {
+            split_components: true,
+            ..Default::default()
+        };
+        let off = so.to_off(split_options).unwrap();
+        assert_eq!(block_count(&off), 2);
+        assert_eq!(block_count(&so.to_off(Default::default()).unwrap()), 1);
+
+        let reloaded = Concrete::from_off(&off).unwrap();
+        test(&reloaded, [1, 8, 12, 8, 1]);
+        assert_eq!(reloaded.split().len(), 2);
+    }
+
+    /// Files with a single block are read and written as before.
+    #[test]
+    fn single_block() {
+        let tet = Concrete::from_off(include_str!("tet.off")).unwrap();
+        assert_eq!(block_count(include_str!("tet.off")), 1);
+
+        let split_options = OffOptions {
+            split_components: true,
+            ..Default::default()
+        };
+        let off = tet.to_off(split_options).unwrap();
+        assert_eq!(block_count(&off), 1);
+        test(&Concrete::from_off(&off).unwrap(), [1, 4, 6, 4, 1]);
+    }
+
+    /// A block that ends early should fail.
+    #[test]
+    #[should_panic(expected = "PartialBlock { block: 2, pos: Position { row: 6, column: 2 } }")]
+    fn partial_block() {
+        unwrap_off("1OFF\n2\n-1\n1\n1OFF\n2\n-1")
+    }
+
+    /// A face without vertices should fail, rather than panic.
+    #[test]
+    #[should_panic(expected = "EmptyFace(Position { row: 5, column: 0 })")]
+    fn empty_face() {
+        unwrap_off("OFF\n3 2 3\n0 0 0\n1 0 0\n0 1 0\n0\n3 0 1 2")
+    }
+
+    /// A block with a different rank than the first should fail.
+    #[test]
+    #[should_panic(expected = "BlockRank { block: 2, pos: Position { row: 4, column: 0 } }")]
+    fn block_rank() {
+        unwrap_off("1OFF\n2\n-1\n1\n2OFF\n3 1\n0 0\n1 0\n0 1\n3 0 1 2")
+    }
+
+    /// A square stored as a polyhedron in 3D is loaded as a polygon in 2D,
+    /// unless told otherwise.
+    #[test]
+    fn auto_flatten() {
+        const SQUARE: &str = "3OFF\n4 1 4\n0 0 0\n1 0 0\n1 1 0\n0 1 0\n4 0 1 2 3";
+        let load = |auto_flatten| {
+            Concrete::from_off_with(
+                SQUARE,
+                OffOptions {
+                    auto_flatten,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let flat = load(true);
+        test(&flat, [1, 4, 4, 1]);
+        assert_eq!(flat.dim(), Some(2));
+        assert!(flat.full_dimensional());
+
+        let raw = load(false);
+        test(&raw, [1, 4, 4, 1, 1]);
+        assert_eq!(raw.dim(), Some(3));
+    }
+
+    /// Thin and tiny polytopes aren't mistaken for flat ones.
+    #[test]
+    fn thin_not_flattened() {
+        for scale in [[1.0, 1.0, 1e-4], [1e-9; 3]] {
+            let mut cube = Concrete::cube();
+            for v in cube.vertices_mut() {
+                for (x, k) in v.iter_mut().zip(scale) {
+                    *x *= k;
+                }
+            }
+
+            let off = cube.to_off(Default::default()).unwrap();
+            let poly = Concrete::from_off_with(&off, Default::default()).unwrap();
+            assert_eq!(poly.dim(), Some(3));
+        }
+    }
 }
@@ -0,0 +1,69 @@
+//! Writing the [tetrahedra](crate::conc::simplicial) that decompose a
+//! polyhedron in the format of TetGen, as a `.node` file with the coordinates
+//! of the vertices and an `.ele` file with the vertices of each tetrahedron.
+//! Both files number their entries starting from 1.
+
+use std::fmt::Write;
+
+use crate::conc::simplicial::SimplicialComplex;
+
+impl SimplicialComplex {
+    /// Writes the vertices in the `.node` format of TetGen.
+    pub fn to_tetgen_node(&self) -> String {
+        let dim = self.vertices.first().map_or(0, |v| v.len());
+        let mut node = format!("{} {} 0 0\n", self.vertices.len(), dim);
+
+        for (idx, v) in self.vertices.iter().enumerate() {
+            write!(node, "{}", idx + 1).unwrap();
+            for x in v.iter() {
+                write!(node, " {}", x + 0.0).unwrap();
+            }
+            node.push('\n');
+        }
+
+        node
+    }
+
+    /// Writes the simplices in the `.ele` format of TetGen.
+    pub fn to_tetgen_ele(&self) -> String {
+        let len = self.simplices.first().map_or(0, Vec::len);
+        let mut ele = format!("{} {} 0\n", self.len(), len);
+
+        for (idx, simplex) in self.simplices.iter().enumerate() {
+            write!(ele, "{}", idx + 1).unwrap();
+            for v in simplex {
+                write!(ele, " {}", v + 1).unwrap();
+            }
+            ele.push('\n');
+        }
+
+        ele
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{conc::Concrete, Polytope};
+
+    /// The tetrahedron is written as itself, coned to its gravicenter.
+    #[test]
+    fn tetrahedron() {
+        let complex = Concrete::simplex(4).simplicial_decomposition().unwrap();
+        let node = complex.to_tetgen_node();
+        let ele = complex.to_tetgen_ele();
+
+        let mut lines = node.lines();
+        assert_eq!(lines.next(), Some("5 3 0 0"));
+        assert_eq!(lines.count(), 5);
+        assert!(node.lines().last().unwrap().starts_with("5 "));
+
+        let mut lines = ele.lines();
+        assert_eq!(lines.next(), Some("4 4 0"));
+        for (idx, line) in lines.enumerate() {
+            let entries: Vec<usize> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+            assert_eq!(entries[0], idx + 1);
+            assert_eq!(entries[1], 5);
+            assert!(entries[2..].iter().all(|&v| (1..=4).contains(&v)));
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Builds a polytope from a plain text list of vertices and a list of facets,
+//! as typed into the UI.
+//!
+//! Every non-empty line of the vertex list contains the coordinates of a
+//! vertex, separated by whitespace or commas. Every non-empty line of the facet
+//! list contains the indices of the vertices of a facet, in cyclic order. As in
+//! OFF files, anything after a `#` is a comment.
+
+use std::{fmt::Display, str::FromStr};
+
+use super::{off::edges_and_faces, Position};
+use crate::{
+    abs::{AbstractBuilder, AbstractError, Ranked},
+    conc::{Concrete, ConcreteError},
+    geometry::Point,
+    Polytope,
+};
+
+/// The text field in which an error was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextField {
+    /// The list of vertices.
+    Vertices,
+
+    /// The list of facets.
+    Facets,
+}
+
+impl Display for TextField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vertices => write!(f, "vertices"),
+            Self::Facets => write!(f, "facets"),
+        }
+    }
+}
+
+/// Any error encountered while parsing a vertex and a facet list.
+#[derive(Clone, Copy, Debug)]
+pub enum ParseError {
+    /// No vertices were given.
+    NoVertices,
+
+    /// Could not parse a number.
+    Parsing(TextField, Position),
+
+    /// A vertex doesn't have as many coordinates as the first one.
+    Dimension {
+        /// The position of the vertex.
+        pos: Position,
+
+        /// The number of coordinates of the first vertex.
+        expected: usize,
+
+        /// The number of coordinates of the vertex.
+        found: usize,
+    },
+
+    /// A facet refers to a vertex that doesn't exist.
+    VertexIndex(Position),
+
+    /// A facet doesn't have at least two distinct vertices.
+    FacetSize(Position),
+
+    /// Only polygons and polyhedra can be built from their facets.
+    UnsupportedDimension(usize),
+
+    /// The facets don't form a valid polytope.
+    Invalid(AbstractError),
+
+    /// The vertices don't fit the polytope.
+    Concrete(ConcreteError),
+}
+
+impl ParseError {
+    /// Returns the text field and the position at which the error was found,
+    /// if any.
+    pub fn position(&self) -> Option<(TextField, Position)> {
+        match *self {
+            Self::Parsing(field, pos) => Some((field, pos)),
+            Self::Dimension { pos, .. } => Some((TextField::Vertices, pos)),
+            Self::VertexIndex(pos) | Self::FacetSize(pos) => Some((TextField::Facets, pos)),
+            Self::NoVertices
+            | Self::UnsupportedDimension(_)
+            | Self::Invalid(_)
+            | Self::Concrete(_) => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoVertices => write!(f, "no vertices given"),
+            Self::Parsing(field, pos) => {
+                write!(f, "could not parse number in {} at {}", field, pos)
+            }
+            Self::Dimension {
+                pos,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} coordinates but found {} at {}",
+                expected, found, pos
+            ),
+            Self::VertexIndex(pos) => write!(f, "vertex index out of bounds at {}", pos),
+            Self::FacetSize(pos) => write!(f, "facet with fewer than 2 vertices at {}", pos),
+            Self::UnsupportedDimension(dim) => write!(
+                f,
+                "only polytopes in 2D or 3D can be built from their facets, found {}D",
+                dim
+            ),
+            Self::Invalid(err) => write!(f, "invalid polytope: {}", err),
+            Self::Concrete(err) => write!(f, "invalid vertices: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The result of parsing a vertex and a facet list.
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Parses every non-empty line of a text into a list of numbers. Returns the
+/// lists, together with the position at which each of them starts.
+fn parse_lines<T: FromStr>(text: &str, field: TextField) -> ParseResult<Vec<(Position, Vec<T>)>> {
+    let mut lines = Vec::new();
+
+    for (row, line) in text.lines().enumerate() {
+        // Anything after a # is a comment.
+        let line = line.split('#').next().unwrap();
+        let mut numbers = Vec::new();
+        let mut start = None;
+
+        for (column, token) in line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .scan(0, |column, token| {
+                let start = *column;
+                *column += token.chars().count() + 1;
+                Some((start, token))
+            })
+            .filter(|(_, token)| !token.is_empty())
+        {
+            let pos = Position {
+                row: row as u32,
+                column: column as u32,
+            };
+            start.get_or_insert(pos);
+
+            numbers.push(token.parse().map_err(|_| ParseError::Parsing(field, pos))?);
+        }
+
+        if let Some(start) = start {
+            lines.push((start, numbers));
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Returns the dimension of the space that a vertex list lives in, inferred
+/// from its first line, or `None` if it's empty or can't be parsed.
+pub fn infer_dim(vertices: &str) -> Option<usize> {
+    parse_lines::<f64>(vertices, TextField::Vertices)
+        .ok()?
+        .first()
+        .map(|(_, coords)| coords.len())
+}
+
+impl Concrete {
+    /// Builds a polygon or a polyhedron from a list of vertices and a list of
+    /// facets, in the format described in the [module
+    /// documentation](crate::file::text).
+    pub fn from_vertex_facet_text(vertices: &str, facets: &str) -> ParseResult<Self> {
+        let vertices = parse_lines::<f64>(vertices, TextField::Vertices)?;
+        let dim = vertices.first().ok_or(ParseError::NoVertices)?.1.len();
+
+        for (pos, coords) in &vertices {
+            if coords.len() != dim {
+                return Err(ParseError::Dimension {
+                    pos: *pos,
+                    expected: dim,
+                    found: coords.len(),
+                });
+            }
+        }
+
+        if dim != 2 && dim != 3 {
+            return Err(ParseError::UnsupportedDimension(dim));
+        }
+
+        let facets = parse_lines::<usize>(facets, TextField::Facets)?;
+        for (pos, facet) in &facets {
+            if facet.iter().any(|&v| v >= vertices.len()) {
+                return Err(ParseError::VertexIndex(*pos));
+            }
+
+            if facet.iter().all(|&v| v == facet[0]) {
+                return Err(ParseError::FacetSize(*pos));
+            }
+        }
+
+        // Builds the polytope just as if it were read from an OFF file.
+        let rank = dim + 1;
+        let (edges, faces) = edges_and_faces(rank, facets.into_iter().map(|(_, facet)| facet));
+
+        let mut abs = AbstractBuilder::with_rank_capacity(rank + 1);
+        abs.push_min();
+        abs.push_vertices(vertices.len());
+        abs.push(edges);
+        abs.push(faces);
+        if rank != 3 {
+            abs.push_max();
+        }
+
+        // Safety: the polytope is checked right after.
+        let poly = Concrete::try_new(
+            vertices.into_iter().map(|(_, v)| v.into()).collect::<Vec<Point<f64>>>(),
+            unsafe { abs.build() },
+        )
+        .map_err(ParseError::Concrete)?;
+        poly.abs().ranks().is_valid().map_err(ParseError::Invalid)?;
+
+        Ok(poly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, test};
+
+    /// Builds a square.
+    #[test]
+    fn square() {
+        let square = Concrete::from_vertex_facet_text(
+            "0 0\n1, 0\n\n1 1 # A comment\n0 1",
+            "0 1 2 3",
+        )
+        .unwrap();
+
+        test(&square, [1, 4, 4, 1]);
+        assert_eq!(square.dim(), Some(2));
+        assert_eq!(infer_dim("0 0 0\n1 1"), Some(3));
+    }
+
+    /// Builds a tetrahedron.
+    #[test]
+    fn tetrahedron() {
+        let tet = Concrete::from_vertex_facet_text(
+            "1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1",
+            "0 1 2\n0 1 3\n0 2 3\n1 2 3",
+        )
+        .unwrap();
+
+        test(&tet, [1, 4, 6, 4, 1]);
+    }
+
+    /// Checks the errors on malformed input.
+    #[test]
+    fn malformed() {
+        let err = Concrete::from_vertex_facet_text("0 0\n1 x", "0 1").unwrap_err();
+        let (field, pos) = err.position().unwrap();
+        assert_eq!(field, TextField::Vertices);
+        assert_eq!((pos.row(), pos.column()), (1, 2));
+
+        let err = Concrete::from_vertex_facet_text("0 0\n1 0 0", "").unwrap_err();
+        assert!(matches!(err, ParseError::Dimension { expected: 2, found: 3, .. }));
+
+        let err = Concrete::from_vertex_facet_text("0 0\n1 0\n0 1", "0 1 2\n\n0 1 3").unwrap_err();
+        assert!(matches!(err, ParseError::VertexIndex(pos) if pos.row() == 2));
+
+        let err = Concrete::from_vertex_facet_text("", "").unwrap_err();
+        assert!(matches!(err, ParseError::NoVertices));
+
+        let err = Concrete::from_vertex_facet_text("0 0 0 0", "0").unwrap_err();
+        assert!(matches!(err, ParseError::UnsupportedDimension(4)));
+
+        let err = Concrete::from_vertex_facet_text("0 0\n1 0", "1 1 1").unwrap_err();
+        assert!(matches!(err, ParseError::FacetSize(_)));
+
+        let err = Concrete::from_vertex_facet_text("0 0\n1 NaN\n0 1", "0 1 2").unwrap_err();
+        assert!(matches!(err, ParseError::Concrete(ConcreteError::NonFinite { idx: 1 })));
+        assert!(err.position().is_none());
+    }
+}
@@ -0,0 +1,196 @@
+//! Saves and loads the element types of a polytope into a sidecar file next to
+//! the polytope's own file, so that other tools can use them, and so that they
+//! stay the same across sessions.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::IoError;
+use crate::{
+    abs::Ranked,
+    conc::{element_types::ElementType, Concrete},
+};
+
+use serde::{Deserialize, Serialize};
+use vec_like::*;
+
+/// The extension of the sidecar files, which replaces that of the polytope's
+/// file.
+pub const SIDECAR_EXTENSION: &str = "types.ron";
+
+/// Returns the path of the sidecar file for a polytope saved into a given
+/// path.
+pub fn sidecar_path<P: AsRef<Path> + ?Sized>(path: &P) -> PathBuf {
+    path.as_ref().with_extension(SIDECAR_EXTENSION)
+}
+
+/// Describes a single type of elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeDescriptor {
+    /// The number of elements of this type.
+    pub count: usize,
+
+    /// The number of facets of each element of this type.
+    pub facets: usize,
+
+    /// The index of a representative of this type.
+    pub example: usize,
+}
+
+/// The element types of a polytope, as stored in a sidecar file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeTable {
+    /// For every rank, the index of the type of every element.
+    pub types: Vec<Vec<usize>>,
+
+    /// For every rank, the descriptor of every type.
+    pub descriptors: Vec<Vec<TypeDescriptor>>,
+}
+
+impl TypeTable {
+    /// Computes the element types of a polytope.
+    pub fn new(poly: &Concrete) -> Self {
+        let (types, type_of_element) = poly.element_types_common();
+
+        let descriptors = types
+            .into_iter()
+            .enumerate()
+            .map(|(r, types)| {
+                types
+                    .into_iter()
+                    .map(|t| TypeDescriptor {
+                        count: t.count,
+                        facets: poly[(r, t.example)].subs.len(),
+                        example: t.example,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            types: type_of_element.into_inner(),
+            descriptors,
+        }
+    }
+
+    /// Returns whether the table could belong to a given polytope, that is,
+    /// whether it has the right amount of elements of each rank, and whether
+    /// every type index and representative is in range.
+    pub fn matches(&self, poly: &Concrete) -> bool {
+        self.types.len() == poly.rank() + 1
+            && self.descriptors.len() == self.types.len()
+            && self
+                .types
+                .iter()
+                .zip(&self.descriptors)
+                .enumerate()
+                .all(|(r, (types, descriptors))| {
+                    types.len() == poly.el_count(r)
+                        && types
+                            .iter()
+                            .all(|&t| t < descriptors.len() || descriptors.is_empty())
+                        && descriptors.iter().all(|d| d.example < types.len())
+                })
+    }
+
+    /// Returns the types of every rank, in the form used by
+    /// [`Concrete::element_types`].
+    pub fn element_types(&self) -> Vec<Vec<ElementType>> {
+        self.descriptors
+            .iter()
+            .map(|descriptors| {
+                descriptors
+                    .iter()
+                    .map(|d| ElementType {
+                        example: d.example,
+                        count: d.count,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Concrete {
+    /// Computes the element types of the polytope, and writes them into a
+    /// sidecar file.
+    pub fn export_types<P: AsRef<Path> + ?Sized>(&self, path: &P) -> Result<(), IoError> {
+        let table = TypeTable::new(self);
+        let src = ron::to_string(&table).map_err(IoError::other)?;
+        fs::write(path, src)
+    }
+
+    /// Reads the element types of the polytope from a sidecar file. Returns
+    /// `None` if there's no such file. If the file can't be read, or doesn't
+    /// match the polytope, prints a warning and recomputes the types instead.
+    pub fn import_types<P: AsRef<Path> + ?Sized>(&self, path: &P) -> Option<TypeTable> {
+        let src = match fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(err) if err.kind() == ErrorKind::NotFound => return None,
+            Err(err) => {
+                println!("Couldn't read element types, recomputing them: {}", err);
+                return Some(TypeTable::new(self));
+            }
+        };
+
+        match ron::from_str::<TypeTable>(&src) {
+            Ok(table) if table.matches(self) => Some(table),
+            Ok(_) => {
+                println!("Element types don't match the polytope, recomputing them.");
+                Some(TypeTable::new(self))
+            }
+            Err(err) => {
+                println!("Couldn't parse element types, recomputing them: {}", err);
+                Some(TypeTable::new(self))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Returns a path in the temporary directory, unique to this process.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("miratope-types-{}-{}", std::process::id(), name))
+    }
+
+    /// Exports and imports the types of a prism.
+    #[test]
+    fn roundtrip() {
+        let prism = Concrete::polygon(5).prism();
+        let path = sidecar_path(&temp_path("prism.off"));
+        assert!(path.to_string_lossy().ends_with("prism.types.ron"));
+
+        assert_eq!(prism.import_types(&path), None);
+        prism.export_types(&path).unwrap();
+        let table = prism.import_types(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(table, TypeTable::new(&prism));
+        assert_eq!(table.element_types(), prism.element_types());
+
+        // The pentagonal prism has two types of faces.
+        assert_eq!(table.descriptors[3].len(), 2);
+        assert_eq!(table.descriptors[3][0].count + table.descriptors[3][1].count, 7);
+    }
+
+    /// Checks that types are recomputed when they don't match the polytope.
+    #[test]
+    fn mismatch() {
+        let path = temp_path("mismatch.types.ron");
+        Concrete::polygon(5).prism().export_types(&path).unwrap();
+
+        let cube = Concrete::cube();
+        let table = cube.import_types(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(table.matches(&cube));
+        assert_eq!(table, TypeTable::new(&cube));
+    }
+}
@@ -12,6 +12,11 @@ pub type VectorSlice<'a, T> = nalgebra::DVectorSlice<'a, T>;
 /// An *n* by *n* matrix.
 pub type Matrix<T> = nalgebra::DMatrix<T>;
 
+pub mod point_cloud;
+pub mod predicates;
+
+pub use point_cloud::PointCloud;
+
 use std::{
     borrow::Cow,
     ops::{Index, IndexMut},
@@ -94,12 +99,19 @@ impl<T: Float> Hypersphere<T> {
 
     /// Calculates the circumsphere of points. Returns `None` if the
     /// points aren't circumscribable.
-    pub fn circumsphere(points: &Vec<Point<T>>) -> Option<Hypersphere<T>> {
+    pub fn circumsphere(points: &[Point<T>]) -> Option<Hypersphere<T>> {
+        Self::try_circumsphere(points).ok()
+    }
+
+    /// Calculates the circumsphere of points. If the points aren't
+    /// circumscribable, returns the index of the first point that doesn't lie
+    /// on the sphere through the previous ones.
+    pub fn try_circumsphere(points: &[Point<T>]) -> Result<Hypersphere<T>, usize> {
         let first_point = points[0].clone();
         let mut center = first_point.clone();
         let mut subspace = Subspace::new(first_point.clone());
 
-        for point in points {
+        for (idx, point) in points.iter().enumerate() {
             // If the new point does not lie on the hyperplane of the others:
             if let Some(basis_vector) = subspace.add(point) {
                 // Calculates the new circumcenter.
@@ -116,15 +128,66 @@ impl<T: Float> Hypersphere<T> {
                 (&center - point).norm(),
                 epsilon = T::EPS
             ) {
-                return None;
+                return Err(idx);
             }
         }
 
-        Some(Hypersphere {
+        Ok(Hypersphere {
             squared_radius: (&center - first_point).norm_squared(),
             center,
         })
     }
+
+    /// Intersects the hypersphere with the line through a point in a given
+    /// direction. Returns the parameters `t` of the two intersection points
+    /// `point + t * direction`, in increasing order, or `None` if the line
+    /// misses the hypersphere or the direction is zero.
+    ///
+    /// A line whose squared distance to the center is within [`Float::EPS`]
+    /// of the squared radius is considered tangent, and both parameters are
+    /// then equal.
+    pub fn intersect_line(&self, point: &Point<T>, direction: &Vector<T>) -> Option<(T, T)> {
+        let norm_squared = direction.norm_squared();
+        if norm_squared < T::EPS {
+            return None;
+        }
+
+        // The parameter of the point on the line closest to the center.
+        let offset = point - &self.center;
+        let closest = -offset.dot(direction) / norm_squared;
+
+        // The squared distance from the center to the line.
+        let squared_distance = (offset + direction * closest).norm_squared();
+        let half_chord = Self::leg(self.squared_radius, squared_distance)?;
+        let t = half_chord / norm_squared.fsqrt();
+
+        Some((closest - t, closest + t))
+    }
+
+    /// Returns the length of the tangent segments from an external point to
+    /// the hypersphere, or `None` if the point is inside of it. Points within
+    /// [`Float::EPS`] of the surface, in squared distance, have a tangent
+    /// length of zero.
+    pub fn tangent_from(&self, external_point: &Point<T>) -> Option<T> {
+        let squared_distance = (external_point - &self.center).norm_squared();
+        Self::leg(squared_distance, self.squared_radius)
+    }
+
+    /// Returns the remaining leg of a right triangle, given the squared
+    /// lengths of its hypotenuse and its other leg. Returns `None` if the
+    /// hypotenuse is shorter by more than [`Float::EPS`], and zero if they're
+    /// within [`Float::EPS`] of each other.
+    fn leg(squared_hypotenuse: T, squared_leg: T) -> Option<T> {
+        let difference = squared_hypotenuse - squared_leg;
+
+        if difference < -T::EPS {
+            None
+        } else if difference < T::EPS {
+            Some(T::ZERO)
+        } else {
+            Some(difference.fsqrt())
+        }
+    }
 }
 
 /// Represents an (affine) subspace, passing through a given point and generated
@@ -181,9 +244,15 @@ impl<T: Float> Subspace<T> {
     /// # Todo:
     /// Implement the [Gram-Schmidt process](https://en.wikipedia.org/wiki/Gram%E2%80%93Schmidt_process#Numerical_stability).
     pub fn add(&mut self, p: &Point<T>) -> Option<&Point<T>> {
+        self.add_within(p, T::EPS)
+    }
+
+    /// Adds a point to the subspace, unless its distance to it is at most a
+    /// given tolerance. Returns the new basis vector if one was added.
+    pub fn add_within(&mut self, p: &Point<T>, tolerance: T) -> Option<&Point<T>> {
         let mut v = p - self.project(p);
 
-        if v.normalize_mut() > T::EPS {
+        if v.normalize_mut() > tolerance {
             self.basis.push(v);
             self.basis.last()
         } else {
@@ -197,7 +266,17 @@ impl<T: Float> Subspace<T> {
     ///
     /// Consider using [`Self::from_points_with`] if you expect your subspace to
     /// have an exact rank.
-    pub fn from_points<'a, U: Iterator<Item = &'a Point<T>>>(mut iter: U) -> Self {
+    pub fn from_points<'a, U: Iterator<Item = &'a Point<T>>>(iter: U) -> Self {
+        Self::from_points_within(iter, T::EPS)
+    }
+
+    /// Creates a subspace from an iterator over points, ignoring those whose
+    /// distance to the subspace spanned by the previous ones is at most a
+    /// given tolerance.
+    pub fn from_points_within<'a, U: Iterator<Item = &'a Point<T>>>(
+        mut iter: U,
+        tolerance: T,
+    ) -> Self {
         let mut subspace = Self::new(
             iter.next()
                 .expect("A hyperplane can't be created from an empty point array!")
@@ -207,7 +286,7 @@ impl<T: Float> Subspace<T> {
         for p in iter {
             // If the subspace is of full rank, we don't need to check any
             // more points.
-            if subspace.add(p).is_some() && subspace.is_full_rank() {
+            if subspace.add_within(p, tolerance).is_some() && subspace.is_full_rank() {
                 return subspace;
             }
         }
@@ -244,6 +323,60 @@ impl<T: Float> Subspace<T> {
         Some(subspace)
     }
 
+    /// Finds the affine subspace of a given rank that best fits a set of
+    /// points, in the least squares sense. This passes through the centroid
+    /// of the points, and is spanned by the principal axes of their
+    /// covariance matrix.
+    ///
+    /// Unlike [`Self::from_points`], the points needn't lie exactly on the
+    /// subspace, so this can be used to measure how far they deviate from it.
+    /// Returns `None` if there are no points or if the rank exceeds the
+    /// dimension of the points.
+    pub fn fit_points<'a, U: Iterator<Item = &'a Point<T>> + Clone>(
+        points: U,
+        rank: usize,
+    ) -> Option<Self> {
+        let mut count = 0;
+        let mut centroid = points.clone().next()?.clone();
+        centroid.fill(T::ZERO);
+
+        for p in points.clone() {
+            centroid += p;
+            count += 1;
+        }
+        centroid /= T::usize(count);
+
+        let dim = centroid.nrows();
+        if rank > dim {
+            return None;
+        }
+
+        // The (unnormalized) covariance matrix of the points.
+        let mut covariance = Matrix::zeros(dim, dim);
+        for p in points {
+            let v = p - &centroid;
+            covariance += &v * v.transpose();
+        }
+
+        // The eigenvectors with the greatest eigenvalues span the subspace.
+        let eigen = covariance.symmetric_eigen();
+        let mut axes: Vec<_> = (0..dim).collect();
+        axes.sort_unstable_by(|&i, &j| {
+            eigen.eigenvalues[j]
+                .partial_cmp(&eigen.eigenvalues[i])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Some(Self {
+            basis: axes
+                .into_iter()
+                .take(rank)
+                .map(|i| eigen.eigenvectors.column(i).into_owned())
+                .collect(),
+            offset: centroid,
+        })
+    }
+
     /// Projects a point onto the subspace.
     pub fn project(&self, p: &Point<T>) -> Point<T> {
         let p = p - &self.offset;
@@ -292,6 +425,23 @@ impl<T: Float> Subspace<T> {
         (p - self.project(p)).try_normalize(T::EPS)
     }
 
+    /// Intersects the subspace with a hypersphere. The result is a
+    /// lower-dimensional hypersphere within the subspace, centered at the
+    /// projection of the hypersphere's center, and given in the coordinates of
+    /// the ambient space. Returns `None` if the subspace misses the
+    /// hypersphere.
+    ///
+    /// As in [`Hypersphere::intersect_line`], a subspace within
+    /// [`Float::EPS`] of being tangent is considered tangent, and the result
+    /// is then a single point.
+    pub fn intersect_hypersphere(&self, sphere: &Hypersphere<T>) -> Option<Hypersphere<T>> {
+        let center = self.project(&sphere.center);
+        let squared_distance = (&center - &sphere.center).norm_squared();
+        let radius = Hypersphere::leg(sphere.squared_radius, squared_distance)?;
+
+        Some(Hypersphere::with_radius(center, radius))
+    }
+
     // Computes a set of independent vectors that span the orthogonal
     // complement of the subspace.
     /* pub fn orthogonal_comp(&self) -> Vec<Vector> {
@@ -346,6 +496,28 @@ impl<T: Float> Hyperplane<T> {
         Self { subspace, normal }
     }
 
+    /// Generates the hyperplane spanned by a set of points, oriented so that a
+    /// given point lies on its negative side. Returns `None` if the points
+    /// don't span a hyperplane, or if the given point lies on it.
+    pub fn from_points_away<'a, U: Iterator<Item = &'a Point<T>>>(
+        points: U,
+        p: &Point<T>,
+    ) -> Option<Self> {
+        let subspace = Subspace::from_points(points);
+        if !subspace.is_hyperplane() {
+            return None;
+        }
+
+        let normal = -subspace.normal(p)?;
+        Some(Self { subspace, normal })
+    }
+
+    /// Returns the unit normal vector of the hyperplane, which points towards
+    /// its positive side.
+    pub fn normal(&self) -> &Vector<T> {
+        &self.normal
+    }
+
     /// Projects a point onto the hyperplane.
     pub fn project(&self, p: &Point<T>) -> Point<T> {
         self.subspace.project(p)
@@ -371,13 +543,7 @@ impl<T: Float> Hyperplane<T> {
     /// Returns the intersection of itself and a line segment, or `None` if it
     /// doesn't exist.
     pub fn intersect(&self, line: Segment<'_, T>) -> Option<Point<T>> {
-        let d0 = self.distance(line.0);
-        let d1 = self.distance(line.1);
-
-        // This right here is some really sensitive code. If we screw up
-        // handling the edge cases, cross-sections through elements will crash.
-        (abs_diff_ne!(d0, d1, epsilon = T::EPS) && (d0 < -T::EPS) != (d1 < -T::EPS))
-            .then(|| line.at(d1 / (d1 - d0)))
+        line.crossing(self.distance(line.0), self.distance(line.1))
     }
 }
 
@@ -390,6 +556,138 @@ impl<'a, T: Float> Segment<'a, T> {
     pub fn at(&self, t: T) -> Point<T> {
         self.0 * t + self.1 * (T::ONE - t)
     }
+
+    /// Returns the point where the segment crosses a hyperplane, given the
+    /// signed distances from both of its endpoints to it, or `None` if it
+    /// doesn't.
+    pub fn crossing(&self, d0: T, d1: T) -> Option<Point<T>> {
+        // This right here is some really sensitive code. If we screw up
+        // handling the edge cases, cross-sections through elements will crash.
+        (abs_diff_ne!(d0, d1, epsilon = T::EPS) && (d0 < -T::EPS) != (d1 < -T::EPS))
+            .then(|| self.at(d1 / (d1 - d0)))
+    }
+
+    /// Returns the point where the segment crosses a hyperplane, given the
+    /// signed distances from both of its endpoints to it and the sides of it
+    /// on which they've been decided to lie, or `None` if it doesn't. Points on
+    /// the hyperplane count as lying on its positive side.
+    ///
+    /// Unlike [`Self::crossing`], whether the segment crosses the hyperplane
+    /// only depends on the sides, so that the distances can be off by
+    /// rounding errors. The crossing is always placed on the segment.
+    pub fn crossing_sides(
+        &self,
+        d0: T,
+        d1: T,
+        s0: std::cmp::Ordering,
+        s1: std::cmp::Ordering,
+    ) -> Option<Point<T>> {
+        ((s0 == std::cmp::Ordering::Less) != (s1 == std::cmp::Ordering::Less)).then(|| {
+            if s0 == std::cmp::Ordering::Equal {
+                self.0.clone()
+            } else if s1 == std::cmp::Ordering::Equal {
+                self.1.clone()
+            } else {
+                let t = d1 / (d1 - d0);
+                if !<T as ordered_float::Float>::is_finite(t) {
+                    self.at(T::ONE / T::TWO)
+                } else if t < T::ZERO {
+                    self.1.clone()
+                } else if t > T::ONE {
+                    self.0.clone()
+                } else {
+                    self.at(t)
+                }
+            }
+        })
+    }
+}
+
+/// Constructors for rotation matrices, specified by vectors rather than by
+/// pairs of coordinate axes.
+pub trait Rotation<T: Float>: Sized {
+    /// Builds the rotation by a given angle in the plane spanned by two
+    /// vectors, in any dimension. The rotation takes the direction of the
+    /// first vector towards that of the second, and fixes every vector
+    /// orthogonal to both. Returns `None` if the vectors don't span a plane.
+    fn rotation_in_plane(e1: &Vector<T>, e2: &Vector<T>, angle: T) -> Option<Self>;
+
+    /// Builds the 3D rotation by a given angle about an axis, which is
+    /// counterclockwise when looking from the tip of the axis. Returns `None`
+    /// if the axis isn't a nonzero 3D vector.
+    fn rotation_about_axis(axis: &Vector<T>, angle: T) -> Option<Self>;
+
+    /// Builds the minimal rotation that takes the direction of a vector to
+    /// that of another. If they're opposite, any half-turn that does this is
+    /// returned. Returns `None` if either vector is zero, or if they're
+    /// opposite in one dimension.
+    fn rotation_from_to(a: &Vector<T>, b: &Vector<T>) -> Option<Self>;
+}
+
+/// Builds the rotation in the plane spanned by two orthonormal vectors, given
+/// the cosine and the sine of the angle.
+fn plane_rotation<T: Float>(u: &Vector<T>, v: &Vector<T>, cos: T, sin: T) -> Matrix<T> {
+    let dim = u.len();
+    let mut m = Matrix::identity(dim, dim);
+
+    m += (u * u.transpose() + v * v.transpose()) * (cos - T::ONE);
+    m += (v * u.transpose() - u * v.transpose()) * sin;
+    m
+}
+
+impl<T: Float> Rotation<T> for Matrix<T> {
+    fn rotation_in_plane(e1: &Vector<T>, e2: &Vector<T>, angle: T) -> Option<Self> {
+        // Gram-Schmidt on the plane basis.
+        let u = e1.clone().try_normalize(T::EPS)?;
+        let v = (e2 - &u * u.dot(e2)).try_normalize(T::EPS)?;
+
+        Some(plane_rotation(&u, &v, angle.fcos(), angle.fsin()))
+    }
+
+    fn rotation_about_axis(axis: &Vector<T>, angle: T) -> Option<Self> {
+        if axis.len() != 3 {
+            return None;
+        }
+
+        // Rodrigues' rotation formula.
+        let k = axis.clone().try_normalize(T::EPS)?;
+        let cross = Matrix::from_row_slice(
+            3,
+            3,
+            &[T::ZERO, -k[2], k[1], k[2], T::ZERO, -k[0], -k[1], k[0], T::ZERO],
+        );
+        let (sin, cos) = angle.fsin_cos();
+
+        Some(Matrix::identity(3, 3) + &cross * sin + &cross * &cross * (T::ONE - cos))
+    }
+
+    fn rotation_from_to(a: &Vector<T>, b: &Vector<T>) -> Option<Self> {
+        let u = a.clone().try_normalize(T::EPS)?;
+        let b = b.clone().try_normalize(T::EPS)?;
+        let cos = u.dot(&b);
+
+        // We use the sine and cosine directly, which is more precise than
+        // computing the angle.
+        let w = &b - &u * cos;
+        let sin = w.norm();
+        if sin > T::EPS {
+            return Some(plane_rotation(&u, &(w / sin), cos, sin));
+        }
+
+        let dim = u.len();
+        if cos > T::ZERO {
+            return Some(Matrix::identity(dim, dim));
+        }
+
+        // For opposite vectors, we turn about the plane through the
+        // coordinate axis that's least aligned with them.
+        let idx = u.iamin();
+        let mut axis = Vector::zeros(dim);
+        axis[idx] = T::ONE;
+        let v = (axis - &u * u[idx]).try_normalize(T::EPS)?;
+
+        Some(plane_rotation(&u, &v, -T::ONE, T::ZERO))
+    }
 }
 
 /// A matrix ordered by fuzzy lexicographic ordering. That is, lexicographic
@@ -552,4 +850,216 @@ mod tests {
             dvector![4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0],
         );
     }
+
+    /// Asserts that two optional pairs of parameters are equal.
+    fn assert_params(params: Option<(f64, f64)>, expected: Option<(f64, f64)>) {
+        match (params, expected) {
+            (Some((t0, t1)), Some((e0, e1))) => {
+                assert_abs_diff_eq!(t0, e0, epsilon = f64::EPS);
+                assert_abs_diff_eq!(t1, e1, epsilon = f64::EPS);
+            }
+            (None, None) => {}
+            _ => panic!("expected {:?}, found {:?}", expected, params),
+        }
+    }
+
+    /// Asserts that a matrix is a rotation, that is, that it's orthogonal and
+    /// has determinant 1.
+    fn assert_rotation(m: &Matrix<f64>) {
+        let dim = m.nrows();
+        assert_abs_diff_eq!(
+            (m.transpose() * m - Matrix::identity(dim, dim)).norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(m.determinant(), 1.0, epsilon = 1e-12);
+    }
+
+    /// Asserts that two vectors are equal.
+    fn assert_vec(p: Vector<f64>, q: Vector<f64>) {
+        assert_abs_diff_eq!((p - q).norm(), 0.0, epsilon = 1e-12)
+    }
+
+    #[test]
+    /// Rotates vectors in planes.
+    fn rotation_in_plane() {
+        // A quarter turn in the xy plane, from non-orthonormal vectors.
+        let m = Matrix::rotation_in_plane(
+            &dvector![2.0, 0.0, 0.0, 0.0],
+            &dvector![1.0, 3.0, 0.0, 0.0],
+            f64::PI / 2.0,
+        )
+        .unwrap();
+        assert_rotation(&m);
+        assert_vec(&m * dvector![1.0, 0.0, 0.0, 0.0], dvector![0.0, 1.0, 0.0, 0.0]);
+        assert_vec(&m * dvector![0.0, 1.0, 0.0, 0.0], dvector![-1.0, 0.0, 0.0, 0.0]);
+        assert_vec(&m * dvector![0.0, 0.0, 1.0, 2.0], dvector![0.0, 0.0, 1.0, 2.0]);
+
+        // A diagonal plane.
+        let m = Matrix::rotation_in_plane(
+            &dvector![1.0, 1.0, 0.0],
+            &dvector![0.0, 0.0, 1.0],
+            f64::PI / 3.0,
+        )
+        .unwrap();
+        assert_rotation(&m);
+        let s = 0.5f64.sqrt();
+        assert_vec(
+            &m * dvector![s, s, 0.0],
+            dvector![0.5 * s, 0.5 * s, 3f64.sqrt() / 2.0],
+        );
+
+        assert!(Matrix::rotation_in_plane(&dvector![1.0, 2.0], &dvector![2.0, 4.0], 1.0).is_none());
+    }
+
+    #[test]
+    /// Rotates vectors about axes.
+    fn rotation_about_axis() {
+        // A third of a turn about the diagonal cycles the axes.
+        let m = Matrix::rotation_about_axis(&dvector![1.0, 1.0, 1.0], f64::TAU / 3.0).unwrap();
+        assert_rotation(&m);
+        assert_vec(&m * dvector![1.0, 0.0, 0.0], dvector![0.0, 1.0, 0.0]);
+        assert_vec(&m * dvector![0.0, 1.0, 0.0], dvector![0.0, 0.0, 1.0]);
+
+        // A quarter turn about the z axis.
+        let m = Matrix::rotation_about_axis(&dvector![0.0, 0.0, 2.0], f64::PI / 2.0).unwrap();
+        assert_vec(&m * dvector![1.0, 0.0, 0.0], dvector![0.0, 1.0, 0.0]);
+
+        assert!(Matrix::rotation_about_axis(&dvector![1.0, 0.0], 1.0).is_none());
+        assert!(Matrix::rotation_about_axis(&dvector![0.0, 0.0, 0.0], 1.0).is_none());
+    }
+
+    #[test]
+    /// Rotates vectors onto others.
+    fn rotation_from_to() {
+        let a = dvector![1.0, 2.0, 2.0, 0.0];
+        let b = dvector![0.0, 0.0, 0.0, 6.0];
+        let m = Matrix::rotation_from_to(&a, &b).unwrap();
+        assert_rotation(&m);
+        assert_vec(&m * &a, dvector![0.0, 0.0, 0.0, 3.0]);
+
+        // Vectors orthogonal to both are fixed.
+        assert_vec(&m * dvector![0.0, 1.0, -1.0, 0.0], dvector![0.0, 1.0, -1.0, 0.0]);
+
+        // Equal and opposite vectors.
+        let m = Matrix::rotation_from_to(&a, &(&a * 2.0)).unwrap();
+        assert_abs_diff_eq!((m - Matrix::identity(4, 4)).norm(), 0.0, epsilon = 1e-12);
+        let m = Matrix::rotation_from_to(&a, &-&a).unwrap();
+        assert_rotation(&m);
+        assert_vec(&m * &a, -&a);
+
+        let a = dvector![0.0, 0.0, 1.0];
+        let m = Matrix::rotation_from_to(&a, &-&a).unwrap();
+        assert_rotation(&m);
+        assert_vec(&m * &a, -&a);
+
+        // There's no rotation in a single dimension.
+        assert!(Matrix::rotation_from_to(&dvector![1.0], &dvector![-1.0]).is_none());
+    }
+
+    #[test]
+    /// Intersects lines with circles and spheres.
+    fn intersect_line() {
+        // A secant through the center of a circle of radius 5.
+        let circle = Hypersphere::with_radius(dvector![1.0, 1.0], 5.0);
+        assert_params(
+            circle.intersect_line(&dvector![1.0, -9.0], &dvector![0.0, 2.0]),
+            Some((2.5, 7.5)),
+        );
+
+        // A 3-4-5 triangle gives a half chord of 4.
+        assert_params(
+            circle.intersect_line(&dvector![4.0, 1.0], &dvector![0.0, 1.0]),
+            Some((-4.0, 4.0)),
+        );
+
+        // Tangent lines, and lines within the tolerance of being tangent.
+        assert_params(
+            circle.intersect_line(&dvector![6.0, 0.0], &dvector![0.0, 1.0]),
+            Some((1.0, 1.0)),
+        );
+        assert_params(
+            circle.intersect_line(&dvector![6.0 + 1e-9, 0.0], &dvector![0.0, 1.0]),
+            Some((1.0, 1.0)),
+        );
+
+        // Lines that miss, and degenerate directions.
+        assert_params(
+            circle.intersect_line(&dvector![6.1, 0.0], &dvector![0.0, 1.0]),
+            None,
+        );
+        assert_params(
+            circle.intersect_line(&dvector![1.0, 1.0], &dvector![0.0, 0.0]),
+            None,
+        );
+
+        // A line through the unit sphere along the diagonal of a cube.
+        let sphere = Hypersphere::<f64>::unit(3);
+        let t = 1.0 / 3f64.sqrt();
+        assert_params(
+            sphere.intersect_line(&dvector![0.0, 0.0, 0.0], &dvector![1.0, 1.0, 1.0]),
+            Some((-t, t)),
+        );
+
+        // A line in 4D at distance 1 from a sphere of radius √2.
+        let sphere = Hypersphere::with_radius(dvector![0.0, 0.0, 0.0, 0.0], 2f64.sqrt());
+        assert_params(
+            sphere.intersect_line(&dvector![0.0, 0.0, 1.0, 0.0], &dvector![1.0, 0.0, 0.0, 0.0]),
+            Some((-1.0, 1.0)),
+        );
+    }
+
+    #[test]
+    /// Computes tangent lengths from points to spheres.
+    fn tangent_from() {
+        let circle = Hypersphere::with_radius(dvector![1.0, 1.0], 3.0);
+        assert_abs_diff_eq!(
+            circle.tangent_from(&dvector![6.0, 1.0]).unwrap(),
+            4.0,
+            epsilon = f64::EPS
+        );
+        assert_eq!(circle.tangent_from(&dvector![4.0, 1.0]), Some(0.0));
+        assert_eq!(circle.tangent_from(&dvector![2.0, 2.0]), None);
+
+        let sphere = Hypersphere::<f64>::unit(4);
+        assert_abs_diff_eq!(
+            sphere.tangent_from(&dvector![1.0, 1.0, 1.0, 1.0]).unwrap(),
+            3f64.sqrt(),
+            epsilon = f64::EPS
+        );
+    }
+
+    #[test]
+    /// Intersects subspaces with spheres.
+    fn intersect_hypersphere() {
+        // A plane at height 3 cuts a sphere of radius 5 in a circle of radius
+        // 4.
+        let mut plane = Subspace::new(dvector![0.0, 0.0, 3.0]);
+        plane.add(&dvector![1.0, 0.0, 3.0]);
+        plane.add(&dvector![0.0, 1.0, 3.0]);
+        let sphere = Hypersphere::with_radius(dvector![1.0, 2.0, 0.0], 5.0);
+
+        let circle = plane.intersect_hypersphere(&sphere).unwrap();
+        assert_eq!(circle.center, dvector![1.0, 2.0, 3.0]);
+        assert_abs_diff_eq!(circle.radius(), 4.0, epsilon = f64::EPS);
+
+        // A tangent plane, and one that misses.
+        let mut plane = Subspace::new(dvector![0.0, 0.0, 5.0]);
+        plane.add(&dvector![1.0, 0.0, 5.0]);
+        plane.add(&dvector![0.0, 1.0, 5.0]);
+        let point = plane.intersect_hypersphere(&sphere).unwrap();
+        assert_eq!(point.squared_radius, 0.0);
+
+        let mut plane = Subspace::new(dvector![0.0, 0.0, 6.0]);
+        plane.add(&dvector![1.0, 0.0, 6.0]);
+        assert!(plane.intersect_hypersphere(&sphere).is_none());
+
+        // A line through a 4D sphere, at distance 1 from its center.
+        let mut line = Subspace::new(dvector![0.0, 1.0, 0.0, 0.0]);
+        line.add(&dvector![1.0, 1.0, 1.0, 0.0]);
+        let sphere = Hypersphere::with_radius(Point::zeros(4), 2.0);
+        let segment = line.intersect_hypersphere(&sphere).unwrap();
+        assert_eq(segment.center.map(|x| x as f32), dvector![0.0, 1.0, 0.0, 0.0]);
+        assert_abs_diff_eq!(segment.squared_radius, 3.0, epsilon = f64::EPS);
+    }
 }
@@ -0,0 +1,309 @@
+//! A list of points that can be merged up to a tolerance, so that every
+//! algorithm that identifies nearby points does so in the same way.
+//!
+//! Points are merged into the first point within the tolerance, in insertion
+//! order, which is never moved afterwards. In particular, a cluster of points
+//! wider than the tolerance may be split into several points, and the points
+//! we keep aren't the centroids of the points merged into them. This makes
+//! the results deterministic, and keeps the indices of earlier points stable
+//! as more are inserted.
+
+use std::{collections::HashMap, iter::FromIterator};
+
+use super::Point;
+
+/// The number of directions that the spatial hash projects points onto.
+/// Finding the neighbors of a point takes `2^GRID_DIM` lookups.
+const GRID_DIM: usize = 3;
+
+/// A cell of the spatial hash.
+type Cell = [i64; GRID_DIM];
+
+/// Returns the weight of the coordinate with a given index in the direction
+/// with index `k` of the spatial hash. The directions are fixed, but generic
+/// enough that points with many equal coordinates, as the vertices of
+/// polytopes often have, still tend to fall into different cells.
+fn weight(k: usize, idx: usize) -> f64 {
+    const ALPHAS: [f64; GRID_DIM] = [
+        std::f64::consts::SQRT_2,
+        1.732_050_807_568_877_2,
+        2.236_067_977_499_79,
+    ];
+    ((idx + 1) as f64 * ALPHAS[k]).fract() - 0.5
+}
+
+/// Returns the positions of a point along the directions of the spatial hash,
+/// in units of the width of its cells.
+fn grid_coords(p: &Point<f64>, width: f64) -> [f64; GRID_DIM] {
+    let mut coords = [0.0; GRID_DIM];
+    for (k, x) in coords.iter_mut().enumerate() {
+        let (dot, norm_sq) = p
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(dot, norm_sq), (idx, y)| {
+                let w = weight(k, idx);
+                (dot + w * y, norm_sq + w * w)
+            });
+        *x = dot / (norm_sq.sqrt() * width);
+    }
+    coords
+}
+
+/// Returns the cell of the spatial hash with some given coordinates.
+fn cell(coords: &[f64; GRID_DIM]) -> Cell {
+    // Casting saturates, and sends NaN to 0.
+    coords.map(|x| x.floor() as i64)
+}
+
+/// Returns the distance between two points.
+fn distance(p: &Point<f64>, q: &Point<f64>) -> f64 {
+    p.iter()
+        .zip(q.iter())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// A list of points, backed by a spatial hash with cells twice as wide as the
+/// last tolerance used to merge them.
+#[derive(Clone, Debug, Default)]
+pub struct PointCloud {
+    /// The points, in insertion order.
+    points: Vec<Point<f64>>,
+
+    /// The width of the cells of the spatial hash, and the indices of the
+    /// points in each of them. This is built lazily, and rebuilt whenever the
+    /// tolerance changes.
+    grid: Option<(f64, HashMap<Cell, Vec<usize>>)>,
+}
+
+impl FromIterator<Point<f64>> for PointCloud {
+    /// Builds a point cloud with the given points, without merging any of
+    /// them.
+    fn from_iter<I: IntoIterator<Item = Point<f64>>>(iter: I) -> Self {
+        Self {
+            points: iter.into_iter().collect(),
+            grid: None,
+        }
+    }
+}
+
+impl PointCloud {
+    /// Initializes an empty point cloud.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns whether there are no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the points, in insertion order.
+    pub fn points(&self) -> &[Point<f64>] {
+        &self.points
+    }
+
+    /// Consumes the point cloud and returns its points, in insertion order.
+    pub fn into_points(self) -> Vec<Point<f64>> {
+        self.points
+    }
+
+    /// Builds the spatial hash for a given cell width, unless it's already
+    /// built.
+    fn build_grid(&mut self, width: f64) {
+        if !matches!(self.grid, Some((w, _)) if w == width) {
+            let mut grid = HashMap::<_, Vec<_>>::new();
+            for (idx, p) in self.points.iter().enumerate() {
+                grid.entry(cell(&grid_coords(p, width)))
+                    .or_default()
+                    .push(idx);
+            }
+            self.grid = Some((width, grid));
+        }
+    }
+
+    /// Returns the index of the first point within a given distance of `p`,
+    /// or inserts `p` and returns its index if there's none. The points are
+    /// never moved, so their indices remain valid as more are inserted.
+    pub fn insert_or_get(&mut self, p: Point<f64>, eps: f64) -> usize {
+        // Cells can't have zero width, but they can be small enough that
+        // only equal points share them.
+        let width = 2.0 * eps.max(f64::MIN_POSITIVE);
+        self.build_grid(width);
+        let grid = &mut self.grid.as_mut().unwrap().1;
+        let coords = grid_coords(&p, width);
+        let home = cell(&coords);
+
+        // Points within the tolerance lie at most half a cell away along
+        // every direction, so they lie in the same cell, or in the adjacent
+        // one on the nearest side.
+        let mut found: Option<usize> = None;
+        for mask in 0..1 << GRID_DIM {
+            let mut neighbor = home;
+            for (k, c) in neighbor.iter_mut().enumerate() {
+                if mask & (1 << k) != 0 {
+                    let step = if coords[k] - (*c as f64) < 0.5 { -1 } else { 1 };
+                    *c = c.saturating_add(step);
+                }
+            }
+
+            for &idx in grid.get(&neighbor).into_iter().flatten() {
+                if distance(&self.points[idx], &p) <= eps {
+                    found = Some(found.map_or(idx, |found| found.min(idx)));
+                }
+            }
+        }
+
+        if let Some(idx) = found {
+            return idx;
+        }
+
+        let idx = self.points.len();
+        grid.entry(home).or_default().push(idx);
+        self.points.push(p);
+        idx
+    }
+
+    /// Merges the points up to a given tolerance. Returns the points that
+    /// remain, along with the index among them of each of the original
+    /// points.
+    ///
+    /// Every point is merged into the first earlier point that remains and
+    /// lies within the tolerance of it, as in [`Self::insert_or_get`].
+    pub fn dedup(&self, eps: f64) -> (Vec<Point<f64>>, Vec<usize>) {
+        let mut cloud = Self::new();
+        let map = self
+            .points
+            .iter()
+            .map(|p| cloud.insert_or_get(p.clone(), eps))
+            .collect();
+
+        (cloud.into_points(), map)
+    }
+
+    /// Returns the index of the point nearest to `p` along with its distance,
+    /// or `None` if there are no points. Ties are broken in favor of the point
+    /// inserted first.
+    pub fn nearest(&self, p: &Point<f64>) -> Option<(usize, f64)> {
+        self.points
+            .iter()
+            .map(|q| distance(q, p))
+            .enumerate()
+            .min_by(|(_, d), (_, e)| d.total_cmp(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::dvector;
+
+    /// Points are merged into the first point within the tolerance, which
+    /// keeps its index.
+    #[test]
+    fn insert_or_get() {
+        let mut cloud = PointCloud::new();
+        assert_eq!(cloud.insert_or_get(dvector![0.0, 0.0], 0.1), 0);
+        assert_eq!(cloud.insert_or_get(dvector![1.0, 0.0], 0.1), 1);
+        assert_eq!(cloud.insert_or_get(dvector![0.05, 0.0], 0.1), 0);
+        assert_eq!(cloud.insert_or_get(dvector![1.0, -0.09], 0.1), 1);
+        assert_eq!(cloud.insert_or_get(dvector![0.0, 0.2], 0.1), 2);
+        assert_eq!(cloud.len(), 3);
+        assert_eq!(cloud.points()[0], dvector![0.0, 0.0]);
+
+        // Changing the tolerance rebuilds the spatial hash.
+        assert_eq!(cloud.insert_or_get(dvector![0.0, 0.1], 0.15), 0);
+        assert_eq!(cloud.insert_or_get(dvector![0.0, 0.0], 0.0), 0);
+        assert_eq!(cloud.insert_or_get(dvector![0.0, 1e-300], 0.0), 3);
+    }
+
+    /// A point close to two others is merged into the one inserted first,
+    /// even if it's farther away, and clusters wider than the tolerance are
+    /// split.
+    #[test]
+    fn clusters() {
+        let chain: PointCloud = (0..5).map(|i| dvector![0.6 * i as f64]).collect();
+        let (points, map) = chain.dedup(1.0);
+        assert_eq!(map, vec![0, 0, 1, 1, 2]);
+        assert_eq!(points, vec![dvector![0.0], dvector![1.2], dvector![2.4]]);
+
+        let cloud: PointCloud = vec![dvector![0.0, 0.0], dvector![1.5, 0.0], dvector![0.8, 0.0]]
+            .into_iter()
+            .collect();
+        assert_eq!(cloud.dedup(1.0).1, vec![0, 1, 0]);
+    }
+
+    /// The result doesn't depend on anything but the order of the points.
+    #[test]
+    fn determinism() {
+        let points: Vec<_> = (0..2000)
+            .map(|i| {
+                let t = i as f64 * 0.618;
+                dvector![t.sin(), (2.0 * t).cos(), t.fract(), (3.0 * t).sin()]
+            })
+            .collect();
+
+        let cloud: PointCloud = points.iter().cloned().collect();
+        let (unique, map) = cloud.dedup(0.05);
+        assert_eq!(cloud.dedup(0.05), (unique.clone(), map.clone()));
+
+        // The spatial hash finds the same points as a linear search.
+        let mut expected: Vec<Point<f64>> = Vec::new();
+        for (p, &idx) in points.iter().zip(&map) {
+            match expected.iter().position(|q| (q - p).norm() <= 0.05) {
+                Some(found) => assert_eq!(found, idx),
+                None => {
+                    assert_eq!(expected.len(), idx);
+                    expected.push(p.clone());
+                }
+            }
+        }
+        assert_eq!(unique, expected);
+    }
+
+    /// Merges a million points, half of which are copies of the others moved
+    /// by less than the tolerance. This takes a while, so it must be run
+    /// explicitly via `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn million_points() {
+        use std::f64::consts::SQRT_2;
+        const SQRT_3: f64 = 1.732_050_807_568_877_2;
+        const SQRT_5: f64 = 2.236_067_977_499_79;
+        const COUNT: usize = 500_000;
+
+        let points: PointCloud = (0..2 * COUNT)
+            .map(|i| {
+                let t = (i % COUNT) as f64;
+                let offset = if i < COUNT { 0.0 } else { 1e-9 };
+                dvector![(t * SQRT_2).fract(), (t * SQRT_3).fract(), (t * SQRT_5).fract()]
+                    .add_scalar(offset)
+            })
+            .collect();
+
+        let start = std::time::Instant::now();
+        let (unique, map) = points.dedup(1e-7);
+        println!("merging {} points took {:?}", 2 * COUNT, start.elapsed());
+
+        assert_eq!(unique.len(), COUNT);
+        assert!((0..COUNT).all(|i| map[i] == map[i + COUNT]));
+    }
+
+    /// The nearest point, with ties broken by insertion order.
+    #[test]
+    fn nearest() {
+        let cloud: PointCloud = vec![dvector![1.0, 0.0], dvector![-1.0, 0.0], dvector![0.0, 3.0]]
+            .into_iter()
+            .collect();
+        assert_eq!(cloud.nearest(&dvector![0.0, 0.0]), Some((0, 1.0)));
+        assert_eq!(cloud.nearest(&dvector![-2.0, 0.0]), Some((1, 1.0)));
+        assert_eq!(cloud.nearest(&dvector![0.0, 2.0]), Some((2, 1.0)));
+        assert_eq!(PointCloud::new().nearest(&dvector![0.0]), None);
+    }
+}
@@ -0,0 +1,502 @@
+//! Geometric predicates, which decide on which side of a hyperplane points
+//! lie.
+//!
+//! These decisions determine the combinatorics of the convex hulls and
+//! cross-sections we build, so getting them wrong can result in invalid
+//! polytopes. The [fast predicates](FastPredicates) use floating point
+//! arithmetic with a tolerance. The [exact predicates](ExactPredicates) first
+//! compute a floating point estimate together with an error bound, and only
+//! when the bound doesn't determine the sign do they redo the computation in
+//! exact arithmetic, using expansions as described in Shewchuk's *Adaptive
+//! Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates*.
+
+use std::cmp::Ordering;
+
+use super::{Hyperplane, Matrix, Point};
+
+/// How robustly to decide on which side of a hyperplane points lie.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RobustnessMode {
+    /// Floating point arithmetic with a tolerance. Points very close to a
+    /// hyperplane are considered to lie on it.
+    #[default]
+    Fast,
+
+    /// Exact arithmetic whenever floating point arithmetic isn't enough to
+    /// decide. Points only lie on a hyperplane when they do so exactly.
+    Adaptive,
+}
+
+/// A way to decide on which side of a hyperplane points lie. Points on the
+/// positive side of a hyperplane are [`Greater`](Ordering::Greater), points on
+/// its negative side are [`Less`](Ordering::Less), and points on the
+/// hyperplane itself are [`Equal`](Ordering::Equal).
+pub trait Predicates {
+    /// Returns the side of the hyperplane through `simplex` on which each of
+    /// `points` lies. The simplex must consist of as many points as the
+    /// dimension of the space. Returns `None` if they don't span a hyperplane.
+    fn orientations(&self, simplex: &[&Point<f64>], points: &[Point<f64>])
+        -> Option<Vec<Ordering>>;
+
+    /// Returns the side of a hyperplane on which each of `points` lies.
+    fn sides(&self, hyperplane: &Hyperplane<f64>, points: &[Point<f64>]) -> Vec<Ordering>;
+}
+
+/// Compares a value against a tolerance around zero.
+fn tolerance_cmp(x: f64, tolerance: f64) -> Ordering {
+    if x >= tolerance {
+        Ordering::Greater
+    } else if x <= -tolerance {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Returns a normal vector to the hyperplane through `dim` points in
+/// `dim`-dimensional space, as the generalized cross product of the vectors
+/// from the first point to the rest. The result isn't normalized, and is zero
+/// whenever the points don't span a hyperplane.
+fn normal(points: &[&Point<f64>], dim: usize) -> Point<f64> {
+    let mat = Matrix::from_fn(dim - 1, dim, |i, j| points[i + 1][j] - points[0][j]);
+
+    Point::from_fn(dim, |j, _| {
+        let det = mat.clone().remove_column(j).determinant();
+        if j % 2 == 0 {
+            det
+        } else {
+            -det
+        }
+    })
+}
+
+/// Predicates in floating point arithmetic. Points closer to a hyperplane than
+/// a given tolerance are considered to lie on it.
+#[derive(Clone, Copy, Debug)]
+pub struct FastPredicates {
+    /// The distance below which points are considered to lie on a hyperplane.
+    pub tolerance: f64,
+}
+
+impl Predicates for FastPredicates {
+    fn orientations(
+        &self,
+        simplex: &[&Point<f64>],
+        points: &[Point<f64>],
+    ) -> Option<Vec<Ordering>> {
+        let normal = normal(simplex, simplex.len());
+        let norm = normal.norm();
+        if norm < self.tolerance {
+            return None;
+        }
+
+        let base = simplex[0];
+        Some(
+            points
+                .iter()
+                .map(|p| tolerance_cmp(normal.dot(&(p - base)) / norm, self.tolerance))
+                .collect(),
+        )
+    }
+
+    fn sides(&self, hyperplane: &Hyperplane<f64>, points: &[Point<f64>]) -> Vec<Ordering> {
+        points
+            .iter()
+            .map(|p| tolerance_cmp(hyperplane.distance(p), self.tolerance))
+            .collect()
+    }
+}
+
+/// Predicates in adaptive precision arithmetic, which always return the exact
+/// result for the given floating point coordinates, barring overflow and
+/// underflow.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactPredicates;
+
+/// Returns the exact sum and the rounding error of the sum of two floats.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Returns the rounded product and the rounding error of the product of two
+/// floats.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// A number represented exactly as the sum of floats of increasing magnitude,
+/// none of which are zero, and whose binary representations don't overlap.
+#[derive(Clone, Debug, Default)]
+struct Expansion(Vec<f64>);
+
+impl Expansion {
+    /// The expansion representing a single float.
+    fn new(x: f64) -> Self {
+        let mut res = Self::default();
+        res.grow(x);
+        res
+    }
+
+    /// Adds a float to the expansion.
+    fn grow(&mut self, b: f64) {
+        let mut components = Vec::with_capacity(self.0.len() + 1);
+        let mut q = b;
+
+        for &e in &self.0 {
+            let (x, y) = two_sum(q, e);
+            if y != 0.0 {
+                components.push(y);
+            }
+            q = x;
+        }
+
+        if q != 0.0 {
+            components.push(q);
+        }
+        self.0 = components;
+    }
+
+    /// Adds the exact product of two floats to the expansion.
+    fn grow_product(&mut self, a: f64, b: f64) {
+        let (x, y) = two_product(a, b);
+        self.grow(y);
+        self.grow(x);
+    }
+
+    /// Adds the product of another expansion and a float to the expansion.
+    fn add_scaled(&mut self, other: &Self, b: f64) {
+        for &e in &other.0 {
+            self.grow_product(e, b);
+        }
+    }
+
+    /// Negates the expansion.
+    fn neg(mut self) -> Self {
+        for x in &mut self.0 {
+            *x = -*x;
+        }
+        self
+    }
+
+    /// Returns the sign of the expansion, which is that of its largest
+    /// component.
+    fn sign(&self) -> Ordering {
+        match self.0.last() {
+            Some(&x) if x > 0.0 => Ordering::Greater,
+            Some(_) => Ordering::Less,
+            None => Ordering::Equal,
+        }
+    }
+}
+
+/// Computes the determinant of the square matrix made out of a given column
+/// and the ones after it, and out of the given rows, by Laplace expansion
+/// along its first column. Also returns the permanent of the matrix of
+/// absolute values, which bounds the rounding error.
+fn determinant_bound(mat: &[Vec<f64>], col: usize, rows: &[usize]) -> (f64, f64) {
+    if let [row] = rows {
+        let x = mat[*row][col];
+        return (x, x.abs());
+    }
+
+    let mut det = 0.0;
+    let mut perm = 0.0;
+    let mut minor_rows = rows[1..].to_vec();
+
+    for k in 0..rows.len() {
+        if k > 0 {
+            minor_rows[k - 1] = rows[k - 1];
+        }
+
+        let x = mat[rows[k]][col];
+        let (minor_det, minor_perm) = determinant_bound(mat, col + 1, &minor_rows);
+        if k % 2 == 0 {
+            det += x * minor_det;
+        } else {
+            det -= x * minor_det;
+        }
+        perm += x.abs() * minor_perm;
+    }
+
+    (det, perm)
+}
+
+/// Computes the exact determinant of the square matrix made out of a given
+/// column and the ones after it, and out of the given rows, by Laplace
+/// expansion along its first column.
+fn determinant_exact(mat: &[Vec<f64>], col: usize, rows: &[usize]) -> Expansion {
+    if let [row] = rows {
+        return Expansion::new(mat[*row][col]);
+    }
+
+    let mut det = Expansion::default();
+    let mut minor_rows = rows[1..].to_vec();
+
+    for k in 0..rows.len() {
+        if k > 0 {
+            minor_rows[k - 1] = rows[k - 1];
+        }
+
+        let x = mat[rows[k]][col];
+        let minor_det = determinant_exact(mat, col + 1, &minor_rows);
+        det.add_scaled(&minor_det, if k % 2 == 0 { x } else { -x });
+    }
+
+    det
+}
+
+/// Returns the sign of the exact determinant of a square matrix, given by its
+/// rows.
+pub fn determinant_sign(mat: &[Vec<f64>]) -> Ordering {
+    let rows: Vec<_> = (0..mat.len()).collect();
+    if rows.is_empty() {
+        return Ordering::Greater;
+    }
+
+    let (det, perm) = determinant_bound(mat, 0, &rows);
+    if det.abs() > error_bound(mat.len(), perm) {
+        det.partial_cmp(&0.0).unwrap()
+    } else {
+        determinant_exact(mat, 0, &rows).sign()
+    }
+}
+
+/// Bounds the rounding error of a determinant of a matrix of a given size,
+/// computed by Laplace expansion, or of a sum of products of such a
+/// determinant by some floats, given the corresponding permanent.
+fn error_bound(size: usize, perm: f64) -> f64 {
+    let n = (size * size + 2 * size + 2) as f64;
+    4.0 * n * f64::EPSILON * perm + f64::MIN_POSITIVE
+}
+
+/// Computes the exact cofactors along the last row of a matrix, given the
+/// matrices that result from removing its last row and each of its columns.
+fn exact_cofactors(mats: &[Vec<Vec<f64>>]) -> Vec<Expansion> {
+    let size = mats.len();
+    let rows: Vec<_> = (0..size - 1).collect();
+
+    mats.iter()
+        .enumerate()
+        .map(|(j, mat)| {
+            let det = determinant_exact(mat, 0, &rows);
+            if (size + j) % 2 == 1 {
+                det
+            } else {
+                det.neg()
+            }
+        })
+        .collect()
+}
+
+impl Predicates for ExactPredicates {
+    fn orientations(
+        &self,
+        simplex: &[&Point<f64>],
+        points: &[Point<f64>],
+    ) -> Option<Vec<Ordering>> {
+        // The orientation of a point is the sign of the determinant whose
+        // rows are the points of the simplex and the point, each followed by
+        // a 1. We expand it along its last row, so that the cofactors only
+        // have to be computed once.
+        let dim = simplex.len();
+        let cofactor_mats: Vec<Vec<Vec<f64>>> = (0..=dim)
+            .map(|j| {
+                simplex
+                    .iter()
+                    .map(|p| {
+                        (0..=dim)
+                            .filter(|&k| k != j)
+                            .map(|k| if k == dim { 1.0 } else { p[k] })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let rows: Vec<_> = (0..dim).collect();
+        let cofactors: Vec<_> = cofactor_mats
+            .iter()
+            .enumerate()
+            .map(|(j, mat)| {
+                let (det, perm) = determinant_bound(mat, 0, &rows);
+                if (dim + j) % 2 == 1 {
+                    (-det, perm)
+                } else {
+                    (det, perm)
+                }
+            })
+            .collect();
+
+        // Computed lazily, only when floating point arithmetic isn't enough.
+        let mut exact = None;
+
+        // The points span a hyperplane iff some cofactor is nonzero.
+        if cofactors
+            .iter()
+            .all(|&(det, perm)| det.abs() <= error_bound(dim, perm))
+            && exact
+                .get_or_insert_with(|| exact_cofactors(&cofactor_mats))
+                .iter()
+                .all(|c| c.sign() == Ordering::Equal)
+        {
+            return None;
+        }
+
+        Some(
+            points
+                .iter()
+                .map(|p| {
+                    let coord = |j| if j == dim { 1.0 } else { p[j] };
+
+                    let mut det = 0.0;
+                    let mut perm = 0.0;
+                    for (j, &(cofactor, cofactor_perm)) in cofactors.iter().enumerate() {
+                        det += coord(j) * cofactor;
+                        perm += coord(j).abs() * cofactor_perm;
+                    }
+
+                    if det.abs() > error_bound(dim + 1, perm) {
+                        det.partial_cmp(&0.0).unwrap()
+                    } else {
+                        let mut det = Expansion::default();
+                        let cofactors =
+                            exact.get_or_insert_with(|| exact_cofactors(&cofactor_mats));
+                        for (j, cofactor) in cofactors.iter().enumerate() {
+                            det.add_scaled(cofactor, coord(j));
+                        }
+                        det.sign()
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn sides(&self, hyperplane: &Hyperplane<f64>, points: &[Point<f64>]) -> Vec<Ordering> {
+        // The side of a point is the sign of (p - o) · n, where o is a point
+        // on the hyperplane and n is its normal.
+        let normal = hyperplane.normal();
+        let offset = &hyperplane.subspace.offset;
+
+        let mut offset_dot = Expansion::default();
+        for (&n, &o) in normal.iter().zip(offset.iter()) {
+            offset_dot.grow_product(n, o);
+        }
+        let offset_dot = offset_dot.neg();
+        let offset_approx: f64 = offset_dot.0.iter().sum();
+        let offset_perm: f64 = normal
+            .iter()
+            .zip(offset.iter())
+            .map(|(n, o)| (n * o).abs())
+            .sum();
+
+        points
+            .iter()
+            .map(|p| {
+                let mut det = offset_approx;
+                let mut perm = offset_perm;
+                for (&n, &x) in normal.iter().zip(p.iter()) {
+                    det += n * x;
+                    perm += (n * x).abs();
+                }
+
+                if det.abs() > error_bound(normal.len(), perm) {
+                    det.partial_cmp(&0.0).unwrap()
+                } else {
+                    let mut det = offset_dot.clone();
+                    for (&n, &x) in normal.iter().zip(p.iter()) {
+                        det.grow_product(n, x);
+                    }
+                    det.sign()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Vector;
+
+    /// Expansions keep track of what floating point arithmetic rounds off.
+    #[test]
+    fn expansion() {
+        let mut x = Expansion::new(1.0);
+        x.grow(1e-30);
+        x.grow(-1.0);
+        assert_eq!(x.0, vec![1e-30]);
+
+        let mut x = Expansion::new(-0.1 * 0.1);
+        x.grow_product(0.1, 0.1);
+        assert_eq!(x.0, vec![0.1f64.mul_add(0.1, -0.1 * 0.1)]);
+        assert_ne!(x.sign(), Ordering::Equal);
+
+        x.add_scaled(&x.clone().neg(), 1.0);
+        assert_eq!(x.sign(), Ordering::Equal);
+    }
+
+    /// Determinants that floating point arithmetic gets wrong.
+    #[test]
+    fn determinant() {
+        let eps = f64::EPSILON;
+        let mat = vec![vec![1.0 + eps, 1.0], vec![1.0, 1.0 - eps]];
+        assert_eq!(determinant_sign(&mat), Ordering::Less);
+
+        let mat = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+        assert_eq!(determinant_sign(&mat), Ordering::Equal);
+    }
+
+    /// Points near a line through two far away points.
+    #[test]
+    fn orientations() {
+        let p0 = Point::from_vec(vec![0.5, 0.5]);
+        let p1 = Point::from_vec(vec![12.0, 12.0]);
+        let points: Vec<_> = (0..64)
+            .map(|i| Point::from_vec(vec![0.5 + f64::from(i) * f64::EPSILON, 0.5]))
+            .collect();
+
+        let exact = ExactPredicates.orientations(&[&p0, &p1], &points).unwrap();
+        assert_eq!(exact[0], Ordering::Equal);
+        assert!(exact[1..]
+            .iter()
+            .all(|&s| s == exact[1] && s != Ordering::Equal));
+
+        let fast = FastPredicates { tolerance: 1e-9 }
+            .orientations(&[&p0, &p1], &points)
+            .unwrap();
+        assert!(fast.iter().all(|&s| s == Ordering::Equal));
+
+        assert!(ExactPredicates.orientations(&[&p0, &p0], &points).is_none());
+    }
+
+    /// A point barely off a hyperplane.
+    #[test]
+    fn sides() {
+        let hyperplane = Hyperplane::new(Vector::from_vec(vec![0.0, 0.0, 1.0]), 0.1);
+        let points = vec![
+            Point::from_vec(vec![3.0, -2.0, 0.1]),
+            Point::from_vec(vec![3.0, -2.0, 0.1 + 1e-15]),
+            Point::from_vec(vec![3.0, -2.0, 0.1 - 1e-15]),
+        ];
+
+        assert_eq!(
+            ExactPredicates.sides(&hyperplane, &points),
+            vec![Ordering::Equal, Ordering::Greater, Ordering::Less]
+        );
+        assert_eq!(
+            FastPredicates {
+                tolerance: f64::EPSILON.sqrt()
+            }
+            .sides(&hyperplane, &points),
+            vec![Ordering::Equal; 3]
+        );
+    }
+}
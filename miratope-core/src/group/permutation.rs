@@ -2,6 +2,7 @@
 
 use std::{
     collections::BTreeMap,
+    fmt::Display,
     marker::PhantomData,
     mem::MaybeUninit,
     ops::{Index, Mul, MulAssign},
@@ -27,7 +28,7 @@ where
     DefaultAllocator: Allocator<usize, N>,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(Ord::cmp(self, other))
     }
 }
 
@@ -35,8 +36,10 @@ impl<N: Dim> Ord for Permutation<N>
 where
     DefaultAllocator: Allocator<usize, N>,
 {
+    /// Compares two permutations lexicographically. Matrices are only
+    /// partially ordered entrywise, so we can't defer to them.
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.partial_cmp(&other.0).unwrap()
+        self.0.iter().cmp(other.0.iter())
     }
 }
 
@@ -155,8 +158,93 @@ impl DPermutation {
         // Safety: all of these elements are different and less than `n`.
         unsafe { Self::from_iterator(0..n, n) }
     }
+
+    /// Parses a permutation on `len` elements written in cycle notation, like
+    /// `(0 1 2)(3 4)`. The entries of each cycle are separated by spaces or
+    /// commas, and start at 0. Elements that aren't in any cycle are fixed, so
+    /// that both the empty string and `()` give the identity.
+    pub fn from_cycles(src: &str, len: usize) -> Result<Self, CycleError> {
+        let bytes = src.as_bytes();
+        let mut data: Vec<usize> = (0..len).collect();
+        let mut found = vec![false; len];
+        let mut cycle: Option<Vec<usize>> = None;
+        let mut pos = 0;
+
+        while pos < bytes.len() {
+            match (bytes[pos], cycle.as_mut()) {
+                (b'(', None) => cycle = Some(Vec::new()),
+
+                // Closes a cycle, sending each entry to the next.
+                (b')', Some(entries)) => {
+                    for (i, &entry) in entries.iter().enumerate() {
+                        data[entry] = entries[(i + 1) % entries.len()];
+                    }
+                    cycle = None;
+                }
+
+                (b'0'..=b'9', Some(entries)) => {
+                    let end = bytes[pos..]
+                        .iter()
+                        .position(|b| !b.is_ascii_digit())
+                        .map_or(bytes.len(), |len| pos + len);
+                    let entry: usize =
+                        src[pos..end].parse().map_err(|_| CycleError::Syntax(pos))?;
+
+                    if entry >= len {
+                        return Err(CycleError::OutOfBounds(entry));
+                    } else if found[entry] {
+                        return Err(CycleError::Repeated(entry));
+                    }
+
+                    found[entry] = true;
+                    entries.push(entry);
+                    pos = end;
+                    continue;
+                }
+
+                (b' ' | b',' | b'\t', _) => {}
+                _ => return Err(CycleError::Syntax(pos)),
+            }
+
+            pos += 1;
+        }
+
+        if cycle.is_some() {
+            Err(CycleError::Syntax(bytes.len()))
+        } else {
+            // Safety: the entries of each cycle are distinct and less than
+            // `len`, so this is a bijection.
+            Ok(unsafe { Self::from_iterator(data, len) })
+        }
+    }
+}
+
+/// An error while parsing a permutation in cycle notation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CycleError {
+    /// There's an unexpected character or an unclosed cycle at a given
+    /// position.
+    Syntax(usize),
+
+    /// Some entry is at least the length of the permutation.
+    OutOfBounds(usize),
+
+    /// Some entry appears more than once.
+    Repeated(usize),
 }
 
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(pos) => write!(f, "invalid cycle notation at position {}", pos),
+            Self::OutOfBounds(entry) => write!(f, "entry {} is out of bounds", entry),
+            Self::Repeated(entry) => write!(f, "entry {} appears more than once", entry),
+        }
+    }
+}
+
+impl std::error::Error for CycleError {}
+
 impl<'a, 'b, N: Dim> Mul<&'b Permutation<N>> for &'a Permutation<N>
 where
     DefaultAllocator: Allocator<usize, N>,
@@ -285,3 +373,48 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Permutations are read from cycle notation, and invalid ones are
+    /// rejected.
+    #[test]
+    fn from_cycles() {
+        let perm = DPermutation::from_cycles("(0 1 2)(3, 4)", 6).unwrap();
+        assert_eq!(perm.iter().collect::<Vec<_>>(), vec![1, 2, 0, 4, 3, 5]);
+        assert_eq!(&perm * &perm.inv(), DPermutation::id(6));
+
+        assert_eq!(DPermutation::from_cycles("", 3), Ok(DPermutation::id(3)));
+        assert_eq!(
+            DPermutation::from_cycles(" ()(1) ", 3),
+            Ok(DPermutation::id(3))
+        );
+
+        assert_eq!(
+            DPermutation::from_cycles("(0 1", 3),
+            Err(CycleError::Syntax(4))
+        );
+        assert_eq!(
+            DPermutation::from_cycles("0 1", 3),
+            Err(CycleError::Syntax(0))
+        );
+        assert_eq!(
+            DPermutation::from_cycles("(0 a)", 3),
+            Err(CycleError::Syntax(3))
+        );
+        assert_eq!(
+            DPermutation::from_cycles("((0 1))", 3),
+            Err(CycleError::Syntax(1))
+        );
+        assert_eq!(
+            DPermutation::from_cycles("(0 3)", 3),
+            Err(CycleError::OutOfBounds(3))
+        );
+        assert_eq!(
+            DPermutation::from_cycles("(0 1)(1 2)", 3),
+            Err(CycleError::Repeated(1))
+        );
+    }
+}
@@ -22,8 +22,34 @@
 //!
 //! If you're interested in actually rendering polytopes, you might want to take
 //! a look at the [`miratope`](https://crates.io/crates/miratope) crate instead.
+//!
+//! This crate doesn't depend on any rendering code, so it can be used on its
+//! own. For instance, the following builds a duoprism, checks it, and takes its
+//! dual:
+//!
+//! ```
+//! use miratope_core::{
+//!     abs::Ranked,
+//!     conc::{Concrete, ConcretePolytope},
+//!     file::FromFile,
+//!     Polytope,
+//! };
+//!
+//! let square = Concrete::polygon(4);
+//! let duoprism = Concrete::polygon(3).duoprism(&square);
+//! assert_eq!(duoprism.el_count(1), 12);
+//! duoprism.assert_valid();
+//!
+//! let dual = duoprism.try_dual().unwrap();
+//! assert_eq!(dual.facet_count(), 12);
+//!
+//! // Polytopes can be read from and written to OFF files.
+//! let off = dual.to_off(Default::default()).unwrap();
+//! assert_eq!(Concrete::from_off(&off).unwrap().vertex_count(), 7);
+//! ```
 
 pub mod abs;
+pub mod cli;
 pub mod conc;
 pub mod cox;
 pub mod file;
@@ -31,6 +57,9 @@ pub mod float;
 pub mod geometry;
 pub mod group;
 
+#[cfg(test)]
+mod snapshot;
+
 use std::{collections::HashSet, error::Error, iter, ops::IndexMut};
 
 use abs::{
@@ -57,6 +86,46 @@ impl std::fmt::Display for DualError {
 
 impl Error for DualError {}
 
+/// Represents an error while building an antiprism.
+#[derive(Clone, Copy, Debug)]
+pub enum AntiprismError {
+    /// The base has no dual with respect to the reciprocation sphere, as a
+    /// facet passes through its center. Recentering the sphere might help.
+    Dual(DualError),
+
+    /// The abstract antiprism of the base doesn't exist, as the base isn't
+    /// dyadic.
+    Dyadic {
+        /// The coordinates of the section at fault.
+        section: abs::Section,
+
+        /// Whether there were more than 4 elements in the section (or less).
+        more: bool,
+    },
+}
+
+impl From<DualError> for AntiprismError {
+    fn from(err: DualError) -> Self {
+        Self::Dual(err)
+    }
+}
+
+impl std::fmt::Display for AntiprismError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dual(err) => write!(f, "the base has no dual: {}", err),
+            Self::Dyadic { section, more } => write!(
+                f,
+                "the base's abstract antiprism doesn't exist: there are {} than 2 elements between {}",
+                if *more { "more" } else { "less" },
+                section
+            ),
+        }
+    }
+}
+
+impl Error for AntiprismError {}
+
 /// Gets the precalculated value for n!.
 fn factorial(n: usize) -> u32 {
     /// Precalculated factorials from 0! to 13!.
@@ -178,7 +247,7 @@ pub trait Polytope:
     where
         Self: 'a,
     {
-        multiproduct(factors, Self::duopyramid, Self::nullitope)
+        multiproduct(factors, ProductKind::Pyramid)
     }
 
     /// Takes the [prism product](https://polytope.miraheze.org/wiki/Prism_product)
@@ -187,7 +256,7 @@ pub trait Polytope:
     where
         Self: 'a,
     {
-        multiproduct(factors, Self::duoprism, Self::point)
+        multiproduct(factors, ProductKind::Prism)
     }
 
     /// Takes the [tegum product](https://polytope.miraheze.org/wiki/Tegum_product)
@@ -196,7 +265,7 @@ pub trait Polytope:
     where
         Self: 'a,
     {
-        multiproduct(factors, Self::duotegum, Self::point)
+        multiproduct(factors, ProductKind::Tegum)
     }
 
     /// Takes the [comb product](https://polytope.miraheze.org/wiki/Comb_product)
@@ -205,13 +274,25 @@ pub trait Polytope:
     where
         Self: 'a,
     {
-        multiproduct(factors, Self::duocomb, Self::nullitope)
+        multiproduct(factors, ProductKind::Comb)
+    }
+
+    /// Takes the product of a given kind of `n` copies of a polytope. Since
+    /// products are associative, this squares intermediate results instead of
+    /// multiplying by the base `n` times, which only takes a logarithmic
+    /// number of products.
+    fn power(kind: ProductKind, base: &Self, n: usize) -> Self {
+        binary_power(base, n, |p, q| kind.apply(p, q), || kind.empty())
     }
 
     /// Builds a [simplex](https://polytope.miraheze.org/wiki/Simplex) with a
     /// given rank.
     fn simplex(rank: usize) -> Self {
-        Self::multipyramid(iter::repeat(&Self::point()).take(rank))
+        if rank == 0 {
+            Self::nullitope()
+        } else {
+            Self::multipyramid(iter::repeat(&Self::point()).take(rank))
+        }
     }
 
     /// Builds a regular [tetrahedron](https://polytope.miraheze.org/wiki/Tetrahedron).
@@ -436,9 +517,9 @@ pub trait Polytope:
     fn hosotope_mut(&mut self);
 
     /// Attempts to build an [antiprism](https://polytope.miraheze.org/wiki/Antiprism)
-    /// based on a given polytope. If it fails, it returns the index of a facet
-    /// through the inversion center.
-    fn try_antiprism(&self) -> Result<Self, Self::DualError>;
+    /// based on a given polytope. Fails if the base isn't dyadic, or if no dual
+    /// of the base could be taken.
+    fn try_antiprism(&self) -> Result<Self, AntiprismError>;
 
     /// Splits compound faces into their components.
     /// Outputs a vec of vecs of split faces per component excluding those that aren't compounds.
@@ -466,23 +547,100 @@ pub trait Polytope:
     }
 }
 
-/// Returns the multiproduct (i.e. multipyramid, multiprism, multitegum, or
-/// multicomb) of an iterator over polytopes. This is built by repeatedly
-/// applying the product on two polytopes. In case the iterator is empty, this
-/// builds a polytope using the specified default function.
-fn multiproduct<'a, T, I, P, D>(factors: I, product: P, default: D) -> T
+/// The different products of polytopes. Each of them is associative, at least
+/// up to isomorphism.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProductKind {
+    /// The [pyramid product](https://polytope.miraheze.org/wiki/Pyramid_product).
+    Pyramid,
+
+    /// The [prism product](https://polytope.miraheze.org/wiki/Prism_product).
+    Prism,
+
+    /// The [tegum product](https://polytope.miraheze.org/wiki/Tegum_product).
+    Tegum,
+
+    /// The [comb product](https://polytope.miraheze.org/wiki/Comb_product).
+    Comb,
+}
+
+impl ProductKind {
+    /// Takes the product of this kind of two polytopes.
+    pub fn apply<T: Polytope>(self, p: &T, q: &T) -> T {
+        match self {
+            Self::Pyramid => p.duopyramid(q),
+            Self::Prism => p.duoprism(q),
+            Self::Tegum => p.duotegum(q),
+            Self::Comb => p.duocomb(q),
+        }
+    }
+
+    /// Returns the product of this kind of no polytopes at all.
+    pub fn empty<T: Polytope>(self) -> T {
+        match self {
+            Self::Pyramid | Self::Comb => T::nullitope(),
+            Self::Prism | Self::Tegum => T::point(),
+        }
+    }
+}
+
+/// Takes the product of `n` copies of a polytope by binary exponentiation. The
+/// product must be associative. In case `n` is zero, this builds a polytope
+/// using the specified default function.
+pub(crate) fn binary_power<T, P, D>(base: &T, mut n: usize, product: P, default: D) -> T
 where
-    T: Clone + 'a,
-    I: IntoIterator<Item = &'a T>,
+    T: Clone,
     P: Fn(&T, &T) -> T,
     D: Fn() -> T,
 {
-    let mut factors = factors.into_iter();
+    if n == 0 {
+        return default();
+    }
+
+    // Invariant: the result is `res` times `square` to the power of `n`.
+    let mut res: Option<T> = None;
+    let mut square = base.clone();
+
+    loop {
+        if n % 2 == 1 {
+            res = Some(match res {
+                Some(res) => product(&res, &square),
+                None if n == 1 => return square,
+                None => square.clone(),
+            });
+        }
+
+        n /= 2;
+        if n == 0 {
+            // We only get here after multiplying by the largest square.
+            return res.unwrap();
+        }
+
+        square = product(&square, &square);
+    }
+}
+
+/// Returns the multiproduct (i.e. multipyramid, multiprism, multitegum, or
+/// multicomb) of an iterator over polytopes. Runs of the same factor, as
+/// determined by pointer equality, are [exponentiated](Polytope::power), and
+/// the results are then multiplied together from left to right.
+fn multiproduct<'a, T, I>(factors: I, kind: ProductKind) -> T
+where
+    T: Polytope + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut runs: Vec<(&T, usize)> = Vec::new();
+    for factor in factors {
+        match runs.last_mut() {
+            Some((last, count)) if std::ptr::eq(*last, factor) => *count += 1,
+            _ => runs.push((factor, 1)),
+        }
+    }
 
-    match (factors.next(), factors.next()) {
-        (None, _) => default(),
-        (Some(factor), None) => factor.clone(),
-        (Some(first), Some(second)) => factors.fold(product(first, second), |p, q| product(&p, q)),
+    let mut powers = runs.into_iter().map(|(factor, n)| T::power(kind, factor, n));
+    match powers.next() {
+        None => kind.empty(),
+        Some(first) => powers.fold(first, |p, q| kind.apply(&p, &q)),
     }
 }
 
@@ -491,14 +649,18 @@ where
 #[cfg(test)]
 pub(crate) fn test<T: Polytope, I: IntoIterator<Item = usize>>(poly: &T, element_counts: I) {
     let mut element_counts = element_counts.into_iter();
-    for r in 0..=poly.rank() {
-        assert_eq!(
-            poly.el_count(r),
-            element_counts.next().unwrap_or_default(),
-            "element count mismatch at rank {}",
-            r
-        )
-    }
+    let expected: abs::ElementCounts = (0..=poly.rank())
+        .map(|_| element_counts.next().unwrap_or_default())
+        .collect();
+    let found = poly.abs().el_counts();
+
+    assert!(
+        found == expected,
+        "element count mismatch: expected {:#}, found {:#}\n{}",
+        expected,
+        found,
+        poly.abs().pretty(4)
+    );
 
     poly.assert_valid();
 }
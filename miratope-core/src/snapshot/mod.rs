@@ -0,0 +1,347 @@
+//! Regression tests that apply chains of operations to polytopes and compare
+//! the results against stored OFF snapshots.
+//!
+//! Each snapshot lives in this directory, as `<case name>.off`. Running the
+//! tests with the `MIRATOPE_UPDATE_SNAPSHOTS` environment variable set writes
+//! the current results into the snapshots instead of checking them.
+
+use std::{cmp::Ordering, env, fs, path::PathBuf};
+
+use crate::{
+    abs::Ranked,
+    conc::{Concrete, ConcretePolytope},
+    file::{off::OffOptions, FromFile},
+    float::Float,
+    geometry::{Hyperplane, Point, Vector},
+    Polytope,
+};
+
+use approx::abs_diff_eq;
+
+/// The environment variable that makes the tests regenerate the snapshots.
+const UPDATE_VAR: &str = "MIRATOPE_UPDATE_SNAPSHOTS";
+
+/// The tolerance for each coordinate when comparing vertices.
+const TOLERANCE: f64 = 1e-9;
+
+/// The polytope an operation chain starts from.
+#[derive(Clone, Copy)]
+enum Input {
+    /// A polytope built by a generator.
+    Build(fn() -> Concrete),
+
+    /// One of the OFF files used by the OFF tests, given by its name.
+    Fixture(&'static str),
+}
+
+impl Input {
+    /// Builds or loads the input polytope.
+    fn load(self) -> Concrete {
+        match self {
+            Self::Build(build) => build(),
+            Self::Fixture(name) => Concrete::from_off(
+                &fs::read_to_string(dir().join("../file/off").join(name)).unwrap(),
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// An operation applied to a polytope.
+#[derive(Clone, Copy)]
+enum Op {
+    /// Takes the dual with respect to the unit hypersphere.
+    Dual,
+
+    /// Builds a pyramid.
+    Pyramid,
+
+    /// Builds a prism.
+    Prism,
+
+    /// Builds a tegum.
+    Tegum,
+
+    /// Slices through the hyperplane with a given normal, which is normalized
+    /// beforehand, at a given distance from the origin. The section is
+    /// flattened into its own subspace.
+    Section(&'static [f64], f64),
+
+    /// Builds a duoprism with a polygon with a given number of sides.
+    Duoprism(usize),
+
+    /// Builds a duopyramid with a polygon with a given number of sides.
+    Duopyramid(usize),
+
+    /// Builds a duotegum with a polygon with a given number of sides.
+    Duotegum(usize),
+}
+
+impl Op {
+    /// Applies the operation to a polytope.
+    fn apply(self, poly: &Concrete) -> Result<Concrete, String> {
+        Ok(match self {
+            Self::Dual => poly.try_dual().map_err(|err| err.to_string())?,
+            Self::Pyramid => poly.pyramid(),
+            Self::Prism => poly.prism(),
+            Self::Tegum => poly.tegum(),
+            Self::Section(normal, pos) => {
+                let normal = Vector::from_vec(normal.to_vec()).normalize();
+                let mut section = poly.cross_section(&Hyperplane::new(normal, pos));
+                section.flatten();
+                section
+            }
+            Self::Duoprism(n) => poly.duoprism(&Concrete::polygon(n)),
+            Self::Duopyramid(n) => poly.duopyramid(&Concrete::polygon(n)),
+            Self::Duotegum(n) => poly.duotegum(&Concrete::polygon(n)),
+        })
+    }
+}
+
+/// A snapshot test: an input polytope, and the operations applied to it in
+/// order.
+struct Case {
+    /// The name of the snapshot file, without the extension.
+    name: &'static str,
+
+    /// The input polytope.
+    input: Input,
+
+    /// The operations applied to it.
+    ops: &'static [Op],
+}
+
+/// Shorthand for a case starting from a generated polytope.
+const fn build(name: &'static str, input: fn() -> Concrete, ops: &'static [Op]) -> Case {
+    Case {
+        name,
+        input: Input::Build(input),
+        ops,
+    }
+}
+
+/// Every snapshot test.
+const CASES: &[Case] = &[
+    // Duals of the Platonic solids.
+    build("tet-dual", || Concrete::platonic(0), &[Op::Dual]),
+    build("cube-dual", || Concrete::platonic(1), &[Op::Dual]),
+    build("oct-dual", || Concrete::platonic(2), &[Op::Dual]),
+    build("doe-dual", || Concrete::platonic(3), &[Op::Dual]),
+    build("ike-dual", || Concrete::platonic(4), &[Op::Dual]),
+    // Pyramids, prisms and tegums of polygons.
+    build("trig-pyramid", || Concrete::polygon(3), &[Op::Pyramid]),
+    build("square-pyramid", || Concrete::polygon(4), &[Op::Pyramid]),
+    build("peg-pyramid", || Concrete::polygon(5), &[Op::Pyramid]),
+    build("star-pyramid", || Concrete::star_polygon(5, 2), &[Op::Pyramid]),
+    build("trig-prism", || Concrete::polygon(3), &[Op::Prism]),
+    build("square-prism", || Concrete::polygon(4), &[Op::Prism]),
+    build("hig-prism", || Concrete::polygon(6), &[Op::Prism]),
+    build("star-prism", || Concrete::star_polygon(5, 2), &[Op::Prism]),
+    build("square-tegum", || Concrete::polygon(4), &[Op::Tegum]),
+    build("peg-tegum", || Concrete::polygon(5), &[Op::Tegum]),
+    build("hig-tegum", || Concrete::polygon(6), &[Op::Tegum]),
+    // Cross-sections of the cube and the tesseract.
+    build("cube-section-axis", Concrete::cube, &[Op::Section(&[1.0, 0.0, 0.0], 0.25)]),
+    build("cube-section-center", Concrete::cube, &[Op::Section(&[1.0, 1.0, 1.0], 0.0)]),
+    build("cube-section-vertex", Concrete::cube, &[Op::Section(&[1.0, 1.0, 1.0], 0.3)]),
+    build("cube-section-edge", Concrete::cube, &[Op::Section(&[1.0, 1.0, 0.0], 0.2)]),
+    build("tes-section-axis", || Concrete::hypercube(5), &[Op::Section(&[1.0, 0.0, 0.0, 0.0], 0.1)]),
+    build("tes-section-center", || Concrete::hypercube(5), &[Op::Section(&[1.0, 1.0, 1.0, 1.0], 0.0)]),
+    build("tes-section-vertex", || Concrete::hypercube(5), &[Op::Section(&[1.0, 1.0, 1.0, 1.0], 0.4)]),
+    build("tes-section-face", || Concrete::hypercube(5), &[Op::Section(&[1.0, 1.0, 0.0, 0.0], 0.2)]),
+    // Products of small polygons.
+    build("trig-square-duoprism", || Concrete::polygon(3), &[Op::Duoprism(4)]),
+    build("peg-peg-duoprism", || Concrete::polygon(5), &[Op::Duoprism(5)]),
+    build("trig-square-duopyramid", || Concrete::polygon(3), &[Op::Duopyramid(4)]),
+    build("trig-peg-duotegum", || Concrete::polygon(3), &[Op::Duotegum(5)]),
+    // Longer chains.
+    build("square-pyramid-prism", || Concrete::polygon(4), &[Op::Pyramid, Op::Prism]),
+    build("peg-prism-dual", || Concrete::polygon(5), &[Op::Prism, Op::Dual]),
+    build("cube-section-prism", Concrete::cube, &[Op::Section(&[1.0, 1.0, 1.0], 0.0), Op::Prism]),
+    Case {
+        name: "tet-file-pyramid",
+        input: Input::Fixture("tet.off"),
+        ops: &[Op::Pyramid],
+    },
+];
+
+/// Returns the directory with the snapshots.
+fn dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/snapshot")
+}
+
+/// Compares two points lexicographically, considering coordinates within the
+/// tolerance as equal.
+fn cmp_points(p: &Point<f64>, q: &Point<f64>) -> Ordering {
+    for (x, y) in p.iter().zip(q.iter()) {
+        if !abs_diff_eq!(x, y, epsilon = TOLERANCE) {
+            return x.partial_cmp(y).unwrap();
+        }
+    }
+
+    p.len().cmp(&q.len())
+}
+
+/// A form of a polytope that doesn't depend on the order of its elements.
+struct Canonical {
+    /// The vertices, sorted lexicographically.
+    vertices: Vec<Point<f64>>,
+
+    /// For every rank from the edges up to the facets, the sorted indices of
+    /// the vertices of every element, in sorted order.
+    elements: Vec<Vec<Vec<usize>>>,
+}
+
+impl Canonical {
+    /// Puts a polytope in canonical form.
+    fn new(poly: &Concrete) -> Self {
+        let mut order: Vec<_> = (0..poly.vertex_count()).collect();
+        order.sort_by(|&i, &j| cmp_points(&poly.vertices[i], &poly.vertices[j]));
+
+        let mut new_idx = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            new_idx[old] = new;
+        }
+
+        let elements = (2..poly.rank())
+            .map(|rank| {
+                let mut elements: Vec<_> = (0..poly.el_count(rank))
+                    .map(|idx| {
+                        let mut vertices: Vec<_> = poly
+                            .abs()
+                            .element_vertices(rank, idx)
+                            .unwrap()
+                            .into_iter()
+                            .map(|v| new_idx[v])
+                            .collect();
+                        vertices.sort_unstable();
+                        vertices
+                    })
+                    .collect();
+                elements.sort();
+                elements
+            })
+            .collect();
+
+        Self {
+            vertices: order.into_iter().map(|i| poly.vertices[i].clone()).collect(),
+            elements,
+        }
+    }
+
+    /// Returns the reason why two canonical forms differ, if they do.
+    fn diff(&self, expected: &Self) -> Option<String> {
+        if self.vertices.len() != expected.vertices.len() {
+            return Some(format!(
+                "expected {} vertices, found {}",
+                expected.vertices.len(),
+                self.vertices.len()
+            ));
+        }
+
+        for (idx, (v, w)) in self.vertices.iter().zip(&expected.vertices).enumerate() {
+            if v.len() != w.len() || !v.iter().zip(w.iter()).all(|(x, y)| abs_diff_eq!(x, y, epsilon = TOLERANCE)) {
+                return Some(format!(
+                    "vertex {} is at {:?}, expected {:?}",
+                    idx,
+                    v.as_slice(),
+                    w.as_slice()
+                ));
+            }
+        }
+
+        for (rank, (els, expected_els)) in self.elements.iter().zip(&expected.elements).enumerate() {
+            if els != expected_els {
+                return Some(format!("elements of rank {} differ", rank + 1));
+            }
+        }
+
+        if self.elements.len() != expected.elements.len() {
+            Some("ranks differ".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Runs a snapshot test, or updates its snapshot.
+fn check(case: &Case) -> Result<(), String> {
+    let mut poly = case.input.load();
+    for op in case.ops {
+        poly = op.apply(&poly)?;
+    }
+
+    let path = dir().join(case.name).with_extension("off");
+    if env::var_os(UPDATE_VAR).is_some() {
+        let off = poly.to_off(OffOptions {
+            comments: false,
+            ..Default::default()
+        }).unwrap();
+        fs::write(&path, off).unwrap();
+        return Ok(());
+    }
+
+    let src = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "couldn't read {}: {} (run with {} set to create it)",
+            path.display(),
+            err,
+            UPDATE_VAR
+        )
+    })?;
+    let expected = Concrete::from_off(&src).map_err(|err| err.to_string())?;
+
+    match Canonical::new(&poly).diff(&Canonical::new(&expected)) {
+        Some(diff) => Err(format!(
+            "{} (element counts {}, expected {})",
+            diff,
+            poly.el_counts(),
+            expected.el_counts()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks every operation chain against its snapshot.
+#[test]
+fn snapshots() {
+    let failures: Vec<_> = CASES
+        .iter()
+        .filter_map(|case| check(case).err().map(|err| format!("{}: {}", case.name, err)))
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} snapshots failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Checks that the hypercubes and orthoplices built from their coordinates are
+/// the same as those built as repeated products of dyads.
+#[test]
+fn direct_constructors() {
+    let mut hypercube = Concrete::point();
+    let mut orthoplex = Concrete::point();
+
+    for rank in 1..=6 {
+        if rank > 1 {
+            hypercube = hypercube.prism();
+            orthoplex = orthoplex.duotegum(&Concrete::dyad_with(f64::SQRT_2));
+        }
+
+        assert_eq!(
+            Canonical::new(&Concrete::hypercube(rank)).diff(&Canonical::new(&hypercube)),
+            None,
+            "hypercube of rank {}",
+            rank
+        );
+        assert_eq!(
+            Canonical::new(&Concrete::orthoplex(rank)).diff(&Canonical::new(&orthoplex)),
+            None,
+            "orthoplex of rank {}",
+            rank
+        );
+    }
+}
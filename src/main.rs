@@ -15,16 +15,16 @@ use bevy::prelude::*;
 use bevy::reflect::TypeUuid;
 use bevy::render::{camera::PerspectiveProjection, pipeline::PipelineDescriptor};
 use bevy_egui::EguiPlugin;
+use mesh::MeshOptions;
 use miratope_core::file::FromFile;
-use no_cull_pipeline::PbrNoBackfaceBundle;
 
 use ui::{
+    appearance::DefaultStyle,
     camera::{CameraInputEvent, ProjectionType},
+    main_window::{spawn_polytope, PolyName, Selected},
     MiratopePlugins,
 };
 
-use crate::mesh::Renderable;
-
 mod mesh;
 mod no_cull_pipeline;
 mod ui;
@@ -60,6 +60,16 @@ const EPS: Float = <Float as miratope_core::float::Float>::EPS;
 
 /// Loads all of the necessary systems for the application to run.
 fn main() {
+    // Runs a single command without the GUI.
+    if std::env::args().nth(1).as_deref() == Some("--headless") {
+        if let Err(err) = miratope_core::cli::run(std::env::args().skip(2)) {
+            eprintln!("{}", err);
+            std::process::exit(err.exit_code());
+        }
+
+        return;
+    }
+
     std::env::set_var("RUST_BACKTRACE", "full");
     App::new()
         .insert_resource(WindowDescriptor {
@@ -81,6 +91,8 @@ fn setup(
     mut materials: ResMut<'_, Assets<StandardMaterial>>,
     mut shaders: ResMut<'_, Assets<Shader>>,
     mut pipelines: ResMut<'_, Assets<PipelineDescriptor>>,
+    mesh_options: Res<'_, MeshOptions>,
+    default_style: Res<'_, DefaultStyle>,
 ) {
     // Default polytope.
     let mut poly = Concrete::from_off(include_str!("default.off")).unwrap();
@@ -91,10 +103,12 @@ fn setup(
     }
 
     // Disables backface culling.
+    let opaque = no_cull_pipeline::build_no_cull_pipeline(&mut shaders);
     pipelines.set_untracked(
-        no_cull_pipeline::NO_CULL_PIPELINE_HANDLE,
-        no_cull_pipeline::build_no_cull_pipeline(&mut shaders),
+        no_cull_pipeline::NO_CULL_TRANSPARENT_PIPELINE_HANDLE,
+        no_cull_pipeline::build_transparent_pipeline(&opaque),
     );
+    pipelines.set_untracked(no_cull_pipeline::NO_CULL_PIPELINE_HANDLE, opaque);
 
     // Selected object (unused as of yet).
     materials.set_untracked(
@@ -102,39 +116,24 @@ fn setup(
         Color::rgb_u8(126, 192, 255).into(),
     );
 
-    // Wireframe material.
-    let wf_material = materials.set(WIREFRAME_UNSELECTED_MATERIAL, Color::rgb_u8(150, 150, 150).into());
-
-    // Mesh material.
-    let mesh_material = materials.add(StandardMaterial {
-        base_color: Color::rgb_u8(255, 255, 255),
-        metallic: 0.0,
-        ..Default::default()
-    });
-
     // Camera configuration.
     let mut cam_anchor = Default::default();
     let mut cam = Default::default();
     CameraInputEvent::reset(&mut cam_anchor, &mut cam);
 
-    commands
-        .spawn()
-        // Mesh
-        .insert_bundle(PbrNoBackfaceBundle {
-            mesh: meshes.add(poly.mesh(ProjectionType::Perspective)),
-            material: mesh_material,
-            ..Default::default()
-        })
-        // Wireframe
-        .with_children(|cb| {
-            cb.spawn().insert_bundle(PbrNoBackfaceBundle {
-                mesh: meshes.add(poly.wireframe(ProjectionType::Perspective)),
-                material: wf_material,
-                ..Default::default()
-            });
-        })
-        // Polytope
-        .insert(poly);
+    // The polytope, selected by default.
+    let poly = spawn_polytope(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        poly,
+        PolyName::default(),
+        default_style.0,
+        Vec3::ZERO,
+        &ProjectionType::Perspective,
+        &mesh_options,
+    );
+    commands.entity(poly).insert(Selected);
 
     // Camera anchor
     commands
@@ -166,5 +165,3 @@ fn setup(
 
 const WIREFRAME_SELECTED_MATERIAL: HandleUntyped =
     HandleUntyped::weak_from_u64(StandardMaterial::TYPE_UUID, 0x82A3A5DD3A34CC21);
-const WIREFRAME_UNSELECTED_MATERIAL: HandleUntyped =
-    HandleUntyped::weak_from_u64(StandardMaterial::TYPE_UUID, 0x82A3A5DD3A34CC22);
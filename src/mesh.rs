@@ -1,54 +1,224 @@
 //! Contains the methods that take a polytope and turn it into a mesh.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use crate::ui::camera::ProjectionType;
-use crate::{Concrete, Float, Point, EPS};
+use crate::{Concrete, Float, Hyperplane, Hypersphere, Point, Vector, EPS};
 
 use bevy::{
-    prelude::Mesh,
+    math::Vec3,
+    prelude::{Mesh, Transform},
     render::{mesh::Indices, pipeline::PrimitiveTopology},
 };
 use lyon::{math::point, path::Path, tessellation::*};
 use miratope_core::conc::cycle::{Cycle, CycleList};
 use miratope_core::{
     abs::{ElementList, Ranked},
-    conc::ConcretePolytope,
-    geometry::{Subspace, Vector},
+    conc::{ConcretePolytope, SKEW_PLANARITY},
+    geometry::Subspace,
 };
 
 use vec_like::*;
 
-/// Attempts to turn the cycle into a 2D path, which can then be given to
-/// the tessellator. Uses the specified vertex list to grab the coordinates
-/// of the vertices on the path.
-///
-/// If the cycle isn't 2D, we return `None`.
-pub fn path(cycle: &Cycle, vertices: &[Point]) -> Option<Path> {
-    let mut builder = Path::builder();
-    let cycle_iter = cycle.iter().map(|&idx| &vertices[idx]);
+/// The rule used to decide which regions of a self-intersecting face, such as
+/// a pentagram, get filled in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindingRule {
+    /// Fills the regions with an odd winding number. The core of a {5/2} is
+    /// left hollow.
+    EvenOdd,
+
+    /// Fills the regions with a nonzero winding number. The core of a {5/2}
+    /// is filled in.
+    NonZero,
+}
+
+impl Default for WindingRule {
+    fn default() -> Self {
+        Self::NonZero
+    }
+}
 
-    // We don't bother with any polygons that aren't in 2D space.
-    let s = Subspace::from_points_with(cycle_iter.clone(), 2)?;
-    if s.rank() != 2 {
-        return None
+impl From<WindingRule> for FillRule {
+    fn from(rule: WindingRule) -> Self {
+        match rule {
+            WindingRule::EvenOdd => FillRule::EvenOdd,
+            WindingRule::NonZero => FillRule::NonZero,
+        }
     }
+}
+
+/// The default number of triangles in each chunk of a mesh.
+pub const DEFAULT_CHUNK_SIZE: usize = 1 << 16;
+
+/// The options that configure how a polytope is turned into a mesh.
+#[derive(Clone, Copy, Debug)]
+pub struct MeshOptions {
+    /// The rule used to fill self-intersecting faces.
+    pub winding_rule: WindingRule,
+
+    /// The maximum number of triangles in each chunk of a mesh, or of edges
+    /// in each chunk of a wireframe. Large polytopes are split into several
+    /// meshes, so that no single one of them gets too big.
+    pub chunk_size: usize,
+
+    /// Whether the triangles of translucent faces are sorted back to front as
+    /// the camera moves. Turning this off is faster, but faces behind others
+    /// might get drawn over them.
+    pub depth_sort: bool,
 
-    let mut flat_points = cycle_iter.map(|p| s.flatten(&p));
+    /// How many times the edges and faces are subdivided before a
+    /// [stereographic projection](Stereographic), so that they follow the
+    /// curved images of their straight counterparts. Every level halves each
+    /// edge and splits each triangle into four.
+    pub refinement: usize,
+}
+
+impl Default for MeshOptions {
+    fn default() -> Self {
+        Self {
+            winding_rule: Default::default(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            depth_sort: true,
+            refinement: 2,
+        }
+    }
+}
 
-    let path_point = |v: &Point| point(v[0] as f32, v[1] as f32);
+/// The [planarity](ConcretePolytope::face_planarity) above which a face is
+/// considered skew. Skew faces are triangulated as a fan from their centroid,
+/// while every other face is flattened into its best-fit plane.
+const SKEW_THRESHOLD: Float = SKEW_PLANARITY;
 
-    // We build a path from the polygon.
-    let v = flat_points.next().unwrap();
-    builder.begin(path_point(&v));
+/// Returns the parameters at which two segments in the plane properly cross,
+/// or `None` if they don't. Crossings at or very near the endpoints are
+/// ignored.
+fn segment_intersection(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> Option<(Float, Float)> {
+    let da = a1 - a0;
+    let db = b1 - b0;
+    let denom = da[0] * db[1] - da[1] * db[0];
 
-    for v in flat_points {
-        builder.line_to(path_point(&v));
+    // The segments are parallel.
+    if denom.abs() < EPS {
+        return None;
     }
 
-    builder.end(true);
+    let d = b0 - a0;
+    let s = (d[0] * db[1] - d[1] * db[0]) / denom;
+    let t = (d[0] * da[1] - d[1] * da[0]) / denom;
 
-    Some(builder.build())
+    (EPS < s && s < 1.0 - EPS && EPS < t && t < 1.0 - EPS).then(|| (s, t))
+}
+
+/// A face cycle flattened into its own plane, where every crossing between two
+/// of its edges has been turned into an explicit vertex.
+struct FlatPolygon {
+    /// The indices of the vertices along the polygon. Indices past the
+    /// polytope's vertex count refer to the extra vertices of the
+    /// triangulation.
+    indices: Vec<u32>,
+
+    /// The 2D coordinates of the vertices along the polygon.
+    points: Vec<Point>,
+}
+
+impl FlatPolygon {
+    /// Flattens a cycle into its best-fit plane and computes the arrangement
+    /// of its edges, adding the crossing points into the extra vertices.
+    /// Returns `None` if the cycle doesn't live in at least 2D space.
+    fn new(cycle: &Cycle, vertices: &[Point], extra_vertices: &mut Vec<Point>) -> Option<Self> {
+        let cycle_iter = cycle.iter().map(|&idx| &vertices[idx]);
+        let s = Subspace::fit_points(cycle_iter.clone(), 2)?;
+
+        let flat_points: Vec<_> = cycle_iter.map(|p| s.flatten(p)).collect();
+        let len = flat_points.len();
+
+        // The crossings along each edge, as pairs of the position along the
+        // edge and the index of the crossing point.
+        let mut crossings = vec![Vec::new(); len];
+        let mut crossing_points = Vec::new();
+
+        // Sweeps the edges from left to right, so that only edges whose
+        // horizontal extents overlap are tested against each other. This
+        // keeps faces with huge numbers of vertices from taking quadratic time.
+        let extent = |i: usize| {
+            let (x0, x1) = (flat_points[i][0], flat_points[(i + 1) % len][0]);
+            (x0.min(x1), x0.max(x1))
+        };
+        let mut order: Vec<usize> = (0..len).collect();
+        order.sort_unstable_by(|&i, &j| extent(i).0.partial_cmp(&extent(j).0).unwrap());
+        let mut active: Vec<usize> = Vec::new();
+
+        for &edge in &order {
+            let min_x = extent(edge).0;
+            active.retain(|&other| extent(other).1 >= min_x);
+
+            for &other in &active {
+                let (i, j) = (edge.min(other), edge.max(other));
+
+                // Adjacent edges can't properly cross.
+                if j == i + 1 || (i == 0 && j == len - 1) {
+                    continue;
+                }
+
+                let (a0, a1) = (&flat_points[i], &flat_points[(i + 1) % len]);
+                let (b0, b1) = (&flat_points[j], &flat_points[(j + 1) % len]);
+                if let Some((s, t)) = segment_intersection(a0, a1, b0, b1) {
+                    let idx = (vertices.len() + extra_vertices.len()) as u32;
+                    let from = &vertices[cycle[i]];
+                    let to = &vertices[cycle[(i + 1) % len]];
+                    extra_vertices.push(from * (1.0 - s) + to * s);
+
+                    crossings[i].push((s, idx));
+                    crossings[j].push((t, idx));
+                    crossing_points.push(a0 * (1.0 - s) + a1 * s);
+                }
+            }
+
+            active.push(edge);
+        }
+
+        // Walks the cycle, inserting the crossings on each edge in order.
+        let mut indices = Vec::with_capacity(len + 2 * crossing_points.len());
+        let mut points = Vec::with_capacity(len + 2 * crossing_points.len());
+        let first_crossing = (vertices.len() + extra_vertices.len() - crossing_points.len()) as u32;
+
+        for (i, edge_crossings) in crossings.iter_mut().enumerate() {
+            indices.push(cycle[i] as u32);
+            points.push(flat_points[i].clone());
+
+            edge_crossings.sort_unstable_by(|(s, _), (t, _)| s.partial_cmp(t).unwrap());
+            for &(_, idx) in edge_crossings.iter() {
+                indices.push(idx);
+                points.push(crossing_points[(idx - first_crossing) as usize].clone());
+            }
+        }
+
+        Some(Self { indices, points })
+    }
+
+    /// Returns whether the original cycle crossed itself.
+    fn is_self_intersecting(&self, cycle: &Cycle) -> bool {
+        self.indices.len() != cycle.len()
+    }
+
+    /// Turns the polygon into a path, which can then be given to the
+    /// tessellator.
+    fn path(&self) -> Path {
+        let mut builder = Path::builder();
+        let path_point = |v: &Point| point(v[0] as f32, v[1] as f32);
+
+        // We build a path from the polygon.
+        let mut points = self.points.iter();
+        builder.begin(path_point(points.next().unwrap()));
+
+        for v in points {
+            builder.line_to(path_point(v));
+        }
+
+        builder.end(true);
+        builder.build()
+    }
 }
 
 /// Represents a triangulation of the faces of a [`Concrete`]. It stores the
@@ -60,13 +230,17 @@ struct Triangulation {
 
     /// Indices of the vertices that make up the triangles.
     triangles: Vec<u32>,
+
+    /// The index of the face that each triangle belongs to.
+    triangle_faces: Vec<usize>,
 }
 
 impl Triangulation {
     /// Creates a new triangulation from a polytope.
-    fn new(polytope: &Concrete) -> Self {
+    fn new(polytope: &Concrete, options: &MeshOptions) -> Self {
         let mut extra_vertices = Vec::new();
         let mut triangles = Vec::new();
+        let mut triangle_faces = Vec::new();
         let empty_els = ElementList::new();
 
         // Either returns a reference to the element list of a given rank, or
@@ -77,22 +251,46 @@ impl Triangulation {
         let faces = elements_or(3);
 
         let concrete_vertex_len = polytope.vertices.len() as u32;
+        let planarity = polytope.face_planarity();
 
         // We render each face separately.
-        for face in faces {
+        for (idx, face) in faces.iter().enumerate() {
             // We tesselate this path.
             let cycles = CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs));
             for cycle in cycles {
-                if let Some(path) = path(&cycle, &polytope.vertices) {
+                // Skew faces are triangulated from their centroid.
+                if planarity.get(idx).map_or(false, |&x| x > SKEW_THRESHOLD) {
+                    let len = cycle.len();
+                    let centroid = cycle.iter().map(|&i| &polytope.vertices[i]).sum::<Point>()
+                        / len as Float;
+                    let center = concrete_vertex_len + extra_vertices.len() as u32;
+                    extra_vertices.push(centroid);
+
+                    for i in 0..len {
+                        triangles.push(center);
+                        triangles.push(cycle[i] as u32);
+                        triangles.push(cycle[(i + 1) % len] as u32);
+                    }
+                } else if let Some(polygon) =
+                    FlatPolygon::new(&cycle, &polytope.vertices, &mut extra_vertices)
+                {
+                    let path = polygon.path();
                     let mut geometry: VertexBuffers<_, u32> = VertexBuffers::new();
 
+                    // Simple polygons are filled the same under either rule.
+                    let fill_rule = if polygon.is_self_intersecting(&cycle) {
+                        options.winding_rule.into()
+                    } else {
+                        FillRule::NonZero
+                    };
+
                     // Configures all of the options of the tessellator.
                     FillTessellator::new()
                         .tessellate_with_ids(
                             path.id_iter(),
                             &path,
                             None,
-                            &FillOptions::with_fill_rule(Default::default(), FillRule::NonZero)
+                            &FillOptions::with_fill_rule(Default::default(), fill_rule)
                                 .with_tolerance(EPS as f32),
                             &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex<'_>| {
                                 vertex.sources().next().unwrap()
@@ -100,11 +298,8 @@ impl Triangulation {
                         )
                         .unwrap();
 
-                    // Maps EndpointIds to the indices in the original vertex list.
-                    let mut id_to_idx = Vec::new();
-                    for idx in cycle {
-                        id_to_idx.push(idx);
-                    }
+                    // Maps EndpointIds to the indices in the vertex list.
+                    let id_to_idx = polygon.indices;
 
                     // We map the output vertices to the original ones, and add any
                     // extra vertices that may be needed.
@@ -114,15 +309,23 @@ impl Triangulation {
                         let new_id = new_id as u32;
 
                         match vertex_source {
-                            // This is one of the concrete vertices of the polytope.
+                            // This is one of the vertices of the polygon.
                             VertexSource::Endpoint { id } => {
-                                vertex_hash.insert(new_id, id_to_idx[id.to_usize()] as u32);
+                                vertex_hash.insert(new_id, id_to_idx[id.to_usize()]);
                             }
 
                             // This is a new vertex that has been added to the tesselation.
                             VertexSource::Edge { from, to, t } => {
-                                let from = &polytope.vertices[id_to_idx[from.to_usize()]];
-                                let to = &polytope.vertices[id_to_idx[to.to_usize()]];
+                                let vertex = |idx: u32| {
+                                    if idx < concrete_vertex_len {
+                                        &polytope.vertices[idx as usize]
+                                    } else {
+                                        &extra_vertices[(idx - concrete_vertex_len) as usize]
+                                    }
+                                };
+
+                                let from = vertex(id_to_idx[from.to_usize()]);
+                                let to = vertex(id_to_idx[to.to_usize()]);
 
                                 let t = t as Float;
                                 let p = from * (1.0 - t) + to * t;
@@ -145,11 +348,15 @@ impl Triangulation {
                     }
                 }
             }
+
+            // Records the face of every triangle we just added.
+            triangle_faces.resize(triangles.len() / 3, idx);
         }
 
         Self {
             extra_vertices,
             triangles,
+            triangle_faces,
         }
     }
 }
@@ -182,12 +389,313 @@ fn empty_mesh() -> Mesh {
     mesh
 }
 
-/// Gets the coordinates of the vertices, after projecting down into 3D.
-fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
+/// Renumbers the vertices used by a chunk of a list of primitives, in the
+/// order in which they first appear. Returns the new indices of the chunk,
+/// together with the vertices it uses.
+fn chunk_vertices(vertices: &[[f32; 3]], chunk: &[u32]) -> (Vec<u32>, Vec<[f32; 3]>) {
+    // Maps the indices of the vertices to their indices in the chunk.
+    let mut new_indices = HashMap::new();
+    let mut chunk_vertices = Vec::new();
+    let chunk_indices = chunk
+        .iter()
+        .map(|&idx| {
+            *new_indices.entry(idx).or_insert_with(|| {
+                chunk_vertices.push(vertices[idx as usize]);
+                chunk_vertices.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    (chunk_indices, chunk_vertices)
+}
+
+/// Returns the index buffer for a chunk, which uses 16-bit indices whenever
+/// these suffice for its vertices.
+fn chunk_index_buffer(indices: Vec<u32>, vertex_count: usize) -> Indices {
+    if vertex_count <= u16::MAX as usize + 1 {
+        Indices::U16(indices.into_iter().map(|idx| idx as u16).collect())
+    } else {
+        Indices::U32(indices)
+    }
+}
+
+/// Splits a list of primitives, given as consecutive groups of indices into a
+/// list of vertices, into meshes with at most `chunk_size` primitives each.
+/// Every mesh only stores the vertices that its primitives use, and uses
+/// 16-bit indices whenever these suffice.
+///
+/// Always returns at least one mesh.
+fn chunked_meshes(
+    vertices: &[[f32; 3]],
+    indices: &[u32],
+    group_len: usize,
+    chunk_size: usize,
+    topology: PrimitiveTopology,
+) -> Vec<Mesh> {
+    if indices.is_empty() {
+        return vec![empty_mesh()];
+    }
+
+    indices
+        .chunks(group_len * chunk_size.max(1))
+        .map(|chunk| {
+            let (chunk_indices, chunk_vertices) = chunk_vertices(vertices, chunk);
+
+            let mut mesh = Mesh::new(topology);
+            mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; chunk_vertices.len()]);
+            mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&chunk_vertices));
+            mesh.set_indices(Some(chunk_index_buffer(chunk_indices, chunk_vertices.len())));
+            mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, chunk_vertices);
+
+            mesh
+        })
+        .collect()
+}
+
+/// How far the camera has to move, in the coordinates of a mesh, before its
+/// triangles are sorted again.
+const RESORT_DISTANCE: f32 = 1e-2;
+
+/// The data needed to draw the triangles of a chunk of faces from back to
+/// front, so that translucent faces are blended in the right order.
+///
+/// The centroids of the triangles are only computed once, so that sorting
+/// again as the camera moves only needs a new permutation of the triangles.
+pub struct DepthSort {
+    /// The indices of the triangles of the chunk in their original order, in
+    /// groups of three.
+    triangles: Vec<u32>,
+
+    /// The centroid of every triangle of the chunk.
+    centroids: Vec<Vec3>,
+
+    /// The number of vertices of the chunk.
+    vertex_count: usize,
+
+    /// The position of the camera the triangles were last sorted for, in the
+    /// coordinates of the mesh.
+    eye: Option<Vec3>,
+}
+
+impl DepthSort {
+    /// Returns whether the camera has moved far enough from where it was when
+    /// the triangles were last sorted.
+    pub fn needs_sort(&self, eye: Vec3) -> bool {
+        self.eye.map_or(true, |old| old.distance(eye) > RESORT_DISTANCE)
+    }
+
+    /// Returns the order in which the triangles are drawn, from the furthest
+    /// to the closest to the camera.
+    pub fn order(&self, eye: Vec3) -> Vec<usize> {
+        let keys: Vec<_> = self
+            .centroids
+            .iter()
+            .map(|c| c.distance_squared(eye))
+            .collect();
+
+        let mut order: Vec<_> = (0..keys.len()).collect();
+        order.sort_unstable_by(|&i, &j| keys[j].partial_cmp(&keys[i]).unwrap());
+        order
+    }
+
+    /// Sorts the triangles for a given camera position, and returns the new
+    /// index buffer of the chunk.
+    pub fn sort(&mut self, eye: Vec3) -> Indices {
+        self.eye = Some(eye);
+
+        let indices = self
+            .order(eye)
+            .into_iter()
+            .flat_map(|t| self.triangles[3 * t..3 * t + 3].iter().copied())
+            .collect();
+
+        chunk_index_buffer(indices, self.vertex_count)
+    }
+}
+
+/// Returns the centroid of every triangle, given as consecutive groups of
+/// three indices into a list of vertices.
+fn triangle_centroids(vertices: &[[f32; 3]], triangles: &[u32]) -> Vec<[f32; 3]> {
+    triangles
+        .chunks(3)
+        .map(|t| {
+            let [a, b, c] = [0, 1, 2].map(|i| vertices[t[i] as usize]);
+            [0, 1, 2].map(|i| (a[i] + b[i] + c[i]) / 3.0)
+        })
+        .collect()
+}
+
+/// Returns the distance from the projection planes used to project a
+/// polytope of at least four dimensions down into 3D. The projection point
+/// has this distance subtracted from every coordinate past the third.
+fn projection_distance(poly: &Concrete) -> Float {
+    let mut direction = Vector::zeros(poly.dim_or());
+    direction[3] = 1.0;
+
+    let (min, max) = poly.minmax(direction).unwrap();
+    (min - 1.0).abs().max(max + 1.0).abs()
+}
+
+/// The most times the edges and faces can be subdivided before a
+/// stereographic projection.
+pub const MAX_REFINEMENT: usize = 4;
+
+/// Points whose angle from the pole of a stereographic projection has a cosine
+/// closer than this to 1 would be projected too far away to be drawn, and are
+/// left out instead.
+const POLE_CUTOFF: Float = 1e-3;
+
+/// The reasons a polytope can't be projected stereographically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StereographicError {
+    /// The vertices of the polytope don't lie on a common hypersphere.
+    NoCircumsphere,
+
+    /// The pole is zero, or doesn't have as many coordinates as the polytope
+    /// has dimensions.
+    InvalidPole,
+}
+
+impl Display for StereographicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoCircumsphere => write!(f, "the polytope has no circumsphere to project from"),
+            Self::InvalidPole => write!(
+                f,
+                "the pole of the projection doesn't match the dimension of the polytope"
+            ),
+        }
+    }
+}
+
+/// The stereographic projection of the circumsphere of a polytope from a
+/// pole, onto the hyperplane through its center orthogonal to the pole.
+///
+/// Points are first moved radially onto the circumsphere, so that the
+/// midpoints added when [refining](MeshOptions::refinement) edges and faces
+/// end up on the images of the great circles through their endpoints.
+pub struct Stereographic {
+    /// The circumsphere of the polytope.
+    sphere: Hypersphere,
+
+    /// The unit vector from the center of the circumsphere to the pole.
+    pole: Vector,
+
+    /// The hyperplane through the origin orthogonal to the pole, in whose
+    /// basis the projected points are given.
+    hyperplane: Hyperplane,
+}
+
+impl Stereographic {
+    /// Sets up the stereographic projection of a polytope from a pole, given
+    /// as a vector from the center of its circumsphere.
+    pub fn new(poly: &Concrete, pole: &Vector) -> Result<Self, StereographicError> {
+        if pole.len() != poly.dim_or() || pole.len() < 2 {
+            return Err(StereographicError::InvalidPole);
+        }
+        let pole = pole
+            .try_normalize(EPS)
+            .ok_or(StereographicError::InvalidPole)?;
+
+        let sphere = (poly.vertex_count() != 0)
+            .then(|| poly.circumsphere())
+            .flatten()
+            .filter(|sphere| sphere.radius() > EPS)
+            .ok_or(StereographicError::NoCircumsphere)?;
+
+        Ok(Self {
+            sphere,
+            hyperplane: Hyperplane::new(pole.clone(), 0.0),
+            pole,
+        })
+    }
+
+    /// Returns the point of the circumsphere that's projected from.
+    pub fn pole_point(&self) -> Point {
+        &self.sphere.center + &self.pole * self.sphere.radius()
+    }
+
+    /// Projects a point, whose first three coordinates in the basis of the
+    /// hyperplane are returned. Points at the center of the circumsphere or
+    /// too close to the pole give `NaN` coordinates.
+    pub fn project(&self, p: &Point) -> [Float; 3] {
+        let mut q = p - &self.sphere.center;
+        q /= q.norm();
+
+        let height = q.dot(&self.pole);
+        if height.is_nan() || 1.0 - height < POLE_CUTOFF {
+            return [Float::NAN; 3];
+        }
+
+        let s = (q - &self.pole * height) * (self.sphere.radius() / (1.0 - height));
+        let coords = self.hyperplane.flatten(&s);
+        [0, 1, 2].map(|i| coords.get(i).copied().unwrap_or_default())
+    }
+
+    /// Returns the direction in which each coordinate axis points at the
+    /// center of the image, which is the point opposite to the pole.
+    fn axis_images(&self, dim: usize) -> Vec<AxisImage> {
+        (0..dim.max(3))
+            .map(|i| {
+                let mut axis = Vector::zeros(dim);
+                if i < dim {
+                    axis[i] = 1.0;
+                }
+
+                let coords = self.hyperplane.flatten(&axis);
+                let direction = [0, 1, 2].map(|j| coords.get(j).copied().unwrap_or_default());
+                if direction.iter().all(|x| x.abs() < EPS) {
+                    AxisImage::Hidden
+                } else {
+                    AxisImage::Direction(direction)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sets up the stereographic projection of a polytope, if that's the
+/// projection type and the polytope has a circumsphere.
+fn stereographic(poly: &Concrete, projection_type: &ProjectionType) -> Option<Stereographic> {
+    Stereographic::new(poly, projection_type.pole()?).ok()
+}
+
+/// Returns how far a point of a polytope of at least four dimensions is from
+/// the viewpoint, for the purpose of ordering its facets from the closest to
+/// the furthest. This is the distance to the projection point under a
+/// perspective projection, or the fourth coordinate under an orthogonal one.
+/// Under a stereographic projection, this is the distance to the pole.
+pub fn view_distance(
+    poly: &Concrete,
+    projection_type: &ProjectionType,
+) -> impl Fn(&Point) -> Float {
+    let pole = stereographic(poly, projection_type).map(|s| s.pole_point());
+    let dist = (!projection_type.is_orthogonal()).then(|| projection_distance(poly));
+
+    move |p: &Point| match (&pole, dist) {
+        (Some(pole), _) => (p - pole).norm(),
+        (None, Some(dist)) => p
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| if i < 3 { x * x } else { (x + dist) * (x + dist) })
+            .sum::<Float>()
+            .sqrt(),
+        (None, None) => p[3],
+    }
+}
+
+/// Gets the coordinates of the vertices, after projecting down into 3D. A
+/// stereographic projection of a polytope without a circumsphere falls back
+/// to a perspective one.
+pub fn projected_coords<'a, I: Iterator<Item = &'a Point>>(
     poly: &Concrete,
     vertices: I,
-    projection_type: ProjectionType,
-) -> Vec<[f32; 3]> {
+    projection_type: &ProjectionType,
+) -> Vec<[Float; 3]> {
+    if let Some(stereographic) = stereographic(poly, projection_type) {
+        return vertices.map(|p| stereographic.project(p)).collect();
+    }
+
     let dim = poly.dim_or();
 
     // Returns the ith coordinate of p, or 0 if it doesn't exist.
@@ -195,75 +703,356 @@ fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
 
     // If the polytope is at most 3D, we just embed it into 3D space.
     if projection_type.is_orthogonal() || dim <= 3 {
-        vertices.map(|p| [0, 1, 2].map(|i| coord(p, i) as f32)).collect()
+        vertices.map(|p| [0, 1, 2].map(|i| coord(p, i))).collect()
     }
     // Else, we project it down.
     else {
-        // Distance from the projection planes.
-        let mut direction = Vector::zeros(dim);
-        direction[3] = 1.0;
-
-        let (min, max) = poly.minmax(direction).unwrap();
-        let dist = (min as f32 - 1.0).abs().max(max as f32 + 1.0).abs();
+        let dist = projection_distance(poly);
 
         vertices
             .map(|p| {
                 // We scale the first three coordinates accordingly.
-                let factor: f32 = p.iter().skip(3).map(|&x| x as f32 + dist).product();
-                [0, 1, 2].map(|i| coord(p, i) as f32 / factor)
+                let factor: Float = p.iter().skip(3).map(|&x| x + dist).product();
+                [0, 1, 2].map(|i| coord(p, i) / factor)
             })
             .collect()
     }
 }
 
-/// A trait for a polytope for which we can build a mesh.
-pub trait Renderable: ConcretePolytope {
-    /// Builds the mesh of a polytope.
-    fn mesh(&self, projection_type: ProjectionType) -> Mesh {
-        // If there's no vertices, returns an empty mesh.
-        if self.vertex_count() == 0 {
-            return empty_mesh();
-        }
-
-        // Triangulates the polytope's faces, projects the vertices of both the
-        // polytope and the triangulation.
-        let triangulation = Triangulation::new(self.con());
-        let vertices = vertex_coords(
-            self.con(),
-            self.vertices()
-                .iter()
-                .chain(triangulation.extra_vertices.iter()),
-            projection_type,
-        );
+/// Where a coordinate axis of a polytope ends up once it's projected down
+/// into 3D.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisImage {
+    /// The axis points in a direction of 3D space.
+    Direction([Float; 3]),
+
+    /// The axis is projected from a point, so that moving along it moves the
+    /// image of a point towards the center. Moving a point whose coordinate on
+    /// this axis is zero by the bounding radius of the polytope scales its
+    /// image by this factor.
+    Radial(Float),
 
-        // Builds the actual mesh.
-        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
-        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; vertices.len()]);
-        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
-        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_indices(Some(Indices::U32(triangulation.triangles)));
+    /// The axis is projected away, so that moving along it doesn't move the
+    /// image of a point at all.
+    Hidden,
+}
 
-        mesh
+/// Returns where each coordinate axis of a polytope ends up once it's
+/// projected down into 3D, the same way [`projected_coords`] projects its
+/// vertices. There are always at least three axes. Under a stereographic
+/// projection, these are the directions at the center of the image.
+pub fn axis_images(poly: &Concrete, projection_type: &ProjectionType) -> Vec<AxisImage> {
+    let dim = poly.dim_or();
+    if let Some(stereographic) = stereographic(poly, projection_type) {
+        return stereographic.axis_images(dim);
     }
 
-    /// Builds the wireframe of a polytope.
-    fn wireframe(&self, projection_type: ProjectionType) -> Mesh {
-        let vertex_count = self.vertex_count();
+    let radial = (!projection_type.is_orthogonal() && dim > 3)
+        .then(|| poly.bounding_radius())
+        .flatten()
+        .map(|radius| {
+            let dist = projection_distance(poly);
+            dist / (dist + radius)
+        });
+
+    (0..dim.max(3))
+        .map(|i| {
+            if i < 3 {
+                let mut direction = [0.0; 3];
+                direction[i] = 1.0;
+                AxisImage::Direction(direction)
+            } else {
+                radial.map_or(AxisImage::Hidden, AxisImage::Radial)
+            }
+        })
+        .collect()
+}
+
+/// Gets the coordinates of the vertices, after projecting down into 3D, as
+/// they're placed in the world.
+pub fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
+    poly: &Concrete,
+    vertices: I,
+    projection_type: &ProjectionType,
+) -> Vec<[f32; 3]> {
+    projected_coords(poly, vertices, projection_type)
+        .into_iter()
+        .map(|p| p.map(|x| x as f32))
+        .collect()
+}
+
+/// The frame in which the meshes of a polytope are built. Coordinates far from
+/// the origin lose precision once they're converted into `f32`, which makes
+/// the mesh jitter as the camera moves. So instead, the projected coordinates
+/// are moved so that their bounding box is centered at the origin, and scaled
+/// down so that it fits in the unit cube. The entity of the polytope gets the
+/// opposite [`Transform`], so that it still shows up in the same place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderFrame {
+    /// The center of the bounding box of the projected coordinates.
+    pub center: [Float; 3],
 
-        // If there's no vertices, returns an empty mesh.
-        if vertex_count == 0 {
-            return empty_mesh();
+    /// Half the longest side of the bounding box, or 1 if it's degenerate.
+    pub scale: Float,
+}
+
+impl Default for RenderFrame {
+    fn default() -> Self {
+        Self {
+            center: [0.0; 3],
+            scale: 1.0,
+        }
+    }
+}
+
+impl RenderFrame {
+    /// Fits a frame around a set of projected coordinates.
+    pub fn new<'a, I: IntoIterator<Item = &'a [Float; 3]>>(coords: I) -> Self {
+        let mut min = [Float::INFINITY; 3];
+        let mut max = [Float::NEG_INFINITY; 3];
+        for p in coords {
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
         }
 
-        let edge_count = self.edge_count();
+        // There were no coordinates.
+        if min[0] > max[0] {
+            return Self::default();
+        }
 
-        // We add a single vertex so that Miratope doesn't crash.
-        let vertices = vertex_coords(self.con(), self.vertices().iter(), projection_type);
-        let mut indices = Vec::with_capacity(edge_count * 2);
+        let scale = (0..3).map(|i| (max[i] - min[i]) / 2.0).fold(0.0, Float::max);
+        Self {
+            center: [0, 1, 2].map(|i| (min[i] + max[i]) / 2.0),
+            scale: if scale.is_normal() { scale } else { 1.0 },
+        }
+    }
+
+    /// Converts projected coordinates into the coordinates of the mesh.
+    pub fn to_mesh(&self, p: [Float; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|i| ((p[i] - self.center[i]) / self.scale) as f32)
+    }
+
+    /// Returns the transform that places a mesh built in this frame, so that
+    /// the origin of the projected coordinates ends up at a given point.
+    pub fn transform(&self, placement: Vec3) -> Transform {
+        let [x, y, z] = self.center.map(|c| c as f32);
+
+        Transform {
+            translation: placement + Vec3::new(x, y, z),
+            scale: Vec3::splat(self.scale as f32),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the point where the origin of the projected coordinates ends up
+    /// under the transform of a mesh built in this frame. This is the inverse
+    /// of [`Self::transform`].
+    pub fn placement(&self, transform: &Transform) -> Vec3 {
+        let [x, y, z] = self.center.map(|c| c as f32);
+        transform.translation - Vec3::new(x, y, z)
+    }
+}
+
+/// Builds a single mesh out of a list of segments, given as pairs of indices
+/// into a list of vertices.
+pub fn segment_mesh(vertices: &[[f32; 3]], segments: &[u32]) -> Mesh {
+    chunked_meshes(
+        vertices,
+        segments,
+        2,
+        segments.len(),
+        PrimitiveTopology::LineList,
+    )
+    .swap_remove(0)
+}
 
-        // Adds the edges to the wireframe.
-        if let Some(edges) = self.get_element_list(2) {
-            for edge in edges {
+/// Subdivides the edges and triangles of a polytope before a stereographic
+/// projection. The midpoint of every segment is added as a synthetic vertex,
+/// which the projection then moves onto the circumsphere.
+struct Refinement<'a> {
+    /// The vertices of the polytope.
+    vertices: &'a [Point],
+
+    /// The synthetic vertices, to which the midpoints are added.
+    extra_vertices: &'a mut Vec<Point>,
+
+    /// The index of the midpoint of every segment that's been split, so that
+    /// the triangles and segments on either side of it share it.
+    midpoints: HashMap<(u32, u32), u32>,
+}
+
+impl<'a> Refinement<'a> {
+    /// Returns the point with a given index among the real and the synthetic
+    /// vertices.
+    fn point(&self, idx: u32) -> &Point {
+        let idx = idx as usize;
+        match idx.checked_sub(self.vertices.len()) {
+            Some(idx) => &self.extra_vertices[idx],
+            None => &self.vertices[idx],
+        }
+    }
+
+    /// Returns the index of the midpoint of two vertices, adding it if it
+    /// doesn't exist yet.
+    fn midpoint(&mut self, a: u32, b: u32) -> u32 {
+        let key = (a.min(b), a.max(b));
+        if let Some(&idx) = self.midpoints.get(&key) {
+            return idx;
+        }
+
+        let midpoint = (self.point(a) + self.point(b)) / 2.0;
+        self.extra_vertices.push(midpoint);
+        let idx = (self.vertices.len() + self.extra_vertices.len() - 1) as u32;
+        self.midpoints.insert(key, idx);
+        idx
+    }
+
+    /// Splits every triangle into four and every segment into two, keeping
+    /// track of the faces and edges they belong to.
+    fn refine(
+        &mut self,
+        triangles: &mut Vec<u32>,
+        triangle_faces: &mut Vec<usize>,
+        edges: &mut Vec<u32>,
+        segment_edges: &mut Vec<usize>,
+    ) {
+        let mut new_triangles = Vec::with_capacity(4 * triangles.len());
+        let mut new_faces = Vec::with_capacity(4 * triangle_faces.len());
+        for (triangle, &face) in triangles.chunks(3).zip(triangle_faces.iter()) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            let ab = self.midpoint(a, b);
+            let bc = self.midpoint(b, c);
+            let ca = self.midpoint(c, a);
+
+            new_triangles.extend_from_slice(&[a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+            new_faces.extend_from_slice(&[face; 4]);
+        }
+
+        let mut new_edges = Vec::with_capacity(2 * edges.len());
+        let mut new_segment_edges = Vec::with_capacity(2 * segment_edges.len());
+        for (segment, &edge) in edges.chunks(2).zip(segment_edges.iter()) {
+            let [a, b] = [segment[0], segment[1]];
+            let ab = self.midpoint(a, b);
+
+            new_edges.extend_from_slice(&[a, ab, ab, b]);
+            new_segment_edges.extend_from_slice(&[edge; 2]);
+        }
+
+        *triangles = new_triangles;
+        *triangle_faces = new_faces;
+        *edges = new_edges;
+        *segment_edges = new_segment_edges;
+    }
+}
+
+/// The geometry that's drawn for a polytope, projected down into 3D. Every
+/// consumer of the drawn polytope reads from this, so that they all agree on
+/// which vertices are real.
+///
+/// Besides the vertices of the polytope, the triangulation might add
+/// synthetic vertices, such as the centroids of skew faces, or the crossing
+/// points of star faces, and so might the [refinement](MeshOptions::refinement)
+/// of a stereographic projection. These come after the real vertices.
+pub struct RenderGeometry {
+    /// The number of real vertices, which are the first ones in
+    /// [`Self::positions`].
+    pub real_vertex_count: usize,
+
+    /// The positions of the real vertices, followed by those of the synthetic
+    /// vertices, in the coordinates of [`Self::frame`].
+    pub positions: Vec<[f32; 3]>,
+
+    /// The frame in which the positions are given.
+    pub frame: RenderFrame,
+
+    /// The indices of the vertices whose coordinates aren't finite, either
+    /// before or after the projection. These, and the edges and faces through
+    /// them, are left out.
+    pub non_finite: Vec<usize>,
+
+    /// The indices of the vertices of every triangle, in groups of three.
+    pub triangles: Vec<u32>,
+
+    /// The index of the face that each triangle belongs to.
+    pub triangle_faces: Vec<usize>,
+
+    /// The indices of the vertices of every segment of the wireframe, in
+    /// groups of two. Each edge is a single segment between real vertices,
+    /// unless it was refined for a stereographic projection.
+    pub edges: Vec<u32>,
+
+    /// The index of the edge that each segment belongs to.
+    pub segment_edges: Vec<usize>,
+}
+
+impl RenderGeometry {
+    /// Triangulates the faces of a polytope, and projects its vertices and
+    /// those of the triangulation down into 3D.
+    ///
+    /// A stereographic projection of a polytope without a circumsphere falls
+    /// back to a perspective one, with a note on the console.
+    pub fn new(poly: &Concrete, projection_type: &ProjectionType, options: &MeshOptions) -> Self {
+        let real_vertex_count = poly.vertex_count();
+
+        // If there's no vertices, there's nothing to draw.
+        if real_vertex_count == 0 {
+            return Self {
+                real_vertex_count,
+                positions: Vec::new(),
+                frame: RenderFrame::default(),
+                non_finite: Vec::new(),
+                triangles: Vec::new(),
+                triangle_faces: Vec::new(),
+                edges: Vec::new(),
+                segment_edges: Vec::new(),
+            };
+        }
+
+        // Vertices whose coordinates aren't finite, as left behind by some
+        // failed operation, are moved to the origin so that they don't break
+        // the triangulation or the projection. Their edges and faces are then
+        // left out, so that no NaNs make it into the meshes.
+        let mut non_finite: Vec<usize> = poly
+            .vertices
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.iter().any(|x| !x.is_finite()))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let finite_poly;
+        let poly = if non_finite.is_empty() {
+            poly
+        } else {
+            println!(
+                "WARNING: Vertices {:?} have coordinates that aren't finite, their edges and faces weren't drawn!",
+                non_finite
+            );
+
+            let mut poly = poly.clone();
+            for &idx in &non_finite {
+                poly.vertices[idx].fill(0.0);
+            }
+
+            finite_poly = poly;
+            &finite_poly
+        };
+
+        let mut is_finite = vec![true; real_vertex_count];
+        for &idx in &non_finite {
+            is_finite[idx] = false;
+        }
+
+        let triangulation = Triangulation::new(poly, options);
+        let mut extra_vertices = triangulation.extra_vertices;
+
+        let mut edges = Vec::with_capacity(poly.edge_count() * 2);
+        let mut segment_edges = Vec::with_capacity(poly.edge_count());
+        let mut finite_edges = Vec::with_capacity(poly.edge_count());
+        if let Some(edge_list) = poly.get_element_list(2) {
+            for (idx, edge) in edge_list.iter().enumerate() {
                 debug_assert_eq!(
                     edge.subs.len(),
                     2,
@@ -271,20 +1060,648 @@ pub trait Renderable: ConcretePolytope {
                     edge.subs.len()
                 );
 
-                indices.push(edge.subs[0] as u16);
-                indices.push(edge.subs[1] as u16);
+                let is_drawn = is_finite[edge.subs[0]] && is_finite[edge.subs[1]];
+                finite_edges.push(is_drawn);
+                if is_drawn {
+                    edges.push(edge.subs[0] as u32);
+                    edges.push(edge.subs[1] as u32);
+                    segment_edges.push(idx);
+                }
             }
         }
 
-        // Sets the mesh attributes.
-        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
-        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
-        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertex_count]);
-        mesh.set_indices(Some(Indices::U16(indices)));
+        let mut triangles = triangulation.triangles;
+        let mut triangle_faces = triangulation.triangle_faces;
+        if !non_finite.is_empty() {
+            let finite_faces: Vec<bool> = poly
+                .get_element_list(3)
+                .map_or(&[][..], ElementList::as_slice)
+                .iter()
+                .map(|face| face.subs.iter().all(|&edge| finite_edges[edge]))
+                .collect();
+
+            let mut kept_triangles = Vec::with_capacity(triangles.len());
+            let mut kept_faces = Vec::with_capacity(triangle_faces.len());
+            for (triangle, &face) in triangles.chunks(3).zip(&triangle_faces) {
+                if finite_faces[face] {
+                    kept_triangles.extend_from_slice(triangle);
+                    kept_faces.push(face);
+                }
+            }
+
+            triangles = kept_triangles;
+            triangle_faces = kept_faces;
+        }
 
-        mesh
+        let stereographic = projection_type
+            .pole()
+            .map(|pole| Stereographic::new(poly, pole));
+        let stereographic = match stereographic {
+            Some(Ok(stereographic)) => Some(stereographic),
+            Some(Err(err)) => {
+                println!("Note: {}, so it was projected in perspective instead.", err);
+                None
+            }
+            None => None,
+        };
+
+        let coords = match &stereographic {
+            Some(stereographic) => {
+                let mut refinement = Refinement {
+                    vertices: &poly.vertices,
+                    extra_vertices: &mut extra_vertices,
+                    midpoints: HashMap::new(),
+                };
+                for _ in 0..options.refinement {
+                    refinement.refine(
+                        &mut triangles,
+                        &mut triangle_faces,
+                        &mut edges,
+                        &mut segment_edges,
+                    );
+                }
+
+                poly.vertices
+                    .iter()
+                    .chain(extra_vertices.iter())
+                    .map(|p| stereographic.project(p))
+                    .collect()
+            }
+            None => projected_coords(
+                poly,
+                poly.vertices.iter().chain(extra_vertices.iter()),
+                projection_type,
+            ),
+        };
+
+        // Points too close to the pole of a stereographic projection don't
+        // have finite coordinates either.
+        let is_drawn: Vec<bool> = coords
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| {
+                is_finite.get(idx).copied().unwrap_or(true) && p.iter().all(|x| x.is_finite())
+            })
+            .collect();
+        for (idx, &finite) in is_finite.iter().enumerate() {
+            if finite && !is_drawn[idx] {
+                non_finite.push(idx);
+            }
+        }
+
+        if is_drawn.contains(&false) {
+            let mut kept_triangles = Vec::with_capacity(triangles.len());
+            let mut kept_faces = Vec::with_capacity(triangle_faces.len());
+            for (triangle, &face) in triangles.chunks(3).zip(&triangle_faces) {
+                if triangle.iter().all(|&idx| is_drawn[idx as usize]) {
+                    kept_triangles.extend_from_slice(triangle);
+                    kept_faces.push(face);
+                }
+            }
+            triangles = kept_triangles;
+            triangle_faces = kept_faces;
+
+            let mut kept_edges = Vec::with_capacity(edges.len());
+            let mut kept_segment_edges = Vec::with_capacity(segment_edges.len());
+            for (segment, &edge) in edges.chunks(2).zip(&segment_edges) {
+                if segment.iter().all(|&idx| is_drawn[idx as usize]) {
+                    kept_edges.extend_from_slice(segment);
+                    kept_segment_edges.push(edge);
+                }
+            }
+            edges = kept_edges;
+            segment_edges = kept_segment_edges;
+        }
+
+        let frame = RenderFrame::new(
+            coords
+                .iter()
+                .zip(&is_drawn)
+                .filter(|&(_, &is_drawn)| is_drawn)
+                .map(|(p, _)| p),
+        );
+        let positions = coords
+            .into_iter()
+            .zip(&is_drawn)
+            .map(|(p, &is_drawn)| frame.to_mesh(if is_drawn { p } else { frame.center }))
+            .collect();
+
+        Self {
+            real_vertex_count,
+            positions,
+            frame,
+            non_finite,
+            triangles,
+            triangle_faces,
+            edges,
+            segment_edges,
+        }
+    }
+
+    /// Returns whether a vertex was added by the triangulation or the
+    /// refinement, rather than being a vertex of the polytope.
+    pub fn is_synthetic(&self, idx: u32) -> bool {
+        idx as usize >= self.real_vertex_count
+    }
+
+    /// Returns the positions of the vertices of the polytope, without the
+    /// synthetic ones.
+    pub fn real_positions(&self) -> &[[f32; 3]] {
+        &self.positions[..self.real_vertex_count]
+    }
+
+    /// Returns the face that a given triangle belongs to.
+    pub fn triangle_face(&self, triangle: usize) -> Option<usize> {
+        self.triangle_faces.get(triangle).copied()
+    }
+
+    /// Returns the centroid of every triangle, in the same order as
+    /// [`Self::triangle_faces`].
+    pub fn triangle_centroids(&self) -> Vec<[f32; 3]> {
+        triangle_centroids(&self.positions, &self.triangles)
+    }
+
+    /// Returns the indices of the vertices of the triangles of the faces
+    /// whose indices satisfy a predicate, in groups of three.
+    fn triangles_filtered(&self, face_filter: &dyn Fn(usize) -> bool) -> Vec<u32> {
+        self.triangles
+            .chunks(3)
+            .zip(&self.triangle_faces)
+            .filter(|&(_, &face)| face_filter(face))
+            .flat_map(|(triangle, _)| triangle.iter().copied())
+            .collect()
+    }
+
+    /// Returns the data needed to sort the triangles of every chunk of a
+    /// mesh built from a list of triangles.
+    fn depth_sorts_of(&self, triangles: &[u32], options: &MeshOptions) -> Vec<DepthSort> {
+        triangles
+            .chunks(3 * options.chunk_size.max(1))
+            .map(|chunk| {
+                let (triangles, vertices) = chunk_vertices(&self.positions, chunk);
+
+                DepthSort {
+                    centroids: triangle_centroids(&vertices, &triangles)
+                        .into_iter()
+                        .map(Vec3::from)
+                        .collect(),
+                    triangles,
+                    vertex_count: vertices.len(),
+                    eye: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the data needed to sort the triangles of every chunk of
+    /// [`Self::mesh`] from back to front.
+    pub fn depth_sorts(&self, options: &MeshOptions) -> Vec<DepthSort> {
+        self.depth_sorts_of(&self.triangles, options)
+    }
+
+    /// Returns the data needed to sort the triangles of every chunk of
+    /// [`Self::mesh_filtered`] from back to front.
+    pub fn depth_sorts_filtered(
+        &self,
+        options: &MeshOptions,
+        face_filter: &dyn Fn(usize) -> bool,
+    ) -> Vec<DepthSort> {
+        self.depth_sorts_of(&self.triangles_filtered(face_filter), options)
+    }
+
+    /// Builds the mesh of the faces, split into chunks with at most
+    /// [`MeshOptions::chunk_size`] triangles each.
+    pub fn mesh(&self, options: &MeshOptions) -> Vec<Mesh> {
+        chunked_meshes(
+            &self.positions,
+            &self.triangles,
+            3,
+            options.chunk_size,
+            PrimitiveTopology::TriangleList,
+        )
+    }
+
+    /// Builds the mesh of the faces whose indices satisfy a predicate, split
+    /// into chunks with at most [`MeshOptions::chunk_size`] triangles each.
+    pub fn mesh_filtered(
+        &self,
+        options: &MeshOptions,
+        face_filter: &dyn Fn(usize) -> bool,
+    ) -> Vec<Mesh> {
+        chunked_meshes(
+            &self.positions,
+            &self.triangles_filtered(face_filter),
+            3,
+            options.chunk_size,
+            PrimitiveTopology::TriangleList,
+        )
+    }
+
+    /// Builds the wireframe, split into chunks with at most
+    /// [`MeshOptions::chunk_size`] edges each.
+    pub fn wireframe(&self, options: &MeshOptions) -> Vec<Mesh> {
+        chunked_meshes(
+            &self.positions,
+            &self.edges,
+            2,
+            options.chunk_size,
+            PrimitiveTopology::LineList,
+        )
+    }
+
+    /// Builds the wireframe of the edges whose indices satisfy a predicate,
+    /// split into chunks with at most [`MeshOptions::chunk_size`] edges each.
+    pub fn wireframe_filtered<F: Fn(usize) -> bool>(
+        &self,
+        options: &MeshOptions,
+        keep: F,
+    ) -> Vec<Mesh> {
+        let edges: Vec<_> = self
+            .edges
+            .chunks(2)
+            .zip(&self.segment_edges)
+            .filter(|&(_, &idx)| keep(idx))
+            .flat_map(|(segment, _)| segment.iter().copied())
+            .collect();
+
+        chunked_meshes(
+            &self.positions,
+            &edges,
+            2,
+            options.chunk_size,
+            PrimitiveTopology::LineList,
+        )
+    }
+}
+
+/// A trait for a polytope for which we can build a mesh.
+pub trait Renderable: ConcretePolytope {
+    /// Builds the [`RenderGeometry`] of a polytope, from which its mesh and
+    /// its wireframe are built.
+    fn render_geometry(
+        &self,
+        projection_type: &ProjectionType,
+        options: &MeshOptions,
+    ) -> RenderGeometry {
+        RenderGeometry::new(self.con(), projection_type, options)
     }
 }
 
 impl<U: ConcretePolytope> Renderable for U {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::Polytope;
+
+    /// The triangles of a skew face are fanned from a synthetic centroid, and
+    /// still report the face they belong to.
+    #[test]
+    fn skew_faces() {
+        // A cube with a displaced vertex, whose three faces are skew.
+        let mut cube = Concrete::cube();
+        cube.vertices[0][2] += 0.3;
+        let skew = cube.skew_faces();
+        assert_eq!(skew.len(), 3);
+
+        let geometry = cube.render_geometry(&ProjectionType::Orthogonal, &Default::default());
+        assert_eq!(geometry.real_vertex_count, 8);
+        assert!(geometry.positions.len() >= 8 + skew.len());
+        assert_eq!(geometry.triangle_faces.len() * 3, geometry.triangles.len());
+        assert_eq!(geometry.edges.len(), 24);
+
+        for (triangle, idxs) in geometry.triangles.chunks(3).enumerate() {
+            let face = geometry.triangle_face(triangle).unwrap();
+            if idxs.iter().any(|&idx| geometry.is_synthetic(idx)) {
+                assert!(skew.contains(&face));
+            }
+
+            // Every real vertex of the triangle is a vertex of its face.
+            let face_vertices = cube.element_vertices_ref(3, face).unwrap();
+            for &idx in idxs.iter().filter(|&&idx| !geometry.is_synthetic(idx)) {
+                let p = &cube.vertices[idx as usize];
+                assert!(face_vertices.contains(&p));
+            }
+        }
+    }
+
+    /// Convex polygons are triangulated into two fewer triangles than they
+    /// have sides, without any extra vertices.
+    #[test]
+    fn convex_triangle_count() {
+        for n in (3..=64).chain([1000, 65537]) {
+            let geometry = Concrete::polygon(n)
+                .render_geometry(&ProjectionType::Orthogonal, &Default::default());
+
+            assert_eq!(geometry.triangles.len(), 3 * (n - 2), "{}-gon", n);
+            assert_eq!(geometry.positions.len(), n, "{}-gon", n);
+        }
+    }
+
+    /// Faces with more vertices than fit in 16-bit indices are triangulated
+    /// and chunked, and every chunk indexes its own vertices.
+    #[test]
+    fn huge_faces() {
+        let n = 100_000;
+        let options = MeshOptions::default();
+
+        for (poly, triangle_count) in [
+            (Concrete::polygon(n), n - 2),
+            (Concrete::polygon(n).prism(), 2 * (n - 2) + 2 * n),
+        ] {
+            let geometry = poly.render_geometry(&ProjectionType::Orthogonal, &options);
+            assert_eq!(geometry.triangles.len(), 3 * triangle_count);
+
+            let mut total = 0;
+            for mesh in geometry.mesh(&options) {
+                let vertex_count = mesh.count_vertices();
+                let indices: Vec<usize> = match mesh.indices().unwrap() {
+                    Indices::U16(indices) => indices.iter().map(|&idx| idx as usize).collect(),
+                    Indices::U32(indices) => indices.iter().map(|&idx| idx as usize).collect(),
+                };
+
+                assert!(indices.iter().all(|&idx| idx < vertex_count));
+                total += indices.len();
+            }
+            assert_eq!(total, 3 * triangle_count);
+        }
+    }
+
+    /// The first three axes keep their directions, and the rest are projected
+    /// from a point or away.
+    #[test]
+    fn axis_images() {
+        let square = Concrete::polygon(4);
+        let images = super::axis_images(&square, &ProjectionType::Perspective);
+        assert_eq!(images.len(), 3);
+        assert_eq!(images[1], AxisImage::Direction([0.0, 1.0, 0.0]));
+
+        let tesseract = Concrete::hypercube(5);
+        let images = super::axis_images(&tesseract, &ProjectionType::Orthogonal);
+        assert_eq!(images.len(), 4);
+        assert_eq!(images[3], AxisImage::Hidden);
+
+        // Moving a point of the 3D subspace along the fourth axis by the
+        // bounding radius scales its image by the given factor.
+        let images = super::axis_images(&tesseract, &ProjectionType::Perspective);
+        let factor = match images[3] {
+            AxisImage::Radial(factor) => factor,
+            image => panic!("expected a radial axis, found {:?}", image),
+        };
+        let point = Point::from_vec(vec![0.1, 0.2, 0.3, 0.0]);
+        let mut moved = point.clone();
+        moved[3] += tesseract.bounding_radius().unwrap();
+        let coords = projected_coords(
+            &tesseract,
+            vec![&point, &moved].into_iter(),
+            &ProjectionType::Perspective,
+        );
+        for i in 0..3 {
+            assert!((coords[1][i] - factor * coords[0][i]).abs() < 1e-12);
+        }
+    }
+
+    /// A cube far from the origin is meshed about the origin, and its frame
+    /// puts it back in place.
+    #[test]
+    fn render_frame() {
+        let offset = 1e7;
+        let mut cube = Concrete::cube();
+        for v in cube.vertices.iter_mut() {
+            v[0] += offset;
+        }
+
+        let geometry = cube.render_geometry(&ProjectionType::Orthogonal, &Default::default());
+        let frame = geometry.frame;
+        assert_eq!(frame.center, [offset, 0.0, 0.0]);
+        assert_eq!(frame.scale, 0.5);
+
+        let placement = Vec3::new(1.0, 2.0, 3.0);
+        let transform = frame.transform(placement);
+        assert_eq!(frame.placement(&transform), placement);
+
+        // The vertices of the mesh span the unit cube, and get back to where
+        // they were under the transform.
+        for (v, &p) in cube.vertices.iter().zip(geometry.real_positions()) {
+            assert!(p.iter().all(|&x| x.abs() == 1.0));
+
+            let world = transform.mul_vec3(Vec3::from(p)) - placement;
+            assert!((world.x - v[0] as f32).abs() < 1.0);
+            assert_eq!(world.y, v[1] as f32);
+            assert_eq!(world.z, v[2] as f32);
+        }
+    }
+
+    /// The edges and faces through a vertex with coordinates that aren't
+    /// finite are left out.
+    #[test]
+    fn non_finite() {
+        let mut cube = Concrete::cube();
+        cube.vertices[0][1] = Float::NAN;
+
+        let geometry = cube.render_geometry(&ProjectionType::Perspective, &Default::default());
+        assert_eq!(geometry.non_finite, vec![0]);
+        assert_eq!(geometry.edges.len(), 2 * 9);
+        assert_eq!(geometry.triangles.len(), 3 * 6);
+        assert_eq!(geometry.triangle_faces.len(), 6);
+        assert!(geometry.triangles.iter().chain(&geometry.edges).all(|&idx| idx != 0));
+        assert!(geometry.positions.iter().flatten().all(|x| x.is_finite()));
+
+        // Frames are fit to the bounding box of the coordinates.
+        let frame = RenderFrame::new(&[[1.0, 1.0, 1.0], [3.0, 5.0, 1.0]]);
+        assert_eq!(frame.center, [2.0, 3.0, 1.0]);
+        assert_eq!(frame.scale, 2.0);
+    }
+
+    /// Returns the stereographic projection from the pole at the end of the
+    /// fourth axis.
+    fn stereographic_w() -> ProjectionType {
+        ProjectionType::stereographic(4)
+    }
+
+    /// The vertices of a tesseract are projected from its circumsphere, with
+    /// the cube closest to the pole on the outside.
+    #[test]
+    fn stereographic() {
+        let tesseract = Concrete::hypercube(5);
+        let pole = stereographic_w().pole().unwrap().clone();
+        let stereographic = Stereographic::new(&tesseract, &pole).unwrap();
+
+        for v in &tesseract.vertices {
+            let coords = stereographic.project(v);
+            let norm = coords.iter().map(|x| x * x).sum::<Float>().sqrt();
+            let expected = if v[3] > 0.0 {
+                3f64.sqrt()
+            } else {
+                1.0 / 3f64.sqrt()
+            };
+            assert!((norm - expected).abs() < 1e-12);
+
+            for i in 0..3 {
+                assert!((coords[i] / norm - v[i] / 0.75f64.sqrt()).abs() < 1e-12);
+            }
+        }
+
+        // The pole itself can't be projected.
+        let pole_point = stereographic.pole_point();
+        assert!(stereographic.project(&pole_point)[0].is_nan());
+        assert!(matches!(
+            super::axis_images(&tesseract, &stereographic_w())[3],
+            AxisImage::Hidden
+        ));
+
+        assert_eq!(
+            Stereographic::new(&tesseract, &Vector::zeros(4)).err(),
+            Some(StereographicError::InvalidPole)
+        );
+        assert_eq!(
+            Stereographic::new(&tesseract, &Vector::from_vec(vec![0.0, 0.0, 1.0])).err(),
+            Some(StereographicError::InvalidPole)
+        );
+    }
+
+    /// Every level of refinement splits each triangle into four and each
+    /// segment into two, which still belong to the same faces and edges.
+    #[test]
+    fn refinement() {
+        let tesseract = Concrete::hypercube(5);
+        let geometry = |refinement| {
+            let options = MeshOptions {
+                refinement,
+                ..Default::default()
+            };
+            tesseract.render_geometry(&stereographic_w(), &options)
+        };
+
+        let coarse = geometry(0);
+        let fine = geometry(2);
+        assert_eq!(coarse.edges.len(), 2 * 32);
+        assert_eq!(fine.triangles.len(), 16 * coarse.triangles.len());
+        assert_eq!(fine.triangle_faces.len(), 16 * coarse.triangle_faces.len());
+        assert_eq!(fine.edges.len(), 4 * coarse.edges.len());
+        assert_eq!(fine.segment_edges.len(), 4 * 32);
+        assert_eq!(fine.real_vertex_count, 16);
+        assert!(fine.positions.iter().flatten().all(|x| x.is_finite()));
+
+        for edge in 0..32 {
+            let count = fine.segment_edges.iter().filter(|&&idx| idx == edge);
+            assert_eq!(count.count(), 4);
+        }
+    }
+
+    /// A polytope without a circumsphere is projected in perspective instead.
+    #[test]
+    fn stereographic_fallback() {
+        let mut tesseract = Concrete::hypercube(5);
+        tesseract.vertices[0][0] += 0.3;
+        assert_eq!(
+            Stereographic::new(&tesseract, stereographic_w().pole().unwrap()).err(),
+            Some(StereographicError::NoCircumsphere)
+        );
+
+        let options = Default::default();
+        let stereographic = tesseract.render_geometry(&stereographic_w(), &options);
+        let perspective = tesseract.render_geometry(&ProjectionType::Perspective, &options);
+        assert_eq!(stereographic.positions, perspective.positions);
+        assert_eq!(stereographic.edges, perspective.edges);
+    }
+
+    /// Splitting the wireframe of a cube keeps every edge exactly once.
+    #[test]
+    fn wireframe_filtered() {
+        let cube = Concrete::cube();
+        let options = MeshOptions {
+            chunk_size: 5,
+            ..Default::default()
+        };
+
+        let geometry = cube.render_geometry(&ProjectionType::Orthogonal, &options);
+        let edge_count = |meshes: Vec<Mesh>| {
+            meshes
+                .iter()
+                .map(|mesh| mesh.indices().unwrap().len())
+                .sum::<usize>()
+                / 2
+        };
+
+        let first = geometry.wireframe_filtered(&options, |idx| idx < 4);
+        let rest = geometry.wireframe_filtered(&options, |idx| idx >= 4);
+        assert_eq!(edge_count(first), 4);
+        assert_eq!(edge_count(rest), 8);
+        assert_eq!(geometry.wireframe_filtered(&options, |_| true).len(), 3);
+    }
+
+    /// Leaving faces out of the mesh leaves out exactly their triangles.
+    #[test]
+    fn mesh_filtered() {
+        let cube = Concrete::cube();
+        let options = MeshOptions {
+            chunk_size: 4,
+            ..Default::default()
+        };
+
+        let geometry = cube.render_geometry(&ProjectionType::Orthogonal, &options);
+        let triangle_count = |meshes: Vec<Mesh>| {
+            meshes
+                .iter()
+                .map(|mesh| mesh.indices().unwrap().len())
+                .sum::<usize>()
+                / 3
+        };
+
+        let even = |face: usize| face % 2 == 0;
+        assert_eq!(triangle_count(geometry.mesh_filtered(&options, &even)), 6);
+        assert_eq!(
+            geometry.depth_sorts_filtered(&options, &even).len(),
+            geometry.mesh_filtered(&options, &even).len()
+        );
+        assert_eq!(triangle_count(geometry.mesh_filtered(&options, &|_| false)), 0);
+        assert_eq!(
+            triangle_count(geometry.mesh_filtered(&options, &|_| true)),
+            triangle_count(geometry.mesh(&options))
+        );
+    }
+
+    /// The triangles of a cube are sorted from the furthest face to the
+    /// closest one.
+    #[test]
+    fn depth_sort() {
+        let cube = Concrete::cube();
+        let options = MeshOptions {
+            chunk_size: 4,
+            ..Default::default()
+        };
+
+        let geometry = cube.render_geometry(&ProjectionType::Orthogonal, &options);
+        let centroids = geometry.triangle_centroids();
+        assert_eq!(centroids.len(), 12);
+
+        let sorts = geometry.depth_sorts(&options);
+        assert_eq!(sorts.len(), geometry.mesh(&options).len());
+
+        let eye = Vec3::new(0.0, 0.0, 10.0);
+        let mut global = 0;
+        for mut sort in sorts {
+            assert!(sort.needs_sort(eye));
+            let order = sort.order(eye);
+
+            // The triangles are sorted by decreasing distance to the camera.
+            let dists: Vec<_> = order
+                .iter()
+                .map(|&t| Vec3::from(centroids[global + t]).distance(eye))
+                .collect();
+            assert!(dists.windows(2).all(|w| w[0] >= w[1]));
+
+            let len = match sort.sort(eye) {
+                Indices::U16(indices) => indices.len(),
+                Indices::U32(indices) => indices.len(),
+            };
+            assert_eq!(len, 3 * order.len());
+            assert!(!sort.needs_sort(eye));
+            assert!(sort.needs_sort(eye + Vec3::X));
+
+            global += order.len();
+        }
+        assert_eq!(global, 12);
+    }
+}
@@ -282,7 +282,7 @@ void main() {
     vec3 fdx = dFdx(v_WorldPosition);
     vec3 fdy = dFdy(v_WorldPosition);
     if (metallic == 0.0) {
-        o_Target = vec4(abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz))) * base_color.xyz, 1.0);
+        o_Target = vec4(abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz))) * base_color.xyz, base_color.w);
     } else {
         o_Target = base_color.xyzw;
     }
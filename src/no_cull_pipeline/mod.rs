@@ -19,6 +19,11 @@ use bevy::{
 pub const NO_CULL_PIPELINE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x7CAE7047DEE79C84);
 
+/// The handle of the pipeline for translucent faces, which doesn't write to
+/// the depth buffer.
+pub const NO_CULL_TRANSPARENT_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x7CAE7047DEE79C85);
+
 pub fn build_no_cull_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
     PipelineDescriptor {
         primitive: PrimitiveState {
@@ -71,6 +76,26 @@ pub fn build_no_cull_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescripto
     }
 }
 
+/// Builds the pipeline for translucent faces from the opaque one.
+pub fn build_transparent_pipeline(opaque: &PipelineDescriptor) -> PipelineDescriptor {
+    let mut pipeline = opaque.clone();
+    if let Some(depth_stencil) = &mut pipeline.depth_stencil {
+        depth_stencil.depth_write_enabled = false;
+    }
+    pipeline
+}
+
+/// Returns the render pipelines for either opaque or translucent faces.
+pub fn render_pipelines(transparent: bool) -> RenderPipelines {
+    let handle = if transparent {
+        NO_CULL_TRANSPARENT_PIPELINE_HANDLE
+    } else {
+        NO_CULL_PIPELINE_HANDLE
+    };
+
+    RenderPipelines::from_pipelines(vec![RenderPipeline::new(handle.typed())])
+}
+
 #[derive(Bundle)]
 pub struct PbrNoBackfaceBundle {
     pub mesh: Handle<Mesh>,
@@ -86,9 +111,7 @@ pub struct PbrNoBackfaceBundle {
 impl Default for PbrNoBackfaceBundle {
     fn default() -> Self {
         Self {
-            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
-                NO_CULL_PIPELINE_HANDLE.typed(),
-            )]),
+            render_pipelines: render_pipelines(false),
             mesh: Default::default(),
             visible: Default::default(),
             material: Default::default(),
@@ -0,0 +1,380 @@
+//! Configures the colors with which each polytope is drawn.
+
+use super::{
+    lang::SelectedLanguage,
+    main_window::{Chunk, Selected},
+    palette::{Palette, Rgba},
+    right_panel::ElementTypesRes,
+    window::{open_window, Window},
+};
+use crate::no_cull_pipeline;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{
+        self,
+        color::Hsva,
+        color_picker::{color_edit_button_hsva, Alpha},
+        Ui,
+    },
+    EguiContext,
+};
+use serde::{Deserialize, Serialize};
+
+/// The plugin in charge of the appearance of every polytope.
+pub struct AppearancePlugin;
+
+impl Plugin for AppearancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppearanceWindow>()
+            .init_resource::<EdgeHighlight>()
+            .add_system(
+                show_appearance_window
+                    .system()
+                    .label("show_appearance_window")
+                    .after("show_top_panel"),
+            )
+            .add_system(open_window::<AppearanceWindow>.system().after("show_top_panel"))
+            .add_system_to_stage(CoreStage::PostUpdate, update_styles.system());
+    }
+}
+
+/// The colors with which a polytope is drawn. Every polytope entity stores
+/// its own style as a component.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolytopeStyle {
+    /// The color of the faces, in sRGB. If its alpha is below 1, the faces
+    /// are drawn as translucent.
+    pub mesh_color: Color,
+
+    /// The color of the wireframe, in sRGB.
+    pub wf_color: Color,
+
+    /// The color of the vertex markers, in sRGB. Vertex markers aren't drawn
+    /// yet, so this is only stored.
+    pub vertex_color: Color,
+}
+
+impl Default for PolytopeStyle {
+    fn default() -> Self {
+        Self {
+            mesh_color: Color::rgb_u8(255, 255, 255),
+            wf_color: Color::rgb_u8(150, 150, 150),
+            vertex_color: Color::rgb_u8(126, 192, 255),
+        }
+    }
+}
+
+impl PolytopeStyle {
+    /// Returns whether the faces are translucent.
+    pub fn is_transparent(&self) -> bool {
+        self.mesh_color.a() < 1.0
+    }
+
+    /// Returns the material for the faces.
+    pub fn mesh_material(&self) -> StandardMaterial {
+        StandardMaterial {
+            base_color: self.mesh_color,
+            metallic: 0.0,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the material for the wireframe.
+    pub fn wf_material(&self) -> StandardMaterial {
+        self.wf_color.into()
+    }
+}
+
+/// Highlights the edges of every polytope whose lengths are far from the most
+/// common one, which helps to judge near-misses and check constructions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeHighlight {
+    /// Whether the outlier edges are highlighted.
+    pub enabled: bool,
+
+    /// How far the length of an edge has to be from the median one to be
+    /// highlighted, as a percentage of the latter.
+    pub threshold: f64,
+
+    /// The color of the highlighted edges, in sRGB.
+    pub color: Color,
+}
+
+impl Default for EdgeHighlight {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 1.0,
+            color: Color::rgb_u8(255, 64, 64),
+        }
+    }
+}
+
+/// The style with which new polytopes are drawn, which is saved in the
+/// configuration.
+#[derive(Clone, Copy, Default)]
+pub struct DefaultStyle(pub PolytopeStyle);
+
+/// Stores whether the appearance window is shown.
+#[derive(Clone, Copy, Default)]
+pub struct AppearanceWindow {
+    /// Whether the appearance window is shown.
+    pub show: bool,
+}
+
+impl Window for AppearanceWindow {
+    const NAME: &'static str = "title.appearance";
+
+    fn is_open(&self) -> bool {
+        self.show
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.show
+    }
+}
+
+/// Shows a color picker for a Bevy color.
+fn color_edit(ui: &mut Ui, color: &mut Color, alpha: Alpha) {
+    let [r, g, b, a] = color.as_rgba_f32().map(|c| (c * 255.0).round() as u8);
+    let mut hsva = Hsva::from_srgba_unmultiplied([r, g, b, a]);
+
+    if color_edit_button_hsva(ui, &mut hsva, alpha).changed() {
+        let [r, g, b, a] = hsva.to_srgba_unmultiplied();
+        *color = Color::rgba_u8(r, g, b, a);
+    }
+}
+
+/// Converts a palette color into a Bevy color.
+fn palette_color([r, g, b, a]: Rgba) -> Color {
+    Color::rgba(r, g, b, a)
+}
+
+/// Shows the palette the element types are colored with, along with the color
+/// of each type of the selected polytope, which can be picked by hand.
+fn show_type_palette(
+    ui: &mut Ui,
+    palette: &mut Palette,
+    element_types: &ElementTypesRes,
+    lang: SelectedLanguage,
+) {
+    egui::ComboBox::from_label(t!(lang, "appearance.type_palette"))
+        .selected_text(palette.choice.name())
+        .show_ui(ui, |ui| {
+            for choice in palette.choices() {
+                let name = choice.name().to_owned();
+                ui.selectable_value(&mut palette.choice, choice, name);
+            }
+        });
+
+    if element_types.stale || element_types.types.is_empty() {
+        ui.label(t!(lang, "appearance.generate_types"));
+        return;
+    }
+
+    // The palette colors the types in the order of their representatives,
+    // while the element types window may have sorted them otherwise.
+    let types: Vec<Vec<_>> = element_types
+        .types
+        .iter()
+        .map(|types| {
+            let mut types: Vec<_> = types.iter().collect();
+            types.sort_by_key(|t| t.example);
+            types
+        })
+        .collect();
+    let signatures: Vec<Vec<_>> = types
+        .iter()
+        .map(|types| types.iter().map(|t| t.signature).collect())
+        .collect();
+    let colors = palette.type_colors(&signatures);
+
+    egui::containers::ScrollArea::from_max_height(300.0).show(ui, |ui| {
+        egui::Grid::new("type_palette_grid").show(ui, |ui| {
+            // The nullitope isn't drawn.
+            for (rank, (types, colors)) in types.iter().zip(&colors).enumerate().skip(1) {
+                for (t, &rgba) in types.iter().zip(colors) {
+                    ui.label(&t.name);
+
+                    let mut color = palette_color(rgba);
+                    color_edit(ui, &mut color, Alpha::Opaque);
+                    if color != palette_color(rgba) {
+                        palette.set_override(rank, t.signature, color.as_rgba_f32());
+                    }
+
+                    if palette.override_color(rank, t.signature).is_some()
+                        && ui.small_button(t!(lang, "window.reset")).clicked()
+                    {
+                        palette.clear_override(rank, t.signature);
+                    }
+
+                    ui.end_row();
+                }
+            }
+        });
+    });
+}
+
+/// Shows the window that edits the style of the selected polytope.
+pub fn show_appearance_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut appearance: ResMut<'_, AppearanceWindow>,
+    mut default_style: ResMut<'_, DefaultStyle>,
+    mut edge_highlight: ResMut<'_, EdgeHighlight>,
+    mut palette: ResMut<'_, Palette>,
+    element_types: Res<'_, ElementTypesRes>,
+    mut selected: Query<'_, '_, &mut PolytopeStyle, With<Selected>>,
+    lang: Res<'_, SelectedLanguage>,
+) {
+    let mut open = appearance.show;
+
+    AppearanceWindow::egui_window(*lang)
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            let mut style = if let Some(style) = selected.iter_mut().next() {
+                style
+            } else {
+                ui.label(t!(lang, "window.no_polytope"));
+                return;
+            };
+
+            // We only write back the style if it changed, so that the
+            // materials aren't updated every frame.
+            let mut new_style = *style;
+
+            egui::Grid::new("appearance_grid").show(ui, |ui| {
+                ui.label(format!("{}:", t!(lang, "appearance.faces")));
+                color_edit(ui, &mut new_style.mesh_color, Alpha::OnlyBlend);
+                ui.end_row();
+
+                ui.label(format!("{}:", t!(lang, "appearance.wireframe")));
+                color_edit(ui, &mut new_style.wf_color, Alpha::Opaque);
+                ui.end_row();
+
+                ui.label(format!("{}:", t!(lang, "appearance.vertices")));
+                color_edit(ui, &mut new_style.vertex_color, Alpha::Opaque);
+                ui.end_row();
+            });
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                // Makes new polytopes use this style, also in later sessions.
+                if ui.button(t!(lang, "appearance.set_default")).clicked() {
+                    default_style.0 = new_style;
+                }
+
+                if ui.button(t!(lang, "window.reset")).clicked() {
+                    new_style = default_style.0;
+                }
+            });
+
+            if new_style != *style {
+                *style = new_style;
+            }
+
+            ui.separator();
+
+            // Same as with the style, the highlight is only written back if
+            // it changed, since every change redraws all wireframes.
+            let mut new_highlight = *edge_highlight;
+
+            ui.checkbox(&mut new_highlight.enabled, t!(lang, "appearance.highlight_edges"));
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut new_highlight.threshold)
+                        .speed(0.01)
+                        .clamp_range(0.0..=100.0),
+                );
+                ui.label(t!(lang, "appearance.from_median"));
+                color_edit(ui, &mut new_highlight.color, Alpha::Opaque);
+            });
+
+            if new_highlight != *edge_highlight {
+                *edge_highlight = new_highlight;
+            }
+
+            ui.separator();
+
+            // The palette is saved with the configuration, so it's also only
+            // written back if it changed.
+            let mut new_palette = palette.clone();
+            show_type_palette(ui, &mut new_palette, &element_types, *lang);
+
+            if new_palette != *palette {
+                *palette = new_palette;
+            }
+        });
+
+    if appearance.show != open {
+        appearance.show = open;
+    }
+}
+
+/// Makes the faces of an entity translucent or opaque. Translucent faces are
+/// sorted back to front, and are drawn without writing to the depth buffer,
+/// so that the faces behind them still show up.
+fn set_transparent(visible: &mut Visible, pipelines: &mut RenderPipelines, transparent: bool) {
+    if visible.is_transparent != transparent {
+        visible.is_transparent = transparent;
+        *pipelines = no_cull_pipeline::render_pipelines(transparent);
+    }
+}
+
+/// Writes the style of every polytope that changed into its materials, and
+/// makes any new face chunks follow the transparency of their polytope.
+pub fn update_styles(
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    polies: Query<
+        '_,
+        '_,
+        (Entity, &PolytopeStyle, &Handle<StandardMaterial>, &Children),
+        Changed<PolytopeStyle>,
+    >,
+    styles: Query<'_, '_, &PolytopeStyle>,
+    chunks: Query<'_, '_, (&Chunk, &Handle<StandardMaterial>), Without<Concrete>>,
+    new_chunks: Query<'_, '_, (Entity, &Chunk, &Parent), Added<Chunk>>,
+    mut faces: Query<'_, '_, (&mut Visible, &mut RenderPipelines)>,
+) {
+    for (entity, style, mesh_handle, children) in polies.iter() {
+        // The face chunks share their material with the polytope entity.
+        if let Some(material) = materials.get_mut(mesh_handle) {
+            *material = style.mesh_material();
+        }
+
+        if let Ok((mut visible, mut pipelines)) = faces.get_mut(entity) {
+            set_transparent(&mut visible, &mut pipelines, style.is_transparent());
+        }
+
+        for &child in children.iter() {
+            match chunks.get(child) {
+                Ok((Chunk::Faces, _)) => {
+                    if let Ok((mut visible, mut pipelines)) = faces.get_mut(child) {
+                        set_transparent(&mut visible, &mut pipelines, style.is_transparent());
+                    }
+                }
+                Ok((Chunk::Wireframe, wf_handle)) => {
+                    if let Some(material) = materials.get_mut(wf_handle) {
+                        *material = style.wf_material();
+                    }
+                }
+                // Highlighted edges don't follow the style of the polytope.
+                Ok((Chunk::Highlight, _)) | Ok((Chunk::Inspect, _)) | Err(_) => {}
+            }
+        }
+    }
+
+    // Face chunks spawned after a polytope changes start out opaque.
+    for (entity, chunk, parent) in new_chunks.iter() {
+        if *chunk == Chunk::Faces {
+            if let (Ok(style), Ok((mut visible, mut pipelines))) =
+                (styles.get(parent.0), faces.get_mut(entity))
+            {
+                set_transparent(&mut visible, &mut pipelines, style.is_transparent());
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! The axes gizmo in the corner of the viewport, which shows where the
+//! coordinate axes point as seen from the camera, and the coordinate grid that
+//! can be shown below the polytope.
+
+use super::{camera::ProjectionType, main_window::Selected};
+use crate::mesh::{axis_images, segment_mesh, AxisImage, RenderFrame};
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::{Concrete, Float};
+
+use bevy::{prelude::*, render::camera::PerspectiveProjection};
+use bevy_egui::{egui, EguiContext};
+
+/// The side of the square the axes gizmo is drawn in, in logical pixels.
+const GIZMO_SIZE: f32 = 90.0;
+
+/// The most grid lines that are drawn on each side of the center, in each
+/// direction.
+const MAX_GRID_LINES: usize = 100;
+
+/// The colors of the x, y and z axes in the gizmo.
+const AXIS_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(230, 80, 80),
+    egui::Color32::from_rgb(100, 200, 80),
+    egui::Color32::from_rgb(80, 130, 240),
+];
+
+/// The plugin in charge of the axes gizmo and the grid.
+pub struct AxesPlugin;
+
+impl Plugin for AxesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AxesSettings>()
+            .add_system(show_axes_gizmo.system().after("show_top_panel"))
+            .add_system_to_stage(CoreStage::PostUpdate, update_grid.system());
+    }
+}
+
+/// Whether the axes gizmo and the grid are shown, and how the grid is spaced.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxesSettings {
+    /// Whether the axes gizmo is shown.
+    pub gizmo: bool,
+
+    /// Whether the grid is shown.
+    pub grid: bool,
+
+    /// Whether the spacing of the grid follows the size of the polytope.
+    pub auto_spacing: bool,
+
+    /// The spacing of the grid when it's not automatic.
+    pub spacing: Float,
+}
+
+impl Default for AxesSettings {
+    fn default() -> Self {
+        Self {
+            gizmo: true,
+            grid: false,
+            auto_spacing: true,
+            spacing: 1.0,
+        }
+    }
+}
+
+impl AxesSettings {
+    /// Returns the spacing of the grid for a polytope with a given bounding
+    /// radius.
+    pub fn spacing(&self, radius: Float) -> Float {
+        if self.auto_spacing {
+            grid_spacing(radius)
+        } else {
+            self.spacing
+        }
+    }
+}
+
+/// Returns a round grid spacing for a polytope with a given bounding radius:
+/// 1, 2 or 5 times a power of ten, close to half the radius.
+pub fn grid_spacing(radius: Float) -> Float {
+    if !(radius.is_finite() && radius > 0.0) {
+        return 1.0;
+    }
+
+    let target = radius / 2.0;
+    let power = (10.0 as Float).powf(target.log10().floor());
+    let mantissa = target / power;
+
+    power
+        * if mantissa < 1.5 {
+            1.0
+        } else if mantissa < 3.5 {
+            2.0
+        } else if mantissa < 7.5 {
+            5.0
+        } else {
+            10.0
+        }
+}
+
+/// Returns the endpoints of the lines of a square grid with a given spacing on
+/// the plane y = `height`, covering at least a disk of a given radius about the
+/// y-axis. The lines go through the multiples of the spacing.
+pub fn grid_lines(radius: Float, spacing: Float, height: Float) -> Vec<[[Float; 3]; 2]> {
+    let n = ((radius / spacing).ceil() as usize).min(MAX_GRID_LINES) as isize;
+    let extent = n as Float * spacing;
+
+    (-n..=n)
+        .flat_map(|i| {
+            let x = i as Float * spacing;
+            [
+                [[x, height, -extent], [x, height, extent]],
+                [[-extent, height, x], [extent, height, x]],
+            ]
+        })
+        .collect()
+}
+
+/// Returns the name of the coordinate axis with a given index.
+pub fn axis_name(idx: usize) -> String {
+    match ["x", "y", "z", "w"].get(idx) {
+        Some(name) => name.to_string(),
+        None => format!("x{}", idx + 1),
+    }
+}
+
+/// Marks the entity that draws the grid.
+pub struct GridOverlay;
+
+/// Shows the axes gizmo in the lower left corner of the viewport. The x, y and
+/// z axes are drawn as seen from the camera. Any further axes don't point in
+/// any direction once projected, so they're listed along with how the
+/// projection treats them.
+pub fn show_axes_gizmo(
+    egui_ctx: Res<'_, EguiContext>,
+    settings: Res<'_, AxesSettings>,
+    projection_type: Res<'_, ProjectionType>,
+    selected: Query<'_, '_, &Concrete, With<Selected>>,
+    cameras: Query<'_, '_, &GlobalTransform, With<PerspectiveProjection>>,
+) {
+    if !settings.gizmo {
+        return;
+    }
+
+    let cam_gtf = match cameras.iter().next() {
+        Some(cam_gtf) => cam_gtf,
+        None => return,
+    };
+    let images = match selected.iter().next() {
+        Some(poly) => axis_images(poly, &projection_type),
+        None => vec![AxisImage::Hidden; 3],
+    };
+
+    // The axes as seen from the camera, the furthest ones first, so that the
+    // closest ones are drawn on top.
+    let view = cam_gtf.rotation.inverse();
+    let mut arrows: Vec<_> = images
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, image)| match *image {
+            AxisImage::Direction([x, y, z]) => {
+                Some((idx, view * Vec3::new(x as f32, y as f32, z as f32)))
+            }
+            _ => None,
+        })
+        .collect();
+    arrows.sort_by(|(_, v), (_, w)| {
+        v.z.partial_cmp(&w.z)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    egui::Area::new("axes_gizmo")
+        .anchor(egui::Align2::LEFT_BOTTOM, [12.0, -12.0])
+        .interactable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            let (response, painter) =
+                ui.allocate_painter(egui::Vec2::splat(GIZMO_SIZE), egui::Sense::hover());
+            let center = response.rect.center();
+            let length = 0.35 * GIZMO_SIZE;
+
+            for (idx, v) in arrows {
+                let color = AXIS_COLORS[idx % AXIS_COLORS.len()];
+                let tip = center + length * egui::Vec2::new(v.x, -v.y);
+
+                painter.line_segment([center, tip], egui::Stroke::new(2.0, color));
+                painter.text(
+                    center + 1.2 * (tip - center),
+                    egui::Align2::CENTER_CENTER,
+                    axis_name(idx),
+                    egui::TextStyle::Small,
+                    color,
+                );
+            }
+
+            for (idx, image) in images.iter().enumerate() {
+                match *image {
+                    AxisImage::Radial(factor) => {
+                        ui.small(format!(
+                            "{}: towards the center (×{:.2})",
+                            axis_name(idx),
+                            factor
+                        ));
+                    }
+                    AxisImage::Hidden if idx >= 3 => {
+                        ui.small(format!("{}: projected away", axis_name(idx)));
+                    }
+                    _ => {}
+                }
+            }
+        });
+}
+
+/// Redraws the grid whenever the settings, the selected polytope or the frame
+/// of its meshes change. The grid lies on the plane one bounding radius below
+/// the origin, so that it's underneath the polytope.
+#[allow(clippy::too_many_arguments)]
+pub fn update_grid(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    mut material: Local<'_, Option<Handle<StandardMaterial>>>,
+    settings: Res<'_, AxesSettings>,
+    selected: Query<'_, '_, (Entity, &Concrete, &RenderFrame), With<Selected>>,
+    changed: Query<
+        '_,
+        '_,
+        (),
+        (
+            With<Selected>,
+            Or<(Changed<Concrete>, Added<Selected>, Changed<RenderFrame>)>,
+        ),
+    >,
+    grids: Query<'_, '_, Entity, With<GridOverlay>>,
+) {
+    if !settings.is_changed() && changed.iter().next().is_none() {
+        return;
+    }
+
+    for grid in grids.iter() {
+        commands.entity(grid).despawn_recursive();
+    }
+
+    if !settings.grid {
+        return;
+    }
+    let (entity, poly, frame) = match selected.iter().next() {
+        Some(selected) => selected,
+        None => return,
+    };
+    let radius = match poly.bounding_radius() {
+        Some(radius) if radius > 0.0 => radius,
+        _ => return,
+    };
+
+    // The grid is drawn as a child of the polytope, so it's given in the
+    // frame of its meshes.
+    let spacing = settings.spacing(radius);
+    let mut coords = Vec::new();
+    for [p, q] in grid_lines(2.0 * radius, spacing, -radius) {
+        coords.push(frame.to_mesh(p));
+        coords.push(frame.to_mesh(q));
+    }
+    let segments: Vec<u32> = (0..coords.len() as u32).collect();
+    let mesh = segment_mesh(&coords, &segments);
+    let material = material
+        .get_or_insert_with(|| materials.add(Color::rgb_u8(140, 140, 140).into()))
+        .clone();
+
+    commands.entity(entity).with_children(|cb| {
+        cb.spawn_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(mesh),
+            material,
+            ..Default::default()
+        })
+        .insert(GridOverlay);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The spacing is a round number close to half the radius.
+    #[test]
+    fn spacing() {
+        assert_eq!(grid_spacing(2.0), 1.0);
+        assert_eq!(grid_spacing(0.75f64.sqrt()), 0.5);
+        assert!((grid_spacing(5.0) - 2.0).abs() < 1e-12);
+        assert!((grid_spacing(0.03) - 0.02).abs() < 1e-12);
+        assert_eq!(grid_spacing(0.0), 1.0);
+
+        let settings = AxesSettings {
+            auto_spacing: false,
+            spacing: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(settings.spacing(100.0), 0.25);
+    }
+
+    /// The lines go through the multiples of the spacing, and cover the disk.
+    #[test]
+    fn lines() {
+        let lines = grid_lines(1.2, 0.5, -1.0);
+        assert_eq!(lines.len(), 2 * 7);
+        assert!(lines.contains(&[[1.5, -1.0, -1.5], [1.5, -1.0, 1.5]]));
+        assert!(lines.contains(&[[-1.5, -1.0, 0.0], [1.5, -1.0, 0.0]]));
+
+        // Tiny spacings don't make for too many lines.
+        assert_eq!(grid_lines(1.0, 1e-9, 0.0).len(), 2 * (2 * MAX_GRID_LINES + 1));
+    }
+
+    /// The first axes get letters, and the rest numbers.
+    #[test]
+    fn names() {
+        assert_eq!(axis_name(0), "x");
+        assert_eq!(axis_name(3), "w");
+        assert_eq!(axis_name(4), "x5");
+    }
+}
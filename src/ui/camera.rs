@@ -6,10 +6,17 @@ use bevy::{
     input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
     math::EulerRot,
     prelude::*,
-    render::camera::Camera,
+    render::camera::{Camera, PerspectiveProjection},
 };
 use bevy_egui::{egui::CtxRef, EguiContext};
 
+use super::{main_window::Selected, replace::PolytopeReplaced};
+use crate::{mesh::vertex_coords, Concrete, Vector};
+
+/// How much room is left around a polytope that's framed by the camera, as a
+/// fraction of its size.
+const FRAME_MARGIN: f32 = 1.15;
+
 /// The plugin handling all camera input.
 pub struct InputPlugin;
 
@@ -20,17 +27,27 @@ impl Plugin for InputPlugin {
             // We register inputs after the library has been shown, so that we
             // know whether mouse input should register.
             .add_system(add_cam_input_events.system().after("show_library"))
+            .add_system(frame_loaded_polytope.system())
             .add_system(update_cameras_and_anchors.system());
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ProjectionType {
     /// We're projecting orthogonally.
     Orthogonal,
 
     /// We're projecting from a point.
     Perspective,
+
+    /// We're projecting the circumsphere of the polytope stereographically
+    /// from a pole, given as a unit vector from its center. This takes away a
+    /// single dimension, so it's meant for polytopes of rank 4 inscribed in a
+    /// 3-sphere.
+    Stereographic {
+        /// The direction of the pole from the center of the circumsphere.
+        pole: Vector,
+    },
 }
 
 impl Default for ProjectionType {
@@ -40,11 +57,12 @@ impl Default for ProjectionType {
 }
 
 impl ProjectionType {
-    /// Flips the projection type.
+    /// Flips the projection type between orthogonal and perspective. A
+    /// stereographic projection becomes orthogonal.
     pub fn flip(&mut self) {
         match self {
             Self::Orthogonal => *self = Self::Perspective,
-            Self::Perspective => *self = Self::Orthogonal,
+            Self::Perspective | Self::Stereographic { .. } => *self = Self::Orthogonal,
         }
     }
 
@@ -52,6 +70,24 @@ impl ProjectionType {
     pub fn is_orthogonal(&self) -> bool {
         matches!(self, Self::Orthogonal)
     }
+
+    /// Returns the stereographic projection of a polytope with a given number
+    /// of dimensions from the pole at the end of its last axis.
+    pub fn stereographic(dim: usize) -> Self {
+        let mut pole = Vector::zeros(dim.max(2));
+        let last = pole.len() - 1;
+        pole[last] = 1.0;
+        Self::Stereographic { pole }
+    }
+
+    /// Returns the pole of a stereographic projection, or `None` for any
+    /// other projection.
+    pub fn pole(&self) -> Option<&Vector> {
+        match self {
+            Self::Stereographic { pole } => Some(pole),
+            _ => None,
+        }
+    }
 }
 
 /// An input event for the camera.
@@ -77,6 +113,9 @@ pub enum CameraInputEvent {
 
     /// Resets the camera to its default state.
     Reset,
+
+    /// Moves the camera to a given distance from the anchor.
+    Frame(f32),
 }
 
 impl Mul<f32> for CameraInputEvent {
@@ -123,6 +162,12 @@ impl CameraInputEvent {
         cam_tf.translation.z = cam_tf.translation.z.max(0.05).min(400.);
     }
 
+    /// Moves the camera to a given distance from the anchor, within the same
+    /// bounds as the zoom.
+    fn frame(distance: f32, cam_tf: &mut Transform) {
+        cam_tf.translation.z = distance.max(0.05).min(400.);
+    }
+
     /// Resets the camera to the default position.
     pub fn reset(anchor_tf: &mut Transform, cam_tf: &mut Transform) {
         *cam_tf = Transform::from_translation(Vec3::new(0., 0., 5.));
@@ -143,6 +188,7 @@ impl CameraInputEvent {
             Self::Roll(roll) => Self::roll(roll, anchor_tf),
             Self::Zoom(zoom) => Self::zoom(zoom, cam_tf),
             Self::Reset => Self::reset(anchor_tf, cam_tf),
+            Self::Frame(distance) => Self::frame(distance, cam_tf),
         }
     }
 
@@ -158,7 +204,8 @@ impl CameraInputEvent {
         const ROLL: CameraInputEvent = CameraInputEvent::Roll(SPIN_RATE);
 
         let real_scale = time.delta_seconds();
-        let scale = if keyboard.pressed(KeyCode::LControl) | keyboard.pressed(KeyCode::RControl) {
+        let ctrl = keyboard.pressed(KeyCode::LControl) | keyboard.pressed(KeyCode::RControl);
+        let scale = if ctrl {
             real_scale * 1.5
         } else if keyboard.pressed(KeyCode::LShift) | keyboard.pressed(KeyCode::RShift) {
             real_scale / 4.
@@ -170,7 +217,9 @@ impl CameraInputEvent {
         let lr = Self::Translate(Vec3::X);
         let ud = Self::Translate(Vec3::Y);
 
-        if !ctx.wants_keyboard_input() {
+        // Keys pressed along with Ctrl are hotkeys, like Ctrl+S, and don't
+        // move the camera.
+        if !ctx.wants_keyboard_input() && !ctrl {
             for keycode in keyboard.get_pressed() {
                 cam_inputs.send(match keycode {
                     KeyCode::S => -scale * ud,
@@ -259,6 +308,40 @@ fn add_cam_input_events(
     }
 }
 
+/// Moves the camera so that a newly loaded polytope fits in view, as it's
+/// projected with the current projection type.
+fn frame_loaded_polytope(
+    mut replaced: EventReader<'_, '_, PolytopeReplaced>,
+    query: Query<'_, '_, &Concrete, With<Selected>>,
+    projection_type: Res<'_, ProjectionType>,
+    mut cam_inputs: EventWriter<'_, '_, CameraInputEvent>,
+) {
+    if replaced.iter().count() == 0 {
+        return;
+    }
+
+    if let Some(poly) = query.iter().next() {
+        let coords = vertex_coords(poly, poly.vertices.iter(), &projection_type);
+        if coords.is_empty() {
+            return;
+        }
+
+        // The bounding box of the projected polytope.
+        let (min, max) = coords.iter().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(min, max), &p| (min.min(p.into()), max.max(p.into())),
+        );
+
+        // The camera orbits the anchor, so we frame a sphere about it that
+        // contains the whole bounding box.
+        let radius = (min + max).length() / 2.0 + (max - min).length() / 2.0;
+        let fov = PerspectiveProjection::default().fov;
+        cam_inputs.send(CameraInputEvent::Frame(
+            FRAME_MARGIN * radius / (fov / 2.0).sin(),
+        ));
+    }
+}
+
 fn update_cameras_and_anchors(
     mut events: EventReader<'_, '_, CameraInputEvent>,
     q: Query<
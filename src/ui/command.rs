@@ -0,0 +1,621 @@
+//! Contains the commands that act on the selected polytope. The menus only
+//! send these as events, and a single system carries them out.
+
+use std::{any::TypeId, sync::Arc};
+
+use super::{
+    custom::{CustomOperation, CustomOperations},
+    dialog::{DialogQueue, DialogRequest},
+    lang::SelectedLanguage,
+    main_window::{DualToggle, PolyName, Selected},
+    top_panel::{SectionDirection, SectionState},
+    window::Window,
+};
+use crate::{Concrete, Hypersphere};
+
+use bevy::prelude::*;
+use miratope_core::{
+    abs::Ranked,
+    conc::{
+        compound::{CompoundError, CompoundPolicy},
+        ConcretePolytope,
+    },
+    Polytope,
+};
+
+/// The plugin in charge of carrying out commands.
+pub struct CommandPlugin;
+
+impl Plugin for CommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PolytopeCommand>()
+            // Commands are sent while the top panel is shown.
+            .add_system(
+                apply_commands
+                    .system()
+                    .label("apply_commands")
+                    .after("show_top_panel"),
+            );
+    }
+}
+
+/// A command on the selected polytope, or on the state around it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PolytopeCommand {
+    /// Opens a file dialog to load a polytope.
+    Open,
+
+    /// Saves the polytope to the file it was opened from or last saved to,
+    /// or opens a file dialog to save it if there's none.
+    Save,
+
+    /// Opens a file dialog to save the polytope.
+    SaveAs,
+
+    /// Converts the polytope into its dual, or back into the polytope its
+    /// dual was taken from.
+    Dual,
+
+    /// Converts the polytope into its Petrial.
+    Petrial,
+
+    /// Converts the polytope into its Petrie polygon.
+    PetriePolygon,
+
+    /// Identifies the opposite vertices of a centrally symmetric polytope.
+    AntipodalQuotient,
+
+    /// Makes a pyramid out of the polytope.
+    Pyramid,
+
+    /// Makes a prism out of the polytope.
+    Prism,
+
+    /// Makes a tegum out of the polytope.
+    Tegum,
+
+    /// Makes an antiprism out of the polytope.
+    Antiprism,
+
+    /// Converts the polytope into its ditope.
+    Ditope,
+
+    /// Converts the polytope into its hosotope.
+    Hosotope,
+
+    /// Identifies the coplanar facets of the polytope.
+    FuseFacets,
+
+    /// Scales the polytope by a given factor.
+    Scale(f64),
+
+    /// Scales the polytope so that its first edge has unit length.
+    ScaleToUnitEdge,
+
+    /// Scales the polytope so that it has unit circumradius.
+    ScaleToUnitCircumradius,
+
+    /// Radially projects the vertices onto the unit sphere.
+    ProjectToSphere,
+
+    /// Moves the polytope so that its circumcenter is at the origin.
+    RecenterByCircumcenter,
+
+    /// Moves the polytope so that its gravicenter is at the origin.
+    RecenterByGravicenter,
+
+    /// Moves the polytope so that the centroid of its faces, weighted by their
+    /// areas, is at the origin.
+    RecenterByAreaCentroid,
+
+    /// Opens or closes the cross-section view.
+    CrossSectionToggle,
+
+    /// Plays the custom operation with a given index in [`CustomOperations`]
+    /// on the polytope.
+    Custom(usize),
+
+    /// Opens the window whose resource has a given type. These are built
+    /// through [`Self::open`].
+    OpenWindow(TypeId),
+
+    /// Spawns a copy of the polytope next to the others.
+    NewPolytope,
+
+    /// Enumerates the facetings of the polytope with the faceting settings,
+    /// and stores them into memory.
+    EnumerateFacetings,
+
+    /// Saves every polytope in memory, one after the other.
+    ExportMemory,
+}
+
+impl PolytopeCommand {
+    /// The command that opens a given window.
+    pub fn open<W: Window + 'static>() -> Self {
+        Self::OpenWindow(TypeId::of::<W>())
+    }
+
+    /// Carries out a command on a polytope with a given name. Commands that
+    /// don't act on the polytope itself do nothing. Returns a message if the
+    /// command fails.
+    fn apply(
+        self,
+        p: &mut Concrete,
+        toggle: Option<Mut<'_, DualToggle>>,
+        name: &mut String,
+        lang: SelectedLanguage,
+    ) -> Result<(), String> {
+        match self {
+            Self::Dual => {
+                // Toggling back to the polytope the dual came from reuses it,
+                // instead of computing the dual again. The duals of compounds
+                // are taken of each component instead.
+                let mut toggle = toggle.filter(|_| !p.is_compound());
+                let result = match &mut toggle {
+                    Some(toggle) => toggle.cache.dual_mut(p).map(|_| 1).map_err(CompoundError::from),
+                    None => {
+                        let sphere = Hypersphere::unit(p.dim().unwrap_or(1));
+                        p.try_dual_with_policy(&sphere, CompoundPolicy::DUAL)
+                            .map(|(q, components)| {
+                                *p = q;
+                                components
+                            })
+                    }
+                };
+
+                match result {
+                    Ok(components) => {
+                        if let Some(mut toggle) = toggle {
+                            toggle.toggled = true;
+                        }
+
+                        *name = format!("Dual of {}", name);
+                        print_components("Dual", components);
+                        println!("Dual succeeded.")
+                    }
+                    Err(err) => return Err(format!("{}: {}", t!(lang, "error.dual"), err)),
+                }
+            }
+
+            Self::Petrial => {
+                if p.petrial_mut() {
+                    *name = format!("Petrial of {}", name);
+                    println!("Petrial succeeded.");
+                } else {
+                    return Err(format!("{}.", t!(lang, "error.petrial")));
+                }
+            }
+
+            Self::PetriePolygon => {
+                p.element_sort();
+                let flag = p.first_flag();
+                match p.petrie_polygon_with(flag) {
+                    Some(q) => {
+                        *p = q;
+                        *name = format!("Petrie polygon of {}", name);
+                        println!("Petrie polygon succeeded.")
+                    }
+                    None => return Err(format!("{}.", t!(lang, "error.petrie_polygon"))),
+                }
+            }
+
+            Self::AntipodalQuotient => match p.antipodal_quotient() {
+                Ok(q) => {
+                    *p = q;
+                    *name = format!("Antipodal quotient of {}", name);
+                    println!("Antipodal quotient succeeded.")
+                }
+                Err(err) => {
+                    return Err(format!("{}: {}", t!(lang, "error.antipodal_quotient"), err))
+                }
+            },
+
+            Self::Pyramid => {
+                *p = p.pyramid();
+                *name = format!("Pyramid of {}", name);
+            }
+
+            Self::Prism => {
+                *p = p.prism();
+                *name = format!("Prism of {}", name);
+            }
+
+            Self::Tegum => {
+                *p = p.tegum();
+                *name = format!("Tegum of {}", name);
+            }
+
+            Self::Antiprism => {
+                // The center the dual of the last component was taken about.
+                let mut center = None;
+                let result = p.apply_with_policy("The antiprism", CompoundPolicy::ANTIPRISM, |p| {
+                    p.try_antiprism_auto().map(|(q, c)| {
+                        center = Some(c);
+                        q
+                    })
+                });
+
+                match result {
+                    Ok((q, components)) => {
+                        *p = q;
+                        *name = format!("Antiprism of {}", name);
+                        print_components("Antiprism", components);
+                        if let Some(center) = center {
+                            println!("Antiprism succeeded, taking the dual about the {}.", center);
+                        }
+                    }
+                    Err(err) => return Err(format!("{}: {}", t!(lang, "error.antiprism"), err)),
+                }
+            }
+
+            Self::Ditope => {
+                p.ditope_mut();
+                *name = format!("Ditope of {}", name);
+                println!("Ditope succeeded!");
+            }
+
+            Self::Hosotope => {
+                p.hosotope_mut();
+                *name = format!("Hosotope of {}", name);
+                println!("Hosotope succeeded!");
+            }
+
+            Self::FuseFacets => {
+                *p = p.fuse_facets();
+                println!("Fuse succeeded!");
+            }
+
+            Self::Scale(factor) => p.scale_mut(factor),
+
+            Self::ScaleToUnitEdge => {
+                let e_l = (&p.vertices[p.abs[2][0].subs[0]] - &p.vertices[p.abs[2][0].subs[1]]).norm();
+                p.scale_mut(1.0 / e_l);
+            }
+
+            Self::ScaleToUnitCircumradius => match p.circumsphere() {
+                Some(sphere) => {
+                    p.scale_mut(1.0 / sphere.radius());
+                }
+                None => return Err("The polytope has no circumsphere.".to_string()),
+            },
+
+            Self::ProjectToSphere => {
+                let sphere = Hypersphere::unit(p.dim_or());
+                if let Err(err) = p.project_to_sphere_mut(&sphere) {
+                    return Err(format!("Projection failed: {}", err));
+                }
+            }
+
+            Self::RecenterByCircumcenter => match p.circumsphere() {
+                Some(sphere) => {
+                    p.recenter_with_mut(&sphere.center);
+                }
+                None => return Err("The polytope has no circumsphere.".to_string()),
+            },
+
+            Self::RecenterByGravicenter => p.recenter_mut(),
+
+            Self::RecenterByAreaCentroid => match p.centroid_by_area() {
+                Some(centroid) => {
+                    p.recenter_with_mut(&centroid);
+                }
+                None => return Err("The polytope has no faces with area.".to_string()),
+            },
+
+            Self::Open
+            | Self::Save
+            | Self::SaveAs
+            | Self::CrossSectionToggle
+            | Self::Custom(_)
+            | Self::OpenWindow(_)
+            | Self::NewPolytope
+            | Self::EnumerateFacetings
+            | Self::ExportMemory => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the number of components an operation was applied to separately,
+/// if the polytope was a compound.
+pub fn print_components(operation: &str, components: usize) {
+    if components > 1 {
+        println!("{} applied to each of {} components.", operation, components);
+    }
+}
+
+/// Plays the steps of a custom operation on a polytope with a given name, one
+/// after the other. If some step fails, the polytope and its name are left
+/// unchanged and a message is returned, so that the whole operation either
+/// happens at once or not at all.
+fn apply_custom(
+    op: &CustomOperation,
+    p: &mut Concrete,
+    name: &mut String,
+    lang: SelectedLanguage,
+) -> Result<(), String> {
+    let mut q = p.clone();
+    let mut q_name = name.clone();
+
+    for (idx, step) in op.steps.iter().enumerate() {
+        if let Err(err) = step.apply(&mut q, None, &mut q_name, lang) {
+            return Err(format!(
+                "{} \"{}\" at step {} ({:?}): {}",
+                t!(lang, "error.custom"),
+                op.name,
+                idx + 1,
+                step,
+                err
+            ));
+        }
+    }
+
+    *p = q;
+    *name = q_name;
+    Ok(())
+}
+
+/// Opens the cross-section view on the selected polytope, or closes it and
+/// restores the polytope that was being sliced.
+fn toggle_cross_section(
+    p: &mut Concrete,
+    poly_name: &mut PolyName,
+    section_state: &mut SectionState,
+    section_direction: &mut Vec<SectionDirection>,
+) {
+    match std::mem::take(section_state) {
+        // The view is active, but will be inactivated.
+        SectionState::Active {
+            original_polytope,
+            original_name,
+            ..
+        } => {
+            *p = Arc::try_unwrap(original_polytope)
+                .unwrap_or_else(|original_polytope| (*original_polytope).clone());
+            poly_name.0 = original_name;
+        }
+
+        // The view is inactive, but will be activated.
+        SectionState::Inactive => {
+            if p.rank() < 4 {
+                // Cannot slice a polygon or lower.
+                println!("Slicing polytopes of rank less than 3 is not supported!");
+            } else {
+                p.ensure_flat();
+
+                // The default direction is in the last coordinate axis.
+                let direction = SectionDirection::last_axis(p.dim_or());
+                let minmax = p.minmax(direction.0.clone()).unwrap_or((-1.0, 1.0));
+                let original_polytope = p.clone();
+
+                section_state.open(original_polytope, poly_name.0.clone(), vec![minmax]);
+                section_direction.clear();
+                section_direction.push(direction);
+            }
+        }
+    }
+}
+
+/// The system that carries out the commands sent this frame, and prints out
+/// their results.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_commands(
+    mut commands: EventReader<'_, '_, PolytopeCommand>,
+    (mut query, mut dual_toggles): (
+        Query<'_, '_, &mut Concrete, With<Selected>>,
+        Query<'_, '_, &mut DualToggle, With<Selected>>,
+    ),
+    mut poly_name: ResMut<'_, PolyName>,
+    mut section_state: ResMut<'_, SectionState>,
+    mut section_direction: ResMut<'_, Vec<SectionDirection>>,
+    mut dialog_queue: ResMut<'_, DialogQueue>,
+    (custom_operations, lang): (Res<'_, CustomOperations>, Res<'_, SelectedLanguage>),
+) {
+    for &command in commands.iter() {
+        match command {
+            PolytopeCommand::Open => dialog_queue.push(DialogRequest::Open),
+            PolytopeCommand::Save => dialog_queue.push(DialogRequest::Save),
+            PolytopeCommand::SaveAs => dialog_queue.push(DialogRequest::SaveAs {
+                suggested_name: poly_name.0.clone(),
+            }),
+
+            PolytopeCommand::CrossSectionToggle => {
+                if let Some(mut p) = query.iter_mut().next() {
+                    toggle_cross_section(
+                        &mut p,
+                        &mut poly_name,
+                        &mut section_state,
+                        &mut section_direction,
+                    );
+                }
+            }
+
+            PolytopeCommand::Custom(idx) => {
+                if let (Some(op), Some(mut p)) =
+                    (custom_operations.0.get(idx), query.iter_mut().next())
+                {
+                    match apply_custom(op, &mut p, &mut poly_name.0, *lang) {
+                        Ok(()) => println!("{} succeeded.", op.name),
+                        Err(err) => eprintln!("{}", err),
+                    }
+                }
+            }
+
+            // These are carried out by the systems in charge of the state
+            // they act on.
+            PolytopeCommand::OpenWindow(_)
+            | PolytopeCommand::NewPolytope
+            | PolytopeCommand::EnumerateFacetings
+            | PolytopeCommand::ExportMemory => {}
+
+            _ => {
+                if let Some(mut p) = query.iter_mut().next() {
+                    if let Err(err) = command.apply(
+                        &mut p,
+                        dual_toggles.iter_mut().next(),
+                        &mut poly_name.0,
+                        *lang,
+                    ) {
+                        eprintln!("{}", err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::file::FromFile;
+
+    /// A cube with a vertex at the origin, as loaded from an OFF file.
+    const CUBE: &str = "OFF
+        8 6 12
+        0 0 0
+        1 0 0
+        0 1 0
+        1 1 0
+        0 0 1
+        1 0 1
+        0 1 1
+        1 1 1
+        4 0 1 3 2
+        4 4 5 7 6
+        4 0 1 5 4
+        4 2 3 7 6
+        4 0 2 6 4
+        4 1 3 7 5";
+
+    /// Builds an app that only carries out commands, with a single selected
+    /// polytope.
+    fn command_app(poly: Concrete) -> App {
+        let mut app = App::new();
+        app.add_event::<PolytopeCommand>()
+            .insert_resource(PolyName("cube".to_string()))
+            .init_resource::<SectionState>()
+            .init_resource::<Vec<SectionDirection>>()
+            .init_resource::<DialogQueue>()
+            .init_resource::<SelectedLanguage>()
+            .init_resource::<CustomOperations>()
+            .add_system(apply_commands.system());
+
+        app.world
+            .spawn()
+            .insert(poly)
+            .insert(DualToggle::default())
+            .insert(Selected);
+        app
+    }
+
+    /// Sends a command, and runs the app once.
+    fn send(app: &mut App, command: PolytopeCommand) {
+        app.world
+            .get_resource_mut::<Events<PolytopeCommand>>()
+            .unwrap()
+            .send(command);
+        app.update();
+    }
+
+    /// Returns the selected polytope.
+    fn selected(app: &mut App) -> Concrete {
+        app.world
+            .query_filtered::<&Concrete, With<Selected>>()
+            .iter(&app.world)
+            .next()
+            .unwrap()
+            .clone()
+    }
+
+    /// The dual of a cube is an octahedron, and its dual is the cube again.
+    #[test]
+    fn dual() {
+        let mut app = command_app(Concrete::hypercube(4));
+        send(&mut app, PolytopeCommand::Dual);
+
+        let octahedron = selected(&mut app);
+        assert_eq!(octahedron.el_count_iter().collect::<Vec<_>>(), vec![1, 6, 12, 8, 1]);
+        assert!(octahedron.abs.is_isomorphic(&Concrete::orthoplex(4).abs));
+        assert_eq!(app.world.get_resource::<PolyName>().unwrap().0, "Dual of cube");
+
+        send(&mut app, PolytopeCommand::Dual);
+        assert!(selected(&mut app).abs.is_isomorphic(&Concrete::hypercube(4).abs));
+    }
+
+    /// The antiprism of the hexagram is taken of each of its triangles, and
+    /// the dual of the result of each of its octahedra.
+    #[test]
+    fn compound() {
+        let mut app = command_app(Concrete::star_polygon(6, 2));
+        send(&mut app, PolytopeCommand::Antiprism);
+
+        let antiprisms = selected(&mut app);
+        assert_eq!(antiprisms.el_count_iter().collect::<Vec<_>>(), vec![1, 12, 24, 16, 1]);
+        assert_eq!(antiprisms.components().len(), 2);
+
+        send(&mut app, PolytopeCommand::Dual);
+        let cubes = selected(&mut app);
+        assert_eq!(cubes.el_count_iter().collect::<Vec<_>>(), vec![1, 16, 24, 12, 1]);
+        assert_eq!(cubes.components().len(), 2);
+    }
+
+    /// A polygon can't be sliced, so the cross-section view stays closed.
+    #[test]
+    fn cross_section() {
+        let mut app = command_app(Concrete::polygon(5));
+        send(&mut app, PolytopeCommand::CrossSectionToggle);
+        assert!(matches!(
+            *app.world.get_resource::<SectionState>().unwrap(),
+            SectionState::Inactive
+        ));
+
+        let mut app = command_app(Concrete::hypercube(4));
+        send(&mut app, PolytopeCommand::CrossSectionToggle);
+        assert!(matches!(
+            *app.world.get_resource::<SectionState>().unwrap(),
+            SectionState::Active { .. }
+        ));
+    }
+
+    /// A custom operation is played as a whole, or not at all.
+    #[test]
+    fn custom() {
+        let dual_prism = CustomOperation::from_ron(
+            r#"(
+                name: "Dual prism",
+                steps: [
+                    (command: "RecenterByCircumcenter"),
+                    (command: "Dual"),
+                    (command: "Prism"),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        // Without recentering, the dual fails since some facets pass through
+        // the origin.
+        let failing = CustomOperation {
+            name: "Failing dual prism".to_string(),
+            steps: vec![PolytopeCommand::Prism, PolytopeCommand::Dual],
+        };
+
+        let mut app = command_app(Concrete::from_off(CUBE).unwrap());
+        app.insert_resource(CustomOperations(vec![dual_prism, failing]));
+
+        send(&mut app, PolytopeCommand::Custom(1));
+        assert_eq!(selected(&mut app).el_count_iter().collect::<Vec<_>>(), vec![1, 8, 12, 6, 1]);
+        assert_eq!(app.world.get_resource::<PolyName>().unwrap().0, "cube");
+
+        send(&mut app, PolytopeCommand::Custom(0));
+        assert_eq!(
+            selected(&mut app).el_count_iter().collect::<Vec<_>>(),
+            vec![1, 12, 30, 28, 10, 1]
+        );
+        assert_eq!(
+            app.world.get_resource::<PolyName>().unwrap().0,
+            "Prism of Dual of cube"
+        );
+    }
+}
@@ -12,6 +12,15 @@ use bevy_egui::{egui, EguiContext};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
+use super::{
+    appearance::{DefaultStyle, PolytopeStyle},
+    lang::SelectedLanguage,
+    library::SpecialParams,
+    palette::Palette,
+    recent::RecentFiles,
+    validation::ValidationSettings,
+};
+
 /// The default path in which we look for the Miratope library.
 const DEFAULT_PATH: &str = "./lib";
 
@@ -38,9 +47,13 @@ impl Plugin for ConfigPlugin {
         app.insert_resource(config_path)
             .insert_resource(LibPath::default())
             .insert_resource(config.background_color.clear_color())
-            .insert_resource(config.mesh_color)
-            .insert_resource(config.wf_color)
+            .insert_resource(DefaultStyle(config.default_style))
             .insert_resource(config.light_mode.visuals())
+            .insert_resource(SelectedLanguage::from_code(&config.language))
+            .insert_resource(config.special_params)
+            .insert_resource(config.recent_files)
+            .insert_resource(config.validation)
+            .insert_resource(config.palette)
             .add_system(update_visuals.system())
             .add_system_to_stage(CoreStage::Last, save_config.system());
     }
@@ -93,26 +106,6 @@ impl BgColor {
     }
 }
 
-/// The mesh color of the polytope in sRGB.
-#[derive(Clone, Serialize, Deserialize)]
-pub struct MeshColor(pub Color);
-
-impl Default for MeshColor {
-    fn default() -> MeshColor {
-        MeshColor(Color::rgb_u8(255, 255, 255))
-    }
-}
-
-/// The wireframe color of the polytope in sRGB.
-#[derive(Clone, Serialize, Deserialize)]
-pub struct WfColor(pub Color);
-
-impl Default for WfColor {
-    fn default() -> WfColor {
-        WfColor(Color::rgb_u8(150, 150, 150))
-    }
-}
-
 /// Whether light mode is turned on or off.
 #[derive(Default, Serialize, Deserialize)]
 pub struct LightMode(bool);
@@ -144,14 +137,32 @@ pub struct Config {
     /// The background color of the application.
     pub background_color: BgColor,
 
-    /// The mesh color of the polytope.
-    pub mesh_color: MeshColor,
-
-    /// The wireframe color of the polytope.
-    pub wf_color: WfColor,
+    /// The style with which new polytopes are drawn.
+    #[serde(default)]
+    pub default_style: PolytopeStyle,
 
     /// Whether light mode is enabled.
     pub light_mode: LightMode,
+
+    /// The code of the language of the user interface.
+    #[serde(default)]
+    pub language: String,
+
+    /// The last values of the entries in the special library.
+    #[serde(default)]
+    pub special_params: SpecialParams,
+
+    /// The files that were recently opened.
+    #[serde(default)]
+    pub recent_files: RecentFiles,
+
+    /// How polytopes are checked after they change.
+    #[serde(default)]
+    pub validation: ValidationSettings,
+
+    /// The colors of the element types.
+    #[serde(default)]
+    pub palette: Palette,
 }
 
 impl Config {
@@ -222,17 +233,25 @@ fn save_config(
     config_path: Res<'_, ConfigPath>,
 
     background_color: Res<'_, ClearColor>,
-    mesh_color: Res<'_, MeshColor>,
-    wf_color: Res<'_, WfColor>,
+    default_style: Res<'_, DefaultStyle>,
     visuals: Res<'_, egui::Visuals>,
+    language: Res<'_, SelectedLanguage>,
+    special_params: Res<'_, SpecialParams>,
+    recent_files: Res<'_, RecentFiles>,
+    validation: Res<'_, ValidationSettings>,
+    palette: Res<'_, Palette>,
 ) {
     // If the application is being exited:
     if exit.iter().next().is_some() {
         let config = Config {
             background_color: BgColor::new(background_color.as_ref()),
-            mesh_color: mesh_color.clone(),
-            wf_color: wf_color.clone(),
+            default_style: default_style.0,
             light_mode: LightMode(!visuals.dark_mode),
+            language: language.language().code.to_string(),
+            special_params: special_params.clone(),
+            recent_files: recent_files.clone(),
+            validation: *validation,
+            palette: palette.clone(),
         };
 
         config.save(&config_path.0);
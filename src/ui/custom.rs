@@ -0,0 +1,335 @@
+//! Custom operations, which are named sequences of [commands](PolytopeCommand)
+//! described in RON files. These are read from the configuration directory at
+//! startup, and shown in the Operations menu.
+//!
+//! A descriptor looks like this:
+//!
+//! ```ron
+//! (
+//!     name: "Scaled dual prism",
+//!     steps: [
+//!         (command: "Scale", args: [2.0]),
+//!         (command: "Dual"),
+//!         (command: "Prism"),
+//!     ],
+//! )
+//! ```
+
+use std::{fmt::Display, fs, path::Path};
+
+use super::{command::PolytopeCommand, config::Config};
+
+use bevy::prelude::{App, Plugin};
+use serde::Deserialize;
+
+/// The name of the folder in the configuration directory that contains the
+/// descriptors of the custom operations.
+const OPERATIONS_DIR: &str = "operations";
+
+/// The plugin that loads the custom operations.
+pub struct CustomPlugin;
+
+impl Plugin for CustomPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CustomOperations::load(
+            Config::config_dir().join(OPERATIONS_DIR),
+        ));
+    }
+}
+
+/// A step of a custom operation, as written in its descriptor.
+#[derive(Deserialize)]
+struct StepDescriptor {
+    /// The name of the command, as in [`PolytopeCommand`].
+    command: String,
+
+    /// The arguments of the command.
+    #[serde(default)]
+    args: Vec<f64>,
+}
+
+/// A custom operation, as written in its descriptor.
+#[derive(Deserialize)]
+struct OperationDescriptor {
+    /// The name of the operation, as shown in the menu.
+    name: String,
+
+    /// The steps of the operation, in order.
+    steps: Vec<StepDescriptor>,
+}
+
+/// An error in the descriptor of a custom operation. Steps are numbered
+/// starting from 1.
+#[derive(Debug)]
+pub enum DescriptorError {
+    /// The descriptor isn't valid RON, or doesn't follow the schema.
+    Ron(ron::Error),
+
+    /// The operation has no steps.
+    NoSteps,
+
+    /// A step refers to a command that doesn't exist, or that doesn't act on
+    /// the polytope.
+    UnknownCommand {
+        /// The number of the step.
+        step: usize,
+
+        /// The name of the command.
+        command: String,
+    },
+
+    /// A step has the wrong number of arguments for its command.
+    Arity {
+        /// The number of the step.
+        step: usize,
+
+        /// The name of the command.
+        command: String,
+
+        /// The number of arguments the command takes.
+        expected: usize,
+
+        /// The number of arguments the step has.
+        found: usize,
+    },
+}
+
+impl Display for DescriptorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ron(err) => write!(f, "invalid descriptor: {}", err),
+            Self::NoSteps => write!(f, "the operation has no steps"),
+            Self::UnknownCommand { step, command } => {
+                write!(f, "step {}: unknown command \"{}\"", step, command)
+            }
+            Self::Arity {
+                step,
+                command,
+                expected,
+                found,
+            } => write!(
+                f,
+                "step {}: command \"{}\" takes {} arguments, but {} were given",
+                step, command, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DescriptorError {}
+
+/// [`ron::Error`] is a type of [`DescriptorError`].
+impl From<ron::Error> for DescriptorError {
+    fn from(err: ron::Error) -> Self {
+        Self::Ron(err)
+    }
+}
+
+/// Returns the command that a step refers to, checking its arguments.
+fn parse_step(step: usize, desc: StepDescriptor) -> Result<PolytopeCommand, DescriptorError> {
+    use PolytopeCommand::*;
+
+    let args = &desc.args;
+    let (arity, command) = match desc.command.as_str() {
+        "Dual" => (0, Dual),
+        "Petrial" => (0, Petrial),
+        "PetriePolygon" => (0, PetriePolygon),
+        "AntipodalQuotient" => (0, AntipodalQuotient),
+        "Pyramid" => (0, Pyramid),
+        "Prism" => (0, Prism),
+        "Tegum" => (0, Tegum),
+        "Antiprism" => (0, Antiprism),
+        "Ditope" => (0, Ditope),
+        "Hosotope" => (0, Hosotope),
+        "FuseFacets" => (0, FuseFacets),
+        "Scale" => (1, Scale(args.first().copied().unwrap_or_default())),
+        "ScaleToUnitEdge" => (0, ScaleToUnitEdge),
+        "ScaleToUnitCircumradius" => (0, ScaleToUnitCircumradius),
+        "ProjectToSphere" => (0, ProjectToSphere),
+        "RecenterByCircumcenter" => (0, RecenterByCircumcenter),
+        "RecenterByGravicenter" => (0, RecenterByGravicenter),
+        "RecenterByAreaCentroid" => (0, RecenterByAreaCentroid),
+        _ => {
+            return Err(DescriptorError::UnknownCommand {
+                step,
+                command: desc.command,
+            })
+        }
+    };
+
+    if args.len() == arity {
+        Ok(command)
+    } else {
+        Err(DescriptorError::Arity {
+            step,
+            found: args.len(),
+            command: desc.command,
+            expected: arity,
+        })
+    }
+}
+
+/// A named sequence of commands, played one after the other on the selected
+/// polytope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomOperation {
+    /// The name of the operation, as shown in the menu.
+    pub name: String,
+
+    /// The commands that make up the operation, in order.
+    pub steps: Vec<PolytopeCommand>,
+}
+
+impl CustomOperation {
+    /// Reads a custom operation from its RON descriptor.
+    pub fn from_ron(src: &str) -> Result<Self, DescriptorError> {
+        let desc: OperationDescriptor = ron::from_str(src)?;
+        if desc.steps.is_empty() {
+            return Err(DescriptorError::NoSteps);
+        }
+
+        Ok(Self {
+            name: desc.name,
+            steps: desc
+                .steps
+                .into_iter()
+                .enumerate()
+                .map(|(idx, step)| parse_step(idx + 1, step))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// The custom operations that were loaded, in the order they're shown in.
+#[derive(Default)]
+pub struct CustomOperations(pub Vec<CustomOperation>);
+
+impl CustomOperations {
+    /// Loads every RON descriptor in a folder, sorted by file name. Invalid
+    /// descriptors are reported and skipped. If the folder doesn't exist,
+    /// there are no custom operations.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Self {
+        let mut paths: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.extension() == Some("ron".as_ref()))
+                .collect(),
+            Err(_) => return Self::default(),
+        };
+        paths.sort();
+
+        let mut operations = Vec::new();
+        for path in paths {
+            match fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|src| CustomOperation::from_ron(&src).map_err(|err| err.to_string()))
+            {
+                Ok(op) => operations.push(op),
+                Err(err) => eprintln!(
+                    "Could not load the custom operation in {}: {}",
+                    path.display(),
+                    err
+                ),
+            }
+        }
+
+        Self(operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A valid descriptor, with and without arguments.
+    #[test]
+    fn parse() {
+        let op = CustomOperation::from_ron(
+            r#"(
+                name: "Scaled dual prism",
+                steps: [
+                    (command: "Scale", args: [2.0]),
+                    (command: "Dual"),
+                    (command: "Prism", args: []),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(op.name, "Scaled dual prism");
+        assert_eq!(
+            op.steps,
+            vec![
+                PolytopeCommand::Scale(2.0),
+                PolytopeCommand::Dual,
+                PolytopeCommand::Prism
+            ]
+        );
+    }
+
+    /// Descriptors that aren't RON, or that don't follow the schema.
+    #[test]
+    fn malformed() {
+        for src in [
+            "(name: \"Dual\", steps: [(command: \"Dual\")]",
+            "(name: \"Dual\")",
+            "(name: \"Dual\", steps: [(command: 3)])",
+            "(name: \"Dual\", steps: [(command: \"Dual\", args: [\"2\"])])",
+        ] {
+            assert!(matches!(
+                CustomOperation::from_ron(src),
+                Err(DescriptorError::Ron(_))
+            ));
+        }
+
+        assert!(matches!(
+            CustomOperation::from_ron("(name: \"Nothing\", steps: [])"),
+            Err(DescriptorError::NoSteps)
+        ));
+    }
+
+    /// Steps with commands that don't exist or don't act on the polytope.
+    #[test]
+    fn unknown_command() {
+        for command in ["Daul", "Open", "CrossSectionToggle", "Custom"] {
+            let src = format!(
+                "(name: \"Op\", steps: [(command: \"Dual\"), (command: \"{}\")])",
+                command
+            );
+
+            match CustomOperation::from_ron(&src) {
+                Err(DescriptorError::UnknownCommand {
+                    step: 2,
+                    command: c,
+                }) => {
+                    assert_eq!(c, command)
+                }
+                res => panic!("unexpected result {:?}", res),
+            }
+        }
+    }
+
+    /// Steps with the wrong number of arguments.
+    #[test]
+    fn arity() {
+        assert!(matches!(
+            CustomOperation::from_ron("(name: \"Op\", steps: [(command: \"Scale\")])"),
+            Err(DescriptorError::Arity {
+                step: 1,
+                expected: 1,
+                found: 0,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            CustomOperation::from_ron("(name: \"Op\", steps: [(command: \"Prism\", args: [1.0])])"),
+            Err(DescriptorError::Arity {
+                step: 1,
+                expected: 0,
+                found: 1,
+                ..
+            })
+        ));
+    }
+}
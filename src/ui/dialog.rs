@@ -0,0 +1,535 @@
+//! The file dialogs. The UI queues requests for them, and a single system
+//! shows at most one of them per frame, consuming its request whatever the
+//! user does with it.
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    lang::SelectedLanguage,
+    lattice::{LatticeExport, LARGE_LATTICE},
+    main_window::{PolyName, Selected},
+    replace::{PendingReplacement, Replacement},
+};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::file::{
+    format::{format_for_path, SaveOptions, FORMATS},
+    lattice::LatticeFormat,
+};
+
+/// The plugin in charge of the file dialogs.
+pub struct DialogPlugin;
+
+impl Plugin for DialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DialogQueue>()
+            .init_non_send_resource::<FileDialogToken>()
+            // Requests are queued by the menus and by the commands.
+            .add_system(
+                file_dialog::<FileDialogToken>
+                    .system()
+                    .after("show_top_panel")
+                    .after("apply_commands"),
+            );
+    }
+}
+
+/// A file dialog that's been asked for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DialogRequest {
+    /// Asks for a file to open.
+    Open,
+
+    /// Asks for a path to save the polytope to, starting with a given file
+    /// name.
+    SaveAs {
+        /// The file name the dialog starts with.
+        suggested_name: String,
+    },
+
+    /// Saves the polytope to the [`CurrentFile`], or asks for a path like
+    /// [`Self::SaveAs`] if there's none, or if its format can't be saved.
+    Save,
+
+    /// Asks for a path to export the face lattice of the polytope to, starting
+    /// with a given file name.
+    ExportLattice {
+        /// The file name the dialog starts with.
+        suggested_name: String,
+    },
+}
+
+/// The file dialogs that have been asked for, in order.
+#[derive(Clone, Debug, Default)]
+pub struct DialogQueue(VecDeque<DialogRequest>);
+
+impl DialogQueue {
+    /// Asks for a file dialog. A request that's already queued isn't queued
+    /// again, so that clicking a button twice, or during a frame in which
+    /// another dialog blocked the application, only shows it once.
+    pub fn push(&mut self, request: DialogRequest) {
+        if !self.0.contains(&request) {
+            self.0.push_back(request);
+        }
+    }
+
+    /// Returns the number of requests in the queue.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no requests in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The file a polytope was last opened from or saved to, if any, which
+/// [`DialogRequest::Save`] saves to without asking. Every polytope entity
+/// stores its own as a component, so that changing the selected polytope
+/// never saves it over the file of another.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CurrentFile(pub Option<PathBuf>);
+
+/// The file dialogs, which are shown by [`FileDialogToken`] and mocked in the
+/// tests. Each of them blocks until the user picks a path or cancels.
+pub trait FileDialogs {
+    /// Returns the path given by an open file dialog.
+    fn pick_file(&self) -> Option<PathBuf>;
+
+    /// Returns the path given by a save file dialog.
+    fn save_file(&self, name: &str) -> Option<PathBuf>;
+
+    /// Returns the path given by a save file dialog for a face lattice in a
+    /// given format.
+    fn save_lattice(&self, name: &str, format: LatticeFormat) -> Option<PathBuf>;
+}
+
+/// Contains all operations that manipulate file dialogs concretely.
+///
+/// Guarantees that file dialogs will be opened on the main thread, so as to
+/// circumvent a MacOS limitation that all GUI operations must be done on the
+/// main thread.
+#[derive(Default)]
+pub struct FileDialogToken(std::marker::PhantomData<*const ()>);
+
+impl FileDialogToken {
+    /// Auxiliary function to create a new file dialog, with a filter for
+    /// every registered format that can be opened or saved, respectively.
+    fn new_file_dialog(save: bool) -> rfd::FileDialog {
+        FORMATS
+            .iter()
+            .filter(|format| {
+                if save {
+                    format.can_save()
+                } else {
+                    format.can_load()
+                }
+            })
+            .fold(rfd::FileDialog::new(), |dialog, format| {
+                dialog.add_filter(format.name(), format.extensions())
+            })
+    }
+}
+
+impl FileDialogs for FileDialogToken {
+    fn pick_file(&self) -> Option<PathBuf> {
+        Self::new_file_dialog(false).pick_file()
+    }
+
+    fn save_file(&self, name: &str) -> Option<PathBuf> {
+        Self::new_file_dialog(true).set_file_name(name).save_file()
+    }
+
+    fn save_lattice(&self, name: &str, format: LatticeFormat) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter(format.name(), &[format.extension()])
+            .set_file_name(name)
+            .save_file()
+    }
+}
+
+/// Returns whether the polytope can be saved to a path without asking, which
+/// requires a format that can be saved.
+fn can_save_to(path: &Path) -> bool {
+    format_for_path(path).map_or(false, |format| format.can_save())
+}
+
+/// The system in charge of showing the file dialogs. It takes the first
+/// request off the queue, if any, and carries it out. Cancelling a dialog
+/// leaves everything as it was.
+pub fn file_dialog<D: FileDialogs + 'static>(
+    mut queue: ResMut<'_, DialogQueue>,
+    mut pending: ResMut<'_, PendingReplacement>,
+    dialogs: NonSend<'_, D>,
+    mut query: Query<'_, '_, (&Concrete, &mut CurrentFile), With<Selected>>,
+    (poly_name, lattice, save_options, lang): (
+        Res<'_, PolyName>,
+        Res<'_, LatticeExport>,
+        Res<'_, SaveOptions>,
+        Res<'_, SelectedLanguage>,
+    ),
+) {
+    // Avoids flagging the queue as changed every frame.
+    if queue.is_empty() {
+        return;
+    }
+    let request = queue.0.pop_front().unwrap();
+
+    match request {
+        // We want to open a file. It only becomes the current file once it's
+        // swapped in.
+        DialogRequest::Open => {
+            if let Some(path) = dialogs.pick_file() {
+                match Replacement::from_path(&path) {
+                    Ok(replacement) => pending.set(replacement),
+                    Err(err) => eprintln!("{}: {}", t!(lang, "error.file_open"), err),
+                }
+            }
+        }
+
+        // We want to save a file.
+        DialogRequest::Save | DialogRequest::SaveAs { .. } => {
+            let (p, mut current_file) = match query.iter_mut().next() {
+                Some(selected) => selected,
+                None => return,
+            };
+
+            let path = match (&request, &current_file.0) {
+                (DialogRequest::Save, Some(path)) if can_save_to(path) => Some(path.clone()),
+                (DialogRequest::SaveAs { suggested_name }, _) => dialogs.save_file(suggested_name),
+                _ => dialogs.save_file(&poly_name.0),
+            };
+
+            if let Some(mut path) = path {
+                // Files are saved as OFF unless told otherwise.
+                if path.extension().is_none() {
+                    path.set_extension("off");
+                }
+
+                match p.save_to_path(&path, &save_options) {
+                    Ok(()) => {
+                        if current_file.0.as_ref() != Some(&path) {
+                            current_file.0 = Some(path);
+                        }
+                    }
+                    Err(err) => eprintln!("{}: {}", t!(lang, "error.file_save"), err),
+                }
+            }
+        }
+
+        // We want to export the face lattice of the polytope.
+        DialogRequest::ExportLattice { suggested_name } => {
+            let format = lattice.settings.format;
+            if let Some(mut path) = dialogs.save_lattice(&suggested_name, format) {
+                if path.extension().is_none() {
+                    path.set_extension(format.extension());
+                }
+
+                if let Some((p, _)) = query.iter().next() {
+                    let nodes = p.abs.lattice_size(&lattice.settings.options(None));
+                    if nodes > LARGE_LATTICE {
+                        println!("WARNING: exporting a lattice with {} nodes.", nodes);
+                    }
+
+                    if let Err(err) = std::fs::write(&path, lattice.settings.export(p)) {
+                        eprintln!("{}: {}", t!(lang, "error.file_save"), err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::{file::FromFile, Polytope};
+    use std::cell::RefCell;
+
+    /// File dialogs that answer with scripted paths, and record the dialogs
+    /// that were shown.
+    #[derive(Default)]
+    struct MockDialogs {
+        /// The answers to the next dialogs, in order. Dialogs past these are
+        /// cancelled.
+        answers: RefCell<VecDeque<Option<PathBuf>>>,
+
+        /// The dialogs that were shown.
+        shown: RefCell<Vec<String>>,
+    }
+
+    impl MockDialogs {
+        /// Records a dialog, and returns its answer.
+        fn show(&self, dialog: String) -> Option<PathBuf> {
+            self.shown.borrow_mut().push(dialog);
+            self.answers.borrow_mut().pop_front().flatten()
+        }
+    }
+
+    impl FileDialogs for MockDialogs {
+        fn pick_file(&self) -> Option<PathBuf> {
+            self.show("open".to_string())
+        }
+
+        fn save_file(&self, name: &str) -> Option<PathBuf> {
+            self.show(format!("save {}", name))
+        }
+
+        fn save_lattice(&self, name: &str, _: LatticeFormat) -> Option<PathBuf> {
+            self.show(format!("lattice {}", name))
+        }
+    }
+
+    /// Builds an app that only shows mocked file dialogs, with a selected
+    /// pentagon.
+    fn dialog_app() -> App {
+        let mut app = App::new();
+        app.insert_resource(PolyName("pentagon".to_string()))
+            .init_resource::<DialogQueue>()
+            .init_resource::<PendingReplacement>()
+            .init_resource::<LatticeExport>()
+            .init_resource::<SaveOptions>()
+            .init_resource::<SelectedLanguage>()
+            .init_non_send_resource::<MockDialogs>()
+            .add_system(file_dialog::<MockDialogs>.system());
+
+        app.world
+            .spawn()
+            .insert(Concrete::polygon(5))
+            .insert(CurrentFile::default())
+            .insert(Selected);
+        app
+    }
+
+    /// Queues some requests, and the answers to the dialogs they'll show.
+    fn request(app: &mut App, requests: Vec<DialogRequest>, answers: Vec<Option<PathBuf>>) {
+        let mut queue = app.world.get_resource_mut::<DialogQueue>().unwrap();
+        for request in requests {
+            queue.push(request);
+        }
+
+        let dialogs = app.world.get_non_send_resource::<MockDialogs>().unwrap();
+        dialogs.answers.borrow_mut().extend(answers);
+    }
+
+    /// Returns and forgets the dialogs that were shown.
+    fn shown(app: &App) -> Vec<String> {
+        let dialogs = app.world.get_non_send_resource::<MockDialogs>().unwrap();
+        dialogs.shown.take()
+    }
+
+    /// Returns the current file of the selected polytope.
+    fn current_file(app: &mut App) -> Option<PathBuf> {
+        app.world
+            .query_filtered::<&CurrentFile, With<Selected>>()
+            .iter(&app.world)
+            .next()
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    /// Sets the current file of the selected polytope.
+    fn set_current_file(app: &mut App, path: PathBuf) {
+        app.world
+            .query_filtered::<&mut CurrentFile, With<Selected>>()
+            .iter_mut(&mut app.world)
+            .next()
+            .unwrap()
+            .0 = Some(path);
+    }
+
+    /// Returns a path in the temporary folder that's unique to a test.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("miratope-dialog-{}-{}", std::process::id(), name))
+    }
+
+    /// Requests that are already queued aren't queued again, and a single
+    /// dialog is shown per frame.
+    #[test]
+    fn double_requests() {
+        let mut app = dialog_app();
+        let save_as = DialogRequest::SaveAs {
+            suggested_name: "pentagon".to_string(),
+        };
+        request(
+            &mut app,
+            vec![
+                DialogRequest::Open,
+                DialogRequest::Open,
+                save_as.clone(),
+                save_as,
+            ],
+            Vec::new(),
+        );
+        assert_eq!(app.world.get_resource::<DialogQueue>().unwrap().len(), 2);
+
+        app.update();
+        assert_eq!(shown(&app), vec!["open"]);
+        app.update();
+        assert_eq!(shown(&app), vec!["save pentagon"]);
+        app.update();
+        assert!(shown(&app).is_empty());
+        assert!(app.world.get_resource::<DialogQueue>().unwrap().is_empty());
+
+        // Once consumed, a request can be queued again.
+        request(&mut app, vec![DialogRequest::Open], Vec::new());
+        app.update();
+        assert_eq!(shown(&app), vec!["open"]);
+    }
+
+    /// Cancelled dialogs leave everything untouched.
+    #[test]
+    fn cancel() {
+        let mut app = dialog_app();
+        let path = temp_path("cancel.off");
+        set_current_file(&mut app, path.clone());
+
+        request(
+            &mut app,
+            vec![
+                DialogRequest::Open,
+                DialogRequest::SaveAs {
+                    suggested_name: "pentagon".to_string(),
+                },
+                DialogRequest::ExportLattice {
+                    suggested_name: "pentagon".to_string(),
+                },
+            ],
+            vec![None, None, None],
+        );
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(shown(&app).len(), 3);
+        assert_eq!(current_file(&mut app), Some(path.clone()));
+        assert!(app
+            .world
+            .get_resource::<PendingReplacement>()
+            .unwrap()
+            .0
+            .is_none());
+        assert!(app.world.get_resource::<DialogQueue>().unwrap().is_empty());
+        assert!(!path.exists());
+    }
+
+    /// Saving without a current file asks for a path, which then becomes the
+    /// current file, and is saved to without asking. Saving as always asks.
+    #[test]
+    fn save_paths() {
+        let mut app = dialog_app();
+        let first = temp_path("first");
+        let second = temp_path("second.off");
+        let first_off = first.with_extension("off");
+
+        request(&mut app, vec![DialogRequest::Save], vec![Some(first)]);
+        app.update();
+        assert_eq!(shown(&app), vec!["save pentagon"]);
+        assert_eq!(current_file(&mut app), Some(first_off.clone()));
+        assert_eq!(Concrete::from_path(&first_off).unwrap().vertices.len(), 5);
+
+        // Saving again overwrites the same file.
+        *app.world
+            .query_filtered::<&mut Concrete, With<Selected>>()
+            .iter_mut(&mut app.world)
+            .next()
+            .unwrap() = Concrete::polygon(7);
+        request(&mut app, vec![DialogRequest::Save], Vec::new());
+        app.update();
+        assert!(shown(&app).is_empty());
+        assert_eq!(Concrete::from_path(&first_off).unwrap().vertices.len(), 7);
+
+        request(
+            &mut app,
+            vec![DialogRequest::SaveAs {
+                suggested_name: "heptagon".to_string(),
+            }],
+            vec![Some(second.clone())],
+        );
+        app.update();
+        assert_eq!(shown(&app), vec!["save heptagon"]);
+        assert_eq!(current_file(&mut app), Some(second.clone()));
+
+        // A current file in a format that can't be saved is saved as.
+        let ggb = temp_path("third.ggb");
+        set_current_file(&mut app, ggb.clone());
+        request(&mut app, vec![DialogRequest::Save], vec![None]);
+        app.update();
+        assert_eq!(shown(&app), vec!["save pentagon"]);
+        assert_eq!(current_file(&mut app), Some(ggb.clone()));
+        assert!(!ggb.exists());
+
+        for path in [first_off, second] {
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    /// Every polytope keeps its own current file, so that saving after
+    /// changing the selection never overwrites the file of another polytope.
+    #[test]
+    fn change_selection() {
+        let mut app = dialog_app();
+        let path = temp_path("selection.off");
+        set_current_file(&mut app, path.clone());
+
+        let pentagon = app
+            .world
+            .query_filtered::<Entity, With<Selected>>()
+            .iter(&app.world)
+            .next()
+            .unwrap();
+        app.world.entity_mut(pentagon).remove::<Selected>();
+        let triangle = app
+            .world
+            .spawn()
+            .insert(Concrete::polygon(3))
+            .insert(CurrentFile::default())
+            .insert(Selected)
+            .id();
+
+        request(&mut app, vec![DialogRequest::Save], vec![None]);
+        app.update();
+        assert_eq!(shown(&app), vec!["save pentagon"]);
+        assert_eq!(current_file(&mut app), None);
+        assert!(!path.exists());
+
+        // Selecting the first polytope again saves it to its own file.
+        app.world.entity_mut(triangle).remove::<Selected>();
+        app.world.entity_mut(pentagon).insert(Selected);
+        request(&mut app, vec![DialogRequest::Save], Vec::new());
+        app.update();
+        assert!(shown(&app).is_empty());
+        assert_eq!(Concrete::from_path(&path).unwrap().vertices.len(), 5);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Opening a file only queues it to replace the selected polytope.
+    #[test]
+    fn open() {
+        let mut app = dialog_app();
+        let path = temp_path("open.off");
+        Concrete::polygon(6)
+            .save_to_path(&path, &Default::default())
+            .unwrap();
+
+        request(
+            &mut app,
+            vec![DialogRequest::Open],
+            vec![Some(path.clone())],
+        );
+        app.update();
+
+        let pending = app.world.get_resource::<PendingReplacement>().unwrap();
+        let replacement = pending.0.as_ref().unwrap();
+        assert_eq!(replacement.path.as_deref(), Some(Path::new(&path)));
+        assert_eq!(replacement.poly.vertices.len(), 6);
+        assert_eq!(current_file(&mut app), None);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+//! Labels with the indices of the elements of the selected polytope, drawn on
+//! top of the viewport at the centroid of each element. These are the same
+//! indices the element inspector uses.
+
+use super::{camera::ProjectionType, main_window::Selected};
+use crate::mesh::{projected_coords, RenderFrame};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::abs::Ranked;
+
+/// The most elements of each rank that get a label.
+const MAX_LABELS: usize = 500;
+
+/// The names of the elements that can be labeled, from vertices to faces.
+const LABELED_NAMES: [&str; 3] = ["vertices", "edges", "faces"];
+
+/// The colors of the labels of vertices, edges and faces.
+const LABEL_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(255, 255, 255),
+    egui::Color32::from_rgb(255, 210, 90),
+    egui::Color32::from_rgb(120, 200, 255),
+];
+
+/// The plugin in charge of the element labels.
+pub struct LabelsPlugin;
+
+impl Plugin for LabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ElementLabels>()
+            .init_resource::<LabelAnchors>()
+            .add_system(show_labels.system().after("show_top_panel"))
+            .add_system_to_stage(CoreStage::PostUpdate, update_label_anchors.system());
+    }
+}
+
+/// Which elements of the selected polytope get labeled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementLabels {
+    /// Whether the vertices, the edges and the faces are labeled.
+    pub shown: [bool; 3],
+}
+
+impl ElementLabels {
+    /// Returns whether any elements are labeled.
+    pub fn any(&self) -> bool {
+        self.shown.iter().any(|&shown| shown)
+    }
+}
+
+/// The points the labels are anchored to, in the frame of the meshes of the
+/// selected polytope. These only change along with the polytope, so moving the
+/// camera just projects them again.
+#[derive(Default)]
+pub struct LabelAnchors(Vec<LabelAnchor>);
+
+/// The point a single label is anchored to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LabelAnchor {
+    /// The index of the labeled elements among vertices, edges and faces.
+    pub kind: usize,
+
+    /// The index of the element.
+    pub idx: usize,
+
+    /// The centroid of the element, in the frame of the meshes.
+    pub coords: [f32; 3],
+}
+
+/// Places labels in the order they're given, skipping any that would overlap
+/// one that's already been placed. Returns the indices of the placed labels.
+pub fn place_labels<I: IntoIterator<Item = egui::Rect>>(rects: I) -> Vec<usize> {
+    let mut placed: Vec<egui::Rect> = Vec::new();
+    let mut indices = Vec::new();
+
+    for (i, rect) in rects.into_iter().enumerate() {
+        if !placed.iter().any(|other| other.intersects(rect)) {
+            placed.push(rect);
+            indices.push(i);
+        }
+    }
+
+    indices
+}
+
+/// Recomputes the anchors of the labels whenever the settings, the selected
+/// polytope, its frame or the projection change.
+pub fn update_label_anchors(
+    labels: Res<'_, ElementLabels>,
+    projection_type: Res<'_, ProjectionType>,
+    mut anchors: ResMut<'_, LabelAnchors>,
+    selected: Query<'_, '_, (&Concrete, &RenderFrame), With<Selected>>,
+    changed: Query<
+        '_,
+        '_,
+        (),
+        (
+            With<Selected>,
+            Or<(Changed<Concrete>, Added<Selected>, Changed<RenderFrame>)>,
+        ),
+    >,
+) {
+    if !labels.is_changed() && !projection_type.is_changed() && changed.iter().next().is_none() {
+        return;
+    }
+
+    anchors.0.clear();
+    let (poly, frame) = match selected.iter().next() {
+        Some(selected) => selected,
+        None => return,
+    };
+
+    for (kind, &shown) in labels.shown.iter().enumerate() {
+        if !shown {
+            continue;
+        }
+
+        let rank = kind + 1;
+        let count = poly.abs.el_count(rank);
+        if count > MAX_LABELS {
+            println!(
+                "Note: only the first {} of {} {} are labeled.",
+                MAX_LABELS, count, LABELED_NAMES[kind]
+            );
+        }
+
+        let centroids = poly.element_centroids(rank, MAX_LABELS);
+        let coords = projected_coords(
+            poly,
+            centroids.iter().map(|(_, centroid)| centroid),
+            &projection_type,
+        );
+        for ((idx, _), coords) in centroids.iter().zip(coords) {
+            anchors.0.push(LabelAnchor {
+                kind,
+                idx: *idx,
+                coords: frame.to_mesh(coords),
+            });
+        }
+    }
+
+    // Lower indices take priority when labels overlap.
+    anchors.0.sort_by_key(|anchor| (anchor.idx, anchor.kind));
+}
+
+/// Draws the labels at the current position of their anchors on screen.
+pub fn show_labels(
+    egui_ctx: Res<'_, EguiContext>,
+    windows: Res<'_, Windows>,
+    labels: Res<'_, ElementLabels>,
+    anchors: Res<'_, LabelAnchors>,
+    selected: Query<'_, '_, &GlobalTransform, With<Selected>>,
+    cameras: Query<'_, '_, (&Camera, &GlobalTransform)>,
+) {
+    if !labels.any() || anchors.0.is_empty() {
+        return;
+    }
+
+    let height = match windows.get_primary() {
+        Some(window) => window.height(),
+        None => return,
+    };
+    let poly_gtf = match selected.iter().next() {
+        Some(poly_gtf) => poly_gtf,
+        None => return,
+    };
+    let (camera, cam_gtf) = match cameras.iter().next() {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    let painter = egui_ctx.ctx().layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("element_labels"),
+    ));
+
+    // Screen coordinates start at the bottom left, and egui's at the top left.
+    let mut galleys = Vec::new();
+    for anchor in &anchors.0 {
+        let world = poly_gtf.mul_vec3(Vec3::from(anchor.coords));
+        if let Some(screen) = camera.world_to_screen(&windows, cam_gtf, world) {
+            let galley = painter.layout_no_wrap(
+                anchor.idx.to_string(),
+                egui::TextStyle::Small,
+                LABEL_COLORS[anchor.kind],
+            );
+            let center = egui::pos2(screen.x, height - screen.y);
+            let rect = egui::Rect::from_center_size(center, galley.size());
+            galleys.push((rect, galley));
+        }
+    }
+
+    for i in place_labels(galleys.iter().map(|(rect, _)| *rect)) {
+        let (rect, galley) = &galleys[i];
+        painter.galley(rect.min, galley.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a square of side 10 with a given upper left corner.
+    fn square(x: f32, y: f32) -> egui::Rect {
+        egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(10.0, 10.0))
+    }
+
+    /// Labels that overlap an earlier one are skipped.
+    #[test]
+    fn overlap() {
+        let rects = [
+            square(0.0, 0.0),
+            square(5.0, 5.0),
+            square(20.0, 0.0),
+            square(12.0, 3.0),
+            square(40.0, 40.0),
+        ];
+        assert_eq!(place_labels(rects), vec![0, 2, 4]);
+
+        // Skipped labels don't block later ones.
+        let rects = [square(0.0, 0.0), square(8.0, 0.0), square(16.0, 0.0)];
+        assert_eq!(place_labels(rects), vec![0, 2]);
+
+        assert!(place_labels(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,640 @@
+//! Translates the text of the user interface into the selected language.
+//!
+//! Every string is looked up by a key in the table of the selected language.
+//! Strings that are missing from it are looked up in English instead. Adding a
+//! new language only requires a new table and a new entry in [`LANGUAGES`].
+
+use std::cell::RefCell;
+
+/// The strings of a language, as pairs of keys and translations.
+type Table = &'static [(&'static str, &'static str)];
+
+/// A language the user interface can be shown in.
+pub struct Language {
+    /// The code of the language, which is stored in the configuration file.
+    pub code: &'static str,
+
+    /// The name of the language, in the language itself.
+    pub name: &'static str,
+
+    /// The translated strings.
+    strings: Table,
+}
+
+impl Language {
+    /// Returns the translation of a key, if there is one.
+    fn get(&self, key: &str) -> Option<&'static str> {
+        self.strings
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// All of the languages the user interface can be shown in. The first one is
+/// the fallback for any missing strings.
+pub const LANGUAGES: &[Language] = &[
+    Language {
+        code: "en",
+        name: "English",
+        strings: EN,
+    },
+    Language {
+        code: "es",
+        name: "Español",
+        strings: ES,
+    },
+];
+
+/// The strings in English.
+const EN: Table = &[
+    ("menu.file", "File"),
+    ("menu.window", "Window"),
+    ("menu.view", "View"),
+    ("menu.properties", "Properties"),
+    ("menu.transform", "Transform"),
+    ("menu.operations", "Operations"),
+    ("menu.cross_section", "Cross-section"),
+    ("menu.faceting", "Faceting"),
+    ("menu.memory", "Memory"),
+    ("menu.help", "Help"),
+    ("file.open", "Open"),
+    ("file.open_recent", "Open recent"),
+    ("file.no_recent", "No recent files"),
+    ("file.pin", "Pin"),
+    ("file.remove", "Remove"),
+    ("file.not_found", "File not found"),
+    ("file.remove_missing", "Remove missing files"),
+    ("file.save", "Save"),
+    ("file.save_as", "Save as..."),
+    ("file.save_types", "Save element types"),
+    ("file.split_components", "Save compounds as separate blocks"),
+    ("file.save_units", "Save units"),
+    ("file.export_lattice", "Export lattice..."),
+    ("file.new_from_text", "New from text..."),
+    ("file.new_orbit", "New orbit polytope..."),
+    ("file.export_memory", "Export all memory slots"),
+    ("file.exit", "Exit"),
+    ("window.new_polytope", "New polytope"),
+    ("window.polytope_list", "Polytope list"),
+    ("view.orthogonal", "Orthogonal projection"),
+    ("view.stereographic", "Stereographic projection"),
+    ("view.pole", "Pole"),
+    ("view.refinement", "Refinement"),
+    ("view.fill_cores", "Fill star polygon cores"),
+    ("view.depth_sort", "Sort translucent faces"),
+    ("view.peel", "Hidden facets"),
+    ("view.axes", "Axes"),
+    ("view.grid", "Grid"),
+    ("view.grid_auto", "Automatic spacing"),
+    ("view.grid_spacing", "Grid spacing"),
+    ("view.labels", "Element labels"),
+    ("view.label_vertices", "Vertices"),
+    ("view.label_edges", "Edges"),
+    ("view.label_faces", "Faces"),
+    ("view.appearance", "Appearance..."),
+    ("view.validation", "Validation"),
+    ("view.validation_off", "Off"),
+    ("view.validation_cheap", "Cheap"),
+    ("view.validation_full", "Full"),
+    ("view.validation_revert", "Revert invalid polytopes"),
+    ("view.language", "Language"),
+    ("ops.dual", "Dual"),
+    ("ops.petrial", "Petrial"),
+    ("ops.petrie_polygon", "Petrie polygon"),
+    ("ops.antipodal_quotient", "Antipodal quotient"),
+    ("ops.delete_element", "Delete element..."),
+    ("ops.pyramid", "Pyramid"),
+    ("ops.prism", "Prism"),
+    ("ops.tower", "Tower..."),
+    ("ops.tegum", "Tegum"),
+    ("ops.antiprism", "Antiprism"),
+    ("ops.ditope", "Ditope"),
+    ("ops.hosotope", "Hosotope"),
+    ("ops.duopyramid", "Duopyramid..."),
+    ("ops.duoprism", "Duoprism..."),
+    ("ops.duotegum", "Duotegum..."),
+    ("ops.duocomb", "Duocomb..."),
+    ("ops.star_product", "Star product..."),
+    ("ops.compound", "Compound..."),
+    ("ops.intersect", "Intersect..."),
+    ("ops.morph", "Morph..."),
+    ("ops.truncate", "Truncate..."),
+    ("ops.canonicalize", "Canonicalize..."),
+    ("ops.snap", "Snap coordinates..."),
+    ("ops.units", "Convert units..."),
+    ("ops.unfold", "Unfold..."),
+    ("ops.fuse_facets", "Identify coplanar facets"),
+    ("ops.custom", "Custom"),
+    ("faceting.enumerate", "Enumerate facetings"),
+    ("faceting.settings", "Settings..."),
+    ("library.refresh", "Refresh library"),
+    ("error.dual", "Dual failed"),
+    ("error.petrial", "Petrial failed"),
+    ("error.petrie_polygon", "Petrie polygon failed"),
+    ("error.antipodal_quotient", "Antipodal quotient failed"),
+    ("error.antiprism", "Antiprism failed"),
+    ("error.custom", "Custom operation failed"),
+    ("error.file_open", "File open failed"),
+    ("error.file_save", "File saving failed"),
+    ("error.special", "Loading the polytope failed"),
+    ("error.invalid", "Invalid polytope"),
+    ("validation.reverted", "Reverted to the last valid polytope"),
+    ("title.polytopes", "Polytopes"),
+    ("title.appearance", "Appearance"),
+    ("title.export_lattice", "Export lattice"),
+    ("title.measure", "Measure"),
+    ("title.dual", "Dual"),
+    ("title.pyramid", "Pyramid"),
+    ("title.prism", "Prism"),
+    ("title.tegum", "Tegum"),
+    ("title.antiprism", "Antiprism"),
+    ("title.duopyramid", "Duopyramid"),
+    ("title.duoprism", "Duoprism"),
+    ("title.duotegum", "Duotegum"),
+    ("title.duocomb", "Duocomb"),
+    ("title.star_product", "Star product"),
+    ("title.compound", "Compound"),
+    ("title.intersect", "Intersect"),
+    ("title.morph", "Morph"),
+    ("title.report", "Report"),
+    ("title.vertex_coordinates", "Vertex coordinates"),
+    ("title.element_inspector", "Element inspector"),
+    ("title.new_from_text", "New from text"),
+    ("title.tower", "Tower"),
+    ("title.orbit_polytope", "Orbit polytope"),
+    ("title.delete_element", "Delete element"),
+    ("title.truncate", "Truncate"),
+    ("title.scale", "Scale"),
+    ("title.canonicalize", "Canonicalize"),
+    ("title.snap_coordinates", "Snap coordinates"),
+    ("title.convert_units", "Convert units"),
+    ("title.unfold", "Unfold"),
+    ("title.faceting_settings", "Faceting settings"),
+    ("title.rotate", "Rotate"),
+    ("title.rotate_with_plane", "Rotate with plane"),
+    ("window.ok", "Ok"),
+    ("window.reset", "Reset"),
+    ("window.clear", "Clear"),
+    ("window.build", "Build"),
+    ("window.error", "Error"),
+    ("window.no_polytope", "No polytope selected."),
+    ("window.select", "Select"),
+    ("window.loaded", "(Loaded polytope)"),
+    ("window.scale", "Scale"),
+    ("window.result", "Result"),
+    ("window.estimate", "est."),
+    ("window.build_anyway", "Build anyway"),
+    ("window.center", "Center"),
+    ("window.radius", "Radius"),
+    ("window.offset", "Offset"),
+    ("window.height", "Height"),
+    ("window.height_offset", "Height offset"),
+    ("window.retroprism", "Retroprism"),
+    ("window.orbiform", "Try to make orbiform"),
+    ("window.match_vertices", "Match vertices by nearest neighbor"),
+    ("window.animation_speed", "Animation speed"),
+    ("window.animate", "Animate"),
+    ("window.rank", "Rank"),
+    ("window.index", "Index"),
+    ("window.group", "Group"),
+    ("report.radii", "Compute radii"),
+    ("report.volume", "Compute volume"),
+    ("report.orientability", "Compute orientability"),
+    ("report.element_types", "Compute element types"),
+    ("report.copy", "Copy to clipboard"),
+    ("vertices.copy_csv", "Copy as CSV"),
+    ("inspector.no_element", "No element selected."),
+    ("inspector.highlight", "Highlight in viewport"),
+    ("inspector.subelements", "Subelements"),
+    ("inspector.superelements", "Superelements"),
+    ("inspector.page", "Page"),
+    ("text.vertices", "Vertices (one per line):"),
+    ("text.facets", "Facets (vertex indices, one per line):"),
+    ("text.dimension", "Dimension"),
+    ("text.unknown", "unknown"),
+    ("tower.scales", "Scales:"),
+    ("tower.heights", "Heights:"),
+    ("orbit.seed", "Seed"),
+    ("orbit.live", "Live preview"),
+    ("edit.vertex", "Vertex"),
+    ("edit.facet", "Facet"),
+    ("edit.delete", "Delete"),
+    ("canonicalize.iterations", "Iterations"),
+    ("snap.tolerance", "Tolerance"),
+    ("snap.simple", "Snap to 0, ±1/2 and ±1"),
+    ("snap.constants", "Snap to 1, φ, φ², √2, √3 and their halves"),
+    ("snap.quantum", "Snap to multiples of"),
+    ("snap.max_shift", "Max. vertex shift (in least edge lengths)"),
+    ("units.target", "Target unit"),
+    ("units.convert", "Convert from the stored units"),
+    ("units.fit", "Fit the longest side of the bounding box"),
+    ("unfold.root_face", "Root face"),
+    ("faceting.max_facet_types", "Max facet types"),
+    ("faceting.max_per_hyperplane", "Max facetings per hyperplane"),
+    ("faceting.full_group", "Full group"),
+    ("faceting.chiral_group", "Chiral subgroup"),
+    ("faceting.from_slot", "From other polytope:"),
+    ("faceting.any_edge_length", "Any single edge length"),
+    ("faceting.edge_length_range", "Edge length range"),
+    ("faceting.min_edge_length", "Min edge length"),
+    ("faceting.max_edge_length", "Max edge length"),
+    ("faceting.min_inradius", "Min inradius"),
+    ("faceting.max_inradius", "Max inradius"),
+    ("faceting.exclude_hemis", "Exclude hemis"),
+    ("faceting.only_below_vertex", "Only hyperplanes perpendicular to a vertex"),
+    ("faceting.uniform", "Only uniform/semiuniform facets"),
+    ("faceting.compounds", "Include trivial compounds"),
+    ("faceting.mark_fissary", "Mark compounds/fissaries"),
+    ("faceting.label_facets", "Label facets"),
+    ("faceting.save", "Save facetings"),
+    ("faceting.save_facets", "Save facets"),
+    ("faceting.save_to_memory", "Save to memory"),
+    ("faceting.save_to_file", "Save to file"),
+    ("faceting.path", "Path"),
+    ("faceting.show_advanced", "Show advanced settings"),
+    ("faceting.hide_advanced", "Hide advanced settings"),
+    ("rotate.degrees", "Use degrees instead of radians"),
+    ("rotate.axes", "Axes"),
+    ("rotate.rotation", "Rotation"),
+    ("rotate.use_origin", "Use a third origin point"),
+    ("rotate.about_axis", "Rotate about an axis"),
+    ("rotate.axis_point", "Axis point"),
+    ("rotate.origin_point", "Origin point"),
+    ("rotate.first_point", "First point"),
+    ("rotate.second_point", "Second point"),
+    ("appearance.type_palette", "Type palette"),
+    ("appearance.generate_types", "Generate the element types to edit their colors."),
+    ("appearance.faces", "Faces"),
+    ("appearance.wireframe", "Wireframe"),
+    ("appearance.vertices", "Vertices"),
+    ("appearance.set_default", "Set as default"),
+    ("appearance.highlight_edges", "Highlight outlier edges"),
+    ("appearance.from_median", "% from the median length"),
+    ("lattice.restrict_ranks", "Only some ranks"),
+    ("lattice.from_rank", "From rank:"),
+    ("lattice.to_rank", "to rank:"),
+    ("lattice.restrict_element", "Only around an element"),
+    ("lattice.below", "Below it"),
+    ("lattice.above", "Above it"),
+    ("lattice.types", "Include element types"),
+    ("lattice.nodes", "nodes"),
+    ("lattice.large", "Large lattices may be slow to lay out. Consider exporting only some ranks."),
+    ("lattice.export", "Export…"),
+    ("memory.clear", "Clear memory"),
+    ("memory.add_slot", "Add slot"),
+    ("memory.stored", "polytopes stored"),
+    ("memory.empty", "Empty"),
+    ("memory.save", "Save"),
+    ("memory.load", "Load"),
+    ("memory.swap", "Swap"),
+    ("measure.help", "Click two vertices to measure their distance, or three to measure the angle at the middle one."),
+    ("measure.none_picked", "No vertices picked."),
+    ("measure.vertices", "Vertices"),
+    ("measure.distance", "Distance"),
+    ("measure.angle", "Angle"),
+    ("help.hotkeys", "Hotkeys"),
+    ("help.hotkeys_text", "V: toggle faces of the selected polytope\nB: toggle wireframe of the selected polytope\nCtrl+S: save the selected polytope"),
+    ("help.camera", "Camera"),
+    ("help.camera_text", "WSADRF: move\nQE: roll\nX: reset\nMouse wheel: zoom\nHold Ctrl: zoom faster\nHold Shift: move slower"),
+    ("help.ui", "UI"),
+    ("help.ui_text", "Hold Ctrl: extra options in some menus\nHold Shift: move number sliders slower"),
+    ("help.right_panel", "Right panel"),
+    ("help.right_panel_text", "Generate: computes the element types of the loaded polytope\nLoad: loads the polytope whose element types are being listed"),
+    ("error.operation", "Operation failed"),
+];
+
+/// The strings in Spanish.
+const ES: Table = &[
+    ("menu.file", "Archivo"),
+    ("menu.window", "Ventana"),
+    ("menu.view", "Ver"),
+    ("menu.properties", "Propiedades"),
+    ("menu.transform", "Transformar"),
+    ("menu.operations", "Operaciones"),
+    ("menu.cross_section", "Sección transversal"),
+    ("menu.faceting", "Facetado"),
+    ("menu.memory", "Memoria"),
+    ("menu.help", "Ayuda"),
+    ("file.open", "Abrir"),
+    ("file.open_recent", "Abrir reciente"),
+    ("file.no_recent", "No hay archivos recientes"),
+    ("file.pin", "Fijar"),
+    ("file.remove", "Quitar"),
+    ("file.not_found", "Archivo no encontrado"),
+    ("file.remove_missing", "Quitar archivos faltantes"),
+    ("file.save", "Guardar"),
+    ("file.save_as", "Guardar como..."),
+    ("file.save_types", "Guardar tipos de elementos"),
+    ("file.split_components", "Guardar compuestos en bloques separados"),
+    ("file.save_units", "Guardar unidades"),
+    ("file.export_lattice", "Exportar retículo..."),
+    ("file.new_from_text", "Nuevo desde texto..."),
+    ("file.new_orbit", "Nuevo politopo de órbita..."),
+    ("file.export_memory", "Exportar todas las ranuras de memoria"),
+    ("file.exit", "Salir"),
+    ("window.new_polytope", "Nuevo politopo"),
+    ("window.polytope_list", "Lista de politopos"),
+    ("view.orthogonal", "Proyección ortogonal"),
+    ("view.stereographic", "Proyección estereográfica"),
+    ("view.pole", "Polo"),
+    ("view.refinement", "Refinamiento"),
+    ("view.fill_cores", "Rellenar núcleos de polígonos estrellados"),
+    ("view.depth_sort", "Ordenar caras translúcidas"),
+    ("view.peel", "Facetas ocultas"),
+    ("view.axes", "Ejes"),
+    ("view.grid", "Cuadrícula"),
+    ("view.grid_auto", "Espaciado automático"),
+    ("view.grid_spacing", "Espaciado de la cuadrícula"),
+    ("view.labels", "Etiquetas de elementos"),
+    ("view.label_vertices", "Vértices"),
+    ("view.label_edges", "Aristas"),
+    ("view.label_faces", "Caras"),
+    ("view.appearance", "Apariencia..."),
+    ("view.validation", "Validación"),
+    ("view.validation_off", "Desactivada"),
+    ("view.validation_cheap", "Rápida"),
+    ("view.validation_full", "Completa"),
+    ("view.validation_revert", "Revertir politopos inválidos"),
+    ("view.language", "Idioma"),
+    ("ops.dual", "Dual"),
+    ("ops.petrial", "Petrial"),
+    ("ops.petrie_polygon", "Polígono de Petrie"),
+    ("ops.antipodal_quotient", "Cociente antipodal"),
+    ("ops.delete_element", "Eliminar elemento..."),
+    ("ops.pyramid", "Pirámide"),
+    ("ops.prism", "Prisma"),
+    ("ops.tower", "Torre..."),
+    ("ops.tegum", "Tegum"),
+    ("ops.antiprism", "Antiprisma"),
+    ("ops.ditope", "Ditopo"),
+    ("ops.hosotope", "Hosotopo"),
+    ("ops.duopyramid", "Duopirámide..."),
+    ("ops.duoprism", "Duoprisma..."),
+    ("ops.duotegum", "Duotegum..."),
+    ("ops.duocomb", "Duopeine..."),
+    ("ops.star_product", "Producto estrella..."),
+    ("ops.compound", "Compuesto..."),
+    ("ops.intersect", "Intersecar..."),
+    ("ops.morph", "Metamorfosis..."),
+    ("ops.truncate", "Truncar..."),
+    ("ops.canonicalize", "Canonizar..."),
+    ("ops.snap", "Redondear coordenadas..."),
+    ("ops.units", "Convertir unidades..."),
+    ("ops.unfold", "Desplegar..."),
+    ("ops.fuse_facets", "Identificar facetas coplanares"),
+    ("ops.custom", "Personalizadas"),
+    ("faceting.enumerate", "Enumerar facetados"),
+    ("faceting.settings", "Configuración..."),
+    ("library.refresh", "Actualizar biblioteca"),
+    ("error.dual", "El dual falló"),
+    ("error.petrial", "El petrial falló"),
+    ("error.petrie_polygon", "El polígono de Petrie falló"),
+    ("error.antipodal_quotient", "El cociente antipodal falló"),
+    ("error.antiprism", "El antiprisma falló"),
+    ("error.custom", "La operación personalizada falló"),
+    ("error.file_open", "No se pudo abrir el archivo"),
+    ("error.file_save", "No se pudo guardar el archivo"),
+    ("error.special", "No se pudo cargar el politopo"),
+    ("error.invalid", "Politopo inválido"),
+    ("validation.reverted", "Se restauró el último politopo válido"),
+    ("title.polytopes", "Politopos"),
+    ("title.appearance", "Apariencia"),
+    ("title.export_lattice", "Exportar retículo"),
+    ("title.measure", "Medir"),
+    ("title.dual", "Dual"),
+    ("title.pyramid", "Pirámide"),
+    ("title.prism", "Prisma"),
+    ("title.tegum", "Tegum"),
+    ("title.antiprism", "Antiprisma"),
+    ("title.duopyramid", "Duopirámide"),
+    ("title.duoprism", "Duoprisma"),
+    ("title.duotegum", "Duotegum"),
+    ("title.duocomb", "Duopeine"),
+    ("title.star_product", "Producto estrella"),
+    ("title.compound", "Compuesto"),
+    ("title.intersect", "Intersecar"),
+    ("title.morph", "Metamorfosis"),
+    ("title.report", "Informe"),
+    ("title.vertex_coordinates", "Coordenadas de los vértices"),
+    ("title.element_inspector", "Inspector de elementos"),
+    ("title.new_from_text", "Nuevo desde texto"),
+    ("title.tower", "Torre"),
+    ("title.orbit_polytope", "Politopo de órbita"),
+    ("title.delete_element", "Eliminar elemento"),
+    ("title.truncate", "Truncar"),
+    ("title.scale", "Escalar"),
+    ("title.canonicalize", "Canonizar"),
+    ("title.snap_coordinates", "Redondear coordenadas"),
+    ("title.convert_units", "Convertir unidades"),
+    ("title.unfold", "Desplegar"),
+    ("title.faceting_settings", "Configuración del facetado"),
+    ("title.rotate", "Rotar"),
+    ("title.rotate_with_plane", "Rotar en un plano"),
+    ("window.ok", "Aceptar"),
+    ("window.reset", "Restablecer"),
+    ("window.clear", "Borrar"),
+    ("window.build", "Construir"),
+    ("window.error", "Error"),
+    ("window.no_polytope", "No hay ningún politopo seleccionado."),
+    ("window.select", "Seleccionar"),
+    ("window.loaded", "(Politopo cargado)"),
+    ("window.scale", "Escala"),
+    ("window.result", "Resultado"),
+    ("window.estimate", "aprox."),
+    ("window.build_anyway", "Construir de todos modos"),
+    ("window.center", "Centro"),
+    ("window.radius", "Radio"),
+    ("window.offset", "Desplazamiento"),
+    ("window.height", "Altura"),
+    ("window.height_offset", "Desplazamiento de la altura"),
+    ("window.retroprism", "Retroprisma"),
+    ("window.orbiform", "Intentar hacerlo orbiforme"),
+    ("window.match_vertices", "Emparejar vértices por el más cercano"),
+    ("window.animation_speed", "Velocidad de la animación"),
+    ("window.animate", "Animar"),
+    ("window.rank", "Rango"),
+    ("window.index", "Índice"),
+    ("window.group", "Grupo"),
+    ("report.radii", "Calcular radios"),
+    ("report.volume", "Calcular volumen"),
+    ("report.orientability", "Calcular orientabilidad"),
+    ("report.element_types", "Calcular tipos de elementos"),
+    ("report.copy", "Copiar al portapapeles"),
+    ("vertices.copy_csv", "Copiar como CSV"),
+    ("inspector.no_element", "No hay ningún elemento seleccionado."),
+    ("inspector.highlight", "Resaltar en la vista"),
+    ("inspector.subelements", "Subelementos"),
+    ("inspector.superelements", "Superelementos"),
+    ("inspector.page", "Página"),
+    ("text.vertices", "Vértices (uno por línea):"),
+    ("text.facets", "Facetas (índices de vértices, una por línea):"),
+    ("text.dimension", "Dimensión"),
+    ("text.unknown", "desconocida"),
+    ("tower.scales", "Escalas:"),
+    ("tower.heights", "Alturas:"),
+    ("orbit.seed", "Semilla"),
+    ("orbit.live", "Vista previa en vivo"),
+    ("edit.vertex", "Vértice"),
+    ("edit.facet", "Faceta"),
+    ("edit.delete", "Eliminar"),
+    ("canonicalize.iterations", "Iteraciones"),
+    ("snap.tolerance", "Tolerancia"),
+    ("snap.simple", "Redondear a 0, ±1/2 y ±1"),
+    ("snap.constants", "Redondear a 1, φ, φ², √2, √3 y sus mitades"),
+    ("snap.quantum", "Redondear a múltiplos de"),
+    ("snap.max_shift", "Desplazamiento máx. de vértices (en aristas mínimas)"),
+    ("units.target", "Unidad de destino"),
+    ("units.convert", "Convertir desde las unidades guardadas"),
+    ("units.fit", "Ajustar el lado más largo de la caja envolvente"),
+    ("unfold.root_face", "Cara raíz"),
+    ("faceting.max_facet_types", "Máx. tipos de facetas"),
+    ("faceting.max_per_hyperplane", "Máx. facetados por hiperplano"),
+    ("faceting.full_group", "Grupo completo"),
+    ("faceting.chiral_group", "Subgrupo quiral"),
+    ("faceting.from_slot", "De otro politopo:"),
+    ("faceting.any_edge_length", "Cualquier longitud de arista única"),
+    ("faceting.edge_length_range", "Rango de longitudes de arista"),
+    ("faceting.min_edge_length", "Longitud mín. de arista"),
+    ("faceting.max_edge_length", "Longitud máx. de arista"),
+    ("faceting.min_inradius", "Inradio mín."),
+    ("faceting.max_inradius", "Inradio máx."),
+    ("faceting.exclude_hemis", "Excluir hemis"),
+    ("faceting.only_below_vertex", "Solo hiperplanos perpendiculares a un vértice"),
+    ("faceting.uniform", "Solo facetas uniformes/semiuniformes"),
+    ("faceting.compounds", "Incluir compuestos triviales"),
+    ("faceting.mark_fissary", "Marcar compuestos/fisarios"),
+    ("faceting.label_facets", "Etiquetar facetas"),
+    ("faceting.save", "Guardar facetados"),
+    ("faceting.save_facets", "Guardar facetas"),
+    ("faceting.save_to_memory", "Guardar en memoria"),
+    ("faceting.save_to_file", "Guardar en archivo"),
+    ("faceting.path", "Ruta"),
+    ("faceting.show_advanced", "Mostrar configuración avanzada"),
+    ("faceting.hide_advanced", "Ocultar configuración avanzada"),
+    ("rotate.degrees", "Usar grados en lugar de radianes"),
+    ("rotate.axes", "Ejes"),
+    ("rotate.rotation", "Rotación"),
+    ("rotate.use_origin", "Usar un tercer punto como origen"),
+    ("rotate.about_axis", "Rotar alrededor de un eje"),
+    ("rotate.axis_point", "Punto del eje"),
+    ("rotate.origin_point", "Punto de origen"),
+    ("rotate.first_point", "Primer punto"),
+    ("rotate.second_point", "Segundo punto"),
+    ("appearance.type_palette", "Paleta de tipos"),
+    ("appearance.generate_types", "Genera los tipos de elementos para editar sus colores."),
+    ("appearance.faces", "Caras"),
+    ("appearance.wireframe", "Malla"),
+    ("appearance.vertices", "Vértices"),
+    ("appearance.set_default", "Usar por defecto"),
+    ("appearance.highlight_edges", "Resaltar aristas atípicas"),
+    ("appearance.from_median", "% de la longitud mediana"),
+    ("lattice.restrict_ranks", "Solo algunos rangos"),
+    ("lattice.from_rank", "Desde el rango:"),
+    ("lattice.to_rank", "hasta el rango:"),
+    ("lattice.restrict_element", "Solo alrededor de un elemento"),
+    ("lattice.below", "Debajo"),
+    ("lattice.above", "Encima"),
+    ("lattice.types", "Incluir tipos de elementos"),
+    ("lattice.nodes", "nodos"),
+    ("lattice.large", "Los retículos grandes pueden tardar en dibujarse. Considera exportar solo algunos rangos."),
+    ("lattice.export", "Exportar…"),
+    ("memory.clear", "Borrar memoria"),
+    ("memory.add_slot", "Añadir ranura"),
+    ("memory.stored", "politopos guardados"),
+    ("memory.empty", "Vacía"),
+    ("memory.save", "Guardar"),
+    ("memory.load", "Cargar"),
+    ("memory.swap", "Intercambiar"),
+    ("measure.help", "Haz clic en dos vértices para medir su distancia, o en tres para medir el ángulo en el del medio."),
+    ("measure.none_picked", "No hay vértices elegidos."),
+    ("measure.vertices", "Vértices"),
+    ("measure.distance", "Distancia"),
+    ("measure.angle", "Ángulo"),
+    ("help.hotkeys", "Atajos de teclado"),
+    ("help.hotkeys_text", "V: mostrar u ocultar las caras del politopo seleccionado\nB: mostrar u ocultar la malla del politopo seleccionado\nCtrl+S: guardar el politopo seleccionado"),
+    ("help.camera", "Cámara"),
+    ("help.camera_text", "WSADRF: mover\nQE: girar\nX: restablecer\nRueda del ratón: zoom\nMantener Ctrl: zoom más rápido\nMantener Mayús: mover más despacio"),
+    ("help.ui", "Interfaz"),
+    ("help.ui_text", "Mantener Ctrl: opciones extra en algunos menús\nMantener Mayús: mover los deslizadores más despacio"),
+    ("help.right_panel", "Panel derecho"),
+    ("help.right_panel_text", "Generar: calcula los tipos de elementos del politopo cargado\nCargar: carga el politopo cuyos tipos de elementos se muestran"),
+    ("error.operation", "La operación falló"),
+];
+
+thread_local! {
+    /// The keys that have already been reported as missing, so that each of
+    /// them is only reported once.
+    static MISSING: RefCell<Vec<(&'static str, &'static str)>> = RefCell::new(Vec::new());
+}
+
+/// The language the user interface is currently shown in, as an index into
+/// [`LANGUAGES`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectedLanguage(pub usize);
+
+impl SelectedLanguage {
+    /// Returns the language with a given code, or English if there's none.
+    pub fn from_code(code: &str) -> Self {
+        Self(LANGUAGES.iter().position(|lang| lang.code == code).unwrap_or(0))
+    }
+
+    /// Returns the selected language.
+    pub fn language(self) -> &'static Language {
+        &LANGUAGES[self.0]
+    }
+
+    /// Returns the translation of a key into the selected language. Falls back
+    /// to English if it's missing, and to the key itself as a last resort.
+    pub fn get(self, key: &'static str) -> &'static str {
+        let lang = self.language();
+        if let Some(value) = lang.get(key) {
+            return value;
+        }
+
+        if cfg!(debug_assertions) {
+            MISSING.with(|missing| {
+                let mut missing = missing.borrow_mut();
+                if !missing.contains(&(lang.code, key)) {
+                    println!("Missing translation of {} into {}.", key, lang.name);
+                    missing.push((lang.code, key));
+                }
+            });
+        }
+
+        LANGUAGES[0].get(key).unwrap_or(key)
+    }
+}
+
+/// Translates a key into the selected language, as in
+/// [`SelectedLanguage::get`].
+macro_rules! t {
+    ($lang:expr, $key:literal) => {
+        $lang.get($key)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every language must translate exactly the same keys as English.
+    #[test]
+    fn complete() {
+        for lang in LANGUAGES {
+            assert_eq!(lang.strings.len(), EN.len(), "{} has extra or missing keys", lang.name);
+            for (key, _) in EN {
+                assert!(lang.get(key).is_some(), "{} is missing {}", lang.name, key);
+            }
+        }
+    }
+
+    /// Missing keys fall back to English.
+    #[test]
+    fn fallback() {
+        let es = SelectedLanguage::from_code("es");
+        assert_eq!(t!(es, "menu.file"), "Archivo");
+        assert_eq!(es.get("not.a.key"), "not.a.key");
+        assert_eq!(SelectedLanguage::from_code("xx"), SelectedLanguage(0));
+    }
+}
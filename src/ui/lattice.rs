@@ -0,0 +1,230 @@
+//! The window from which the face lattice of the selected polytope is exported
+//! into a graph file, as DOT or GraphML.
+
+use super::{
+    dialog::{DialogQueue, DialogRequest},
+    lang::SelectedLanguage,
+    main_window::{PolyName, Selected},
+    window::{open_window, Window},
+};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::{
+    abs::{ElementMap, Ranked},
+    file::lattice::{Closure, LatticeFormat, LatticeOptions},
+};
+
+/// The number of nodes above which exporting the lattice shows a warning.
+pub const LARGE_LATTICE: usize = 10_000;
+
+/// The plugin in charge of exporting face lattices.
+pub struct LatticePlugin;
+
+impl Plugin for LatticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LatticeExport>()
+            .add_system(show_lattice_window.system().after("show_top_panel"))
+            .add_system(open_window::<LatticeExport>.system().after("show_top_panel"));
+    }
+}
+
+/// The settings with which the face lattice is exported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatticeSettings {
+    /// The format of the exported file.
+    pub format: LatticeFormat,
+
+    /// Whether only the elements in a range of ranks are exported.
+    pub restrict_ranks: bool,
+
+    /// The least and greatest ranks of the exported elements.
+    pub ranks: (isize, isize),
+
+    /// Whether only the elements below or above a given one are exported.
+    pub restrict_element: bool,
+
+    /// The rank and index of the element whose elements below or above it are
+    /// exported.
+    pub element: (isize, usize),
+
+    /// Whether the elements below or above the element are exported.
+    pub closure: Closure,
+
+    /// Whether the element types are written along with the elements.
+    pub types: bool,
+}
+
+impl Default for LatticeSettings {
+    fn default() -> Self {
+        Self {
+            format: LatticeFormat::Dot,
+            restrict_ranks: false,
+            ranks: (-1, 0),
+            restrict_element: false,
+            element: (0, 0),
+            closure: Closure::Down,
+            types: false,
+        }
+    }
+}
+
+impl LatticeSettings {
+    /// Returns the options with which the lattice is exported. The ranks are
+    /// converted into the internal ones.
+    pub fn options<'a>(&self, types: Option<&'a ElementMap<usize>>) -> LatticeOptions<'a> {
+        LatticeOptions {
+            ranks: if self.restrict_ranks {
+                Some(((self.ranks.0 + 1) as usize, (self.ranks.1 + 1) as usize))
+            } else {
+                None
+            },
+            element: if self.restrict_element {
+                Some(((self.element.0 + 1) as usize, self.element.1, self.closure))
+            } else {
+                None
+            },
+            types,
+        }
+    }
+
+    /// Writes the face lattice of a polytope with these settings.
+    pub fn export(&self, poly: &Concrete) -> String {
+        let types = if self.types {
+            Some(poly.element_types_common().1)
+        } else {
+            None
+        };
+
+        poly.abs.to_lattice(self.format, &self.options(types.as_ref()))
+    }
+}
+
+/// The state of the lattice export window.
+#[derive(Clone, Debug, Default)]
+pub struct LatticeExport {
+    /// Whether the window is open.
+    pub open: bool,
+
+    /// The settings with which the lattice is exported.
+    pub settings: LatticeSettings,
+}
+
+impl Window for LatticeExport {
+    const NAME: &'static str = "title.export_lattice";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+/// Shows the window with the settings for the exported lattice, and the number
+/// of nodes it will have.
+pub fn show_lattice_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut lattice: ResMut<'_, LatticeExport>,
+    mut dialog_queue: ResMut<'_, DialogQueue>,
+    poly_name: Res<'_, PolyName>,
+    selected: Query<'_, '_, &Concrete, With<Selected>>,
+    changed: Query<'_, '_, (), (With<Selected>, Changed<Concrete>)>,
+    lang: Res<'_, SelectedLanguage>,
+
+    // The settings the node count was last computed for, and the count.
+    mut size: Local<'_, Option<(LatticeSettings, usize)>>,
+) {
+    if !lattice.open {
+        return;
+    }
+
+    if changed.iter().next().is_some() {
+        *size = None;
+    }
+
+    let mut open = true;
+    let mut export = false;
+    let settings = &mut lattice.settings;
+
+    LatticeExport::egui_window(*lang)
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            let poly = if let Some(poly) = selected.iter().next() {
+                poly
+            } else {
+                ui.label(t!(lang, "window.no_polytope"));
+                return;
+            };
+            let max_rank = poly.rank() as isize - 1;
+
+            ui.horizontal(|ui| {
+                for format in LatticeFormat::ALL {
+                    ui.selectable_value(&mut settings.format, format, format.name());
+                }
+            });
+
+            ui.checkbox(&mut settings.restrict_ranks, t!(lang, "lattice.restrict_ranks"));
+            if settings.restrict_ranks {
+                ui.horizontal(|ui| {
+                    ui.label(t!(lang, "lattice.from_rank"));
+                    ui.add(egui::DragValue::new(&mut settings.ranks.0).clamp_range(-1..=max_rank));
+                    ui.label(t!(lang, "lattice.to_rank"));
+                    ui.add(egui::DragValue::new(&mut settings.ranks.1).clamp_range(-1..=max_rank));
+                });
+            }
+
+            ui.checkbox(&mut settings.restrict_element, t!(lang, "lattice.restrict_element"));
+            if settings.restrict_element {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", t!(lang, "window.rank")));
+                    ui.add(egui::DragValue::new(&mut settings.element.0).clamp_range(-1..=max_rank));
+
+                    let count = poly.el_count((settings.element.0 + 1) as usize);
+                    ui.label(format!("{}:", t!(lang, "window.index")));
+                    ui.add(
+                        egui::DragValue::new(&mut settings.element.1)
+                            .clamp_range(0..=count.saturating_sub(1)),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut settings.closure, Closure::Down, t!(lang, "lattice.below"));
+                    ui.selectable_value(&mut settings.closure, Closure::Up, t!(lang, "lattice.above"));
+                });
+            }
+
+            ui.checkbox(&mut settings.types, t!(lang, "lattice.types"));
+            ui.separator();
+
+            // The node count is only recomputed when the settings change.
+            if size.as_ref().map_or(true, |(last, _)| *last != *settings) {
+                let nodes = poly.abs.lattice_size(&settings.options(None));
+                *size = Some((settings.clone(), nodes));
+            }
+            let nodes = size.as_ref().map_or(0, |&(_, nodes)| nodes);
+
+            ui.label(format!("{} {}", nodes, t!(lang, "lattice.nodes")));
+            if nodes > LARGE_LATTICE {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    t!(lang, "lattice.large"),
+                );
+            }
+
+            if ui.button(t!(lang, "lattice.export")).clicked() {
+                export = true;
+            }
+        });
+
+    if !open {
+        lattice.open = false;
+    } else if export {
+        dialog_queue.push(DialogRequest::ExportLattice {
+            suggested_name: poly_name.0.clone(),
+        });
+    }
+}
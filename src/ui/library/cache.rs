@@ -0,0 +1,491 @@
+//! Reads and writes the `.folder` files, which store the contents of each
+//! folder of the library in the order they're shown, along with any special
+//! entries.
+//!
+//! Every file in a `.folder` file is stored along with its size and a hash of
+//! its contents, so that we can tell when the folder has changed since. In
+//! that case, the folder is read again, and the `.folder` file is rewritten.
+//! The entries that still exist keep their old order, and any new ones are
+//! added at the end in alphabetical order.
+//!
+//! Files are only hashed again when their size or modification time changed.
+//! Modification times don't survive being checked out by Git, so they're
+//! never trusted to tell that a file changed, only that it didn't.
+
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use super::Library;
+
+use serde::{Deserialize, Serialize};
+
+/// The version of the format of the `.folder` files. Files without a version
+/// are the plain lists of entries from before the files were checked.
+pub const CACHE_VERSION: u32 = 1;
+
+/// The name of the file that stores the contents of each folder.
+pub const CACHE_NAME: &str = ".folder";
+
+/// The size and hash of a file, which tell whether it has changed, along with
+/// its modification time, which tells whether it needs to be hashed again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// The modification time of the file in nanoseconds since the Unix epoch,
+    /// if known.
+    #[serde(default)]
+    pub modified: Option<u64>,
+
+    /// The hash of the contents of the file.
+    pub hash: u64,
+}
+
+impl Stamp {
+    /// Hashes some bytes with 64-bit FNV-1a, starting from their length as
+    /// big-endian bytes. Unlike the hashers of the standard library, this
+    /// gives the same result on every platform and version of Rust, so that
+    /// `.folder` files can be shared.
+    pub fn new(bytes: &[u8]) -> Self {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let size = bytes.len() as u64;
+        let hash = size
+            .to_be_bytes()
+            .iter()
+            .chain(bytes)
+            .fold(OFFSET, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(PRIME)
+            });
+
+        Self {
+            size,
+            modified: None,
+            hash,
+        }
+    }
+
+    /// Returns the stamp of the file at a given path. If the file has the size
+    /// and modification time of an older stamp, it's assumed not to have
+    /// changed, and the older stamp is returned without reading the file.
+    pub fn read(path: &Path, old: Option<Self>) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|time| time.as_nanos() as u64);
+
+        match old {
+            Some(old) if modified.is_some() && old.size == size && old.modified == modified => {
+                Ok(old)
+            }
+            _ => Ok(Self {
+                modified,
+                ..Self::new(&fs::read(path)?)
+            }),
+        }
+    }
+}
+
+/// An entry of a `.folder` file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// The entry in the library.
+    pub entry: Library,
+
+    /// The stamp of the file, if the entry is a file.
+    #[serde(default)]
+    pub stamp: Option<Stamp>,
+}
+
+impl CacheEntry {
+    /// Returns whether the entry refers to the same file or folder as another.
+    fn same_path(&self, other: &Self) -> bool {
+        matches!(
+            (&self.entry, &other.entry),
+            (Library::File { .. }, Library::File { .. })
+                | (
+                    Library::UnloadedFolder { .. } | Library::LoadedFolder { .. },
+                    Library::UnloadedFolder { .. } | Library::LoadedFolder { .. }
+                )
+        ) && self.entry.path_name() == other.entry.path_name()
+    }
+}
+
+/// The contents of a `.folder` file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FolderCache {
+    /// The version of the format of the file.
+    pub version: u32,
+
+    /// The entries of the folder, in the order they're shown.
+    pub entries: Vec<CacheEntry>,
+}
+
+impl FolderCache {
+    /// Reads the `.folder` file in a folder. Files in older formats are read
+    /// without their stamps, so that every entry in them is checked again.
+    /// Returns `None` if the file doesn't exist or can't be read.
+    pub fn read(folder: &Path) -> Option<Self> {
+        let path = folder.join(CACHE_NAME);
+        let bytes = fs::read(&path).ok()?;
+        let src = String::from_utf8_lossy(&bytes);
+
+        match ron::from_str::<Self>(&src) {
+            Ok(cache) if cache.version == CACHE_VERSION => return Some(cache),
+            Ok(cache) => eprintln!(
+                "{}: unknown version {}, reading it again",
+                path.display(),
+                cache.version
+            ),
+            Err(_) => {
+                if let Ok(entries) = ron::from_str::<Vec<Library>>(&src) {
+                    return Some(Self::unstamped(entries));
+                }
+                eprintln!("{}: invalid folder file, reading it again", path.display());
+            }
+        }
+
+        None
+    }
+
+    /// Builds a cache with some entries but none of their stamps.
+    fn unstamped(entries: Vec<Library>) -> Self {
+        Self {
+            version: CACHE_VERSION,
+            entries: entries
+                .into_iter()
+                .map(|entry| CacheEntry { entry, stamp: None })
+                .collect(),
+        }
+    }
+
+    /// Returns the stamp of the file with a given name, if it has one.
+    fn stamp(&self, name: &str) -> Option<Stamp> {
+        self.entries.iter().find_map(|entry| match &entry.entry {
+            Library::File { name: file } if file == name => entry.stamp,
+            _ => None,
+        })
+    }
+
+    /// Reads the files and folders that can be shown in the library from a
+    /// folder, in alphabetical order. Files whose size and modification time
+    /// match those in an older cache aren't read again. Files that can't be
+    /// read are left out.
+    pub fn scan(folder: &Path, old: Option<&Self>) -> io::Result<Self> {
+        let mut paths = fs::read_dir(folder)?
+            .map(|res| res.map(|e| e.path()))
+            .collect::<io::Result<Vec<PathBuf>>>()?;
+        paths.sort();
+
+        let mut entries = Vec::new();
+        for path in &paths {
+            // Adds a new unloaded folder.
+            if let Some(entry) = Library::new_folder(path) {
+                entries.push(CacheEntry { entry, stamp: None });
+            }
+            // Adds a new file.
+            else {
+                let ext = path.extension();
+                if ext == Some(OsStr::new("off")) || ext == Some(OsStr::new("ggb")) {
+                    let entry = Library::new_file(path);
+                    let old_stamp = old.and_then(|old| old.stamp(entry.path_name()));
+
+                    match Stamp::read(path, old_stamp) {
+                        Ok(stamp) => entries.push(CacheEntry {
+                            entry,
+                            stamp: Some(stamp),
+                        }),
+                        Err(err) => eprintln!("Could not read {}: {}", path.display(), err),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            version: CACHE_VERSION,
+            entries,
+        })
+    }
+
+    /// Returns whether the files and folders in `self` are exactly those of
+    /// another cache, with the same stamps.
+    fn matches(&self, other: &Self) -> bool {
+        let paths = |cache: &Self| {
+            let mut paths: Vec<_> = cache
+                .entries
+                .iter()
+                .filter(|entry| !matches!(entry.entry, Library::Special(_)))
+                .map(|entry| (entry.entry.path_name().to_owned(), entry.stamp))
+                .collect();
+            paths.sort_by(|a, b| a.0.cmp(&b.0));
+            paths
+        };
+
+        paths(self) == paths(other)
+            && self.entries.iter().all(|entry| {
+                matches!(entry.entry, Library::Special(_))
+                    || other.entries.iter().any(|other| entry.same_path(other))
+            })
+    }
+
+    /// Updates the entries of `self` to those of a newer scan. Special entries
+    /// and entries that still exist keep their order, while new ones are added
+    /// at the end.
+    fn merge(&mut self, mut scan: Self) {
+        let mut entries = Vec::with_capacity(scan.entries.len());
+
+        for entry in self.entries.drain(..) {
+            if let Library::Special(_) = entry.entry {
+                entries.push(entry);
+            } else if let Some(idx) = scan.entries.iter().position(|new| new.same_path(&entry)) {
+                entries.push(scan.entries.remove(idx));
+            }
+        }
+
+        entries.append(&mut scan.entries);
+        self.entries = entries;
+    }
+
+    /// Writes the `.folder` file in a folder.
+    pub fn write(&self, folder: &Path) -> io::Result<()> {
+        let config = ron::ser::PrettyConfig::new().with_depth_limit(2);
+        let src = ron::ser::to_string_pretty(self, config)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(folder.join(CACHE_NAME), src)
+    }
+
+    /// Returns the contents of a folder as they should be shown in the library.
+    /// These are read from its `.folder` file, unless it's missing or doesn't
+    /// match the folder, in which case the folder is read and the `.folder`
+    /// file is rewritten.
+    pub fn load(folder: &Path) -> io::Result<Vec<Library>> {
+        let old = Self::read(folder);
+        let scan = Self::scan(folder, old.as_ref())?;
+        let cache = match old {
+            Some(cache) if cache.matches(&scan) => cache,
+            Some(mut cache) => {
+                cache.merge(scan);
+                cache.write_or_warn(folder);
+                cache
+            }
+            None => {
+                scan.write_or_warn(folder);
+                scan
+            }
+        };
+
+        Ok(cache.entries.into_iter().map(|entry| entry.entry).collect())
+    }
+
+    /// Writes the `.folder` file in a folder, or prints an error if it can't.
+    /// The library still works without it.
+    fn write_or_warn(&self, folder: &Path) {
+        if let Err(err) = self.write(folder) {
+            eprintln!(
+                "Could not write to {}: {}",
+                folder.join(CACHE_NAME).display(),
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::library::special::SpecialLibrary;
+
+    /// An empty folder in the temporary directory, which is removed once the
+    /// test ends.
+    struct TempFolder(PathBuf);
+
+    impl TempFolder {
+        /// Creates an empty folder with a name unique to a test.
+        fn new(test: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "miratope-folder-{}-{}",
+                test,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        /// Writes a file in the folder.
+        fn write(&self, name: &str, contents: &str) {
+            fs::write(self.0.join(name), contents).unwrap();
+        }
+
+        /// Returns the names of the entries shown in the library.
+        fn load(&self) -> Vec<String> {
+            FolderCache::load(&self.0)
+                .unwrap()
+                .iter()
+                .map(|entry| match entry {
+                    Library::Special(special) => format!("{:?}", special),
+                    _ => entry.path_name().to_owned(),
+                })
+                .collect()
+        }
+    }
+
+    impl Drop for TempFolder {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// The stamps don't depend on the platform.
+    #[test]
+    fn stamp() {
+        assert_eq!(
+            Stamp::new(b""),
+            Stamp {
+                size: 0,
+                modified: None,
+                hash: 0xa8c7_f832_281a_39c5
+            }
+        );
+        assert_eq!(Stamp::new(b"OFF\n").size, 4);
+        assert_ne!(Stamp::new(b"OFF\n"), Stamp::new(b"OFF \n"));
+    }
+
+    /// Files that are added, removed or renamed show up the next time the
+    /// folder is loaded.
+    #[test]
+    fn changes() {
+        let folder = TempFolder::new("changes");
+        folder.write("b.off", "OFF");
+        folder.write("a.off", "OFF");
+        folder.write("notes.txt", "");
+        fs::create_dir(folder.0.join("c")).unwrap();
+        assert_eq!(folder.load(), vec!["a.off", "b.off", "c"]);
+
+        let cache = FolderCache::read(&folder.0).unwrap();
+        assert_eq!(cache, FolderCache::scan(&folder.0, None).unwrap());
+
+        folder.write("d.ggb", "");
+        assert_eq!(folder.load(), vec!["a.off", "b.off", "c", "d.ggb"]);
+
+        fs::remove_file(folder.0.join("b.off")).unwrap();
+        assert_eq!(folder.load(), vec!["a.off", "c", "d.ggb"]);
+
+        fs::rename(folder.0.join("a.off"), folder.0.join("e.off")).unwrap();
+        assert_eq!(folder.load(), vec!["c", "d.ggb", "e.off"]);
+
+        // A file that becomes a folder.
+        fs::remove_file(folder.0.join("d.ggb")).unwrap();
+        fs::create_dir(folder.0.join("d.ggb")).unwrap();
+        let contents = FolderCache::load(&folder.0).unwrap();
+        assert!(matches!(&contents[2], Library::UnloadedFolder { name } if name == "d.ggb"));
+    }
+
+    /// Changing the contents of a file updates its stamp.
+    #[test]
+    fn contents() {
+        let folder = TempFolder::new("contents");
+        folder.write("a.off", "OFF");
+        folder.load();
+
+        folder.write("a.off", "OFF\n3 1 0");
+        assert!(!FolderCache::read(&folder.0)
+            .unwrap()
+            .matches(&FolderCache::scan(&folder.0, None).unwrap()));
+        folder.load();
+        let stamp = FolderCache::read(&folder.0).unwrap().entries[0]
+            .stamp
+            .unwrap();
+        assert_eq!(stamp.hash, Stamp::new(b"OFF\n3 1 0").hash);
+    }
+
+    /// Files are only read again when their size or modification time
+    /// changed.
+    #[test]
+    fn cheap_stamps() {
+        let folder = TempFolder::new("cheap_stamps");
+        folder.write("a.off", "OFF");
+        folder.load();
+
+        // A stale hash is kept as long as the file looks untouched.
+        let mut cache = FolderCache::read(&folder.0).unwrap();
+        cache.entries[0].stamp.as_mut().unwrap().hash = 0;
+        assert_eq!(
+            FolderCache::scan(&folder.0, Some(&cache)).unwrap().entries[0].stamp,
+            cache.entries[0].stamp
+        );
+
+        // A different modification time makes it be hashed again.
+        cache.entries[0].stamp.as_mut().unwrap().modified = Some(0);
+        let stamp = FolderCache::scan(&folder.0, Some(&cache)).unwrap().entries[0]
+            .stamp
+            .unwrap();
+        assert_eq!(stamp.hash, Stamp::new(b"OFF").hash);
+    }
+
+    /// Files that can't be read are left out instead of failing the folder.
+    #[cfg(unix)]
+    #[test]
+    fn unreadable() {
+        let folder = TempFolder::new("unreadable");
+        folder.write("a.off", "OFF");
+        std::os::unix::fs::symlink(folder.0.join("missing.off"), folder.0.join("b.off")).unwrap();
+
+        assert_eq!(folder.load(), vec!["a.off"]);
+    }
+
+    /// The order and special entries of older `.folder` files are kept.
+    #[test]
+    fn migrate() {
+        let folder = TempFolder::new("migrate");
+        folder.write("a.off", "");
+        folder.write("b.off", "");
+        folder.write("c.off", "");
+        folder.write(
+            CACHE_NAME,
+            r#"[File(name:"c.off"),Special(Rotunda),File(name:"gone.off"),File(name:"a.off")]"#,
+        );
+
+        assert_eq!(folder.load(), vec!["c.off", "Rotunda", "a.off", "b.off"]);
+        let cache = FolderCache::read(&folder.0).unwrap();
+        assert_eq!(cache.version, CACHE_VERSION);
+        assert_eq!(
+            cache.entries[1].entry,
+            Library::Special(SpecialLibrary::Rotunda)
+        );
+        assert!(cache.matches(&FolderCache::scan(&folder.0, None).unwrap()));
+
+        // The order is still kept after further changes.
+        folder.write("0.off", "");
+        assert_eq!(
+            folder.load(),
+            vec!["c.off", "Rotunda", "a.off", "b.off", "0.off"]
+        );
+    }
+
+    /// Corrupt `.folder` files and those from unknown versions are replaced.
+    #[test]
+    fn corrupt() {
+        let folder = TempFolder::new("corrupt");
+        folder.write("b.off", "");
+        folder.write("a.off", "");
+
+        for src in ["[File(name:", "(version: 99, entries: [])"] {
+            folder.write(CACHE_NAME, src);
+            assert_eq!(folder.load(), vec!["a.off", "b.off"]);
+            assert_eq!(FolderCache::read(&folder.0).unwrap().version, CACHE_VERSION);
+        }
+
+        fs::write(folder.0.join(CACHE_NAME), [0xff, 0xfe, 0x00]).unwrap();
+        assert_eq!(folder.load(), vec!["a.off", "b.off"]);
+    }
+}
@@ -2,19 +2,20 @@
 
 use std::{
     ffi::{OsStr, OsString},
-    fs, io,
-    path::PathBuf,
+    io,
+    path::{Path, PathBuf},
 };
 
-use super::{config::LibPath, main_window::PolyName};
-use crate::Concrete;
-use miratope_core::file::FromFile;
+use super::{config::LibPath, lang::SelectedLanguage, replace::{PendingReplacement, Replacement}};
 use special::*;
 
+pub use special::SpecialParams;
+
 use bevy::prelude::*;
 use bevy_egui::{egui, egui::Ui, EguiContext};
 use serde::{Deserialize, Serialize};
 
+mod cache;
 mod special;
 
 /// The plugin that loads the library.
@@ -88,8 +89,8 @@ impl std::ops::BitOrAssign for ShowResult {
 /// Represents any of the files or folders that make up the Miratope library.
 ///
 /// The library is internally stored is a tree-like structure. Once a folder
-/// loads, it's (currently) never unloaded.
-#[derive(Serialize, Deserialize)]
+/// loads, it's only unloaded when the whole library is refreshed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Library {
     /// A folder whose contents have not yet been read.
     UnloadedFolder {
@@ -153,57 +154,41 @@ impl Library {
         })
     }
 
-    /// Reads a folder's data from the `.folder` file. If it doesn't exist, it
-    /// defaults to loading the folder's name and its data in alphabetical
-    /// order. If that also fails, it returns an `Err`.
+    /// Reads a folder's contents from its `.folder` file. If it doesn't exist
+    /// or is outdated, the folder is read again, and the file is rewritten.
+    /// If that also fails, it returns an `Err`.
     pub fn folder_contents<U: AsRef<OsStr>>(path: U) -> io::Result<Vec<Self>> {
         let path = PathBuf::from(&path);
         if !path.is_dir() {
             return Ok(Vec::new());
         }
 
-        // Attempts to read from the .folder file.
-        if let Some(Ok(folder)) = fs::read(path.join(".folder"))
-            .ok()
-            .map(|file| ron::from_str(&String::from_utf8(file).unwrap()))
-        {
-            Ok(folder)
-        }
-        // Otherwise, just manually goes through the files.
-        else {
-            let mut contents = Vec::new();
-            let mut paths = fs::read_dir(path.clone())?.map(|res| res.map(|e| e.path())).collect::<Result<Vec<PathBuf>, io::Error>>()?;
-            paths.sort();
-
-            for path in &paths {
-                // Adds a new unloaded folder.
-                if let Some(unloaded_folder) = Self::new_folder(path) {
-                    contents.push(unloaded_folder);
-                }
-                // Adds a new file.
-                else {
-                    let ext = path.extension();
-                    if ext == Some(OsStr::new("off")) || ext == Some(OsStr::new("ggb")) {
-                        contents.push(Self::new_file(path));
-                    }
-                }
-            }
-
-            Ok(contents)
-        }
+        cache::FolderCache::load(&path)
     }
 
-    /// Shows the library in a given `Ui`, starting from a given path.
-    pub fn show(&mut self, ui: &mut Ui, path: PathBuf) -> ShowResult {
+    /// Shows the library in a given `Ui`, starting from a given path. The
+    /// special entries start out with their last values, and any changes to
+    /// them are stored back.
+    pub fn show(&mut self, ui: &mut Ui, path: PathBuf, params: &mut SpecialParams) -> ShowResult {
         match self {
             // Shows a collapsing drop-down, and loads the folder in case it's clicked.
             Self::UnloadedFolder { name, .. } => {
+                let mut contents = Self::folder_contents(&path).unwrap_or_else(|err| {
+                    eprintln!("Could not read {}: {}", path.display(), err);
+                    Vec::new()
+                });
+                for lib in &mut contents {
+                    if let Self::Special(special) = lib {
+                        params.restore(special);
+                    }
+                }
+
                 *self = Self::LoadedFolder {
                     name: name.clone(),
-                    contents: Self::folder_contents(&path).unwrap(),
+                    contents,
                 };
 
-                self.show(ui, path)
+                self.show(ui, path, params)
             }
 
             // Shows a drop-down with all of the files and folders.
@@ -214,7 +199,7 @@ impl Library {
                     for lib in contents.iter_mut() {
                         let mut new_path = path.clone();
                         new_path.push(lib.path_name());
-                        res |= lib.show(ui, new_path);
+                        res |= lib.show(ui, new_path, params);
                     }
 
                     res
@@ -238,7 +223,11 @@ impl Library {
             }
 
             // Shows any of the special files.
-            Self::Special(special) => special.show(ui),
+            Self::Special(special) => {
+                let res = special.show(ui, &mut params.linked);
+                params.remember(special);
+                res
+            }
         }
     }
 }
@@ -246,10 +235,11 @@ impl Library {
 /// The system that shows the Miratope library.
 fn show_library(
     egui_ctx: Res<'_, EguiContext>,
-    mut query: Query<'_, '_, &mut Concrete>,
-    mut poly_name: ResMut<'_, PolyName>,
     mut library: ResMut<'_, Option<Library>>,
     lib_path: Res<'_, LibPath>,
+    lang: Res<'_, SelectedLanguage>,
+    mut pending: ResMut<'_, PendingReplacement>,
+    mut special_params: ResMut<'_, SpecialParams>,
 ) {
     // Shows the polytope library.
     if let Some(library) = library.as_mut() {
@@ -257,28 +247,33 @@ fn show_library(
             .default_width(300.0)
             .max_width(450.0)
             .show(egui_ctx.ctx(), |ui| {
+                // Unloads every folder, so that they're read again.
+                if ui.button(t!(lang, "library.refresh")).clicked() {
+                    if let Some(new_library) = Library::new_folder(lib_path.as_ref()) {
+                        *library = new_library;
+                    }
+                }
+
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    match library.show(ui, PathBuf::from(lib_path.as_ref())) {
+                    match library.show(ui, PathBuf::from(lib_path.as_ref()), &mut special_params) {
                         // No action needs to be taken.
                         ShowResult::None => {}
 
                         // Loads a selected file.
-                        ShowResult::Load(file) => match Concrete::from_path(&file) {
-                            Ok(q) => {
-                                *query.iter_mut().next().unwrap() = q;
-                                let path_buf = PathBuf::from(file);
-                                let file_name = path_buf.file_name().unwrap().to_str().unwrap();
-                                poly_name.0 = file_name[..file_name.len()-4].into();
-                            },
-                            Err(err) => eprintln!("File open failed: {}", err),
+                        ShowResult::Load(file) => match Replacement::from_path(Path::new(&file)) {
+                            Ok(replacement) => pending.set(replacement),
+                            Err(err) => eprintln!("{}: {}", t!(lang, "error.file_open"), err),
                         },
 
                         // Loads a special polytope.
-                        ShowResult::Special(special) => {
-                            let (a, b) = special.load();
-                            *query.iter_mut().next().unwrap() = a;
-                            poly_name.0 = b;
-                        }
+                        ShowResult::Special(special) => match special
+                            .build()
+                            .map_err(|err| err.to_string())
+                            .and_then(|poly| Replacement::new(poly, special.name()))
+                        {
+                            Ok(replacement) => pending.set(replacement),
+                            Err(err) => eprintln!("{}: {}", t!(lang, "error.special"), err),
+                        },
                     }
                 })
             });
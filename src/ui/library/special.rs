@@ -1,23 +1,36 @@
 //! Contains the code for the "special library", i.e. the hardcoded families of
 //! polytopes in the Miratope library.
 
+use std::fmt::Display;
+
 use egui::Ui;
 use serde::{Deserialize, Serialize};
 
 use crate::Concrete;
-use miratope_core::conc::ConcretePolytope;
+use miratope_core::conc::{
+    hull::{duoantiprism_uniform, step_prism_valid, DuoantiprismError},
+    schlafli::SchlafliError,
+    shapes::{CATALAN_NAMES, ICOSAHEDRAL_NAMES, PLATONIC_NAMES},
+    ConcretePolytope,
+};
 use miratope_core::Polytope;
 
 use super::ShowResult;
 
+/// The largest number of vertices of a step prism that can be built. Step
+/// prisms are built as convex hulls, which takes a fraction of a second with
+/// this many vertices, but freezes the UI for much longer with a few times
+/// more.
+const MAX_STEP_PRISM_VERTICES: usize = 50;
+
 /// Represents any of the special polytopes in Miratope's library, namely those
 /// families that are generated by code.
 ///
 /// The variants of the special library store whatever value is currently being
 /// stored on screen. When the user clicks on the button to load them, they're
 /// sent together with their values as a [`ShowResult`] to the [`show_library`]
-/// system, which then actually loads the polytope.
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+/// system, which then builds the polytope with [`SpecialLibrary::build`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub enum SpecialLibrary {
     /// A regular polygon.
     Polygon(usize, usize),
@@ -31,9 +44,15 @@ pub enum SpecialLibrary {
     /// A (4D uniform) duoprism.
     Duoprism(usize, usize, usize, usize),
 
+    /// A 4D duoantiprism, uniform only in a few cases.
+    Duoantiprism(usize, usize, usize, usize),
+
     /// A (4D uniform) antiprismatic prism.
     AntiprismPrism(usize, usize),
 
+    /// A 4D step prism, given by its number of vertices and its two steps.
+    StepPrism(usize, usize, usize),
+
     /// A simplex.
     Simplex(isize),
 
@@ -42,6 +61,90 @@ pub enum SpecialLibrary {
 
     /// An orthoplex.
     Orthoplex(isize),
+
+    /// A Platonic solid, given by its index in [`PLATONIC_NAMES`].
+    Platonic(usize),
+
+    /// A Catalan solid, given by its index in [`CATALAN_NAMES`].
+    Catalan(usize),
+
+    /// A polytope with icosahedral symmetry built from its exact coordinates,
+    /// given by its index in [`ICOSAHEDRAL_NAMES`].
+    Icosahedral(usize),
+
+    /// A cupola with regular faces.
+    Cupola(usize),
+
+    /// The pentagonal rotunda.
+    Rotunda,
+
+    /// A regular polytope, given by its Schläfli symbol.
+    Schlafli(String),
+}
+
+/// The last values of the entries in the special library, which are stored in
+/// the configuration so that they survive across sessions.
+///
+/// Values are matched to entries by their kind rather than by their position
+/// in the library, so reordering the library doesn't mix them up. Entries of
+/// the same kind share their values.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct SpecialParams {
+    /// The last values of each kind of entry, with at most one per kind.
+    #[serde(default)]
+    entries: Vec<SpecialLibrary>,
+
+    /// Whether the second polygon of duoprisms and duoantiprisms copies the
+    /// first.
+    #[serde(default)]
+    pub linked: bool,
+}
+
+impl SpecialParams {
+    /// Stores the values of an entry, replacing those of its kind.
+    pub fn remember(&mut self, special: &SpecialLibrary) {
+        let kind = std::mem::discriminant(special);
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| std::mem::discriminant(*entry) == kind)
+        {
+            Some(entry) => entry.clone_from(special),
+            None => self.entries.push(special.clone()),
+        }
+    }
+
+    /// Sets an entry to the stored values of its kind, if there are any.
+    pub fn restore(&self, special: &mut SpecialLibrary) {
+        let kind = std::mem::discriminant(special);
+        if let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| std::mem::discriminant(*entry) == kind)
+        {
+            *special = entry.clone();
+            special.constrain(self.linked);
+        }
+    }
+}
+
+/// Shows a button together with a drop-down to select one of the given names.
+/// Returns whether the button was clicked.
+fn show_named(ui: &mut Ui, text: &str, idx: &mut usize, names: &[&str]) -> bool {
+    ui.horizontal(|ui| {
+        let clicked = ui.button(text).clicked();
+
+        egui::ComboBox::from_id_source(text)
+            .selected_text(names[*idx])
+            .show_ui(ui, |ui| {
+                for (i, name) in names.iter().enumerate() {
+                    ui.selectable_value(idx, i, *name);
+                }
+            });
+
+        clicked
+    })
+    .inner
 }
 
 impl SpecialLibrary {
@@ -52,16 +155,40 @@ impl SpecialLibrary {
             Self::Prism(_, _) => "Prism",
             Self::Antiprism(_, _) => "Antiprism",
             Self::Duoprism(_, _, _, _) => "Duoprism",
+            Self::Duoantiprism(_, _, _, _) => "Duoantiprism",
             Self::AntiprismPrism(_, _) => "Antiprism prism",
+            Self::StepPrism(_, _, _) => "Step prism",
             Self::Simplex(_) => "Simplex",
             Self::Hypercube(_) => "Hypercube",
             Self::Orthoplex(_) => "Orthoplex",
+            Self::Platonic(_) => "Platonic solid",
+            Self::Catalan(_) => "Catalan solid",
+            Self::Icosahedral(_) => "Icosahedral",
+            Self::Cupola(_) => "Cupola",
+            Self::Rotunda => "Pentagonal rotunda",
+            Self::Schlafli(_) => "Schläfli symbol",
         }
     }
 
     /// Shows the special component of the library. Returns the action selected
     /// by the user, if any.
-    pub fn show(&mut self, ui: &mut Ui) -> ShowResult {
+    ///
+    /// The `linked` flag is shared by every duoprism and duoantiprism, and
+    /// makes their second polygon copy the first.
+    pub fn show(&mut self, ui: &mut Ui, linked: &mut bool) -> ShowResult {
+        let clicked = self.show_widgets(ui, linked);
+        self.constrain(*linked);
+
+        if clicked {
+            ShowResult::Special(self.clone())
+        } else {
+            ShowResult::None
+        }
+    }
+
+    /// Shows the button and the parameters of the entry. Returns whether the
+    /// button was clicked.
+    fn show_widgets(&mut self, ui: &mut Ui, linked: &mut bool) -> bool {
         let text = self.label();
 
         match self {
@@ -81,16 +208,15 @@ impl SpecialLibrary {
                     );
 
                     // Turning number.
-                    let max_n = *n / 2;
                     ui.label("d:");
-                    ui.add(egui::DragValue::new(d).speed(0.03).clamp_range(1..=max_n));
+                    ui.add(
+                        egui::DragValue::new(d)
+                            .speed(0.03)
+                            .clamp_range(1..=usize::MAX),
+                    );
                 });
 
-                if clicked {
-                    ShowResult::Special(*self)
-                } else {
-                    ShowResult::None
-                }
+                clicked
             }
 
             // An {n / d} uniform antiprism.
@@ -107,22 +233,21 @@ impl SpecialLibrary {
                     );
 
                     // Turning number.
-                    let max_n = *n * 2 / 3;
                     ui.label("d:");
-                    ui.add(egui::DragValue::new(d).speed(0.03).clamp_range(1..=max_n));
+                    ui.add(
+                        egui::DragValue::new(d)
+                            .speed(0.03)
+                            .clamp_range(1..=usize::MAX),
+                    );
 
                     clicked
                 });
 
-                if clicked.inner {
-                    ShowResult::Special(*self)
-                } else {
-                    ShowResult::None
-                }
+                clicked.inner
             }
 
-            // A uniform duoprism based on two polygons.
-            Self::Duoprism(n1, d1, n2, d2) => {
+            // A duoprism or duoantiprism based on two polygons.
+            Self::Duoprism(n1, d1, n2, d2) | Self::Duoantiprism(n1, d1, n2, d2) => {
                 let clicked = ui.horizontal_wrapped(|ui| {
                     let clicked = ui.button(text).clicked();
 
@@ -135,31 +260,67 @@ impl SpecialLibrary {
                     );
 
                     // Turning number.
-                    let max_n1 = *n1 / 2;
                     ui.label("d₁:");
-                    ui.add(egui::DragValue::new(d1).speed(0.03).clamp_range(1..=max_n1));
+                    ui.add(
+                        egui::DragValue::new(d1)
+                            .speed(0.03)
+                            .clamp_range(1..=usize::MAX),
+                    );
 
-                    // Number of sides.
-                    ui.label("n₂:");
+                    // Whether the second polygon copies the first.
+                    ui.checkbox(linked, "Link");
+
+                    ui.scope(|ui| {
+                        ui.set_enabled(!*linked);
+
+                        // Number of sides.
+                        ui.label("n₂:");
+                        ui.add(
+                            egui::DragValue::new(n2)
+                                .speed(0.03)
+                                .clamp_range(2..=usize::MAX),
+                        );
+
+                        // Turning number.
+                        ui.label("d₂:");
+                        ui.add(
+                            egui::DragValue::new(d2)
+                                .speed(0.03)
+                                .clamp_range(1..=usize::MAX),
+                        );
+                    });
+
+                    clicked
+                });
+
+                clicked.inner
+            }
+
+            // A step prism based on two steps.
+            Self::StepPrism(n, s1, s2) => {
+                let clicked = ui.horizontal(|ui| {
+                    let valid = step_prism_valid(*n, &[*s1, *s2]);
+                    let clicked = ui.add(egui::Button::new(text).enabled(valid)).clicked();
+
+                    // Number of vertices.
+                    ui.label("n:");
                     ui.add(
-                        egui::DragValue::new(n2)
+                        egui::DragValue::new(n)
                             .speed(0.03)
-                            .clamp_range(2..=usize::MAX),
+                            .clamp_range(5..=MAX_STEP_PRISM_VERTICES),
                     );
 
-                    // Turning number.
-                    let max_n2 = *n2 / 2;
-                    ui.label("d₂:");
-                    ui.add(egui::DragValue::new(d2).speed(0.03).clamp_range(1..=max_n2));
+                    // Steps.
+                    let max_s = *n - 1;
+                    ui.label("s₁:");
+                    ui.add(egui::DragValue::new(s1).speed(0.03).clamp_range(1..=max_s));
+                    ui.label("s₂:");
+                    ui.add(egui::DragValue::new(s2).speed(0.03).clamp_range(1..=max_s));
 
                     clicked
                 });
 
-                if clicked.inner {
-                    ShowResult::Special(*self)
-                } else {
-                    ShowResult::None
-                }
+                clicked.inner
             }
 
             // A simplex, hypercube, or orthoplex of a given rank.
@@ -173,97 +334,537 @@ impl SpecialLibrary {
                     clicked
                 });
 
-                if clicked.inner {
-                    ShowResult::Special(*self)
+                clicked.inner
+            }
+
+            // A Platonic, Catalan or icosahedral polytope, selected by name.
+            Self::Platonic(idx) => show_named(ui, text, idx, &PLATONIC_NAMES),
+            Self::Catalan(idx) => show_named(ui, text, idx, &CATALAN_NAMES),
+            Self::Icosahedral(idx) => show_named(ui, text, idx, &ICOSAHEDRAL_NAMES),
+
+            // A cupola with a given number of sides on its top.
+            Self::Cupola(n) => {
+                let clicked = ui.horizontal(|ui| {
+                    let clicked = ui.button(text).clicked();
+
+                    // Only these cupolae have regular faces.
+                    ui.label("n:");
+                    ui.add(egui::DragValue::new(n).speed(0.03).clamp_range(2..=5));
+
+                    clicked
+                });
+
+                clicked.inner
+            }
+
+            // The pentagonal rotunda.
+            Self::Rotunda => {
+                ui.button(text).clicked()
+            }
+
+            // A regular polytope, typed in as a Schläfli symbol.
+            Self::Schlafli(symbol) => {
+                let clicked = ui.horizontal(|ui| {
+                    let clicked = ui.button(text).clicked();
+                    ui.text_edit_singleline(symbol);
+                    clicked
+                });
+
+                clicked.inner
+            }
+        }
+    }
+
+    /// Enforces the constraints between the parameters of the entry that the
+    /// ranges of the individual widgets can't. Turning numbers are lowered as
+    /// the number of sides shrinks, so that the polygons don't degenerate, and
+    /// steps are kept below the number of vertices. If `linked` is set, the
+    /// second polygon of a duoprism or duoantiprism copies the first. Step
+    /// prisms loaded from storage are also capped at
+    /// [`MAX_STEP_PRISM_VERTICES`] vertices.
+    pub fn constrain(&mut self, linked: bool) {
+        match self {
+            Self::Polygon(n, d) | Self::Prism(n, d) => {
+                *d = (*d).clamp(1, max_polygon_turning(*n));
+            }
+            Self::Antiprism(n, d) | Self::AntiprismPrism(n, d) => {
+                *d = (*d).clamp(1, max_antiprism_turning(*n));
+            }
+            Self::Duoprism(n1, d1, n2, d2) | Self::Duoantiprism(n1, d1, n2, d2) => {
+                *d1 = (*d1).clamp(1, max_polygon_turning(*n1));
+
+                if linked {
+                    *n2 = *n1;
+                    *d2 = *d1;
                 } else {
-                    ShowResult::None
+                    *d2 = (*d2).clamp(1, max_polygon_turning(*n2));
                 }
             }
+            Self::StepPrism(n, s1, s2) => {
+                *n = (*n).min(MAX_STEP_PRISM_VERTICES);
+                let max_s = n.saturating_sub(1).max(1);
+                *s1 = (*s1).clamp(1, max_s);
+                *s2 = (*s2).clamp(1, max_s);
+            }
+            _ => {}
         }
     }
 
-    /// Loads the given special polytope from the library.
-    pub fn load(&self) -> (Concrete, String) {
-        match *self {
-            // Loads a regular star polygon.
-            Self::Polygon(n, d) => (
-                Concrete::star_polygon_with_edge(n, d, 1.0),
-                format!(
-                    "{}{}-gon",
-                    n,
-                    if d > 1 {format!("/{}", d)} else {"".to_string()}
-                )
-            ),
+    /// Returns the name of the given special polytope.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Polygon(n, d) => format!("{}-gon", fraction(*n, *d)),
+            Self::Prism(n, d) => format!("{}-gonal prism", fraction(*n, *d)),
+            Self::Antiprism(n, d) => format!("{}-gonal antiprism", fraction(*n, *d)),
+            Self::Duoprism(n1, d1, n2, d2) => {
+                format!("{}-{} duoprism", fraction(*n1, *d1), fraction(*n2, *d2))
+            }
+            Self::Duoantiprism(n1, d1, n2, d2) => {
+                format!("{}-{} duoantiprism", fraction(*n1, *d1), fraction(*n2, *d2))
+            }
+            Self::AntiprismPrism(n, d) => format!("{}-gonal antiprism prism", fraction(*n, *d)),
+            Self::StepPrism(n, s1, s2) => format!("{}-{}-{} step prism", n, s1, s2),
+            Self::Simplex(rank) => format!("{}-simplex", rank),
+            Self::Hypercube(rank) => format!("{}-cube", rank),
+            Self::Orthoplex(rank) => format!("{}-orthoplex", rank),
+            Self::Platonic(idx) => PLATONIC_NAMES.get(*idx).unwrap_or(&"").to_string(),
+            Self::Catalan(idx) => CATALAN_NAMES.get(*idx).unwrap_or(&"").to_string(),
+            Self::Icosahedral(idx) => ICOSAHEDRAL_NAMES.get(*idx).unwrap_or(&"").to_string(),
+            Self::Cupola(n) => format!("{}-gonal cupola", n),
+            Self::Rotunda => "Pentagonal rotunda".to_string(),
+            Self::Schlafli(symbol) => symbol.trim().to_string(),
+        }
+    }
 
-            // Loads a uniform polygonal prism.
-            Self::Prism(n, d) => (
-                Concrete::uniform_prism(n, d),
-                format!(
-                    "{}{}-gonal prism",
-                    n,
-                    if d > 1 {format!("/{}", d)} else {"".to_string()}
-                )
-            ),
+    /// Builds the given special polytope from the library, checking that its
+    /// parameters are valid.
+    pub fn build(&self) -> Result<Concrete, SpecialError> {
+        Ok(match *self {
+            // Builds a regular star polygon.
+            Self::Polygon(n, d) => {
+                check_polygon(n, d)?;
+                Concrete::star_polygon_with_edge(n, d, 1.0)
+            }
 
-            // Loads a uniform polygonal antiprism.
-            Self::Antiprism(n, d) => (
-                Concrete::uniform_antiprism(n, d),
-                format!(
-                    "{}{}-gonal antiprism",
-                    n,
-                    if d > 1 {format!("/{}", d)} else {"".to_string()}
-                )
-            ),
+            // Builds a uniform polygonal prism.
+            Self::Prism(n, d) => {
+                check_polygon(n, d)?;
+                Concrete::uniform_prism(n, d)
+            }
 
-            // Loads a uniform polygonal duoprism.
-            Self::Duoprism(n1, d1, n2, d2) => (
-                {
-                    let p1 = Concrete::star_polygon_with_edge(n1, d1, 1.0);
-
-                    // Avoids duplicate work if possible.
-                    if n1 == n2 && d1 == d2 {
-                        Concrete::duoprism(&p1, &p1)
-                    } else {
-                        let p2 = Concrete::star_polygon_with_edge(n2, d2, 1.0);
-                        Concrete::duoprism(&p1, &p2)
-                    }
-                },
-                format!(
-                    "{}{}-{}{} duoprism",
-                    n1,
-                    if d1 > 1 {format!("/{}", d1)} else {"".to_string()},
-                    n2,
-                    if d2 > 1 {format!("/{}", d2)} else {"".to_string()}
-                )
-            ),
+            // Builds a uniform polygonal antiprism.
+            Self::Antiprism(n, d) => {
+                check_antiprism(n, d)?;
+                Concrete::uniform_antiprism(n, d)
+            }
 
-            // Loads a uniform polygonal antiprism prism.
-            Self::AntiprismPrism(n, d) => (
-                Concrete::uniform_antiprism(n, d).prism(),
-                format!(
-                    "{}{}-gonal antiprism prism",
-                    n,
-                    if d > 1 {format!("/{}", d)} else {"".to_string()}
-                )
-            ),
+            // Builds a uniform polygonal duoprism.
+            Self::Duoprism(n1, d1, n2, d2) => {
+                check_polygon(n1, d1)?;
+                check_polygon(n2, d2)?;
+                let p1 = Concrete::star_polygon_with_edge(n1, d1, 1.0);
+
+                // Avoids duplicate work if possible.
+                if n1 == n2 && d1 == d2 {
+                    Concrete::duoprism(&p1, &p1)
+                } else {
+                    let p2 = Concrete::star_polygon_with_edge(n2, d2, 1.0);
+                    Concrete::duoprism(&p1, &p2)
+                }
+            }
+
+            // Builds a duoantiprism, with unit edges on its base polygons.
+            Self::Duoantiprism(n1, d1, n2, d2) => {
+                check_polygon(n1, d1)?;
+                check_polygon(n2, d2)?;
+                if !duoantiprism_uniform(n1, d1, n2, d2) {
+                    println!("This duoantiprism has no uniform realization.");
+                }
+
+                Concrete::duoantiprism(n1, d1, n2, d2)?
+            }
+
+            // Builds a uniform polygonal antiprism prism.
+            Self::AntiprismPrism(n, d) => {
+                check_antiprism(n, d)?;
+                Concrete::uniform_antiprism(n, d).prism()
+            }
+
+            // Builds a step prism.
+            Self::StepPrism(n, s1, s2) => Concrete::step_prism(n, &[s1, s2])
+                .ok_or(SpecialError::StepPrism { n, steps: [s1, s2] })?,
+
+            // Builds a simplex with a given rank.
+            Self::Simplex(rank) => Concrete::simplex(check_rank(rank)?),
+
+            // Builds a hypercube with a given rank.
+            Self::Hypercube(rank) => Concrete::hypercube(check_rank(rank)?),
+
+            // Builds an orthoplex with a given rank.
+            Self::Orthoplex(rank) => Concrete::orthoplex(check_rank(rank)?),
+
+            // Builds a Platonic solid with unit edge length.
+            Self::Platonic(idx) => {
+                if idx >= PLATONIC_NAMES.len() {
+                    return Err(SpecialError::Index(idx));
+                }
+                Concrete::platonic(idx)
+            }
+
+            // Builds a Catalan solid, dual to an Archimedean solid with unit
+            // edge length.
+            Self::Catalan(idx) => {
+                if idx >= CATALAN_NAMES.len() {
+                    return Err(SpecialError::Index(idx));
+                }
+                Concrete::catalan(idx)
+            }
 
-            // Loads a simplex with a given rank.
-            Self::Simplex(rank) => (
-                Concrete::simplex((rank + 1) as usize),
-                format!("{}-simplex", rank)
+            // Builds an icosahedral polytope with unit edge length from its
+            // exact coordinates.
+            Self::Icosahedral(idx) => {
+                if idx >= ICOSAHEDRAL_NAMES.len() {
+                    return Err(SpecialError::Index(idx));
+                }
+                Concrete::icosahedral(idx)
+            }
+
+            // Builds a cupola with regular faces.
+            Self::Cupola(n) => {
+                if !(2..=5).contains(&n) {
+                    return Err(SpecialError::Cupola(n));
+                }
+                Concrete::cupola(n)
+            }
+
+            // Builds the pentagonal rotunda.
+            Self::Rotunda => Concrete::pentagonal_rotunda(),
+
+            // Builds a regular polytope with unit edge length.
+            Self::Schlafli(ref symbol) => Concrete::from_schlafli(symbol)?,
+        })
+    }
+}
+
+/// Writes `n` or `n/d`, depending on whether `d` is 1.
+fn fraction(n: usize, d: usize) -> String {
+    if d > 1 {
+        format!("{}/{}", n, d)
+    } else {
+        n.to_string()
+    }
+}
+
+/// Checks that `{n / d}` is a non-degenerate polygon or compound of polygons,
+/// which happens when `0 < d < n / 2`.
+fn check_polygon(n: usize, d: usize) -> Result<(), SpecialError> {
+    if d == 0 || 2 * d >= n {
+        Err(SpecialError::Polygon { n, d })
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the largest turning number `d` for which the `n/d` polygon doesn't
+/// degenerate, or 1 if there's none.
+fn max_polygon_turning(n: usize) -> usize {
+    (n.saturating_sub(1) / 2).max(1)
+}
+
+/// Returns the largest turning number `d` for which the `n/d` antiprism doesn't
+/// degenerate, or 1 if there's none.
+fn max_antiprism_turning(n: usize) -> usize {
+    ((2 * n).saturating_sub(1) / 3).max(1)
+}
+
+/// Checks that the `n/d` antiprism is non-degenerate, which happens when
+/// `0 < d < 2n / 3`. Crossed antiprisms, where `d > n / 2`, are allowed, and so
+/// are the compounds of digonal antiprisms, where `d = n / 2`.
+fn check_antiprism(n: usize, d: usize) -> Result<(), SpecialError> {
+    if n < 2 || d == 0 || 3 * d >= 2 * n {
+        Err(SpecialError::Antiprism { n, d })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that a rank is at least -1, and returns the corresponding rank as
+/// stored internally.
+fn check_rank(rank: isize) -> Result<usize, SpecialError> {
+    if rank < -1 {
+        Err(SpecialError::Rank(rank))
+    } else {
+        Ok((rank + 1) as usize)
+    }
+}
+
+/// An error while building a polytope from the special library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpecialError {
+    /// The polygon `{n / d}` is degenerate or doesn't exist.
+    Polygon {
+        /// The number of sides.
+        n: usize,
+
+        /// The turning number.
+        d: usize,
+    },
+
+    /// The `n/d` antiprism is degenerate or doesn't exist.
+    Antiprism {
+        /// The number of sides of the base.
+        n: usize,
+
+        /// The turning number of the base.
+        d: usize,
+    },
+
+    /// The step prism isn't [valid](step_prism_valid).
+    StepPrism {
+        /// The number of vertices.
+        n: usize,
+
+        /// The steps.
+        steps: [usize; 2],
+    },
+
+    /// The rank is less than -1.
+    Rank(isize),
+
+    /// There's no Platonic, Catalan or icosahedral polytope with this index.
+    Index(usize),
+
+    /// There's no cupola with regular faces with this many sides.
+    Cupola(usize),
+
+    /// The duoantiprism couldn't be built.
+    Duoantiprism(DuoantiprismError),
+
+    /// The Schläfli symbol couldn't be read.
+    Schlafli(SchlafliError),
+}
+
+impl From<DuoantiprismError> for SpecialError {
+    fn from(err: DuoantiprismError) -> Self {
+        Self::Duoantiprism(err)
+    }
+}
+
+impl From<SchlafliError> for SpecialError {
+    fn from(err: SchlafliError) -> Self {
+        Self::Schlafli(err)
+    }
+}
+
+impl Display for SpecialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Polygon { n, d } => {
+                if *d == 0 || 2 * d > *n {
+                    write!(
+                        f,
+                        "{{{}/{}}} is not allowed, d must be between 1 and n/2",
+                        n, d
+                    )
+                } else {
+                    write!(f, "{{{}/{}}} is a degenerate compound of dyads", n, d)
+                }
+            }
+            Self::Antiprism { n, d } => write!(
+                f,
+                "the {}/{} antiprism is degenerate, d must be less than 2n/3",
+                n, d
             ),
+            Self::StepPrism { n, steps } => write!(
+                f,
+                "the {}-{}-{} step prism is degenerate",
+                n, steps[0], steps[1]
+            ),
+            Self::Rank(rank) => write!(f, "rank {} is less than -1", rank),
+            Self::Index(idx) => write!(f, "there is no polytope with index {}", idx),
+            Self::Cupola(n) => write!(f, "the {}-gonal cupola doesn't have regular faces", n),
+            Self::Duoantiprism(err) => write!(f, "{}", err),
+            Self::Schlafli(err) => write!(f, "{}", err),
+        }
+    }
+}
 
-            // Loads a hypercube with a given rank.
-            Self::Hypercube(rank) => (
-                Concrete::hypercube((rank + 1) as usize),
-                format!("{}-cube", rank)
+impl std::error::Error for SpecialError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::abs::Ranked;
+
+    /// Builds an entry of every kind, and checks its element counts and name.
+    #[test]
+    fn build() {
+        use SpecialLibrary::*;
+
+        let entries = vec![
+            (Polygon(5, 2), vec![1, 5, 5, 1], "5/2-gon"),
+            (Polygon(6, 2), vec![1, 6, 6, 1], "6/2-gon"),
+            (Prism(6, 1), vec![1, 12, 18, 8, 1], "6-gonal prism"),
+            (
+                Antiprism(5, 3),
+                vec![1, 10, 20, 12, 1],
+                "5/3-gonal antiprism",
+            ),
+            (
+                Antiprism(4, 2),
+                vec![1, 8, 16, 10, 1],
+                "4/2-gonal antiprism",
+            ),
+            (
+                Duoprism(3, 1, 5, 2),
+                vec![1, 15, 30, 23, 8, 1],
+                "3-5/2 duoprism",
             ),
+            (
+                Duoantiprism(4, 1, 3, 1),
+                vec![1, 24, 96, 110, 38, 1],
+                "4-3 duoantiprism",
+            ),
+            (
+                AntiprismPrism(3, 1),
+                vec![1, 12, 30, 28, 10, 1],
+                "3-gonal antiprism prism",
+            ),
+            (
+                StepPrism(5, 1, 2),
+                vec![1, 5, 10, 10, 5, 1],
+                "5-1-2 step prism",
+            ),
+            (Simplex(3), vec![1, 4, 6, 4, 1], "3-simplex"),
+            (Hypercube(4), vec![1, 16, 32, 24, 8, 1], "4-cube"),
+            (Orthoplex(-1), vec![1], "-1-orthoplex"),
+            (Platonic(4), vec![1, 12, 30, 20, 1], "Icosahedron"),
+            (Catalan(0), vec![1, 8, 18, 12, 1], "Triakis tetrahedron"),
+            (Icosahedral(4), vec![1, 600, 1200, 720, 120, 1], "120-cell"),
+            (Cupola(3), vec![1, 9, 15, 8, 1], "3-gonal cupola"),
+            (Rotunda, vec![1, 20, 35, 17, 1], "Pentagonal rotunda"),
+            (
+                Schlafli(" {5,3} ".to_string()),
+                vec![1, 20, 30, 12, 1],
+                "{5,3}",
+            ),
+        ];
+
+        for (special, counts, name) in entries {
+            let poly = special.build().unwrap();
+            assert_eq!(poly.abs.el_counts().0, counts, "{:?}", special);
+            assert_eq!(special.name(), name);
+        }
+    }
 
-            // Loads an orthoplex with a given rank.
-            Self::Orthoplex(rank) => (
-                Concrete::orthoplex((rank + 1) as usize),
-                format!("{}-orthoplex", rank)
+    /// Entries with invalid or degenerate parameters fail to build.
+    #[test]
+    fn build_errors() {
+        use SpecialLibrary::*;
+
+        let entries = vec![
+            (Polygon(4, 2), SpecialError::Polygon { n: 4, d: 2 }),
+            (Polygon(5, 3), SpecialError::Polygon { n: 5, d: 3 }),
+            (Prism(3, 0), SpecialError::Polygon { n: 3, d: 0 }),
+            (Antiprism(3, 2), SpecialError::Antiprism { n: 3, d: 2 }),
+            (Duoprism(3, 1, 4, 2), SpecialError::Polygon { n: 4, d: 2 }),
+            (
+                Duoantiprism(5, 2, 5, 1),
+                SpecialError::Duoantiprism(DuoantiprismError::Star),
+            ),
+            (
+                StepPrism(6, 2, 4),
+                SpecialError::StepPrism {
+                    n: 6,
+                    steps: [2, 4],
+                },
             ),
+            (Simplex(-2), SpecialError::Rank(-2)),
+            (Platonic(5), SpecialError::Index(5)),
+            (Icosahedral(5), SpecialError::Index(5)),
+            (Cupola(6), SpecialError::Cupola(6)),
+            (
+                Schlafli("{6,3}".to_string()),
+                SpecialError::Schlafli(SchlafliError::Tiling("{6,3}".to_string())),
+            ),
+        ];
+
+        for (special, err) in entries {
+            assert_eq!(special.build().unwrap_err(), err);
+        }
+    }
+
+    /// Turning numbers are lowered as the number of sides shrinks, and linked
+    /// duoprisms copy their first polygon.
+    #[test]
+    fn constrain() {
+        use SpecialLibrary::*;
+
+        let entries = vec![
+            (Polygon(7, 3), Polygon(7, 3)),
+            (Polygon(5, 3), Polygon(5, 2)),
+            (Prism(4, 2), Prism(4, 1)),
+            (Polygon(2, 5), Polygon(2, 1)),
+            (Antiprism(5, 3), Antiprism(5, 3)),
+            (AntiprismPrism(3, 2), AntiprismPrism(3, 1)),
+            (Duoprism(5, 3, 7, 4), Duoprism(5, 2, 7, 3)),
+            (StepPrism(5, 1, 9), StepPrism(5, 1, 4)),
+            (StepPrism(1000, 1, 999), StepPrism(50, 1, 49)),
+            (Simplex(-4), Simplex(-4)),
+        ];
+
+        for (mut special, constrained) in entries {
+            special.constrain(false);
+            assert_eq!(special, constrained);
         }
+
+        let mut duoprism = Duoprism(5, 3, 7, 4);
+        duoprism.constrain(true);
+        assert_eq!(duoprism, Duoprism(5, 2, 5, 2));
+
+        let mut duoantiprism = Duoantiprism(4, 1, 3, 1);
+        duoantiprism.constrain(true);
+        assert_eq!(duoantiprism, Duoantiprism(4, 1, 4, 1));
+    }
+
+    /// Stored values survive a round trip through RON, and are restored by
+    /// kind regardless of the order they were stored in.
+    #[test]
+    fn params() {
+        use SpecialLibrary::*;
+
+        let mut params = SpecialParams::default();
+        params.remember(&Polygon(7, 2));
+        params.remember(&Schlafli("{3,5}".to_string()));
+        params.remember(&Duoprism(4, 1, 6, 1));
+        params.remember(&Polygon(8, 3));
+        params.linked = true;
+
+        let ron = ron::to_string(&params).unwrap();
+        let params: SpecialParams = ron::from_str(&ron).unwrap();
+        assert_eq!(ron::to_string(&params).unwrap(), ron);
+
+        // Only the last value of each kind is kept.
+        let mut polygon = Polygon(3, 1);
+        params.restore(&mut polygon);
+        assert_eq!(polygon, Polygon(8, 3));
+
+        let mut schlafli = Schlafli("{4,3}".to_string());
+        params.restore(&mut schlafli);
+        assert_eq!(schlafli, Schlafli("{3,5}".to_string()));
+
+        // Restored values are constrained.
+        let mut duoprism = Duoprism(3, 1, 3, 1);
+        params.restore(&mut duoprism);
+        assert_eq!(duoprism, Duoprism(4, 1, 4, 1));
+
+        // Kinds without a stored value are left alone.
+        let mut prism = Prism(5, 2);
+        params.restore(&mut prism);
+        assert_eq!(prism, Prism(5, 2));
+
+        // Configurations without any stored values still load.
+        let params: SpecialParams = ron::from_str("()").unwrap();
+        assert_eq!(params, SpecialParams::default());
     }
 }
@@ -1,16 +1,24 @@
 //! The systems that update the main window.
 
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use super::config::{MeshColor, WfColor};
-use super::right_panel::ElementTypesRes;
-use super::{camera::ProjectionType, top_panel::SectionState};
-use crate::mesh::Renderable;
+use super::appearance::{EdgeHighlight, PolytopeStyle};
+use super::{camera::ProjectionType, command::PolytopeCommand, dialog::CurrentFile, lang::SelectedLanguage, memory::SharedPolytope, replace::PolytopeReplaced, top_panel::{SectionSlice, SectionState}, window::{open_window, Window}};
+use crate::mesh::{view_distance, DepthSort, MeshOptions, RenderFrame, RenderGeometry, Renderable};
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
 use crate::Concrete;
 
-use bevy::prelude::*;
-use bevy_egui::EguiSettings;
-use miratope_core::abs::Ranked;
+use bevy::{prelude::*, render::camera::Camera};
+use bevy_egui::{egui, EguiContext, EguiSettings};
+use miratope_core::{
+    abs::Ranked,
+    conc::{dual_cache::DualCache, edge_stats::{EquilateralReport, RelativeOrAbsolute}, ConcretePolytope},
+    file::off::block_count,
+};
+use vec_like::VecLike;
 
 /// The plugin in charge of the Miratope main window, and of drawing the
 /// polytope onto it.
@@ -20,12 +28,36 @@ impl Plugin for MainWindowPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_to_stage(CoreStage::PreUpdate, update_visible.system())
             .add_system(update_scale_factor.system())
-            .add_system_to_stage(CoreStage::PostUpdate, update_changed_polytopes.system())
-            .add_system_to_stage(CoreStage::PostUpdate, update_changed_color.system())
-            .init_resource::<PolyName>();
+            .add_system_to_stage(CoreStage::PostUpdate, update_peel.system().label("update_peel"))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_changed_polytopes.system().after("update_peel"),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, update_selected_name.system())
+            .add_system_to_stage(CoreStage::PostUpdate, update_title.system())
+            .add_system_to_stage(CoreStage::PostUpdate, update_dual_toggles.system())
+            .add_system_to_stage(CoreStage::Last, sort_translucent_faces.system())
+            .add_system(spawn_new_polytope.system().after("show_polytope_list"))
+            .add_system(
+                show_polytope_list
+                    .system()
+                    .label("show_polytope_list")
+                    .after("show_top_panel"),
+            )
+            .add_system(open_window::<PolytopeList>.system().after("show_top_panel"))
+            .init_resource::<PolyName>()
+            .init_resource::<PolytopeList>()
+            .init_resource::<ElementHighlight>()
+            .init_resource::<FaceFilter>()
+            .init_resource::<Peel>()
+            .init_resource::<MeshOptions>();
     }
 }
 
+/// The name of the selected polytope. Every polytope entity also stores its
+/// own name as a component, which is kept in sync with this resource while the
+/// entity is selected.
+#[derive(Clone)]
 pub struct PolyName(pub String);
 
 impl Default for PolyName {
@@ -33,30 +65,567 @@ impl Default for PolyName {
         let mut args = std::env::args();
         args.next();
         if let Some(path) = args.next() {
-            PolyName(PathBuf::from(path).file_stem().unwrap().to_string_lossy().into_owned())
+            PolyName::from_path(&PathBuf::from(path))
         } else {
             PolyName("default".to_string())
         }
     }
 }
 
+impl PolyName {
+    /// The name of a polytope loaded from a file, which is its file stem. If
+    /// the file stores a compound as several OFF blocks, the name also says
+    /// how many components it has.
+    pub fn from_path(path: &Path) -> Self {
+        let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let is_off = path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("off"));
+
+        match std::fs::read_to_string(path) {
+            Ok(src) if is_off && block_count(&src) > 1 => {
+                PolyName(format!("{} (compound of {})", stem, block_count(&src)))
+            }
+            _ => PolyName(stem),
+        }
+    }
+}
+
+/// Marks the polytope entity that all operations act on. There should only
+/// ever be one selected entity at a time.
+#[derive(Clone, Copy, Default)]
+pub struct Selected;
+
+/// Caches the dual of a polytope entity, so that toggling between them is
+/// instant. The cache is cleared whenever the polytope changes in any other
+/// way.
+#[derive(Default)]
+pub struct DualToggle {
+    /// The cached dual pairing.
+    pub cache: DualCache,
+
+    /// Whether the polytope was just changed by toggling its dual.
+    pub toggled: bool,
+}
+
+/// Stores the state of the polytope list.
+#[derive(Default)]
+pub struct PolytopeList {
+    /// Whether the polytope list window is shown.
+    pub show: bool,
+}
+
+impl Window for PolytopeList {
+    const NAME: &'static str = "title.polytopes";
+
+    fn is_open(&self) -> bool {
+        self.show
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.show
+    }
+}
+
+/// The element of the selected polytope whose edges are drawn emphasized, as
+/// chosen in the element inspector.
+pub struct ElementHighlight {
+    /// The rank and index of the highlighted element, if any.
+    pub element: Option<(usize, usize)>,
+
+    /// The color of the edges of the highlighted element, in sRGB.
+    pub color: Color,
+}
+
+impl Default for ElementHighlight {
+    fn default() -> Self {
+        Self {
+            element: None,
+            color: Color::rgb_u8(255, 200, 0),
+        }
+    }
+}
+
+impl ElementHighlight {
+    /// Returns the indices of the edges that are drawn emphasized on a
+    /// polytope, sorted and without repetitions. These are the edges of the
+    /// highlighted element, or the edges through it if it's a vertex.
+    pub fn edges(&self, poly: &Concrete) -> Vec<usize> {
+        let (rank, idx) = match self.element {
+            Some(element) => element,
+            None => return Vec::new(),
+        };
+
+        let mut edges = match rank {
+            1 => poly
+                .abs
+                .get_element(1, idx)
+                .map(|vertex| vertex.sups.as_slice().to_vec())
+                .unwrap_or_default(),
+            _ => poly.abs.element_subelements(rank, idx, 2).unwrap_or_default(),
+        };
+
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+}
+
+/// The faces of the selected polytope that are left out of its solid mesh, as
+/// chosen by their types in the right panel. The wireframe is always drawn in
+/// full.
+///
+/// Operations that keep the faces of the polytope, like transformations, keep
+/// the filter too. It's ignored whenever the face count changes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaceFilter {
+    /// Whether each face is hidden, or an empty list if none is.
+    pub hidden: Vec<bool>,
+}
+
+impl FaceFilter {
+    /// Returns whether the filter hides some face of a polytope.
+    pub fn applies_to(&self, poly: &Concrete) -> bool {
+        self.hidden.len() == poly.el_count(3) && self.hidden.contains(&true)
+    }
+
+    /// Returns whether a face is drawn.
+    pub fn shows(&self, face: usize) -> bool {
+        !self.hidden.get(face).copied().unwrap_or_default()
+    }
+}
+
+/// Peels the selected polytope of four or more dimensions, by leaving the
+/// facets closest to the viewpoint out of its solid mesh, so that the ones
+/// further inside show up. A face is only left out once every facet it's in
+/// is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Peel {
+    /// The facets of the selected polytope, from the closest to the viewpoint
+    /// to the furthest. Empty if the polytope can't be peeled.
+    pub order: Vec<usize>,
+
+    /// The facets that contain each face of the selected polytope.
+    pub face_facets: Vec<Vec<usize>>,
+
+    /// How many of the facets at the start of the order are hidden.
+    pub hidden: usize,
+}
+
+impl Peel {
+    /// Orders the facets of a polytope by their distance to the viewpoint.
+    /// The number of hidden facets is kept, unless the facet count changed.
+    pub fn new(poly: &Concrete, projection_type: &ProjectionType, hidden: usize) -> Self {
+        if poly.dim_or() < 4 || poly.rank() < 5 {
+            return Self::default();
+        }
+
+        let order = poly.facet_order_by(view_distance(poly, projection_type));
+        Self {
+            hidden: if hidden <= order.len() { hidden } else { 0 },
+            order,
+            face_facets: poly.abs.face_facets(),
+        }
+    }
+
+    /// Returns whether some face of a polytope is hidden.
+    pub fn applies_to(&self, poly: &Concrete) -> bool {
+        self.hidden > 0 && self.face_facets.len() == poly.el_count(3)
+    }
+
+    /// Returns whether each facet is hidden.
+    pub fn hidden_facets(&self) -> Vec<bool> {
+        let mut hidden = vec![false; self.order.len()];
+        for &facet in &self.order[..self.hidden.min(self.order.len())] {
+            hidden[facet] = true;
+        }
+
+        hidden
+    }
+}
+
+/// Orders the facets of the selected polytope for peeling whenever it or the
+/// projection changes.
+pub fn update_peel(
+    mut peel: ResMut<'_, Peel>,
+    projection_type: Res<'_, ProjectionType>,
+    selected: Query<'_, '_, &Concrete, With<Selected>>,
+    changed: Query<'_, '_, (), (With<Selected>, Or<(Changed<Concrete>, Added<Selected>)>)>,
+) {
+    if changed.iter().next().is_none() && !projection_type.is_changed() {
+        return;
+    }
+
+    let new_peel = match selected.iter().next() {
+        Some(poly) => Peel::new(poly, &projection_type, peel.hidden),
+        None => Peel::default(),
+    };
+
+    if *peel != new_peel {
+        *peel = new_peel;
+    }
+}
+
+/// Marks the child entities of a polytope that hold chunks of its meshes. The
+/// first chunk of the faces is stored in the polytope entity itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Chunk {
+    /// A chunk of the faces, past the first.
+    Faces,
+
+    /// A chunk of the wireframe.
+    Wireframe,
+
+    /// A chunk of the highlighted edges, which are left out of the wireframe.
+    Highlight,
+
+    /// A chunk of the edges of the element picked in the inspector, which are
+    /// left out of both the wireframe and the highlighted edges.
+    Inspect,
+}
+
+/// Pairs every chunk of the faces of a polytope with the data needed to sort
+/// its triangles. The wireframe chunks have no such data.
+fn face_chunks(
+    geometry: &RenderGeometry,
+    mesh_options: &MeshOptions,
+    face_filter: &dyn Fn(usize) -> bool,
+) -> impl Iterator<Item = (Mesh, Option<DepthSort>)> {
+    geometry.mesh_filtered(mesh_options, face_filter).into_iter().zip(
+        geometry
+            .depth_sorts_filtered(mesh_options, face_filter)
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::repeat_with(|| None)),
+    )
+}
+
+/// Spawns a chunk of a polytope as a child entity.
+fn spawn_chunk(
+    cb: &mut ChildBuilder<'_, '_, '_>,
+    mesh: Handle<Mesh>,
+    depth_sort: Option<DepthSort>,
+    material: Handle<StandardMaterial>,
+    is_visible: bool,
+    chunk: Chunk,
+) {
+    let mut entity = cb.spawn();
+    entity
+        .insert_bundle(PbrNoBackfaceBundle {
+            mesh,
+            material,
+            visible: Visible {
+                is_visible,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(chunk);
+
+    if let Some(depth_sort) = depth_sort {
+        entity.insert(depth_sort);
+    }
+}
+
+/// Stores a list of meshes into the existing chunk entities of a given kind,
+/// spawning new chunks or despawning the leftover ones as needed.
+#[allow(clippy::too_many_arguments)]
+fn update_chunks<I: Iterator<Item = (Mesh, Option<DepthSort>)>>(
+    commands: &mut Commands<'_, '_>,
+    meshes: &mut Assets<Mesh>,
+    parent: Entity,
+    new_meshes: I,
+    old_chunks: Vec<(Entity, Handle<Mesh>)>,
+    material: &Handle<StandardMaterial>,
+    is_visible: bool,
+    chunk: Chunk,
+) {
+    let mut old_chunks = old_chunks.into_iter();
+    let mut new_handles = Vec::new();
+
+    for (mesh, depth_sort) in new_meshes {
+        match old_chunks.next() {
+            Some((entity, handle)) => {
+                *meshes.get_mut(&handle).unwrap() = mesh;
+                set_depth_sort(commands, entity, depth_sort);
+            }
+            None => new_handles.push((meshes.add(mesh), depth_sort)),
+        }
+    }
+
+    if !new_handles.is_empty() {
+        commands.entity(parent).with_children(|cb| {
+            for (handle, depth_sort) in new_handles {
+                spawn_chunk(cb, handle, depth_sort, material.clone(), is_visible, chunk);
+            }
+        });
+    }
+
+    for (entity, _) in old_chunks {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Spawns a new polytope entity together with its wireframe, and returns its
+/// ID. Any chunks of the mesh past the first are spawned as children. The
+/// polytope gets its own materials, built from the given style.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_polytope(
+    commands: &mut Commands<'_, '_>,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    poly: Concrete,
+    name: PolyName,
+    style: PolytopeStyle,
+    translation: Vec3,
+    projection_type: &ProjectionType,
+    mesh_options: &MeshOptions,
+) -> Entity {
+    let geometry = poly.render_geometry(projection_type, mesh_options);
+    let mut mesh_chunks = face_chunks(&geometry, mesh_options, &|_| true);
+    let wf_chunks = geometry.wireframe(mesh_options);
+    let mesh_material = materials.add(style.mesh_material());
+    let wf_material = materials.add(style.wf_material());
+    let (mesh, depth_sort) = mesh_chunks.next().unwrap();
+
+    let mut entity = commands.spawn();
+    entity
+        // Mesh
+        .insert_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(mesh),
+            material: mesh_material.clone(),
+            transform: geometry.frame.transform(translation),
+            ..Default::default()
+        })
+        // Remaining mesh chunks and wireframe
+        .with_children(|cb| {
+            for (mesh, depth_sort) in mesh_chunks {
+                spawn_chunk(
+                    cb,
+                    meshes.add(mesh),
+                    depth_sort,
+                    mesh_material.clone(),
+                    true,
+                    Chunk::Faces,
+                );
+            }
+
+            for wf in wf_chunks {
+                spawn_chunk(cb, meshes.add(wf), None, wf_material.clone(), true, Chunk::Wireframe);
+            }
+        })
+        // Polytope
+        .insert(poly)
+        .insert(name)
+        .insert(style)
+        .insert(geometry.frame)
+        .insert(DualToggle::default())
+        .insert(CurrentFile::default())
+        .insert(SharedPolytope::default());
+
+    if let Some(depth_sort) = depth_sort {
+        entity.insert(depth_sort);
+    }
+
+    entity.id()
+}
+
+/// Gives an entity new data for sorting its triangles, or removes the old data
+/// if there's none.
+fn set_depth_sort(commands: &mut Commands<'_, '_>, entity: Entity, depth_sort: Option<DepthSort>) {
+    match depth_sort {
+        Some(depth_sort) => {
+            commands.entity(entity).insert(depth_sort);
+        }
+        None => {
+            commands.entity(entity).remove::<DepthSort>();
+        }
+    }
+}
+
+/// Spawns a copy of the selected polytope next to the existing ones, with the
+/// same style, and selects it, whenever [`PolytopeCommand::NewPolytope`] is
+/// sent.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_new_polytope(
+    mut commands: Commands<'_, '_>,
+    mut events: EventReader<'_, '_, PolytopeCommand>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    mut poly_name: ResMut<'_, PolyName>,
+    selected: Query<'_, '_, (Entity, &Concrete, &PolytopeStyle), With<Selected>>,
+    polies: Query<'_, '_, (&Transform, &RenderFrame), With<Concrete>>,
+    projection_type: Res<'_, ProjectionType>,
+    mesh_options: Res<'_, MeshOptions>,
+) {
+    if events
+        .iter()
+        .filter(|&&command| command == PolytopeCommand::NewPolytope)
+        .count()
+        == 0
+    {
+        return;
+    }
+
+    if let Some((entity, poly, style)) = selected.iter().next() {
+        // We place the new polytope to the right of all others, leaving a gap
+        // proportional to its size.
+        let radius = poly.vertices.iter().map(|v| v.norm()).fold(1.0, f64::max) as f32;
+        let x = polies
+            .iter()
+            .map(|(tf, frame)| frame.placement(tf).x)
+            .fold(0.0, f32::max)
+            + 3.0 * radius;
+
+        let new_entity = spawn_polytope(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            poly.clone(),
+            PolyName(format!("Copy of {}", poly_name.0)),
+            *style,
+            Vec3::new(x, 0.0, 0.0),
+            &projection_type,
+            &mesh_options,
+        );
+
+        commands.entity(entity).remove::<Selected>();
+        commands.entity(new_entity).insert(Selected);
+        poly_name.0 = format!("Copy of {}", poly_name.0);
+    }
+}
+
+/// Shows the list of loaded polytopes, from which one can change the selected
+/// polytope and toggle the visibility of each of them. The extra slices of the
+/// cross-section view aren't listed.
+pub fn show_polytope_list(
+    mut commands: Commands<'_, '_>,
+    mut events: EventWriter<'_, '_, PolytopeCommand>,
+    egui_ctx: Res<'_, EguiContext>,
+    mut polytope_list: ResMut<'_, PolytopeList>,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut polies: Query<
+        '_,
+        '_,
+        (
+            Entity,
+            &mut Concrete,
+            &PolyName,
+            &mut Visible,
+            &Children,
+            Option<&Selected>,
+        ),
+        Without<SectionSlice>,
+    >,
+    mut chunks_vis: Query<'_, '_, &mut Visible, (With<Chunk>, Without<Concrete>)>,
+    lang: Res<'_, SelectedLanguage>,
+) {
+    let mut open = polytope_list.show;
+    let mut new_selection = None;
+
+    PolytopeList::egui_window(*lang)
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            for (entity, _, name, mut visible, children, selected) in polies.iter_mut() {
+                ui.horizontal(|ui| {
+                    // Toggles both the faces and the wireframe of the polytope.
+                    let mut new_visible = visible.is_visible;
+                    if ui.checkbox(&mut new_visible, "").changed() {
+                        visible.is_visible = new_visible;
+
+                        for child in children.iter() {
+                            if let Ok(mut chunk_visible) = chunks_vis.get_mut(*child) {
+                                chunk_visible.is_visible = new_visible;
+                            }
+                        }
+                    }
+
+                    if ui.selectable_label(selected.is_some(), &name.0).clicked()
+                        && selected.is_none()
+                    {
+                        new_selection = Some(entity);
+                    }
+                });
+            }
+
+            ui.separator();
+
+            if ui.button(t!(lang, "window.new_polytope")).clicked() {
+                events.send(PolytopeCommand::NewPolytope);
+            }
+        });
+
+    polytope_list.show = open;
+
+    // Moves the selection to another polytope.
+    if let Some(new_entity) = new_selection {
+        for (entity, mut poly, name, _, _, selected) in polies.iter_mut() {
+            if selected.is_some() {
+                commands.entity(entity).remove::<Selected>();
+            } else if entity == new_entity {
+                commands.entity(entity).insert(Selected);
+                poly_name.0 = name.0.clone();
+
+                // Forces the UI to update to the newly selected polytope.
+                poly.set_changed();
+            }
+        }
+    }
+}
+
+/// Clears the dual cache of every polytope that changed in any way other than
+/// by toggling its dual.
+pub fn update_dual_toggles(mut toggles: Query<'_, '_, &mut DualToggle, Changed<Concrete>>) {
+    for mut toggle in toggles.iter_mut() {
+        if toggle.toggled {
+            toggle.toggled = false;
+        } else if !toggle.cache.is_empty() {
+            toggle.cache.clear();
+        }
+    }
+}
+
+/// Stores the name of the selected polytope in its entity.
+pub fn update_selected_name(
+    poly_name: Res<'_, PolyName>,
+    mut names: Query<'_, '_, &mut PolyName, With<Selected>>,
+) {
+    if poly_name.is_changed() {
+        for mut name in names.iter_mut() {
+            name.0 = poly_name.0.clone();
+        }
+    }
+}
+
+/// Toggles the faces and the wireframe of the selected polytope.
 pub fn update_visible(
     keyboard: Res<'_, Input<KeyCode>>,
-    mut polies_vis: Query<'_, '_, &mut Visible, With<Concrete>>,
-    mut wfs_vis: Query<'_, '_, &mut Visible, Without<Concrete>>,
+    mut polies_vis: Query<'_, '_, (&mut Visible, &Children), (With<Concrete>, With<Selected>)>,
+    mut chunks_vis: Query<'_, '_, (&mut Visible, &Chunk), Without<Concrete>>,
 ) {
     if keyboard.get_pressed().count() == 1 {
-        if keyboard.just_pressed(KeyCode::V) {
-            if let Some(mut visible) = polies_vis.iter_mut().next() {
+        if let Some((mut visible, children)) = polies_vis.iter_mut().next() {
+            let faces = keyboard.just_pressed(KeyCode::V);
+            let wireframe = keyboard.just_pressed(KeyCode::B);
+
+            if faces {
                 let vis = visible.is_visible;
                 visible.is_visible = !vis;
             }
-        }
 
-        if keyboard.just_pressed(KeyCode::B) {
-            if let Some(mut visible) = wfs_vis.iter_mut().next() {
-                let vis = visible.is_visible;
-                visible.is_visible = !vis;
+            for child in children.iter() {
+                if let Ok((mut chunk_visible, chunk)) = chunks_vis.get_mut(*child) {
+                    match chunk {
+                        // The face chunks follow the visibility of the first one.
+                        Chunk::Faces if faces => chunk_visible.is_visible = visible.is_visible,
+                        Chunk::Wireframe | Chunk::Highlight | Chunk::Inspect if wireframe => {
+                            let vis = chunk_visible.is_visible;
+                            chunk_visible.is_visible = !vis;
+                        }
+                        _ => {}
+                    }
+                }
             }
         }
     }
@@ -69,66 +638,302 @@ pub fn update_scale_factor(mut egui_settings: ResMut<'_, EguiSettings>, windows:
     }
 }
 
+/// The data kept between runs of [`update_changed_polytopes`] to highlight the
+/// outlier edges.
+#[derive(Default)]
+pub struct HighlightCache {
+    /// The edges of every polytope that don't have the median length, which
+    /// are thrown away whenever it or the highlighting threshold changes.
+    reports: HashMap<Entity, EquilateralReport>,
+
+    /// The material shared by the highlighted edges of every polytope.
+    material: Option<Handle<StandardMaterial>>,
+
+    /// The material of the edges of the element picked in the inspector.
+    inspect_material: Option<Handle<StandardMaterial>>,
+}
+
 /// Updates polytopes after an operation.
+#[allow(clippy::too_many_arguments)]
 pub fn update_changed_polytopes(
+    mut commands: Commands<'_, '_>,
     mut meshes: ResMut<'_, Assets<Mesh>>,
-    polies: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
-    wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
-    mut windows: ResMut<'_, Windows>,
+    mut material_assets: ResMut<'_, Assets<StandardMaterial>>,
+    mut highlight_cache: Local<'_, HighlightCache>,
+    polies: Query<
+        '_,
+        '_,
+        (Entity, &Concrete, &Handle<Mesh>, &Children, Option<&Selected>),
+        Changed<Concrete>,
+    >,
+    all_polies: Query<'_, '_, (Entity, &Concrete, &Handle<Mesh>, &Children, Option<&Selected>)>,
+    materials: Query<'_, '_, (&Handle<StandardMaterial>, &Visible), With<Concrete>>,
+    chunks: Query<
+        '_,
+        '_,
+        (&Chunk, &Handle<Mesh>, &Handle<StandardMaterial>, &Visible),
+        Without<Concrete>,
+    >,
+    mut frames: Query<'_, '_, (&mut Transform, &mut RenderFrame)>,
     mut section_state: ResMut<'_, SectionState>,
-    mut element_types: ResMut<'_, ElementTypesRes>,
-    name: Res<'_, PolyName>,
 
     orthogonal: Res<'_, ProjectionType>,
+    mesh_options: Res<'_, MeshOptions>,
+    (edge_highlight, element_highlight, face_filter, peel): (
+        Res<'_, EdgeHighlight>,
+        Res<'_, ElementHighlight>,
+        Res<'_, FaceFilter>,
+        Res<'_, Peel>,
+    ),
 ) {
-    for (poly, mesh_handle, children) in polies.iter() {
-        if cfg!(debug_assertions) {
-            poly.assert_valid();
+    // Only the edge lengths of unchanged polytopes that still exist are kept.
+    let HighlightCache {
+        reports,
+        material,
+        inspect_material,
+    } = &mut *highlight_cache;
+    for (entity, ..) in polies.iter() {
+        reports.remove(&entity);
+    }
+    reports.retain(|&entity, _| all_polies.get(entity).is_ok());
+    if edge_highlight.is_changed() {
+        reports.clear();
+    }
+
+    let highlight_material = material
+        .get_or_insert_with(|| material_assets.add(edge_highlight.color.into()))
+        .clone();
+    if edge_highlight.is_changed() {
+        if let Some(material) = material_assets.get_mut(&highlight_material) {
+            *material = edge_highlight.color.into();
         }
+    }
 
-        if !element_types.main_updating {
-            element_types.main = false;
-        } else {
-            element_types.main_updating = false;
+    let inspect_material = inspect_material
+        .get_or_insert_with(|| material_assets.add(element_highlight.color.into()))
+        .clone();
+    if element_highlight.is_changed() {
+        if let Some(material) = material_assets.get_mut(&inspect_material) {
+            *material = element_highlight.color.into();
         }
+    }
 
-        *meshes.get_mut(mesh_handle).unwrap() = poly.mesh(*orthogonal);
+    // Updates the mesh and all wireframes of a polytope, spawning or
+    // despawning chunks as needed.
+    let mut update_meshes =
+        |entity: Entity, poly: &Concrete, mesh_handle: &Handle<Mesh>, children: &Children| {
+            let geometry = poly.render_geometry(&orthogonal, &mesh_options);
 
-        // Updates all wireframes.
-        for child in children.iter() {
-            if let Ok(wf_handle) = wfs.get_component::<Handle<Mesh>>(*child) {
-                *meshes.get_mut(wf_handle).unwrap() = poly.wireframe(*orthogonal);
+            // The polytope stays in place as the frame of its meshes moves.
+            if let Ok((mut transform, mut frame)) = frames.get_mut(entity) {
+                if *frame != geometry.frame {
+                    let placement = frame.placement(&transform);
+                    *frame = geometry.frame;
+                    *transform = frame.transform(placement);
+                }
             }
+
+            // The edges of the inspected element are drawn apart from
+            // everything else, and faces are hidden, only on the selected
+            // polytope.
+            let is_selected = matches!(all_polies.get(entity), Ok((.., Some(_))));
+            let inspected = if is_selected {
+                element_highlight.edges(poly)
+            } else {
+                Vec::new()
+            };
+            let filtered = is_selected && face_filter.applies_to(poly);
+            let hidden_facets = if is_selected && peel.applies_to(poly) {
+                peel.hidden_facets()
+            } else {
+                Vec::new()
+            };
+            let is_shown = |face: usize| {
+                (!filtered || face_filter.shows(face))
+                    && (hidden_facets.is_empty()
+                        || peel.face_facets[face].iter().any(|&facet| !hidden_facets[facet]))
+            };
+            let is_inspected = |idx: usize| inspected.binary_search(&idx).is_ok();
+
+            // The outlier edges are drawn apart from the wireframe.
+            let outliers = if edge_highlight.enabled {
+                let tolerance = RelativeOrAbsolute::Relative(edge_highlight.threshold / 100.0);
+                reports
+                    .entry(entity)
+                    .or_insert_with(|| poly.equilateral_report(tolerance))
+                    .outlier_edges()
+            } else {
+                Vec::new()
+            };
+            let is_outlier =
+                |idx: usize| !is_inspected(idx) && outliers.binary_search(&idx).is_ok();
+
+            let mut mesh_chunks = face_chunks(&geometry, &mesh_options, &is_shown);
+            let (mesh, depth_sort) = mesh_chunks.next().unwrap();
+            *meshes.get_mut(mesh_handle).unwrap() = mesh;
+            set_depth_sort(&mut commands, entity, depth_sort);
+
+            let mut face_chunks = Vec::new();
+            let mut wf_chunks = Vec::new();
+            let mut highlight_chunks = Vec::new();
+            let mut inspect_chunks = Vec::new();
+            let mut wf_material = None;
+            for &child in children.iter() {
+                if let Ok((chunk, handle, material, visible)) = chunks.get(child) {
+                    match chunk {
+                        Chunk::Faces => face_chunks.push((child, handle.clone())),
+                        Chunk::Wireframe => {
+                            wf_chunks.push((child, handle.clone()));
+                            wf_material.get_or_insert((material.clone(), visible.is_visible));
+                        }
+                        Chunk::Highlight => highlight_chunks.push((child, handle.clone())),
+                        Chunk::Inspect => inspect_chunks.push((child, handle.clone())),
+                    }
+                }
+            }
+
+            let (mesh_material, mesh_visible) = materials.get(entity).unwrap();
+            update_chunks(
+                &mut commands,
+                &mut meshes,
+                entity,
+                mesh_chunks,
+                face_chunks,
+                mesh_material,
+                mesh_visible.is_visible,
+                Chunk::Faces,
+            );
+
+            // There's always at least one wireframe chunk to copy the material
+            // from.
+            if let Some((wf_material, wf_visible)) = wf_material {
+                update_chunks(
+                    &mut commands,
+                    &mut meshes,
+                    entity,
+                    geometry
+                        .wireframe_filtered(&mesh_options, |idx| {
+                            !is_outlier(idx) && !is_inspected(idx)
+                        })
+                        .into_iter()
+                        .map(|wf| (wf, None)),
+                    wf_chunks,
+                    &wf_material,
+                    wf_visible,
+                    Chunk::Wireframe,
+                );
+
+                let highlight_meshes = if outliers.is_empty() {
+                    Vec::new()
+                } else {
+                    geometry.wireframe_filtered(&mesh_options, is_outlier)
+                };
+                update_chunks(
+                    &mut commands,
+                    &mut meshes,
+                    entity,
+                    highlight_meshes.into_iter().map(|wf| (wf, None)),
+                    highlight_chunks,
+                    &highlight_material,
+                    wf_visible,
+                    Chunk::Highlight,
+                );
+
+                let inspect_meshes = if inspected.is_empty() {
+                    Vec::new()
+                } else {
+                    geometry.wireframe_filtered(&mesh_options, is_inspected)
+                };
+                update_chunks(
+                    &mut commands,
+                    &mut meshes,
+                    entity,
+                    inspect_meshes.into_iter().map(|wf| (wf, None)),
+                    inspect_chunks,
+                    &inspect_material,
+                    wf_visible,
+                    Chunk::Inspect,
+                );
+            }
+        };
+
+    // Changing the projection type, the mesh options, any of the highlights,
+    // the hidden faces or the peeled facets requires every polytope to be
+    // redrawn. The polytopes themselves aren't marked as changed, so that
+    // none of this is undone.
+    if orthogonal.is_changed()
+        || mesh_options.is_changed()
+        || edge_highlight.is_changed()
+        || element_highlight.is_changed()
+        || face_filter.is_changed()
+        || peel.is_changed()
+    {
+        for (entity, poly, mesh_handle, children, _) in all_polies.iter() {
+            update_meshes(entity, poly, mesh_handle, children);
+        }
+    }
+
+    for (entity, poly, mesh_handle, children, selected) in polies.iter() {
+        update_meshes(entity, poly, mesh_handle, children);
+
+        // Only the selected polytope is tied to the rest of the UI.
+        if selected.is_none() {
+            continue;
         }
 
         // We reset the cross-section view if we didn't use it to change the polytope.
         if !section_state.is_changed() {
             section_state.close();
         }
+    }
+}
 
-        windows
-            .get_primary_mut()
-            .unwrap()
-            .set_title(format!("{} - Miratope v{}", name.0, env!("CARGO_PKG_VERSION")));
+/// Shows the name of the selected polytope in the window title whenever it
+/// changes, or whenever the polytope is replaced by a loaded one.
+pub fn update_title(
+    mut replaced: EventReader<'_, '_, PolytopeReplaced>,
+    name: Res<'_, PolyName>,
+    mut windows: ResMut<'_, Windows>,
+) {
+    if replaced.iter().count() == 0 && !name.is_changed() {
+        return;
+    }
 
+    if let Some(window) = windows.get_primary_mut() {
+        window.set_title(format!("{} - Miratope v{}", name.0, env!("CARGO_PKG_VERSION")));
     }
 }
 
-pub fn update_changed_color(
-    mut materials: ResMut<'_, Assets<StandardMaterial>>,
-    mut polies: Query<'_, '_, &Handle<StandardMaterial>, With<Concrete>>,
-    mut wfs: Query<'_, '_, &Handle<StandardMaterial>, Without<Concrete>>,
-    mesh_color: Res<'_, MeshColor>,
-    wf_color: Res<'_, WfColor>,
+/// Sorts the triangles of every translucent chunk of faces from back to front
+/// whenever the camera moves far enough, so that they're blended in the right
+/// order. This runs after the transforms have been propagated.
+pub fn sort_translucent_faces(
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mesh_options: Res<'_, MeshOptions>,
+    cameras: Query<'_, '_, &GlobalTransform, With<Camera>>,
+    mut faces: Query<'_, '_, (&Handle<Mesh>, &GlobalTransform, &Visible, &mut DepthSort)>,
 ) {
-    if let Some(material_handle) = polies.iter_mut().next() {
-        *materials.get_mut(material_handle).unwrap() = StandardMaterial {
-            base_color: mesh_color.0,
-            metallic: 0.0,
-            ..Default::default()
-        };
+    if !mesh_options.depth_sort {
+        return;
     }
-    if let Some(wf_handle) = wfs.iter_mut().next() {
-        *materials.get_mut(wf_handle).unwrap() = wf_color.0.into()
+
+    let eye = match cameras.iter().next() {
+        Some(cam_gtf) => cam_gtf.translation,
+        None => return,
+    };
+
+    for (handle, gtf, visible, mut depth_sort) in faces.iter_mut() {
+        if !visible.is_visible || !visible.is_transparent {
+            continue;
+        }
+
+        // The position of the camera in the coordinates of the mesh.
+        let eye = gtf.compute_matrix().inverse().transform_point3(eye);
+
+        if depth_sort.needs_sort(eye) {
+            if let Some(mesh) = meshes.get_mut(handle) {
+                mesh.set_indices(Some(depth_sort.sort(eye)));
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,358 @@
+//! The measurement mode, in which vertices of the selected polytope are picked
+//! by clicking on them, to measure the distance or the angle between them.
+
+use std::fmt::Display;
+
+use super::{camera::ProjectionType, lang::SelectedLanguage, main_window::Selected};
+use crate::mesh::{projected_coords, segment_mesh, RenderFrame};
+use crate::no_cull_pipeline::PbrNoBackfaceBundle;
+use crate::Concrete;
+
+use bevy::{prelude::*, render::camera::Camera};
+use bevy_egui::{egui, EguiContext};
+
+/// How close the cursor has to be to a vertex to pick it, in logical pixels.
+const PICK_RADIUS: f32 = 12.0;
+
+/// The most vertices that can be picked at once.
+const MAX_PICKED: usize = 3;
+
+/// The plugin in charge of the measurement mode.
+pub struct MeasurePlugin;
+
+impl Plugin for MeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Measurement>()
+            .add_system(
+                show_measure_window
+                    .system()
+                    .label("show_measure_window")
+                    .after("show_top_panel"),
+            )
+            // Clicks on the window shouldn't pick vertices behind it.
+            .add_system(pick_vertices.system().after("show_measure_window"))
+            .add_system_to_stage(CoreStage::PostUpdate, update_measure_overlay.system());
+    }
+}
+
+/// The state of the measurement mode.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Measurement {
+    /// Whether the measurement mode is active.
+    pub active: bool,
+
+    /// The indices of the picked vertices of the selected polytope, in the
+    /// order in which they were picked.
+    pub picked: Vec<usize>,
+}
+
+/// The value measured from the picked vertices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeasureValue {
+    /// The distance between two vertices.
+    Distance(f64),
+
+    /// The angle at the middle one of three vertices, in radians.
+    Angle(f64),
+}
+
+impl Display for MeasureValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Distance(distance) => write!(f, "Distance: {}", distance),
+            Self::Angle(angle) => write!(f, "Angle: {}°", angle.to_degrees()),
+        }
+    }
+}
+
+impl MeasureValue {
+    /// Returns the value as shown in the measurement window, in the selected
+    /// language.
+    pub fn label(self, lang: SelectedLanguage) -> String {
+        match self {
+            Self::Distance(distance) => format!("{}: {}", t!(lang, "measure.distance"), distance),
+            Self::Angle(angle) => format!("{}: {}°", t!(lang, "measure.angle"), angle.to_degrees()),
+        }
+    }
+}
+
+impl Measurement {
+    /// Enters the measurement mode if it's inactive, and leaves it otherwise.
+    pub fn toggle(&mut self) {
+        self.active = !self.active;
+        self.picked.clear();
+    }
+
+    /// Unpicks every vertex.
+    pub fn clear(&mut self) {
+        self.picked.clear();
+    }
+
+    /// Picks a vertex. Picking the last picked vertex again unpicks it, and
+    /// picking a vertex after the maximum starts a new measurement.
+    pub fn pick(&mut self, idx: usize) {
+        if self.picked.last() == Some(&idx) {
+            self.picked.pop();
+        } else {
+            if self.picked.len() == MAX_PICKED {
+                self.picked.clear();
+            }
+
+            self.picked.push(idx);
+        }
+    }
+
+    /// Returns the segments between consecutive picked vertices, as pairs of
+    /// indices into the picked vertices.
+    pub fn segments(&self) -> Vec<u32> {
+        (1..self.picked.len() as u32)
+            .flat_map(|i| [i - 1, i])
+            .collect()
+    }
+
+    /// Measures the distance between two picked vertices, or the angle at the
+    /// middle one of three, in the full dimension of the polytope.
+    pub fn value(&self, poly: &Concrete) -> Option<MeasureValue> {
+        match *self.picked.as_slice() {
+            [i, j] => poly.vertex_distance(i, j).map(MeasureValue::Distance),
+            [i, j, k] => poly.vertex_angle(i, j, k).map(MeasureValue::Angle),
+            _ => None,
+        }
+    }
+}
+
+/// Marks the entity that draws the segments between the picked vertices.
+pub struct MeasureOverlay;
+
+/// Shows the window with the picked vertices and the measured value, for as
+/// long as the measurement mode is active. Closing it leaves the mode.
+pub fn show_measure_window(
+    egui_ctx: Res<'_, EguiContext>,
+    mut measurement: ResMut<'_, Measurement>,
+    selected: Query<'_, '_, &Concrete, With<Selected>>,
+    lang: Res<'_, SelectedLanguage>,
+) {
+    if !measurement.active {
+        return;
+    }
+
+    let mut open = true;
+    let mut clear = false;
+
+    egui::Window::new(t!(lang, "title.measure"))
+        .id(egui::Id::new("title.measure"))
+        .open(&mut open)
+        .resizable(false)
+        .show(egui_ctx.ctx(), |ui| {
+            let poly = if let Some(poly) = selected.iter().next() {
+                poly
+            } else {
+                ui.label(t!(lang, "window.no_polytope"));
+                return;
+            };
+
+            ui.label(t!(lang, "measure.help"));
+            ui.separator();
+
+            if measurement.picked.is_empty() {
+                ui.label(t!(lang, "measure.none_picked"));
+            } else {
+                ui.label(format!("{}: {:?}", t!(lang, "measure.vertices"), measurement.picked));
+            }
+
+            if let Some(value) = measurement.value(poly) {
+                ui.label(value.label(*lang));
+            }
+
+            // Esc also clears the picked vertices.
+            if ui.button(t!(lang, "window.clear")).clicked() {
+                clear = true;
+            }
+        });
+
+    if !open {
+        measurement.toggle();
+    } else if clear {
+        measurement.clear();
+    }
+}
+
+/// Picks the vertex of the selected polytope closest to the cursor on a click,
+/// as long as it's close enough, and clears the picked vertices on Esc.
+#[allow(clippy::too_many_arguments)]
+pub fn pick_vertices(
+    egui_ctx: Res<'_, EguiContext>,
+    mouse_button: Res<'_, Input<MouseButton>>,
+    keyboard: Res<'_, Input<KeyCode>>,
+    windows: Res<'_, Windows>,
+    projection_type: Res<'_, ProjectionType>,
+    mut measurement: ResMut<'_, Measurement>,
+    selected: Query<'_, '_, (&Concrete, &RenderFrame, &GlobalTransform), With<Selected>>,
+    cameras: Query<'_, '_, (&Camera, &GlobalTransform)>,
+) {
+    if !measurement.active {
+        return;
+    }
+
+    let ctx = egui_ctx.ctx();
+    if keyboard.just_pressed(KeyCode::Escape) && !ctx.wants_keyboard_input() {
+        measurement.clear();
+        return;
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || ctx.wants_pointer_input() {
+        return;
+    }
+
+    let cursor = match windows.get_primary().and_then(|window| window.cursor_position()) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+    let (poly, frame, poly_gtf) = match selected.iter().next() {
+        Some(selected) => selected,
+        None => return,
+    };
+    let (camera, cam_gtf) = match cameras.iter().next() {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    // The vertex drawn closest to the cursor, and its distance to it.
+    let mut closest: Option<(usize, f32)> = None;
+    for (idx, coords) in projected_coords(poly, poly.vertices.iter(), &projection_type)
+        .into_iter()
+        .enumerate()
+    {
+        let world = poly_gtf.mul_vec3(Vec3::from(frame.to_mesh(coords)));
+        if let Some(screen) = camera.world_to_screen(&windows, cam_gtf, world) {
+            let distance = screen.distance(cursor);
+            if distance < PICK_RADIUS && closest.map_or(true, |(_, d)| distance < d) {
+                closest = Some((idx, distance));
+            }
+        }
+    }
+
+    if let Some((idx, _)) = closest {
+        measurement.pick(idx);
+
+        // The measurements are also printed, so that they can be copied.
+        if let Some(value) = measurement.value(poly) {
+            println!("{} (vertices {:?})", value, measurement.picked);
+        }
+    }
+}
+
+/// Redraws the segments between the picked vertices whenever they change, or
+/// whenever the selected polytope, the projection or the frame of its meshes
+/// changes. Changing the selected polytope also unpicks every vertex.
+#[allow(clippy::too_many_arguments)]
+pub fn update_measure_overlay(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    mut material: Local<'_, Option<Handle<StandardMaterial>>>,
+    mut measurement: ResMut<'_, Measurement>,
+    projection_type: Res<'_, ProjectionType>,
+    selected: Query<'_, '_, (Entity, &Concrete, &RenderFrame), With<Selected>>,
+    changed: Query<'_, '_, (), (With<Selected>, Or<(Changed<Concrete>, Added<Selected>)>)>,
+    moved: Query<'_, '_, (), (With<Selected>, Changed<RenderFrame>)>,
+    overlays: Query<'_, '_, Entity, With<MeasureOverlay>>,
+) {
+    let changed = changed.iter().next().is_some();
+    if changed && !measurement.picked.is_empty() {
+        measurement.clear();
+    }
+
+    // The segments also follow the frame of the meshes of the polytope.
+    let moved = moved.iter().next().is_some();
+    if !measurement.is_changed() && !projection_type.is_changed() && !changed && !moved {
+        return;
+    }
+
+    for overlay in overlays.iter() {
+        commands.entity(overlay).despawn_recursive();
+    }
+
+    let (entity, poly, frame) = match selected.iter().next() {
+        Some(selected) => selected,
+        None => return,
+    };
+    if !measurement.active || measurement.picked.len() < 2 {
+        return;
+    }
+
+    // The segments are drawn as a child of the polytope, so they're given in
+    // the frame of its meshes.
+    let picked = measurement.picked.iter().map(|&idx| &poly.vertices[idx]);
+    let coords: Vec<_> = projected_coords(poly, picked, &projection_type)
+        .into_iter()
+        .map(|p| frame.to_mesh(p))
+        .collect();
+    let mesh = segment_mesh(&coords, &measurement.segments());
+    let material = material
+        .get_or_insert_with(|| materials.add(Color::rgb_u8(64, 255, 128).into()))
+        .clone();
+
+    commands.entity(entity).with_children(|cb| {
+        cb.spawn_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(mesh),
+            material,
+            ..Default::default()
+        })
+        .insert(MeasureOverlay);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::Polytope;
+
+    /// Picking starts over after three vertices, and picking the last vertex
+    /// again unpicks it.
+    #[test]
+    fn pick() {
+        let mut measurement = Measurement::default();
+        measurement.toggle();
+        assert!(measurement.active);
+
+        for idx in [0, 1, 2] {
+            measurement.pick(idx);
+        }
+        assert_eq!(measurement.picked, vec![0, 1, 2]);
+        assert_eq!(measurement.segments(), vec![0, 1, 1, 2]);
+
+        measurement.pick(2);
+        assert_eq!(measurement.picked, vec![0, 1]);
+
+        measurement.pick(2);
+        measurement.pick(3);
+        assert_eq!(measurement.picked, vec![3]);
+        assert!(measurement.segments().is_empty());
+
+        measurement.toggle();
+        assert!(!measurement.active);
+        assert!(measurement.picked.is_empty());
+    }
+
+    /// Two vertices give a distance, and three an angle.
+    #[test]
+    fn value() {
+        let square = Concrete::polygon(4);
+        let mut measurement = Measurement::default();
+        assert_eq!(measurement.value(&square), None);
+
+        measurement.pick(0);
+        measurement.pick(1);
+        match measurement.value(&square) {
+            Some(MeasureValue::Distance(d)) => assert!((d - 2f64.sqrt()).abs() < 1e-9),
+            value => panic!("expected a distance, found {:?}", value),
+        }
+
+        measurement.pick(2);
+        match measurement.value(&square) {
+            Some(MeasureValue::Angle(a)) => assert!((a.to_degrees() - 90.0).abs() < 1e-9),
+            value => panic!("expected an angle, found {:?}", value),
+        }
+    }
+}
@@ -1,24 +1,71 @@
 //! Manages the memory tab.
 
-use bevy::prelude::{Query, Res, ResMut};
+use std::sync::Arc;
+
+use bevy::prelude::{Changed, Query, Res, ResMut, With};
 use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::make_mut;
 
 use crate::Concrete;
 
-use super::main_window::PolyName;
+use super::{
+    lang::SelectedLanguage,
+    main_window::{PolyName, Selected},
+};
 
-/// Represents the memory slots to store polytopes.
+/// Represents the memory slots to store polytopes. Slots holding the same
+/// polytope share a single copy of it.
 #[derive(Default)]
-pub struct Memory(pub Vec<Option<(Concrete, Option<String>)>>);
+pub struct Memory(pub Vec<Option<(Arc<Concrete>, Option<String>)>>);
 
 impl std::ops::Index<usize> for Memory {
-    type Output = Option<(Concrete, Option<String>)>;
+    type Output = Option<(Arc<Concrete>, Option<String>)>;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.0[index]
     }
 }
 
+/// A copy of a polytope entity that's shared with the memory slots, so that
+/// saving it into any number of them copies it at most once. It's dropped
+/// whenever the polytope changes in any other way than by loading it from
+/// memory.
+#[derive(Default)]
+pub struct SharedPolytope {
+    /// The shared copy of the polytope, if there is one.
+    poly: Option<Arc<Concrete>>,
+
+    /// Whether the polytope was just loaded from memory.
+    loaded: bool,
+}
+
+impl SharedPolytope {
+    /// Returns a handle to the shared copy of a polytope, copying it only if
+    /// there isn't one already.
+    pub fn get(&mut self, poly: &Concrete) -> Arc<Concrete> {
+        Arc::clone(self.poly.get_or_insert_with(|| Arc::new(poly.clone())))
+    }
+
+    /// Loads a polytope from memory, and shares it with the memory slot.
+    pub fn load(&mut self, target: &mut Concrete, poly: &Arc<Concrete>) {
+        *target = (**poly).clone();
+        self.poly = Some(Arc::clone(poly));
+        self.loaded = true;
+    }
+}
+
+/// Drops the shared copy of every polytope that changed in any way other than
+/// by loading it from memory.
+pub fn update_shared_polytopes(mut shared: Query<'_, '_, &mut SharedPolytope, Changed<Concrete>>) {
+    for mut shared in shared.iter_mut() {
+        if shared.loaded {
+            shared.loaded = false;
+        } else if shared.poly.is_some() {
+            shared.poly = None;
+        }
+    }
+}
+
 /// The label for the `n`-th memory slot.
 pub fn slot_label(n: usize) -> String {
     format!("polytope {}", n)
@@ -31,18 +78,28 @@ impl Memory {
     }
 
     /// Returns an iterator over the memory slots.
-    pub fn iter(&self) -> std::slice::Iter<'_, Option<(Concrete, Option<String>)>> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Option<(Arc<Concrete>, Option<String>)>> {
         self.0.iter()
     }
 
     /// Appends an element.
-    pub fn push(&mut self, a: (Concrete, Option<String>)) {
-        self.0.push(Some(a));
+    pub fn push(&mut self, (poly, label): (Concrete, Option<String>)) {
+        self.0.push(Some((Arc::new(poly), label)));
+    }
+
+    /// Returns the number of distinct copies of polytopes held in memory.
+    pub fn copy_count(&self) -> usize {
+        let mut ptrs: Vec<_> = self.iter().flatten().map(|(poly, _)| Arc::as_ptr(poly)).collect();
+        ptrs.sort_unstable();
+        ptrs.dedup();
+        ptrs.len()
     }
 
     /// Shows the memory menu in a specified Ui.
-    pub fn show(&mut self, query: &mut Query<'_, '_, &mut Concrete>, poly_name: &mut ResMut<'_, PolyName>, egui_ctx: &Res<'_, EguiContext>, open: &mut bool) {
-        egui::Window::new("Memory")
+    pub fn show(&mut self, query: &mut Query<'_, '_, &mut Concrete, With<Selected>>, shared: &mut Query<'_, '_, &mut SharedPolytope, With<Selected>>, poly_name: &mut ResMut<'_, PolyName>, egui_ctx: &Res<'_, EguiContext>, open: &mut bool, lang: SelectedLanguage) {
+        // The window is identified by the key of its name, as in `Window::egui_window`.
+        egui::Window::new(t!(lang, "menu.memory"))
+            .id(egui::Id::new("menu.memory"))
             .open(open)
             .scroll(true)
             .default_width(260.0)
@@ -50,14 +107,16 @@ impl Memory {
             egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
                 
                 ui.horizontal(|ui| {
-                    if ui.button("Clear memory").clicked() {
+                    if ui.button(t!(lang, "memory.clear")).clicked() {
                         self.0.clear();
                     }
         
-                    if ui.button("Add slot").clicked() {
+                    if ui.button(t!(lang, "memory.add_slot")).clicked() {
                         self.0.push(None);
                     }
                 });
+
+                ui.label(format!("{} {}", self.copy_count(), t!(lang, "memory.stored")));
     
                 ui.separator();
     
@@ -67,11 +126,11 @@ impl Memory {
                         None => {
                             ui.horizontal(|ui| {
                                 ui.label(format!("{}:", idx));
-                                ui.label("Empty");
+                                ui.label(t!(lang, "memory.empty"));
 
-                                if ui.button("Save").clicked() {
-                                    if let Some(p) = query.iter_mut().next() {
-                                        *slot = Some((p.clone(), Some(poly_name.0.clone())));
+                                if ui.button(t!(lang, "memory.save")).clicked() {
+                                    if let (Some(p), Some(mut shared)) = (query.iter_mut().next(), shared.iter_mut().next()) {
+                                        *slot = Some((shared.get(&p), Some(poly_name.0.clone())));
                                     }
                                 }
                              });
@@ -96,26 +155,31 @@ impl Memory {
                                 ui.label(&name);
 
                                 // Clones a polytope from memory.
-                                if ui.button("Load").clicked() {
-                                    *query.iter_mut().next().unwrap() = poly.clone();
-                                    poly_name.0 = name.clone();
+                                if ui.button(t!(lang, "memory.load")).clicked() {
+                                    if let (Some(mut p), Some(mut shared)) = (query.iter_mut().next(), shared.iter_mut().next()) {
+                                        shared.load(&mut p, poly);
+                                        poly_name.0 = name.clone();
+                                    }
                                 }
 
                                 // Swaps the current polytope with the one on memory.
-                                if ui.button("Swap").clicked() {
-                                    std::mem::swap(query.iter_mut().next().unwrap().as_mut(), poly);
+                                if ui.button(t!(lang, "memory.swap")).clicked() {
+                                    let mut p = query.iter_mut().next().unwrap();
+                                    std::mem::swap(p.as_mut(), make_mut(poly));
                                     *label = Some(poly_name.0.clone());
                                     poly_name.0 = name;
                                 }
 
                                 // Clones a polytope into memory.
-                                if ui.button("Save").clicked() {
-                                    *poly = query.iter_mut().next().unwrap().clone();
-                                    *label = Some(poly_name.0.clone());
+                                if ui.button(t!(lang, "memory.save")).clicked() {
+                                    if let (Some(p), Some(mut shared)) = (query.iter_mut().next(), shared.iter_mut().next()) {
+                                        *poly = shared.get(&p);
+                                        *label = Some(poly_name.0.clone());
+                                    }
                                 }
 
                                 // Clears a polytope from memory.
-                                if ui.button("Clear").clicked() {
+                                if ui.button(t!(lang, "window.clear")).clicked() {
                                     clear = true;
                                 }
                             });
@@ -130,3 +194,66 @@ impl Memory {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::prelude::*;
+    use miratope_core::{conc::ConcretePolytope, Polytope};
+
+    /// Slots that hold the same polytope only count it once.
+    #[test]
+    fn copy_count() {
+        let cube = Arc::new(Concrete::hypercube(4));
+        let mut memory = Memory(vec![Some((cube, None)); 5]);
+        memory.0.push(None);
+        assert_eq!(memory.copy_count(), 1);
+
+        memory.push((Concrete::hypercube(4), Some("cube".to_string())));
+        assert_eq!(memory.copy_count(), 2);
+
+        make_mut(&mut memory.0[0].as_mut().unwrap().0).scale_mut(2.0);
+        assert_eq!(memory.copy_count(), 3);
+    }
+
+    /// Saving a polytope into many slots copies it once, until it changes.
+    #[test]
+    fn shared_polytope() {
+        let mut app = App::new();
+        app.add_system(update_shared_polytopes.system());
+        let entity = app
+            .world
+            .spawn()
+            .insert(Concrete::hexacosichoron())
+            .insert(SharedPolytope::default())
+            .id();
+        app.update();
+
+        let save = |app: &mut App| {
+            let poly = app.world.get::<Concrete>(entity).unwrap().clone();
+            app.world.get_mut::<SharedPolytope>(entity).unwrap().get(&poly)
+        };
+
+        let mut memory = Memory::default();
+        for _ in 0..5 {
+            memory.0.push(Some((save(&mut app), None)));
+            app.update();
+        }
+        assert_eq!(memory.copy_count(), 1);
+
+        // Loading a polytope shares it with its slot.
+        let slot = Arc::clone(&memory[0].as_ref().unwrap().0);
+        let mut shared = app.world.entity_mut(entity).remove::<SharedPolytope>().unwrap();
+        shared.load(&mut app.world.get_mut::<Concrete>(entity).unwrap(), &slot);
+        app.world.entity_mut(entity).insert(shared);
+        app.update();
+        memory.0.push(Some((save(&mut app), None)));
+        assert_eq!(memory.copy_count(), 1);
+
+        // Changing the polytope drops the shared copy.
+        app.world.get_mut::<Concrete>(entity).unwrap().scale_mut(2.0);
+        app.update();
+        memory.0.push(Some((save(&mut app), None)));
+        assert_eq!(memory.copy_count(), 2);
+    }
+}
@@ -4,11 +4,26 @@ use crate::{Point, EPS};
 use approx::abs_diff_eq;
 use bevy_egui::egui::{self, Ui, Widget};
 
+// The translation macros must be declared before every module that uses them.
+#[macro_use]
+pub mod lang;
+pub mod appearance;
+pub mod axes;
 pub mod camera;
+pub mod command;
 pub mod config;
+pub mod custom;
+pub mod dialog;
+pub mod labels;
+pub mod lattice;
 pub mod library;
 pub mod main_window;
+pub mod measure;
 pub mod memory;
+pub mod palette;
+pub mod recent;
+pub mod replace;
+pub mod validation;
 pub mod window;
 pub mod top_panel;
 pub mod right_panel;
@@ -24,7 +39,17 @@ impl bevy::prelude::PluginGroup for MiratopePlugins {
             .add(window::WindowPlugin)
             .add(library::LibraryPlugin)
             .add(main_window::MainWindowPlugin)
+            .add(appearance::AppearancePlugin)
+            .add(measure::MeasurePlugin)
+            .add(axes::AxesPlugin)
+            .add(labels::LabelsPlugin)
+            .add(replace::ReplacePlugin)
+            .add(dialog::DialogPlugin)
+            .add(validation::ValidationPlugin)
+            .add(lattice::LatticePlugin)
             .add(top_panel::TopPanelPlugin)
+            .add(command::CommandPlugin)
+            .add(custom::CustomPlugin)
             .add(right_panel::RightPanelPlugin);
     }
 }
@@ -68,38 +93,89 @@ impl<'a> UnitPointWidget<'a> {
     }
 }
 
+/// Gets rid of floating point shenanigans, by snapping values close to 0 or
+/// ±1 to them.
+fn snap(coord: f64) -> f64 {
+    if abs_diff_eq!(coord, 0.0, epsilon = EPS.sqrt()) {
+        0.0
+    } else if abs_diff_eq!(coord, 1.0, epsilon = EPS) {
+        1.0
+    } else if abs_diff_eq!(coord, -1.0, epsilon = EPS) {
+        -1.0
+    } else {
+        coord
+    }
+}
+
+/// Sets a coordinate of a unit point to a given value, clamped between -1 and
+/// 1, and rescales the other coordinates proportionally so that the point
+/// keeps unit norm. This way, the coordinate being dragged tracks the cursor.
+///
+/// If the other coordinates are all zero, the remaining norm is spread evenly
+/// among them.
+pub fn set_unit_coord(point: &mut Point, idx: usize, value: f64) {
+    let value = snap(value.clamp(-1.0, 1.0));
+    let rest = 1.0 - value * value;
+
+    // A single coordinate can only be ±1.
+    if point.len() == 1 {
+        point[0] = if value < 0.0 { -1.0 } else { 1.0 };
+        return;
+    }
+
+    let others = point.norm_squared() - point[idx] * point[idx];
+    if others > EPS {
+        let scale = (rest / others).sqrt();
+        for coord in point.iter_mut() {
+            *coord = snap(*coord * scale);
+        }
+    } else {
+        let spread = (rest / (point.len() - 1) as f64).sqrt();
+        for coord in point.iter_mut() {
+            *coord = snap(spread);
+        }
+    }
+
+    point[idx] = value;
+}
+
+/// Normalizes a point in place. If it's too close to zero, sets it to the
+/// direction of the last coordinate axis instead.
+pub fn normalize_or_last_axis(point: &mut Point) {
+    if point.try_normalize_mut(EPS).is_none() {
+        let dim = point.len();
+        point.fill(0.0);
+        if dim > 0 {
+            point[dim - 1] = 1.0;
+        }
+    }
+}
+
 impl<'a> Widget for UnitPointWidget<'a> {
     fn ui(self, ui: &mut Ui) -> egui::Response {
         ui.horizontal(|ui| {
-            let old_point = self.0.point.clone();
-            let mut modified_coord = 0;
+            let point = self.0.point;
 
-            for (idx, coord) in self.0.point.iter_mut().enumerate() {
-                ui.add(egui::DragValue::new(coord).speed(0.01));
+            // Points set from elsewhere might not be normalized yet. We don't
+            // renormalize unit points, so that dragging doesn't fight it.
+            if !abs_diff_eq!(point.norm(), 1.0, epsilon = EPS) {
+                normalize_or_last_axis(point);
+            }
 
-                // The index of the modified coordinate.
-                if abs_diff_eq!(old_point[idx], *coord, epsilon = EPS) {
-                    modified_coord = idx;
-                }
+            // Only a single coordinate can be dragged at once.
+            let mut modified = None;
+            for (idx, coord) in point.iter().enumerate() {
+                let mut new_coord = *coord;
+                ui.add(egui::DragValue::new(&mut new_coord).speed(0.01));
 
-                // Gets rid of floating point shenanigans.
-                if abs_diff_eq!(*coord, 0.0, epsilon = EPS.sqrt()) {
-                    *coord = 0.0;
-                } else if abs_diff_eq!(*coord, 1.0, epsilon = EPS) {
-                    *coord = 1.0;
-                } else if abs_diff_eq!(*coord, -1.0, epsilon = EPS) {
-                    *coord = -1.0;
+                #[allow(clippy::float_cmp)]
+                if new_coord != *coord {
+                    modified = Some((idx, new_coord));
                 }
             }
 
-            // Normalizes the point.
-            if self.0.point.try_normalize_mut(EPS).is_none() {
-                // If this fails, sets it to the axis direction corresponding
-                // to the last modified coordinate.
-                for coord in self.0.point.iter_mut() {
-                    *coord = 0.0;
-                }
-                self.0.point[modified_coord] = 1.0;
+            if let Some((idx, value)) = modified {
+                set_unit_coord(point, idx, value);
             }
 
             ui.label(self.0.label);
@@ -107,3 +183,55 @@ impl<'a> Widget for UnitPointWidget<'a> {
         .response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that two points are equal.
+    fn assert_point(p: &Point, q: &[f64]) {
+        assert!(
+            p.iter().zip(q).all(|(x, y)| abs_diff_eq!(x, y, epsilon = EPS)),
+            "expected {:?}, found {:?}",
+            q,
+            p.as_slice()
+        );
+    }
+
+    /// Dragging a coordinate rescales the others, and keeps the dragged one.
+    #[test]
+    fn rescale() {
+        let mut point = Point::from_vec(vec![0.6, 0.8, 0.0]);
+        set_unit_coord(&mut point, 2, 0.6);
+        assert_point(&point, &[0.48, 0.64, 0.6]);
+
+        // Values are clamped.
+        set_unit_coord(&mut point, 0, -3.0);
+        assert_point(&point, &[-1.0, 0.0, 0.0]);
+    }
+
+    /// Dragging away from an axis spreads the rest evenly.
+    #[test]
+    fn spread() {
+        let mut point = Point::from_vec(vec![0.0, 0.0, 1.0]);
+        set_unit_coord(&mut point, 2, 0.6);
+        let rest = 0.32f64.sqrt();
+        assert_point(&point, &[rest, rest, 0.6]);
+
+        let mut point = Point::from_vec(vec![1.0]);
+        set_unit_coord(&mut point, 0, -0.5);
+        assert_point(&point, &[-1.0]);
+    }
+
+    /// Zero points are replaced by the last axis.
+    #[test]
+    fn normalize() {
+        let mut point = Point::from_vec(vec![3.0, 0.0, 4.0]);
+        normalize_or_last_axis(&mut point);
+        assert_point(&point, &[0.6, 0.0, 0.8]);
+
+        let mut point = Point::zeros(4);
+        normalize_or_last_axis(&mut point);
+        assert_point(&point, &[0.0, 0.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,393 @@
+//! The palettes that assign a color to every element type of a polytope, and
+//! the colors the user picked for individual types.
+//!
+//! Within each rank, types are numbered by their representatives, so that
+//! sorting the element types panel doesn't change their colors. When a rank
+//! has more types than the palette has colors, the palette is cycled through
+//! again, each time alternately lighter and darker than the last: the type
+//! with index `i` gets color `i % n` of the palette, shaded by `i / n`, as in
+//! [`shade`]. The rank gradient instead encodes the rank by the hue and the
+//! type by the lightness, so it never runs out of colors.
+
+use miratope_core::conc::element_types::TypeSignature;
+use serde::{Deserialize, Serialize};
+
+/// A color in sRGB, with alpha, and every component between 0 and 1.
+pub type Rgba = [f32; 4];
+
+/// Converts a hexadecimal sRGB color into an opaque [`Rgba`].
+const fn hex(rgb: u32) -> Rgba {
+    [
+        ((rgb >> 16) & 0xff) as f32 / 255.0,
+        ((rgb >> 8) & 0xff) as f32 / 255.0,
+        (rgb & 0xff) as f32 / 255.0,
+        1.0,
+    ]
+}
+
+/// The default palette, from Tableau 10.
+pub const CATEGORICAL: [Rgba; 10] = [
+    hex(0x4e79a7),
+    hex(0xf28e2b),
+    hex(0xe15759),
+    hex(0x76b7b2),
+    hex(0x59a14f),
+    hex(0xedc948),
+    hex(0xb07aa1),
+    hex(0xff9da7),
+    hex(0x9c755f),
+    hex(0xbab0ac),
+];
+
+/// The palette of Okabe and Ito, which stays distinguishable under the common
+/// forms of color blindness. Its black is replaced by a gray, which shows the
+/// shading of faces better.
+pub const OKABE_ITO: [Rgba; 8] = [
+    hex(0xe69f00),
+    hex(0x56b4e9),
+    hex(0x009e73),
+    hex(0xf0e442),
+    hex(0x0072b2),
+    hex(0xd55e00),
+    hex(0xcc79a7),
+    hex(0x999999),
+];
+
+/// A palette of grays, from light to dark.
+pub const GRAYSCALE: [Rgba; 5] = [
+    hex(0xe0e0e0),
+    hex(0xb4b4b4),
+    hex(0x888888),
+    hex(0x5c5c5c),
+    hex(0x303030),
+];
+
+/// Varies the lightness of a color for the `cycle`-th time a palette is
+/// cycled through. Odd cycles mix the color with white, and even ones with
+/// black, by an amount that grows every two cycles: 40% for the first two,
+/// 64% for the next two, and so on.
+pub fn shade(color: Rgba, cycle: usize) -> Rgba {
+    if cycle == 0 {
+        return color;
+    }
+
+    let amount = 1.0 - 0.6f32.powi(((cycle + 1) / 2) as i32);
+    let target = if cycle % 2 == 1 { 1.0 } else { 0.0 };
+    let [r, g, b, a] = color;
+    let mix = |c: f32| c + (target - c) * amount;
+    [mix(r), mix(g), mix(b), a]
+}
+
+/// Converts a color from HSL into sRGB, with every component between 0 and
+/// 1. The hue is measured in turns.
+fn hsl(h: f32, s: f32, l: f32) -> Rgba {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(1.0) * 6.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    let m = l - c / 2.0;
+    [r + m, g + m, b + m, 1.0]
+}
+
+/// A palette defined by the user.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserPalette {
+    /// The name of the palette.
+    pub name: String,
+
+    /// The colors of the palette.
+    pub colors: Vec<Rgba>,
+}
+
+/// The palette the element types are colored with.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PaletteChoice {
+    /// The default palette, [`CATEGORICAL`].
+    #[default]
+    Categorical,
+
+    /// The colorblind-safe palette [`OKABE_ITO`].
+    OkabeIto,
+
+    /// The palette [`GRAYSCALE`].
+    Grayscale,
+
+    /// The hue encodes the rank, and the lightness the type.
+    RankGradient,
+
+    /// The user palette with a given name.
+    User(String),
+}
+
+impl PaletteChoice {
+    /// The built-in palettes.
+    pub const BUILT_IN: [Self; 4] = [
+        Self::Categorical,
+        Self::OkabeIto,
+        Self::Grayscale,
+        Self::RankGradient,
+    ];
+
+    /// Returns the name of the palette.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Categorical => "Categorical",
+            Self::OkabeIto => "Okabe–Ito",
+            Self::Grayscale => "Grayscale",
+            Self::RankGradient => "Rank gradient",
+            Self::User(name) => name,
+        }
+    }
+}
+
+/// A color picked for the element types of a given rank with a given
+/// signature. These are matched by their signatures, so that they survive the
+/// element types being generated again.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypeOverride {
+    /// The rank of the elements, as in [`ElementTypesRes`](super::right_panel::ElementTypesRes).
+    pub rank: usize,
+
+    /// The signature of the type.
+    pub signature: TypeSignature,
+
+    /// The color of the type.
+    pub color: Rgba,
+}
+
+/// The colors of the element types, which are stored in the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    /// The palette in use.
+    #[serde(default)]
+    pub choice: PaletteChoice,
+
+    /// The palettes defined by the user.
+    #[serde(default)]
+    pub user: Vec<UserPalette>,
+
+    /// The colors picked for individual types, which take precedence over the
+    /// palette.
+    #[serde(default)]
+    pub overrides: Vec<TypeOverride>,
+}
+
+impl Palette {
+    /// Returns every palette that can be chosen, built-in ones first.
+    pub fn choices(&self) -> Vec<PaletteChoice> {
+        let user = self
+            .user
+            .iter()
+            .map(|palette| PaletteChoice::User(palette.name.clone()));
+        PaletteChoice::BUILT_IN.iter().cloned().chain(user).collect()
+    }
+
+    /// Returns the colors of the palette in use, or `None` for the rank
+    /// gradient. Missing or empty user palettes fall back to the default one.
+    pub fn colors(&self) -> Option<&[Rgba]> {
+        Some(match &self.choice {
+            PaletteChoice::Categorical => &CATEGORICAL,
+            PaletteChoice::OkabeIto => &OKABE_ITO,
+            PaletteChoice::Grayscale => &GRAYSCALE,
+            PaletteChoice::RankGradient => return None,
+            PaletteChoice::User(name) => match self.user.iter().find(|p| &p.name == name) {
+                Some(palette) if !palette.colors.is_empty() => &palette.colors,
+                _ => &CATEGORICAL,
+            },
+        })
+    }
+
+    /// Returns the color the palette gives to the type with index `idx` out of
+    /// `count` types of a given rank, out of `rank_count` ranks. Overrides
+    /// aren't taken into account.
+    pub fn type_color(&self, rank: usize, idx: usize, count: usize, rank_count: usize) -> Rgba {
+        match self.colors() {
+            Some(colors) => shade(colors[idx % colors.len()], idx / colors.len()),
+            None => {
+                let hue = 0.8 * rank as f32 / rank_count.max(1) as f32;
+                let lightness = if count <= 1 {
+                    0.55
+                } else {
+                    0.35 + 0.4 * idx as f32 / (count - 1) as f32
+                };
+                hsl(hue, 0.65, lightness)
+            }
+        }
+    }
+
+    /// Returns the color picked for the types of a given rank and signature,
+    /// if any.
+    pub fn override_color(&self, rank: usize, signature: TypeSignature) -> Option<Rgba> {
+        self.overrides
+            .iter()
+            .find(|o| o.rank == rank && o.signature == signature)
+            .map(|o| o.color)
+    }
+
+    /// Picks the color for the types of a given rank and signature.
+    pub fn set_override(&mut self, rank: usize, signature: TypeSignature, color: Rgba) {
+        self.clear_override(rank, signature);
+        self.overrides.push(TypeOverride {
+            rank,
+            signature,
+            color,
+        });
+    }
+
+    /// Forgets the color picked for the types of a given rank and signature.
+    pub fn clear_override(&mut self, rank: usize, signature: TypeSignature) {
+        self.overrides
+            .retain(|o| o.rank != rank || o.signature != signature);
+    }
+
+    /// Returns the color of every element type, given the signatures of the
+    /// types of each rank, ordered by their representatives.
+    pub fn type_colors(&self, types: &[Vec<TypeSignature>]) -> Vec<Vec<Rgba>> {
+        types
+            .iter()
+            .enumerate()
+            .map(|(rank, signatures)| {
+                signatures
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, &signature)| {
+                        self.override_color(rank, signature).unwrap_or_else(|| {
+                            self.type_color(rank, idx, signatures.len(), types.len())
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Concrete;
+    use miratope_core::Polytope;
+
+    /// Returns the signature of a type with some number of facets, which
+    /// appears a single time.
+    fn signature(facets: usize) -> TypeSignature {
+        TypeSignature { facets, count: 1 }
+    }
+
+    /// Returns the signatures of the element types of a polytope, ordered by
+    /// their representatives.
+    fn signatures(poly: &Concrete) -> Vec<Vec<TypeSignature>> {
+        poly.element_types()
+            .into_iter()
+            .enumerate()
+            .map(|(rank, types)| {
+                let mut types: Vec<_> = types
+                    .into_iter()
+                    .map(|t| (t.example, poly.type_signature(rank, t)))
+                    .collect();
+                types.sort_by_key(|&(example, _)| example);
+                types.into_iter().map(|(_, signature)| signature).collect()
+            })
+            .collect()
+    }
+
+    /// Returns the lightness of a color.
+    fn lightness(color: Rgba) -> f32 {
+        color[..3].iter().sum()
+    }
+
+    /// Types past the palette get its colors again, lighter and darker each
+    /// time, and always in the same way.
+    #[test]
+    fn overflow() {
+        let palette = Palette::default();
+        let types = vec![(0..40).map(signature).collect::<Vec<_>>()];
+        let colors = palette.type_colors(&types);
+        assert_eq!(colors, palette.type_colors(&types));
+
+        let colors = &colors[0];
+        assert_eq!(colors[..10], CATEGORICAL);
+        for i in 0..10 {
+            assert!(lightness(colors[i + 10]) > lightness(colors[i]));
+            assert!(lightness(colors[i + 20]) < lightness(colors[i]));
+            assert!(lightness(colors[i + 30]) > lightness(colors[i + 10]));
+        }
+
+        for (i, a) in colors.iter().enumerate() {
+            assert!(a.iter().all(|c| (0.0..=1.0).contains(c)));
+            assert!(colors[..i].iter().all(|b| a != b));
+        }
+    }
+
+    /// The rank gradient tells ranks apart by hue and types by lightness.
+    #[test]
+    fn rank_gradient() {
+        let palette = Palette {
+            choice: PaletteChoice::RankGradient,
+            ..Default::default()
+        };
+        let types = vec![vec![signature(0)], (3..6).map(signature).collect()];
+        let colors = palette.type_colors(&types);
+
+        assert_ne!(colors[0][0], colors[1][0]);
+        assert!(lightness(colors[1][0]) < lightness(colors[1][1]));
+        assert!(lightness(colors[1][1]) < lightness(colors[1][2]));
+    }
+
+    /// User palettes are used by name, and fall back to the default one if
+    /// they're missing or empty.
+    #[test]
+    fn user() {
+        let red = [1.0, 0.0, 0.0, 0.5];
+        let mut palette = Palette {
+            choice: PaletteChoice::User("red".to_string()),
+            user: vec![UserPalette {
+                name: "red".to_string(),
+                colors: vec![red],
+            }],
+            overrides: Vec::new(),
+        };
+        assert_eq!(palette.type_color(1, 0, 2, 3), red);
+        assert_eq!(palette.type_color(1, 1, 2, 3), shade(red, 1));
+        assert_eq!(palette.choices().len(), 5);
+
+        palette.user[0].colors.clear();
+        assert_eq!(palette.colors(), Some(&CATEGORICAL[..]));
+        palette.choice = PaletteChoice::User("missing".to_string());
+        assert_eq!(palette.colors(), Some(&CATEGORICAL[..]));
+    }
+
+    /// A color picked for a type applies to it once its types are generated
+    /// again, also after the palette is saved and loaded, but not to types of
+    /// other ranks.
+    #[test]
+    fn overrides() {
+        let mut palette = Palette::default();
+        let cube = Concrete::cube();
+        let square = cube.type_signature(3, cube.element_types()[3][0]);
+        let green = [0.0, 1.0, 0.0, 1.0];
+        palette.set_override(3, square, green);
+        palette.set_override(3, square, green);
+        assert_eq!(palette.overrides.len(), 1);
+
+        let palette: Palette = ron::from_str(&ron::to_string(&palette).unwrap()).unwrap();
+        let colors = palette.type_colors(&signatures(&Concrete::cube()));
+        assert_eq!(colors[3], vec![green]);
+        assert_ne!(colors[2][0], green);
+
+        // The faces of the octahedron are different.
+        let colors = palette.type_colors(&signatures(&Concrete::orthoplex(4)));
+        assert_eq!(colors[3], vec![CATEGORICAL[0]]);
+
+        let mut palette = palette;
+        palette.clear_override(3, square);
+        assert_eq!(palette.type_colors(&signatures(&Concrete::cube()))[3], vec![CATEGORICAL[0]]);
+    }
+}
@@ -0,0 +1,262 @@
+//! The files that were recently opened, which are listed in the file menu so
+//! that they can be opened again. The list is kept in the configuration file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::lang::SelectedLanguage;
+
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+/// The most files that are remembered, not counting pinned ones.
+pub const MAX_RECENT: usize = 10;
+
+/// A file that was recently opened.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RecentFile {
+    /// The path to the file.
+    pub path: PathBuf,
+
+    /// The name of the polytope in the file, as it was when it was opened.
+    pub name: String,
+
+    /// Whether the file is kept in the list regardless of how many others
+    /// are opened after it.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl RecentFile {
+    /// Returns the folder the file is in, as a hint for telling apart files
+    /// with the same name.
+    pub fn folder(&self) -> String {
+        self.path
+            .parent()
+            .map(|parent| parent.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// The files that were recently opened, from most to least recent.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct RecentFiles(pub Vec<RecentFile>);
+
+/// What the user did with the list of recent files.
+enum RecentAction {
+    /// Open the file with a given index.
+    Open(usize),
+
+    /// Pin or unpin the file with a given index.
+    TogglePin(usize),
+
+    /// Forget the file with a given index.
+    Remove(usize),
+
+    /// Forget every file that no longer exists.
+    RemoveMissing,
+}
+
+impl RecentFiles {
+    /// Records that a file was opened, moving it to the front of the list if
+    /// it was already in it. The oldest unpinned files are then forgotten, so
+    /// that at most [`MAX_RECENT`] of them remain.
+    pub fn push(&mut self, path: &Path, name: String) {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        let pinned = match self.0.iter().position(|file| file.path == path) {
+            Some(idx) => self.0.remove(idx).pinned,
+            None => false,
+        };
+
+        self.0.insert(0, RecentFile { path, name, pinned });
+
+        let mut unpinned = 0;
+        self.0.retain(|file| {
+            if !file.pinned {
+                unpinned += 1;
+            }
+            file.pinned || unpinned <= MAX_RECENT
+        });
+    }
+
+    /// Pins or unpins the file with a given index. A file that's unpinned
+    /// counts as recent again, so it can push out the oldest unpinned file.
+    pub fn toggle_pin(&mut self, idx: usize) {
+        if let Some(file) = self.0.get_mut(idx) {
+            file.pinned = !file.pinned;
+            if !file.pinned {
+                let file = self.0.remove(idx);
+                self.push(&file.path, file.name);
+            }
+        }
+    }
+
+    /// Forgets the file with a given index.
+    pub fn remove(&mut self, idx: usize) {
+        if idx < self.0.len() {
+            self.0.remove(idx);
+        }
+    }
+
+    /// Forgets every file that no longer exists, pinned or not. Returns how
+    /// many were forgotten.
+    pub fn prune_missing(&mut self) -> usize {
+        let len = self.0.len();
+        self.0.retain(|file| file.path.exists());
+        len - self.0.len()
+    }
+
+    /// Shows the list of recent files, with the pinned ones first. Files that
+    /// no longer exist are greyed out, and can be removed from the list.
+    /// Returns the path of the file to open, if any.
+    pub fn show(&mut self, ui: &mut egui::Ui, lang: SelectedLanguage) -> Option<PathBuf> {
+        if self.0.is_empty() {
+            ui.label(t!(lang, "file.no_recent"));
+            return None;
+        }
+
+        let order = (0..self.0.len())
+            .filter(|&idx| self.0[idx].pinned)
+            .chain((0..self.0.len()).filter(|&idx| !self.0[idx].pinned));
+        let mut action = None;
+        let mut any_missing = false;
+
+        for idx in order {
+            let file = &self.0[idx];
+            let exists = file.path.exists();
+            any_missing |= !exists;
+
+            ui.horizontal(|ui| {
+                if ui
+                    .selectable_label(file.pinned, t!(lang, "file.pin"))
+                    .clicked()
+                {
+                    action = Some(RecentAction::TogglePin(idx));
+                }
+
+                let button = ui.add(egui::Button::new(&file.name).enabled(exists));
+                if exists {
+                    if button.on_hover_text(file.folder()).clicked() {
+                        action = Some(RecentAction::Open(idx));
+                    }
+                } else {
+                    button.on_hover_text(t!(lang, "file.not_found"));
+                    if ui.small_button(t!(lang, "file.remove")).clicked() {
+                        action = Some(RecentAction::Remove(idx));
+                    }
+                }
+            });
+        }
+
+        if any_missing && ui.button(t!(lang, "file.remove_missing")).clicked() {
+            action = Some(RecentAction::RemoveMissing);
+        }
+
+        match action? {
+            RecentAction::Open(idx) => return Some(self.0[idx].path.clone()),
+            RecentAction::TogglePin(idx) => self.toggle_pin(idx),
+            RecentAction::Remove(idx) => self.remove(idx),
+            RecentAction::RemoveMissing => {
+                self.prune_missing();
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the names of the files in the list, in order.
+    fn names(recent: &RecentFiles) -> Vec<&str> {
+        recent.0.iter().map(|file| file.name.as_str()).collect()
+    }
+
+    /// Opens a file with a given name, which doesn't need to exist.
+    fn open(recent: &mut RecentFiles, name: &str) {
+        recent.push(
+            Path::new(&format!("missing/{}.off", name)),
+            name.to_string(),
+        );
+    }
+
+    /// Opening a file again moves it to the front, without duplicating it.
+    #[test]
+    fn dedup() {
+        let mut recent = RecentFiles::default();
+        open(&mut recent, "cube");
+        open(&mut recent, "tet");
+        open(&mut recent, "cube");
+        assert_eq!(names(&recent), vec!["cube", "tet"]);
+
+        // The name is updated to the one the file has now.
+        recent.push(Path::new("missing/tet.off"), "tetrahedron".to_string());
+        assert_eq!(names(&recent), vec!["tetrahedron", "cube"]);
+    }
+
+    /// Only the most recent files are remembered.
+    #[test]
+    fn aging() {
+        let mut recent = RecentFiles::default();
+        for i in 0..MAX_RECENT + 3 {
+            open(&mut recent, &i.to_string());
+        }
+
+        assert_eq!(recent.0.len(), MAX_RECENT);
+        assert_eq!(recent.0[0].name, (MAX_RECENT + 2).to_string());
+        assert_eq!(recent.0[MAX_RECENT - 1].name, "3");
+    }
+
+    /// Pinned files never age out, and don't count towards the limit.
+    #[test]
+    fn pin() {
+        let mut recent = RecentFiles::default();
+        open(&mut recent, "pinned");
+        recent.toggle_pin(0);
+        for i in 0..2 * MAX_RECENT {
+            open(&mut recent, &i.to_string());
+        }
+
+        assert_eq!(recent.0.len(), MAX_RECENT + 1);
+        assert_eq!(recent.0.last().unwrap().name, "pinned");
+        assert!(recent.0.last().unwrap().pinned);
+
+        // Opening a pinned file keeps it pinned.
+        open(&mut recent, "pinned");
+        assert_eq!(recent.0[0].name, "pinned");
+        assert!(recent.0[0].pinned);
+        assert_eq!(recent.0.len(), MAX_RECENT + 1);
+
+        // Unpinning it makes it the most recent file, and pushes out the
+        // oldest one.
+        recent.toggle_pin(0);
+        assert_eq!(recent.0.len(), MAX_RECENT);
+        assert_eq!(recent.0[0].name, "pinned");
+        assert!(!recent.0[0].pinned);
+    }
+
+    /// Files that no longer exist can be forgotten, pinned or not.
+    #[test]
+    fn prune_missing() {
+        let path = std::env::temp_dir().join(format!("miratope-recent-{}.off", std::process::id()));
+        fs::write(&path, "").unwrap();
+
+        let mut recent = RecentFiles::default();
+        open(&mut recent, "missing");
+        recent.toggle_pin(0);
+        recent.push(&path, "existing".to_string());
+        open(&mut recent, "gone");
+
+        assert_eq!(recent.prune_missing(), 2);
+        assert_eq!(names(&recent), vec!["existing"]);
+        assert_eq!(recent.0[0].path, fs::canonicalize(&path).unwrap());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(recent.prune_missing(), 1);
+        assert!(recent.0.is_empty());
+    }
+}
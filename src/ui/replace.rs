@@ -0,0 +1,305 @@
+//! Replaces the selected polytope by a loaded one as a single transaction. A
+//! polytope is loaded and validated in full before anything else is touched,
+//! so that a load that fails halfway leaves everything as it was. Only then is
+//! it swapped in, and the state that depended on the old polytope reset.
+
+use std::path::{Path, PathBuf};
+
+use super::{
+    dialog::CurrentFile,
+    main_window::{DualToggle, ElementHighlight, PolyName, Selected},
+    measure::Measurement,
+    recent::RecentFiles,
+    right_panel::ElementTypesRes,
+    top_panel::SectionState,
+};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::file::{
+    types::{sidecar_path, TypeTable},
+    FromFile,
+};
+
+/// The plugin in charge of replacing the selected polytope.
+pub struct ReplacePlugin;
+
+impl Plugin for ReplacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingReplacement>()
+            .add_event::<PolytopeReplaced>()
+            .add_system(
+                replace_polytope
+                    .system()
+                    .label("replace_polytope")
+                    .after("show_top_panel"),
+            );
+    }
+}
+
+/// Sent once whenever the selected polytope is replaced by a loaded one, so
+/// that the camera can frame it. Operations on the current polytope don't send
+/// it, so that they never undo the user's own camera adjustments.
+pub struct PolytopeReplaced;
+
+/// A polytope that has been loaded and validated in full, along with
+/// everything that was loaded with it.
+pub struct Replacement {
+    /// The loaded polytope.
+    pub poly: Concrete,
+
+    /// The name of the loaded polytope.
+    pub name: String,
+
+    /// The element types saved along with the polytope, if any.
+    pub types: Option<TypeTable>,
+
+    /// The file the polytope was loaded from, if any.
+    pub path: Option<PathBuf>,
+}
+
+impl Replacement {
+    /// Validates a polytope that's to replace the selected one.
+    pub fn new(poly: Concrete, name: String) -> Result<Self, String> {
+        poly.validate().map_err(|err| err.to_string())?;
+
+        Ok(Self {
+            poly,
+            name,
+            types: None,
+            path: None,
+        })
+    }
+
+    /// Loads and validates a polytope from a file, along with the element
+    /// types saved next to it.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        let poly = Concrete::from_path(&path).map_err(|err| err.to_string())?;
+        let types = poly.import_types(&sidecar_path(path));
+
+        let mut replacement = Self::new(poly, PolyName::from_path(path).0)?;
+        replacement.types = types;
+        replacement.path = Some(path.to_owned());
+        Ok(replacement)
+    }
+}
+
+/// The polytope that will replace the selected one, if any. If several are
+/// loaded in the same frame, only the last one is kept.
+#[derive(Default)]
+pub struct PendingReplacement(pub Option<Replacement>);
+
+impl PendingReplacement {
+    /// Queues a polytope to replace the selected one.
+    pub fn set(&mut self, replacement: Replacement) {
+        self.0 = Some(replacement);
+    }
+}
+
+/// Swaps the pending polytope into the selected entity, and resets everything
+/// that referred to the old one: the cross-section view, the dual cache, and the
+/// highlighted and measured elements. The element types are taken from the ones
+/// loaded with the polytope, if any, and otherwise go stale like on any change.
+/// Polytopes loaded from files are added to the recent files, and become the
+/// current file of the selected polytope. Any other polytope leaves it with no
+/// current file.
+#[allow(clippy::too_many_arguments)]
+pub fn replace_polytope(
+    mut pending: ResMut<'_, PendingReplacement>,
+    mut query: Query<
+        '_,
+        '_,
+        (&mut Concrete, Option<&mut DualToggle>, Option<&mut CurrentFile>),
+        With<Selected>,
+    >,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut section_state: ResMut<'_, SectionState>,
+    mut element_types: ResMut<'_, ElementTypesRes>,
+    mut element_highlight: ResMut<'_, ElementHighlight>,
+    mut measurement: ResMut<'_, Measurement>,
+    mut recent_files: ResMut<'_, RecentFiles>,
+    mut replaced: EventWriter<'_, '_, PolytopeReplaced>,
+) {
+    // Avoids flagging the resource as changed every frame.
+    if pending.0.is_none() {
+        return;
+    }
+
+    let (mut p, dual_toggle, current_file) = match query.iter_mut().next() {
+        Some(selected) => selected,
+        None => return,
+    };
+    let Replacement {
+        poly,
+        name,
+        types,
+        path,
+    } = pending.0.take().unwrap();
+
+    *p = poly;
+    if let Some(mut dual_toggle) = dual_toggle {
+        *dual_toggle = DualToggle::default();
+    }
+
+    section_state.close();
+    if let Some(table) = types {
+        *element_types = element_types.from_poly_with_types(
+            &p,
+            name.clone(),
+            table.element_types(),
+            table.types,
+        );
+    }
+    element_highlight.element = None;
+    measurement.clear();
+
+    if let Some(path) = &path {
+        recent_files.push(path, name.clone());
+    }
+    if let Some(mut current_file) = current_file {
+        current_file.0 = path;
+    }
+    poly_name.0 = name;
+    replaced.send(PolytopeReplaced);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::{abs::Ranked, Polytope};
+
+    /// Builds an app that only replaces polytopes, with a selected cube whose
+    /// cross-section view is open and which has a highlighted element.
+    fn replace_app() -> App {
+        let mut app = App::new();
+        app.add_event::<PolytopeReplaced>()
+            .insert_resource(PolyName("cube".to_string()))
+            .init_resource::<PendingReplacement>()
+            .init_resource::<SectionState>()
+            .init_resource::<ElementTypesRes>()
+            .init_resource::<ElementHighlight>()
+            .init_resource::<Measurement>()
+            .init_resource::<RecentFiles>()
+            .add_system(replace_polytope.system());
+
+        let cube = Concrete::hypercube(4);
+        app.world
+            .get_resource_mut::<SectionState>()
+            .unwrap()
+            .open(cube.clone(), "cube".to_string(), vec![(-1.0, 1.0)]);
+        app.world.get_resource_mut::<ElementHighlight>().unwrap().element = Some((2, 0));
+        app.world.get_resource_mut::<Measurement>().unwrap().pick(0);
+
+        app.world
+            .spawn()
+            .insert(cube)
+            .insert(DualToggle::default())
+            .insert(CurrentFile::default())
+            .insert(Selected);
+        app
+    }
+
+    /// Returns the selected polytope.
+    fn selected(app: &mut App) -> Concrete {
+        app.world
+            .query_filtered::<&Concrete, With<Selected>>()
+            .iter(&app.world)
+            .next()
+            .unwrap()
+            .clone()
+    }
+
+    /// Returns the current file of the selected polytope.
+    fn current_file(app: &mut App) -> Option<PathBuf> {
+        app.world
+            .query_filtered::<&CurrentFile, With<Selected>>()
+            .iter(&app.world)
+            .next()
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    /// Returns whether the cross-section view is open, whether there's a
+    /// highlighted element, and whether there are picked vertices.
+    fn dependent_state(app: &App) -> (bool, bool, bool) {
+        (
+            matches!(
+                *app.world.get_resource::<SectionState>().unwrap(),
+                SectionState::Active { .. }
+            ),
+            app.world.get_resource::<ElementHighlight>().unwrap().element.is_some(),
+            !app.world.get_resource::<Measurement>().unwrap().picked.is_empty(),
+        )
+    }
+
+    /// A failing load changes nothing, and a successful one after it replaces
+    /// the polytope and resets everything that depended on it, exactly once.
+    #[test]
+    fn failed_then_successful() {
+        let mut app = replace_app();
+        let mut reader = app
+            .world
+            .get_resource::<Events<PolytopeReplaced>>()
+            .unwrap()
+            .get_reader();
+
+        // A missing file, and a polytope with a NaN coordinate.
+        assert!(Replacement::from_path(Path::new("missing.off")).is_err());
+        let mut nan = Concrete::polygon(5);
+        nan.vertices[2][0] = f64::NAN;
+        assert!(Replacement::new(nan, "pentagon".to_string()).is_err());
+        app.update();
+
+        assert_eq!(selected(&mut app).el_count_iter().collect::<Vec<_>>(), vec![1, 8, 12, 6, 1]);
+        assert_eq!(app.world.get_resource::<PolyName>().unwrap().0, "cube");
+        assert_eq!(dependent_state(&app), (true, true, true));
+        let events = app.world.get_resource::<Events<PolytopeReplaced>>().unwrap();
+        assert_eq!(reader.iter(events).count(), 0);
+
+        let pentagon = Replacement::new(Concrete::polygon(5), "pentagon".to_string()).unwrap();
+        app.world.get_resource_mut::<PendingReplacement>().unwrap().set(pentagon);
+        app.update();
+        app.update();
+
+        assert_eq!(selected(&mut app).el_count_iter().collect::<Vec<_>>(), vec![1, 5, 5, 1]);
+        assert_eq!(app.world.get_resource::<PolyName>().unwrap().0, "pentagon");
+        assert_eq!(dependent_state(&app), (false, false, false));
+        assert!(app.world.get_resource::<PendingReplacement>().unwrap().0.is_none());
+        let events = app.world.get_resource::<Events<PolytopeReplaced>>().unwrap();
+        assert_eq!(reader.iter(events).count(), 1);
+    }
+
+    /// Only polytopes loaded from files, once they're swapped in, are added
+    /// to the recent files and become the current file.
+    #[test]
+    fn recent_files() {
+        let mut app = replace_app();
+        let path = std::env::temp_dir().join(format!("miratope-replace-{}.off", std::process::id()));
+        Concrete::polygon(5)
+            .save_to_path(&path, &Default::default())
+            .unwrap();
+
+        let pentagon = Replacement::new(Concrete::polygon(5), "pentagon".to_string()).unwrap();
+        app.world.get_resource_mut::<PendingReplacement>().unwrap().set(pentagon);
+        app.update();
+        assert!(app.world.get_resource::<RecentFiles>().unwrap().0.is_empty());
+
+        let loaded = Replacement::from_path(&path).unwrap();
+        assert!(app.world.get_resource::<RecentFiles>().unwrap().0.is_empty());
+        app.world.get_resource_mut::<PendingReplacement>().unwrap().set(loaded);
+        app.update();
+
+        let recent = &app.world.get_resource::<RecentFiles>().unwrap().0;
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].path, std::fs::canonicalize(&path).unwrap());
+        assert_eq!(recent[0].name, PolyName::from_path(&path).0);
+        assert_eq!(current_file(&mut app).as_deref(), Some(path.as_path()));
+
+        let pentagon = Replacement::new(Concrete::polygon(5), "pentagon".to_string()).unwrap();
+        app.world.get_resource_mut::<PendingReplacement>().unwrap().set(pentagon);
+        app.update();
+        assert!(current_file(&mut app).is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 //! Contains all code related to the right side panel.
 
+use std::{borrow::Cow, cmp::Ordering, collections::HashSet};
+
 use crate::Concrete;
 
 use bevy::prelude::*;
@@ -7,15 +9,54 @@ use bevy_egui::{
     egui,
     EguiContext,
 };
-use miratope_core::{conc::{element_types::{EL_NAMES, EL_SUFFIXES}, ConcretePolytope}, Polytope, abs::Ranked, geometry::{Subspace, Point, Vector}};
+use miratope_core::{conc::{element_types::{self, ElementType, TypeSignature, EL_SUFFIXES}, ConcretePolytope}, Polytope, abs::Ranked, geometry::{Subspace, Point, Vector}};
 use vec_like::VecLike;
 
-use super::{top_panel::{SectionDirection, SectionState}, main_window::PolyName};
+use super::{top_panel::{SectionDirection, SectionState}, main_window::{FaceFilter, PolyName, Selected}};
+
+/// The largest number of elements of a polytope whose element types are
+/// regenerated automatically whenever it changes.
+pub const AUTO_REFRESH_LIMIT: usize = 20_000;
+
+/// A column of the element type table, by which its rows can be sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeColumn {
+    /// The number of elements of the type.
+    Count,
+
+    /// The number of facets of the element.
+    Facets,
+
+    /// The number of facets of the element figure.
+    FigFacets,
+
+    /// The norm, length or circumradius of the element.
+    Measure,
+}
+
+impl TypeColumn {
+    /// All of the columns, in the order they're shown.
+    pub const ALL: [Self; 4] = [Self::Count, Self::Facets, Self::FigFacets, Self::Measure];
+
+    /// The header of the column.
+    pub fn header(self, r: usize) -> &'static str {
+        match self {
+            Self::Count => "Count",
+            Self::Facets => "Element",
+            Self::FigFacets => "Figure",
+            Self::Measure => match r {
+                1 => "Norm",
+                2 => "Length",
+                _ => "Radius",
+            },
+        }
+    }
+}
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct ElementTypeWithData {
     /// The index of the representative for this element type.
-    example: usize,
+    pub example: usize,
 
     /// The number of elements of this type.
     pub count: usize,
@@ -23,11 +64,62 @@ pub struct ElementTypeWithData {
     /// The number of facets.
     facets: usize,
 
+    /// The key by which this type is matched to the types of the polytope
+    /// after it changes.
+    pub signature: TypeSignature,
+
     /// The number of facets of the figure.
     fig_facets: usize,
 
-    /// The circumradius of the element, or distance from the origin if it's a vertex.
-    radius: Option<f64>,
+    /// The norm of the vertex, the length of the edge, or the circumradius of
+    /// any other element.
+    measure: Option<f64>,
+
+    /// The name of the element, such as `5-gon`.
+    pub name: String,
+
+    /// The name of the element figure.
+    fig_name: String,
+
+    /// The measure, formatted for display.
+    measure_text: String,
+}
+
+impl ElementTypeWithData {
+    /// Compares two element types by the value in a given column. Elements
+    /// without a measure go last.
+    fn cmp_by(&self, other: &Self, column: TypeColumn) -> Ordering {
+        match column {
+            TypeColumn::Count => self.count.cmp(&other.count),
+            TypeColumn::Facets => self.facets.cmp(&other.facets),
+            TypeColumn::FigFacets => self.fig_facets.cmp(&other.fig_facets),
+            TypeColumn::Measure => match (self.measure, other.measure) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+    }
+
+    /// Returns whether the element or its figure matches a filter.
+    fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || self.name.to_lowercase().contains(&filter)
+            || self.fig_name.to_lowercase().contains(&filter)
+    }
+}
+
+/// Returns the name of an element of a given rank with a given number of
+/// facets, such as `5-gon`.
+fn element_name(facets: usize, r: usize) -> String {
+    format!("{}-{}", facets, EL_SUFFIXES.get(r).copied().unwrap_or(""))
+}
+
+/// Returns the name of the elements of a given rank, such as `Faces`.
+fn rank_name(r: usize) -> Cow<'static, str> {
+    element_types::element_name(r as isize - 1)
 }
 
 #[derive(Clone)]
@@ -48,14 +140,41 @@ pub struct ElementTypesRes {
     /// The components.
     components: Option<Vec<Concrete>>,
 
-    /// Whether the loaded polytope matches `poly` and the buttons should be greyed out.
-    pub main: bool,
+    /// The fingerprint of the polytope the types were generated from.
+    fingerprint: u64,
 
-    /// Whether we're updating `main`.
-    pub main_updating: bool,
+    /// Whether the selected polytope has changed into one that the types don't
+    /// describe. The table is then greyed out.
+    pub stale: bool,
+
+    /// Whether the types are regenerated as soon as they go stale, as long as
+    /// the polytope has at most [`AUTO_REFRESH_LIMIT`] elements.
+    pub auto_refresh: bool,
 
     /// Whether we want to defiss the components when generating them.
     pub defiss: bool,
+
+    /// The column the tables are sorted by, and whether they're sorted in
+    /// descending order.
+    sort: Option<(TypeColumn, bool)>,
+
+    /// Only the element types whose names contain this text are shown.
+    pub filter: String,
+
+    /// The rank and the representative of the selected element type. These
+    /// don't change when the rows are sorted.
+    selected: Option<(usize, usize)>,
+
+    /// Whether the table scrolls to the selected row the next time it's shown,
+    /// after the row was matched to one of the regenerated types.
+    scroll_to_selected: bool,
+
+    /// The representative of the type of every face, or an empty list if
+    /// these aren't known.
+    face_types: Vec<usize>,
+
+    /// The representatives of the face types that are left out of the mesh.
+    hidden_face_types: HashSet<usize>,
 }
 
 impl Default for ElementTypesRes {
@@ -66,68 +185,171 @@ impl Default for ElementTypesRes {
             poly_name: "nullitope".to_string(),
             types: Vec::new(),
             components: None,
-            main: true,
-            main_updating: false,
+            fingerprint: 0,
+            stale: true,
+            auto_refresh: false,
             defiss: false,
+            sort: None,
+            filter: String::new(),
+            selected: None,
+            scroll_to_selected: false,
+            face_types: Vec::new(),
+            hidden_face_types: HashSet::new(),
         }
     }
 }
 
 impl ElementTypesRes {
-    fn from_poly(&self, poly: Mut<'_, Concrete>, poly_name: String) -> ElementTypesRes {
+    fn from_poly(&self, poly: &Concrete, poly_name: String) -> ElementTypesRes {
+        let (plain_types, types_of_elements) = poly.element_types_common();
+        self.from_poly_with_types(poly, poly_name, plain_types, types_of_elements.into_inner())
+    }
+
+    /// Builds the panel data from a polytope and its already known element
+    /// types, such as those imported from a sidecar file, together with the
+    /// index of the type of every element.
+    pub fn from_poly_with_types(
+        &self,
+        poly: &Concrete,
+        poly_name: String,
+        plain_types: Vec<Vec<ElementType>>,
+        types_of_elements: Vec<Vec<usize>>,
+    ) -> ElementTypesRes {
+        let fingerprint = poly.fingerprint();
         let mut poly = poly.clone();
         poly.element_sort();
 
-        let plain_types = poly.element_types();
+        // Face types are told apart by their representatives, which don't
+        // change when the rows are sorted.
+        let face_types = match (plain_types.get(3), types_of_elements.get(3)) {
+            (Some(types), Some(face_types)) => face_types
+                .iter()
+                .map(|&t| types.get(t).map(|t| t.example))
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
         let mut types_with_data = Vec::new();
-    
-        for (r, types) in plain_types.clone().into_iter().enumerate() {
+
+        for (r, types) in plain_types.into_iter().enumerate() {
             let rank = poly.rank();
             if r == rank {
                 break;
             }
-    
+
             let abs = &poly.abs;
             let dual_abs = &abs.dual();
             let mut types_with_data_this_rank = Vec::new();
-            
+
             for t in types {
                 let idx = t.example;
-    
+
                 let facets = abs[(r, idx)].subs.len();
+                let signature = poly.type_signature(r, t);
                 let fig_facets = dual_abs.element_vertices(rank-r, idx).unwrap().len();
-                let radius = 
+                let measure =
                     if r == 1 {
                         Some(poly.vertices[idx].norm())
                     }
                     else {
-                        if let Some(sphere) = poly.element(r, idx).unwrap().circumsphere() {
-                        Some(sphere.radius())
-                        } else {
-                            None
-                        }
+                        poly.element(r, idx).unwrap().circumsphere().map(|sphere| {
+                            if r == 2 { sphere.radius() * 2.0 } else { sphere.radius() }
+                        })
                     };
-    
+
                 types_with_data_this_rank.push(ElementTypeWithData {
                     example: idx,
                     count: t.count,
                     facets,
+                    signature,
                     fig_facets,
-                    radius,
+                    measure,
+                    name: element_name(facets, r),
+                    fig_name: element_name(fig_facets, rank - r),
+                    measure_text: measure.map_or_else(String::new, |m| format!("{:.10}", m)),
                 });
             }
             types_with_data.push(types_with_data_this_rank);
         }
-    
-        ElementTypesRes {
+
+        let mut res = ElementTypesRes {
             active: true,
             poly: poly.clone(),
             poly_name,
             types: types_with_data,
             components: None,
-            main: true,
-            main_updating: false,
+            fingerprint,
+            stale: false,
+            auto_refresh: self.auto_refresh,
             defiss: self.defiss,
+            sort: self.sort,
+            filter: self.filter.clone(),
+            selected: None,
+            scroll_to_selected: false,
+            face_types,
+            hidden_face_types: HashSet::new(),
+        };
+        res.apply_sort();
+        res
+    }
+
+    /// Regenerates the element types from a polytope. If a row was selected,
+    /// the type that best matches it by its signature is selected instead.
+    pub fn regenerate(&mut self, poly: &Concrete, poly_name: String) {
+        let selected = self.selected.and_then(|(r, example)| {
+            let t = self.types.get(r)?.iter().find(|t| t.example == example)?;
+            Some((r, t.signature))
+        });
+
+        *self = self.from_poly(poly, poly_name);
+
+        if let Some((r, signature)) = selected {
+            if let Some(types) = self.types.get(r) {
+                let signatures: Vec<_> = types.iter().map(|t| t.signature).collect();
+                if let Some(idx) = signature.best_match(&signatures) {
+                    self.selected = Some((r, types[idx].example));
+                    self.scroll_to_selected = true;
+                }
+            }
+        }
+    }
+
+    /// Called whenever the selected polytope changes, or another one is
+    /// selected. Marks the types as stale unless they describe it, and
+    /// regenerates them if auto-refresh is on and the polytope is small enough.
+    pub fn polytope_changed(&mut self, poly: &Concrete, poly_name: &str) {
+        if self.active && poly.fingerprint() == self.fingerprint {
+            self.stale = false;
+            return;
+        }
+
+        self.stale = true;
+        if self.auto_refresh && poly.el_count_iter().sum::<usize>() <= AUTO_REFRESH_LIMIT {
+            self.regenerate(poly, poly_name.to_string());
+        }
+    }
+
+    /// Shows or hides the faces of the type with a given representative.
+    pub fn toggle_face_type(&mut self, example: usize) {
+        if !self.hidden_face_types.remove(&example) {
+            self.hidden_face_types.insert(example);
+        }
+    }
+
+    /// Returns the faces that are left out of the mesh, according to the
+    /// hidden face types.
+    pub fn face_filter(&self) -> FaceFilter {
+        if self.hidden_face_types.is_empty() {
+            return FaceFilter::default();
+        }
+
+        FaceFilter {
+            hidden: self
+                .face_types
+                .iter()
+                .map(|t| self.hidden_face_types.contains(t))
+                .collect(),
         }
     }
 
@@ -135,6 +357,64 @@ impl ElementTypesRes {
         self.poly.element_sort();
         self.components = if self.defiss { Some(self.poly.defiss()) } else { Some(self.poly.split()) };
     }
+
+    /// Sorts the tables by a column. Sorting twice by the same column reverses
+    /// the order.
+    pub fn sort_by(&mut self, column: TypeColumn) {
+        self.sort = match self.sort {
+            Some((c, descending)) if c == column => Some((column, !descending)),
+            _ => Some((column, false)),
+        };
+        self.apply_sort();
+    }
+
+    /// Sorts the rows of every table by the selected column. The sort is
+    /// stable, so ties keep their previous order.
+    fn apply_sort(&mut self) {
+        if let Some((column, descending)) = self.sort {
+            for types in &mut self.types {
+                types.sort_by(|a, b| {
+                    let ord = a.cmp_by(b, column);
+                    if descending { ord.reverse() } else { ord }
+                });
+            }
+        }
+    }
+
+    /// Returns the table as comma-separated values, with one row per element
+    /// type that matches the filter, in the order they're shown.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,count,element,figure,measure\n");
+
+        for (r, types) in self.types.iter().enumerate().skip(1) {
+            for t in types.iter().filter(|t| t.matches(&self.filter)) {
+                csv.push_str(&format!(
+                    "{},{},{},{},{}\n",
+                    rank_name(r),
+                    t.count,
+                    t.name,
+                    t.fig_name,
+                    t.measure_text
+                ));
+            }
+        }
+
+        csv
+    }
+}
+
+/// An action requested from a row of the element type table, which is
+/// carried out once the table has been drawn.
+#[derive(Clone, Copy)]
+enum RowAction {
+    /// Loads an element.
+    Element(usize, usize),
+
+    /// Loads an element figure.
+    Figure(usize, usize),
+
+    /// Aligns the cross-section with an element.
+    AlignSlice(usize, usize),
 }
 
 /// The plugin in charge of everything on the right panel.
@@ -149,7 +429,21 @@ impl Plugin for RightPanelPlugin {
                     .system()
                     .label("show_right_panel")
                     .after("show_top_panel"),
-            );
+            )
+            // Every change made during the frame has been made by then.
+            .add_system_to_stage(CoreStage::PostUpdate, update_element_types.system());
+    }
+}
+
+/// Keeps the element types up to date whenever the selected polytope changes,
+/// or another one is selected.
+pub fn update_element_types(
+    mut element_types: ResMut<'_, ElementTypesRes>,
+    poly_name: Res<'_, PolyName>,
+    changed: Query<'_, '_, &Concrete, (With<Selected>, Or<(Changed<Concrete>, Added<Selected>)>)>,
+) {
+    if let Some(poly) = changed.iter().next() {
+        element_types.polytope_changed(poly, &poly_name.0);
     }
 }
 
@@ -158,15 +452,22 @@ impl Plugin for RightPanelPlugin {
 pub fn show_right_panel(
     // Info about the application state.
     egui_ctx: Res<'_, EguiContext>,
-    mut query: Query<'_, '_, &mut Concrete>,
+    mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
     mut poly_name: ResMut<'_, PolyName>,
 
     // The Miratope resources controlled by the right panel.
     mut element_types: ResMut<'_, ElementTypesRes>,
     mut section_direction: ResMut<'_, Vec<SectionDirection>>,
     section_state: Res<'_, SectionState>,
-
+    mut face_filter: ResMut<'_, FaceFilter>,
 ) {
+    // The hidden faces are only written back when they change, since every
+    // change redraws all polytopes.
+    let new_filter = element_types.face_filter();
+    if *face_filter != new_filter {
+        *face_filter = new_filter;
+    }
+
     // The right panel.
     egui::SidePanel::right("right_panel")
         .default_width(300.0)
@@ -174,110 +475,181 @@ pub fn show_right_panel(
         .show(egui_ctx.ctx(), |ui| {
             
             ui.horizontal(|ui| {
-                if ui.add(egui::Button::new("Generate").enabled(!element_types.main)).clicked() {
+                if ui.add(egui::Button::new("Generate").enabled(element_types.stale)).clicked() {
                     if let Some(p) = query.iter_mut().next() {
-                        element_types.main = true;
-                        *element_types = element_types.from_poly(p, poly_name.0.clone());
+                        element_types.regenerate(&p, poly_name.0.clone());
                     }
                 }
     
-                if ui.add(egui::Button::new("Load").enabled(!element_types.main)).clicked() {
+                // The types stop being stale once the polytope is loaded back.
+                let can_load = element_types.stale && element_types.active;
+                if ui.add(egui::Button::new("Load").enabled(can_load)).clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        element_types.main = true;
-                        element_types.main_updating = true;
                         *p = element_types.poly.clone();
                         poly_name.0 = element_types.poly_name.clone();
                     }
                 }
+
+                if ui.checkbox(&mut element_types.auto_refresh, "Auto-refresh").changed()
+                    && element_types.auto_refresh
+                    && element_types.stale
+                {
+                    if let Some(p) = query.iter_mut().next() {
+                        element_types.polytope_changed(&p, &poly_name.0);
+                    }
+                }
             });
 
             ui.separator();
 
             if element_types.active {
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    for (r, types) in element_types.types.clone().into_iter().enumerate().skip(1) {
-                        let poly = &element_types.poly;
-                        let rank = element_types.poly.rank();
+                    // Stale types are greyed out until they're regenerated.
+                    ui.set_enabled(!element_types.stale);
 
-                        if r == rank {
-                            break;
+                    ui.horizontal(|ui| {
+                        ui.label("Filter");
+                        ui.text_edit_singleline(&mut element_types.filter);
+
+                        if ui.button("Copy as CSV").clicked() {
+                            ui.output().copied_text = element_types.to_csv();
                         }
+                    });
 
-                        ui.heading(format!("{}",
-                            if rank > EL_NAMES.len() {format!("{}-elements", r-1)}
-                            else {EL_NAMES[r].to_string()})
-                        );
-                        for t in types {
-                            let i = t.example;
-
-                            ui.horizontal(|ui| {
-
-                                // The number of elements in this orbit
-                                ui.label(format!("{} ×",t.count));
-
-                                // Button to get the element
-                                if ui.button(format!("{}-{}", 
-                                    t.facets,
-                                    if r >= EL_SUFFIXES.len() {"".to_string()}
-                                    else {EL_SUFFIXES[r].to_string()}
-                                )).clicked() {
-                                    if let Some(mut p) = query.iter_mut().next() {
-                                        if let Some(mut element) = poly.element(r,i) {
-                                            element.flatten();
-                                            element.recenter();
-                                            *p = element;
-                                            poly_name.0 = format!("Element of {}",element_types.poly_name.clone());
-                                        } else {
-                                            eprintln!("Element failed: no element at rank {}, index {}", r, i);
-                                        }
-                                    }
+                    ui.separator();
+
+                    let rank = element_types.poly.rank();
+
+                    // Checks if the sliced polytope and the polytope the types are of have the same rank.
+                    let can_align = matches!(*section_state, SectionState::Active{..})
+                        && section_direction[0].0.len() + 1 == rank;
+
+                    // Clicks are only acted upon once the tables have been drawn.
+                    let mut selected = element_types.selected;
+                    let scroll_to_selected = element_types.scroll_to_selected;
+                    let mut sort = None;
+                    let mut action = None;
+                    let mut toggled_face_type = None;
+
+                    // Faces can be hidden by type if their types are known.
+                    let can_hide = !element_types.face_types.is_empty();
+
+                    for (r, types) in element_types.types.iter().enumerate().skip(1) {
+                        let filter = &element_types.filter;
+                        if !types.iter().any(|t| t.matches(filter)) {
+                            continue;
+                        }
+
+                        ui.heading(rank_name(r));
+
+                        egui::Grid::new(("element_types", r)).striped(true).show(ui, |ui| {
+                            for column in TypeColumn::ALL.iter().copied() {
+                                let header = match element_types.sort {
+                                    Some((c, descending)) if c == column => format!(
+                                        "{} {}",
+                                        column.header(r),
+                                        if descending {"▼"} else {"▲"}
+                                    ),
+                                    _ => column.header(r).to_string(),
+                                };
+
+                                if ui.selectable_label(element_types.sort.map(|(c, _)| c) == Some(column), header).clicked() {
+                                    sort = Some(column);
                                 }
+                            }
+                            if r == 3 && can_hide {
+                                ui.label("Shown");
+                            }
+                            ui.end_row();
 
-                                // Button to get the element figure
-                                if ui.button(format!("{}-{}",
-                                    t.fig_facets,
-                                    if rank - r >= EL_SUFFIXES.len() {"".to_string()}
-                                    else {EL_SUFFIXES[rank - r].to_string()}
-                                )).clicked() {
-                                    if let Some(mut p) = query.iter_mut().next() {
-                                        match poly.element_fig(r, i) {
-                                            Ok(Some(mut figure)) => {
-                                                figure.flatten();
-                                                figure.recenter();
-                                                *p = figure;
-                                                poly_name.0 = format!("Figure of {}",element_types.poly_name.clone());
-                                            }
-                                            Ok(None) => eprintln!("Figure failed: no element at rank {}, index {}", r, i),
-                                            Err(err) => eprintln!("Figure failed: {}", err),
-                                        }
+                            for t in types.iter().filter(|t| t.matches(filter)) {
+                                let i = t.example;
+
+                                ui.label(t.count.to_string());
+                                let response = ui.selectable_label(selected == Some((r, i)), &t.name);
+                                if response.clicked() {
+                                    selected = Some((r, i));
+                                }
+                                if scroll_to_selected && selected == Some((r, i)) {
+                                    response.scroll_to_me(egui::Align::Center);
+                                }
+                                ui.label(&t.fig_name);
+                                ui.label(&t.measure_text);
+
+                                // Hidden faces are left out of the mesh, but
+                                // not out of the wireframe.
+                                if r == 3 && can_hide {
+                                    let mut shown = !element_types.hidden_face_types.contains(&i);
+                                    if ui.checkbox(&mut shown, "").changed() {
+                                        toggled_face_type = Some(i);
                                     }
                                 }
 
-                                if let SectionState::Active{..} = *section_state {
-                                    if section_direction[0].0.len() == rank-1 { // Checks if the sliced polytope and the polytope the types are of have the same rank.
-                                        if ui.button("Align slice").clicked() {
-                                            if let Some(element) = poly.element(r,i) {
-                                                section_direction[0] = SectionDirection(Vector::from(Point::from(
-                                                    Subspace::from_points(element.vertices.iter())
-                                                        .project(&Point::zeros(rank-1))
-                                                        .normalize()
-                                                )));
-                                            }
-                                        }
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("Element").clicked() {
+                                        action = Some(RowAction::Element(r, i));
                                     }
-                                }
+                                    if ui.small_button("Figure").clicked() {
+                                        action = Some(RowAction::Figure(r, i));
+                                    }
+                                    if can_align && ui.small_button("Align slice").clicked() {
+                                        action = Some(RowAction::AlignSlice(r, i));
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
 
-                                if let Some(radius) = t.radius {
-                                    ui.label(
-                                        if r == 1 {format!("norm {:.10}", radius)}
-                                        else if r == 2 {format!("length {:.10}", radius*2.0)}
-                                        else {format!("radius {:.10}", radius)}
-                                    );
+                        ui.separator();
+                    }
+
+                    element_types.selected = selected;
+                    element_types.scroll_to_selected = false;
+                    if let Some(column) = sort {
+                        element_types.sort_by(column);
+                    }
+                    if let Some(example) = toggled_face_type {
+                        element_types.toggle_face_type(example);
+                    }
+
+                    let poly = &element_types.poly;
+                    match action {
+                        Some(RowAction::Element(r, i)) => {
+                            if let Some(mut p) = query.iter_mut().next() {
+                                if let Some(mut element) = poly.element(r, i) {
+                                    element.ensure_flat();
+                                    element.recenter_mut();
+                                    *p = element;
+                                    poly_name.0 = format!("Element of {}", element_types.poly_name.clone());
+                                } else {
+                                    eprintln!("Element failed: no element at rank {}, index {}", r, i);
                                 }
-                            });
+                            }
                         }
-
-                        ui.separator();
+                        Some(RowAction::Figure(r, i)) => {
+                            if let Some(mut p) = query.iter_mut().next() {
+                                match poly.element_fig(r, i) {
+                                    Ok(Some(mut figure)) => {
+                                        figure.ensure_flat();
+                                        figure.recenter_mut();
+                                        *p = figure;
+                                        poly_name.0 = format!("Figure of {}", element_types.poly_name.clone());
+                                    }
+                                    Ok(None) => eprintln!("Figure failed: no element at rank {}, index {}", r, i),
+                                    Err(err) => eprintln!("Figure failed: {}", err),
+                                }
+                            }
+                        }
+                        Some(RowAction::AlignSlice(r, i)) => {
+                            if let Some(element) = poly.element(r, i) {
+                                section_direction[0] = SectionDirection(Vector::from(Point::from(
+                                    Subspace::from_points(element.vertices.iter())
+                                        .project(&Point::zeros(rank-1))
+                                        .normalize()
+                                )));
+                            }
+                        }
+                        None => {}
                     }
 
                     ui.horizontal(|ui| {
@@ -325,4 +697,80 @@ pub fn show_right_panel(
                 }); 
             }
     });
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an app that only keeps the element types up to date, with a
+    /// selected cuboctahedron whose square faces are selected in the table.
+    fn types_app(auto_refresh: bool) -> App {
+        let mut app = App::new();
+        app.insert_resource(PolyName("cuboctahedron".to_string()))
+            .init_resource::<ElementTypesRes>()
+            .add_system(update_element_types.system());
+
+        let cuboctahedron = Concrete::archimedean(1);
+        let mut element_types = ElementTypesRes::default();
+        element_types.auto_refresh = auto_refresh;
+        element_types.regenerate(&cuboctahedron, "cuboctahedron".to_string());
+        let square = element_types.types[3].iter().find(|t| t.facets == 4).unwrap().example;
+        element_types.selected = Some((3, square));
+
+        app.insert_resource(element_types);
+        app.world.spawn().insert(cuboctahedron).insert(Selected);
+        app.update();
+        app
+    }
+
+    /// Replaces the selected polytope by its dual.
+    fn dualize(app: &mut App) {
+        let mut query = app.world.query_filtered::<&mut Concrete, With<Selected>>();
+        query.iter_mut(&mut app.world).next().unwrap().try_dual_mut().unwrap();
+        app.update();
+    }
+
+    /// The types of a freshly selected polytope they describe aren't stale.
+    #[test]
+    fn not_stale() {
+        let app = types_app(false);
+        let element_types = app.world.get_resource::<ElementTypesRes>().unwrap();
+        assert!(!element_types.stale);
+        assert_eq!(element_types.types[3].len(), 2);
+    }
+
+    /// Changing the polytope without auto-refresh leaves the types stale until
+    /// the old polytope is loaded back.
+    #[test]
+    fn stale() {
+        let mut app = types_app(false);
+        dualize(&mut app);
+        let element_types = app.world.get_resource::<ElementTypesRes>().unwrap();
+        assert!(element_types.stale);
+        assert_eq!(element_types.types[3].len(), 2);
+
+        let poly = element_types.poly.clone();
+        let mut query = app.world.query_filtered::<&mut Concrete, With<Selected>>();
+        *query.iter_mut(&mut app.world).next().unwrap() = poly;
+        app.update();
+        assert!(!app.world.get_resource::<ElementTypesRes>().unwrap().stale);
+    }
+
+    /// With auto-refresh, the types of the rhombic dodecahedron replace those
+    /// of the cuboctahedron, and its rhombi take over the selection from the
+    /// squares, which have the same number of sides and the closest count.
+    #[test]
+    fn auto_refresh() {
+        let mut app = types_app(true);
+        dualize(&mut app);
+        let element_types = app.world.get_resource::<ElementTypesRes>().unwrap();
+        assert!(!element_types.stale);
+        assert_eq!(element_types.types[3].len(), 1);
+
+        let rhombus = &element_types.types[3][0];
+        assert_eq!((rhombus.facets, rhombus.count), (4, 12));
+        assert_eq!(element_types.selected, Some((3, rhombus.example)));
+        assert!(element_types.scroll_to_selected);
+    }
+}
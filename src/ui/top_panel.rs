@@ -1,44 +1,69 @@
 //! Contains all code related to the top bar.
 
-use std::path::PathBuf;
+use std::{collections::BTreeMap, sync::Arc};
 
-use super::{camera::ProjectionType, memory::Memory, window::{Window, *}, UnitPointWidget, main_window::PolyName, config::{MeshColor, WfColor}};
-use crate::{Concrete, Float, Hyperplane, Point, Vector};
+use super::{command::PolytopeCommand, custom::CustomOperations, camera::ProjectionType, lang::{SelectedLanguage, LANGUAGES}, memory::{update_shared_polytopes, Memory, SharedPolytope}, window::*, UnitPointWidget, main_window::{spawn_polytope, Peel, PolyName, PolytopeList, Selected}, appearance::{AppearanceWindow, PolytopeStyle}, axes::AxesSettings, labels::ElementLabels, measure::Measurement, lattice::LatticeExport, recent::RecentFiles, dialog::{DialogQueue, DialogRequest}, replace::{PendingReplacement, Replacement}, validation::ValidationSettings};
+use crate::{mesh::{MeshOptions, RenderFrame, WindingRule, MAX_REFINEMENT}, Concrete, Float, Hyperplane, Point, Vector};
 
 use bevy::prelude::*;
 use bevy_egui::{egui::{self, menu, Ui}, EguiContext};
-use miratope_core::{conc::{ConcretePolytope, faceting::GroupEnum, symmetry::Vertices}, file::FromFile, float::Float as Float2, Polytope, abs::Ranked};
+use miratope_core::{conc::{ConcretePolytope, element_types::element_name, identify::Registry, faceting::GroupEnum, slices::{RotatingSection, SlicePrecomputation}, symmetry::Vertices}, file::format::SaveOptions, float::Float as Float2, Polytope, abs::{provenance::Provenance, Ranked, ValidationLevel}};
 
 /// The plugin in charge of everything on the top panel.
 pub struct TopPanelPlugin;
 
 impl Plugin for TopPanelPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<FileDialogState>()
+        app.init_resource::<SaveOptions>()
             .init_resource::<SectionState>()
             .init_resource::<Vec<SectionDirection>>()
             .init_resource::<Memory>()
             .init_resource::<ShowMemory>()
             .init_resource::<ShowHelp>()
             .init_resource::<ExportMemory>()
-            .init_non_send_resource::<FileDialogToken>()
-            .add_system(file_dialog.system())
             // Windows must be the first thing shown.
             .add_system(
                 show_top_panel
                     .system()
                     .label("show_top_panel")
                     .after("show_windows"),
-            );
+            )
+            .add_system(enumerate_facetings.system().after("show_top_panel"))
+            .add_system(export_memory.system().after("show_top_panel"))
+            .add_system(update_section_slices.system().after("show_top_panel"))
+            .add_system(rotate_section.system().before("show_top_panel"))
+            .add_system_to_stage(CoreStage::PostUpdate, update_shared_polytopes.system());
+    }
+}
+
+/// The largest number of parallel slices the cross-section view can show at
+/// once.
+pub const MAX_SLICE_COUNT: usize = 15;
+
+/// How far the slicing hyperplane is kept from the ends of its range, so that
+/// the slices are never empty.
+const SLIDER_MARGIN: Float = 0.0000001;
+
+/// Returns the position of a slicing hyperplane moved inside the range of its
+/// slider, which leaves a margin at both ends. If the range is too small for
+/// the margins, returns its midpoint instead.
+fn clamp_to_slider(pos: Float, (min, max): (Float, Float)) -> Float {
+    let (lo, hi) = (min + SLIDER_MARGIN, max - SLIDER_MARGIN);
+    if lo <= hi {
+        pos.clamp(lo, hi)
+    } else {
+        (min + max) / 2.0
     }
 }
 
 /// Stores the state of the cross-section view.
+#[derive(Clone)]
 pub enum SectionState {
     /// The view is active.
     Active {
-        /// The polytope from which the cross-section originates.
-        original_polytope: Concrete,
+        /// The polytope from which the cross-section originates. It's shared
+        /// so that cloning the state doesn't clone the polytope.
+        original_polytope: Arc<Concrete>,
 
         /// The name of the polytope.
         original_name: String,
@@ -46,6 +71,10 @@ pub enum SectionState {
         /// The range of the slider.
         minmax: Vec<(Float, Float)>,
 
+        /// The settings each slider range was computed with, so that it's
+        /// only recomputed when they change.
+        minmax_keys: Vec<Vec<Float>>,
+
         /// The position of the slicing hyperplane.
         hyperplane_pos: Vec<Float>,
 
@@ -54,6 +83,25 @@ pub enum SectionState {
 
         /// Whether we're not updating the cross-section.
         lock: bool,
+
+        /// The element of the original polytope that each element of the
+        /// cross-section lies in.
+        provenance: Option<Provenance>,
+
+        /// The face of the cross-section whose provenance is shown.
+        inspected_face: usize,
+
+        /// The number of parallel slices shown at once through the last
+        /// slicing hyperplane. The selected polytope is the middle one.
+        slice_count: usize,
+
+        /// The slices shown besides the middle one, together with their
+        /// offsets from it, in steps. They're shared for the same reason as
+        /// the original polytope.
+        slices: Arc<Vec<(isize, Concrete)>>,
+
+        /// The rotation of the first slicing hyperplane, if it's rotating.
+        rotation: Option<SectionRotation>,
     },
 
     /// The view is inactive.
@@ -89,42 +137,92 @@ impl SectionState {
 
     pub fn open(&mut self, original_polytope: Concrete, name: String, minmax: Vec<(f64, f64)>) {
         *self = SectionState::Active {
-            original_polytope,
+            original_polytope: Arc::new(original_polytope),
             original_name: name,
             minmax: minmax.clone(),
+            minmax_keys: Vec::new(),
             hyperplane_pos: minmax.clone().into_iter().map(|m| (m.0 + m.1) / 2.0).collect(),
             flatten: true,
             lock: false,
+            provenance: None,
+            inspected_face: 0,
+            slice_count: 1,
+            slices: Arc::new(Vec::new()),
+            rotation: None,
         }
     }
 }
 
-impl Clone for SectionState {
-    fn clone(&self) -> Self {
-        if let SectionState::Active{
-                original_polytope,
-                original_name,
-                minmax,
-                hyperplane_pos,
-                flatten,
-                lock,
-            } = self{
-                
-            SectionState::Active{
-                original_polytope: original_polytope.clone(),
-                original_name: original_name.clone(),
-                minmax: minmax.clone(),
-                hyperplane_pos: hyperplane_pos.clone(),
-                flatten: *flatten,
-                lock: *lock,
-            }
+/// The rotation of the first slicing hyperplane of the cross-section view. Its
+/// normal rotates in the plane of two coordinate axes, while it passes through
+/// the gravicenter of the original polytope.
+#[derive(Clone)]
+pub struct SectionRotation {
+    /// The coordinate axes the normal rotates from and towards.
+    pub axes: (usize, usize),
+
+    /// The angle of the normal from the first axis, in radians.
+    pub angle: Float,
+
+    /// The speed of the rotation, in radians per second.
+    pub speed: Float,
+
+    /// Whether the angle advances over time.
+    pub playing: bool,
+
+    /// The heights of the vertices of the original polytope along the plane
+    /// of rotation, together with the axes they were computed for. They're
+    /// shared so that cloning the state doesn't clone them.
+    precomputation: Option<((usize, usize), Arc<RotatingSection>)>,
+}
+
+impl SectionRotation {
+    /// Starts rotating from a given axis towards another.
+    pub fn new(axes: (usize, usize)) -> Self {
+        Self {
+            axes,
+            angle: 0.0,
+            speed: 0.5,
+            playing: true,
+            precomputation: None,
         }
-        else
-        {
-            SectionState::Inactive
+    }
+
+    /// Returns the heights of the vertices of a polytope along the plane of
+    /// rotation, computing them only if the axes changed.
+    fn precomputation(&mut self, poly: &Concrete) -> Option<Arc<RotatingSection>> {
+        if !matches!(&self.precomputation, Some((axes, _)) if *axes == self.axes) {
+            let dim = poly.dim_or();
+            let (from, to) = self.axes;
+            if from >= dim || to >= dim {
+                return None;
+            }
+
+            let center = poly.gravicenter()?;
+            let section = RotatingSection::new(
+                poly,
+                (&SectionDirection::axis(dim, from).0, &SectionDirection::axis(dim, to).0),
+                &center,
+            )?;
+            self.precomputation = Some((self.axes, Arc::new(section)));
         }
+
+        self.precomputation.as_ref().map(|(_, section)| Arc::clone(section))
     }
 }
+
+/// Returns the offsets of the slices besides the middle one, in steps, when
+/// showing a given number of them.
+fn slice_offsets(slice_count: usize) -> impl Iterator<Item = isize> {
+    let half = (slice_count / 2) as isize;
+    (-half..slice_count as isize - half).filter(|&j| j != 0)
+}
+
+/// Marks the polytopes that show the slices of the cross-section view besides
+/// the middle one, by their index in the list of slices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SectionSlice(pub usize);
+
 impl Default for SectionState {
     fn default() -> Self {
         Self::Inactive
@@ -140,6 +238,48 @@ impl Default for SectionDirection {
     }
 }
 
+impl SectionDirection {
+    /// The direction of the last coordinate axis, which is the default
+    /// direction for cross-sections.
+    pub fn last_axis(dim: usize) -> Self {
+        let mut direction = Vector::zeros(dim);
+        if dim > 0 {
+            direction[dim - 1] = 1.0;
+        }
+        Self(direction)
+    }
+
+    /// The direction of a given coordinate axis.
+    pub fn axis(dim: usize, idx: usize) -> Self {
+        let mut direction = Vector::zeros(dim);
+        direction[idx] = 1.0;
+        Self(direction)
+    }
+
+    /// Resets the direction to the last coordinate axis if it doesn't have
+    /// the given dimension, like when the sliced polytope changes dimension.
+    pub fn resize_to(&mut self, dim: usize) {
+        if self.0.len() != dim {
+            *self = Self::last_axis(dim);
+        }
+    }
+
+    /// Returns whether the direction can be used to slice a polytope, which
+    /// requires it not to be too close to zero.
+    pub fn is_valid(&self) -> bool {
+        self.0.norm() > f64::EPS
+    }
+
+    /// Sets the direction to a new one, normalized. If the new direction is
+    /// too close to zero, the previous one is kept and a note is logged.
+    pub fn set(&mut self, direction: Vector) {
+        match direction.try_normalize(f64::EPS) {
+            Some(direction) => self.0 = direction,
+            None => println!("Note: ignored a cross-section direction too close to zero."),
+        }
+    }
+}
+
 /// Stores whether the memory window is shown.
 pub struct ShowMemory(bool);
 
@@ -167,146 +307,11 @@ impl Default for ExportMemory {
     }
 }
 
-/// Contains all operations that manipulate file dialogs concretely.
-///
-/// Guarantees that file dialogs will be opened on the main thread, so as to
-/// circumvent a MacOS limitation that all GUI operations must be done on the
-/// main thread.
-#[derive(Default)]
-pub struct FileDialogToken(std::marker::PhantomData<*const ()>);
-
-impl FileDialogToken {
-    /// Auxiliary function to create a new file dialog.
-    fn new_file_dialog() -> rfd::FileDialog {
-        rfd::FileDialog::new()
-            .add_filter("OFF File", &["off"])
-    }
-
-    /// Returns the path given by an open file dialog.
-    fn pick_file(&self) -> Option<PathBuf> {
-        Self::new_file_dialog().pick_file()
-    }
-
-    /// Returns the path given by a save file dialog.
-    fn save_file(&self, name: &str) -> Option<PathBuf> {
-        Self::new_file_dialog().set_file_name(name).save_file()
-    }
-}
-
-/// The type of file dialog we're showing.
-enum FileDialogMode {
-    /// We're not currently showing any file dialog.
-    Disabled,
-
-    /// We're showing a file dialog to open a file.
-    Open,
-
-    /// We're showing a file dialog to save a file.
-    Save,
-}
-
-/// The file dialog is disabled by default.
-impl Default for FileDialogMode {
-    fn default() -> Self {
-        Self::Disabled
-    }
-}
-
-/// The state the file dialog is in.
-#[derive(Default)]
-pub struct FileDialogState {
-    /// The file dialog mode.
-    mode: FileDialogMode,
-
-    /// The name of the file to load or save, if any.
-    name: Option<String>,
-}
-
-impl FileDialogState {
-    /// Changes the file dialog mode to [`FileDialogMode::Open`].
-    pub fn open(&mut self) {
-        self.mode = FileDialogMode::Open;
-    }
-
-    /// Changes the file dialog mode to [`FileDialogMode::Save`], and loads the
-    /// name of the file.
-    pub fn save(&mut self, name: String) {
-        self.mode = FileDialogMode::Save;
-        self.name = Some(name);
-    }
-
-    /// Gets the name of the file dialog.
-    pub fn unwrap_name(&self) -> &str {
-        self.name.as_ref().unwrap()
-    }
-}
-
-/// The system in charge of showing the file dialog.
-pub fn file_dialog(
-    mut query: Query<'_, '_, &mut Concrete>,
-    mut name: ResMut<'_, PolyName>,
-    file_dialog_state: Res<'_, FileDialogState>,
-    file_dialog: NonSend<'_, FileDialogToken>,
-) {
-    if file_dialog_state.is_changed() {
-        match file_dialog_state.mode {
-            // We want to save a file.
-            FileDialogMode::Save => {
-                if let Some(path) = file_dialog.save_file(file_dialog_state.unwrap_name()) {
-                    if let Some(p) = query.iter_mut().next() {
-                        if let Err(err) = p.con().to_path(&path, Default::default()) {
-                            eprintln!("File saving failed: {}", err);
-                        }
-                    }
-                }
-            }
-
-            // We want to open a file.
-            FileDialogMode::Open => {
-                if let Some(path) = file_dialog.pick_file() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        match Concrete::from_path(&path) {
-                            Ok(q) => {
-                                *p = q;
-                                name.0 = path.file_stem().unwrap().to_string_lossy().into_owned();
-                            }
-                            Err(err) => eprintln!("File open failed: {}", err),
-                        }
-                    }
-                }
-            }
-
-            // There's nothing to do with the file dialog this frame.
-            FileDialogMode::Disabled => {}
-        }
-    }
-}
-
 /// Whether the hotkey to enable "advanced" options is enabled.
 pub fn advanced(keyboard: &Input<KeyCode>) -> bool {
     keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl)
 }
 
-/// All of the windows that can be shown on screen, as mutable resources.
-pub type EguiWindows<'a> = (
-    (ResMut<'a, DualWindow>,
-    ResMut<'a, PyramidWindow>,
-    ResMut<'a, PrismWindow>,
-    ResMut<'a, TegumWindow>,
-    ResMut<'a, AntiprismWindow>,
-    ResMut<'a, DuopyramidWindow>,
-    ResMut<'a, DuoprismWindow>,
-    ResMut<'a, DuotegumWindow>,
-    ResMut<'a, DuocombWindow>,
-    ResMut<'a, StarWindow>,
-    ResMut<'a, CompoundWindow>), // Workaround for an argument count limit
-    ResMut<'a, TruncateWindow>,
-    ResMut<'a, ScaleWindow>,
-    ResMut<'a, FacetingSettings>,
-    ResMut<'a, RotateWindow>,
-    ResMut<'a, PlaneWindow>,
-);
-
 macro_rules! element_sort {
     ($p:ident) => {
         if !$p.abs().sorted() {
@@ -319,113 +324,351 @@ macro_rules! element_sort {
 #[allow(clippy::too_many_arguments)]
 pub fn show_top_panel(
     // Info about the application state.
-    egui_ctx: Res<'_, EguiContext>,
-    mut query: Query<'_, '_, &mut Concrete>,
-    keyboard: Res<'_, Input<KeyCode>>,
+    (egui_ctx, keyboard): (Res<'_, EguiContext>, Res<'_, Input<KeyCode>>),
+    mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+    mut commands: EventWriter<'_, '_, PolytopeCommand>,
 
     // The Miratope resources controlled by the top panel.
     mut section_state: ResMut<'_, SectionState>,
     mut section_direction: ResMut<'_, Vec<SectionDirection>>,
-    mut file_dialog_state: ResMut<'_, FileDialogState>,
-    mut projection_type: ResMut<'_, ProjectionType>,
+    (mut save_options, mut recent_files, mut pending): (
+        ResMut<'_, SaveOptions>,
+        ResMut<'_, RecentFiles>,
+        ResMut<'_, PendingReplacement>,
+    ),
+    (mut projection_type, mut mesh_options, mut peel): (
+        ResMut<'_, ProjectionType>,
+        ResMut<'_, MeshOptions>,
+        ResMut<'_, Peel>,
+    ),
     mut poly_name: ResMut<'_, PolyName>,
-    mut memory: ResMut<'_, Memory>,
+    (mut memory, mut shared): (
+        ResMut<'_, Memory>,
+        Query<'_, '_, &mut SharedPolytope, With<Selected>>,
+    ),
     mut show_memory: ResMut<'_, ShowMemory>,
     mut show_help: ResMut<'_, ShowHelp>,
-    mut export_memory: ResMut<'_, ExportMemory>,
-    mut colors: (ResMut<'_, ClearColor>, ResMut<'_, MeshColor>, ResMut<'_, WfColor>),
-
-    mut visuals: ResMut<'_, egui::Visuals>,
-
-    // The different windows that can be shown.
-    (
-        (mut dual_window,
-        mut pyramid_window,
-        mut prism_window,
-        mut tegum_window,
-        mut antiprism_window,
-        mut duopyramid_window,
-        mut duoprism_window,
-        mut duotegum_window,
-        mut duocomb_window,
-        mut star_window,
-        mut compound_window),
-        mut truncate_window,
-        mut scale_window,
-        mut faceting_settings,
-        mut rotate_window,
-        mut plane_window,
-    ): EguiWindows<'_>,
+    mut clear_color: ResMut<'_, ClearColor>,
+
+    // The registry of known polytopes is only built when first needed.
+    (mut visuals, mut lang, mut registry, mut validation): (
+        ResMut<'_, egui::Visuals>,
+        ResMut<'_, SelectedLanguage>,
+        Local<'_, Option<Registry>>,
+        ResMut<'_, ValidationSettings>,
+    ),
+
+    // The view settings and custom operations shown in the menus.
+    (custom_operations, mut measurement, mut axes, mut element_labels): (
+        Res<'_, CustomOperations>,
+        ResMut<'_, Measurement>,
+        ResMut<'_, AxesSettings>,
+        ResMut<'_, ElementLabels>,
+    ),
 ) {
+    // Ctrl+S saves the polytope.
+    if advanced(&keyboard)
+        && keyboard.just_pressed(KeyCode::S)
+        && !egui_ctx.ctx().wants_keyboard_input()
+    {
+        commands.send(PolytopeCommand::Save);
+    }
+
     // The top bar.
     egui::TopBottomPanel::top("top_panel").show(egui_ctx.ctx(), |ui| {
         menu::bar(ui, |ui| {
             
             // Operations on files.
-            menu::menu(ui, "File", |ui| {
+            menu::menu(ui, t!(lang, "menu.file"), |ui| {
                 // Loads a file.
-                if ui.button("Open").clicked() {
-                    file_dialog_state.open();
+                if ui.button(t!(lang, "file.open")).clicked() {
+                    commands.send(PolytopeCommand::Open);
                 }
 
-                // Saves a file.
-                if ui.button("Save").clicked() {
-                    file_dialog_state.save(poly_name.0.clone());
+                // Loads a recently opened file.
+                ui.collapsing(t!(lang, "file.open_recent"), |ui| {
+                    if let Some(path) = recent_files.show(ui, *lang) {
+                        match Replacement::from_path(&path) {
+                            Ok(replacement) => pending.set(replacement),
+                            Err(err) => eprintln!("{}: {}", t!(lang, "error.file_open"), err),
+                        }
+                    }
+                });
+
+                // Saves a file, to the file it was opened from or last saved
+                // to if possible.
+                if ui.button(t!(lang, "file.save")).clicked() {
+                    commands.send(PolytopeCommand::Save);
                 }
 
-                if ui.button("Export all memory slots").clicked() {
-                    export_memory.0 = true;
-                    export_memory.1 = 0;
+                // Saves a file, always asking for the path.
+                if ui.button(t!(lang, "file.save_as")).clicked() {
+                    commands.send(PolytopeCommand::SaveAs);
+                }
+
+                // Exports the face lattice into a graph file.
+                if ui.button(t!(lang, "file.export_lattice")).clicked() {
+                    commands.send(PolytopeCommand::open::<LatticeExport>());
+                }
+
+                // Whether saving a file also saves its element types.
+                ui.checkbox(&mut save_options.types, t!(lang, "file.save_types"));
+
+                // Whether compounds are saved as one OFF block per component.
+                ui.checkbox(
+                    &mut save_options.off.split_components,
+                    t!(lang, "file.split_components"),
+                );
+
+                // Whether the units of the polytope are saved as a comment.
+                ui.checkbox(&mut save_options.off.units, t!(lang, "file.save_units"));
+
+                // Builds a polytope from its vertices and facets.
+                if ui.button(t!(lang, "file.new_from_text")).clicked() {
+                    commands.send(PolytopeCommand::open::<TextWindow>());
+                }
+
+                // Builds a polytope as the orbit of a point under a group.
+                if ui.button(t!(lang, "file.new_orbit")).clicked() {
+                    commands.send(PolytopeCommand::open::<OrbitWindow>());
+                }
+
+                // Saves every polytope in memory.
+                if ui.button(t!(lang, "file.export_memory")).clicked() {
+                    commands.send(PolytopeCommand::ExportMemory);
                 }
 
                 ui.separator();
 
                 // Quits the application.
-                if ui.button("Exit").clicked() {
+                if ui.button(t!(lang, "file.exit")).clicked() {
                     std::process::exit(0);
                 }
             });
 
-            if export_memory.0 {
-                let idx = export_memory.1;
-                if idx == memory.len() {
-                    export_memory.1 = 0;
-                    export_memory.0 = false;
+            // Manages the loaded polytopes.
+            menu::menu(ui, t!(lang, "menu.window"), |ui| {
+                // Spawns a copy of the selected polytope.
+                if ui.button(t!(lang, "window.new_polytope")).clicked() {
+                    commands.send(PolytopeCommand::NewPolytope);
                 }
-                else {
-                    if let Some((poly, label)) = &memory[idx] {
-                        if let Some(mut p) = query.iter_mut().next() {
-                            *p = poly.clone();
-                            let name = match label {
-                                None => {
-                                    format!("polytope {}", idx)
-                                }
-                                Some(a) => a.to_string()
-                            };
-                            poly_name.0 = name.clone();
-                            file_dialog_state.save(name);
-                        }
-                    }
-                    export_memory.1 += 1;
+
+                // Shows the list of loaded polytopes.
+                if ui.button(t!(lang, "window.polytope_list")).clicked() {
+                    commands.send(PolytopeCommand::open::<PolytopeList>());
                 }
-            }
+            });
 
             // Configures the view.
-            menu::menu(ui, "View", |ui| {
+            menu::menu(ui, t!(lang, "menu.view"), |ui| {
                 let mut checked = projection_type.is_orthogonal();
 
-                if ui.checkbox(&mut checked, "Orthogonal projection").clicked() {
+                if ui.checkbox(&mut checked, t!(lang, "view.orthogonal")).clicked() {
                     projection_type.flip();
 
-                    // Forces an update on all polytopes.
+                    // Forces an update on the selected polytope.
                     if let Some(mut p) = query.iter_mut().next() {
                         p.set_changed();
                     }
                 }
+
+                // Projects the circumsphere of the selected polytope from a
+                // pole, which suits polytopes of rank 4 best.
+                let dim = query.iter_mut().next().map(|p| p.dim_or());
+                let mut stereographic = projection_type.pole().is_some();
+
+                if ui.checkbox(&mut stereographic, t!(lang, "view.stereographic")).clicked() {
+                    *projection_type = if stereographic {
+                        ProjectionType::stereographic(dim.unwrap_or(4))
+                    } else {
+                        ProjectionType::Perspective
+                    };
+
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                if let Some(pole) = projection_type.pole().cloned() {
+                    // The pole is reset whenever it doesn't fit the polytope.
+                    let mut new_pole = match dim {
+                        Some(dim) if dim >= 2 && dim != pole.len() => {
+                            ProjectionType::stereographic(dim).pole().unwrap().clone()
+                        }
+                        _ => pole.clone(),
+                    };
+                    ui.add(UnitPointWidget::new(&mut new_pole, t!(lang, "view.pole")));
+
+                    if new_pole != pole {
+                        *projection_type = ProjectionType::Stereographic { pole: new_pole };
+
+                        if let Some(mut p) = query.iter_mut().next() {
+                            p.set_changed();
+                        }
+                    }
+
+                    // Subdivides the edges and faces so that they curve.
+                    let mut refinement = mesh_options.refinement;
+                    ui.add(
+                        egui::Slider::new(&mut refinement, 0..=MAX_REFINEMENT)
+                            .text(t!(lang, "view.refinement")),
+                    );
+
+                    if refinement != mesh_options.refinement {
+                        mesh_options.refinement = refinement;
+                    }
+                }
+
+                // Fills in the cores of self-intersecting faces, like those of
+                // pentagrams, or leaves them hollow.
+                let mut fill_cores = mesh_options.winding_rule == WindingRule::NonZero;
+
+                if ui.checkbox(&mut fill_cores, t!(lang, "view.fill_cores")).clicked() {
+                    mesh_options.winding_rule = if fill_cores {
+                        WindingRule::NonZero
+                    } else {
+                        WindingRule::EvenOdd
+                    };
+                }
+
+                // Sorts the triangles of translucent faces from back to front
+                // as the camera moves. Turning this off is faster.
+                let mut depth_sort = mesh_options.depth_sort;
+
+                if ui.checkbox(&mut depth_sort, t!(lang, "view.depth_sort")).clicked() {
+                    mesh_options.depth_sort = depth_sort;
+                }
+
+                // Hides the facets of the selected polytope closest to the
+                // viewpoint, so that the ones further inside show up.
+                if !peel.order.is_empty() {
+                    let mut hidden = peel.hidden;
+                    ui.add(
+                        egui::Slider::new(&mut hidden, 0..=peel.order.len())
+                            .text(t!(lang, "view.peel")),
+                    );
+
+                    if hidden != peel.hidden {
+                        peel.hidden = hidden;
+                    }
+                }
+
+                ui.separator();
+
+                // Shows the axes gizmo in the corner of the viewport, and the
+                // grid underneath the polytope.
+                let mut gizmo = axes.gizmo;
+                if ui.checkbox(&mut gizmo, t!(lang, "view.axes")).clicked() {
+                    axes.gizmo = gizmo;
+                }
+
+                let mut grid = axes.grid;
+                if ui.checkbox(&mut grid, t!(lang, "view.grid")).clicked() {
+                    axes.grid = grid;
+                }
+
+                if axes.grid {
+                    let mut auto_spacing = axes.auto_spacing;
+                    if ui.checkbox(&mut auto_spacing, t!(lang, "view.grid_auto")).clicked() {
+                        axes.auto_spacing = auto_spacing;
+                    }
+
+                    if !axes.auto_spacing {
+                        let mut spacing = axes.spacing;
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::DragValue::new(&mut spacing)
+                                    .speed(0.01)
+                                    .clamp_range(0.001..=1000.0),
+                            );
+                            ui.label(t!(lang, "view.grid_spacing"));
+                        });
+
+                        if spacing != axes.spacing {
+                            axes.spacing = spacing;
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                // Labels the elements of the selected polytope with their
+                // indices.
+                ui.label(t!(lang, "view.labels"));
+                let names = [
+                    t!(lang, "view.label_vertices"),
+                    t!(lang, "view.label_edges"),
+                    t!(lang, "view.label_faces"),
+                ];
+                for (kind, name) in names.iter().enumerate() {
+                    let mut shown = element_labels.shown[kind];
+                    if ui.checkbox(&mut shown, *name).clicked() {
+                        element_labels.shown[kind] = shown;
+                    }
+                }
+
+                ui.separator();
+
+                // Opens the colors of the selected polytope.
+                if ui.button(t!(lang, "view.appearance")).clicked() {
+                    commands.send(PolytopeCommand::open::<AppearanceWindow>());
+                }
+
+                ui.separator();
+
+                // Sets how thoroughly polytopes are checked after they change.
+                ui.label(t!(lang, "view.validation"));
+                let levels = [
+                    (ValidationLevel::Off, t!(lang, "view.validation_off")),
+                    (ValidationLevel::Cheap, t!(lang, "view.validation_cheap")),
+                    (ValidationLevel::Full, t!(lang, "view.validation_full")),
+                ];
+                for (level, name) in levels {
+                    ui.radio_value(&mut validation.level, level, name);
+                }
+
+                let mut revert = validation.revert;
+                if ui.checkbox(&mut revert, t!(lang, "view.validation_revert")).clicked() {
+                    validation.revert = revert;
+                }
+
+                ui.separator();
+
+                // Switches the language of the user interface.
+                ui.label(t!(lang, "view.language"));
+                for (idx, language) in LANGUAGES.iter().enumerate() {
+                    ui.radio_value(&mut lang.0, idx, language.name);
+                }
             });
 
             // Prints out properties about the loaded polytope.
-            menu::menu(ui, "Properties", |ui| {
+            menu::menu(ui, t!(lang, "menu.properties"), |ui| {
+                // Opens a window with a report of all of the properties below.
+                if ui.button("Report...").clicked() {
+                    commands.send(PolytopeCommand::open::<ReportWindow>());
+                }
+
+                // Opens a window with the coordinates of every vertex.
+                if ui.button("Vertex coordinates...").clicked() {
+                    commands.send(PolytopeCommand::open::<VertexWindow>());
+                }
+
+                // Opens a window to browse the elements and their incidences.
+                if ui.button("Inspect elements...").clicked() {
+                    commands.send(PolytopeCommand::open::<InspectorWindow>());
+                }
+
+                // Enters or leaves the mode where clicking on vertices
+                // measures distances and angles.
+                if ui
+                    .selectable_label(measurement.active, "Measure...")
+                    .clicked()
+                {
+                    measurement.toggle();
+                }
+
+                ui.separator();
+
                 // Determines the circumsphere of the polytope.
                 if ui.button("Circumsphere").clicked() {
                     if let Some(p) = query.iter_mut().next() {
@@ -453,19 +696,143 @@ pub fn show_top_panel(
                     }
                 }
 
+                // Determines whether the polytope is combinatorially equal to
+                // its dual.
+                if ui.button("Self-dual?").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        if p.is_self_dual() {
+                            println!("The polytope is self-dual.");
+                        } else {
+                            println!("The polytope is not self-dual.");
+                        }
+                    }
+                }
+
+                // Looks up the polytope among the families that Miratope can
+                // generate.
+                if ui.button("Identify").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match registry.get_or_insert_with(Registry::generated).identify(&p.abs) {
+                            Some(name) => println!("The polytope is combinatorially the {}.", name),
+                            None => println!("The polytope is unknown."),
+                        }
+                    }
+                }
+
+                // Builds the compound of a self-dual polytope with its dual,
+                // scaled to match it.
+                if ui.button("Self-dual compound").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.self_dual_compound() {
+                            Some(q) => {
+                                *p = q;
+                                poly_name.0 = format!("Compound of {} and its dual", poly_name.0);
+                            }
+                            None => eprintln!("The polytope has no self-dual compound."),
+                        }
+                    }
+                }
+
                 // Gets the volume of the polytope.
                 if ui.button("Volume").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
                         element_sort!(p);
 
-                        if let Some(vol) = p.volume() {
-                            println!("The volume is {}.", vol);
-                        } else {
-                            println!("The polytope has no volume.");
+                        match p.volume() {
+                            Ok(Some(vol)) => println!("The volume is {}.", vol),
+                            Ok(None) => println!("The polytope has no volume."),
+                            Err(err) => println!("Volume failed: {}.", err),
+                        }
+                    }
+                }
+
+                // Gets the total content of the facets of the polytope.
+                if ui.button("Surface area").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.surface_area() {
+                            Some(area) => println!("The surface area is {}.", area),
+                            None => println!("The polytope has no surface area."),
+                        }
+                        let skew = p.skew_faces().len();
+                        if skew != 0 {
+                            println!("Warning: {} faces are skew, their areas are only approximate.", skew);
+                        }
+                    }
+                }
+
+                // Lists the distinct areas of the faces of the polytope.
+                if ui.button("Face areas").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let mut areas = p.face_areas();
+                        if areas.is_empty() {
+                            println!("The polytope has no faces.");
+                        }
+
+                        // Groups together areas that are close enough.
+                        areas.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                        let mut distinct: Vec<(Float, usize)> = Vec::new();
+                        for area in areas {
+                            match distinct.last_mut() {
+                                Some((last, count)) if area - *last < 1e-6 => *count += 1,
+                                _ => distinct.push((area, 1)),
+                            }
+                        }
+                        for (area, count) in distinct {
+                            println!("{}: {} faces", area, count);
+                        }
+
+                        let skew = p.skew_faces().len();
+                        if skew != 0 {
+                            println!("Warning: {} faces are skew, their areas are only approximate.", skew);
+                        }
+                    }
+                }
+
+                // Gets how far the faces of the polytope are from planar.
+                if ui.button("Face planarity").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.max_face_planarity() {
+                            Some(x) => println!("The greatest face planarity deviation is {}.", x),
+                            None => println!("The polytope has no faces."),
+                        }
+                    }
+                }
+
+                // Gets the statistics of the edge lengths of the polytope.
+                if ui.button("Edge lengths").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        print!("Edge lengths: {}", p.edge_length_stats());
+                    }
+                }
+
+                // Lists the distinct angles between the facets at every ridge.
+                if ui.button("Dihedral angles").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let angles = p.ridge_angles();
+                        let distinct = angles.distinct(1e-6);
+
+                        if distinct.is_empty() {
+                            println!("The polytope has no dihedral angles.");
+                        }
+                        for (angle, count) in distinct {
+                            println!("{:.4}°: {} ridges", angle.to_degrees(), count);
+                        }
+                        if angles.skipped != 0 {
+                            println!("Skipped {} ridges not on exactly two facets.", angles.skipped);
+                        }
+                        if angles.non_planar {
+                            println!("Warning: some facets aren't flat, best-fit hyperplanes were used.");
                         }
                     }
                 }
 
+                // Checks the conditions for the polytope to be uniform.
+                if ui.button("Uniformity").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        print!("{}", p.uniformity_report());
+                    }
+                }
+
                 // Gets the number of flags of the polytope.
                 if ui.button("Flag count").clicked() {
                     if let Some(p) = query.iter_mut().next() {
@@ -502,362 +869,305 @@ pub fn show_top_panel(
                     }
                 }
                 
-                // Gets if it is fissary.
-                if ui.button("Is fissary").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        p.element_sort();
-                        if p.is_fissary() {
-                            println!("The polytope is fissary.")
-                        } else {
-                            println!("The polytope is not fissary.")
+                // Counts the fissary elements of every rank.
+                if ui.button("Check fissary").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let mut counts = BTreeMap::new();
+                        for (rank, _) in p.abs.fissary_elements() {
+                            *counts.entry(rank).or_insert(0) += 1;
+                        }
+
+                        if counts.is_empty() {
+                            println!("Check fissary: the polytope is not fissary.");
+                        }
+                        for (rank, count) in counts {
+                            println!(
+                                "Check fissary: found {} fissary {}",
+                                count,
+                                element_name(rank).to_lowercase()
+                            );
                         }
                     }
                 }
             });
 
-            menu::menu(ui, "Transform", |ui| {
+            menu::menu(ui, t!(lang, "menu.transform"), |ui| {
             
                 if ui.button("Scale to unit edge length").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    let e_l = (&p.vertices[p.abs[2][0].subs[0]] - &p.vertices[p.abs[2][0].subs[1]]).norm();
-                    p.scale(1.0/e_l);
+                    commands.send(PolytopeCommand::ScaleToUnitEdge);
                 }
 
                 if ui.button("Scale to unit circumradius").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    match p.circumsphere() {
-                        Some(sphere) => {
-                            p.scale(1.0/sphere.radius());
-                        }
-                        None => println!("The polytope has no circumsphere."),
-                    }
+                    commands.send(PolytopeCommand::ScaleToUnitCircumradius);
+                }
+
+                // Radially projects the vertices onto the unit sphere.
+                if ui.button("Project to unit sphere").clicked() {
+                    commands.send(PolytopeCommand::ProjectToSphere);
                 }
 
                 // Opens a window to scale a polytope by some factor.
                 if ui.button("Scale...").clicked() {
-                    scale_window.open();
+                    commands.send(PolytopeCommand::open::<ScaleWindow>());
                 }
                 
                 ui.separator();
 
                 // Moves a polytope so that the circumcenter is at the origin.
                 if ui.button("Recenter by circumcenter").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    match p.circumsphere() {
-                        Some(sphere) => {
-                            p.recenter_with(&sphere.center);
-                        }
-                        None => println!("The polytope has no circumsphere."),
-                    }
+                    commands.send(PolytopeCommand::RecenterByCircumcenter);
                 }
                 
                 // Moves a polytope so that the gravicenter is at the origin.
                 if ui.button("Recenter by gravicenter").clicked() {
-                    query.iter_mut().next().unwrap().recenter();
+                    commands.send(PolytopeCommand::RecenterByGravicenter);
+                }
+
+                // Moves a polytope so that the area-weighted centroid of its
+                // faces is at the origin.
+                if ui.button("Recenter by area centroid").clicked() {
+                    commands.send(PolytopeCommand::RecenterByAreaCentroid);
                 }
                 
                 ui.separator();
                 
                 // Rotates a polytope around the origin.
                 if ui.button("Rotate...").clicked() {
-                    rotate_window.open();
+                    commands.send(PolytopeCommand::open::<RotateWindow>());
                 }
                 
                 //Rotates a polytope around the origin along a given plane intersecting the origin.
                 if ui.button("Rotate with plane...").clicked() {
-                    plane_window.open();
+                    commands.send(PolytopeCommand::open::<PlaneWindow>());
                 }
                 
             });
 
             // Operations on polytopes.
-            menu::menu(ui, "Operations", |ui| {
+            menu::menu(ui, t!(lang, "menu.operations"), |ui| {
                 // Converts the active polytope into its dual.
                 if advanced(&keyboard) {
-                    if ui.button("Dual...").clicked() {
-                        dual_window.open();
-                    }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Dual").clicked() {
-                        match p.try_dual_mut() {
-                            Ok(_) => {
-                                poly_name.0 = format!("Dual of {}", poly_name.0);
-                                println!("Dual succeeded.")
-                            },
-                            Err(err) => eprintln!("Dual failed: {}", err),
-                        }
+                    if ui.button(format!("{}...", t!(lang, "ops.dual"))).clicked() {
+                        commands.send(PolytopeCommand::open::<DualWindow>());
                     }
+                } else if ui.button(t!(lang, "ops.dual")).clicked() {
+                    commands.send(PolytopeCommand::Dual);
                 }
 
                 ui.separator();
 
                 // Converts the active polytope into its Petrial.
-                if ui.button("Petrial").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        if p.petrial_mut() {
-                            poly_name.0 = format!("Petrial of {}", poly_name.0);
-                            println!("Petrial succeeded.");
-                        } else {
-                            eprintln!("Petrial failed.");
-                        }
-                    }
+                if ui.button(t!(lang, "ops.petrial")).clicked() {
+                    commands.send(PolytopeCommand::Petrial);
                 }
 
                 // Converts the active polytope into its Petrie polygon.
-                if ui.button("Petrie polygon").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        p.element_sort();
-                        let flag = p.first_flag();
-                        match p.petrie_polygon_with(flag) {
-                            Some(q) => {
-                                *p = q;
-                                poly_name.0 = format!("Petrie polygon of {}", poly_name.0);
-                                println!("Petrie polygon succeeded.")
-                            }
-                            None => eprintln!("Petrie polygon failed."),
-                        }
-                    }
+                if ui.button(t!(lang, "ops.petrie_polygon")).clicked() {
+                    commands.send(PolytopeCommand::PetriePolygon);
+                }
+
+                // Identifies the opposite vertices of a centrally symmetric
+                // polytope, like when building a hemicube from a cube.
+                if ui.button(t!(lang, "ops.antipodal_quotient")).clicked() {
+                    commands.send(PolytopeCommand::AntipodalQuotient);
+                }
+
+                // Deletes a vertex or a facet of the polytope, and closes up
+                // the hole.
+                if ui.button(t!(lang, "ops.delete_element")).clicked() {
+                    commands.send(PolytopeCommand::open::<EditWindow>());
                 }
 
                 ui.separator();
 
                 // Makes a pyramid out of the current polytope.
                 if advanced(&keyboard) {
-                    if ui.button("Pyramid...").clicked() {
-                        pyramid_window.open();
-                    }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Pyramid").clicked() {
-                        *p = p.pyramid();
-                        poly_name.0 = format!("Pyramid of {}", poly_name.0);
+                    if ui.button(format!("{}...", t!(lang, "ops.pyramid"))).clicked() {
+                        commands.send(PolytopeCommand::open::<PyramidWindow>());
                     }
+                } else if ui.button(t!(lang, "ops.pyramid")).clicked() {
+                    commands.send(PolytopeCommand::Pyramid);
                 }
 
                 // Makes a prism out of the current polytope.
                 if advanced(&keyboard) {
-                    if ui.button("Prism...").clicked() {
-                        prism_window.open();
-                    }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Prism").clicked() {
-                        *p = p.prism();
-                        poly_name.0 = format!("Prism of {}", poly_name.0);
+                    if ui.button(format!("{}...", t!(lang, "ops.prism"))).clicked() {
+                        commands.send(PolytopeCommand::open::<PrismWindow>());
                     }
+                } else if ui.button(t!(lang, "ops.prism")).clicked() {
+                    commands.send(PolytopeCommand::Prism);
+                }
+
+                // Stacks scaled copies of the current polytope into a tower.
+                if ui.button(t!(lang, "ops.tower")).clicked() {
+                    commands.send(PolytopeCommand::open::<TowerWindow>());
                 }
 
                 // Makes a tegum out of the current polytope.
                 if advanced(&keyboard) {
-                    if ui.button("Tegum...").clicked() {
-                        tegum_window.open();
-                    }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Tegum").clicked() {
-                        *p = p.tegum();
-                        poly_name.0 = format!("Tegum of {}", poly_name.0);
+                    if ui.button(format!("{}...", t!(lang, "ops.tegum"))).clicked() {
+                        commands.send(PolytopeCommand::open::<TegumWindow>());
                     }
+                } else if ui.button(t!(lang, "ops.tegum")).clicked() {
+                    commands.send(PolytopeCommand::Tegum);
                 }
 
                 // Converts the active polytope into its antiprism.
                 if advanced(&keyboard) {
-                    if ui.button("Antiprism...").clicked() {
-                        antiprism_window.open();
-                    }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Antiprism").clicked() {
-                        match p.try_antiprism() {
-                            Ok(q) => {
-                                *p = q;
-                                poly_name.0 = format!("Antiprism of {}", poly_name.0);
-                            },
-                            Err(err) => eprintln!("Antiprism failed: {}", err),
-                        }
+                    if ui.button(format!("{}...", t!(lang, "ops.antiprism"))).clicked() {
+                        commands.send(PolytopeCommand::open::<AntiprismWindow>());
                     }
+                } else if ui.button(t!(lang, "ops.antiprism")).clicked() {
+                    commands.send(PolytopeCommand::Antiprism);
                 }
 
                 // Converts the active polytope into its ditope.
-                if ui.button("Ditope").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        p.ditope_mut();
-                        poly_name.0 = format!("Ditope of {}", poly_name.0);
-                        println!("Ditope succeeded!");
-                    }
+                if ui.button(t!(lang, "ops.ditope")).clicked() {
+                    commands.send(PolytopeCommand::Ditope);
                 }
 
                 // Converts the active polytope into its hosotope.
-                if ui.button("Hosotope").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        p.hosotope_mut();
-                        poly_name.0 = format!("Hosotope of {}", poly_name.0);
-                        println!("Hosotope succeeded!");
-                    }
+                if ui.button(t!(lang, "ops.hosotope")).clicked() {
+                    commands.send(PolytopeCommand::Hosotope);
                 }
                 
                 ui.separator();
 
                 // Opens the window to make duopyramids.
-                if ui.button("Duopyramid...").clicked() {
-                    duopyramid_window.open();
+                if ui.button(t!(lang, "ops.duopyramid")).clicked() {
+                    commands.send(PolytopeCommand::open::<DuopyramidWindow>());
                 }
 
                 // Opens the window to make duoprisms.
-                if ui.button("Duoprism...").clicked() {
-                    duoprism_window.open();
+                if ui.button(t!(lang, "ops.duoprism")).clicked() {
+                    commands.send(PolytopeCommand::open::<DuoprismWindow>());
                 }
 
                 // Opens the window to make duotegums.
-                if ui.button("Duotegum...").clicked() {
-                    duotegum_window.open();
+                if ui.button(t!(lang, "ops.duotegum")).clicked() {
+                    commands.send(PolytopeCommand::open::<DuotegumWindow>());
                 }
 
                 // Opens the window to make duocombs.
-                if ui.button("Duocomb...").clicked() {
-                    duocomb_window.open();
+                if ui.button(t!(lang, "ops.duocomb")).clicked() {
+                    commands.send(PolytopeCommand::open::<DuocombWindow>());
                 }
 
                 // Opens the window to make star products.
-                if ui.button("Star product...").clicked() {
-                    star_window.open();
+                if ui.button(t!(lang, "ops.star_product")).clicked() {
+                    commands.send(PolytopeCommand::open::<StarWindow>());
                 }
 
                 // Opens the window to make compounds.
-                if ui.button("Compound...").clicked() {
-                    compound_window.open();
+                if ui.button(t!(lang, "ops.compound")).clicked() {
+                    commands.send(PolytopeCommand::open::<CompoundWindow>());
                 }
 
-                ui.separator();
+                // Opens the window to intersect convex polytopes.
+                if ui.button(t!(lang, "ops.intersect")).clicked() {
+                    commands.send(PolytopeCommand::open::<IntersectWindow>());
+                }
 
-                if ui.button("Truncate...").clicked() {
-                    truncate_window.open();
+                // Opens the window to morph polytopes.
+                if ui.button(t!(lang, "ops.morph")).clicked() {
+                    commands.send(PolytopeCommand::open::<MorphWindow>());
                 }
-                
+
                 ui.separator();
 
-                if ui.button("Identify coplanar facets").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        *p = p.fuse_facets();
-                        println!("Fuse succeeded!");
-                    }
+                if ui.button(t!(lang, "ops.truncate")).clicked() {
+                    commands.send(PolytopeCommand::open::<TruncateWindow>());
                 }
-            });
 
-            // Toggles cross-section mode.
-            if ui.button("Cross-section").clicked() {
-                match section_state.as_mut() {
-                    // The view is active, but will be inactivated.
-                    SectionState::Active {
-                        original_polytope,
-                        original_name,
-                        ..
-                    } => {
-                        *query.iter_mut().next().unwrap() = original_polytope.clone();
-                        poly_name.0 = original_name.clone();
-                        section_state.close();
-                    }
+                // Opens the window to canonicalize a convex polyhedron.
+                if ui.button(t!(lang, "ops.canonicalize")).clicked() {
+                    commands.send(PolytopeCommand::open::<CanonicalizeWindow>());
+                }
 
-                    // The view is inactive, but will be activated.
-                    SectionState::Inactive => {
-                        let mut p = query.iter_mut().next().unwrap();
+                // Opens the window to snap the coordinates to exact values.
+                if ui.button(t!(lang, "ops.snap")).clicked() {
+                    commands.send(PolytopeCommand::open::<SnapWindow>());
+                }
 
-                        if p.rank() < 4 { // Cannot slice a polygon or lower.
-                            println!("Slicing polytopes of rank less than 3 is not supported!");
-                        } else {
-                            p.flatten();
+                // Opens the window to rescale into another unit or size.
+                if ui.button(t!(lang, "ops.units")).clicked() {
+                    commands.send(PolytopeCommand::open::<UnitsWindow>());
+                }
+
+                // Opens the window to unfold a polyhedron into a net.
+                if ui.button(t!(lang, "ops.unfold")).clicked() {
+                    commands.send(PolytopeCommand::open::<UnfoldWindow>());
+                }
+                
+                ui.separator();
+
+                if ui.button(t!(lang, "ops.fuse_facets")).clicked() {
+                    commands.send(PolytopeCommand::FuseFacets);
+                }
 
-                            // The default direction is in the last coordinate axis.
-                            let dim = p.dim_or();
-                            let mut direction = Vector::zeros(dim);
-                            if dim > 0 {
-                                direction[dim - 1] = 1.0;
+                // Plays the custom operations loaded from the configuration
+                // directory.
+                if !custom_operations.0.is_empty() {
+                    ui.separator();
+
+                    ui.collapsing(t!(lang, "ops.custom"), |ui| {
+                        for (idx, op) in custom_operations.0.iter().enumerate() {
+                            if ui.button(&op.name).clicked() {
+                                commands.send(PolytopeCommand::Custom(idx));
                             }
-    
-                            let minmax = p.minmax(direction.clone()).unwrap_or((-1.0, 1.0));
-                            let original_polytope = p.clone();
-    
-                            section_state.open(original_polytope, poly_name.0.clone(), vec![minmax]);
-                            section_direction.clear();
-                            section_direction.push(SectionDirection{0:direction});
                         }
-                    }
-                };
+                    });
+                }
+            });
+
+            // Toggles cross-section mode.
+            if ui.button(t!(lang, "menu.cross_section")).clicked() {
+                commands.send(PolytopeCommand::CrossSectionToggle);
             }
 
-            menu::menu(ui, "Faceting", |ui| {
-                if ui.button("Enumerate facetings").clicked() {
-                    if let Some(p) = query.iter_mut().next() {
-                        let mut vertices_thing = (Vertices(vec![]), vec![]);
-                        if let GroupEnum2::FromSlot(slot) = faceting_settings.group {
-                            vertices_thing = Vertices(p.vertices.clone()).copy_by_symmetry(slot.to_poly(&mut memory, &p).unwrap().clone().get_symmetry_group().unwrap().0);
-                        }
-                        let facetings = p.clone().faceting(
-                            match faceting_settings.group {
-                                GroupEnum2::Chiral(_) => p.vertices.clone(),
-                                GroupEnum2::FromSlot(_) => vertices_thing.0.0
-                            },
-                            match faceting_settings.group {
-                                GroupEnum2::Chiral(chiral) => GroupEnum::Chiral(chiral),
-                                GroupEnum2::FromSlot(_) => GroupEnum::VertexMap(vertices_thing.1)
-                            },
-                            faceting_settings.any_single_edge_length,
-                            if faceting_settings.do_min_edge_length {Some(faceting_settings.min_edge_length)} else {None}, 
-                            if faceting_settings.do_max_edge_length {Some(faceting_settings.max_edge_length)} else {None}, 
-                            if faceting_settings.do_min_inradius {Some(faceting_settings.min_inradius)} else {None}, 
-                            if faceting_settings.do_max_inradius {Some(faceting_settings.max_inradius)} else {None}, 
-                            faceting_settings.exclude_hemis,
-                            faceting_settings.only_below_vertex,
-                            if faceting_settings.max_facet_types == 0 {None} else {Some(faceting_settings.max_facet_types)},
-                            if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)},
-                            faceting_settings.uniform,
-                            faceting_settings.compounds,
-                            faceting_settings.mark_fissary,
-                            faceting_settings.label_facets,
-                            faceting_settings.save,
-                            faceting_settings.save_facets,
-                            faceting_settings.save_to_file,
-                            faceting_settings.file_path.clone(),
-                        );
-                        for faceting in facetings {
-                            memory.push(faceting);
-                        }
-                    }
+            menu::menu(ui, t!(lang, "menu.faceting"), |ui| {
+                if ui.button(t!(lang, "faceting.enumerate")).clicked() {
+                    commands.send(PolytopeCommand::EnumerateFacetings);
                 }
                 
                 ui.separator();
 
-                if ui.button("Settings...").clicked() {
-                    faceting_settings.open();
+                if ui.button(t!(lang, "faceting.settings")).clicked() {
+                    commands.send(PolytopeCommand::open::<FacetingSettings>());
                 }
             });
 
-            if ui.button("Memory").clicked() {
+            if ui.button(t!(lang, "menu.memory")).clicked() {
                 show_memory.0 = !show_memory.0;
             }
-            memory.show(&mut query, &mut poly_name, &egui_ctx, &mut show_memory.0);
+            memory.show(&mut query, &mut shared, &mut poly_name, &egui_ctx, &mut show_memory.0, *lang);
 
-            if ui.button("Help").clicked() {
+            if ui.button(t!(lang, "menu.help")).clicked() {
                 show_help.0 = !show_help.0;
             }
-            egui::Window::new("Help")
+            egui::Window::new(t!(lang, "menu.help"))
+                .id(egui::Id::new("menu.help"))
                 .open(&mut show_help.0)
                 .resizable(false)
                 .show(egui_ctx.ctx(), |ui| {
-                    ui.heading("Hotkeys");
-                    ui.label("V: toggle faces\nB: toggle wireframe");
+                    ui.heading(t!(lang, "help.hotkeys"));
+                    ui.label(t!(lang, "help.hotkeys_text"));
                     ui.separator();
-                    ui.heading("Camera");
-                    ui.label("WSADRF: move\nQE: roll\nX: reset\nMouse wheel: zoom\nHold Ctrl: move faster\nHold Shift: move slower");
+                    ui.heading(t!(lang, "help.camera"));
+                    ui.label(t!(lang, "help.camera_text"));
                     ui.separator();
-                    ui.heading("UI");
-                    ui.label("Hold Ctrl: extra options in some menus\nHold Shift: move number sliders slower");
+                    ui.heading(t!(lang, "help.ui"));
+                    ui.label(t!(lang, "help.ui_text"));
                     ui.separator();
-                    ui.heading("Right panel");
-                    ui.label("Generate: computes the element types of the loaded polytope\nLoad: loads the polytope whose element types are being listed");
+                    ui.heading(t!(lang, "help.right_panel"));
+                    ui.label(t!(lang, "help.right_panel_text"));
                     ui.separator();
                 });
 
             // Background color picker.
 
             // The current background color.
-            let [r, g, b, a] = colors.0.0.as_rgba_f32().map(|c| (c * 255.0) as u8);
+            let [r, g, b, a] = clear_color.0.as_rgba_f32().map(|c| (c * 255.0) as u8);
             let color = egui::Color32::from_rgba_premultiplied(r, g, b, a);
 
             // The new background color.
@@ -870,53 +1180,7 @@ pub fn show_top_panel(
 
             // Updates the background color if necessary.
             if color != new_color {
-                colors.0.0 = Color::rgb(
-                    new_color.r() as f32 / 255.0,
-                    new_color.g() as f32 / 255.0,
-                    new_color.b() as f32 / 255.0,
-                );
-            }
-
-            // Mesh color picker.
-
-            // The current mesh color.
-            let [r, g, b, a] = colors.1.0.as_rgba_f32().map(|c| (c * 255.0) as u8);
-            let color = egui::Color32::from_rgba_premultiplied(r, g, b, a);
-
-            // The new mesh color.
-            let mut new_color = color;
-            egui::color_picker::color_edit_button_srgba(
-                ui,
-                &mut new_color,
-                egui::color_picker::Alpha::Opaque,
-            );
-
-            // Updates the mesh color if necessary.
-            if color != new_color {
-                colors.1.0 = Color::rgb(
-                    new_color.r() as f32 / 255.0,
-                    new_color.g() as f32 / 255.0,
-                    new_color.b() as f32 / 255.0,
-                );
-            }
-
-            // Wireframe color picker.
-
-            // The current wireframe color.
-            let [r, g, b, a] = colors.2.0.as_rgba_f32().map(|c| (c * 255.0) as u8);
-            let color = egui::Color32::from_rgba_premultiplied(r, g, b, a);
-
-            // The new wireframe color.
-            let mut new_color = color;
-            egui::color_picker::color_edit_button_srgba(
-                ui,
-                &mut new_color,
-                egui::color_picker::Alpha::Opaque,
-            );
-
-            // Updates the wireframe color if necessary.
-            if color != new_color {
-                colors.2.0 = Color::rgb(
+                clear_color.0 = Color::rgb(
                     new_color.r() as f32 / 255.0,
                     new_color.g() as f32 / 255.0,
                     new_color.b() as f32 / 255.0,
@@ -934,11 +1198,107 @@ pub fn show_top_panel(
     });
 }
 
+/// The system that enumerates the facetings of the selected polytope when
+/// [`PolytopeCommand::EnumerateFacetings`] is sent.
+pub fn enumerate_facetings(
+    mut commands: EventReader<'_, '_, PolytopeCommand>,
+    query: Query<'_, '_, &Concrete, With<Selected>>,
+    faceting_settings: Res<'_, FacetingSettings>,
+    mut memory: ResMut<'_, Memory>,
+) {
+    for _ in commands
+        .iter()
+        .filter(|&&command| command == PolytopeCommand::EnumerateFacetings)
+    {
+        if let Some(p) = query.iter().next() {
+            let mut vertices_thing = (Vertices(vec![]), vec![]);
+            if let GroupEnum2::FromSlot(slot) = faceting_settings.group {
+                vertices_thing = Vertices(p.vertices.clone()).copy_by_symmetry(slot.to_poly(&mut memory, p).unwrap().clone().get_symmetry_group().unwrap().0);
+            }
+            let facetings = p.clone().faceting(
+                match faceting_settings.group {
+                    GroupEnum2::Chiral(_) => p.vertices.clone(),
+                    GroupEnum2::FromSlot(_) => vertices_thing.0.0
+                },
+                match faceting_settings.group {
+                    GroupEnum2::Chiral(chiral) => GroupEnum::Chiral(chiral),
+                    GroupEnum2::FromSlot(_) => GroupEnum::VertexMap(vertices_thing.1)
+                },
+                faceting_settings.any_single_edge_length,
+                if faceting_settings.do_min_edge_length {Some(faceting_settings.min_edge_length)} else {None}, 
+                if faceting_settings.do_max_edge_length {Some(faceting_settings.max_edge_length)} else {None}, 
+                if faceting_settings.do_min_inradius {Some(faceting_settings.min_inradius)} else {None}, 
+                if faceting_settings.do_max_inradius {Some(faceting_settings.max_inradius)} else {None}, 
+                faceting_settings.exclude_hemis,
+                faceting_settings.only_below_vertex,
+                if faceting_settings.max_facet_types == 0 {None} else {Some(faceting_settings.max_facet_types)},
+                if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)},
+                faceting_settings.uniform,
+                faceting_settings.compounds,
+                faceting_settings.mark_fissary,
+                faceting_settings.label_facets,
+                faceting_settings.save,
+                faceting_settings.save_facets,
+                faceting_settings.save_to_file,
+                faceting_settings.file_path.clone(),
+            );
+            for faceting in facetings {
+                memory.push(faceting);
+            }
+        }
+    }
+}
+
+/// The system that saves the polytopes in memory one after the other, after
+/// [`PolytopeCommand::ExportMemory`] is sent.
+pub fn export_memory(
+    mut commands: EventReader<'_, '_, PolytopeCommand>,
+    mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+    mut export_memory: ResMut<'_, ExportMemory>,
+    memory: Res<'_, Memory>,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut dialog_queue: ResMut<'_, DialogQueue>,
+) {
+    if commands
+        .iter()
+        .filter(|&&command| command == PolytopeCommand::ExportMemory)
+        .count()
+        != 0
+    {
+        export_memory.0 = true;
+        export_memory.1 = 0;
+    }
+
+    if export_memory.0 {
+        let idx = export_memory.1;
+        if idx == memory.len() {
+            export_memory.1 = 0;
+            export_memory.0 = false;
+        }
+        else {
+            if let Some((poly, label)) = &memory[idx] {
+                if let Some(mut p) = query.iter_mut().next() {
+                    *p = (**poly).clone();
+                    let name = match label {
+                        None => {
+                            format!("polytope {}", idx)
+                        }
+                        Some(a) => a.to_string()
+                    };
+                    poly_name.0 = name.clone();
+                    dialog_queue.push(DialogRequest::SaveAs { suggested_name: name });
+                }
+            }
+            export_memory.1 += 1;
+        }
+    }
+}
+
 /// Shows any secondary views that are active. Currently, just shows the
 /// cross-section view.
 fn show_views(
     ui: &mut Ui,
-    mut query: Query<'_, '_, &mut Concrete>,
+    mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
     poly_name: &mut ResMut<'_, PolyName>,
     mut section_state: ResMut<'_, SectionState>,
     mut section_direction: ResMut<'_, Vec<SectionDirection>>
@@ -949,13 +1309,17 @@ fn show_views(
         hyperplane_pos,
         flatten,
         lock,
+        slice_count,
+        rotation,
         ..
     } = &(*section_state)
     {
+        let rotation = rotation.clone();
         let minmax = minmax.clone();
         let hyperplane_pos = hyperplane_pos.clone();
         let flatten = flatten.clone();
         let lock = lock.clone();
+        let slice_count = *slice_count;
 
         ui.label("Cross section settings:");
         ui.spacing_mut().slider_width = ui.available_width() / 3.0;
@@ -964,12 +1328,18 @@ fn show_views(
         let mut i = 0;
 
         while i < hyperplane_pos.len() {
+            // A rotating hyperplane is controlled by its angle instead.
+            if let (0, Some(rotation)) = (i, &rotation) {
+                show_rotation(ui, rotation, section_direction[0].0.len(), &mut section_state);
+                i = i + 1;
+                continue;
+            }
             
             let mut new_hyperplane_pos = hyperplane_pos[i];
             ui.add(
                 egui::Slider::new(
                     &mut new_hyperplane_pos,
-                    (minmax[i].0 + 0.0000001)..=(minmax[i].1 - 0.0000001), // We do this to avoid empty slices.
+                    (minmax[i].0 + SLIDER_MARGIN)..=(minmax[i].1 - SLIDER_MARGIN), // We do this to avoid empty slices.
                 )
                 .text("Slice depth")
                 .prefix("pos: "),
@@ -997,12 +1367,23 @@ fn show_views(
                 if ui.button("Diagonal").clicked() {
                     new_direction = Point::from_element(new_direction.len(), 1.0/(new_direction.len() as f64).sqrt());
                 }
+
+                // Snaps the direction to a coordinate axis.
+                for axis in 0..new_direction.len() {
+                    if ui.small_button(format!("x{}", axis)).clicked() {
+                        new_direction = SectionDirection::axis(new_direction.len(), axis).0;
+                    }
+                }
+
+                if ui.small_button("Flip").clicked() {
+                    new_direction = -new_direction.clone();
+                }
             });
             
             // Updates the slicing direction.
             #[allow(clippy::float_cmp)]
             if section_direction[i].0 != new_direction {
-                section_direction[i].0 = new_direction;
+                section_direction[i].set(new_direction);
             }
 
             i = i + 1;
@@ -1024,13 +1405,8 @@ fn show_views(
                     }
                 )).clicked() {
                 let p = query.iter_mut().next().unwrap();
-                let dim = p.dim_or();
-                let mut direction = Vector::zeros(dim);
-                if dim > 0 {
-                    direction[dim - 1] = 1.0;
-                }
                 section_state.add();
-                section_direction.push(SectionDirection{0:direction});
+                section_direction.push(SectionDirection::last_axis(p.dim_or()));
             }
             // Cross sections on a higher dimension
             if ui.add(egui::Button::new("-").enabled(section_direction.len() > 1)).clicked() {
@@ -1061,17 +1437,87 @@ fn show_views(
                     unreachable!()
                 }
             }
+
+            // Rotates the first slicing hyperplane about the center.
+            let dim = section_direction[0].0.len();
+            let mut rotate = rotation.is_some();
+            ui.add(egui::Checkbox::new(&mut rotate, "Rotate"));
+
+            if rotate != rotation.is_some() && (!rotate || dim >= 2) {
+                if let SectionState::Active { rotation, .. } = section_state.as_mut() {
+                    *rotation = if rotate {
+                        Some(SectionRotation::new((dim - 1, 0)))
+                    } else {
+                        None
+                    };
+                } else {
+                    unreachable!()
+                }
+            }
+
+            let mut new_slice_count = slice_count;
+            ui.add(egui::Slider::new(&mut new_slice_count, 1..=MAX_SLICE_COUNT).text("Slices"));
+
+            // Updates the number of parallel slices.
+            if slice_count != new_slice_count {
+                if let SectionState::Active { slice_count, .. } = section_state.as_mut() {
+                    *slice_count = new_slice_count;
+                } else {
+                    unreachable!()
+                }
+            }
         });
+
+        // Shows which element of the original polytope a face of the
+        // cross-section lies in.
+        let face_count = query
+            .iter_mut()
+            .next()
+            .filter(|p| p.rank() >= 4)
+            .map_or(0, |p| p.face_count());
+
+        if face_count > 0 {
+            if let SectionState::Active {
+                provenance: Some(provenance),
+                inspected_face,
+                ..
+            } = &*section_state
+            {
+                let mut new_face = (*inspected_face).min(face_count - 1);
+                let source = provenance.get(3, new_face);
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut new_face).clamp_range(0..=face_count - 1));
+
+                    match source {
+                        Some(source) => ui.label(format!("Face from {} of the original", source)),
+                        None => ui.label("Face of unknown origin"),
+                    };
+                });
+
+                if new_face != *inspected_face {
+                    if let SectionState::Active { inspected_face, .. } = section_state.as_mut() {
+                        *inspected_face = new_face;
+                    }
+                }
+            }
+        }
     }
 
-    if section_state.is_changed() {
+    if section_state.is_changed() || section_direction.is_changed() {
         if let SectionState::Active {
             original_polytope,
             original_name,
             hyperplane_pos,
             minmax,
+            minmax_keys,
             flatten,
             lock,
+            provenance,
+            slice_count,
+            slices,
+            rotation,
+            ..
         } = section_state.as_mut() {
 
             // We don't update the view if it's locked.
@@ -1080,35 +1526,314 @@ fn show_views(
             }
 
             if let Some(mut p) = query.iter_mut().next() {
-                let mut r = original_polytope.clone();
-                let mut i = 0;
-                while i < hyperplane_pos.len() {
-                    let hyp_pos = hyperplane_pos[i];
+                // The first slice is taken directly from the original
+                // polytope, so that it's never cloned.
+                let mut r: Option<Concrete> = None;
+                let mut r_provenance = None;
+                let mut other_slices = Vec::new();
+                minmax_keys.resize(hyperplane_pos.len(), Vec::new());
+
+                for i in 0..hyperplane_pos.len() {
+                    let current = r.as_ref().unwrap_or(&**original_polytope);
+
+                    if let Some(dim) = current.dim() {
+                        // The direction might be stale if the dimension of
+                        // the polytope being sliced changed.
+                        if section_direction[i].0.len() != dim {
+                            section_direction[i].resize_to(dim);
+                        }
+                        // A direction too close to zero can't define a
+                        // hyperplane, so we stop slicing.
+                        if !section_direction[i].is_valid() {
+                            println!("Note: the cross-section direction is too close to zero.");
+                            break;
+                        }
+                        // The rotating hyperplane is taken from the heights
+                        // along the plane of rotation, which are only
+                        // computed once.
+                        let rotating = match rotation.as_mut() {
+                            Some(rotation) if i == 0 => rotation
+                                .precomputation(current)
+                                .map(|section| (section, rotation.angle)),
+                            _ => None,
+                        };
+                        if let Some((section, angle)) = &rotating {
+                            section_direction[0].0 = section.normal(*angle);
+                            hyperplane_pos[0] = section.pos(*angle);
+                        }
 
-                    if let Some(dim) = r.dim() {
-                        let hyperplane = Hyperplane::new(section_direction[i].0.clone(), hyp_pos);
-                        minmax[i] = r
-                            .minmax(section_direction[i].0.clone())
-                            .unwrap_or((-1.0, 1.0));
+                        let direction = &section_direction[i].0;
+
+                        // The range of the slider only depends on the
+                        // directions, on the flattening, and on the earlier
+                        // slices.
+                        let key =
+                            minmax_key(&section_direction[..=i], &hyperplane_pos[..i], *flatten);
+                        if minmax_keys[i] != key {
+                            minmax[i] = current.minmax(direction.clone()).unwrap_or((-1.0, 1.0));
+                            minmax[i].0 += f64::EPS;
+                            minmax_keys[i] = key;
+
+                            // The hyperplane could otherwise be left outside
+                            // of the new range.
+                            hyperplane_pos[i] = clamp_to_slider(hyperplane_pos[i], minmax[i]);
+                        }
+
+                        let hyperplane = Hyperplane::new(direction.clone(), hyperplane_pos[i]);
+                        let (mut slice, slice_provenance) = match &rotating {
+                            Some((section, angle)) => {
+                                section.cross_section_with_provenance(current, *angle)
+                            }
+                            None => current.cross_section_with_provenance(&hyperplane),
+                        };
 
-                        minmax[i].0 += f64::EPS;
-                        let mut slice = r.cross_section(&hyperplane);
+                        // Traces the elements back to the original polytope.
+                        r_provenance = Some(match r_provenance {
+                            Some(earlier) => slice_provenance.compose(&earlier),
+                            None => slice_provenance,
+                        });
 
                         if *flatten {
-                            slice.flatten_into(&hyperplane.subspace);
-                            slice.recenter_with(
-                                &hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))),
-                            );
+                            flatten_slice(&mut slice, &hyperplane, dim);
+                        }
+
+                        // The other parallel slices through the last
+                        // hyperplane, evenly spaced around the middle one.
+                        if i + 1 == hyperplane_pos.len() && *slice_count > 1 {
+                            let step = (minmax[i].1 - minmax[i].0) / (*slice_count + 1) as f64;
+                            let precomputation = SlicePrecomputation::new(current, direction);
+
+                            other_slices = slice_offsets(*slice_count)
+                                .map(|j| {
+                                    let pos = hyperplane_pos[i] + j as f64 * step;
+                                    let mut other = precomputation.cross_section(pos);
+                                    if *flatten {
+                                        let hyperplane = Hyperplane::new(direction.clone(), pos);
+                                        flatten_slice(&mut other, &hyperplane, dim);
+                                    }
+
+                                    (j, other)
+                                })
+                                .collect();
                         }
 
-                        r = slice;
+                        r = Some(slice);
                     }
-                    i += 1;
                 }
-                *p = r;
+
+                *p = r.unwrap_or_else(|| (**original_polytope).clone());
+                *provenance = r_provenance;
+                *slices = Arc::new(other_slices);
             }
 
             poly_name.0 = format!("Slice of {}", original_name);
         }
     }
 }
+
+/// Shows the settings of the rotating hyperplane of the cross-section view:
+/// its angle, its speed, and the axes it rotates between.
+fn show_rotation(
+    ui: &mut Ui,
+    rotation: &SectionRotation,
+    dim: usize,
+    section_state: &mut ResMut<'_, SectionState>,
+) {
+    let mut new_rotation = rotation.clone();
+    let mut changed = ui
+        .add(
+            egui::Slider::new(&mut new_rotation.angle, 0.0..=std::f64::consts::TAU)
+                .text("Angle"),
+        )
+        .changed();
+
+    ui.horizontal(|ui| {
+        changed |= ui
+            .add(egui::Slider::new(&mut new_rotation.speed, -2.0..=2.0).text("Speed"))
+            .changed();
+        changed |= ui.checkbox(&mut new_rotation.playing, "Play").changed();
+    });
+
+    // Picking the axis the other one is on swaps them.
+    let (from, to) = rotation.axes;
+    for (label, first) in [("From", true), ("Towards", false)] {
+        ui.horizontal(|ui| {
+            ui.label(label);
+
+            for axis in 0..dim {
+                let current = if first { from } else { to };
+                if ui.selectable_label(current == axis, format!("x{}", axis)).clicked() {
+                    new_rotation.axes = match (first, axis) {
+                        (true, axis) if axis == to => (to, from),
+                        (true, axis) => (axis, to),
+                        (false, axis) if axis == from => (to, from),
+                        (false, axis) => (from, axis),
+                    };
+                    changed = true;
+                }
+            }
+        });
+    }
+
+    if changed {
+        if let SectionState::Active { rotation, .. } = section_state.as_mut() {
+            *rotation = Some(new_rotation);
+        }
+    }
+}
+
+/// Advances the angle of the rotating hyperplane of the cross-section view,
+/// while it plays and the view isn't locked.
+pub fn rotate_section(time: Res<'_, Time>, mut section_state: ResMut<'_, SectionState>) {
+    let playing = matches!(
+        &*section_state,
+        SectionState::Active {
+            rotation: Some(rotation),
+            lock: false,
+            ..
+        } if rotation.playing
+    );
+    if !playing {
+        return;
+    }
+
+    if let SectionState::Active {
+        rotation: Some(rotation),
+        ..
+    } = section_state.as_mut()
+    {
+        rotation.angle = (rotation.angle + rotation.speed * time.delta_seconds_f64())
+            .rem_euclid(std::f64::consts::TAU);
+    }
+}
+
+/// Flattens a slice into the hyperplane it was taken through, and moves it so
+/// that the projection of the origin onto the hyperplane becomes the origin.
+fn flatten_slice(slice: &mut Concrete, hyperplane: &Hyperplane, dim: usize) {
+    slice.flatten_into(&hyperplane.subspace);
+    slice.recenter_with_mut(&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))));
+}
+
+/// Spawns, updates and despawns the polytopes that show the slices of the
+/// cross-section view besides the middle one. They're laid out in a row next
+/// to the selected polytope, in the order of their positions.
+#[allow(clippy::too_many_arguments)]
+pub fn update_section_slices(
+    mut commands: Commands<'_, '_>,
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut materials: ResMut<'_, Assets<StandardMaterial>>,
+    section_state: Res<'_, SectionState>,
+    selected: Query<'_, '_, (&Transform, &RenderFrame, &PolytopeStyle), With<Selected>>,
+    mut slice_entities: Query<
+        '_,
+        '_,
+        (Entity, &SectionSlice, &mut Concrete, &mut Transform, &RenderFrame),
+        Without<Selected>,
+    >,
+    projection_type: Res<'_, ProjectionType>,
+    mesh_options: Res<'_, MeshOptions>,
+) {
+    if !section_state.is_changed() {
+        return;
+    }
+
+    let (slices, radius) = match &*section_state {
+        SectionState::Active {
+            original_polytope,
+            slices,
+            ..
+        } => (
+            &slices[..],
+            original_polytope.vertices.iter().map(|v| v.norm()).fold(1.0, f64::max) as f32,
+        ),
+        SectionState::Inactive => (&[][..], 1.0),
+    };
+
+    let (origin, style) = match selected.iter().next() {
+        Some((transform, frame, style)) => (frame.placement(transform), *style),
+        None => return,
+    };
+    let translation = |j: isize| origin + Vec3::new(2.5 * radius * j as f32, 0.0, 0.0);
+
+    let mut spawned = 0;
+    for (entity, &SectionSlice(idx), mut poly, mut transform, frame) in slice_entities.iter_mut() {
+        match slices.get(idx) {
+            Some((j, slice)) => {
+                *poly = slice.clone();
+                *transform = frame.transform(translation(*j));
+                spawned += 1;
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+
+    for (idx, (j, slice)) in slices.iter().enumerate().skip(spawned) {
+        let entity = spawn_polytope(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            slice.clone(),
+            PolyName(format!("Slice {}", j)),
+            style,
+            translation(*j),
+            &projection_type,
+            &mesh_options,
+        );
+
+        commands.entity(entity).insert(SectionSlice(idx));
+    }
+}
+
+/// Returns the settings that the slider range of the last given direction
+/// depends on: every direction up to it, and the positions and flattening of
+/// the slices before it.
+fn minmax_key(
+    directions: &[SectionDirection],
+    hyperplane_pos: &[Float],
+    flatten: bool,
+) -> Vec<Float> {
+    let mut key: Vec<Float> = directions.iter().flat_map(|d| d.0.iter().copied()).collect();
+    key.extend_from_slice(hyperplane_pos);
+    key.push(if flatten { 1.0 } else { 0.0 });
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions inside the range of the slider are kept.
+    #[test]
+    fn clamp_inside() {
+        assert_eq!(clamp_to_slider(0.25, (-1.0, 1.0)), 0.25);
+        assert_eq!(clamp_to_slider(-0.5, (-1.0, 1.0)), -0.5);
+    }
+
+    /// Positions outside the range of the slider are moved to its ends.
+    #[test]
+    fn clamp_outside() {
+        assert_eq!(clamp_to_slider(3.0, (-1.0, 1.0)), 1.0 - SLIDER_MARGIN);
+        assert_eq!(clamp_to_slider(-3.0, (-1.0, 1.0)), -1.0 + SLIDER_MARGIN);
+        assert_eq!(clamp_to_slider(1.0, (-1.0, 1.0)), 1.0 - SLIDER_MARGIN);
+    }
+
+    /// A range too small for the margins clamps to its midpoint.
+    #[test]
+    fn clamp_degenerate() {
+        assert_eq!(clamp_to_slider(5.0, (2.0, 2.0)), 2.0);
+        assert_eq!(clamp_to_slider(-5.0, (0.0, SLIDER_MARGIN)), SLIDER_MARGIN / 2.0);
+    }
+
+    /// Directions too close to zero are ignored.
+    #[test]
+    fn direction_set() {
+        let mut direction = SectionDirection::axis(3, 1);
+        direction.set(Vector::zeros(3));
+        assert_eq!(direction.0, SectionDirection::axis(3, 1).0);
+
+        direction.set(Vector::from_vec(vec![0.0, 0.0, -2.0]));
+        assert_eq!(direction.0, -SectionDirection::last_axis(3).0);
+        assert!(direction.is_valid());
+        assert!(!SectionDirection::default().is_valid());
+    }
+}
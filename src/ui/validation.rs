@@ -0,0 +1,148 @@
+//! Checks every polytope whenever it changes, so that bugs in operations don't
+//! silently leave a corrupt polytope on screen.
+
+use std::collections::HashMap;
+
+use super::lang::SelectedLanguage;
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::abs::ValidationLevel;
+use serde::{Deserialize, Serialize};
+
+/// The plugin that checks the polytopes after they change.
+pub struct ValidationPlugin;
+
+impl Plugin for ValidationPlugin {
+    fn build(&self, app: &mut App) {
+        // The polytopes must be checked before they're drawn.
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            validate_changed_polytopes.system().before("update_peel"),
+        );
+    }
+}
+
+/// How polytopes are checked after they change. This is stored in the
+/// configuration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidationSettings {
+    /// How thoroughly polytopes are checked.
+    #[serde(default)]
+    pub level: ValidationLevel,
+
+    /// Whether an invalid polytope is replaced by the last valid one. This
+    /// keeps a copy of every valid polytope around.
+    #[serde(default)]
+    pub revert: bool,
+}
+
+/// Checks every polytope that changed, and prints out the error if it's
+/// invalid. If enabled, invalid polytopes are reverted to the last valid one,
+/// which is stored whenever a polytope passes the check.
+pub fn validate_changed_polytopes(
+    settings: Res<'_, ValidationSettings>,
+    lang: Res<'_, SelectedLanguage>,
+    mut last_valid: Local<'_, HashMap<Entity, Concrete>>,
+    mut polies: Query<'_, '_, (Entity, &mut Concrete), Changed<Concrete>>,
+    all_polies: Query<'_, '_, Entity, With<Concrete>>,
+) {
+    if settings.level == ValidationLevel::Off || !settings.revert {
+        last_valid.clear();
+    }
+    last_valid.retain(|&entity, _| all_polies.get(entity).is_ok());
+
+    for (entity, mut poly) in polies.iter_mut() {
+        match poly.abs.validate(settings.level) {
+            Ok(()) => {
+                if settings.level != ValidationLevel::Off && settings.revert {
+                    last_valid.insert(entity, poly.clone());
+                }
+            }
+
+            Err(err) => {
+                eprintln!("{}: {} ({:?})", t!(lang, "error.invalid"), err, err);
+
+                if let Some(valid) = last_valid.get(&entity) {
+                    *poly = valid.clone();
+                    eprintln!("{}", t!(lang, "validation.reverted"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miratope_core::{abs::Ranked, Polytope};
+
+    /// Builds an app that only checks polytopes, with a single square.
+    fn validation_app(settings: ValidationSettings) -> (App, Entity) {
+        let mut app = App::new();
+        app.insert_resource(settings)
+            .init_resource::<SelectedLanguage>()
+            .add_system(validate_changed_polytopes.system());
+
+        let entity = app.world.spawn().insert(Concrete::polygon(4)).id();
+        app.update();
+        (app, entity)
+    }
+
+    /// Breaks the polytope of an entity by removing a vertex from an edge.
+    fn corrupt(app: &mut App, entity: Entity) {
+        let mut poly = app.world.get_mut::<Concrete>(entity).unwrap();
+
+        // Safety: we're breaking the polytope on purpose.
+        let edge = unsafe { &mut poly.abs.ranks_mut()[2][0] };
+        edge.subs = vec![edge.subs[0]].into();
+    }
+
+    /// Returns the number of vertices of each edge of an entity's polytope.
+    fn edge_sizes(app: &App, entity: Entity) -> Vec<usize> {
+        let poly = app.world.get::<Concrete>(entity).unwrap();
+        (0..poly.edge_count())
+            .map(|idx| poly[(2, idx)].subs.len())
+            .collect()
+    }
+
+    /// An invalid polytope is reverted to the last valid one only if enabled,
+    /// and only when it fails the check at the chosen level.
+    #[test]
+    fn revert() {
+        let full = ValidationSettings {
+            level: ValidationLevel::Full,
+            revert: true,
+        };
+
+        let (mut app, entity) = validation_app(full);
+        corrupt(&mut app, entity);
+        app.update();
+        assert_eq!(edge_sizes(&app, entity), vec![2; 4]);
+
+        // A later valid polytope replaces the stored one.
+        *app.world.get_mut::<Concrete>(entity).unwrap() = Concrete::polygon(5);
+        app.update();
+        corrupt(&mut app, entity);
+        app.update();
+        assert_eq!(edge_sizes(&app, entity), vec![2; 5]);
+
+        // Without reverting, or with the cheap check, which doesn't notice
+        // the missing vertex, the polytope stays broken.
+        for settings in [
+            ValidationSettings {
+                level: ValidationLevel::Cheap,
+                ..full
+            },
+            ValidationSettings {
+                revert: false,
+                ..full
+            },
+        ] {
+            let (mut app, entity) = validation_app(settings);
+            corrupt(&mut app, entity);
+            app.update();
+            assert_eq!(edge_sizes(&app, entity), vec![1, 2, 2, 2]);
+        }
+    }
+}
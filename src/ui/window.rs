@@ -3,24 +3,34 @@
 //! All windows are l&mut &mut oaded in parallel, before the top panel and the library are
 //! shown on screen.
 
-use std::marker::PhantomData;
+use std::{error::Error, marker::PhantomData};
 
 use super::{
+    command::{print_components, PolytopeCommand},
+    lang::SelectedLanguage,
     memory::{slot_label, Memory},
     PointWidget,
 };
-use crate::{Concrete, Float, Hypersphere, Point, ui::main_window::PolyName};
-
-use miratope_core::{conc::ConcretePolytope, Polytope, abs::Ranked};
+use crate::{Concrete, Float, Hypersphere, Point, EPS, ui::main_window::{ElementHighlight, PolyName, Selected}};
+
+use miratope_core::{
+    conc::{compound::CompoundPolicy, edit::EditError, snap::SnapOptions, units::{UnitInfo, UNITS}, element_types::element_name, hull::OrbitError, report::{Report, ReportOptions}, ConcretePolytope, MorphError},
+    file::{text::{infer_dim, ParseError, TextField}, FromFile},
+    geometry::{Matrix, Rotation},
+    group::Group,
+    AntiprismError, Polytope, abs::{Abstract, Ranked},
+};
 
 use bevy::prelude::*;
 use bevy_egui::{
     egui::{self, CtxRef, Layout, Ui, Widget},
     EguiContext,
 };
+use vec_like::VecLike;
 
-/// The text on the loaded polytope slot.
-const LOADED_LABEL: &str = "(Loaded polytope)";
+/// The estimated size in bytes above which a product is only built once the
+/// user confirms it.
+pub const LARGE_PRODUCT: usize = 1_000_000_000;
 
 /// The result of showing a window, updated every frame.
 pub enum ShowResult {
@@ -46,15 +56,47 @@ impl Plugin for WindowPlugin {
             .add_plugin(PyramidWindow::plugin())
             .add_plugin(PrismWindow::plugin())
             .add_plugin(TegumWindow::plugin())
-            .add_plugin(AntiprismWindow::plugin())
+            .insert_resource(AntiprismWindow::default())
+            .add_system(AntiprismWindow::show_error_system.system().label("show_windows"))
+            .add_system(AntiprismWindow::update_system.system().label("show_windows"))
+            .add_system(open_window::<AntiprismWindow>.system().after("show_top_panel"))
             .add_plugin(DuopyramidWindow::plugin())
             .add_plugin(DuoprismWindow::plugin())
             .add_plugin(DuotegumWindow::plugin())
             .add_plugin(DuocombWindow::plugin())
             .add_plugin(StarWindow::plugin())
             .add_plugin(CompoundWindow::plugin())
+            .add_plugin(IntersectWindow::plugin())
+            .add_plugin(MorphWindow::plugin())
+            .add_system(MorphWindow::animate_system.system().label("show_windows"))
+            .init_resource::<ReportWindow>()
+            .add_system(ReportWindow::show_system.system().label("show_windows"))
+            .add_system(ReportWindow::update_system.system().label("show_windows"))
+            .add_system(open_window::<ReportWindow>.system().after("show_top_panel"))
+            .init_resource::<VertexWindow>()
+            .add_system(VertexWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<VertexWindow>.system().after("show_top_panel"))
+            .init_resource::<InspectorWindow>()
+            .add_system(InspectorWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<InspectorWindow>.system().after("show_top_panel"))
+            .init_resource::<TextWindow>()
+            .add_system(TextWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<TextWindow>.system().after("show_top_panel"))
+            .init_resource::<TowerWindow>()
+            .add_system(TowerWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<TowerWindow>.system().after("show_top_panel"))
+            .init_resource::<OrbitWindow>()
+            .add_system(OrbitWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<OrbitWindow>.system().after("show_top_panel"))
+            .init_resource::<EditWindow>()
+            .add_system(EditWindow::show_system.system().label("show_windows"))
+            .add_system(open_window::<EditWindow>.system().after("show_top_panel"))
             .add_plugin(TruncateWindow::plugin())
             .add_plugin(ScaleWindow::plugin())
+            .add_plugin(CanonicalizeWindow::plugin())
+            .add_plugin(SnapWindow::plugin())
+            .add_plugin(UnitsWindow::plugin())
+            .add_plugin(UnfoldWindow::plugin())
             .add_plugin(FacetingSettings::plugin())
             .add_plugin(RotateWindow::plugin())
             .add_plugin(PlaneWindow::plugin());
@@ -64,12 +106,28 @@ impl Plugin for WindowPlugin {
 /// A widget consisting of a Reset button and an Ok button, right-aligned.
 pub struct OkReset<'a> {
     result: &'a mut ShowResult,
+
+    /// The language of the buttons.
+    lang: SelectedLanguage,
+
+    /// Whether the Ok button can be clicked.
+    ok_enabled: bool,
 }
 
 impl<'a> OkReset<'a> {
     /// Initializes the buttons on screen.
-    pub fn new(result: &'a mut ShowResult) -> Self {
-        Self { result }
+    pub fn new(result: &'a mut ShowResult, lang: SelectedLanguage) -> Self {
+        Self {
+            result,
+            lang,
+            ok_enabled: true,
+        }
+    }
+
+    /// Sets whether the Ok button can be clicked.
+    pub fn ok_enabled(mut self, ok_enabled: bool) -> Self {
+        self.ok_enabled = ok_enabled;
+        self
     }
 }
 
@@ -79,9 +137,9 @@ impl<'a> Widget for OkReset<'a> {
         let size = egui::Vec2::new(ui.min_size().x, 30.0);
 
         ui.allocate_ui_with_layout(size, Layout::right_to_left(), |ui| {
-            if ui.button("Ok").clicked() {
+            if ui.add(egui::Button::new(t!(self.lang, "window.ok")).enabled(self.ok_enabled)).clicked() {
                 *self.result = ShowResult::Ok;
-            } else if ui.button("Reset").clicked() {
+            } else if ui.button(t!(self.lang, "window.reset")).clicked() {
                 *self.result = ShowResult::Reset;
             }
         })
@@ -94,10 +152,25 @@ fn resize(point: &mut Point, dim: usize) {
     *point = point.clone().resize_vertically(dim, 0.0)
 }
 
+/// Adds drag values for the scale factors of both factors of a product.
+fn scale_drag_values(ui: &mut Ui, scales: &mut [Float; 2], lang: SelectedLanguage) {
+    for (idx, scale) in scales.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(scale)
+                    .speed(0.01)
+                    .clamp_range(0.0..=Float::MAX),
+            );
+
+            ui.label(format!("{} #{}", t!(lang, "window.scale"), idx + 1));
+        });
+    }
+}
+
 /// The base trait for a window, containing the common code. You probably don't
 /// want to implement **only** this.
 pub trait Window: Send + Sync + Default {
-    /// The name on the window, shown on the upper left.
+    /// The key of the name on the window, shown on the upper left.
     const NAME: &'static str;
 
     /// Returns whether the window is open.
@@ -116,6 +189,24 @@ pub trait Window: Send + Sync + Default {
     fn close(&mut self) {
         *self.is_open_mut() = false;
     }
+
+    /// Creates the window on screen, with its name in the selected language.
+    /// The window is identified by the key of its name, so that it stays in
+    /// place when the language is changed.
+    fn egui_window<'a>(lang: SelectedLanguage) -> egui::Window<'a> {
+        egui::Window::new(lang.get(Self::NAME)).id(egui::Id::new(Self::NAME))
+    }
+}
+
+/// Opens a window whenever [`PolytopeCommand::open`] is sent for it.
+pub fn open_window<W: Window + 'static>(
+    mut commands: EventReader<'_, '_, PolytopeCommand>,
+    mut window: ResMut<'_, W>,
+) {
+    let open = PolytopeCommand::open::<W>();
+    if commands.iter().filter(|&&command| command == open).count() != 0 {
+        window.open();
+    }
 }
 
 /// Implements the common methods of [`PlainWindow`] and [`UpdateWindow`]. Note
@@ -124,16 +215,16 @@ pub trait Window: Send + Sync + Default {
 macro_rules! impl_show {
     () => {
         /// Shows the window on screen.
-        fn show(&mut self, ctx: &CtxRef) -> ShowResult {
+        fn show(&mut self, ctx: &CtxRef, lang: SelectedLanguage) -> ShowResult {
             let mut open = self.is_open();
             let mut result = ShowResult::None;
 
-            egui::Window::new(Self::NAME)
+            Self::egui_window(lang)
                 .open(&mut open)
                 .resizable(false)
                 .show(ctx, |ui| {
-                    self.build(ui);
-                    ui.add(OkReset::new(&mut result));
+                    self.build(ui, lang);
+                    ui.add(OkReset::new(&mut result, lang));
                 });
 
             if open {
@@ -148,12 +239,13 @@ macro_rules! impl_show {
         fn show_system(
             mut self_: ResMut<'_, Self>,
             egui_ctx: Res<'_, EguiContext>,
-            mut query: Query<'_, '_, &mut Concrete>,
+            mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
             mut poly_name: ResMut<'_, PolyName>,
+            lang: Res<'_, SelectedLanguage>,
         ) where
             Self: 'static,
         {
-            match self_.show(egui_ctx.ctx()) {
+            match self_.show(egui_ctx.ctx(), *lang) {
                 ShowResult::Ok => {
                     for mut polytope in query.iter_mut() {
                         self_.action(polytope.as_mut());
@@ -179,7 +271,7 @@ pub trait PlainWindow: Window {
     fn name_action(&self, name: &mut String);
 
     /// Builds the window to be shown on screen.
-    fn build(&mut self, ui: &mut Ui);
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage);
 
     /// Resets a window to its default state.
     fn reset(&mut self) {
@@ -202,7 +294,8 @@ pub struct PlainWindowPlugin<T: PlainWindow>(PhantomData<T>);
 impl<T: PlainWindow + 'static> Plugin for PlainWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
-            .add_system(T::show_system.system().label("show_windows"));
+            .add_system(T::show_system.system().label("show_windows"))
+            .add_system(open_window::<T>.system().after("show_top_panel"));
     }
 }
 
@@ -216,7 +309,7 @@ pub trait UpdateWindow: Window {
     fn name_action(&self, name: &mut String);
 
     /// Builds the window to be shown on screen.
-    fn build(&mut self, ui: &mut Ui);
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage);
 
     /// The default state of the window, when the polytope on the screen has a
     /// given rank.
@@ -240,7 +333,7 @@ pub trait UpdateWindow: Window {
     /// updated.
     fn update_system(
         mut self_: ResMut<'_, Self>,
-        query: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
+        query: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children), (Changed<Concrete>, With<Selected>)>,
     ) where
         Self: 'static,
     {
@@ -263,7 +356,8 @@ impl<T: UpdateWindow + 'static> Plugin for UpdateWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.insert_resource(T::default())
             .add_system(T::show_system.system().label("show_windows"))
-            .add_system(T::update_system.system().label("show_windows"));
+            .add_system(T::update_system.system().label("show_windows"))
+            .add_system(open_window::<T>.system().after("show_top_panel"));
     }
 }
 
@@ -287,15 +381,170 @@ impl Default for Slot {
 }
 
 impl Slot {
+    /// Returns the name of the polytope in the slot, given the name of the
+    /// loaded polytope.
+    pub fn name(self, loaded: &str, memory: &Memory) -> String {
+        match self {
+            Self::None => String::new(),
+            Self::Loaded => loaded.to_string(),
+            Self::Memory(idx) => match &memory[idx].as_ref().unwrap().1 {
+                Some(label) => label.to_string(),
+                None => slot_label(idx),
+            },
+        }
+    }
+
     pub fn to_poly<'a>(self, memory: &'a Memory, loaded: &'a Concrete) -> Option<&'a Concrete> {
         match self {
             Self::None => None,
-            Self::Memory(idx) => Some(&memory[idx].as_ref().unwrap().0),
+            Self::Memory(idx) => Some(&*memory[idx].as_ref().unwrap().0),
             Self::Loaded => Some(loaded),
         }
     }
 }
 
+/// Writes a number with commas between every group of three digits.
+fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut res = String::new();
+
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx != 0 && (digits.len() - idx) % 3 == 0 {
+            res.push(',');
+        }
+        res.push(digit);
+    }
+
+    res
+}
+
+/// Writes a number of bytes in the largest unit that keeps it above 1.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while size >= 1000.0 && unit + 1 < UNITS.len() {
+        size /= 1000.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// A preview of the product of the polytopes selected in a [`DuoWindow`],
+/// computed from the factors without building it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProductPreview {
+    /// The element counts of the factors the preview was computed for, if both
+    /// of them are selected.
+    factors: Option<[Vec<usize>; 2]>,
+
+    /// The element counts of the product. These are exact.
+    pub counts: Vec<usize>,
+
+    /// The estimated number of bytes the product takes up.
+    pub bytes: usize,
+
+    /// Whether the user chose to build the product even though it's large.
+    pub confirmed: bool,
+}
+
+impl ProductPreview {
+    /// Recomputes the preview whenever the element counts of the factors
+    /// change, in which case any confirmation to build a large product is
+    /// withdrawn. The flags are those of [`Abstract::product_with_progress`].
+    pub fn update(&mut self, factors: [Option<&Concrete>; 2], (min, max): (bool, bool)) {
+        let (p, q) = match factors {
+            [Some(p), Some(q)] => (p, q),
+            _ => {
+                *self = Self::default();
+                return;
+            }
+        };
+
+        let counts = [
+            p.abs.el_count_iter().collect(),
+            q.abs.el_count_iter().collect(),
+        ];
+        if self.factors.as_ref() == Some(&counts) {
+            return;
+        }
+
+        self.counts = Abstract::product_counts(&p.abs, &q.abs, min, max);
+        self.bytes = Abstract::product_size_estimate(&p.abs, &q.abs, min, max);
+
+        // Only pyramid products add a dimension.
+        if let Some(&vertices) = self.counts.get(1) {
+            let dim = p.dim_or() + q.dim_or() + (!min && !max) as usize;
+            self.bytes +=
+                vertices * (std::mem::size_of::<Point>() + dim * std::mem::size_of::<Float>());
+        }
+
+        self.factors = Some(counts);
+        self.confirmed = false;
+    }
+
+    /// Returns whether the product is estimated to take up more memory than
+    /// [`LARGE_PRODUCT`].
+    pub fn is_large(&self) -> bool {
+        self.bytes > LARGE_PRODUCT
+    }
+
+    /// Returns whether the product may be built, that is, whether it's small
+    /// or the user confirmed it anyway.
+    pub fn allows_build(&self) -> bool {
+        !self.is_large() || self.confirmed
+    }
+
+    /// Returns the line describing the product, like
+    /// `Result: 15 vertices, 30 edges, 23 faces, 8 cells, est. 6.4 KB`.
+    pub fn summary(&self, lang: SelectedLanguage) -> String {
+        let rank = self.counts.len().saturating_sub(1);
+        let elements = if rank == 0 {
+            "nullitope".to_string()
+        } else {
+            (1..rank.max(2))
+                .map(|r| {
+                    format!(
+                        "{} {}",
+                        thousands(self.counts[r]),
+                        element_name(r as isize - 1).to_lowercase()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "{}: {}, {} {}",
+            t!(lang, "window.result"),
+            elements,
+            t!(lang, "window.estimate"),
+            format_bytes(self.bytes)
+        )
+    }
+
+    /// Shows the preview, and the option to build the product anyway if it's
+    /// large.
+    pub fn show(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        if self.factors.is_none() {
+            return;
+        }
+
+        if self.is_large() {
+            ui.colored_label(egui::Color32::YELLOW, self.summary(lang));
+            ui.checkbox(&mut self.confirmed, t!(lang, "window.build_anyway"));
+        } else {
+            ui.label(self.summary(lang));
+        }
+    }
+}
+
 /// A window that depends on [`Memory`], and that
 /// doesn't need to be updated when the polytope is changed.
 pub trait MemoryWindow: Window {
@@ -303,7 +552,7 @@ pub trait MemoryWindow: Window {
     fn action(&self, polytope: &mut Concrete);
 
     /// Builds the window to be shown on screen.
-    fn build(&mut self, ui: &mut Ui, memory: &Memory);
+    fn build(&mut self, ui: &mut Ui, memory: &Memory, lang: SelectedLanguage);
 
     /// Resets a window to its default state.
     fn reset(&mut self) {
@@ -312,16 +561,16 @@ pub trait MemoryWindow: Window {
     }
 
     /// Shows the window on screen.
-    fn show(&mut self, ctx: &CtxRef, memory: &Memory) -> ShowResult {
+    fn show(&mut self, ctx: &CtxRef, memory: &Memory, lang: SelectedLanguage) -> ShowResult {
         let mut open = self.is_open();
         let mut result = ShowResult::None;
 
-        egui::Window::new(Self::NAME)
+        Self::egui_window(lang)
             .open(&mut open)
             .resizable(false)
             .show(ctx, |ui| {
-                self.build(ui, memory);
-                ui.add(OkReset::new(&mut result));
+                self.build(ui, memory, lang);
+                ui.add(OkReset::new(&mut result, lang));
             });
 
         if open {
@@ -336,12 +585,13 @@ pub trait MemoryWindow: Window {
     fn show_system(
         mut self_: ResMut<'_, Self>,
         egui_ctx: Res<'_, EguiContext>,
-        mut query: Query<'_, '_, &mut Concrete>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
         memory: Res<'_, Memory>,
+        lang: Res<'_, SelectedLanguage>,
     ) where
         Self: 'static,
     {
-        match self_.show(egui_ctx.ctx(), &memory) {
+        match self_.show(egui_ctx.ctx(), &memory, *lang) {
             ShowResult::Ok => {
                 for mut polytope in query.iter_mut() {
                     self_.action(polytope.as_mut());
@@ -367,15 +617,23 @@ pub struct MemoryWindowPlugin<T: MemoryWindow>(PhantomData<T>);
 impl<T: MemoryWindow + 'static> Plugin for MemoryWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
-            .add_system(T::show_system.system().label("show_windows"));
+            .add_system(T::show_system.system().label("show_windows"))
+            .add_system(open_window::<T>.system().after("show_top_panel"));
     }
 }
 
 /// A window for any duo-something. All of these depend on the [`Memory`] but
 /// don't need to be updated when the polytope changes.
 pub trait DuoWindow: Window {
+    /// Whether the minimal and maximal elements of the factors are left out of
+    /// the product, as in [`Abstract::product_with_progress`].
+    const PRODUCT: (bool, bool);
+
     /// The duo-operation to apply.
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete;
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>>;
+
+    /// A mutable reference to the preview of the product.
+    fn preview_mut(&mut self) -> &mut ProductPreview;
 
     /// The slots in memory.
     fn slots(&self) -> [Slot; 2];
@@ -400,31 +658,48 @@ pub trait DuoWindow: Window {
             .map(|p| p.map(|poly| poly.dim()).flatten().unwrap_or_default())
     }
 
-    /// Applies the action of the window to the polytope.
-    fn action(&self, polytope: &mut Concrete, memory: &Memory) {
+    /// Applies the action of the window to the polytope. If the operation
+    /// fails, the error is reported and the polytope is left unchanged.
+    /// Returns whether the operation succeeded.
+    fn action(&self, polytope: &mut Concrete, memory: &Memory, lang: SelectedLanguage) -> bool {
         if let [Some(p), Some(q)] = self.polytopes(polytope, memory) {
-            *polytope = self.operation(p, q);
+            match self.operation(p, q) {
+                Ok(result) => {
+                    *polytope = result;
+                    return true;
+                }
+                Err(err) => eprintln!("{} ({}): {}", t!(lang, "error.operation"), lang.get(Self::NAME), err),
+            }
         }
+
+        false
+    }
+
+    /// Returns the names of the polytopes currently selected, given the name
+    /// of the loaded polytope.
+    fn slot_names(&self, loaded: &str, memory: &Memory) -> [String; 2] {
+        self.slots().map(|slot| slot.name(loaded, memory))
     }
 
     /// Applies an action to the polytope name.
     fn name_action(&self, name: &mut String, memory: &Memory);
 
     /// Builds the window to be shown on screen.
-    fn build(&mut self, _: &mut Ui, _: &Concrete, _: &Memory) {}
+    fn build(&mut self, _: &mut Ui, _: &Concrete, _: &Memory, _: SelectedLanguage) {}
 
-    fn build_dropdowns(&mut self, ui: &mut Ui, memory: &Memory) {
-        const SELECT: &str = "Select";
+    fn build_dropdowns(&mut self, ui: &mut Ui, memory: &Memory, lang: SelectedLanguage) {
+        let select = t!(lang, "window.select");
+        let loaded_label = t!(lang, "window.loaded");
 
         // Iterates over both slots.
         for (slot_idx, selected) in self.slots_mut().iter_mut().enumerate() {
             // The text for the selected option.
             let selected_text = match selected {
                 // Nothing has been selected.
-                Slot::None => SELECT.to_string(),
+                Slot::None => select.to_string(),
 
                 // The loaded polytope is selected.
-                Slot::Loaded => LOADED_LABEL.to_string(),
+                Slot::Loaded => loaded_label.to_string(),
 
                 // Something is selected from the memory.
                 Slot::Memory(selected_idx) => if *selected_idx < memory.len() {
@@ -432,7 +707,7 @@ pub trait DuoWindow: Window {
                         // Whatever was previously selected got deleted off the memory.
                         None => {
                             *selected = Slot::None;
-                            SELECT.to_string()
+                            select.to_string()
                         }
 
                         // Shows the name of the selected polytope.
@@ -448,7 +723,7 @@ pub trait DuoWindow: Window {
                     }
                 } else {
                     *selected = Slot::None;
-                    SELECT.to_string()
+                    select.to_string()
                 },
             };
 
@@ -461,7 +736,7 @@ pub trait DuoWindow: Window {
                     // The currently loaded polytope.
                     let mut loaded_selected = false;
 
-                    ui.selectable_value(&mut loaded_selected, true, LOADED_LABEL);
+                    ui.selectable_value(&mut loaded_selected, true, loaded_label);
 
                     // If the value was changed, update it.
                     if loaded_selected {
@@ -503,17 +778,31 @@ pub trait DuoWindow: Window {
     }
 
     /// Shows the window on screen.
-    fn show(&mut self, ctx: &CtxRef, polytope: &Concrete, memory: &Memory) -> ShowResult {
+    fn show(
+        &mut self,
+        ctx: &CtxRef,
+        polytope: &Concrete,
+        memory: &Memory,
+        lang: SelectedLanguage,
+    ) -> ShowResult {
         let mut open = self.is_open();
         let mut result = ShowResult::None;
 
-        egui::Window::new(Self::NAME)
+        Self::egui_window(lang)
             .open(&mut open)
             .resizable(false)
             .show(ctx, |ui| {
-                self.build_dropdowns(ui, memory);
-                self.build(ui, polytope, memory);
-                ui.add(OkReset::new(&mut result));
+                self.build_dropdowns(ui, memory, lang);
+                self.build(ui, polytope, memory, lang);
+
+                // Large products have to be confirmed before they're built.
+                let [i, j] = self.slots();
+                let preview = self.preview_mut();
+                preview.update([i.to_poly(memory, polytope), j.to_poly(memory, polytope)], Self::PRODUCT);
+                preview.show(ui, lang);
+
+                let ok_enabled = preview.allows_build();
+                ui.add(OkReset::new(&mut result, lang).ok_enabled(ok_enabled));
             });
 
         if open {
@@ -528,17 +817,19 @@ pub trait DuoWindow: Window {
     fn show_system(
         mut self_: ResMut<'_, Self>,
         egui_ctx: Res<'_, EguiContext>,
-        mut query: Query<'_, '_, &mut Concrete>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
         memory: Res<'_, Memory>,
         mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
     ) where
         Self: 'static,
     {
         for mut polytope in query.iter_mut() {
-            match self_.show(egui_ctx.ctx(), &polytope, &memory) {
+            match self_.show(egui_ctx.ctx(), &polytope, &memory, *lang) {
                 ShowResult::Ok => {
-                    self_.action(polytope.as_mut(), &memory);
-                    self_.name_action(&mut poly_name.0, &memory);
+                    if self_.action(polytope.as_mut(), &memory, *lang) {
+                        self_.name_action(&mut poly_name.0, &memory);
+                    }
                     self_.close()
                 }
                 ShowResult::Close => self_.close(),
@@ -561,7 +852,8 @@ pub struct DuoWindowPlugin<T: DuoWindow>(PhantomData<T>);
 impl<T: DuoWindow + 'static> Plugin for DuoWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
-            .add_system(T::show_system.system().label("show_windows"));
+            .add_system(T::show_system.system().label("show_windows"))
+            .add_system(open_window::<T>.system().after("show_top_panel"));
     }
 }
 
@@ -588,7 +880,7 @@ impl Default for DualWindow {
 }
 
 impl Window for DualWindow {
-    const NAME: &'static str = "Dual";
+    const NAME: &'static str = "title.dual";
 
     fn is_open(&self) -> bool {
         self.open
@@ -612,8 +904,8 @@ impl UpdateWindow for DualWindow {
         *name = format!("Dual of {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.center, "Center"));
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.add(PointWidget::new(&mut self.center, t!(lang, "window.center")));
 
         ui.horizontal(|ui| {
             ui.add(
@@ -622,7 +914,7 @@ impl UpdateWindow for DualWindow {
                     .clamp_range(0.0..=Float::MAX),
             );
 
-            ui.label("Radius");
+            ui.label(t!(lang, "window.radius"));
         });
     }
 
@@ -666,7 +958,7 @@ impl Default for PyramidWindow {
 }
 
 impl Window for PyramidWindow {
-    const NAME: &'static str = "Pyramid";
+    const NAME: &'static str = "title.pyramid";
 
     fn is_open(&self) -> bool {
         self.open
@@ -686,8 +978,8 @@ impl UpdateWindow for PyramidWindow {
         *name = format!("Pyramid of {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.offset, "Offset"));
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.add(PointWidget::new(&mut self.offset, t!(lang, "window.offset")));
 
         ui.horizontal(|ui| {
             ui.add(
@@ -696,7 +988,7 @@ impl UpdateWindow for PyramidWindow {
                     .clamp_range(0.0..=Float::MAX),
             );
 
-            ui.label("Height");
+            ui.label(t!(lang, "window.height"));
         });
     }
 
@@ -727,7 +1019,7 @@ pub struct PrismWindow {
 }
 
 impl Window for PrismWindow {
-    const NAME: &'static str = "Prism";
+    const NAME: &'static str = "title.prism";
 
     fn is_open(&self) -> bool {
         self.open
@@ -747,9 +1039,9 @@ impl PlainWindow for PrismWindow {
         *name = format!("Prism of {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
         ui.horizontal(|ui| {
-            ui.label("Height:");
+            ui.label(format!("{}:", t!(lang, "window.height")));
             ui.add(
                 egui::DragValue::new(&mut self.height)
                     .speed(0.01)
@@ -795,7 +1087,7 @@ impl Default for TegumWindow {
 }
 
 impl Window for TegumWindow {
-    const NAME: &'static str = "Tegum";
+    const NAME: &'static str = "title.tegum";
 
     fn is_open(&self) -> bool {
         self.open
@@ -820,8 +1112,8 @@ impl UpdateWindow for TegumWindow {
         *name = format!("Tegum of {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.offset, "Offset"));
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.add(PointWidget::new(&mut self.offset, t!(lang, "window.offset")));
 
         ui.horizontal(|ui| {
             ui.add(
@@ -829,12 +1121,12 @@ impl UpdateWindow for TegumWindow {
                     .speed(0.01)
                     .clamp_range(0.0..=Float::MAX),
             );
-            ui.label("Height");
+            ui.label(t!(lang, "window.height"));
         });
 
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.height_offset).speed(0.01));
-            ui.label("Height offset");
+            ui.label(t!(lang, "window.height_offset"));
         });
     }
 
@@ -866,6 +1158,9 @@ pub struct AntiprismWindow {
 
     /// Whether the antiprism is a retroprism.
     retroprism: bool,
+
+    /// The error from the last attempt to build the antiprism, if any.
+    error: Option<AntiprismError>,
 }
 
 impl Default for AntiprismWindow {
@@ -874,12 +1169,56 @@ impl Default for AntiprismWindow {
             dual: Default::default(),
             height: 1.0,
             retroprism: false,
+            error: None,
+        }
+    }
+}
+
+impl AntiprismWindow {
+    /// Builds the antiprism of a polytope with the parameters in the window.
+    fn antiprism(&self, polytope: &Concrete) -> Result<Concrete, AntiprismError> {
+        let radius = self.dual.radius;
+        let mut squared_radius = radius * radius;
+        if self.retroprism {
+            squared_radius *= -1.0;
+        }
+
+        let sphere = Hypersphere::with_squared_radius(self.dual.center.clone(), squared_radius);
+        polytope.try_antiprism_with(&sphere, self.height)
+    }
+
+    /// The system that shows the window. Unlike most other windows, it stays
+    /// open and shows the error whenever the antiprism can't be built.
+    fn show_error_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+        mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        match self_.show(egui_ctx.ctx(), *lang) {
+            ShowResult::Ok => {
+                if let Some(mut polytope) = query.iter_mut().next() {
+                    match self_.antiprism(&polytope) {
+                        Ok(antiprism) => {
+                            *polytope = antiprism;
+                            self_.name_action(&mut poly_name.0);
+                            self_.error = None;
+                            self_.close();
+                        }
+                        Err(err) => self_.error = Some(err),
+                    }
+                }
+            }
+            ShowResult::Close => self_.close(),
+            ShowResult::Reset => self_.reset(),
+            ShowResult::None => {}
         }
     }
 }
 
 impl Window for AntiprismWindow {
-    const NAME: &'static str = "Antiprism";
+    const NAME: &'static str = "title.antiprism";
 
     fn is_open(&self) -> bool {
         self.dual.open
@@ -892,15 +1231,7 @@ impl Window for AntiprismWindow {
 
 impl UpdateWindow for AntiprismWindow {
     fn action(&self, polytope: &mut Concrete) {
-        let radius = self.dual.radius;
-        let mut squared_radius = radius * radius;
-        if self.retroprism {
-            squared_radius *= -1.0;
-        }
-
-        let sphere = Hypersphere::with_squared_radius(self.dual.center.clone(), squared_radius);
-
-        match polytope.try_antiprism_with(&sphere, self.height) {
+        match self.antiprism(polytope) {
             Ok(antiprism) => *polytope = antiprism,
             Err(err) => eprintln!("Antiprism failed: {}", err),
         }
@@ -910,8 +1241,8 @@ impl UpdateWindow for AntiprismWindow {
         *name = format!("Dual of {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.dual.center, "Center"));
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.add(PointWidget::new(&mut self.dual.center, t!(lang, "window.center")));
 
         ui.horizontal(|ui| {
             ui.add(
@@ -919,19 +1250,24 @@ impl UpdateWindow for AntiprismWindow {
                     .speed(0.01)
                     .clamp_range(0.0..=Float::MAX),
             );
-            ui.label("Radius");
+            ui.label(t!(lang, "window.radius"));
         });
 
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.height).speed(0.01));
-            ui.label("Height");
+            ui.label(t!(lang, "window.height"));
         });
 
         ui.horizontal(|ui| {
             ui.add(
-                egui::Checkbox::new(&mut self.retroprism, "Retroprism"), //.text_style(TextStyle::Body),
+                egui::Checkbox::new(&mut self.retroprism, t!(lang, "window.retroprism")), //.text_style(TextStyle::Body),
             );
         });
+
+        if let Some(error) = self.error {
+            ui.separator();
+            ui.colored_label(egui::Color32::RED, format!("{}: {}", t!(lang, "window.error"), error));
+        }
     }
 
     fn dim(&self) -> usize {
@@ -965,6 +1301,12 @@ pub struct DuopyramidWindow {
 
     /// The offset of each base.
     offsets: [Point; 2],
+
+    /// The scale factor of each base.
+    scales: [Float; 2],
+
+    /// The preview of the duopyramid.
+    preview: ProductPreview,
 }
 
 impl Default for DuopyramidWindow {
@@ -974,12 +1316,14 @@ impl Default for DuopyramidWindow {
             slots: Default::default(),
             height: 1.0,
             offsets: [Point::zeros(0), Point::zeros(0)],
+            scales: [1.0, 1.0],
+            preview: Default::default(),
         }
     }
 }
 
 impl Window for DuopyramidWindow {
-    const NAME: &'static str = "Duopyramid";
+    const NAME: &'static str = "title.duopyramid";
 
     fn is_open(&self) -> bool {
         self.open
@@ -991,29 +1335,17 @@ impl Window for DuopyramidWindow {
 }
 
 impl DuoWindow for DuopyramidWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    const PRODUCT: (bool, bool) = (false, false);
+
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
         let [p_offset, q_offset] = &self.offsets;
-        Concrete::duopyramid_with(p, q, p_offset, q_offset, self.height)
+        let [p_scale, q_scale] = self.scales;
+
+        Ok(Concrete::duopyramid_with(p, q, p_offset, q_offset, p_scale, q_scale, self.height)?)
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Duopyramid of ({}, {})", name_a, name_b);
     }
 
@@ -1025,63 +1357,58 @@ impl DuoWindow for DuopyramidWindow {
         &mut self.slots
     }
 
-    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, memory: &Memory) {
+    fn preview_mut(&mut self) -> &mut ProductPreview {
+        &mut self.preview
+    }
+
+    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, memory: &Memory, lang: SelectedLanguage) {
         let [p_dim, q_dim] = self.dim_or(polytope, memory);
 
         resize(&mut self.offsets[0], p_dim);
         resize(&mut self.offsets[1], q_dim);
 
-        ui.add(PointWidget::new(&mut self.offsets[0], "Offset #1"));
-        ui.add(PointWidget::new(&mut self.offsets[1], "Offset #2"));
+        ui.add(PointWidget::new(&mut self.offsets[0], format!("{} #1", t!(lang, "window.offset"))));
+        ui.add(PointWidget::new(&mut self.offsets[1], format!("{} #2", t!(lang, "window.offset"))));
+
+        scale_drag_values(ui, &mut self.scales, lang);
 
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.height).clamp_range(0.0..=Float::MAX));
-            ui.label("Height");
+            ui.label(t!(lang, "window.height"));
         });
 
         if ui.add(
-            egui::Button::new("Try to make orbiform")
+            egui::Button::new(t!(lang, "window.orbiform"))
                 .enabled(!matches!(self.slots[0], Slot::None) && !matches!(self.slots[1], Slot::None))
             ).clicked() {
                 if let Some(circum0) = match self.slots[0] {
                     Slot::Loaded => polytope,
-                    Slot::Memory(i) => &memory[i].as_ref().unwrap().0,
+                    Slot::Memory(i) => &*memory[i].as_ref().unwrap().0,
                     Slot::None => unreachable!(),
                 }.circumsphere() {
                     if let Some(circum1) = match self.slots[1] {
                         Slot::Loaded => polytope,
-                        Slot::Memory(i) => &memory[i].as_ref().unwrap().0,
+                        Slot::Memory(i) => &*memory[i].as_ref().unwrap().0,
                         Slot::None => unreachable!(),
                     }.circumsphere() {
 
-                        let sq_height = 1. - circum0.squared_radius - circum1.squared_radius;
+                        let [scale0, scale1] = self.scales;
+                        let sq_height = 1.
+                            - circum0.squared_radius * scale0 * scale0
+                            - circum1.squared_radius * scale1 * scale1;
                         if sq_height >= 0. {
                             self.height = sq_height.sqrt();
-                            self.offsets[0] = -circum0.center;
-                            self.offsets[1] = -circum1.center;
+                            self.offsets[0] = -circum0.center * scale0;
+                            self.offsets[1] = -circum1.center * scale1;
                         } else {
                             println!("Orbiform failed: height is imaginary.");
                         }
 
                     } else {
-                        println!("Orbiform failed: {} has no circumsphere.", match self.slots[1] {
-                            Slot::Loaded => "Loaded polytope".to_string(),
-                            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                                Some(label) => label.to_string(),
-                                None => format!("polytope {}", i),
-                            },
-                            Slot::None => unreachable!(),
-                        });
+                        println!("Orbiform failed: {} has no circumsphere.", self.slots[1].name("Loaded polytope", memory));
                     }
                 } else {
-                    println!("Orbiform failed: {} has no circumsphere.", match self.slots[0] {
-                        Slot::Loaded => "Loaded polytope".to_string(),
-                        Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                            Some(label) => label.to_string(),
-                            None => format!("polytope {}", i),
-                        },
-                        Slot::None => unreachable!(),
-                    });
+                    println!("Orbiform failed: {} has no circumsphere.", self.slots[0].name("Loaded polytope", memory));
                 }
         }
     }
@@ -1089,17 +1416,33 @@ impl DuoWindow for DuopyramidWindow {
 
 /// A window that allows a user to build a duoprism, either using the polytopes
 /// in memory or the currently loaded one.
-#[derive(Default)]
 pub struct DuoprismWindow {
     /// Whether the window is open.
     open: bool,
 
     /// The slots that are currently selected.
     slots: [Slot; 2],
+
+    /// The scale factor of each factor.
+    scales: [Float; 2],
+
+    /// The preview of the duoprism.
+    preview: ProductPreview,
+}
+
+impl Default for DuoprismWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            slots: Default::default(),
+            scales: [1.0, 1.0],
+            preview: Default::default(),
+        }
+    }
 }
 
 impl Window for DuoprismWindow {
-    const NAME: &'static str = "Duoprism";
+    const NAME: &'static str = "title.duoprism";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1111,28 +1454,16 @@ impl Window for DuoprismWindow {
 }
 
 impl DuoWindow for DuoprismWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
-        p.duoprism(q)
+    const PRODUCT: (bool, bool) = (true, false);
+
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
+        let [p_scale, q_scale] = self.scales;
+
+        Ok(Concrete::duoprism_with(p, q, p_scale, q_scale)?)
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Duoprism of ({}, {})", name_a, name_b);
     }
 
@@ -1143,6 +1474,14 @@ impl DuoWindow for DuoprismWindow {
     fn slots_mut(&mut self) -> &mut [Slot; 2] {
         &mut self.slots
     }
+
+    fn preview_mut(&mut self) -> &mut ProductPreview {
+        &mut self.preview
+    }
+
+    fn build(&mut self, ui: &mut Ui, _: &Concrete, _: &Memory, lang: SelectedLanguage) {
+        scale_drag_values(ui, &mut self.scales, lang);
+    }
 }
 
 /// A window that allows a user to build a duotegum, either using the polytopes
@@ -1156,6 +1495,12 @@ pub struct DuotegumWindow {
 
     /// The offset of each base.
     offsets: [Point; 2],
+
+    /// The scale factor of each base.
+    scales: [Float; 2],
+
+    /// The preview of the duotegum.
+    preview: ProductPreview,
 }
 
 impl Default for DuotegumWindow {
@@ -1164,12 +1509,14 @@ impl Default for DuotegumWindow {
             open: false,
             slots: Default::default(),
             offsets: [Point::zeros(0), Point::zeros(0)],
+            scales: [1.0, 1.0],
+            preview: Default::default(),
         }
     }
 }
 
 impl Window for DuotegumWindow {
-    const NAME: &'static str = "Duotegum";
+    const NAME: &'static str = "title.duotegum";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1181,29 +1528,17 @@ impl Window for DuotegumWindow {
 }
 
 impl DuoWindow for DuotegumWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    const PRODUCT: (bool, bool) = (false, true);
+
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
         let [p_offset, q_offset] = &self.offsets;
-        Concrete::duotegum_with(p, q, p_offset, q_offset)
+        let [p_scale, q_scale] = self.scales;
+
+        Ok(Concrete::duotegum_with(p, q, p_offset, q_offset, p_scale, q_scale)?)
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Duotegum of ({}, {})", name_a, name_b);
     }
 
@@ -1215,14 +1550,20 @@ impl DuoWindow for DuotegumWindow {
         &mut self.slots
     }
 
-    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, memory: &Memory) {
+    fn preview_mut(&mut self) -> &mut ProductPreview {
+        &mut self.preview
+    }
+
+    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, memory: &Memory, lang: SelectedLanguage) {
         let [p_dim, q_dim] = self.dim_or(polytope, memory);
 
         resize(&mut self.offsets[0], p_dim);
         resize(&mut self.offsets[1], q_dim);
 
-        ui.add(PointWidget::new(&mut self.offsets[0], "Offset #1"));
-        ui.add(PointWidget::new(&mut self.offsets[1], "Offset #2"));
+        ui.add(PointWidget::new(&mut self.offsets[0], format!("{} #1", t!(lang, "window.offset"))));
+        ui.add(PointWidget::new(&mut self.offsets[1], format!("{} #2", t!(lang, "window.offset"))));
+
+        scale_drag_values(ui, &mut self.scales, lang);
     }
 }
 
@@ -1235,10 +1576,13 @@ pub struct DuocombWindow {
 
     /// The slots that are currently selected.
     slots: [Slot; 2],
+
+    /// The preview of the duocomb.
+    preview: ProductPreview,
 }
 
 impl Window for DuocombWindow {
-    const NAME: &'static str = "Duocomb";
+    const NAME: &'static str = "title.duocomb";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1250,28 +1594,14 @@ impl Window for DuocombWindow {
 }
 
 impl DuoWindow for DuocombWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
-        p.duocomb(q)
+    const PRODUCT: (bool, bool) = (true, true);
+
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
+        Ok(p.duocomb(q))
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Comb of ({}, {})", name_a, name_b);
     }
 
@@ -1282,6 +1612,10 @@ impl DuoWindow for DuocombWindow {
     fn slots_mut(&mut self) -> &mut [Slot; 2] {
         &mut self.slots
     }
+
+    fn preview_mut(&mut self) -> &mut ProductPreview {
+        &mut self.preview
+    }
 }
 
 /// A window that allows a user to build a star product, either using the polytopes
@@ -1296,7 +1630,7 @@ pub struct StarWindow {
 }
 
 impl Window for StarWindow {
-    const NAME: &'static str = "Star product";
+    const NAME: &'static str = "title.star_product";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1308,28 +1642,12 @@ impl Window for StarWindow {
 }
 
 impl DuoWindow for StarWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
-        p.star_product(q)
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
+        Ok(p.star_product(q))
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Star of ({}, {})", name_a, name_b);
     }
 
@@ -1354,7 +1672,7 @@ pub struct CompoundWindow {
 }
 
 impl Window for CompoundWindow {
-    const NAME: &'static str = "Compound";
+    const NAME: &'static str = "title.compound";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1366,30 +1684,14 @@ impl Window for CompoundWindow {
 }
 
 impl DuoWindow for CompoundWindow {
-    fn operation(&self, p: &Concrete, q: &Concrete) -> Concrete {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
         let mut p2 = p.clone();
         p2.comp_append(q.clone());
-        p2
+        Ok(p2)
     }
 
     fn name_action(&self, name: &mut String, memory: &Memory) {
-        let name_a = match self.slots[0] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-        let name_b = match self.slots[1] {
-            Slot::Loaded => name.clone(),
-            Slot::Memory(i) => match &memory[i].as_ref().unwrap().1 {
-                Some(label) => label.to_string(),
-                None => format!("polytope {}", i),
-            },
-            Slot::None => "".to_string(),
-        };
-
+        let [name_a, name_b] = self.slot_names(name, memory);
         *name = format!("Compound of ({}, {})", name_a, name_b);
     }
 
@@ -1402,24 +1704,19 @@ impl DuoWindow for CompoundWindow {
     }
 }
 
-/// A window to configure a truncation of the polytope.
+/// A window that allows a user to intersect two convex polytopes, either using
+/// the polytopes in memory or the currently loaded one.
 #[derive(Default)]
-pub struct TruncateWindow {
+pub struct IntersectWindow {
     /// Whether the window is open.
     open: bool,
 
-    /// The rank of the polytope.
-    rank: usize,
-
-    /// Which nodes are ringed.
-    truncate_type: Vec<bool>,
-
-    /// The weights applied to the coordinates. Intuitively, the truncation depths.
-    depth: Vec<f64>,
+    /// The slots that are currently selected.
+    slots: [Slot; 2],
 }
 
-impl Window for TruncateWindow {
-    const NAME: &'static str = "Truncate";
+impl Window for IntersectWindow {
+    const NAME: &'static str = "title.intersect";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1430,53 +1727,1213 @@ impl Window for TruncateWindow {
     }
 }
 
-impl UpdateWindow for TruncateWindow {
-    fn action(&self, polytope: &mut Concrete) {
-        let mut rings = Vec::new();
-        for (rank, ringed) in self.truncate_type.iter().enumerate() {
-            if *ringed {
-                rings.push(rank);
-            }
-        }
-        polytope.element_sort();
-        *polytope = polytope.truncate_with(rings, self.depth.clone());
+impl DuoWindow for IntersectWindow {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
+        p.intersect(q)
+            .ok_or_else(|| "the intersection is empty or lower-dimensional".into())
     }
 
-    fn name_action(&self, name: &mut String) {
-        *name = format!("Truncated {}", name);
+    fn name_action(&self, name: &mut String, memory: &Memory) {
+        let [name_a, name_b] = self.slot_names(name, memory);
+        *name = format!("Intersection of ({}, {})", name_a, name_b);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        for r in 0..self.rank {
-            ui.horizontal(|ui| {
-                ui.add(egui::Checkbox::new(&mut self.truncate_type[r], ""));
-                ui.add(egui::DragValue::new(&mut self.depth[r]).speed(0.01));
-            });
-        }
+    fn slots(&self) -> [Slot; 2] {
+        self.slots
     }
 
-    fn dim(&self) -> usize {
-        self.rank
+    fn slots_mut(&mut self) -> &mut [Slot; 2] {
+        &mut self.slots
     }
+}
 
-    fn default_with(dim: usize) -> Self {
-        Self {
-            rank: dim,
-            truncate_type: vec![false; dim],
-            depth: vec![1.0; dim],
-            ..Default::default()
-        }
-    }
+/// A window that allows a user to morph a polytope into another with the same
+/// number of vertices, either using the polytopes in memory or the currently
+/// loaded one.
+pub struct MorphWindow {
+    /// Whether the window is open.
+    open: bool,
 
-    fn update(&mut self, dim: usize) {
-        self.rank = dim;
-        self.truncate_type = vec![false; dim];
-        self.depth = vec![1.0; dim];
+    /// The slots that are currently selected.
+    slots: [Slot; 2],
+
+    /// The interpolation parameter. Values outside of `[0, 1]` extrapolate.
+    t: f64,
+
+    /// Whether to match the vertices by nearest neighbor rather than by order.
+    matched: bool,
+
+    /// The speed at which `t` is swept while animating, in units per second.
+    speed: f64,
+
+    /// The polytopes being morphed while the animation plays.
+    animation: Option<(Concrete, Concrete)>,
+}
+
+impl Default for MorphWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            slots: Default::default(),
+            t: 0.5,
+            matched: false,
+            speed: 0.5,
+            animation: None,
+        }
+    }
+}
+
+impl Window for MorphWindow {
+    const NAME: &'static str = "title.morph";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.animation = None;
+    }
+}
+
+impl MorphWindow {
+    /// Morphs a polytope into another at the current value of `t`.
+    fn morph(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, MorphError> {
+        if self.matched {
+            p.morph_matched(q, self.t)
+        } else {
+            p.morph(q, self.t)
+        }
+    }
+
+    /// The system that sweeps `t` back and forth while the animation plays,
+    /// and updates the selected polytope accordingly.
+    fn animate_system(
+        mut self_: ResMut<'_, Self>,
+        time: Res<'_, Time>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+    ) {
+        if self_.animation.is_none() {
+            return;
+        }
+
+        // Bounces t between 0 and 1.
+        self_.t += self_.speed * time.delta_seconds_f64();
+        if self_.t > 1.0 {
+            self_.t = 2.0 - self_.t;
+            self_.speed = -self_.speed;
+        } else if self_.t < 0.0 {
+            self_.t = -self_.t;
+            self_.speed = -self_.speed;
+        }
+
+        let (p, q) = self_.animation.as_ref().unwrap();
+        match self_.morph(p, q) {
+            Ok(morph) => {
+                for mut polytope in query.iter_mut() {
+                    *polytope = morph.clone();
+                }
+            }
+            Err(err) => {
+                eprintln!("Morph failed: {}", err);
+                self_.animation = None;
+            }
+        }
+    }
+}
+
+impl DuoWindow for MorphWindow {
+    fn operation(&self, p: &Concrete, q: &Concrete) -> Result<Concrete, Box<dyn Error>> {
+        Ok(self.morph(p, q)?)
+    }
+
+    fn name_action(&self, name: &mut String, memory: &Memory) {
+        let [name_a, name_b] = self.slot_names(name, memory);
+        *name = format!("Morph of ({}, {})", name_a, name_b);
+    }
+
+    fn slots(&self) -> [Slot; 2] {
+        self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [Slot; 2] {
+        &mut self.slots
+    }
+
+    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, memory: &Memory, lang: SelectedLanguage) {
+        ui.add(egui::Slider::new(&mut self.t, 0.0..=1.0).clamp_to_range(false).text("t"));
+        ui.checkbox(&mut self.matched, t!(lang, "window.match_vertices"));
+
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut self.speed).speed(0.01));
+            ui.label(t!(lang, "window.animation_speed"));
+        });
+
+        // Takes a snapshot of both polytopes, so that the animation doesn't
+        // feed back into itself when morphing the loaded polytope.
+        let mut animate = self.animation.is_some();
+        if ui.checkbox(&mut animate, t!(lang, "window.animate")).changed() {
+            self.animation = if animate {
+                match self.polytopes(polytope, memory) {
+                    [Some(p), Some(q)] => Some((p.clone(), q.clone())),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+        }
+    }
+}
+
+/// A window that shows a report of the properties of the selected polytope as
+/// selectable text. The expensive entries are only computed on request.
+#[derive(Default)]
+pub struct ReportWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The report of the selected polytope, or `None` if it has to be
+    /// rebuilt.
+    report: Option<Report>,
+}
+
+impl Window for ReportWindow {
+    const NAME: &'static str = "title.report";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl ReportWindow {
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        query: Query<'_, '_, &Concrete, With<Selected>>,
+        poly_name: Res<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let polytope = match query.iter().next() {
+            Some(polytope) => polytope,
+            None => return,
+        };
+
+        let mut open = true;
+        let mut compute = ReportOptions::default();
+        let report = self_
+            .report
+            .get_or_insert_with(|| polytope.report(ReportOptions::default()));
+        report.name = Some(poly_name.0.clone());
+
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .show(egui_ctx.ctx(), |ui| {
+                // Edits to the text are thrown away, but it can still be
+                // selected and copied.
+                let mut text = report.to_string();
+                ui.add(egui::TextEdit::multiline(&mut text).code_editor());
+
+                ui.horizontal(|ui| {
+                    if report.inradius.is_skipped() && ui.button(t!(lang, "report.radii")).clicked() {
+                        compute.radii = true;
+                    }
+                    if report.volume.is_skipped() && ui.button(t!(lang, "report.volume")).clicked() {
+                        compute.volume = true;
+                    }
+                    if report.orientable.is_skipped()
+                        && ui.button(t!(lang, "report.orientability")).clicked()
+                    {
+                        compute.orientability = true;
+                    }
+                    if report.element_types.is_skipped()
+                        && ui.button(t!(lang, "report.element_types")).clicked()
+                    {
+                        compute.element_types = true;
+                    }
+                });
+
+                if ui.button(t!(lang, "report.copy")).clicked() {
+                    ui.output().copied_text = report.to_string();
+                }
+            });
+
+        report.compute(polytope, compute);
+
+        if !open {
+            self_.close();
+        }
+    }
+
+    /// The system that throws away the report whenever the selected polytope
+    /// changes.
+    fn update_system(
+        mut self_: ResMut<'_, Self>,
+        query: Query<'_, '_, &Concrete, (Or<(Changed<Concrete>, Added<Selected>)>, With<Selected>)>,
+    ) {
+        if query.iter().next().is_some() {
+            self_.report = None;
+        }
+    }
+}
+
+/// A window that lists the coordinates and norm of every vertex of the
+/// selected polytope.
+#[derive(Default)]
+pub struct VertexWindow {
+    /// Whether the window is open.
+    open: bool,
+}
+
+impl Window for VertexWindow {
+    const NAME: &'static str = "title.vertex_coordinates";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl VertexWindow {
+    /// Returns the vertex table of a polytope as CSV, with a header row.
+    fn csv(polytope: &Concrete) -> String {
+        let dim = polytope.dim_or();
+        let mut csv = String::from("index");
+        for i in 0..dim {
+            csv.push_str(&format!(",x{}", i));
+        }
+        csv.push_str(",norm\n");
+
+        for (idx, v, norm) in polytope.vertex_table() {
+            csv.push_str(&idx.to_string());
+            for x in v.iter() {
+                csv.push_str(&format!(",{}", x));
+            }
+            csv.push_str(&format!(",{}\n", norm));
+        }
+
+        csv
+    }
+
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        query: Query<'_, '_, &Concrete, With<Selected>>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let polytope = match query.iter().next() {
+            Some(polytope) => polytope,
+            None => return,
+        };
+
+        let mut open = true;
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .show(egui_ctx.ctx(), |ui| {
+                if ui.button(t!(lang, "vertices.copy_csv")).clicked() {
+                    ui.output().copied_text = Self::csv(polytope);
+                }
+
+                ui.separator();
+
+                egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
+                    egui::Grid::new("vertex_table").striped(true).show(ui, |ui| {
+                        for (idx, v, norm) in polytope.vertex_table() {
+                            ui.label(idx.to_string());
+                            let coords: Vec<_> = v.iter().map(|x| format!("{:.6}", x)).collect();
+                            ui.label(format!("({})", coords.join(", ")));
+                            ui.label(format!("{:.6}", norm));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self_.close();
+        }
+    }
+}
+
+/// The number of elements on each page of the element inspector.
+const INSPECTOR_PAGE_SIZE: usize = 50;
+
+/// A window to browse the elements of the selected polytope, jumping between
+/// their subelements and superelements.
+#[derive(Default)]
+pub struct InspectorWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The rank whose elements are listed.
+    rank: usize,
+
+    /// The page of the list of elements that's shown.
+    page: usize,
+
+    /// The rank and index of the inspected element, if any.
+    selected: Option<(usize, usize)>,
+
+    /// The elements that were inspected before the current one, from oldest to
+    /// newest.
+    history: Vec<(usize, usize)>,
+
+    /// Whether the inspected element is highlighted in the viewport.
+    highlight: bool,
+}
+
+impl Window for InspectorWindow {
+    const NAME: &'static str = "title.element_inspector";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl InspectorWindow {
+    /// Shows the list containing a given element.
+    fn show_element(&mut self, (rank, idx): (usize, usize)) {
+        self.rank = rank;
+        self.page = idx / INSPECTOR_PAGE_SIZE;
+    }
+
+    /// Inspects an element, adding the current one to the history.
+    fn select(&mut self, element: (usize, usize)) {
+        if let Some(old) = self.selected.replace(element) {
+            if old != element {
+                self.history.push(old);
+            }
+        }
+
+        self.show_element(element);
+    }
+
+    /// Goes back to the element at a given position in the history, forgetting
+    /// every element after it.
+    fn go_back(&mut self, pos: usize) {
+        let element = self.history[pos];
+        self.history.truncate(pos);
+        self.selected = Some(element);
+        self.show_element(element);
+    }
+
+    /// Forgets the inspected element and the history.
+    fn reset(&mut self) {
+        self.page = 0;
+        self.selected = None;
+        self.history.clear();
+    }
+
+    /// Shows a list of buttons with the indices of some elements of a given
+    /// rank. Clicking on any of them stores the element in `clicked`.
+    fn index_buttons(
+        ui: &mut Ui,
+        rank: usize,
+        indices: &[usize],
+        clicked: &mut Option<(usize, usize)>,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            for &idx in indices {
+                if ui.small_button(idx.to_string()).clicked() {
+                    *clicked = Some((rank, idx));
+                }
+            }
+        });
+    }
+
+    /// Shows the contents of the window for a given polytope.
+    fn build(&mut self, ui: &mut Ui, polytope: &Concrete, lang: SelectedLanguage) {
+        let abs = &polytope.abs;
+        let rank = abs.rank();
+        let mut clicked = None;
+
+        // The breadcrumb trail.
+        ui.horizontal_wrapped(|ui| {
+            let mut back = None;
+            for (pos, &(r, idx)) in self.history.iter().enumerate() {
+                if ui
+                    .small_button(format!("{} #{}", element_name(r as isize - 1), idx))
+                    .clicked()
+                {
+                    back = Some(pos);
+                }
+                ui.label("›");
+            }
+
+            match self.selected {
+                Some((r, idx)) => ui.label(format!("{} #{}", element_name(r as isize - 1), idx)),
+                None => ui.label(t!(lang, "inspector.no_element")),
+            };
+
+            if let Some(pos) = back {
+                self.go_back(pos);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.highlight, t!(lang, "inspector.highlight"));
+
+            if ui.button(t!(lang, "window.clear")).clicked() {
+                self.reset();
+            }
+        });
+
+        ui.separator();
+
+        // The inspected element.
+        if let Some((r, idx)) = self.selected {
+            let element = &abs[(r, idx)];
+
+            if r != 0 {
+                ui.label(format!(
+                    "{} ({}):",
+                    t!(lang, "inspector.subelements"),
+                    element_name(r as isize - 2)
+                ));
+                Self::index_buttons(ui, r - 1, element.subs.as_slice(), &mut clicked);
+            }
+
+            if r != rank {
+                ui.label(format!(
+                    "{} ({}):",
+                    t!(lang, "inspector.superelements"),
+                    element_name(r as isize)
+                ));
+                Self::index_buttons(ui, r + 1, element.sups.as_slice(), &mut clicked);
+            }
+
+            ui.separator();
+        }
+
+        // The rank selector.
+        let old_rank = self.rank;
+        egui::ComboBox::from_label(t!(lang, "window.rank"))
+            .selected_text(element_name(self.rank as isize - 1))
+            .show_ui(ui, |ui| {
+                for r in 0..=rank {
+                    ui.selectable_value(&mut self.rank, r, element_name(r as isize - 1));
+                }
+            });
+        if self.rank != old_rank {
+            self.page = 0;
+        }
+
+        // The page selector.
+        let count = abs.el_count(self.rank);
+        let pages = (count + INSPECTOR_PAGE_SIZE - 1) / INSPECTOR_PAGE_SIZE;
+        ui.horizontal(|ui| {
+            if ui.button("<").clicked() && self.page != 0 {
+                self.page -= 1;
+            }
+
+            ui.label(format!("{} {} / {}", t!(lang, "inspector.page"), self.page + 1, pages.max(1)));
+
+            if ui.button(">").clicked() && self.page + 1 < pages {
+                self.page += 1;
+            }
+        });
+
+        // The elements on the page, with their subelements.
+        let start = self.page * INSPECTOR_PAGE_SIZE;
+        egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
+            egui::Grid::new("inspector_elements")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, element) in abs
+                        .elements_page(self.rank, start, INSPECTOR_PAGE_SIZE)
+                        .iter()
+                        .enumerate()
+                    {
+                        let idx = start + i;
+                        let is_selected = self.selected == Some((self.rank, idx));
+                        if ui.selectable_label(is_selected, idx.to_string()).clicked() {
+                            clicked = Some((self.rank, idx));
+                        }
+
+                        if self.rank == 0 {
+                            ui.label("");
+                        } else {
+                            Self::index_buttons(ui, self.rank - 1, element.subs.as_slice(), &mut clicked);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if let Some(element) = clicked {
+            self.select(element);
+        }
+    }
+
+    /// The system that shows the window and highlights the inspected element.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        mut element_highlight: ResMut<'_, ElementHighlight>,
+        query: Query<'_, '_, &Concrete, With<Selected>>,
+        changed: Query<'_, '_, (), (Or<(Changed<Concrete>, Added<Selected>)>, With<Selected>)>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        // The indices of the elements don't mean anything once the polytope
+        // changes.
+        if changed.iter().next().is_some() {
+            self_.reset();
+            self_.rank = self_.rank.min(query.iter().next().map_or(0, |p| p.rank()));
+        }
+
+        if self_.open {
+            if let Some(polytope) = query.iter().next() {
+                let mut open = true;
+                Self::egui_window(*lang)
+                    .open(&mut open)
+                    .show(egui_ctx.ctx(), |ui| self_.build(ui, polytope, *lang));
+
+                if !open {
+                    self_.close();
+                }
+            }
+        }
+
+        // We only mark the resource as changed when needed, as this redraws
+        // every polytope.
+        let element = if self_.open && self_.highlight {
+            self_.selected
+        } else {
+            None
+        };
+        if element_highlight.element != element {
+            element_highlight.element = element;
+        }
+    }
+}
+
+/// A window that builds a new polytope from a list of vertex coordinates and a
+/// list of facets, typed in as text.
+#[derive(Default)]
+pub struct TextWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The coordinates of the vertices, one vertex per line.
+    vertices: String,
+
+    /// The indices of the vertices of each facet, one facet per line.
+    facets: String,
+
+    /// The error from the last attempt to build the polytope, if any.
+    error: Option<ParseError>,
+}
+
+impl Window for TextWindow {
+    const NAME: &'static str = "title.new_from_text";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl TextWindow {
+    /// Returns the line of a text field where the last error was found,
+    /// together with its contents.
+    fn error_line(&self) -> Option<(usize, &str)> {
+        let (field, pos) = self.error?.position()?;
+        let text = match field {
+            TextField::Vertices => &self.vertices,
+            TextField::Facets => &self.facets,
+        };
+
+        Some((pos.row(), text.lines().nth(pos.row())?))
+    }
+
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+        mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let mut open = true;
+        let mut build = false;
+
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.label(t!(lang, "text.vertices"));
+                ui.add(egui::TextEdit::multiline(&mut self_.vertices).code_editor());
+
+                match infer_dim(&self_.vertices) {
+                    Some(dim) => ui.label(format!("{}: {}", t!(lang, "text.dimension"), dim)),
+                    None => ui.label(format!("{}: {}", t!(lang, "text.dimension"), t!(lang, "text.unknown"))),
+                };
+
+                ui.label(t!(lang, "text.facets"));
+                ui.add(egui::TextEdit::multiline(&mut self_.facets).code_editor());
+
+                if let Some(error) = self_.error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::RED, format!("{}: {}", t!(lang, "window.error"), error));
+
+                    if let Some((row, line)) = self_.error_line() {
+                        ui.colored_label(egui::Color32::RED, format!("{}: {}", row + 1, line));
+                    }
+                }
+
+                ui.separator();
+                build = ui.button(t!(lang, "window.build")).clicked();
+            });
+
+        if build {
+            match Concrete::from_vertex_facet_text(&self_.vertices, &self_.facets) {
+                Ok(poly) => {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        *p = poly;
+                        poly_name.0 = "Custom polytope".to_string();
+                    }
+
+                    self_.error = None;
+                }
+                Err(err) => self_.error = Some(err),
+            }
+        }
+
+        if !open {
+            self_.close();
+        }
+    }
+}
+
+/// A window that builds a tower by stacking scaled copies of the polytope at
+/// the given heights along a new axis.
+pub struct TowerWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The scale factor of each layer, separated by commas.
+    scales: String,
+
+    /// The height of each layer, separated by commas.
+    heights: String,
+
+    /// The error from the last attempt to build the tower, if any.
+    error: Option<String>,
+}
+
+impl Default for TowerWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            scales: "1, 1, 1".to_string(),
+            heights: "0, 1, 2".to_string(),
+            error: None,
+        }
+    }
+}
+
+impl Window for TowerWindow {
+    const NAME: &'static str = "title.tower";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl TowerWindow {
+    /// Parses a list of numbers separated by commas.
+    fn parse_list(text: &str) -> Result<Vec<Float>, String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(|x| x.parse().map_err(|_| format!("invalid number: {}", x)))
+            .collect()
+    }
+
+    /// Builds the tower of a polytope with the scales and heights in the
+    /// window.
+    fn tower(&self, polytope: &Concrete) -> Result<Concrete, String> {
+        let scales = Self::parse_list(&self.scales)?;
+        let heights = Self::parse_list(&self.heights)?;
+        if scales.len() != heights.len() {
+            return Err(format!(
+                "there are {} scales but {} heights",
+                scales.len(),
+                heights.len()
+            ));
+        }
+
+        let layers: Vec<_> = scales
+            .into_iter()
+            .zip(heights)
+            .map(|(scale, height)| (polytope.clone().scale(scale), height))
+            .collect();
+
+        Concrete::tower(&layers).map_err(|err| err.to_string())
+    }
+
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+        mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let mut open = true;
+        let mut build = false;
+
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!(lang, "tower.scales"));
+                    ui.text_edit_singleline(&mut self_.scales);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(t!(lang, "tower.heights"));
+                    ui.text_edit_singleline(&mut self_.heights);
+                });
+
+                if let Some(error) = &self_.error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::RED, format!("{}: {}", t!(lang, "window.error"), error));
+                }
+
+                ui.separator();
+                build = ui.button(t!(lang, "window.build")).clicked();
+            });
+
+        if build {
+            if let Some(mut p) = query.iter_mut().next() {
+                match self_.tower(&p) {
+                    Ok(tower) => {
+                        *p = tower;
+                        poly_name.0 = format!("Tower of {}", poly_name.0);
+                        self_.error = None;
+                    }
+                    Err(err) => self_.error = Some(err),
+                }
+            }
+        }
+
+        if !open {
+            self_.close();
+        }
+    }
+}
+
+/// The families of symmetry groups in the orbit window.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NamedGroup {
+    /// The A(n) groups, of the simplices.
+    Simplex,
+
+    /// The B(n) groups, of the hypercubes.
+    Hypercube,
+
+    /// The H(n) groups, of the pentagonal polytopes.
+    Pentagonal,
+}
+
+impl NamedGroup {
+    /// All of the families, in the order they're shown.
+    const ALL: [Self; 3] = [Self::Simplex, Self::Hypercube, Self::Pentagonal];
+
+    /// The label of the family on the dropdown.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Simplex => "A",
+            Self::Hypercube => "B",
+            Self::Pentagonal => "H",
+        }
+    }
+
+    /// The least rank of a group in the family. We only go up to rank 3, as
+    /// the convex hull of an orbit is found by brute force.
+    fn min_rank(self) -> usize {
+        match self {
+            Self::Simplex => 1,
+            Self::Hypercube | Self::Pentagonal => 2,
+        }
+    }
+
+    /// Builds the group of the family with a given rank.
+    fn group(self, rank: usize) -> Group<std::vec::IntoIter<Matrix<Float>>> {
+        match self {
+            Self::Simplex => Group::simplex(rank).cache(),
+            Self::Hypercube => Group::hypercube(rank).cache(),
+            Self::Pentagonal => Group::pentagonal(rank).cache(),
+        }
+    }
+}
+
+/// A window that builds the convex hull of the orbit of a seed point under a
+/// symmetry group. The polytope can be rebuilt as the seed is dragged around.
+pub struct OrbitWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The family of the group.
+    family: NamedGroup,
+
+    /// The rank of the group.
+    rank: usize,
+
+    /// The point whose orbit is taken.
+    seed: Point,
+
+    /// Whether to rebuild the polytope whenever the parameters change.
+    live: bool,
+
+    /// The last group that was built, together with its family and rank.
+    group: Option<(NamedGroup, usize, Group<std::vec::IntoIter<Matrix<Float>>>)>,
+
+    /// Whether the parameters changed since the polytope was last built.
+    changed: bool,
+
+    /// The time since the polytope was last built, in seconds.
+    since_build: f64,
+
+    /// The error from the last attempt to build the polytope, if any.
+    error: Option<OrbitError>,
+}
+
+impl Default for OrbitWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            family: NamedGroup::Hypercube,
+            rank: 3,
+            seed: Point::from_vec(vec![1.0, 2.0, 3.0]),
+            live: true,
+            group: None,
+            changed: false,
+            since_build: 0.0,
+            error: None,
+        }
+    }
+}
+
+impl Window for OrbitWindow {
+    const NAME: &'static str = "title.orbit_polytope";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl OrbitWindow {
+    /// The least time between two rebuilds of the live preview, in seconds.
+    const THROTTLE: f64 = 0.2;
+
+    /// Builds the orbit polytope, reusing the group from the last build if
+    /// possible.
+    fn orbit(&mut self) -> Result<Concrete, OrbitError> {
+        let (family, rank) = (self.family, self.rank);
+        if !matches!(self.group, Some((f, r, _)) if f == family && r == rank) {
+            self.group = Some((family, rank, family.group(rank)));
+        }
+
+        let (_, _, group) = self.group.as_ref().unwrap();
+        Concrete::orbit_polytope(self.seed.clone(), group)
+    }
+
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        time: Res<'_, Time>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+        mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let mut open = true;
+        let mut build = false;
+        self_.since_build += time.delta_seconds_f64();
+
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                let this = &mut *self_;
+                let old = (this.family, this.rank, this.seed.clone());
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source("orbit_group")
+                        .selected_text(this.family.label())
+                        .show_ui(ui, |ui| {
+                            for family in NamedGroup::ALL {
+                                ui.selectable_value(&mut this.family, family, family.label());
+                            }
+                        });
+
+                    ui.add(
+                        egui::DragValue::new(&mut this.rank)
+                            .speed(0.03)
+                            .clamp_range(this.family.min_rank()..=3),
+                    );
+                    ui.label(t!(lang, "window.group"));
+                });
+
+                // The rank might be out of range for a new family.
+                this.rank = this.rank.max(this.family.min_rank());
+                resize(&mut this.seed, this.rank);
+                ui.add(PointWidget::new(&mut this.seed, t!(lang, "orbit.seed")));
+                ui.checkbox(&mut this.live, t!(lang, "orbit.live"));
+
+                if old != (this.family, this.rank, this.seed.clone()) {
+                    this.changed = true;
+                }
+
+                if let Some(error) = this.error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::RED, format!("{}: {}", t!(lang, "window.error"), error));
+                }
+
+                ui.separator();
+                build = ui.button(t!(lang, "window.build")).clicked();
+            });
+
+        let live = self_.live && self_.changed && self_.since_build >= Self::THROTTLE;
+        if build || live {
+            self_.changed = false;
+            self_.since_build = 0.0;
+
+            match self_.orbit() {
+                Ok(poly) => {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        *p = poly;
+                        poly_name.0 = format!("{}{} orbit polytope", self_.family.label(), self_.rank);
+                    }
+
+                    self_.error = None;
+                }
+                Err(err) => self_.error = Some(err),
+            }
+        }
+
+        if !open {
+            self_.close();
+        }
+    }
+}
+
+/// A window that deletes a vertex or a facet of the polytope by its index.
+#[derive(Default)]
+pub struct EditWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// Whether to delete a facet rather than a vertex.
+    facet: bool,
+
+    /// The index of the element to delete.
+    idx: usize,
+
+    /// The error from the last attempt to delete an element, if any.
+    error: Option<EditError>,
+}
+
+impl Window for EditWindow {
+    const NAME: &'static str = "title.delete_element";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl EditWindow {
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        mut query: Query<'_, '_, &mut Concrete, With<Selected>>,
+        mut poly_name: ResMut<'_, PolyName>,
+        lang: Res<'_, SelectedLanguage>,
+    ) {
+        if !self_.open {
+            return;
+        }
+
+        let mut open = true;
+        let mut delete = false;
+
+        Self::egui_window(*lang)
+            .open(&mut open)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self_.facet, false, t!(lang, "edit.vertex"));
+                    ui.radio_value(&mut self_.facet, true, t!(lang, "edit.facet"));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", t!(lang, "window.index")));
+                    ui.add(egui::DragValue::new(&mut self_.idx).speed(0.05));
+                });
+
+                if let Some(error) = self_.error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::RED, format!("{}: {}", t!(lang, "window.error"), error));
+                }
+
+                ui.separator();
+                delete = ui.button(t!(lang, "edit.delete")).clicked();
+            });
+
+        if delete {
+            if let Some(mut p) = query.iter_mut().next() {
+                let result = if self_.facet {
+                    p.delete_facet(self_.idx)
+                } else {
+                    p.delete_vertex(self_.idx)
+                };
+
+                match result {
+                    Ok(()) => {
+                        poly_name.0 = format!("Edited {}", poly_name.0);
+                        self_.error = None;
+                    }
+                    Err(err) => self_.error = Some(err),
+                }
+            }
+        }
+
+        if !open {
+            self_.close();
+        }
+    }
+}
+
+/// A window to configure a truncation of the polytope.
+#[derive(Default)]
+pub struct TruncateWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The rank of the polytope.
+    rank: usize,
+
+    /// Which nodes are ringed.
+    truncate_type: Vec<bool>,
+
+    /// The weights applied to the coordinates. Intuitively, the truncation depths.
+    depth: Vec<f64>,
+}
+
+impl Window for TruncateWindow {
+    const NAME: &'static str = "title.truncate";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for TruncateWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let mut rings = Vec::new();
+        for (rank, ringed) in self.truncate_type.iter().enumerate() {
+            if *ringed {
+                rings.push(rank);
+            }
+        }
+        polytope.element_sort();
+        *polytope = polytope.truncate_with(rings, self.depth.clone());
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Truncated {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui, _: SelectedLanguage) {
+        for r in 0..self.rank {
+            ui.horizontal(|ui| {
+                ui.add(egui::Checkbox::new(&mut self.truncate_type[r], ""));
+                ui.add(egui::DragValue::new(&mut self.depth[r]).speed(0.01));
+            });
+        }
+    }
+
+    fn dim(&self) -> usize {
+        self.rank
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            rank: dim,
+            truncate_type: vec![false; dim],
+            depth: vec![1.0; dim],
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        self.rank = dim;
+        self.truncate_type = vec![false; dim];
+        self.depth = vec![1.0; dim];
     }
 
     fn update_system(
         mut self_: ResMut<'_, Self>,
-        query: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
+        query: Query<'_, '_, (&Concrete, &Handle<Mesh>, &Children), (Changed<Concrete>, With<Selected>)>,
     ) where
         Self: 'static,
     {
@@ -1501,7 +2958,7 @@ pub struct ScaleWindow {
 }
 
 impl Window for ScaleWindow {
-    const NAME: &'static str = "Scale";
+    const NAME: &'static str = "title.scale";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1514,12 +2971,12 @@ impl Window for ScaleWindow {
 
 impl PlainWindow for ScaleWindow {
     fn action(&self, polytope: &mut Concrete) {
-        polytope.scale(self.scale);
+        polytope.scale_mut(self.scale);
     }
 
     fn name_action(&self, _name: &mut String) {}
 
-    fn build(&mut self, ui: &mut Ui) {
+    fn build(&mut self, ui: &mut Ui, _: SelectedLanguage) {
         ui.horizontal(|ui| {
             ui.add(
                 egui::DragValue::new(&mut self.scale)
@@ -1529,6 +2986,314 @@ impl PlainWindow for ScaleWindow {
     }
 }
 
+/// A window that moves the vertices of a convex polyhedron into its canonical
+/// form.
+pub struct CanonicalizeWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The maximum number of iterations.
+    iterations: usize,
+}
+
+impl Default for CanonicalizeWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            iterations: 10_000,
+        }
+    }
+}
+
+impl Window for CanonicalizeWindow {
+    const NAME: &'static str = "title.canonicalize";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for CanonicalizeWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        match polytope.canonicalize_with_policy(
+            self.iterations,
+            EPS * 1e-3,
+            CompoundPolicy::CANONICALIZE,
+        ) {
+            Ok((true, components)) => {
+                print_components("Canonicalization", components);
+                println!("Canonicalization succeeded.");
+            }
+            Ok((false, _)) => println!(
+                "Canonicalization didn't converge after {} iterations.",
+                self.iterations
+            ),
+            Err(err) => println!("Canonicalization failed: {}.", err),
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Canonical {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.iterations)
+                    .speed(100.0)
+                    .clamp_range(1..=1_000_000),
+            );
+            ui.label(t!(lang, "canonicalize.iterations"));
+        });
+    }
+}
+
+/// A window that snaps the coordinates of the polytope to nearby exact values.
+pub struct SnapWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The options with which the coordinates are snapped.
+    options: SnapOptions,
+
+    /// The value whose multiples the coordinates are snapped to, if enabled.
+    quantum: f64,
+}
+
+impl Default for SnapWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            options: Default::default(),
+            quantum: 0.125,
+        }
+    }
+}
+
+impl Window for SnapWindow {
+    const NAME: &'static str = "title.snap_coordinates";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for SnapWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        match polytope.snap_coordinates(self.options) {
+            Ok(report) => println!("Snapping succeeded: {}.", report),
+            Err(err) => println!("Snapping failed: {}.", err),
+        }
+    }
+
+    fn name_action(&self, _name: &mut String) {}
+
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        let options = &mut self.options;
+        let quantum = &mut self.quantum;
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut options.eps)
+                    .speed(1e-7)
+                    .clamp_range(0.0..=0.1),
+            );
+            ui.label(t!(lang, "snap.tolerance"));
+        });
+
+        ui.checkbox(&mut options.simple, t!(lang, "snap.simple"));
+        ui.checkbox(&mut options.constants, t!(lang, "snap.constants"));
+
+        ui.horizontal(|ui| {
+            let mut enabled = options.quantum.is_some();
+            ui.checkbox(&mut enabled, t!(lang, "snap.quantum"));
+            ui.add(
+                egui::DragValue::new(quantum)
+                    .speed(0.001)
+                    .clamp_range(1e-6..=10.0),
+            );
+            options.quantum = enabled.then(|| *quantum);
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut options.max_shift)
+                    .speed(0.001)
+                    .clamp_range(0.0..=1.0),
+            );
+            ui.label(t!(lang, "snap.max_shift"));
+        });
+    }
+}
+
+/// A window that rescales a polytope into another unit, or to a physical size
+/// for printing.
+pub struct UnitsWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The index of the target unit in [`UNITS`].
+    unit: usize,
+
+    /// Whether the polytope is scaled to a given size, rather than converted
+    /// from the units it's stored in.
+    fit: bool,
+
+    /// The length the longest side of the bounding box is scaled to, in the
+    /// target unit.
+    size: f64,
+}
+
+impl Default for UnitsWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            unit: 0,
+            fit: true,
+            size: 50.0,
+        }
+    }
+}
+
+impl UnitsWindow {
+    /// Returns the unit the polytope is converted into.
+    fn target(&self) -> UnitInfo {
+        UnitInfo::named(UNITS[self.unit].0).unwrap()
+    }
+}
+
+impl Window for UnitsWindow {
+    const NAME: &'static str = "title.convert_units";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for UnitsWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let target = self.target();
+
+        if self.fit {
+            match polytope.fit_to_size(self.size, &target) {
+                Some(factor) => println!(
+                    "Scaled by {} so that the longest side of the bounding box is {} {}.",
+                    factor, self.size, target
+                ),
+                None => println!("Scaling failed: the bounding box has no size."),
+            }
+        } else {
+            let from = polytope.units.clone();
+            match (from, polytope.convert_units(&target)) {
+                (Some(from), Some(factor)) => {
+                    println!("Converted from {} to {}, scaled by {}.", from, target, factor)
+                }
+                _ => println!("Conversion failed: the units of the polytope aren't known."),
+            }
+        }
+    }
+
+    fn name_action(&self, _name: &mut String) {}
+
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        egui::ComboBox::from_label(t!(lang, "units.target"))
+            .selected_text(UNITS[self.unit].0)
+            .show_ui(ui, |ui| {
+                for (idx, (name, _)) in UNITS.iter().enumerate() {
+                    ui.selectable_value(&mut self.unit, idx, *name);
+                }
+            });
+
+        ui.radio_value(&mut self.fit, false, t!(lang, "units.convert"));
+        ui.radio_value(&mut self.fit, true, t!(lang, "units.fit"));
+
+        if self.fit {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.size)
+                        .speed(0.1)
+                        .clamp_range(1e-6..=1e6),
+                );
+                ui.label(UNITS[self.unit].0);
+            });
+        }
+    }
+}
+
+/// A window that unfolds a polyhedron into a net.
+#[derive(Default)]
+pub struct UnfoldWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The index of the face the net is laid out from.
+    root_face: usize,
+}
+
+impl Window for UnfoldWindow {
+    const NAME: &'static str = "title.unfold";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for UnfoldWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let net = match polytope.unfold(self.root_face) {
+            Ok(net) => net,
+            Err(err) => {
+                println!("Unfolding failed: {}.", err);
+                return;
+            }
+        };
+
+        // The net is loaded like any open surface from an OFF file.
+        match Concrete::from_off(&net.to_off()) {
+            Ok(poly) => {
+                *polytope = poly;
+
+                if net.overlaps.is_empty() {
+                    println!("Unfolding succeeded.");
+                } else {
+                    println!(
+                        "Unfolding succeeded, with {} pairs of overlapping faces.",
+                        net.overlaps.len()
+                    );
+                }
+            }
+            Err(err) => println!("Unfolding failed: {}.", err),
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Net of {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut self.root_face).speed(0.1));
+            ui.label(t!(lang, "unfold.root_face"));
+        });
+    }
+}
+
 /// Where to get the symmetry group for faceting
 #[derive(PartialEq)]
 pub enum GroupEnum2 {
@@ -1652,7 +3417,7 @@ impl Default for FacetingSettings {
 }
 
 impl Window for FacetingSettings {
-    const NAME: &'static str = "Faceting settings";
+    const NAME: &'static str = "title.faceting_settings";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1667,9 +3432,9 @@ impl MemoryWindow for FacetingSettings {
     fn action(&self, _polytope: &mut Concrete) {
     }
 
-    fn build(&mut self, ui: &mut Ui, memory: &Memory) {
+    fn build(&mut self, ui: &mut Ui, memory: &Memory, lang: SelectedLanguage) {
         ui.horizontal(|ui| {
-            ui.label("Max facet types");
+            ui.label(t!(lang, "faceting.max_facet_types"));
             ui.add(
                 egui::DragValue::new(&mut self.max_facet_types)
                     .speed(0.02)
@@ -1678,7 +3443,7 @@ impl MemoryWindow for FacetingSettings {
         });
         if self.show_advanced_settings {
             ui.horizontal(|ui| {
-                ui.label("Max facetings per hyperplane");
+                ui.label(t!(lang, "faceting.max_per_hyperplane"));
                 ui.add(
                     egui::DragValue::new(&mut self.max_per_hyperplane)
                         .speed(200)
@@ -1688,23 +3453,24 @@ impl MemoryWindow for FacetingSettings {
         }
         ui.separator();
 
-        ui.label("Group:");
+        ui.label(format!("{}:", t!(lang, "window.group")));
 
-        ui.radio_value(&mut self.group, GroupEnum2::Chiral(false), "Full group");
-        ui.radio_value(&mut self.group, GroupEnum2::Chiral(true), "Chiral subgroup");
+        ui.radio_value(&mut self.group, GroupEnum2::Chiral(false), t!(lang, "faceting.full_group"));
+        ui.radio_value(&mut self.group, GroupEnum2::Chiral(true), t!(lang, "faceting.chiral_group"));
 
         ui.horizontal(|ui| {
-            ui.radio_value(&mut self.group, GroupEnum2::FromSlot(self.slot), "From other polytope:");
+            ui.radio_value(&mut self.group, GroupEnum2::FromSlot(self.slot), t!(lang, "faceting.from_slot"));
                 
-            const SELECT: &str = "Select";
+            let select = t!(lang, "window.select");
+            let loaded_label = t!(lang, "window.loaded");
 
             // The text for the selected option.
             let selected_text = match self.slot {
                 // Nothing has been selected.
-                Slot::None => SELECT.to_string(),
+                Slot::None => select.to_string(),
 
                 // The loaded polytope is selected.
-                Slot::Loaded => LOADED_LABEL.to_string(),
+                Slot::Loaded => loaded_label.to_string(),
 
                 // Something is selected from the memory.
                 Slot::Memory(selected_idx) => if selected_idx < memory.len() {
@@ -1712,7 +3478,7 @@ impl MemoryWindow for FacetingSettings {
                         // Whatever was previously selected got deleted off the memory.
                         None => {
                             self.slot = Slot::None;
-                            SELECT.to_string()
+                            select.to_string()
                         }
 
                         // Shows the name of the selected polytope.
@@ -1728,7 +3494,7 @@ impl MemoryWindow for FacetingSettings {
                     }
                 } else {
                     self.slot = Slot::None;
-                    SELECT.to_string()
+                    select.to_string()
                 },
             };
 
@@ -1741,7 +3507,7 @@ impl MemoryWindow for FacetingSettings {
                     // The currently loaded polytope.
                     let mut loaded_selected = false;
 
-                    ui.selectable_value(&mut loaded_selected, true, LOADED_LABEL);
+                    ui.selectable_value(&mut loaded_selected, true, loaded_label);
 
                     // If the value was changed, update it.
                     if loaded_selected {
@@ -1779,8 +3545,8 @@ impl MemoryWindow for FacetingSettings {
 
         ui.separator();
 
-        ui.radio_value(&mut self.any_single_edge_length, true, "Any single edge length");
-        ui.radio_value(&mut self.any_single_edge_length, false, "Edge length range");
+        ui.radio_value(&mut self.any_single_edge_length, true, t!(lang, "faceting.any_edge_length"));
+        ui.radio_value(&mut self.any_single_edge_length, false, t!(lang, "faceting.edge_length_range"));
 
         ui.horizontal(|ui| {
             ui.add(
@@ -1789,7 +3555,7 @@ impl MemoryWindow for FacetingSettings {
             ui.add(
                 egui::DragValue::new(&mut self.min_edge_length).clamp_range(0.0..=Float::MAX).speed(0.01)
             );
-            ui.label("Min edge length");
+            ui.label(t!(lang, "faceting.min_edge_length"));
         });
 
         ui.horizontal(|ui| {
@@ -1799,7 +3565,7 @@ impl MemoryWindow for FacetingSettings {
             ui.add(
                 egui::DragValue::new(&mut self.max_edge_length).clamp_range(0.0..=Float::MAX).speed(0.01)
             );
-            ui.label("Max edge length");
+            ui.label(t!(lang, "faceting.max_edge_length"));
         });
 
         if self.show_advanced_settings {
@@ -1810,7 +3576,7 @@ impl MemoryWindow for FacetingSettings {
                 ui.add(
                     egui::DragValue::new(&mut self.min_inradius).clamp_range(0.0..=Float::MAX).speed(0.001)
                 );
-                ui.label("Min inradius");
+                ui.label(t!(lang, "faceting.min_inradius"));
             });
     
             ui.horizontal(|ui| {
@@ -1820,55 +3586,55 @@ impl MemoryWindow for FacetingSettings {
                 ui.add(
                     egui::DragValue::new(&mut self.max_inradius).clamp_range(0.0..=Float::MAX).speed(0.001)
                 );
-                ui.label("Max inradius");
+                ui.label(t!(lang, "faceting.max_inradius"));
             });
     
             ui.add(
-                egui::Checkbox::new(&mut self.exclude_hemis, "Exclude hemis")
+                egui::Checkbox::new(&mut self.exclude_hemis, t!(lang, "faceting.exclude_hemis"))
             );
     
             ui.add(
-                egui::Checkbox::new(&mut self.only_below_vertex, "Only hyperplanes perpendicular to a vertex")
+                egui::Checkbox::new(&mut self.only_below_vertex, t!(lang, "faceting.only_below_vertex"))
             );
         }
 
         ui.separator();
 
         ui.add(
-            egui::Checkbox::new(&mut self.uniform, "Only uniform/semiuniform facets")
+            egui::Checkbox::new(&mut self.uniform, t!(lang, "faceting.uniform"))
         );
 
         if self.show_advanced_settings {
             ui.separator();
         
             ui.add(
-                egui::Checkbox::new(&mut self.compounds, "Include trivial compounds")
+                egui::Checkbox::new(&mut self.compounds, t!(lang, "faceting.compounds"))
             );
     
             ui.add(
-                egui::Checkbox::new(&mut self.mark_fissary, "Mark compounds/fissaries")
+                egui::Checkbox::new(&mut self.mark_fissary, t!(lang, "faceting.mark_fissary"))
             );
     
             ui.add(
-                egui::Checkbox::new(&mut self.label_facets, "Label facets")
+                egui::Checkbox::new(&mut self.label_facets, t!(lang, "faceting.label_facets"))
             );
         }
 
         ui.separator();
 
         ui.add(
-            egui::Checkbox::new(&mut self.save, "Save facetings")
+            egui::Checkbox::new(&mut self.save, t!(lang, "faceting.save"))
         );
 
         ui.add(
-            egui::Checkbox::new(&mut self.save_facets, "Save facets")
+            egui::Checkbox::new(&mut self.save_facets, t!(lang, "faceting.save_facets"))
         );
 
-        ui.radio_value(&mut self.save_to_file, false, "Save to memory");
+        ui.radio_value(&mut self.save_to_file, false, t!(lang, "faceting.save_to_memory"));
 
         ui.horizontal(|ui| {
-            ui.radio_value(&mut self.save_to_file, true, "Save to file");
-            ui.label("Path:");
+            ui.radio_value(&mut self.save_to_file, true, t!(lang, "faceting.save_to_file"));
+            ui.label(format!("{}:", t!(lang, "faceting.path")));
             ui.add(
                 egui::TextEdit::singleline(&mut self.file_path).enabled(self.save_to_file)
             );
@@ -1876,7 +3642,7 @@ impl MemoryWindow for FacetingSettings {
 
         ui.separator();
 
-        if ui.button(if self.show_advanced_settings {"Hide advanced settings"} else {"Show advanced settings"}).clicked() {
+        if ui.button(if self.show_advanced_settings {t!(lang, "faceting.hide_advanced")} else {t!(lang, "faceting.show_advanced")}).clicked() {
             self.show_advanced_settings = !self.show_advanced_settings;
         }
     }
@@ -1900,7 +3666,7 @@ pub struct RotateWindow {
 }
 
 impl Window for RotateWindow {
-    const NAME: &'static str = "Rotate";
+    const NAME: &'static str = "title.rotate";
 
     fn is_open(&self) -> bool {
         self.open
@@ -1949,19 +3715,19 @@ impl UpdateWindow for RotateWindow {
         *name = format!("Rotated {}", name);
     }
     
-    fn build(&mut self, ui: &mut Ui) {
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
         let mut index = 0;
-        ui.add(egui::Checkbox::new(&mut self.degcheck, "Use degrees instead of radians"));
+        ui.add(egui::Checkbox::new(&mut self.degcheck, t!(lang, "rotate.degrees")));
         for r in 0..self.rank-1 {
             for s in (r+1)..=self.rank-1 {
                 ui.horizontal(|ui| {
                     if self.degcheck {
                         ui.add(egui::DragValue::new(&mut self.rots[ index ]).speed(1.0).clamp_range::<f64>(0.0..=360.0));
-                        ui.label("Axes ".to_owned()+&r.to_string()+" and "+&s.to_string());
+                        ui.label(format!("{} {}, {}", t!(lang, "rotate.axes"), r, s));
                     }
                     else{
                         ui.add(egui::DragValue::new(&mut self.rots[ index ]).speed(0.01).clamp_range::<f64>(0.0..=6.283185307179586));
-                        ui.label("Axes ".to_owned()+&r.to_string()+" and "+&s.to_string());
+                        ui.label(format!("{} {}, {}", t!(lang, "rotate.axes"), r, s));
                     }
                     index += 1; //setting index value
                 });
@@ -2010,6 +3776,9 @@ pub struct PlaneWindow {
     origincheck: bool,
     po: Point,
 
+    /// Whether to rotate about the axis through the first point instead, in
+    /// 3D.
+    axischeck: bool,
 }
 
 impl Default for PlaneWindow {
@@ -2027,12 +3796,14 @@ impl Default for PlaneWindow {
             
             origincheck: false,
             po: Point::zeros(0),
+
+            axischeck: false,
         }
     }
 }
 
 impl Window for PlaneWindow {
-    const NAME: &'static str = "Rotate with plane";
+    const NAME: &'static str = "title.rotate_with_plane";
 
     fn is_open(&self) -> bool {
         self.open
@@ -2043,110 +3814,61 @@ impl Window for PlaneWindow {
     }
 }
 
-fn dot(u: &Vec<f64>, v: &Vec<f64>) -> f64 {
-    let mut sum = 0.0;
-    for i in 0..u.len() {
-        sum += u[i]*v[i];
+impl PlaneWindow {
+    /// Whether the rotation is about an axis rather than in a plane.
+    fn about_axis(&self) -> bool {
+        self.axischeck && self.rank == 3
     }
-    return sum
 }
 
 impl UpdateWindow for PlaneWindow {
     fn action(&self, polytope: &mut Concrete) {
-        if self.p1 == Point::zeros(self.rank) || self.p2 == Point::zeros(self.rank) {
-            println!("Points within plane cannot be located at the origin.");
-        }
-        else if self.rot == 0.0 {
-            println!("Rotated, but the rotation amount was set to 0 so there was no change.");
-        }
-        else {			
-            //Step 0: Make plane of orthonormal basis based on input
-            //Subtract po from p1 and p2
-            let mut sub1: Vec<f64> = Vec::new();
-            let mut sub2: Vec<f64> = Vec::new();
-            
-            for i in 0..self.rank {
-                sub1.push( self.p1[i]-self.po[i] );
-                sub2.push( self.p2[i]-self.po[i] );
-            }
-            
-            //Make points sub1 and sub2 into unit Vec<f64> objects.
-            let ss1: f64 = sub1.iter().map(|&x| x*x).sum();
-            let ss2: f64 = sub2.iter().map(|&x| x*x).sum();
-            
-            let mut v1: Vec<f64> = Vec::new();
-            let mut v2: Vec<f64> = Vec::new();
-            
-            for i in 0..self.rank {
-                v1.push( (sub1[i])/ss1.sqrt() );
-                v2.push( (sub2[i]-self.po[i])/ss2.sqrt() );
-            }
-            
-            //Implement Gram-Schmidt process to make vectors orthonormal
-            let prod = dot(&v1,&v2);
-            
-            let mut u2: Vec<f64> = Vec::new();
-            for i in 0..self.rank {
-                u2.push(v2[i] - v1[i] * prod);
-            }
-            let ss3: f64 = u2.iter().map(|&x| x*x).sum();
-            
-            for i in 0..self.rank {
-                v2[i] = u2[i]/ss3.sqrt();
-            }
-            
-            let theta: f64;
-            if self.degcheck { //theta is the rotation amount in radians, which may or may not need conversion
-                theta = self.rot * 0.017453292519943295;
-            }
-            else {
-                theta = self.rot;
-            }
-            
-            for v in polytope.vertices_mut() {
-                
-                //Step 1: Find perpendicular intersection of point and plane, in orthonormal basis
-                //Equivalent to solving for the vector Q where (v-Q)·v1 = (v-Q)·v2 = 0, and Q is in the v1v2 plane.
-                //From this we find Q in the v1v2 basis. It turns out to equal [v·v1/v1·v1,v·v2/v2·v2].
-                //Because x·x = 1 for unit vectors x, we can simplify this to [v·v1,v·v2].
-                let mut vvec = Vec::new();
-                for i in 0..self.rank {
-                    vvec.push( v[i] );
-                }
-                let vf = vec![ dot(&vvec,&v1) , dot(&vvec,&v2) ];
-                
-                //Step 2: Rotate point around plane in basis
-                let mut vr = Point::zeros(2);
-                vr[0] = vf[0]*theta.cos() - vf[1]*theta.sin();
-                vr[1] = vf[0]*theta.sin() + vf[1]*theta.cos();
-                
-                //Step 3: Determine non-basis coordinates of rotated point and intersection point
-                let mut vc = Point::zeros(self.rank); //Intersection point
-                let mut vrc = Point::zeros(self.rank); //Rotated point
-                for i in 0..self.rank {
-                    vrc[i] = vr[0]*v1[i]+vr[1]*v2[i];
-                    vc[i] = vf[0]*v1[i]+vf[1]*v2[i];
-                }
-                
-                //Step 4: Reverse vector transformation between original point and intersection point onto rotated point. This is our new point.
-                //new v = vrc + v - vc
-                for i in 0..self.rank {
-                    v[i] = vrc[i] + v[i] - vc[i];
+        let theta = if self.degcheck {
+            self.rot.to_radians()
+        } else {
+            self.rot
+        };
+
+        // The points are taken relative to the origin point.
+        let origin = if self.origincheck {
+            self.po.clone()
+        } else {
+            Point::zeros(self.rank)
+        };
+        let e1 = &self.p1 - &origin;
+        let e2 = &self.p2 - &origin;
+
+        let rotation = if self.about_axis() {
+            Matrix::rotation_about_axis(&e1, theta)
+        } else {
+            Matrix::rotation_in_plane(&e1, &e2, theta)
+        };
+
+        match rotation {
+            Some(m) => {
+                polytope.shift_mut(&-&origin);
+                let result = polytope.apply_mut(&m);
+                polytope.shift_mut(&origin);
+
+                if let Err(err) = result {
+                    eprintln!("Rotation failed: {}", err);
+                    return;
                 }
+                println!("Rotated!");
             }
-            
-            println!("Rotated!");
-        
+            None if self.about_axis() => {
+                println!("The axis point cannot be located at the origin.")
+            }
+            None => println!("The points must span a plane through the origin."),
         }
-    
     }
 
     fn name_action(&self, name: &mut String) {
         *name = format!("Rotated {}", name);
     }
 
-    fn build(&mut self, ui: &mut Ui) {
-        ui.add(egui::Checkbox::new(&mut self.degcheck, "Use degrees instead of radians"));
+    fn build(&mut self, ui: &mut Ui, lang: SelectedLanguage) {
+        ui.add(egui::Checkbox::new(&mut self.degcheck, t!(lang, "rotate.degrees")));
         
         ui.horizontal(|ui| {
             
@@ -2157,18 +3879,29 @@ impl UpdateWindow for PlaneWindow {
                 ui.add(egui::DragValue::new(&mut self.rot).speed(0.01).clamp_range::<f64>(0.0..=6.283185307179586));
             }
             
-            ui.label("Rotation"); 
+            ui.label(t!(lang, "rotate.rotation")); 
         });
         
         
         ui.separator();
         
-        ui.add(egui::Checkbox::new(&mut self.origincheck, "Use a third origin point"));
-        
-        ui.add(PointWidget::new(&mut self.p1, "First point"));
-        ui.add(PointWidget::new(&mut self.p2, "Second point"));
+        ui.add(egui::Checkbox::new(&mut self.origincheck, t!(lang, "rotate.use_origin")));
+        if self.rank == 3 {
+            ui.add(egui::Checkbox::new(&mut self.axischeck, t!(lang, "rotate.about_axis")));
+        }
+
+        if self.about_axis() {
+            ui.add(PointWidget::new(&mut self.p1, t!(lang, "rotate.axis_point")));
+            if self.origincheck {
+                ui.add(PointWidget::new(&mut self.po, t!(lang, "rotate.origin_point")));
+            }
+            return;
+        }
+
+        ui.add(PointWidget::new(&mut self.p1, t!(lang, "rotate.first_point")));
+        ui.add(PointWidget::new(&mut self.p2, t!(lang, "rotate.second_point")));
         if self.origincheck {
-            ui.add(PointWidget::new(&mut self.po, "Origin point"));
+            ui.add(PointWidget::new(&mut self.po, t!(lang, "rotate.origin_point")));
         }
         
     }
@@ -2194,4 +3927,106 @@ impl UpdateWindow for PlaneWindow {
         self.p2 = Point::zeros(dim);
         self.po = Point::zeros(dim);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the preview of a window matches the element counts of the
+    /// products it builds.
+    fn check_counts<T: DuoWindow>(product: fn(&Concrete, &Concrete) -> Concrete) {
+        let factors = [
+            Concrete::point(),
+            Concrete::polygon(3),
+            Concrete::polygon(5),
+            Concrete::hypercube(4),
+        ];
+
+        for p in &factors {
+            for q in &factors {
+                let mut preview = ProductPreview::default();
+                preview.update([Some(p), Some(q)], T::PRODUCT);
+                assert_eq!(preview.counts, product(p, q).abs.el_counts().0, "{}", T::NAME);
+            }
+        }
+    }
+
+    /// The predicted element counts are those of the built products.
+    #[test]
+    fn preview_counts() {
+        check_counts::<DuopyramidWindow>(|p, q| p.duopyramid(q));
+        check_counts::<DuoprismWindow>(|p, q| p.duoprism(q));
+        check_counts::<DuotegumWindow>(|p, q| p.duotegum(q));
+        check_counts::<DuocombWindow>(|p, q| p.duocomb(q));
+
+        let mut preview = ProductPreview::default();
+        let (triangle, pentagon) = (Concrete::polygon(3), Concrete::polygon(5));
+        preview.update([Some(&triangle), Some(&pentagon)], DuoprismWindow::PRODUCT);
+        assert!(preview
+            .summary(SelectedLanguage::default())
+            .starts_with("Result: 15 vertices, 30 edges, 23 faces, 8 cells, est. "));
+
+        preview.update([Some(&triangle), None], DuoprismWindow::PRODUCT);
+        assert_eq!(preview, ProductPreview::default());
+    }
+
+    /// Large products are only built once confirmed, and the confirmation is
+    /// withdrawn when the factors change.
+    #[test]
+    fn large_product() {
+        let mut preview = ProductPreview::default();
+        let (small, large) = (Concrete::polygon(4), Concrete::polygon(100_000));
+
+        preview.update([Some(&small), Some(&large)], DuoprismWindow::PRODUCT);
+        assert!(!preview.is_large());
+        assert!(preview.allows_build());
+
+        preview.update([Some(&large), Some(&large)], DuoprismWindow::PRODUCT);
+        assert!(preview.is_large());
+        assert!(!preview.allows_build());
+        assert!(preview
+            .summary(SelectedLanguage::default())
+            .starts_with("Result: 10,000,000,000 vertices, "));
+
+        preview.confirmed = true;
+        assert!(preview.allows_build());
+        preview.update([Some(&large), Some(&large)], DuoprismWindow::PRODUCT);
+        assert!(preview.allows_build());
+
+        preview.update([Some(&large), Some(&Concrete::polygon(99_999))], DuoprismWindow::PRODUCT);
+        assert!(!preview.allows_build());
+    }
+
+    /// Opening a window through a command leaves every other window closed.
+    #[test]
+    fn open_command() {
+        let mut app = App::new();
+        app.add_event::<PolytopeCommand>()
+            .init_resource::<ScaleWindow>()
+            .init_resource::<RotateWindow>()
+            .add_system(open_window::<ScaleWindow>.system())
+            .add_system(open_window::<RotateWindow>.system());
+
+        app.world
+            .get_resource_mut::<Events<PolytopeCommand>>()
+            .unwrap()
+            .send(PolytopeCommand::open::<ScaleWindow>());
+        app.update();
+
+        assert!(app.world.get_resource::<ScaleWindow>().unwrap().is_open());
+        assert!(!app.world.get_resource::<RotateWindow>().unwrap().is_open());
+    }
+
+    /// Numbers of elements and bytes are written legibly.
+    #[test]
+    fn formatting() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(14_400), "14,400");
+        assert_eq!(thousands(1_234_567), "1,234,567");
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(6_400), "6.4 KB");
+        assert_eq!(format_bytes(3_200_000_000), "3.2 GB");
+    }
+}